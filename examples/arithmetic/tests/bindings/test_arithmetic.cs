@@ -0,0 +1,41 @@
+using System;
+using uniffi.arithmetic;
+
+static void Assert(bool condition, string message)
+{
+    if (!condition)
+    {
+        throw new Exception(message);
+    }
+}
+
+static void AssertThrows<T>(Action action, string message) where T : Exception
+{
+    try
+    {
+        action();
+    }
+    catch (T)
+    {
+        return;
+    }
+    throw new Exception(message);
+}
+
+AssertThrows<uniffi.arithmetic.ArithmeticException.IntegerOverflow>(
+    () => ArithmeticMethods.Add(18446744073709551615, 1), "Should have overflowed");
+Assert(ArithmeticMethods.Add(2, 4) == 6, "add(2, 4)");
+Assert(ArithmeticMethods.Add(4, 8) == 12, "add(4, 8)");
+
+AssertThrows<uniffi.arithmetic.ArithmeticException.IntegerOverflow>(
+    () => ArithmeticMethods.Sub(0, 1), "Should have overflowed");
+Assert(ArithmeticMethods.Sub(4, 2) == 2, "sub(4, 2)");
+Assert(ArithmeticMethods.Sub(8, 4) == 4, "sub(8, 4)");
+
+Assert(ArithmeticMethods.Div(8, 4) == 2, "div(8, 4)");
+AssertThrows<InternalException>(() => ArithmeticMethods.Div(8, 0), "Should have panicked");
+
+Assert(ArithmeticMethods.Equal(2, 2), "equal(2, 2)");
+Assert(ArithmeticMethods.Equal(4, 4), "equal(4, 4)");
+Assert(!ArithmeticMethods.Equal(2, 4), "equal(2, 4)");
+Assert(!ArithmeticMethods.Equal(4, 8), "equal(4, 8)");
@@ -0,0 +1,49 @@
+package main
+
+import (
+	"errors"
+
+	"uniffi_test/arithmetic"
+)
+
+func assert(condition bool, message string) {
+	if !condition {
+		panic(message)
+	}
+}
+
+func assertPanics(f func(), message string) {
+	defer func() {
+		if recover() == nil {
+			panic(message)
+		}
+	}()
+	f()
+}
+
+func main() {
+	_, err := arithmetic.Add(18446744073709551615, 1)
+	var overflow *arithmetic.ArithmeticErrorIntegerOverflow
+	assert(errors.As(err, &overflow), "Should have overflowed")
+
+	sum, err := arithmetic.Add(2, 4)
+	assert(err == nil && sum == 6, "add(2, 4)")
+	sum, err = arithmetic.Add(4, 8)
+	assert(err == nil && sum == 12, "add(4, 8)")
+
+	_, err = arithmetic.Sub(0, 1)
+	assert(errors.As(err, &overflow), "Should have overflowed")
+
+	difference, err := arithmetic.Sub(4, 2)
+	assert(err == nil && difference == 2, "sub(4, 2)")
+	difference, err = arithmetic.Sub(8, 4)
+	assert(err == nil && difference == 4, "sub(8, 4)")
+
+	assert(arithmetic.Div(8, 4) == 2, "div(8, 4)")
+	assertPanics(func() { arithmetic.Div(8, 0) }, "Should have panicked")
+
+	assert(arithmetic.Equal(2, 2), "equal(2, 2)")
+	assert(arithmetic.Equal(4, 4), "equal(4, 4)")
+	assert(!arithmetic.Equal(2, 4), "equal(2, 4)")
+	assert(!arithmetic.Equal(4, 8), "equal(4, 8)")
+}
@@ -0,0 +1,18 @@
+const assert = require("assert");
+const { ArithmeticError, UniffiInternalError, add, div, equal, sub } = require("./arithmetic");
+
+assert.throws(() => add(18446744073709551615n, 1n), ArithmeticError.IntegerOverflow);
+assert.strictEqual(add(2n, 4n), 6n);
+assert.strictEqual(add(4n, 8n), 12n);
+
+assert.throws(() => sub(0n, 1n), ArithmeticError.IntegerOverflow);
+assert.strictEqual(sub(4n, 2n), 2n);
+assert.strictEqual(sub(8n, 4n), 4n);
+
+assert.strictEqual(div(8n, 4n), 2n);
+assert.throws(() => div(8n, 0n), UniffiInternalError);
+
+assert.ok(equal(2n, 2n));
+assert.ok(equal(4n, 4n));
+assert.ok(!equal(2n, 4n));
+assert.ok(!equal(4n, 8n));
@@ -0,0 +1,16 @@
+using System;
+using System.Diagnostics;
+using uniffi.uniffi_example_futures;
+
+var stopwatch = Stopwatch.StartNew();
+var result = await UniffiExampleFuturesMethods.SayAfter(20, "Alice");
+stopwatch.Stop();
+
+if (result != "Hello, Alice!")
+{
+    throw new Exception($"Unexpected result: {result}");
+}
+if (stopwatch.ElapsedMilliseconds < 20)
+{
+    throw new Exception($"Returned too early, after {stopwatch.ElapsedMilliseconds}ms");
+}
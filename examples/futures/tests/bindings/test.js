@@ -0,0 +1,18 @@
+const assert = require("assert");
+const { sayAfter } = require("./uniffi_example_futures");
+
+(async () => {
+  const start = Date.now();
+  const result = await sayAfter(20n, "Alice");
+  const elapsed = Date.now() - start;
+
+  assert.strictEqual(result, "Hello, Alice!");
+  assert.ok(elapsed >= 20, `Returned too early, after ${elapsed}ms`);
+
+  // Several futures can be awaited at once
+  const results = await Promise.all([sayAfter(30n, "Bob"), sayAfter(10n, "Carol")]);
+  assert.deepStrictEqual(results, ["Hello, Bob!", "Hello, Carol!"]);
+})().catch((e) => {
+  console.error(e);
+  process.exit(1);
+});
@@ -15,6 +15,7 @@ fn main() {
 
     let options = RunScriptOptions {
         show_compiler_messages: args.compiler_messages,
+        ..RunScriptOptions::default()
     };
 
     if args.should_run_python() {
@@ -39,6 +40,24 @@ fn main() {
         .unwrap()
     }
 
+    if args.should_run_kotlin_jni() {
+        kotlin::run_script(
+            std::env!("CARGO_TARGET_TMPDIR"),
+            "uniffi-fixture-benchmarks",
+            "benches/bindings/run_benchmarks.kts",
+            script_args
+                .iter()
+                .cloned()
+                .chain(std::iter::once(String::from("--jni-run")))
+                .collect(),
+            &RunScriptOptions {
+                config_file: Some("uniffi-jni.toml".into()),
+                ..options.clone()
+            },
+        )
+        .unwrap()
+    }
+
     if args.should_run_swift() {
         swift::run_script(
             std::env!("CARGO_TARGET_TMPDIR"),
@@ -17,6 +17,9 @@ pub struct Args {
     /// Run Swift tests
     #[clap(short, long, display_order = 0)]
     pub swift: bool,
+    /// Run Kotlin tests with `ffi_backend = "jni"`
+    #[clap(short = 'j', long = "kt-jni", display_order = 0)]
+    pub kotlin_jni: bool,
 
     /// Dump compiler output to the console.  Good for debugging new benchmarks.
     #[clap(long, display_order = 1)]
@@ -30,6 +33,11 @@ pub struct Args {
     // It would be great to also support the baseline arguments, but there doesn't seem to be any
     // way to manually set those.
 
+    // Set by `benchmarks.rs` when running the Kotlin tests with `ffi_backend = "jni"`, to name the
+    // benchmarks differently
+    #[clap(long, hide = true)]
+    pub jni_run: bool,
+
     // Ignore the `--bench` arg, which Cargo passes to us
     #[clap(long, hide = true)]
     bench: bool,
@@ -46,13 +54,18 @@ impl Args {
         self.kotlin || self.no_languages_selected()
     }
 
+    /// Should we run the Kotlin tests with `ffi_backend = "jni"`?
+    pub fn should_run_kotlin_jni(&self) -> bool {
+        self.kotlin_jni || self.no_languages_selected()
+    }
+
     /// Should we run the Swift tests?
     pub fn should_run_swift(&self) -> bool {
         self.swift || self.no_languages_selected()
     }
 
     pub fn no_languages_selected(&self) -> bool {
-        !(self.python || self.kotlin || self.swift)
+        !(self.python || self.kotlin || self.swift || self.kotlin_jni)
     }
 
     /// Parse arguments for run_benchmarks()
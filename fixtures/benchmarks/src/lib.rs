@@ -35,6 +35,11 @@ pub fn test_no_args_void_return() {}
 pub fn run_benchmarks(language: String, cb: Box<dyn TestCallbackInterface>) {
     let args = Args::parse_for_run_benchmarks();
     let mut c = args.build_criterion();
+    let language = if args.jni_run {
+        format!("{language}-jni")
+    } else {
+        language
+    };
 
     c.benchmark_group("calls")
         // FFI Function call benchmarks
@@ -0,0 +1,170 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! A reference `uniffi-bindgen` plugin, which "generates bindings" as a Markdown summary of the
+//! component.
+//!
+//! Run it with `uniffi-bindgen generate --language plugin:path/to/uniffi-bindgen-markdown`.  It's
+//! configured with the `[bindings.markdown]` table of `uniffi.toml`:
+//!
+//! ```toml
+//! [bindings.markdown]
+//! # The title of the page, which defaults to the namespace
+//! title = "Arithmetic"
+//! # The name of the generated file, which defaults to `<namespace>.md`
+//! file_name = "README.md"
+//! ```
+//!
+//! This deliberately only depends on `serde_json`, to show that plugins don't need to link
+//! against `uniffi_bindgen`.
+
+use std::fmt::Write as _;
+use std::io::{self, Read, Write};
+
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+
+// The version of the plugin protocol that this plugin understands.
+const PROTOCOL_VERSION: u64 = 1;
+
+fn main() -> Result<()> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    let request: Value = serde_json::from_str(&input).context("Invalid request")?;
+    if request["protocol_version"] != PROTOCOL_VERSION {
+        bail!(
+            "Unsupported protocol version: {}",
+            request["protocol_version"]
+        );
+    }
+
+    let component = &request["component"];
+    let config = &request["config"]["markdown"];
+    let namespace = str_field(component, "namespace")?;
+    let title = config["title"].as_str().unwrap_or(namespace);
+    let file_name = match config["file_name"].as_str() {
+        Some(file_name) => file_name.to_string(),
+        None => format!("{namespace}.md"),
+    };
+
+    let response = json!({
+        "files": [
+            {
+                "path": file_name,
+                "contents": render(title, &request)?,
+            }
+        ]
+    });
+    serde_json::to_writer(io::stdout().lock(), &response)?;
+    io::stdout().flush()?;
+    Ok(())
+}
+
+fn render(title: &str, request: &Value) -> Result<String> {
+    let component = &request["component"];
+    let mut out = String::new();
+    writeln!(out, "# {title}")?;
+    writeln!(out)?;
+    if let Some(docstring) = component["docstring"].as_str() {
+        writeln!(out, "{docstring}")?;
+        writeln!(out)?;
+    }
+    writeln!(out, "Crate: `{}`", str_field(component, "crate_name")?)?;
+    if let Some(cdylib_name) = request["cdylib_name"].as_str() {
+        writeln!(out, "Library: `{cdylib_name}`")?;
+    }
+
+    let functions = array_field(component, "functions")?;
+    if !functions.is_empty() {
+        writeln!(out)?;
+        writeln!(out, "## Functions")?;
+        writeln!(out)?;
+        for func in functions {
+            writeln!(out, "- `{}`", signature(func)?)?;
+        }
+    }
+
+    let records = array_field(component, "records")?;
+    if !records.is_empty() {
+        writeln!(out)?;
+        writeln!(out, "## Records")?;
+        for record in records {
+            writeln!(out)?;
+            writeln!(out, "### {}", str_field(record, "name")?)?;
+            writeln!(out)?;
+            for field in array_field(record, "fields")? {
+                writeln!(
+                    out,
+                    "- `{}: {}`",
+                    str_field(field, "name")?,
+                    type_name(&field["type_"])
+                )?;
+            }
+        }
+    }
+
+    let objects = array_field(component, "objects")?;
+    if !objects.is_empty() {
+        writeln!(out)?;
+        writeln!(out, "## Objects")?;
+        for object in objects {
+            writeln!(out)?;
+            writeln!(out, "### {}", str_field(object, "name")?)?;
+            writeln!(out)?;
+            for method in array_field(object, "methods")? {
+                writeln!(out, "- `{}`", signature(method)?)?;
+            }
+        }
+    }
+    Ok(out)
+}
+
+fn signature(func: &Value) -> Result<String> {
+    let arguments = array_field(func, "arguments")?
+        .iter()
+        .map(|arg| {
+            Ok(format!(
+                "{}: {}",
+                str_field(arg, "name")?,
+                type_name(&arg["type_"])
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let mut signature = format!("{}({})", str_field(func, "name")?, arguments.join(", "));
+    if !func["return_type"].is_null() {
+        write!(signature, " -> {}", type_name(&func["return_type"]))?;
+    }
+    if !func["throws"].is_null() {
+        write!(signature, " throws {}", type_name(&func["throws"]))?;
+    }
+    Ok(signature)
+}
+
+// Types are serialized as `"UInt32"` for primitive types, or `{ "Record": { "name": ... } }`
+// for types with fields.
+fn type_name(type_: &Value) -> String {
+    match type_ {
+        Value::String(name) => name.clone(),
+        Value::Object(map) => match map.iter().next() {
+            Some((kind, fields)) => match fields["name"].as_str() {
+                Some(name) => name.to_string(),
+                None => kind.clone(),
+            },
+            None => "?".to_string(),
+        },
+        _ => "?".to_string(),
+    }
+}
+
+fn str_field<'a>(value: &'a Value, name: &str) -> Result<&'a str> {
+    value[name]
+        .as_str()
+        .with_context(|| format!("Missing string field `{name}`"))
+}
+
+fn array_field<'a>(value: &'a Value, name: &str) -> Result<&'a Vec<Value>> {
+    value[name]
+        .as_array()
+        .with_context(|| format!("Missing array field `{name}`"))
+}
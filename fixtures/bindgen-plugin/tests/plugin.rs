@@ -0,0 +1,51 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use camino::Utf8PathBuf;
+use uniffi_bindgen::plugin::PluginBindingGenerator;
+
+fn generate(out_dir: &str) -> String {
+    let crate_root = Utf8PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let out_dir = Utf8PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(out_dir);
+    uniffi_bindgen::generate_external_bindings(
+        PluginBindingGenerator::new(env!("CARGO_BIN_EXE_uniffi-bindgen-markdown")),
+        crate_root.join("src/plugin.udl"),
+        None::<&str>,
+        Some(&out_dir),
+        None::<&str>,
+        None,
+    )
+    .unwrap();
+    std::fs::read_to_string(out_dir.join("plugin_fixture.md")).unwrap()
+}
+
+#[test]
+fn test_plugin_writes_files() {
+    let markdown = generate("plugin-writes-files");
+    assert!(markdown.starts_with("# Plugin fixture\n"));
+    assert!(markdown.contains("An interface that the test runs the plugin on."));
+    assert!(markdown.contains("Crate: `uniffi_fixture_bindgen_plugin`"));
+    assert!(markdown.contains("- `add(left: UInt32, right: UInt32) -> UInt32`"));
+    assert!(
+        markdown.contains("- `sub(left: UInt32, right: UInt32) -> UInt32 throws ArithmeticError`")
+    );
+    assert!(markdown.contains("- `origin() -> Point`"));
+    assert!(markdown.contains("### Point\n\n- `x: Float64`\n- `y: Float64`"));
+    assert!(markdown.contains("### Counter\n\n- `get() -> UInt64`\n- `increment()`"));
+}
+
+#[test]
+fn test_plugin_failure() {
+    let crate_root = Utf8PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let err = uniffi_bindgen::generate_external_bindings(
+        PluginBindingGenerator::new(crate_root.join("does-not-exist")),
+        crate_root.join("src/plugin.udl"),
+        None::<&str>,
+        Some(Utf8PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("plugin-failure")),
+        None::<&str>,
+        None,
+    )
+    .unwrap_err();
+    assert!(err.to_string().starts_with("Failed to run plugin"));
+}
@@ -0,0 +1,78 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::{Arc, Mutex};
+
+/// Add two numbers
+#[uniffi::export]
+pub fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[uniffi::export]
+pub fn greet(name: String) -> String {
+    format!("Hello, {name}!")
+}
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum ArithmeticError {
+    #[error("Division by zero")]
+    DivisionByZero,
+}
+
+#[uniffi::export]
+pub fn divide(a: u32, b: u32) -> Result<u32, ArithmeticError> {
+    a.checked_div(b).ok_or(ArithmeticError::DivisionByZero)
+}
+
+#[uniffi::export]
+pub async fn add_async(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+/// A counter that can be shared between threads
+#[derive(uniffi::Object)]
+pub struct Counter {
+    value: Mutex<u64>,
+}
+
+#[uniffi::export]
+impl Counter {
+    #[uniffi::constructor]
+    pub fn new(initial: u64) -> Self {
+        Self {
+            value: Mutex::new(initial),
+        }
+    }
+
+    /// Increment the counter, returning the new value
+    pub fn increment(&self) -> u64 {
+        let mut value = self.value.lock().unwrap();
+        *value += 1;
+        *value
+    }
+}
+
+/// Implemented by the foreign side
+#[uniffi::export(callback_interface)]
+pub trait Logger: Send + Sync {
+    fn log(&self, message: String);
+}
+
+#[uniffi::export]
+pub fn log_with(logger: Box<dyn Logger>, message: String) {
+    logger.log(message)
+}
+
+#[uniffi::export(with_foreign)]
+pub trait Shape: Send + Sync {
+    fn area(&self) -> f64;
+}
+
+#[uniffi::export]
+pub fn total_area(shapes: Vec<Arc<dyn Shape>>) -> f64 {
+    shapes.iter().map(|s| s.area()).sum()
+}
+
+uniffi::setup_scaffolding!();
@@ -0,0 +1,138 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+#include <assert.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "uniffi_c_header.h"
+
+static RustCallStatus new_call_status(void) {
+    RustCallStatus status;
+    memset(&status, 0, sizeof(status));
+    return status;
+}
+
+// Strings are passed as a `RustBuffer` containing the UTF-8 bytes
+static RustBuffer lower_string(const char *value) {
+    ForeignBytes bytes = { (int32_t)strlen(value), (const uint8_t *)value };
+    RustCallStatus status = new_call_status();
+    RustBuffer buf = ffi_uniffi_c_header_rustbuffer_from_bytes(bytes, &status);
+    assert(status.code == UNIFFI_CALL_SUCCESS);
+    return buf;
+}
+
+static int string_equals(RustBuffer buf, const char *expected) {
+    return buf.len == strlen(expected) && memcmp(buf.data, expected, buf.len) == 0;
+}
+
+static void free_buffer(RustBuffer buf) {
+    RustCallStatus status = new_call_status();
+    ffi_uniffi_c_header_rustbuffer_free(buf, &status);
+    assert(status.code == UNIFFI_CALL_SUCCESS);
+}
+
+static void test_functions(void) {
+    RustCallStatus status = new_call_status();
+    assert(uniffi_uniffi_c_header_fn_func_add(2, 3, &status) == 5);
+    assert(status.code == UNIFFI_CALL_SUCCESS);
+
+    RustBuffer greeting = uniffi_uniffi_c_header_fn_func_greet(lower_string("C"), &status);
+    assert(status.code == UNIFFI_CALL_SUCCESS);
+    assert(string_equals(greeting, "Hello, C!"));
+    free_buffer(greeting);
+}
+
+static void test_errors(void) {
+    RustCallStatus status = new_call_status();
+    assert(uniffi_uniffi_c_header_fn_func_divide(6, 3, &status) == 2);
+    assert(status.code == UNIFFI_CALL_SUCCESS);
+
+    uniffi_uniffi_c_header_fn_func_divide(1, 0, &status);
+    assert(status.code == UNIFFI_CALL_ERROR);
+    // Enums are serialized as a big-endian, 1-based, variant index.
+    const uint8_t division_by_zero[] = { 0, 0, 0, 1 };
+    assert(status.errorBuf.len == sizeof(division_by_zero));
+    assert(memcmp(status.errorBuf.data, division_by_zero, sizeof(division_by_zero)) == 0);
+    free_buffer(status.errorBuf);
+}
+
+static void test_objects(void) {
+    RustCallStatus status = new_call_status();
+    void *counter = uniffi_uniffi_c_header_fn_constructor_counter_new(10, &status);
+    assert(status.code == UNIFFI_CALL_SUCCESS);
+    // Methods consume a reference to the object, so clone it for each call.
+    void *clone = uniffi_uniffi_c_header_fn_clone_counter(counter, &status);
+    assert(uniffi_uniffi_c_header_fn_method_counter_increment(clone, &status) == 11);
+    clone = uniffi_uniffi_c_header_fn_clone_counter(counter, &status);
+    assert(uniffi_uniffi_c_header_fn_method_counter_increment(clone, &status) == 12);
+    assert(status.code == UNIFFI_CALL_SUCCESS);
+    uniffi_uniffi_c_header_fn_free_counter(counter, &status);
+    assert(status.code == UNIFFI_CALL_SUCCESS);
+}
+
+static int8_t last_poll_result = -1;
+
+static void continuation_callback(uint64_t data, int8_t poll_result) {
+    (void)data;
+    last_poll_result = poll_result;
+}
+
+static void test_async(void) {
+    uint64_t future = uniffi_uniffi_c_header_fn_func_add_async(4, 5);
+    do {
+        last_poll_result = -1;
+        ffi_uniffi_c_header_rust_future_poll_u32(future, continuation_callback, 0);
+        // The future has no runtime to wait on, so it always calls back before poll returns.
+        assert(last_poll_result != -1);
+    } while (last_poll_result != UNIFFI_RUST_FUTURE_POLL_READY);
+
+    RustCallStatus status = new_call_status();
+    assert(ffi_uniffi_c_header_rust_future_complete_u32(future, &status) == 9);
+    assert(status.code == UNIFFI_CALL_SUCCESS);
+    ffi_uniffi_c_header_rust_future_free_u32(future);
+}
+
+static RustBuffer last_message;
+static uint64_t freed_handle;
+
+static void logger_log(uint64_t handle, RustBuffer message, void *out_return, RustCallStatus *status) {
+    (void)out_return;
+    (void)status;
+    assert(handle == 42);
+    last_message = message;
+}
+
+static void logger_free(uint64_t handle) {
+    freed_handle = handle;
+}
+
+static UniffiVTableCallbackInterfaceLogger logger_vtable = { logger_log, logger_free };
+
+static void test_callback_interfaces(void) {
+    uniffi_uniffi_c_header_fn_init_callback_vtable_logger(&logger_vtable);
+
+    RustCallStatus status = new_call_status();
+    uniffi_uniffi_c_header_fn_func_log_with(42, lower_string("from Rust"), &status);
+    assert(status.code == UNIFFI_CALL_SUCCESS);
+    assert(string_equals(last_message, "from Rust"));
+    free_buffer(last_message);
+    assert(freed_handle == 42);
+}
+
+static void test_contract_version(void) {
+    assert(ffi_uniffi_c_header_uniffi_contract_version() > 0);
+}
+
+int main(void) {
+    test_functions();
+    test_errors();
+    test_objects();
+    test_async();
+    test_callback_interfaces();
+    test_contract_version();
+    printf("All C header tests passed\n");
+    return EXIT_SUCCESS;
+}
@@ -0,0 +1,155 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+	"reflect"
+	"strings"
+
+	"uniffi_test/fixture_callbacks"
+)
+
+func assert(condition bool, message string) {
+	if !condition {
+		panic(message)
+	}
+}
+
+// 1. Pass in the callback as arguments.
+// Make the callback methods use multiple arguments, with a variety of types, and
+// with a variety of return types.
+type GoGetters struct{}
+
+func (GoGetters) GetBool(v bool, argumentTwo bool) (bool, error) {
+	return v != argumentTwo, nil
+}
+
+func (GoGetters) GetString(v string, arg2 bool) (string, error) {
+	switch v {
+	case "bad-argument":
+		return "", &fixture_callbacks.SimpleErrorBadArgument{}
+	case "unexpected-error":
+		return "", errors.New("unexpected value")
+	}
+	if arg2 {
+		return "1234567890123", nil
+	}
+	return v, nil
+}
+
+func (GoGetters) GetOption(v *string, arg2 bool) (*string, error) {
+	if v != nil {
+		switch *v {
+		case "bad-argument":
+			return nil, &fixture_callbacks.ComplexErrorReallyBadArgument{Code: 20}
+		case "unexpected-error":
+			return nil, errors.New("unexpected value")
+		}
+	}
+	if arg2 && v != nil {
+		upper := strings.ToUpper(*v)
+		return &upper, nil
+	}
+	return v, nil
+}
+
+func (GoGetters) GetList(v []int32, arg2 bool) ([]int32, error) {
+	if arg2 {
+		return v, nil
+	}
+	return []int32{}, nil
+}
+
+func (GoGetters) GetNothing(v string) error {
+	switch v {
+	case "bad-argument":
+		return &fixture_callbacks.SimpleErrorBadArgument{}
+	case "unexpected-error":
+		return errors.New("unexpected value")
+	}
+	return nil
+}
+
+// 2. Pass the callback in as a constructor argument, to be stored on the Object struct.
+// This is crucial if we want to configure a system at startup,
+// then use it without passing callbacks all the time.
+type StoredGoStringifier struct{}
+
+func (StoredGoStringifier) FromSimpleType(value int32) string {
+	return fmt.Sprintf("go: %d", value)
+}
+
+func (StoredGoStringifier) FromComplexType(values *[]*float64) string {
+	return fmt.Sprintf("go: %v", values)
+}
+
+func ptr[T any](v T) *T {
+	return &v
+}
+
+func main() {
+	rustGetters := fixture_callbacks.NewRustGetters()
+	defer rustGetters.Destroy()
+	callback := GoGetters{}
+
+	for _, v := range []bool{true, false} {
+		expected, _ := callback.GetBool(v, true)
+		observed, err := rustGetters.GetBool(callback, v, true)
+		assert(err == nil && expected == observed, fmt.Sprintf("roundtripping through callback: %v != %v", expected, observed))
+	}
+
+	for _, v := range [][]int32{{1, 2}, {0, 1}} {
+		expected, _ := callback.GetList(v, true)
+		observed, err := rustGetters.GetList(callback, v, true)
+		assert(err == nil && reflect.DeepEqual(expected, observed), fmt.Sprintf("roundtripping through callback: %v != %v", expected, observed))
+	}
+
+	for _, v := range []string{"Hello", "world"} {
+		expected, _ := callback.GetString(v, true)
+		observed, err := rustGetters.GetString(callback, v, true)
+		assert(err == nil && expected == observed, fmt.Sprintf("roundtripping through callback: %v != %v", expected, observed))
+	}
+
+	for _, v := range []*string{ptr("Some"), nil} {
+		expected, _ := callback.GetOption(v, false)
+		observed, err := rustGetters.GetOption(callback, v, false)
+		assert(err == nil && reflect.DeepEqual(expected, observed), fmt.Sprintf("roundtripping through callback: %v != %v", expected, observed))
+	}
+
+	result, err := rustGetters.GetStringOptionalCallback(callback, "TestString", false)
+	assert(err == nil && result != nil && *result == "TestString", "get_string_optional_callback with a callback")
+	result, err = rustGetters.GetStringOptionalCallback(nil, "TestString", false)
+	assert(err == nil && result == nil, "get_string_optional_callback without a callback")
+
+	assert(rustGetters.GetNothing(callback, "TestString") == nil, "get_nothing")
+
+	goStringifier := StoredGoStringifier{}
+	rustStringifier := fixture_callbacks.NewRustStringifier(goStringifier)
+	defer rustStringifier.Destroy()
+	for _, v := range []int32{1, 2} {
+		expected := goStringifier.FromSimpleType(v)
+		observed := rustStringifier.FromSimpleType(v)
+		assert(expected == observed, fmt.Sprintf("callback is sent on construction: %v != %v", expected, observed))
+	}
+
+	// Errors returned by the callbacks
+	var badArgument *fixture_callbacks.SimpleErrorBadArgument
+	var unexpectedError *fixture_callbacks.SimpleErrorUnexpectedError
+	_, err = rustGetters.GetString(callback, "bad-argument", true)
+	assert(errors.As(err, &badArgument), "get_string should fail with BadArgument")
+	_, err = rustGetters.GetString(callback, "unexpected-error", true)
+	assert(errors.As(err, &unexpectedError), "get_string should fail with UnexpectedError")
+
+	err = rustGetters.GetNothing(callback, "bad-argument")
+	assert(errors.As(err, &badArgument), "get_nothing should fail with BadArgument")
+	err = rustGetters.GetNothing(callback, "unexpected-error")
+	assert(errors.As(err, &unexpectedError), "get_nothing should fail with UnexpectedError")
+
+	var reallyBadArgument *fixture_callbacks.ComplexErrorReallyBadArgument
+	var unexpectedErrorWithReason *fixture_callbacks.ComplexErrorUnexpectedErrorWithReason
+	_, err = rustGetters.GetOption(callback, ptr("bad-argument"), true)
+	assert(errors.As(err, &reallyBadArgument) && reallyBadArgument.Code == 20, "get_option should fail with ReallyBadArgument(20)")
+	_, err = rustGetters.GetOption(callback, ptr("unexpected-error"), true)
+	assert(errors.As(err, &unexpectedErrorWithReason), "get_option should fail with UnexpectedErrorWithReason")
+	assert(unexpectedErrorWithReason.Reason == "unexpected value", fmt.Sprintf("unexpected reason: %v", unexpectedErrorWithReason.Reason))
+}
@@ -0,0 +1,104 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+const assert = require("assert");
+const { ComplexError, RustGetters, RustStringifier, SimpleError } = require("./fixture_callbacks");
+
+// 1. Pass in the callback as arguments.
+// Make the callback methods use multiple arguments, with a variety of types, and
+// with a variety of return types.
+const rustGetters = new RustGetters();
+
+class JsGetters {
+  getBool(v, argumentTwo) {
+    return v !== argumentTwo;
+  }
+
+  getString(v, arg2) {
+    if (v === "bad-argument") {
+      throw new SimpleError.BadArgument("bad argument");
+    }
+    if (v === "unexpected-error") {
+      throw new Error("unexpected value");
+    }
+    return arg2 ? "1234567890123" : v;
+  }
+
+  getOption(v, arg2) {
+    if (v === "bad-argument") {
+      throw new ComplexError.ReallyBadArgument(20);
+    }
+    if (v === "unexpected-error") {
+      throw new Error("unexpected value");
+    }
+    if (arg2) {
+      return v !== undefined ? v.toUpperCase() : undefined;
+    }
+    return v;
+  }
+
+  getList(v, arg2) {
+    return arg2 ? v : [];
+  }
+
+  getNothing(v) {
+    if (v === "bad-argument") {
+      throw new SimpleError.BadArgument("bad argument");
+    }
+    if (v === "unexpected-error") {
+      throw new Error("unexpected value");
+    }
+  }
+}
+
+const callback = new JsGetters();
+for (const v of [true, false]) {
+  assert.strictEqual(rustGetters.getBool(callback, v, true), callback.getBool(v, true));
+}
+for (const v of [[1, 2], [0, 1]]) {
+  assert.deepStrictEqual(rustGetters.getList(callback, v, true), callback.getList(v, true));
+}
+for (const v of ["Hello", "world"]) {
+  assert.strictEqual(rustGetters.getString(callback, v, true), callback.getString(v, true));
+}
+for (const v of ["Some", undefined]) {
+  assert.strictEqual(rustGetters.getOption(callback, v, false), callback.getOption(v, false));
+}
+assert.strictEqual(rustGetters.getStringOptionalCallback(callback, "TestString", false), "TestString");
+assert.strictEqual(rustGetters.getStringOptionalCallback(undefined, "TestString", false), undefined);
+rustGetters.getNothing(callback, "TestString");
+
+// 2. Pass the callback in as a constructor argument, to be stored on the Object struct.
+// This is crucial if we want to configure a system at startup,
+// then use it without passing callbacks all the time.
+class StoredJsStringifier {
+  fromSimpleType(value) {
+    return `javascript: ${value}`;
+  }
+
+  fromComplexType(values) {
+    return `javascript: ${values}`;
+  }
+}
+
+const jsStringifier = new StoredJsStringifier();
+const rustStringifier = new RustStringifier(jsStringifier);
+for (const v of [1, 2]) {
+  assert.strictEqual(rustStringifier.fromSimpleType(v), jsStringifier.fromSimpleType(v));
+}
+
+// 3. Errors thrown by callbacks
+assert.throws(() => rustGetters.getString(callback, "bad-argument", true), SimpleError.BadArgument);
+assert.throws(() => rustGetters.getString(callback, "unexpected-error", true), SimpleError.UnexpectedError);
+assert.throws(() => rustGetters.getNothing(callback, "bad-argument"), SimpleError.BadArgument);
+assert.throws(() => rustGetters.getNothing(callback, "unexpected-error"), SimpleError.UnexpectedError);
+
+assert.throws(
+  () => rustGetters.getOption(callback, "bad-argument", true),
+  (e) => e instanceof ComplexError.ReallyBadArgument && e.code === 20,
+);
+assert.throws(
+  () => rustGetters.getOption(callback, "unexpected-error", true),
+  (e) => e instanceof ComplexError.UnexpectedErrorWithReason && e.reason === "Error: unexpected value",
+);
@@ -2,4 +2,6 @@ uniffi::build_foreign_language_testcases!(
     "tests/bindings/test_callbacks.kts",
     "tests/bindings/test_callbacks.swift",
     "tests/bindings/test_callbacks.py",
+    "tests/bindings/test_callbacks.js",
+    "tests/bindings/test_callbacks.go",
 );
@@ -510,7 +510,7 @@ pub enum Color {
 }
 
 #[derive(Debug, Clone)]
-struct Patch {
+pub struct Patch {
     color: Color,
 }
 
@@ -524,6 +524,14 @@ impl Patch {
     }
 }
 
+#[uniffi::export]
+impl Patch {
+    // Test methods that consume the object
+    fn into_color(self) -> Color {
+        self.color
+    }
+}
+
 // This is a small implementation of a counter that allows waiting on one thread,
 // and counting on another thread. We use it to test that the UniFFI generated scaffolding
 // doesn't introduce unexpected locking behaviour between threads.
@@ -0,0 +1,470 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+	"reflect"
+	"strings"
+	"time"
+
+	"uniffi_test/coverall"
+)
+
+func assert(condition bool, message string) {
+	if !condition {
+		panic(message)
+	}
+}
+
+// assertPanics checks that `f` panics with an `*InternalError` containing `message`.
+func assertPanics(f func(), message string) {
+	defer func() {
+		r := recover()
+		internalError, ok := r.(*coverall.InternalError)
+		if !ok || !strings.Contains(internalError.Message, message) {
+			panic(fmt.Sprintf("expected a panic containing %q, got %v", message, r))
+		}
+	}()
+	f()
+}
+
+// Any test not terminating with zero objects alive will cause others to
+// fail - this helps us work out which test kept things alive.
+func assertNoneAlive(test string) {
+	assert(coverall.GetNumAlive() == 0, fmt.Sprintf("%s: %d objects alive", test, coverall.GetNumAlive()))
+}
+
+func testSomeDict() {
+	d := coverall.CreateSomeDict()
+	assert(d.Text == "text", "text")
+	assert(*d.MaybeText == "maybe_text", "maybe_text")
+	assert(string(d.SomeBytes) == "some_bytes", "some_bytes")
+	assert(string(*d.MaybeSomeBytes) == "maybe_some_bytes", "maybe_some_bytes")
+	assert(d.ABool, "a_bool")
+	assert(!*d.MaybeABool, "maybe_a_bool")
+	assert(d.Unsigned8 == 1 && *d.MaybeUnsigned8 == 2, "unsigned8")
+	assert(d.Unsigned16 == 3 && *d.MaybeUnsigned16 == 4, "unsigned16")
+	assert(d.Unsigned64 == 18446744073709551615 && *d.MaybeUnsigned64 == 0, "unsigned64")
+	assert(d.Signed8 == 8 && *d.MaybeSigned8 == 0, "signed8")
+	assert(d.Signed64 == 9223372036854775807 && *d.MaybeSigned64 == 0, "signed64")
+	assert(d.Float32 == 1.2345 && *d.MaybeFloat32 == 22.0/7.0, "float32")
+	assert(d.Float64 == 0.0 && *d.MaybeFloat64 == 1.0, "float64")
+	assert(d.Coveralls.GetName() == "some_dict", "coveralls")
+	assert(d.TestTrait.Name() == "node-2", "test_trait")
+	d.Coveralls.Destroy()
+	assertNoneAlive("testSomeDict")
+}
+
+func testNoneDict() {
+	d := coverall.CreateNoneDict()
+	assert(d.Text == "text", "text")
+	assert(d.MaybeText == nil, "maybe_text")
+	assert(string(d.SomeBytes) == "some_bytes", "some_bytes")
+	assert(d.MaybeSomeBytes == nil, "maybe_some_bytes")
+	assert(d.ABool, "a_bool")
+	assert(d.MaybeABool == nil, "maybe_a_bool")
+	assert(d.MaybeUnsigned8 == nil && d.MaybeUnsigned16 == nil && d.MaybeUnsigned64 == nil, "maybe_unsigned")
+	assert(d.MaybeSigned8 == nil && d.MaybeSigned64 == nil, "maybe_signed")
+	assert(d.MaybeFloat32 == nil && d.MaybeFloat64 == nil, "maybe_float")
+	assert(d.Coveralls == nil, "coveralls")
+	assert(d.TestTrait == nil, "test_trait")
+	assertNoneAlive("testNoneDict")
+}
+
+func testConstructors() {
+	coveralls := coverall.NewCoveralls("c1")
+	assert(coverall.GetNumAlive() == 1, "one alive")
+	assert(coveralls.GetName() == "c1", "c1")
+
+	coveralls2, err := coverall.NewCoverallsFallibleNew("c2", false)
+	assert(err == nil && coverall.GetNumAlive() == 2, "two alive")
+	assert(coveralls2.GetName() == "c2", "c2")
+
+	_, err = coverall.NewCoverallsFallibleNew("", true)
+	var tooManyHoles *coverall.CoverallErrorTooManyHoles
+	assert(errors.As(err, &tooManyHoles), "fallible_new should fail")
+
+	assertPanics(func() { coverall.NewCoverallsPanickingNew("expected panic: woe is me") }, "expected panic: woe is me")
+
+	coveralls2.Destroy()
+	assert(coverall.GetNumAlive() == 1, "one alive after Destroy()")
+	coveralls.Destroy()
+	assertNoneAlive("testConstructors")
+}
+
+func testSimpleErrors() {
+	coveralls := coverall.NewCoveralls("test_errors")
+	defer coveralls.Destroy()
+
+	var tooManyHoles *coverall.CoverallErrorTooManyHoles
+	_, err := coveralls.MaybeThrow(true)
+	assert(errors.As(err, &tooManyHoles), "maybe_throw should fail")
+	assert(err.Error() == "The coverall has too many holes", err.Error())
+
+	err = coverall.ThrowMacroError()
+	var tooManyMacros *coverall.CoverallMacroErrorTooManyMacros
+	assert(errors.As(err, &tooManyMacros), "throw_macro_error should fail")
+	assert(err.Error() == "The coverall has too many macros", err.Error())
+
+	_, err = coveralls.MaybeThrowInto(true)
+	assert(errors.As(err, &tooManyHoles), "maybe_throw_into should fail")
+
+	assertPanics(func() { coveralls.Panic("expected panic: oh no") }, "expected panic: oh no")
+}
+
+func testFlatErrors() {
+	// This is testing enums which have fields in Rust but are marked as "flat" for the ffi.
+	err := coverall.ThrowFlatError()
+	var tooManyVariants *coverall.CoverallFlatErrorTooManyVariants
+	assert(errors.As(err, &tooManyVariants), "throw_flat_error should fail")
+	assert(err.Error() == "Too many variants: 99", err.Error())
+
+	err = coverall.ThrowFlatMacroError()
+	var tooManyMacroVariants *coverall.CoverallFlatMacroErrorTooManyVariants
+	assert(errors.As(err, &tooManyMacroVariants), "throw_flat_macro_error should fail")
+	assert(err.Error() == "Too many variants: 88", err.Error())
+
+	err = coverall.ThrowRichErrorNoVariantData()
+	var tooManyPlainVariants *coverall.CoverallRichErrorNoVariantDataTooManyPlainVariants
+	assert(errors.As(err, &tooManyPlainVariants), "throw_rich_error_no_variant_data should fail")
+}
+
+func testComplexErrors() {
+	coveralls := coverall.NewCoveralls("test_complex_errors")
+	defer coveralls.Destroy()
+
+	ok, err := coveralls.MaybeThrowComplex(0)
+	assert(err == nil && ok, "maybe_throw_complex(0)")
+
+	_, err = coveralls.MaybeThrowComplex(1)
+	var osError *coverall.ComplexErrorOsError
+	assert(errors.As(err, &osError), "maybe_throw_complex(1)")
+	assert(osError.Code == 10 && osError.ExtendedCode == 20, fmt.Sprint(osError))
+
+	err = coverall.ThrowComplexMacroError()
+	var macroOsError *coverall.ComplexMacroErrorOsError
+	assert(errors.As(err, &macroOsError), "throw_complex_macro_error")
+	assert(macroOsError.Code == 1 && macroOsError.ExtendedCode == 2, fmt.Sprint(macroOsError))
+
+	_, err = coveralls.MaybeThrowComplex(2)
+	var permissionDenied *coverall.ComplexErrorPermissionDenied
+	assert(errors.As(err, &permissionDenied), "maybe_throw_complex(2)")
+	assert(permissionDenied.Reason == "Forbidden", permissionDenied.Reason)
+
+	_, err = coveralls.MaybeThrowComplex(3)
+	var unknownError *coverall.ComplexErrorUnknownError
+	assert(errors.As(err, &unknownError), "maybe_throw_complex(3)")
+
+	assertPanics(func() { coveralls.MaybeThrowComplex(4) }, "")
+}
+
+func testErrorValues() {
+	err := coverall.ThrowRootError()
+	var rootComplex *coverall.RootErrorComplex
+	assert(errors.As(err, &rootComplex), "throw_root_error")
+	var osError *coverall.ComplexErrorOsError
+	assert(errors.As(rootComplex.Error_, &osError) && osError.Code == 1, "throw_root_error should hold an OsError")
+
+	var rootOther *coverall.RootErrorOther
+	assert(errors.As(coverall.GetRootError(), &rootOther), "get_root_error")
+	assert(rootOther.Error_ == coverall.OtherErrorUnexpected, "get_root_error should hold OtherError.UNEXPECTED")
+
+	var permissionDenied *coverall.ComplexErrorPermissionDenied
+	assert(errors.As(coverall.GetComplexError(nil), &permissionDenied), "get_complex_error")
+	assert(coverall.GetErrorDict(nil).ComplexError == nil, "get_error_dict")
+}
+
+func testEnums() {
+	_, ok := coverall.GetSimpleFlatMacroEnum(0).(coverall.SimpleFlatMacroEnumFirst)
+	assert(ok, "get_simple_flat_macro_enum(0)")
+}
+
+func testArcs() {
+	coveralls := coverall.NewCoveralls("test_arcs")
+	assert(coverall.GetNumAlive() == 1, "one alive")
+	// One reference is held by Go, and one by the `Arc<Self>` method receiver.
+	assert(coveralls.StrongCount() == 2, "strong_count")
+	assert(coveralls.GetOther() == nil, "get_other")
+
+	coveralls.TakeOther(coveralls)
+	// should now be a new strong ref.
+	assert(coveralls.StrongCount() == 3, "strong_count after take_other")
+	// but the same number of instances.
+	assert(coverall.GetNumAlive() == 1, "one alive after take_other")
+	// and check it's the correct object.
+	other := coveralls.GetOther()
+	assert(other.GetName() == "test_arcs", "get_other().get_name()")
+	other.Destroy()
+
+	var tooManyHoles *coverall.CoverallErrorTooManyHoles
+	assert(errors.As(coveralls.TakeOtherFallible(), &tooManyHoles), "take_other_fallible")
+	assertPanics(func() { coveralls.TakeOtherPanic("expected panic: with an arc!") }, "expected panic: with an arc!")
+
+	coveralls.TakeOther(nil)
+	assert(coveralls.StrongCount() == 2, "strong_count after take_other(nil)")
+	coveralls.Destroy()
+	assertNoneAlive("testArcs")
+}
+
+func testReturnObjects() {
+	coveralls := coverall.NewCoveralls("test_return_objects")
+	assert(coverall.GetNumAlive() == 1, "one alive")
+	c2 := coveralls.CloneMe()
+	assert(c2.GetName() == coveralls.GetName(), "clone_me")
+	assert(coverall.GetNumAlive() == 2, "two alive")
+
+	coveralls.TakeOther(c2)
+	assert(coverall.GetNumAlive() == 2, "two alive after take_other")
+	assert(c2.StrongCount() == 3, "c2 strong_count")
+
+	// The Rust struct isn't dropped, since `coveralls` holds an `Arc<>` to it.
+	c2.Destroy()
+	assert(coverall.GetNumAlive() == 2, "two alive after destroying c2")
+
+	redPatch := coverall.NewPatch(coverall.ColorRed)
+	coveralls.AddPatch(redPatch)
+	redPatch.Destroy()
+	bluePatch := coverall.NewPatch(coverall.ColorBlue)
+	coveralls.AddRepair(coverall.Repair{When: time.Now(), Patch: bluePatch})
+	bluePatch.Destroy()
+	repairs := coveralls.GetRepairs()
+	assert(len(repairs) == 2, "get_repairs")
+	assert(repairs[0].Patch.GetColor() == coverall.ColorRed, "first repair")
+	assert(repairs[1].Patch.GetColor() == coverall.ColorBlue, "second repair")
+	for _, repair := range repairs {
+		repair.Patch.Destroy()
+	}
+
+	// Destroying `coveralls` drops both.
+	coveralls.Destroy()
+	assertNoneAlive("testReturnObjects")
+}
+
+func testDictWithDefaults() {
+	d := coverall.DictWithDefaults{Name: "this", Category: nil, Integer: 42}
+	assert(d.Name == "this" && d.Category == nil && d.Integer == 42, "DictWithDefaults")
+}
+
+func testDictWithNonStringKeys() {
+	coveralls := coverall.NewCoveralls("test_dict")
+	defer coveralls.Destroy()
+	assert(coveralls.GetDict("answer", 42)["answer"] == 42, "get_dict")
+	assert(coveralls.GetDict2("answer", 42)["answer"] == 42, "get_dict2")
+	assert(coveralls.GetDict3(31, 42)[31] == 42, "get_dict3")
+}
+
+func testConsumingMethods() {
+	patch := coverall.NewPatch(coverall.ColorGreen)
+	assert(patch.IntoColor() == coverall.ColorGreen, "into_color")
+	assertPanics(func() { patch.GetColor() }, "after being consumed")
+
+	// The patch can't be consumed while `coveralls` holds another reference to it, but it can
+	// still be used afterwards.
+	coveralls := coverall.NewCoveralls("test_consuming_methods")
+	patch = coverall.NewPatch(coverall.ColorRed)
+	coveralls.AddPatch(patch)
+	assertPanics(func() { patch.IntoColor() }, "other references")
+	func() {
+		defer func() {
+			assert(!recover().(*coverall.InternalError).Panic, "failing to consume a patch isn't a Rust panic")
+		}()
+		patch.IntoColor()
+	}()
+	assert(patch.GetColor() == coverall.ColorRed, "get_color after failing to consume")
+	coveralls.Destroy()
+	assert(patch.IntoColor() == coverall.ColorRed, "into_color after the other reference is gone")
+}
+
+func testBytes() {
+	coveralls := coverall.NewCoveralls("test_bytes")
+	defer coveralls.Destroy()
+	assert(string(coveralls.Reverse([]byte("123"))) == "321", "reverse")
+}
+
+func testReturnOnlyDict() {
+	// ReturnOnlyDict should only be returned from Rust, so passing one in is an internal error
+	// rather than an attempt to use an invalid value.
+	assertPanics(func() {
+		coverall.TryInputReturnOnlyDict(coverall.ReturnOnlyDict{E: &coverall.CoverallFlatErrorTooManyVariants{}})
+	}, "")
+}
+
+type GoGetters struct{}
+
+func (GoGetters) GetBool(v bool, arg2 bool) bool {
+	return v != arg2
+}
+
+func (GoGetters) GetString(v string, arg2 bool) (string, error) {
+	switch {
+	case v == "too-many-holes":
+		return "", &coverall.CoverallErrorTooManyHoles{}
+	case v == "unexpected-error":
+		return "", errors.New("unexpected error")
+	case arg2:
+		return strings.ToUpper(v), nil
+	default:
+		return v, nil
+	}
+}
+
+func (GoGetters) GetOption(v string, arg2 bool) (*string, error) {
+	switch {
+	case v == "os-error":
+		return nil, &coverall.ComplexErrorOsError{Code: 100, ExtendedCode: 200}
+	case v == "unknown-error":
+		return nil, &coverall.ComplexErrorUnknownError{}
+	case arg2 && v == "":
+		return nil, nil
+	case arg2:
+		upper := strings.ToUpper(v)
+		return &upper, nil
+	default:
+		return &v, nil
+	}
+}
+
+func (GoGetters) GetList(v []int32, arg2 bool) []int32 {
+	if arg2 {
+		return v
+	}
+	return []int32{}
+}
+
+func (GoGetters) GetNothing(v string) {}
+
+func (GoGetters) RoundTripObject(coveralls *coverall.Coveralls) *coverall.Coveralls {
+	return coveralls
+}
+
+type GoNode struct {
+	parent coverall.NodeTrait
+}
+
+func (n *GoNode) Name() string {
+	return "node-go"
+}
+
+func (n *GoNode) SetParent(parent coverall.NodeTrait) {
+	n.parent = parent
+}
+
+func (n *GoNode) GetParent() coverall.NodeTrait {
+	return n.parent
+}
+
+func (n *GoNode) StrongCount() uint64 {
+	return 0
+}
+
+func testGoGetters() {
+	coverall.TestGetters(GoGetters{})
+}
+
+func checkGetters(getters coverall.Getters) {
+	assert(!getters.GetBool(true, true), "get_bool(true, true)")
+	assert(getters.GetBool(true, false), "get_bool(true, false)")
+
+	s, err := getters.GetString("hello", true)
+	assert(err == nil && s == "HELLO", "get_string")
+	o, err := getters.GetOption("hello", false)
+	assert(err == nil && *o == "hello", "get_option")
+	o, err = getters.GetOption("", true)
+	assert(err == nil && o == nil, "get_option empty")
+	assert(reflect.DeepEqual(getters.GetList([]int32{1, 2, 3}, true), []int32{1, 2, 3}), "get_list")
+	assert(len(getters.GetList([]int32{1, 2, 3}, false)) == 0, "get_list empty")
+	getters.GetNothing("hello")
+
+	var tooManyHoles *coverall.CoverallErrorTooManyHoles
+	_, err = getters.GetString("too-many-holes", true)
+	assert(errors.As(err, &tooManyHoles), "get_string(too-many-holes)")
+	var osError *coverall.ComplexErrorOsError
+	_, err = getters.GetOption("os-error", true)
+	assert(errors.As(err, &osError) && osError.Code == 100 && osError.ExtendedCode == 200, "get_option(os-error)")
+	var unknownError *coverall.ComplexErrorUnknownError
+	_, err = getters.GetOption("unknown-error", true)
+	assert(errors.As(err, &unknownError), "get_option(unknown-error)")
+}
+
+func testRustGetters() {
+	getters := coverall.MakeRustGetters()
+	checkGetters(getters)
+	assertPanics(func() { getters.GetString("unexpected-error", true) }, "")
+	getters.(*coverall.GettersImpl).Destroy()
+}
+
+func testPath() {
+	// get_traits creates 2 objects that implement the trait
+	traits := coverall.GetTraits()
+	assert(traits[0].Name() == "node-1", "traits[0].name()")
+	// Note: strong counts are 1 more than you might expect, because the strong_count() method
+	// holds a strong ref.
+	assert(traits[0].StrongCount() == 2, "traits[0].strong_count()")
+	assert(traits[1].Name() == "node-2", "traits[1].name()")
+	assert(traits[1].StrongCount() == 2, "traits[1].strong_count()")
+
+	// Let's try connecting them together
+	traits[0].SetParent(traits[1])
+	// This doesn't increase the Rust strong count, since `traits[1]` is passed to Rust as a Go
+	// implementation which calls the Rust object.
+	assert(traits[1].StrongCount() == 2, "traits[1].strong_count() after set_parent()")
+	assert(reflect.DeepEqual(coverall.AncestorNames(traits[0]), []string{"node-2"}), "ancestor_names(traits[0])")
+	assert(len(coverall.AncestorNames(traits[1])) == 0, "ancestor_names(traits[1])")
+	assert(traits[0].GetParent().Name() == "node-2", "traits[0].get_parent()")
+
+	// Throw in a Go implementation of the trait
+	// The ancestry chain now goes traits[0] -> traits[1] -> goNode
+	goNode := &GoNode{}
+	traits[1].SetParent(goNode)
+	assert(reflect.DeepEqual(coverall.AncestorNames(traits[0]), []string{"node-2", "node-go"}), "ancestor_names(traits[0]) with goNode")
+	assert(reflect.DeepEqual(coverall.AncestorNames(traits[1]), []string{"node-go"}), "ancestor_names(traits[1]) with goNode")
+	assert(len(coverall.AncestorNames(goNode)) == 0, "ancestor_names(goNode)")
+
+	// Rotating things.
+	// The ancestry chain now goes goNode -> traits[0] -> traits[1]
+	traits[1].SetParent(nil)
+	goNode.SetParent(traits[0])
+	assert(reflect.DeepEqual(coverall.AncestorNames(goNode), []string{"node-1", "node-2"}), "ancestor_names(goNode) rotated")
+	assert(reflect.DeepEqual(coverall.AncestorNames(traits[0]), []string{"node-2"}), "ancestor_names(traits[0]) rotated")
+	assert(len(coverall.AncestorNames(traits[1])) == 0, "ancestor_names(traits[1]) rotated")
+
+	// Make sure we don't crash when undoing it all
+	goNode.SetParent(nil)
+	traits[0].SetParent(nil)
+}
+
+func testRoundTripping() {
+	rustGetters := coverall.MakeRustGetters()
+	// Check that these don't cause use-after-free bugs
+	coverall.TestRoundTripThroughRust(rustGetters)
+	coverall.TestRoundTripThroughForeign(GoGetters{})
+}
+
+func testRustOnlyTraits() {
+	traits := coverall.GetStringUtilTraits()
+	assert(traits[0].Concat("cow", "boy") == "cowboy", "traits[0].concat()")
+	assert(traits[1].Concat("cow", "boy") == "cowboy", "traits[1].concat()")
+}
+
+func main() {
+	testSomeDict()
+	testNoneDict()
+	testConstructors()
+	testSimpleErrors()
+	testFlatErrors()
+	testComplexErrors()
+	testErrorValues()
+	testEnums()
+	testArcs()
+	testReturnObjects()
+	testDictWithDefaults()
+	testDictWithNonStringKeys()
+	testConsumingMethods()
+	testBytes()
+	testReturnOnlyDict()
+	testGoGetters()
+	testRustGetters()
+	testPath()
+	testRoundTripping()
+	testRustOnlyTraits()
+}
@@ -108,6 +108,20 @@ Coveralls("test_arcs").use { coveralls ->
     } catch (e: InternalException) {
         // No problemo!
     }
+
+    val observed = mutableListOf<String?>()
+    RustPanic.observer = { panic -> observed.add(panic.message) }
+    try {
+        coveralls.panic("expected panic: details")
+        throw RuntimeException("Should have thrown a RustPanic")
+    } catch (e: RustPanic) {
+        assert(e.message == "expected panic: details")
+        assert(e.file!!.endsWith("lib.rs"))
+        assert(e.line!! > 0)
+    } finally {
+        RustPanic.observer = null
+    }
+    assert(observed == listOf("expected panic: details"))
     coveralls.takeOther(null);
     assert(coveralls.strongCount() == 2UL);
 }
@@ -136,6 +150,16 @@ Coveralls("test_return_objects").use { coveralls ->
 // Destroying `coveralls` will kill both.
 assert(getNumAlive() == 0UL);
 
+// Test object scopes
+
+uniffiObjectScope {
+    val coveralls = Coveralls("test_object_scopes").closeOnExit()
+    coveralls.cloneMe().closeOnExit()
+    assert(getNumAlive() == 2UL)
+}
+// Leaving the scope closes both objects.
+assert(getNumAlive() == 0UL);
+
 Coveralls("test_simple_errors").use { coveralls ->
     try {
         coveralls.maybeThrow(true)
@@ -228,6 +252,34 @@ Coveralls("test_interfaces_in_dicts").use { coveralls ->
     assert(coveralls.getRepairs().size == 2)
 }
 
+// Methods that take `self` by value consume the object
+Patch(Color.GREEN).let { patch ->
+    assert(patch.intoColor() == Color.GREEN)
+    try {
+        patch.getColor()
+        throw RuntimeException("Should have thrown")
+    } catch (e: IllegalStateException) {
+        assert(e.message!!.contains("already been consumed"))
+    }
+}
+
+// The patch can't be consumed while the coveralls holds another reference to it, but it can still
+// be used
+Patch(Color.RED).let { patch ->
+    val coveralls = Coveralls("test_consuming_methods")
+    coveralls.addPatch(patch)
+    try {
+        patch.intoColor()
+        throw RuntimeException("Should have thrown")
+    } catch (e: InternalException) {
+        assert(e !is RustPanic)
+        assert(e.message!!.contains("other references"))
+    }
+    assert(patch.getColor() == Color.RED)
+    coveralls.destroy()
+    assert(patch.intoColor() == Color.RED)
+}
+
 Coveralls("test_regressions").use { coveralls ->
     assert(coveralls.getStatus("success") == "status: success")
 }
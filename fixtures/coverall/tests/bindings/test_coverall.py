@@ -122,6 +122,23 @@ class TestCoverall(unittest.TestCase):
         with self.assertRaisesRegex(InternalError, "expected panic: oh no"):
             coveralls.panic("expected panic: oh no")
 
+    def test_panic_details(self):
+        coveralls = Coveralls("test_panic_details")
+        with self.assertRaises(RustPanic) as cm:
+            coveralls.panic("expected panic: details")
+        self.assertEqual(cm.exception.message, "expected panic: details")
+        self.assertTrue(cm.exception.file.endswith("lib.rs"))
+        self.assertGreater(cm.exception.line, 0)
+
+        observed = []
+        RustPanic.observer = observed.append
+        try:
+            with self.assertRaises(RustPanic):
+                coveralls.panic("expected panic: observed")
+        finally:
+            RustPanic.observer = None
+        self.assertEqual([p.message for p in observed], ["expected panic: observed"])
+
     def test_flat_errors(self):
         # This is testing enums which have fields in Rust but are marked as "flat" for the ffi.
         with self.assertRaisesRegex(CoverallFlatError.TooManyVariants, "Too many variants: 99") as cm:
@@ -261,6 +278,29 @@ class TestCoverall(unittest.TestCase):
         with self.assertRaisesRegex(TypeError, "Coveralls.*Patch"):
             coveralls.take_other(patch)
 
+    def test_consuming_methods(self):
+        patch = Patch(Color.GREEN)
+        self.assertEqual(patch.into_color(), Color.GREEN)
+        with self.assertRaisesRegex(RuntimeError, "already been consumed"):
+            patch.get_color()
+
+        # The patch can't be consumed while the coveralls holds another reference to it, but it
+        # can still be used.
+        coveralls = Coveralls("test_consuming_methods")
+        patch = Patch(Color.RED)
+        coveralls.add_patch(patch)
+        with self.assertRaisesRegex(InternalError, "other references") as cm:
+            patch.into_color()
+        self.assertNotIsInstance(cm.exception, RustPanic)
+        self.assertEqual(patch.get_color(), Color.RED)
+        self.assertEqual(coveralls.get_repairs()[0].patch.get_color(), Color.RED)
+
+        # Once the other references are gone, it can be consumed.
+        del coveralls
+        self.assertEqual(patch.into_color(), Color.RED)
+        with self.assertRaisesRegex(RuntimeError, "already been consumed"):
+            patch.get_color()
+
     def test_dict_with_defaults(self):
         """ This does not call Rust code. """
 
@@ -253,6 +253,26 @@ class TestCoverall < Test::Unit::TestCase
     end
   end
 
+  def test_consuming_methods
+    patch = Coverall::Patch.new Coverall::Color::GREEN
+    assert_equal patch.into_color, Coverall::Color::GREEN
+    assert_raise_message /already been consumed/ do
+      patch.get_color
+    end
+
+    # The patch can't be consumed while `coveralls` holds another reference to it, but it can still
+    # be used afterwards.
+    coveralls = Coverall::Coveralls.new "test_consuming_methods"
+    patch = Coverall::Patch.new Coverall::Color::RED
+    coveralls.add_patch patch
+    err = assert_raise Coverall::InternalError do
+      patch.into_color
+    end
+    assert_not_kind_of Coverall::RustPanic, err
+    assert_match(/other references/, err.message)
+    assert_equal patch.get_color, Coverall::Color::RED
+  end
+
   def test_bytes
     coveralls = Coverall::Coveralls.new "test_bytes"
     assert_equal coveralls.reverse("123"), "321"
@@ -173,9 +173,21 @@ do {
     do {
         let _ = try coveralls.maybeThrowComplex(input: 4)
         fatalError("should have thrown")
-    } catch {
-        assert(String(describing: error) == "rustPanic(\"Invalid input\")")
+    } catch let e as CoverallRustPanic {
+        assert(e.message == "Invalid input")
+        assert(e.file!.hasSuffix("lib.rs"))
+        assert(e.line! > 0)
+    }
+
+    var observed: [String] = []
+    CoverallRustPanic.observer = { panic in observed.append(panic.message) }
+    do {
+        let _ = try coveralls.maybeThrowComplex(input: 4)
+        fatalError("should have thrown")
+    } catch is CoverallRustPanic {
+        assert(observed == ["Invalid input"])
     }
+    CoverallRustPanic.observer = nil
 
 }
 
@@ -274,6 +286,36 @@ do {
     assert(coveralls.getRepairs().count == 2)
 }
 
+// Methods that take `self` by value consume the object
+do {
+    let patch = Patch(color: Color.green)
+    assert(try! patch.intoColor() == Color.green)
+    do {
+        _ = try patch.getColor()
+        fatalError("Should have thrown")
+    } catch let e as CoverallObjectConsumed {
+        assert(e.typeName == "Patch")
+    }
+}
+
+// The patch can't be consumed while the coveralls holds another reference to it, but it can still
+// be used afterwards
+do {
+    let patch = Patch(color: Color.red)
+    var coveralls: Coveralls? = Coveralls(name: "test_consuming_methods")
+    coveralls!.addPatch(patch: patch)
+    do {
+        _ = try patch.intoColor()
+        fatalError("Should have thrown")
+    } catch {
+        assert(!(error is CoverallRustPanic))
+        assert(error.localizedDescription.contains("other references"))
+    }
+    assert(try! patch.getColor() == Color.red)
+    coveralls = nil
+    assert(try! patch.intoColor() == Color.red)
+}
+
 // Test bytes
 do {
     let coveralls = Coveralls(name: "test_bytes")
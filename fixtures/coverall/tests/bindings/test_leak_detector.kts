@@ -0,0 +1,51 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import java.util.concurrent.ConcurrentLinkedQueue
+import uniffi.coverall.*
+
+// Test that `UniffiLeakDetector` reports objects that are garbage collected without being closed,
+// along with where they were allocated.
+
+val leaks = ConcurrentLinkedQueue<Pair<String, Throwable>>()
+UniffiLeakDetector.reporter = { className, allocationSite -> leaks.add(Pair(className, allocationSite)) }
+UniffiLeakDetector.enabled = true
+
+// Keep collecting garbage until `condition` is true, so that the cleaner runs
+fun collectGarbageUntil(condition: () -> Boolean) {
+    val deadline = System.currentTimeMillis() + 10_000
+    while (!condition() && System.currentTimeMillis() < deadline) {
+        System.gc()
+        Thread.sleep(10)
+    }
+}
+
+// Allocate in a function, so that nothing keeps the objects reachable.
+fun allocateWithoutClosing() {
+    Coveralls("test_leak_detector")
+}
+
+fun allocateAndClose() {
+    Coveralls("test_leak_detector_closed").close()
+}
+
+allocateAndClose()
+allocateWithoutClosing()
+collectGarbageUntil { leaks.isNotEmpty() && getNumAlive() == 0UL }
+
+assert(leaks.size == 1) { "expected a single leak report, got ${leaks.size}" }
+val (className, allocationSite) = leaks.first()
+assert(className == "Coveralls") { "unexpected class name: $className" }
+assert(allocationSite.stackTrace.any { it.methodName == "allocateWithoutClosing" }) {
+    "the allocation site should point to allocateWithoutClosing()"
+}
+// With the default cleanup policy, the leaked object is still freed.
+assert(getNumAlive() == 0UL)
+
+// Objects allocated while the detector is off aren't tracked.
+UniffiLeakDetector.enabled = false
+allocateWithoutClosing()
+collectGarbageUntil { getNumAlive() == 0UL }
+assert(getNumAlive() == 0UL)
+assert(leaks.size == 1)
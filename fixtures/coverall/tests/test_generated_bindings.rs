@@ -3,5 +3,25 @@ uniffi::build_foreign_language_testcases!(
     "tests/bindings/test_coverall.kts",
     "tests/bindings/test_coverall.rb",
     "tests/bindings/test_coverall.swift",
+    "tests/bindings/test_coverall.go",
     "tests/bindings/test_handlerace.kts",
+    "tests/bindings/test_leak_detector.kts",
 );
+
+// Run the Kotlin tests again with `ffi_backend = "jni"`
+#[test]
+fn uniffi_foreign_language_testcase_test_coverall_kts_jni() -> uniffi::deps::anyhow::Result<()> {
+    uniffi::kotlin_run_script(
+        std::env!("CARGO_TARGET_TMPDIR"),
+        std::env!("CARGO_PKG_NAME"),
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/bindings/test_coverall.kts"
+        ),
+        vec![],
+        &uniffi::RunScriptOptions {
+            config_file: Some(concat!(env!("CARGO_MANIFEST_DIR"), "/uniffi-jni.toml").into()),
+            ..uniffi::RunScriptOptions::default()
+        },
+    )
+}
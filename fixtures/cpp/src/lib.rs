@@ -0,0 +1,219 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+#[uniffi::export]
+fn greet(name: String) -> String {
+    format!("Hello, {name}!")
+}
+
+#[uniffi::export]
+fn first_word(text: String) -> Option<String> {
+    text.split_whitespace().next().map(str::to_owned)
+}
+
+#[uniffi::export]
+fn sum_all(values: Vec<i64>) -> i64 {
+    values.iter().sum()
+}
+
+#[uniffi::export]
+fn word_lengths(words: Vec<String>) -> HashMap<String, u32> {
+    words
+        .into_iter()
+        .map(|w| {
+            let len = w.chars().count() as u32;
+            (w, len)
+        })
+        .collect()
+}
+
+#[uniffi::export]
+fn reverse_bytes(bytes: Vec<u8>) -> Vec<u8> {
+    bytes.into_iter().rev().collect()
+}
+
+#[uniffi::export]
+fn is_positive(value: f64) -> bool {
+    value > 0.0
+}
+
+#[uniffi::export]
+fn add_duration(time: SystemTime, duration: Duration) -> SystemTime {
+    time + duration
+}
+
+/// A record with default values
+#[derive(uniffi::Record)]
+pub struct Settings {
+    pub name: String,
+    #[uniffi(default = 3)]
+    pub retries: u32,
+    #[uniffi(default = true)]
+    pub verbose: bool,
+    #[uniffi(default = None)]
+    pub nickname: Option<String>,
+    pub tags: Vec<String>,
+}
+
+#[uniffi::export]
+fn describe_settings(settings: Settings) -> String {
+    format!(
+        "{} retries={} verbose={} nickname={} tags={}",
+        settings.name,
+        settings.retries,
+        settings.verbose,
+        settings.nickname.as_deref().unwrap_or("-"),
+        settings.tags.join(",")
+    )
+}
+
+#[derive(uniffi::Enum, Clone, Copy)]
+pub enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+#[uniffi::export]
+fn next_color(color: Color) -> Color {
+    match color {
+        Color::Red => Color::Green,
+        Color::Green => Color::Blue,
+        Color::Blue => Color::Red,
+    }
+}
+
+#[derive(uniffi::Enum)]
+pub enum Shape {
+    Point,
+    Circle { radius: f64 },
+    Rectangle { width: f64, height: f64 },
+    Labelled(String, Color),
+}
+
+#[uniffi::export]
+fn scale_shape(shape: Shape, factor: f64) -> Shape {
+    match shape {
+        Shape::Point => Shape::Point,
+        Shape::Circle { radius } => Shape::Circle {
+            radius: radius * factor,
+        },
+        Shape::Rectangle { width, height } => Shape::Rectangle {
+            width: width * factor,
+            height: height * factor,
+        },
+        Shape::Labelled(label, color) => Shape::Labelled(format!("{label} x{factor}"), color),
+    }
+}
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+#[uniffi(flat_error)]
+pub enum ParseError {
+    #[error("empty input")]
+    Empty,
+    #[error("invalid number: {0}")]
+    Invalid(String),
+}
+
+#[uniffi::export]
+fn parse_number(text: String) -> Result<i32, ParseError> {
+    if text.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    text.parse()
+        .map_err(|_| ParseError::Invalid(text.to_owned()))
+}
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum AccountError {
+    #[error("insufficient funds")]
+    InsufficientFunds { balance: u64, requested: u64 },
+    #[error("account closed")]
+    Closed,
+}
+
+/// Wrapper around a number of cents
+pub struct Cents(pub u64);
+uniffi::custom_newtype!(Cents, u64);
+
+/// A bank account, shared between all the handles that refer to it
+#[derive(uniffi::Object)]
+#[uniffi::export(Display, Eq)]
+pub struct Account {
+    owner: String,
+    balance: Mutex<u64>,
+}
+
+#[uniffi::export]
+impl Account {
+    #[uniffi::constructor]
+    fn new(owner: String) -> Arc<Self> {
+        Self::with_balance(owner, Cents(0))
+    }
+
+    #[uniffi::constructor]
+    fn with_balance(owner: String, balance: Cents) -> Arc<Self> {
+        Arc::new(Self {
+            owner,
+            balance: Mutex::new(balance.0),
+        })
+    }
+
+    fn balance(&self) -> Cents {
+        Cents(*self.balance.lock().unwrap())
+    }
+
+    fn deposit(&self, amount: Cents) {
+        *self.balance.lock().unwrap() += amount.0;
+    }
+
+    fn withdraw(&self, amount: Cents) -> Result<Cents, AccountError> {
+        let mut balance = self.balance.lock().unwrap();
+        if amount.0 > *balance {
+            return Err(AccountError::InsufficientFunds {
+                balance: *balance,
+                requested: amount.0,
+            });
+        }
+        *balance -= amount.0;
+        Ok(Cents(*balance))
+    }
+
+    fn close(&self) -> Result<(), AccountError> {
+        Err(AccountError::Closed)
+    }
+
+    /// Close the account for good, returning its balance
+    fn into_balance(self) -> Cents {
+        Cents(self.balance.into_inner().unwrap())
+    }
+}
+
+impl fmt::Display for Account {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Account({})", self.owner)
+    }
+}
+
+impl PartialEq for Account {
+    fn eq(&self, other: &Self) -> bool {
+        self.owner == other.owner
+    }
+}
+
+impl Eq for Account {}
+
+#[uniffi::export]
+fn richest(accounts: Vec<Arc<Account>>) -> Option<Arc<Account>> {
+    accounts
+        .into_iter()
+        .max_by_key(|a| *a.balance.lock().unwrap())
+}
+
+uniffi::setup_scaffolding!();
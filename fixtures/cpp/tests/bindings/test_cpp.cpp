@@ -0,0 +1,162 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+#include <cassert>
+#include <cstdlib>
+#include <iostream>
+
+#include "uniffi_cpp.hpp"
+
+using namespace uniffi_cpp;
+
+static void test_builtin_types() {
+    assert(greet("C++") == "Hello, C++!");
+    assert(greet("") == "Hello, !");
+    assert(first_word("hello world") == std::optional<std::string>("hello"));
+    assert(!first_word("   ").has_value());
+    assert(sum_all({1, 2, 3, -10}) == -4);
+    assert(sum_all({}) == 0);
+    auto lengths = word_lengths({"one", "three", "héllo"});
+    assert(lengths.size() == 3);
+    assert(lengths["three"] == 5);
+    assert(lengths["héllo"] == 5);
+    assert((reverse_bytes({1, 2, 3}) == std::vector<uint8_t>{3, 2, 1}));
+    assert(is_positive(0.5));
+    assert(!is_positive(-0.5));
+}
+
+static void test_time() {
+    using namespace std::chrono;
+    system_clock::time_point epoch;
+    auto later = add_duration(epoch + seconds(10), milliseconds(1500));
+    assert(later == epoch + milliseconds(11500));
+    auto before_epoch = add_duration(epoch - milliseconds(2500), seconds(1));
+    assert(before_epoch == epoch - milliseconds(1500));
+}
+
+static void test_records() {
+    Settings settings;
+    settings.name = "test";
+    assert(settings.retries == 3);
+    assert(settings.verbose);
+    assert(describe_settings(settings) == "test retries=3 verbose=true nickname=- tags=");
+
+    settings.nickname = "t";
+    settings.tags = {"a", "b"};
+    settings.verbose = false;
+    assert(describe_settings(settings) == "test retries=3 verbose=false nickname=t tags=a,b");
+}
+
+static void test_enums() {
+    assert(next_color(Color::Red) == Color::Green);
+    assert(next_color(Color::Blue) == Color::Red);
+
+    auto point = scale_shape(Shape{Shape::Point{}}, 2.0);
+    assert(std::holds_alternative<Shape::Point>(point.variant));
+
+    auto rect = scale_shape(Shape{Shape::Rectangle{1.5, 2.0}}, 2.0);
+    auto& scaled = std::get<Shape::Rectangle>(rect.variant);
+    assert(scaled.width == 3.0);
+    assert(scaled.height == 4.0);
+
+    auto labelled = scale_shape(Shape{Shape::Labelled{"box", Color::Blue}}, 3.0);
+    auto& label = std::get<Shape::Labelled>(labelled.variant);
+    assert(label.v0 == "box x3");
+    assert(label.v1 == Color::Blue);
+}
+
+static void test_errors() {
+    assert(parse_number("42") == 42);
+    try {
+        parse_number("");
+        assert(false);
+    } catch (const ParseError::Empty& e) {
+        assert(std::string(e.what()) == "empty input");
+    }
+    try {
+        parse_number("abc");
+        assert(false);
+    } catch (const ParseError& e) {
+        // Flat errors carry the message from the Rust `Display` implementation
+        assert(dynamic_cast<const ParseError::Invalid*>(&e) != nullptr);
+        assert(std::string(e.what()) == "invalid number: abc");
+    }
+
+    Account account("alice");
+    try {
+        account.withdraw(10);
+        assert(false);
+    } catch (const AccountError::InsufficientFunds& e) {
+        assert(e.balance == 0);
+        assert(e.requested == 10);
+    }
+    try {
+        account.close();
+        assert(false);
+    } catch (const AccountError::Closed&) {
+    }
+}
+
+static void test_objects() {
+    Account alice("alice");
+    assert(alice.balance() == 0);
+    alice.deposit(100);
+    assert(alice.withdraw(30) == 70);
+
+    // Copies refer to the same Rust object
+    Account copy = alice;
+    copy.deposit(5);
+    assert(alice.balance() == 75);
+
+    Account bob = Account::with_balance("bob", 200);
+    assert(bob.to_string() == "Account(bob)");
+    assert(bob != alice);
+    assert(bob == Account::with_balance("bob", 0));
+
+    auto richest_account = richest({alice, bob});
+    assert(richest_account.has_value());
+    assert(*richest_account == bob);
+    assert(!richest({}).has_value());
+
+    Account moved = std::move(bob);
+    assert(moved.balance() == 200);
+    moved = alice;
+    assert(moved.balance() == 75);
+}
+
+static void test_consuming_methods() {
+    Account account = Account::with_balance("carol", 30);
+    {
+        Account copy = account;
+        // The Rust object can't be consumed while a copy refers to it, but the copy is still usable
+        try {
+            std::move(copy).into_balance();
+            assert(false);
+        } catch (const RustPanic&) {
+            assert(false);
+        } catch (const InternalError& e) {
+            assert(std::string(e.what()).find("other references") != std::string::npos);
+        }
+        assert(copy.balance() == 30);
+    }
+    assert(std::move(account).into_balance() == 30);
+    try {
+        account.balance();
+        assert(false);
+    } catch (const std::logic_error& e) {
+        assert(std::string(e.what()) == "Account object has been moved from or consumed");
+    }
+}
+
+int main() {
+    test_builtin_types();
+    test_time();
+    test_records();
+    test_enums();
+    test_errors();
+    test_objects();
+    test_consuming_methods();
+    std::cout << "All C++ tests passed" << std::endl;
+    return EXIT_SUCCESS;
+}
@@ -0,0 +1,103 @@
+using System;
+using System.Collections.Generic;
+using System.Linq;
+using uniffi.uniffi_cpp;
+
+static void Assert(bool condition, string message)
+{
+    if (!condition)
+    {
+        throw new Exception(message);
+    }
+}
+
+static T AssertThrows<T>(Action action, string message) where T : Exception
+{
+    try
+    {
+        action();
+    }
+    catch (T e)
+    {
+        return e;
+    }
+    throw new Exception(message);
+}
+
+// Builtin types
+Assert(UniffiCppMethods.Greet("C#") == "Hello, C#!", "greet");
+Assert(UniffiCppMethods.FirstWord("hello world") == "hello", "first_word");
+Assert(UniffiCppMethods.FirstWord("   ") == null, "first_word of blank text");
+Assert(UniffiCppMethods.SumAll(new List<long> { 1, 2, 3, -10 }) == -4, "sum_all");
+var lengths = UniffiCppMethods.WordLengths(new List<string> { "one", "three", "héllo" });
+Assert(lengths.Count == 3 && lengths["three"] == 5 && lengths["héllo"] == 5, "word_lengths");
+Assert(UniffiCppMethods.ReverseBytes(new byte[] { 1, 2, 3 }).SequenceEqual(new byte[] { 3, 2, 1 }), "reverse_bytes");
+Assert(UniffiCppMethods.IsPositive(0.5) && !UniffiCppMethods.IsPositive(-0.5), "is_positive");
+
+// Timestamps and durations
+var time = DateTime.UnixEpoch.AddMilliseconds(10000);
+Assert(UniffiCppMethods.AddDuration(time, TimeSpan.FromMilliseconds(1500)) == DateTime.UnixEpoch.AddMilliseconds(11500), "add_duration");
+
+// Records are `record class` types, with value equality
+var settings = new Settings("test", 3, true, null, new List<string> { "a", "b" });
+Assert(UniffiCppMethods.DescribeSettings(settings) == "test retries=3 verbose=true nickname=- tags=a,b", "describe_settings");
+Assert(UniffiCppMethods.DescribeSettings(settings with { Verbose = false, Nickname = "t" }) == "test retries=3 verbose=false nickname=t tags=a,b", "describe_settings with");
+var tags = new List<string>();
+Assert(new Settings("x", 1, false, null, tags) == new Settings("x", 1, false, null, tags), "record equality");
+
+// Enums without fields are C# enums, enums with fields are sealed record hierarchies
+Assert(UniffiCppMethods.NextColor(Color.Red) == Color.Green, "next_color(Red)");
+Assert(UniffiCppMethods.NextColor(Color.Blue) == Color.Red, "next_color(Blue)");
+Assert(UniffiCppMethods.ScaleShape(new Shape.Point(), 2) is Shape.Point, "scale_shape(Point)");
+Assert(UniffiCppMethods.ScaleShape(new Shape.Rectangle(1.5, 2), 2) == new Shape.Rectangle(3, 4), "scale_shape(Rectangle)");
+var scaled = UniffiCppMethods.ScaleShape(new Shape.Labelled("box", Color.Green), 2);
+Assert(scaled switch
+{
+    Shape.Labelled(var label, var color) => label == "box x2" && color == Color.Green,
+    _ => false,
+}, "scale_shape(Labelled)");
+
+// Errors are exceptions, with a nested class for each variant
+Assert(UniffiCppMethods.ParseNumber("42") == 42, "parse_number");
+AssertThrows<ParseException.Empty>(() => UniffiCppMethods.ParseNumber(""), "parse_number should fail on empty input");
+var invalid = AssertThrows<ParseException>(() => UniffiCppMethods.ParseNumber("abc"), "parse_number should fail on invalid input");
+Assert(invalid is ParseException.Invalid && invalid.Message == "invalid number: abc", "ParseException.Invalid");
+
+// Objects hold a SafeHandle to the Rust object
+using (var account = Account.WithBalance("alice", 100))
+{
+    Assert(account.Balance() == 100, "balance");
+    account.Deposit(50);
+    Assert(account.Withdraw(30) == 120, "withdraw");
+    var insufficient = AssertThrows<AccountException.InsufficientFunds>(() => account.Withdraw(1000), "withdraw should fail");
+    Assert(insufficient.Balance == 120 && insufficient.Requested == 1000, "InsufficientFunds fields");
+    AssertThrows<AccountException.Closed>(() => account.Close(), "close should fail");
+    Assert(account.ToString() == "Account(alice)", "ToString()");
+
+    using var bob = new Account("bob");
+    var other = new Account("alice");
+    Assert(account.Equals(other) && !account.Equals(bob), "Equals()");
+    Assert(UniffiCppMethods.Richest(new List<Account> { other, account })?.ToString() == "Account(alice)", "richest");
+    Assert(UniffiCppMethods.Richest(new List<Account>()) == null, "richest of no accounts");
+
+    // Disposing twice is fine, but the object can't be used afterwards.
+    other.Dispose();
+    other.Dispose();
+    AssertThrows<ObjectDisposedException>(() => other.Balance(), "disposed objects can't be used");
+}
+
+// Methods that take `self` by value consume the object
+var savings = Account.WithBalance("carol", 30);
+Assert(savings.IntoBalance() == 30, "into_balance");
+AssertThrows<ObjectDisposedException>(() => savings.Balance(), "consumed objects can't be used");
+var shared = Account.WithBalance("dave", 10);
+var richestAccount = UniffiCppMethods.Richest(new List<Account> { shared })!;
+var shareError = AssertThrows<InternalException>(() => shared.IntoBalance(), "shared objects can't be consumed");
+Assert(!(shareError is RustPanicException), "failing to consume a shared object isn't a panic");
+Assert(shareError.Message.Contains("other references"), "into_balance of a shared object");
+Assert(shared.Balance() == 10, "objects aren't consumed if the method can't take them");
+shared.Dispose();
+Assert(richestAccount.IntoBalance() == 10, "into_balance after the other reference is gone");
+
+// Panics are InternalExceptions
+AssertThrows<InternalException>(() => UniffiCppMethods.SumAll(new List<long> { long.MaxValue, 1 }), "sum_all should overflow");
@@ -0,0 +1,111 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+const assert = require("assert");
+const {
+  Account,
+  AccountError,
+  Color,
+  ParseError,
+  addDuration,
+  describeSettings,
+  firstWord,
+  greet,
+  isPositive,
+  nextColor,
+  parseNumber,
+  reverseBytes,
+  richest,
+  scaleShape,
+  sumAll,
+  wordLengths,
+  UniffiInternalError,
+  UniffiRustPanic,
+} = require("./uniffi_cpp");
+
+// Builtin types
+assert.strictEqual(greet("JavaScript"), "Hello, JavaScript!");
+assert.strictEqual(greet(""), "Hello, !");
+assert.strictEqual(firstWord("hello world"), "hello");
+assert.strictEqual(firstWord("   "), undefined);
+assert.strictEqual(sumAll([1n, 2n, 3n, -10n]), -4n);
+assert.strictEqual(sumAll([]), 0n);
+const lengths = wordLengths(["one", "three", "héllo"]);
+assert.strictEqual(lengths.size, 3);
+assert.strictEqual(lengths.get("three"), 5);
+assert.strictEqual(lengths.get("héllo"), 5);
+assert.deepStrictEqual(reverseBytes(new Uint8Array([1, 2, 3])), new Uint8Array([3, 2, 1]));
+assert.ok(isPositive(0.5));
+assert.ok(!isPositive(-0.5));
+assert.throws(() => sumAll([1]), RangeError);
+
+// Timestamps and durations
+assert.strictEqual(addDuration(new Date(10000), 1500).getTime(), 11500);
+assert.strictEqual(addDuration(new Date(-2500), 1000).getTime(), -1500);
+assert.throws(() => addDuration(new Date(0), -1), RangeError);
+
+// Records, with default values for missing fields
+assert.strictEqual(describeSettings({ name: "test", tags: [] }), "test retries=3 verbose=true nickname=- tags=");
+assert.strictEqual(
+  describeSettings({ name: "test", verbose: false, nickname: "t", tags: ["a", "b"] }),
+  "test retries=3 verbose=false nickname=t tags=a,b",
+);
+
+// Enums
+assert.strictEqual(nextColor(Color.Red), Color.Green);
+assert.strictEqual(nextColor(Color.Blue), Color.Red);
+assert.deepStrictEqual(scaleShape({ tag: "Point" }, 2), { tag: "Point" });
+assert.deepStrictEqual(scaleShape({ tag: "Rectangle", width: 1.5, height: 2 }, 2), {
+  tag: "Rectangle",
+  width: 3,
+  height: 4,
+});
+assert.deepStrictEqual(scaleShape({ tag: "Labelled", v0: "box", v1: Color.Green }, 2), {
+  tag: "Labelled",
+  v0: "box x2",
+  v1: Color.Green,
+});
+
+// Errors
+assert.strictEqual(parseNumber("42"), 42);
+assert.throws(() => parseNumber(""), ParseError.Empty);
+assert.throws(() => parseNumber("abc"), (e) => e instanceof ParseError.Invalid && e.message === "invalid number: abc");
+
+// Objects
+const account = Account.withBalance("alice", 100n);
+assert.strictEqual(account.balance(), 100n);
+account.deposit(50n);
+assert.strictEqual(account.withdraw(30n), 120n);
+assert.throws(
+  () => account.withdraw(1000n),
+  (e) => e instanceof AccountError.InsufficientFunds && e.balance === 120n && e.requested === 1000n,
+);
+assert.throws(() => account.close(), AccountError.Closed);
+assert.strictEqual(account.toString(), "Account(alice)");
+
+const other = new Account("alice");
+assert.ok(account.equals(other));
+assert.ok(!account.equals(new Account("bob")));
+assert.strictEqual(richest([other, account]).toString(), "Account(alice)");
+assert.strictEqual(richest([]), undefined);
+
+other.destroy();
+other.destroy();
+assert.throws(() => other.balance(), /destroyed/);
+
+// Methods that take `self` by value consume the object
+const savings = Account.withBalance("carol", 30n);
+assert.strictEqual(savings.intoBalance(), 30n);
+assert.throws(() => savings.balance(), /destroyed or consumed/);
+assert.throws(() => savings.intoBalance(), /destroyed or consumed/);
+// The Rust object can't be consumed while another object refers to it, but this one is still usable
+const shared = Account.withBalance("dave", 10n);
+const richestAccount = richest([shared]);
+assert.throws(
+  () => shared.intoBalance(),
+  (e) => e instanceof UniffiInternalError && !(e instanceof UniffiRustPanic) && /other references/.test(e.message),
+);
+assert.strictEqual(shared.balance(), 10n);
+shared.destroy();
+assert.strictEqual(richestAccount.intoBalance(), 10n);
@@ -0,0 +1,5 @@
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_cpp.cpp",
+    "tests/bindings/test_cpp.js",
+    "tests/bindings/test_cpp.cs",
+);
@@ -0,0 +1,56 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::Arc;
+
+/// An object whose foreign wrappers are reused when the same Rust object is returned again
+#[derive(uniffi::Object)]
+#[uniffi(stable_identity)]
+pub struct IdentityNode {
+    name: String,
+}
+
+#[uniffi::export]
+impl IdentityNode {
+    #[uniffi::constructor]
+    fn new(name: String) -> Arc<Self> {
+        Arc::new(Self { name })
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_self(self: Arc<Self>) -> Arc<Self> {
+        self
+    }
+}
+
+/// Keeps a reference to an `IdentityNode`, so it can be returned after the foreign side destroyed
+/// its object
+#[derive(uniffi::Object)]
+pub struct IdentityNodeHolder {
+    node: Arc<IdentityNode>,
+}
+
+#[uniffi::export]
+impl IdentityNodeHolder {
+    #[uniffi::constructor]
+    fn new(node: Arc<IdentityNode>) -> Arc<Self> {
+        Arc::new(Self { node })
+    }
+
+    fn node(&self) -> Arc<IdentityNode> {
+        self.node.clone()
+    }
+}
+
+/// Return the same `IdentityNode` twice
+#[uniffi::export]
+fn identity_node_pair(name: String) -> Vec<Arc<IdentityNode>> {
+    let node = IdentityNode::new(name);
+    vec![node.clone(), node]
+}
+
+uniffi::setup_scaffolding!();
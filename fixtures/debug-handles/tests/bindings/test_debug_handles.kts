@@ -0,0 +1,21 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.fixture.debug_handles.*;
+
+val identityNode = IdentityNode("root")
+assert(identityNode.getSelf() === identityNode)
+val (firstNode, secondNode) = identityNodePair("pair")
+assert(firstNode === secondNode)
+assert(firstNode !== identityNode)
+assert(firstNode.name() == "pair")
+val identityNodeHolder = IdentityNodeHolder(identityNode)
+assert(identityNodeHolder.node() === identityNode)
+identityNode.destroy()
+// Rust still has the object, but lifting it again creates a new Kotlin object once the old one
+// is destroyed
+val reliftedNode = identityNodeHolder.node()
+assert(reliftedNode !== identityNode)
+assert(reliftedNode.name() == "root")
+assert(identityNodeHolder.node() === reliftedNode)
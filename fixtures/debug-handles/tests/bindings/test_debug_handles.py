@@ -0,0 +1,16 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+from uniffi_debug_handles import *
+
+identity_node = IdentityNode("root")
+assert(identity_node.get_self() is identity_node)
+[first_node, second_node] = identity_node_pair("pair")
+assert(first_node is second_node)
+assert(first_node is not identity_node)
+assert(first_node.name() == "pair")
+identity_node_holder = IdentityNodeHolder(identity_node)
+assert(identity_node_holder.node() is identity_node)
+assert(identity_node_holder.node().get_self() is identity_node)
+assert(identity_node.name() == "root")
@@ -0,0 +1,15 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi_debug_handles
+
+let identityNode = IdentityNode(name: "root")
+assert(identityNode.getSelf() === identityNode)
+let identityNodes = identityNodePair(name: "pair")
+assert(identityNodes[0] === identityNodes[1])
+assert(identityNodes[0] !== identityNode)
+assert(identityNodes[0].name() == "pair")
+let identityNodeHolder = IdentityNodeHolder(node: identityNode)
+assert(identityNodeHolder.node() === identityNode)
+assert(identityNode.name() == "root")
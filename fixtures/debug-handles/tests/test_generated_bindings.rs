@@ -0,0 +1,5 @@
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_debug_handles.kts",
+    "tests/bindings/test_debug_handles.swift",
+    "tests/bindings/test_debug_handles.py",
+);
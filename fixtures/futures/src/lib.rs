@@ -190,6 +190,12 @@ impl Megaphone {
             Ok(42)
         }
     }
+
+    /// A sync method that blocks, which the bindings call from a background thread.
+    #[uniffi::method(blocking)]
+    pub fn say_after_blocking(&self, ms: u16, who: String) -> String {
+        say_after_blocking(ms, who).to_uppercase()
+    }
 }
 
 // The async_runtime attribute used to error when *any* function in the impl block was not async,
@@ -218,6 +224,78 @@ pub async fn say_after_with_tokio(ms: u16, who: String) -> String {
     format!("Hello, {who} (with Tokio)!")
 }
 
+// Say something after a certain amount of time, by using smol's `Timer`, which needs smol's
+// reactor to be running.
+#[uniffi::export(async_runtime = "smol")]
+pub async fn say_after_with_smol(ms: u16, who: String) -> String {
+    smol::Timer::after(Duration::from_millis(ms.into())).await;
+
+    format!("Hello, {who} (with smol)!")
+}
+
+/// A custom async runtime, which runs futures on a single-threaded executor.
+pub struct SingleThreadRuntime;
+
+impl uniffi::AsyncRuntime for SingleThreadRuntime {
+    type Future<F> = futures::future::RemoteHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static;
+
+    fn wrap<F>(future: F) -> Self::Future<F>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        use futures::{executor::ThreadPool, FutureExt};
+        use once_cell::sync::Lazy;
+
+        static EXECUTOR: Lazy<ThreadPool> = Lazy::new(|| {
+            ThreadPool::builder()
+                .pool_size(1)
+                .name_prefix("single-thread-runtime-")
+                .create()
+                .unwrap()
+        });
+
+        // Dropping the handle cancels the future, like the foreign code cancelling the call.
+        let (future, handle) = future.remote_handle();
+        EXECUTOR.spawn_ok(future);
+        handle
+    }
+}
+
+/// Return the name of the thread that the future runs on, which is the executor thread of
+/// `SingleThreadRuntime`.
+#[uniffi::export(async_runtime = SingleThreadRuntime)]
+pub async fn thread_name_in_custom_runtime() -> String {
+    thread::current().name().unwrap_or_default().to_string()
+}
+
+/// A sync function that blocks the calling thread, which the bindings call from a background
+/// thread.
+#[uniffi::export(blocking)]
+pub fn say_after_blocking(ms: u16, who: String) -> String {
+    thread::sleep(Duration::from_millis(ms.into()));
+
+    format!("Hello, {who} (blocking)!")
+}
+
+/// A blocking function that can throw.
+#[uniffi::export(blocking)]
+pub fn fallible_blocking(do_fail: bool) -> Result<u8, MyError> {
+    if do_fail {
+        Err(MyError::Foo)
+    } else {
+        Ok(42)
+    }
+}
+
+/// A blocking function defined in the UDL file.
+pub fn sleep_blocking(ms: u16) {
+    thread::sleep(Duration::from_millis(ms.into()));
+}
+
 #[derive(uniffi::Record)]
 pub struct MyRecord {
     pub a: String,
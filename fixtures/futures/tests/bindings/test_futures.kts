@@ -223,6 +223,27 @@ runBlocking {
     assertApproximateTime(time, 200, "with tokio runtime")
 }
 
+// Test blocking functions, which run on `Dispatchers.IO`.
+runBlocking {
+    val time = measureTimeMillis {
+        val resultAlice = async { sayAfterBlocking(200U, "Alice") }
+        val resultBob = async { newMegaphone().sayAfterBlocking(200U, "Bob") }
+
+        assert(resultAlice.await() == "Hello, Alice (blocking)!")
+        assert(resultBob.await() == "HELLO, BOB (BLOCKING)!")
+    }
+
+    assertApproximateTime(time, 200, "blocking functions")
+
+    assert(fallibleBlocking(false) == 42.toUByte())
+    try {
+        fallibleBlocking(true)
+        assert(false) // should never be reached
+    } catch (exception: MyException) {
+        assert(true)
+    }
+}
+
 // Test fallible function/method.
 runBlocking {
     val time1 = measureTimeMillis {
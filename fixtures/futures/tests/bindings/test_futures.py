@@ -186,6 +186,50 @@ class TestFutures(unittest.TestCase):
 
         asyncio.run(test())
 
+    def test_with_smol_runtime(self):
+        async def test():
+            t0 = now()
+            result_alice = await say_after_with_smol(200, 'Alice')
+            t1 = now()
+
+            t_delta = (t1 - t0).total_seconds()
+            self.assertGreater(t_delta, 0.2)
+            self.assertEqual(result_alice, 'Hello, Alice (with smol)!')
+
+        asyncio.run(test())
+
+    def test_with_custom_runtime(self):
+        async def test():
+            result = await thread_name_in_custom_runtime()
+            self.assertEqual(result, 'single-thread-runtime-0')
+
+        asyncio.run(test())
+
+    def test_blocking(self):
+        async def test():
+            t0 = now()
+            results = await asyncio.gather(
+                say_after_blocking(200, 'Alice'),
+                say_after_blocking(200, 'Bob'),
+                sleep_blocking(200),
+            )
+            t1 = now()
+
+            # The calls run on executor threads, so they don't block the event loop or each other.
+            t_delta = (t1 - t0).total_seconds()
+            self.assertGreater(t_delta, 0.2)
+            self.assertLess(t_delta, 0.4)
+            self.assertEqual(results, ['Hello, Alice (blocking)!', 'Hello, Bob (blocking)!', None])
+
+            megaphone = new_megaphone()
+            self.assertEqual(await megaphone.say_after_blocking(0, 'Alice'), 'HELLO, ALICE (BLOCKING)!')
+
+            self.assertEqual(await fallible_blocking(False), 42)
+            with self.assertRaises(MyError.Foo):
+                await fallible_blocking(True)
+
+        asyncio.run(test())
+
     def test_fallible(self):
         async def test():
             result = await fallible_me(False)
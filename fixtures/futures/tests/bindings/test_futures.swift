@@ -268,6 +268,30 @@ Task {
 	counter.leave()
 }
 
+// Test blocking functions, which run in detached tasks.
+counter.enter()
+
+Task {
+	let t0 = Date()
+	async let resultAlice = sayAfterBlocking(ms: 200, who: "Alice")
+	async let resultBob = newMegaphone().sayAfterBlocking(ms: 200, who: "Bob")
+	let results = await [resultAlice, resultBob]
+	let t1 = Date()
+
+	let tDelta = DateInterval(start: t0, end: t1)
+	assert(tDelta.duration > 0.2 && tDelta.duration < 0.3)
+	assert(results == ["Hello, Alice (blocking)!", "HELLO, BOB (BLOCKING)!"])
+
+	let result = try await fallibleBlocking(doFail: false)
+	assert(result == 42)
+	do {
+		let _ = try await fallibleBlocking(doFail: true)
+		assertionFailure("fallibleBlocking(doFail: true) should have thrown")
+	} catch MyError.Foo {}
+
+	counter.leave()
+}
+
 // Test fallible function/method…
 // … which doesn't throw.
 counter.enter()
@@ -424,6 +424,8 @@ mod test_metadata {
                 module_path: "uniffi_fixture_metadata".into(),
                 name: "Calculator".into(),
                 imp: ObjectImpl::Struct,
+                thread_affine: false,
+                stable_identity: false,
                 docstring: None,
             },
         );
@@ -524,6 +526,7 @@ mod test_function_metadata {
                 module_path: "uniffi_fixture_metadata".into(),
                 name: "test_func".into(),
                 is_async: false,
+                is_blocking: false,
                 inputs: vec![
                     FnParamMetadata::simple(
                         "person",
@@ -556,6 +559,7 @@ mod test_function_metadata {
                 module_path: "uniffi_fixture_metadata".into(),
                 name: "test_func_no_return".into(),
                 is_async: false,
+                is_blocking: false,
                 inputs: vec![],
                 return_type: None,
                 throws: None,
@@ -575,6 +579,7 @@ mod test_function_metadata {
                 module_path: "uniffi_fixture_metadata".into(),
                 name: "test_func_that_throws".into(),
                 is_async: false,
+                is_blocking: false,
                 inputs: vec![],
                 return_type: Some(Type::Enum {
                     module_path: "uniffi_fixture_metadata".into(),
@@ -600,6 +605,7 @@ mod test_function_metadata {
                 module_path: "uniffi_fixture_metadata".into(),
                 name: "test_func_no_return_that_throws".into(),
                 is_async: false,
+                is_blocking: false,
                 inputs: vec![],
                 return_type: None,
                 throws: Some(Type::Enum {
@@ -624,6 +630,7 @@ mod test_function_metadata {
                 self_name: "Calculator".into(),
                 name: "add".into(),
                 is_async: false,
+                is_blocking: false,
                 inputs: vec![
                     FnParamMetadata::simple("a", Type::UInt8),
                     FnParamMetadata::simple("b", Type::UInt8),
@@ -631,6 +638,7 @@ mod test_function_metadata {
                 return_type: Some(Type::UInt8),
                 throws: None,
                 takes_self_by_arc: false,
+                takes_self_by_value: false,
                 checksum: Some(
                     UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATOR_ADD.checksum(),
                 ),
@@ -647,6 +655,7 @@ mod test_function_metadata {
                 module_path: "uniffi_fixture_metadata".into(),
                 name: "test_async_func".into(),
                 is_async: true,
+                is_blocking: false,
                 inputs: vec![
                     FnParamMetadata::simple(
                         "person",
@@ -681,6 +690,7 @@ mod test_function_metadata {
                 module_path: "uniffi_fixture_metadata".into(),
                 name: "test_async_func_that_throws".into(),
                 is_async: true,
+                is_blocking: false,
                 inputs: vec![],
                 return_type: Some(Type::Enum {
                     module_path: "uniffi_fixture_metadata".into(),
@@ -708,6 +718,7 @@ mod test_function_metadata {
                 self_name: "Calculator".into(),
                 name: "async_sub".into(),
                 is_async: true,
+                is_blocking: false,
                 inputs: vec![
                     FnParamMetadata::simple("a", Type::UInt8),
                     FnParamMetadata::simple("b", Type::UInt8),
@@ -715,6 +726,7 @@ mod test_function_metadata {
                 return_type: Some(Type::UInt8),
                 throws: None,
                 takes_self_by_arc: false,
+                takes_self_by_value: false,
                 checksum: Some(
                     UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATOR_ASYNC_SUB
                         .checksum(),
@@ -732,6 +744,8 @@ mod test_function_metadata {
                 module_path: "uniffi_fixture_metadata".into(),
                 name: "CalculatorDisplay".into(),
                 imp: ObjectImpl::Trait,
+                thread_affine: false,
+                stable_identity: false,
                 docstring: None,
             },
         );
@@ -745,6 +759,8 @@ mod test_function_metadata {
                 module_path: "uniffi_fixture_metadata".into(),
                 name: "TraitWithForeign".into(),
                 imp: ObjectImpl::CallbackTrait,
+                thread_affine: false,
+                stable_identity: false,
                 docstring: None,
             },
         );
@@ -766,9 +782,11 @@ mod test_function_metadata {
                 self_name: "Calculator".into(),
                 name: "get_display".into(),
                 is_async: false,
+                is_blocking: false,
                 inputs: vec![],
                 throws: None,
                 takes_self_by_arc: false,
+                takes_self_by_value: false,
                 checksum: Some(
                     UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATOR_GET_DISPLAY
                         .checksum(),
@@ -822,6 +840,7 @@ mod test_function_metadata {
                 module_path: "uniffi_fixture_metadata".into(),
                 name: "input_trait_with_foreign".into(),
                 is_async: false,
+                is_blocking: false,
                 throws: None,
                 checksum: Some(
                     UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_FUNC_INPUT_TRAIT_WITH_FOREIGN
@@ -862,3 +881,49 @@ mod test_function_metadata {
         );
     }
 }
+
+mod test_embedded_files {
+    use super::*;
+
+    uniffi::embed_source_file!(UNIFFI_META_TEST_EMBEDDED_FILE, 4, b"\x00abc");
+
+    #[test]
+    fn test_embed_source_file() {
+        // The static is only defined with the `embed-sources` feature
+        assert_eq!(&UNIFFI_META_TEST_EMBEDDED_FILE, b"\x00abc");
+    }
+
+    #[test]
+    fn test_udl_source() {
+        // UDL files can be too large for a `MetadataBuffer`, or even a long string
+        let udl = "namespace test {};\n".repeat(5000);
+        check_metadata(
+            &encode_udl_source("uniffi_fixture_metadata", &udl),
+            UdlSource {
+                module_path: "uniffi_fixture_metadata".into(),
+                contents: udl,
+            },
+        );
+    }
+
+    #[test]
+    fn test_config_file() {
+        check_metadata(
+            &encode_config_file(
+                "uniffi_fixture_metadata",
+                Some("[bindings.kotlin]\npackage_name = \"test\"\n"),
+            ),
+            ConfigFile {
+                module_path: "uniffi_fixture_metadata".into(),
+                contents: Some("[bindings.kotlin]\npackage_name = \"test\"\n".into()),
+            },
+        );
+        check_metadata(
+            &encode_config_file("uniffi_fixture_metadata", None),
+            ConfigFile {
+                module_path: "uniffi_fixture_metadata".into(),
+                contents: None,
+            },
+        );
+    }
+}
@@ -2,7 +2,12 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
 
 mod callback_interface;
 
@@ -374,4 +379,188 @@ impl ObjectWithDefaults {
     }
 }
 
+/// Test `&mut self` methods on an object stored behind a `Mutex`
+#[derive(uniffi::Object)]
+#[uniffi(mutable)]
+pub struct MutableCounter {
+    count: u32,
+}
+
+#[uniffi::export]
+impl MutableCounter {
+    #[uniffi::constructor]
+    fn new() -> Self {
+        Self { count: 0 }
+    }
+
+    fn increment(&mut self) -> u32 {
+        self.count += 1;
+        self.count
+    }
+
+    fn get(&self) -> u32 {
+        self.count
+    }
+}
+
+#[uniffi::export]
+fn counter_value(counter: &Mutex<MutableCounter>) -> u32 {
+    counter.lock().unwrap().count
+}
+
+/// Test `&mut self` methods on an object stored behind a `RwLock`
+#[derive(uniffi::Object)]
+#[uniffi(mutable = "rwlock")]
+pub struct RwLockedList {
+    items: Vec<String>,
+}
+
+#[uniffi::export]
+impl RwLockedList {
+    #[uniffi::constructor]
+    fn new() -> Self {
+        Self { items: vec![] }
+    }
+
+    fn push(&mut self, item: String) {
+        self.items.push(item);
+    }
+
+    fn joined(&self, sep: String) -> String {
+        self.items.join(&sep)
+    }
+
+    fn into_items(self) -> Vec<String> {
+        self.items
+    }
+}
+
+/// Test an object that isn't `Send` or `Sync`
+#[derive(uniffi::Object)]
+#[uniffi(thread_affine)]
+pub struct ThreadAffineCounter {
+    count: Rc<Cell<u32>>,
+}
+
+#[uniffi::export]
+impl ThreadAffineCounter {
+    #[uniffi::constructor]
+    fn new() -> Self {
+        Self {
+            count: Rc::new(Cell::new(0)),
+        }
+    }
+
+    fn increment(&self) -> u32 {
+        self.count.set(self.count.get() + 1);
+        self.count.get()
+    }
+
+    /// Throwing version of `increment()`, which lets Swift catch calls from the wrong thread
+    fn try_increment(&self) -> Result<u32, BasicError> {
+        Ok(self.increment())
+    }
+}
+
+/// Test an object whose foreign wrappers are reused when the same Rust object is returned again
+#[derive(uniffi::Object)]
+#[uniffi(stable_identity)]
+pub struct IdentityNode {
+    name: String,
+}
+
+#[uniffi::export]
+impl IdentityNode {
+    #[uniffi::constructor]
+    fn new(name: String) -> Arc<Self> {
+        Arc::new(Self { name })
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_self(self: Arc<Self>) -> Arc<Self> {
+        self
+    }
+}
+
+/// Keeps a reference to an `IdentityNode`, so it can be returned after the foreign side destroyed
+/// its object
+#[derive(uniffi::Object)]
+pub struct IdentityNodeHolder {
+    node: Arc<IdentityNode>,
+}
+
+#[uniffi::export]
+impl IdentityNodeHolder {
+    #[uniffi::constructor]
+    fn new(node: Arc<IdentityNode>) -> Arc<Self> {
+        Arc::new(Self { node })
+    }
+
+    fn node(&self) -> Arc<IdentityNode> {
+        self.node.clone()
+    }
+}
+
+/// Return the same `IdentityNode` twice
+#[uniffi::export]
+fn identity_node_pair(name: String) -> Vec<Arc<IdentityNode>> {
+    let node = IdentityNode::new(name);
+    vec![node.clone(), node]
+}
+
+/// Log a message at each level, to test the log bridge
+#[uniffi::export]
+fn log_at_each_level(message: String) {
+    log::error!("{message}");
+    log::warn!("{message}");
+    log::info!("{message}");
+    log::debug!("{message}");
+    log::trace!("{message}");
+}
+
+/// Call a foreign closure
+#[uniffi::export]
+fn apply_closure(f: Box<dyn Fn(u32, String) -> String + Send + Sync>, count: u32) -> String {
+    f(count, "apple".to_string())
+}
+
+/// Call a foreign closure from a thread that Rust spawned
+///
+/// Returns `None` if the closure threw, which panics the thread.
+#[uniffi::export]
+fn apply_closure_on_thread(
+    f: Box<dyn Fn(u32, String) -> String + Send + Sync>,
+    count: u32,
+) -> Option<String> {
+    std::thread::spawn(move || f(count, "apple".to_string()))
+        .join()
+        .ok()
+}
+
+/// Call a foreign closure that doesn't return anything for each item
+#[uniffi::export]
+fn for_each_item(items: Vec<String>, f: Arc<dyn Fn(String) + Send + Sync>) {
+    for item in items {
+        f(item)
+    }
+}
+
+/// Return a Rust closure to the foreign side
+#[uniffi::export]
+fn make_adder(amount: i32) -> Box<dyn Fn(i32) -> i32 + Send + Sync> {
+    Box::new(move |value| value + amount)
+}
+
+/// Pass a foreign closure back to the foreign side, wrapped in a Rust one
+#[uniffi::export]
+fn compose(
+    f: Box<dyn Fn(i32) -> i32 + Send + Sync>,
+    g: Box<dyn Fn(i32) -> i32 + Send + Sync>,
+) -> Box<dyn Fn(i32) -> i32 + Send + Sync> {
+    Box::new(move |value| g(f(value)))
+}
+
 uniffi::include_scaffolding!("proc-macro");
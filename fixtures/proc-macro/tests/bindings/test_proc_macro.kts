@@ -55,6 +55,54 @@ assert(doubleWithDefault() == 42)
 val objWithDefaults = ObjectWithDefaults()
 assert(objWithDefaults.addToNum() == 42)
 
+val counter = MutableCounter()
+assert(counter.increment() == 1u)
+assert(counter.increment() == 2u)
+assert(counter.get() == 2u)
+assert(counterValue(counter) == 2u)
+
+val lockedList = RwLockedList()
+lockedList.push("a")
+lockedList.push("b")
+assert(lockedList.joined(", ") == "a, b")
+assert(lockedList.intoItems() == listOf("a", "b"))
+try {
+    lockedList.joined(", ")
+    throw RuntimeException("Should have thrown")
+} catch (e: IllegalStateException) {
+    assert(e.message!!.contains("already been consumed"))
+}
+
+val threadAffineCounter = ThreadAffineCounter()
+assert(threadAffineCounter.increment() == 1u)
+val otherThread = Thread {
+    try {
+        threadAffineCounter.increment()
+        throw RuntimeException("Should have thrown")
+    } catch (e: InternalException) {
+        assert(e.message!!.contains("can only be used from the thread that created it"))
+    }
+}
+otherThread.start()
+otherThread.join()
+assert(threadAffineCounter.increment() == 2u)
+threadAffineCounter.destroy()
+
+val identityNode = IdentityNode("root")
+assert(identityNode.getSelf() === identityNode)
+val (firstNode, secondNode) = identityNodePair("pair")
+assert(firstNode === secondNode)
+assert(firstNode !== identityNode)
+assert(firstNode.name() == "pair")
+val identityNodeHolder = IdentityNodeHolder(identityNode)
+assert(identityNodeHolder.node() === identityNode)
+identityNode.destroy()
+// Rust still has the object, but lifting it again creates a new Kotlin object once the old one
+// is destroyed
+val reliftedNode = identityNodeHolder.node()
+assert(reliftedNode !== identityNode)
+assert(reliftedNode.name() == "root")
+
 // Traits
 
 val traitImpl = obj.getTrait(null)
@@ -118,3 +166,42 @@ val eb = MixedEnum.Both("hi", 2)
 val (s, i) = eb
 assert(s == "hi")
 assert(i == 2L)
+
+// closures
+assert(applyClosure({ count, fruit -> "$count ${fruit}s" }, 3u) == "3 apples")
+val seen = mutableListOf<String>()
+forEachItem(listOf("a", "b")) { seen.add(it) }
+assert(seen == listOf("a", "b"))
+val addTwo = makeAdder(2)
+assert(addTwo(40) == 42)
+assert(compose(addTwo, { it * 10 })(1) == 30)
+try {
+    applyClosure({ _, _ -> throw RuntimeException("no fruit") }, 3u)
+    throw RuntimeException("Should have thrown")
+} catch (e: RustPanic) {
+    assert(e.message!!.contains("Foreign closure failed"))
+}
+assert(applyClosureOnThread({ count, fruit -> "$count ${fruit}s" }, 3u) == "3 apples")
+assert(applyClosureOnThread({ _, _ -> throw RuntimeException("no fruit") }, 3u) == null)
+
+// log bridge
+val logRecords = mutableListOf<java.util.logging.LogRecord>()
+val rustLogger = java.util.logging.Logger.getLogger("uniffi_proc_macro")
+rustLogger.level = java.util.logging.Level.ALL
+rustLogger.addHandler(object : java.util.logging.Handler() {
+    override fun publish(record: java.util.logging.LogRecord) { logRecords.add(record) }
+    override fun flush() {}
+    override fun close() {}
+})
+RustLogBridge.install(RustLogLevel.INFO)
+logAtEachLevel("hello")
+assert(logRecords.map { it.level } == listOf(
+    java.util.logging.Level.SEVERE,
+    java.util.logging.Level.WARNING,
+    java.util.logging.Level.INFO,
+))
+assert(logRecords.all { it.message == "hello" })
+logRecords.clear()
+RustLogBridge.setLevel(RustLogLevel.OFF)
+logAtEachLevel("off")
+assert(logRecords.isEmpty())
@@ -2,6 +2,9 @@
 # License, v. 2.0. If a copy of the MPL was not distributed with this
 # file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+import logging
+import threading
+
 from proc_macro import *
 
 one = make_one(123)
@@ -77,6 +80,45 @@ assert(double_with_default() == 42)
 obj_with_defaults = ObjectWithDefaults()
 assert(obj_with_defaults.add_to_num() == 42)
 
+counter = MutableCounter()
+assert(counter.increment() == 1)
+assert(counter.increment() == 2)
+assert(counter.get() == 2)
+assert(counter_value(counter) == 2)
+
+locked_list = RwLockedList()
+locked_list.push("a")
+locked_list.push("b")
+assert(locked_list.joined(", ") == "a, b")
+assert(locked_list.into_items() == ["a", "b"])
+try:
+    locked_list.joined(", ")
+    raise Exception("Should have raised")
+except RuntimeError as e:
+    assert("already been consumed" in str(e))
+
+thread_affine_counter = ThreadAffineCounter()
+assert(thread_affine_counter.increment() == 1)
+assert(thread_affine_counter.increment() == 2)
+assert("created it" in ThreadAffineCounter.__doc__)
+def increment_from_other_thread():
+    try:
+        thread_affine_counter.increment()
+        raise Exception("Should have raised")
+    except InternalError as e:
+        assert("can only be used from the thread that created it" in str(e))
+other_thread = threading.Thread(target=increment_from_other_thread)
+other_thread.start()
+other_thread.join()
+assert(thread_affine_counter.increment() == 3)
+
+identity_node = IdentityNode("root")
+assert(identity_node.get_self() is identity_node)
+[first_node, second_node] = identity_node_pair("pair")
+assert(first_node is second_node)
+assert(first_node is not identity_node)
+assert(first_node.name() == "pair")
+
 # Traits
 class PyTestCallbackInterface(TestCallbackInterface):
     def do_nothing(self):
@@ -144,3 +186,53 @@ assert(MixedEnum.BOTH("hello", 1)[-1] == 1)
 assert(str(MixedEnum.BOTH("hello", 2)) == "MixedEnum.BOTH('hello', 2)")
 
 assert(get_mixed_enum(MixedEnum.ALL("string", 2)).is_all())
+
+# closures
+assert apply_closure(lambda count, fruit: f"{count} {fruit}s", 3) == "3 apples"
+seen = []
+for_each_item(["a", "b"], seen.append)
+assert seen == ["a", "b"]
+add_two = make_adder(2)
+assert add_two(40) == 42
+assert compose(add_two, lambda v: v * 10)(1) == 30
+def failing_closure(count, fruit):
+    raise ValueError("no fruit")
+try:
+    apply_closure(failing_closure, 3)
+    raise Exception("Should have raised")
+except RustPanic as e:
+    assert("Foreign closure failed" in str(e))
+assert apply_closure_on_thread(lambda count, fruit: f"{count} {fruit}s", 3) == "3 apples"
+assert apply_closure_on_thread(failing_closure, 3) is None
+
+# log bridge
+class RecordingHandler(logging.Handler):
+    def __init__(self):
+        super().__init__(logging.NOTSET)
+        self.records = []
+
+    def emit(self, record):
+        self.records.append((record.name, record.levelno, record.getMessage()))
+
+handler = RecordingHandler()
+rust_logger = logging.getLogger("uniffi_proc_macro")
+rust_logger.addHandler(handler)
+rust_logger.setLevel(1)
+install_rust_log_bridge(logging.INFO)
+log_at_each_level("hello")
+assert handler.records == [
+    ("uniffi_proc_macro", logging.ERROR, "hello"),
+    ("uniffi_proc_macro", logging.WARNING, "hello"),
+    ("uniffi_proc_macro", logging.INFO, "hello"),
+]
+handler.records.clear()
+set_rust_log_level(logging.DEBUG - 5)
+log_at_each_level("bye")
+# The scaffolding also logs each call at the debug level
+assert [level for (_, level, message) in handler.records if message == "bye"] == [
+    logging.ERROR, logging.WARNING, logging.INFO, logging.DEBUG, logging.DEBUG - 5
+]
+set_rust_log_level(logging.CRITICAL)
+handler.records.clear()
+log_at_each_level("off")
+assert handler.records == []
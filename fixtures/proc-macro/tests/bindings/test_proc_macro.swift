@@ -67,6 +67,50 @@ assert(doubleWithDefault() == 42)
 let objWithDefaults = ObjectWithDefaults()
 assert(objWithDefaults.addToNum() == 42)
 
+let counter = MutableCounter()
+assert(counter.increment() == 1)
+assert(counter.increment() == 2)
+assert(counter.get() == 2)
+assert(counterValue(counter: counter) == 2)
+
+let lockedList = RwLockedList()
+try! lockedList.push(item: "a")
+try! lockedList.push(item: "b")
+assert(try! lockedList.joined(sep: ", ") == "a, b")
+assert(try! lockedList.intoItems() == ["a", "b"])
+do {
+    _ = try lockedList.joined(sep: ", ")
+    fatalError("Should have thrown")
+} catch let e as ProcMacroObjectConsumed {
+    assert(e.typeName == "RwLockedList")
+}
+
+let threadAffineCounter = ThreadAffineCounter()
+assert(threadAffineCounter.increment() == 1)
+assert(threadAffineCounter.increment() == 2)
+// Calling `increment()` from another thread would be a fatal error, use the throwing version.
+var otherThreadError: Error? = nil
+let otherThreadDone = DispatchSemaphore(value: 0)
+Thread {
+    do {
+        _ = try threadAffineCounter.tryIncrement()
+    } catch {
+        otherThreadError = error
+    }
+    otherThreadDone.signal()
+}.start()
+otherThreadDone.wait()
+assert(otherThreadError != nil)
+assert(otherThreadError!.localizedDescription.contains("can only be used from the thread that created it"))
+assert(try! threadAffineCounter.tryIncrement() == 3)
+
+let identityNode = IdentityNode(name: "root")
+assert(identityNode.getSelf() === identityNode)
+let identityNodes = identityNodePair(name: "pair")
+assert(identityNodes[0] === identityNodes[1])
+assert(identityNodes[0] !== identityNode)
+assert(identityNodes[0].name() == "pair")
+
 // Traits
 
 class SwiftTestCallbackInterface : TestCallbackInterface {
@@ -139,3 +183,31 @@ switch MixedEnum.all(s: "string", i: 2) {
     default:
         assert(false)
 }
+
+// closures
+assert(applyClosure(f: { count, fruit in "\(count) \(fruit)s" }, count: 3) == "3 apples")
+var seen: [String] = []
+forEachItem(items: ["a", "b"], f: { seen.append($0) })
+assert(seen == ["a", "b"])
+let addTwo = makeAdder(amount: 2)
+assert(addTwo(40) == 42)
+assert(compose(f: addTwo, g: { $0 * 10 })(1) == 30)
+assert(applyClosureOnThread(f: { count, fruit in "\(count) \(fruit)s" }, count: 3) == "3 apples")
+
+// log bridge
+var logRecords: [(ProcMacroRustLogLevel, String, String)] = []
+ProcMacroRustLogBridge.install(level: .info, handler: { level, target, message in
+    logRecords.append((level, target, message))
+})
+logAtEachLevel(message: "hello")
+assert(logRecords.map { $0.0 } == [.error, .warn, .info])
+assert(logRecords.allSatisfy { $0.1 == "uniffi_proc_macro" && $0.2 == "hello" })
+logRecords.removeAll()
+ProcMacroRustLogBridge.setLevel(.trace)
+logAtEachLevel(message: "trace")
+// The scaffolding also logs each call at the debug level
+assert(logRecords.filter { $0.2 == "trace" }.map { $0.0 } == [.error, .warn, .info, .debug, .trace])
+logRecords.removeAll()
+ProcMacroRustLogBridge.setLevel(.off)
+logAtEachLevel(message: "off")
+assert(logRecords.isEmpty)
@@ -0,0 +1,95 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+#[derive(uniffi::Enum, Clone, Debug, PartialEq)]
+pub enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+#[derive(uniffi::Enum, Clone, Debug, PartialEq)]
+pub enum Shape {
+    Point,
+    Circle { radius: f64 },
+    Polygon { points: Vec<Point> },
+    Labelled(String, Option<Color>),
+}
+
+#[derive(uniffi::Record, Clone, Debug, PartialEq)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(uniffi::Record, Clone, Debug, PartialEq)]
+pub struct Drawing {
+    name: String,
+    background: Option<Color>,
+    shapes: Vec<Shape>,
+    layers: HashMap<String, Vec<Option<Point>>>,
+    tags: Vec<String>,
+}
+
+#[uniffi::export]
+fn sample_drawing() -> Drawing {
+    Drawing {
+        name: "sample".to_string(),
+        background: Some(Color::Blue),
+        shapes: vec![
+            Shape::Point,
+            Shape::Circle { radius: 1.5 },
+            Shape::Polygon {
+                points: vec![Point { x: 0, y: 0 }, Point { x: 1, y: 2 }],
+            },
+            Shape::Labelled("label".to_string(), Some(Color::Red)),
+            Shape::Labelled("unlabelled".to_string(), None),
+        ],
+        layers: HashMap::from([("base".to_string(), vec![Some(Point { x: 3, y: 4 }), None])]),
+        tags: vec!["a".to_string(), "b".to_string()],
+    }
+}
+
+#[uniffi::export]
+fn drawing_identity(drawing: Drawing) -> Drawing {
+    drawing
+}
+
+#[derive(uniffi::Record, Clone, Debug, PartialEq)]
+pub struct Event {
+    name: String,
+    at: SystemTime,
+    duration: Option<Duration>,
+    checkpoints: Vec<SystemTime>,
+}
+
+#[uniffi::export]
+fn sample_event() -> Event {
+    let at = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    Event {
+        name: "sample".to_string(),
+        at,
+        duration: Some(Duration::from_millis(1500)),
+        checkpoints: vec![at + Duration::from_secs(1), at + Duration::from_secs(2)],
+    }
+}
+
+#[derive(uniffi::Record, Clone, Debug, PartialEq)]
+pub struct Attachment {
+    name: String,
+    data: Vec<u8>,
+}
+
+#[uniffi::export]
+fn sample_attachment() -> Attachment {
+    Attachment {
+        name: "sample".to_string(),
+        data: vec![0, 1, 2, 255],
+    }
+}
+
+uniffi::setup_scaffolding!();
@@ -0,0 +1,54 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import java.time.Duration
+import java.time.Instant
+import kotlinx.serialization.KSerializer
+import kotlinx.serialization.decodeFromString
+import kotlinx.serialization.descriptors.PrimitiveKind
+import kotlinx.serialization.descriptors.PrimitiveSerialDescriptor
+import kotlinx.serialization.encodeToString
+import kotlinx.serialization.encoding.Decoder
+import kotlinx.serialization.encoding.Encoder
+import kotlinx.serialization.json.Json
+import kotlinx.serialization.modules.SerializersModule
+import kotlinx.serialization.modules.contextual
+import uniffi.uniffi_serialization.*
+
+// Timestamps and durations are `@Contextual`, so we need to provide serializers for them.
+object InstantSerializer : KSerializer<Instant> {
+    override val descriptor = PrimitiveSerialDescriptor("Instant", PrimitiveKind.STRING)
+    override fun serialize(encoder: Encoder, value: Instant) = encoder.encodeString(value.toString())
+    override fun deserialize(decoder: Decoder): Instant = Instant.parse(decoder.decodeString())
+}
+
+object DurationSerializer : KSerializer<Duration> {
+    override val descriptor = PrimitiveSerialDescriptor("Duration", PrimitiveKind.STRING)
+    override fun serialize(encoder: Encoder, value: Duration) = encoder.encodeString(value.toString())
+    override fun deserialize(decoder: Decoder): Duration = Duration.parse(decoder.decodeString())
+}
+
+val json = Json {
+    serializersModule = SerializersModule {
+        contextual(InstantSerializer)
+        contextual(DurationSerializer)
+    }
+}
+
+val drawing = sampleDrawing()
+val decoded = json.decodeFromString<Drawing>(json.encodeToString(drawing))
+assert(decoded == drawing)
+assert(drawingIdentity(decoded) == drawing)
+
+assert(json.decodeFromString<Color>(json.encodeToString(Color.GREEN)) == Color.GREEN)
+
+val shape: Shape = Shape.Labelled("label", Color.RED)
+val encodedShape = json.encodeToString(shape)
+assert(encodedShape.contains("\"type\":\"Labelled\""))
+assert(json.decodeFromString<Shape>(encodedShape) == shape)
+
+val event = sampleEvent()
+val encodedEvent = json.encodeToString(event)
+assert(encodedEvent.contains("2023-11-14T22:13:20Z"))
+assert(json.decodeFromString<Event>(encodedEvent) == event)
@@ -0,0 +1,55 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+import json
+import unittest
+from uniffi_serialization import *
+
+class TestDictConversion(unittest.TestCase):
+    def test_record_to_dict(self):
+        d = sample_drawing().to_dict()
+        self.assertEqual(d["name"], "sample")
+        self.assertEqual(d["background"], "BLUE")
+        self.assertEqual(d["shapes"], [
+            {"Point": {}},
+            {"Circle": {"radius": 1.5}},
+            {"Polygon": {"points": [{"x": 0, "y": 0}, {"x": 1, "y": 2}]}},
+            {"Labelled": ["label", "RED"]},
+            {"Labelled": ["unlabelled", None]},
+        ])
+        self.assertEqual(d["layers"], {"base": [{"x": 3, "y": 4}, None]})
+        self.assertEqual(d["tags"], ["a", "b"])
+
+    def test_json_round_trip(self):
+        drawing = sample_drawing()
+        encoded = json.dumps(drawing.to_dict())
+        decoded = Drawing.from_dict(json.loads(encoded))
+        self.assertEqual(decoded, drawing)
+        # The decoded value must still be lowerable across the FFI.
+        self.assertEqual(drawing_identity(decoded), drawing)
+
+    def test_builtin_types_to_dict(self):
+        d = sample_event().to_dict()
+        self.assertEqual(d["at"], "2023-11-14T22:13:20+00:00")
+        self.assertEqual(d["duration"], 1.5)
+        self.assertEqual(d["checkpoints"], ["2023-11-14T22:13:21+00:00", "2023-11-14T22:13:22+00:00"])
+        self.assertEqual(sample_attachment().to_dict()["data"], "AAEC/w==")
+
+    def test_builtin_types_json_round_trip(self):
+        event = sample_event()
+        self.assertEqual(Event.from_dict(json.loads(json.dumps(event.to_dict()))), event)
+        attachment = sample_attachment()
+        decoded = Attachment.from_dict(json.loads(json.dumps(attachment.to_dict())))
+        self.assertEqual(decoded, attachment)
+
+    def test_enums(self):
+        self.assertEqual(Color.GREEN.to_dict(), "GREEN")
+        self.assertEqual(Color.from_dict("GREEN"), Color.GREEN)
+        circle = Shape.CIRCLE(radius=2.0)
+        self.assertEqual(Shape.from_dict(circle.to_dict()), circle)
+        with self.assertRaises(ValueError):
+            Shape.from_dict({"Triangle": {}})
+
+if __name__ == '__main__':
+    unittest.main()
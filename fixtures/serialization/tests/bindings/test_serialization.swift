@@ -0,0 +1,19 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import Foundation
+import uniffi_serialization
+
+let drawing = sampleDrawing()
+let encoded = try! JSONEncoder().encode(drawing)
+let decoded = try! JSONDecoder().decode(Drawing.self, from: encoded)
+assert(decoded == drawing)
+assert(drawingIdentity(drawing: decoded) == drawing)
+
+let color = try! JSONDecoder().decode(Color.self, from: JSONEncoder().encode(Color.green))
+assert(color == .green)
+
+let shape = Shape.labelled("label", .red)
+let decodedShape = try! JSONDecoder().decode(Shape.self, from: JSONEncoder().encode(shape))
+assert(decodedShape == shape)
@@ -0,0 +1,5 @@
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_serialization.kts",
+    "tests/bindings/test_serialization.py",
+    "tests/bindings/test_serialization.swift",
+);
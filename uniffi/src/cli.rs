@@ -3,8 +3,9 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use camino::Utf8PathBuf;
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use uniffi_bindgen::bindings::TargetLanguage;
+use uniffi_bindgen::plugin::{PluginBindingGenerator, PLUGIN_LANGUAGE_PREFIX};
 
 // Structs to help our cmdline parsing. Note that docstrings below form part
 // of the "help" output.
@@ -24,8 +25,10 @@ enum Commands {
     /// Generate foreign language bindings
     Generate {
         /// Foreign language(s) for which to build bindings.
-        #[clap(long, short, value_enum)]
-        language: Vec<TargetLanguage>,
+        ///
+        /// Use `plugin:<exe>` to generate bindings with an external plugin executable.
+        #[clap(long, short, value_parser = parse_language)]
+        language: Vec<Language>,
 
         /// Directory in which to write generated files. Default is same folder as .udl file.
         #[clap(long, short)]
@@ -73,9 +76,47 @@ enum Commands {
 
     /// Print a debug representation of the interface from a dynamic library
     PrintRepr {
+        /// Output format: `debug` for the Rust debug representation of the metadata, or `json`
+        /// for a versioned JSON representation of the whole interface.
+        #[clap(long, value_enum, default_value = "debug")]
+        format: ReprFormat,
+
         /// Path to the library file (.so, .dll, .dylib, or .a)
         path: Utf8PathBuf,
     },
+
+    /// Compare the interfaces of two versions of a library, and fail if there are breaking changes
+    CheckCompat {
+        /// Only fail for changes that are breaking in these languages. Default is all languages.
+        #[clap(long, short, value_enum)]
+        language: Vec<TargetLanguage>,
+
+        /// Path to the old version of the library
+        old: Utf8PathBuf,
+
+        /// Path to the new version of the library
+        new: Utf8PathBuf,
+    },
+}
+
+#[derive(Clone)]
+enum Language {
+    Builtin(TargetLanguage),
+    Plugin(Utf8PathBuf),
+}
+
+fn parse_language(value: &str) -> Result<Language, String> {
+    match value.strip_prefix(PLUGIN_LANGUAGE_PREFIX) {
+        Some("") => Err("expected the path to a plugin executable".to_string()),
+        Some(executable) => Ok(Language::Plugin(executable.into())),
+        None => TargetLanguage::from_str(value, true).map(Language::Builtin),
+    }
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum ReprFormat {
+    Debug,
+    Json,
 }
 
 pub fn run_main() -> anyhow::Result<()> {
@@ -91,32 +132,63 @@ pub fn run_main() -> anyhow::Result<()> {
             crate_name,
             library_mode,
         } => {
+            let mut builtins = vec![];
+            let mut plugins = vec![];
+            for language in language {
+                match language {
+                    Language::Builtin(language) => builtins.push(language),
+                    Language::Plugin(executable) => plugins.push(executable),
+                }
+            }
             if library_mode {
                 if lib_file.is_some() {
                     panic!("--lib-file is not compatible with --library.")
                 }
                 let out_dir = out_dir.expect("--out-dir is required when using --library");
-                if language.is_empty() {
+                if builtins.is_empty() && plugins.is_empty() {
                     panic!("please specify at least one language with --language")
                 }
-                uniffi_bindgen::library_mode::generate_bindings(
-                    &source,
-                    crate_name,
-                    &language,
-                    config.as_deref(),
-                    &out_dir,
-                    !no_format,
-                )?;
+                if !builtins.is_empty() {
+                    uniffi_bindgen::library_mode::generate_bindings(
+                        &source,
+                        crate_name.clone(),
+                        &builtins,
+                        config.as_deref(),
+                        &out_dir,
+                        !no_format,
+                    )?;
+                }
+                for executable in plugins {
+                    uniffi_bindgen::library_mode::generate_external_bindings(
+                        PluginBindingGenerator::new(executable),
+                        &source,
+                        crate_name.clone(),
+                        config.as_deref(),
+                        &out_dir,
+                    )?;
+                }
             } else {
-                uniffi_bindgen::generate_bindings(
-                    &source,
-                    config.as_deref(),
-                    language,
-                    out_dir.as_deref(),
-                    lib_file.as_deref(),
-                    crate_name.as_deref(),
-                    !no_format,
-                )?;
+                if !builtins.is_empty() || plugins.is_empty() {
+                    uniffi_bindgen::generate_bindings(
+                        &source,
+                        config.as_deref(),
+                        builtins,
+                        out_dir.as_deref(),
+                        lib_file.as_deref(),
+                        crate_name.as_deref(),
+                        !no_format,
+                    )?;
+                }
+                for executable in plugins {
+                    uniffi_bindgen::generate_external_bindings(
+                        PluginBindingGenerator::new(executable),
+                        &source,
+                        config.as_deref(),
+                        out_dir.as_deref(),
+                        lib_file.as_deref(),
+                        crate_name.as_deref(),
+                    )?;
+                }
             }
         }
         Commands::Scaffolding {
@@ -130,8 +202,22 @@ pub fn run_main() -> anyhow::Result<()> {
                 !no_format,
             )?;
         }
-        Commands::PrintRepr { path } => {
-            uniffi_bindgen::print_repr(&path)?;
+        Commands::PrintRepr { format, path } => match format {
+            ReprFormat::Debug => uniffi_bindgen::print_repr(&path)?,
+            ReprFormat::Json => uniffi_bindgen::print_repr_json(&path)?,
+        },
+        Commands::CheckCompat { language, old, new } => {
+            let report = uniffi_bindgen::compat::CompatReport::from_libraries(&old, &new)?;
+            print!("{report}");
+            let breaking = if language.is_empty() {
+                report.is_breaking()
+            } else {
+                report.is_breaking_for(&language)
+            };
+            if breaking {
+                eprintln!("Found breaking changes between {old} and {new}");
+                std::process::exit(1);
+            }
         }
     };
     Ok(())
@@ -8,6 +8,16 @@ pub use uniffi_macros::*;
 #[cfg(feature = "cli")]
 mod cli;
 #[cfg(feature = "bindgen-tests")]
+pub use uniffi_bindgen::bindings::c::run_test as c_run_test;
+#[cfg(feature = "bindgen-tests")]
+pub use uniffi_bindgen::bindings::cpp::run_test as cpp_run_test;
+#[cfg(feature = "bindgen-tests")]
+pub use uniffi_bindgen::bindings::csharp::run_test as csharp_run_test;
+#[cfg(feature = "bindgen-tests")]
+pub use uniffi_bindgen::bindings::go::run_test as go_run_test;
+#[cfg(feature = "bindgen-tests")]
+pub use uniffi_bindgen::bindings::kotlin::run_script as kotlin_run_script;
+#[cfg(feature = "bindgen-tests")]
 pub use uniffi_bindgen::bindings::kotlin::run_test as kotlin_run_test;
 #[cfg(feature = "bindgen-tests")]
 pub use uniffi_bindgen::bindings::python::run_test as python_run_test;
@@ -15,10 +25,14 @@ pub use uniffi_bindgen::bindings::python::run_test as python_run_test;
 pub use uniffi_bindgen::bindings::ruby::run_test as ruby_run_test;
 #[cfg(feature = "bindgen-tests")]
 pub use uniffi_bindgen::bindings::swift::run_test as swift_run_test;
+#[cfg(feature = "bindgen-tests")]
+pub use uniffi_bindgen::bindings::typescript::run_test as typescript_run_test;
+#[cfg(feature = "bindgen-tests")]
+pub use uniffi_bindgen::bindings::RunScriptOptions;
 #[cfg(feature = "bindgen")]
 pub use uniffi_bindgen::{
     bindings::TargetLanguage, generate_bindings, generate_component_scaffolding,
-    generate_component_scaffolding_for_crate, print_repr,
+    generate_component_scaffolding_for_crate, print_repr, print_repr_json,
 };
 #[cfg(feature = "build")]
 pub use uniffi_build::{generate_scaffolding, generate_scaffolding_for_crate};
@@ -0,0 +1,10 @@
+fn main() {}
+
+// Normally this is defined by the scaffolding code, manually define it for the UI test
+pub struct UniFfiTag;
+
+// `()` can be returned, but it can't be used as an argument or inside another type
+#[uniffi::export]
+fn take_units(units: Vec<()>) -> () {
+    drop(units)
+}
@@ -0,0 +1,333 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+
+use askama::Template;
+use heck::{ToShoutySnakeCase, ToUpperCamelCase};
+use serde::{Deserialize, Serialize};
+
+use crate::interface::*;
+use crate::BindingsConfig;
+
+// Config options to customize the generated C header.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Config {
+    header_filename: Option<String>,
+    cdylib_name: Option<String>,
+}
+
+impl Config {
+    /// A config for a header that's generated alongside other bindings.
+    pub(crate) fn with_header_filename(header_filename: String, cdylib_name: String) -> Self {
+        Self {
+            header_filename: Some(header_filename),
+            cdylib_name: Some(cdylib_name),
+        }
+    }
+
+    /// The filename of the generated header, `{namespace}.h` by default.
+    pub fn header_filename(&self, ci: &ComponentInterface) -> String {
+        self.header_filename
+            .clone()
+            .unwrap_or_else(|| format!("{}.h", ci.namespace()))
+    }
+
+    /// The name of the compiled Rust library containing the FFI implementation.
+    pub fn cdylib_name(&self) -> String {
+        self.cdylib_name
+            .clone()
+            .unwrap_or_else(|| "uniffi".to_string())
+    }
+}
+
+impl BindingsConfig for Config {
+    fn update_from_ci(&mut self, ci: &ComponentInterface) {
+        self.cdylib_name
+            .get_or_insert_with(|| format!("uniffi_{}", ci.namespace()));
+    }
+
+    fn update_from_cdylib_name(&mut self, cdylib_name: &str) {
+        self.cdylib_name
+            .get_or_insert_with(|| cdylib_name.to_string());
+    }
+
+    fn update_from_dependency_configs(&mut self, _config_map: HashMap<&str, &Self>) {}
+}
+
+/// Template for the standalone C header
+///
+/// This declares everything in `ComponentInterface::ffi_definitions()`, with a comment describing
+/// each definition.
+#[derive(Template)]
+#[template(syntax = "c", escape = "none", path = "HeaderTemplate.h")]
+pub struct CHeader<'a> {
+    config: Config,
+    ci: &'a ComponentInterface,
+    docs: HashMap<String, Vec<String>>,
+}
+
+impl<'a> CHeader<'a> {
+    pub fn new(config: Config, ci: &'a ComponentInterface) -> Self {
+        Self {
+            config,
+            ci,
+            docs: definition_docs(ci),
+        }
+    }
+
+    /// The lines of the doc comment for an FFI definition
+    fn doc_lines(&self, name: &str) -> &[String] {
+        self.docs.get(name).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    fn include_guard(&self) -> String {
+        format!("UNIFFI_{}_H", self.ci.namespace().to_shouty_snake_case())
+    }
+}
+
+/// Build the doc comments for the FFI definitions, keyed by the definition name.
+///
+/// The FFI definitions don't know which part of the interface they came from, so we walk the
+/// interface here instead.
+fn definition_docs(ci: &ComponentInterface) -> HashMap<String, Vec<String>> {
+    let mut docs = HashMap::new();
+    let mut add = |name: &str, summary: String, docstring: Option<&str>, extra: &[&str]| {
+        let mut lines: Vec<String> = summary.lines().map(str::to_owned).collect();
+        if let Some(docstring) = docstring {
+            lines.push(String::new());
+            lines.extend(docstring.lines().map(str::to_owned));
+        }
+        if !extra.is_empty() {
+            lines.push(String::new());
+            lines.extend(extra.iter().map(|s| s.to_string()));
+        }
+        docs.insert(name.to_owned(), lines);
+    };
+    const ASYNC_NOTE: &[&str] = &[
+        "This returns a future handle.",
+        "Drive it with the `rust_future_*` functions for its return type.",
+    ];
+    let async_note = |is_async: bool| if is_async { ASYNC_NOTE } else { &[] };
+
+    for func in ci.function_definitions() {
+        add(
+            func.ffi_func().name(),
+            format!("Call the `{}` function.", func.name()),
+            func.docstring(),
+            async_note(func.is_async()),
+        );
+    }
+    for obj in ci.object_definitions() {
+        let obj_name = obj.name();
+        add(
+            obj.ffi_object_clone().name(),
+            format!("Clone a `{obj_name}` pointer, incrementing its reference count."),
+            None,
+            &[],
+        );
+        add(
+            obj.ffi_object_free().name(),
+            format!("Free a `{obj_name}` pointer, decrementing its reference count."),
+            obj.docstring(),
+            &[],
+        );
+        if obj.has_callback_interface() {
+            add(
+                obj.ffi_init_callback().name(),
+                format!("Register the vtable for foreign implementations of `{obj_name}`."),
+                None,
+                &["The vtable must stay valid for as long as the library is loaded."],
+            );
+        }
+        for cons in obj.constructors() {
+            add(
+                cons.ffi_func().name(),
+                format!(
+                    "Construct a `{obj_name}` with the `{}` constructor.",
+                    cons.name()
+                ),
+                cons.docstring(),
+                async_note(cons.is_async()),
+            );
+        }
+        let trait_methods = obj.uniffi_traits().into_iter().flat_map(|t| match t {
+            UniffiTrait::Debug { fmt }
+            | UniffiTrait::Display { fmt }
+            | UniffiTrait::Hash { hash: fmt } => vec![fmt],
+            UniffiTrait::Eq { eq, ne } => vec![eq, ne],
+        });
+        for meth in obj.methods().into_iter().chain(trait_methods) {
+            add(
+                meth.ffi_func().name(),
+                format!("Call the `{obj_name}::{}` method.", meth.name()),
+                meth.docstring(),
+                async_note(meth.is_async()),
+            );
+        }
+        for (callback, meth) in obj.vtable_methods() {
+            add(
+                callback.name(),
+                format!(
+                    "Foreign implementation of the `{obj_name}::{}` method.",
+                    meth.name()
+                ),
+                meth.docstring(),
+                &[],
+            );
+        }
+        if let Some(vtable) = obj.vtable_definition() {
+            add(
+                vtable.name(),
+                format!("VTable for foreign implementations of `{obj_name}`."),
+                None,
+                &[],
+            );
+        }
+    }
+    for cbi in ci.callback_interface_definitions() {
+        let cbi_name = cbi.name();
+        add(
+            cbi.ffi_init_callback().name(),
+            format!("Register the vtable for the `{cbi_name}` callback interface."),
+            cbi.docstring(),
+            &["The vtable must stay valid for as long as the library is loaded."],
+        );
+        for (callback, meth) in cbi.vtable_methods() {
+            add(
+                callback.name(),
+                format!(
+                    "Foreign implementation of the `{cbi_name}::{}` method.",
+                    meth.name()
+                ),
+                meth.docstring(),
+                &[],
+            );
+        }
+        add(
+            cbi.vtable_definition().name(),
+            format!("VTable for the `{cbi_name}` callback interface."),
+            None,
+            &[],
+        );
+    }
+    for (name, _) in ci.iter_checksums() {
+        add(
+            &name,
+            "Checksum of an exported item, used to check that this header matches the library."
+                .to_owned(),
+            None,
+            &[],
+        );
+    }
+    add(
+        ci.ffi_uniffi_contract_version().name(),
+        "The version of the FFI contract that the library implements.".to_owned(),
+        None,
+        &[],
+    );
+    add(
+        ci.ffi_rustbuffer_alloc().name(),
+        "Allocate a `RustBuffer` with at least `size` bytes of capacity.".to_owned(),
+        None,
+        &[],
+    );
+    add(
+        ci.ffi_rustbuffer_from_bytes().name(),
+        "Copy foreign bytes into a newly allocated `RustBuffer`.".to_owned(),
+        None,
+        &[],
+    );
+    add(
+        ci.ffi_rustbuffer_free().name(),
+        "Free a `RustBuffer` that was returned by the library.".to_owned(),
+        None,
+        &[],
+    );
+    add(
+        ci.ffi_rustbuffer_reserve().name(),
+        "Grow a `RustBuffer` so that it can hold `additional` more bytes.".to_owned(),
+        None,
+        &[],
+    );
+    for func in ci.iter_futures_ffi_function_definitions() {
+        let summary = if func.name().contains("_rust_future_poll_") {
+            "Poll a future handle.\n\n`callback` is called with `callback_data` once the future can make progress."
+        } else if func.name().contains("_rust_future_cancel_") {
+            "Cancel a future handle.\n\n`complete` must still be called to release its result."
+        } else if func.name().contains("_rust_future_complete_") {
+            "Get the result of a future handle.\n\nCall this once polling returned `UNIFFI_RUST_FUTURE_POLL_READY`."
+        } else {
+            "Free a future handle."
+        };
+        add(func.name(), summary.to_owned(), None, &[]);
+    }
+    for def in ci.ffi_definitions() {
+        let name = def.name();
+        let summary = if name == "RustFutureContinuationCallback" {
+            "Called by the library when a polled future can make progress.\n\n`poll_result` is one of the `UNIFFI_RUST_FUTURE_POLL_*` values."
+        } else if name == "ForeignFutureFree" {
+            "Free a foreign future, cancelling it if it's still running."
+        } else if name == "CallbackInterfaceFree" {
+            "Free a handle to a foreign callback interface implementation."
+        } else if name == "ForeignFuture" {
+            "A future returned by an async foreign callback method."
+        } else if name.starts_with("ForeignFutureStruct") {
+            "The result of an async foreign callback method."
+        } else if name.starts_with("ForeignFutureComplete") {
+            "Called by the foreign side to complete an async callback method."
+        } else {
+            continue;
+        };
+        add(name, summary.to_owned(), None, &[]);
+    }
+    docs
+}
+
+pub(crate) mod filters {
+    use super::*;
+
+    /// The C type for an FFI type
+    pub fn ffi_type_name(ffi_type: &FfiType) -> Result<String, askama::Error> {
+        Ok(match ffi_type {
+            FfiType::Int8 => "int8_t".into(),
+            FfiType::UInt8 => "uint8_t".into(),
+            FfiType::Int16 => "int16_t".into(),
+            FfiType::UInt16 => "uint16_t".into(),
+            FfiType::Int32 => "int32_t".into(),
+            FfiType::UInt32 => "uint32_t".into(),
+            FfiType::Int64 => "int64_t".into(),
+            FfiType::UInt64 => "uint64_t".into(),
+            FfiType::Float32 => "float".into(),
+            FfiType::Float64 => "double".into(),
+            FfiType::Handle => "uint64_t".into(),
+            FfiType::RustArcPtr(_) => "void*".into(),
+            FfiType::RustBuffer(_) => "RustBuffer".into(),
+            FfiType::RustCallStatus => "RustCallStatus".into(),
+            FfiType::ForeignBytes => "ForeignBytes".into(),
+            FfiType::Callback(name) => ffi_callback_name(name)?,
+            FfiType::Struct(name) => ffi_struct_name(name)?,
+            FfiType::Reference(inner) => format!("{}*", ffi_type_name(inner)?),
+            FfiType::VoidPointer => "void*".into(),
+        })
+    }
+
+    /// The C typedef name for an FFI callback function
+    ///
+    /// This matches the Swift bridging header, so that both headers can be included together.
+    pub fn ffi_callback_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(format!("Uniffi{}", nm.to_upper_camel_case()))
+    }
+
+    /// The C typedef name for an FFI struct
+    pub fn ffi_struct_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(format!("Uniffi{}", nm.to_upper_camel_case()))
+    }
+
+    /// The include guard for an FFI definition, shared with the Swift bridging header
+    pub fn if_guard_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(format!("UNIFFI_FFIDEF_{}", nm.to_shouty_snake_case()))
+    }
+}
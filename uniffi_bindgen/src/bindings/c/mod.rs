@@ -0,0 +1,44 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # C header generation
+//!
+//! This generates a standalone C header declaring the scaffolding functions of a component, along
+//! with the `RustBuffer`, `RustCallStatus`, callback and vtable types that they use.  It's meant
+//! for consumers that link against the cdylib directly, like C and C++ code, rather than going
+//! through one of the higher-level bindings.
+//!
+//! The header only declares the raw FFI, so callers need to serialize compound types into
+//! `RustBuffer`s themselves, following the format described in the manual.
+
+use anyhow::{Context, Result};
+use camino::Utf8Path;
+use fs_err as fs;
+
+pub mod gen_c;
+mod test;
+pub use gen_c::{CHeader, Config};
+pub use test::{run_script, run_test};
+
+use super::super::interface::ComponentInterface;
+
+// Generate a C header for the given ComponentInterface, in the given output directory.
+pub fn write_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+    out_dir: &Utf8Path,
+    _try_format_code: bool,
+) -> Result<()> {
+    let header_file = out_dir.join(config.header_filename(ci));
+    fs::write(header_file, generate_header(config, ci)?)?;
+    Ok(())
+}
+
+// Generate a C header for the given ComponentInterface, as a string.
+pub fn generate_header(config: &Config, ci: &ComponentInterface) -> Result<String> {
+    use askama::Template;
+    CHeader::new(config.clone(), ci)
+        .render()
+        .context("failed to render C header")
+}
@@ -0,0 +1,146 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+/**
+ * C declarations for the `{{ ci.namespace() }}` component.
+ *
+{%- match ci.namespace_docstring() %}
+{%- when Some(docstring) %}
+{%- for line in docstring.lines() %}
+ *{% if !line.is_empty() %} {{ line }}{% endif %}
+{%- endfor %}
+ *
+{%- when None %}
+{%- endmatch %}
+ * Link against the `{{ config.cdylib_name() }}` library to use them.
+ *
+ * Arguments and return values that are not primitives are passed as a `RustBuffer` containing
+ * the serialized value.  Every `RustBuffer` returned by the library, including the error buffer
+ * of a `RustCallStatus`, must be freed with `{{ ci.ffi_rustbuffer_free().name() }}`.
+ */
+#ifndef {{ self.include_guard() }}
+#define {{ self.include_guard() }}
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// The following structs are used to implement the lowest level
+// of the FFI, and thus useful to multiple uniffied crates.
+// We ensure they are declared exactly once, with a header guard, UNIFFI_SHARED_H.
+// This block must stay in sync with the Swift bridging header.
+#ifdef UNIFFI_SHARED_H
+    // We also try to prevent mixing versions of shared uniffi header structs.
+    #ifndef UNIFFI_SHARED_HEADER_V4
+        #error Combining helper code from multiple versions of uniffi is not supported
+    #endif // ndef UNIFFI_SHARED_HEADER_V4
+#else
+#define UNIFFI_SHARED_H
+#define UNIFFI_SHARED_HEADER_V4
+
+/**
+ * A byte buffer allocated by the library.
+ *
+ * `len` bytes of `data` are valid, out of `capacity` allocated bytes.
+ */
+typedef struct RustBuffer
+{
+    uint64_t capacity;
+    uint64_t len;
+    uint8_t *data;
+} RustBuffer;
+
+/**
+ * A borrowed byte buffer allocated by the caller.
+ */
+typedef struct ForeignBytes
+{
+    int32_t len;
+    const uint8_t *data;
+} ForeignBytes;
+
+/**
+ * Call status, passed as the last argument to scaffolding functions that can fail.
+ *
+ * Initialize `code` to `UNIFFI_CALL_SUCCESS` and `errorBuf` to zeroes before the call.
+ */
+typedef struct RustCallStatus {
+    int8_t code;
+    RustBuffer errorBuf;
+} RustCallStatus;
+
+#endif // def UNIFFI_SHARED_H
+
+#ifndef UNIFFI_CALL_SUCCESS
+/** The call was successful. */
+#define UNIFFI_CALL_SUCCESS 0
+/** The call returned an error, serialized in `errorBuf`. */
+#define UNIFFI_CALL_ERROR 1
+/** The call failed unexpectedly. `errorBuf` may contain the error message as a UTF-8 string. */
+#define UNIFFI_CALL_UNEXPECTED_ERROR 2
+/** The async call was cancelled. */
+#define UNIFFI_CALL_CANCELLED 3
+/**
+ * The call panicked. `errorBuf` may contain the serialized panic details: the message, file,
+ * line (u32), thread name and backtrace, with empty strings and line 0 when they're unknown.
+ */
+#define UNIFFI_CALL_PANIC 4
+#endif
+
+#ifndef UNIFFI_RUST_FUTURE_POLL_READY
+/** Passed to `RustFutureContinuationCallback` when the future is ready to complete. */
+#define UNIFFI_RUST_FUTURE_POLL_READY 0
+/** Passed to `RustFutureContinuationCallback` when the future should be polled again. */
+#define UNIFFI_RUST_FUTURE_POLL_MAYBE_READY 1
+#endif
+
+{% for def in ci.ffi_definitions() -%}
+#ifndef {{ def.name()|if_guard_name }}
+#define {{ def.name()|if_guard_name }}
+{%- let docs = self.doc_lines(def.name()) %}
+{%- if !docs.is_empty() %}
+/**
+{%- for line in docs %}
+ *{% if !line.is_empty() %} {{ line }}{% endif %}
+{%- endfor %}
+ */
+{%- endif %}
+{%- match def %}
+{%- when FfiDefinition::CallbackFunction(callback) %}
+typedef {% match callback.return_type() %}{% when Some(return_type) %}{{ return_type|ffi_type_name }}{% when None %}void{% endmatch %} (*{{ callback.name()|ffi_callback_name }})(
+    {%- for arg in callback.arguments() -%}
+    {{ arg.type_().borrow()|ffi_type_name }} {{ arg.name() }}
+    {%- if !loop.last || callback.has_rust_call_status_arg() %}, {% endif %}
+    {%- endfor -%}
+    {%- if callback.has_rust_call_status_arg() %}RustCallStatus* uniffi_call_status
+    {%- else if callback.arguments().is_empty() %}void
+    {%- endif -%}
+);
+{%- when FfiDefinition::Struct(struct) %}
+typedef struct {{ struct.name()|ffi_struct_name }} {
+    {%- for field in struct.fields() %}
+    {{ field.type_().borrow()|ffi_type_name }} {{ field.name() }};
+    {%- endfor %}
+} {{ struct.name()|ffi_struct_name }};
+{%- when FfiDefinition::Function(func) %}
+{% match func.return_type() -%}{%- when Some with (type_) %}{{ type_|ffi_type_name }}{% when None %}void{% endmatch %} {{ func.name() }}(
+    {%- for arg in func.arguments() %}
+    {{- arg.type_().borrow()|ffi_type_name }} {{ arg.name() -}}{% if !loop.last || func.has_rust_call_status_arg() %}, {% endif %}
+    {%- endfor %}
+    {%- if func.has_rust_call_status_arg() %}RustCallStatus* out_status
+    {%- else if func.arguments().is_empty() %}void
+    {%- endif -%}
+);
+{%- endmatch %}
+#endif
+
+{% endfor -%}
+#ifdef __cplusplus
+} // extern "C"
+#endif
+
+#endif // {{ self.include_guard() }}
@@ -0,0 +1,90 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+License, v. 2.0. If a copy of the MPL was not distributed with this
+* file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use crate::{
+    bindings::{RunScriptOptions, TargetLanguage},
+    library_mode::generate_bindings,
+};
+use anyhow::{bail, Context, Result};
+use camino::Utf8Path;
+use std::env;
+use std::process::{Command, Stdio};
+use uniffi_testing::UniFFITestHelper;
+
+/// Run C tests for a UniFFI test fixture
+pub fn run_test(tmp_dir: &str, fixture_name: &str, script_file: &str) -> Result<()> {
+    run_script(
+        tmp_dir,
+        fixture_name,
+        script_file,
+        vec![],
+        &RunScriptOptions::default(),
+    )
+}
+
+/// Compile a C source file against the generated header and run it
+///
+/// The compiler is `$CC`, or `cc` if that's not set.  The test program is linked directly
+/// against the cdylib.
+pub fn run_script(
+    tmp_dir: &str,
+    crate_name: &str,
+    script_file: &str,
+    args: Vec<String>,
+    options: &RunScriptOptions,
+) -> Result<()> {
+    let script_path = Utf8Path::new(script_file).canonicalize_utf8()?;
+    let test_helper = UniFFITestHelper::new(crate_name)?;
+    let out_dir = test_helper.create_out_dir(tmp_dir, &script_path)?;
+    let cdylib_path = test_helper.copy_cdylib_to_out_dir(&out_dir)?;
+    generate_bindings(
+        &cdylib_path,
+        None,
+        &[TargetLanguage::C],
+        None,
+        &out_dir,
+        false,
+    )?;
+
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let exe_path = out_dir.join("test_script");
+    let mut command = Command::new(&compiler);
+    command
+        .current_dir(&out_dir)
+        .arg("-std=c99")
+        .arg("-Wall")
+        .arg("-Wextra")
+        .arg("-pedantic")
+        .arg("-Werror")
+        .arg("-I")
+        .arg(&out_dir)
+        .arg("-o")
+        .arg(&exe_path)
+        .arg(&script_path)
+        .arg(&cdylib_path)
+        .arg(format!("-Wl,-rpath,{out_dir}"));
+    if !options.show_compiler_messages {
+        command.stderr(Stdio::null());
+    }
+    let status = command
+        .spawn()
+        .with_context(|| format!("Failed to spawn `{compiler}` when compiling test script"))?
+        .wait()
+        .with_context(|| format!("Failed to wait for `{compiler}` when compiling test script"))?;
+    if !status.success() {
+        bail!("running `{compiler}` to compile test script failed ({command:?})");
+    }
+
+    let status = Command::new(&exe_path)
+        .current_dir(&out_dir)
+        .args(args)
+        .spawn()
+        .context("Failed to spawn the compiled test script")?
+        .wait()
+        .context("Failed to wait for the compiled test script")?;
+    if !status.success() {
+        bail!("running the compiled test script failed");
+    }
+    Ok(())
+}
@@ -0,0 +1,455 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::collections::{BTreeSet, HashMap};
+
+use anyhow::{bail, Result};
+use askama::Template;
+use heck::{ToSnakeCase, ToUpperCamelCase};
+use serde::{Deserialize, Serialize};
+
+use crate::interface::*;
+use crate::BindingsConfig;
+
+// https://en.cppreference.com/w/cpp/keyword
+static KEYWORDS: &[&str] = &[
+    "alignas",
+    "alignof",
+    "and",
+    "and_eq",
+    "asm",
+    "auto",
+    "bitand",
+    "bitor",
+    "bool",
+    "break",
+    "case",
+    "catch",
+    "char",
+    "char8_t",
+    "char16_t",
+    "char32_t",
+    "class",
+    "compl",
+    "concept",
+    "const",
+    "consteval",
+    "constexpr",
+    "constinit",
+    "const_cast",
+    "continue",
+    "co_await",
+    "co_return",
+    "co_yield",
+    "decltype",
+    "default",
+    "delete",
+    "do",
+    "double",
+    "dynamic_cast",
+    "else",
+    "enum",
+    "explicit",
+    "export",
+    "extern",
+    "false",
+    "float",
+    "for",
+    "friend",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "mutable",
+    "namespace",
+    "new",
+    "noexcept",
+    "not",
+    "not_eq",
+    "nullptr",
+    "operator",
+    "or",
+    "or_eq",
+    "private",
+    "protected",
+    "public",
+    "register",
+    "reinterpret_cast",
+    "requires",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "static_assert",
+    "static_cast",
+    "struct",
+    "switch",
+    "template",
+    "this",
+    "thread_local",
+    "throw",
+    "true",
+    "try",
+    "typedef",
+    "typeid",
+    "typename",
+    "union",
+    "unsigned",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "wchar_t",
+    "while",
+    "xor",
+    "xor_eq",
+];
+
+/// Append an underscore to names that are C++ keywords
+fn quote_keyword(nm: String) -> String {
+    if KEYWORDS.contains(&nm.as_str()) {
+        format!("{nm}_")
+    } else {
+        nm
+    }
+}
+
+// Config options to customize the generated C++ bindings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Config {
+    cdylib_name: Option<String>,
+    namespace: Option<String>,
+}
+
+impl Config {
+    /// The name of the compiled Rust library containing the FFI implementation.
+    pub fn cdylib_name(&self) -> String {
+        self.cdylib_name
+            .clone()
+            .unwrap_or_else(|| "uniffi".to_string())
+    }
+
+    /// The C++ namespace for the generated code, the component namespace by default.
+    pub fn namespace(&self, ci: &ComponentInterface) -> String {
+        self.namespace
+            .clone()
+            .unwrap_or_else(|| ci.namespace().to_snake_case())
+    }
+}
+
+impl BindingsConfig for Config {
+    fn update_from_ci(&mut self, ci: &ComponentInterface) {
+        self.cdylib_name
+            .get_or_insert_with(|| format!("uniffi_{}", ci.namespace()));
+    }
+
+    fn update_from_cdylib_name(&mut self, cdylib_name: &str) {
+        self.cdylib_name
+            .get_or_insert_with(|| cdylib_name.to_string());
+    }
+
+    fn update_from_dependency_configs(&mut self, _config_map: HashMap<&str, &Self>) {}
+}
+
+/// Bail out if the interface uses features that the C++ bindings don't support yet.
+///
+/// It's better to fail loudly than to generate bindings that silently leave out part of the API.
+pub fn check_supported(ci: &ComponentInterface) -> Result<()> {
+    if let Some(cbi) = ci.callback_interface_definitions().first() {
+        bail!(
+            "C++ bindings don't support callback interfaces yet (found `{}`)",
+            cbi.name()
+        );
+    }
+    for obj in ci.object_definitions() {
+        if obj.has_callback_interface() {
+            bail!(
+                "C++ bindings don't support foreign trait implementations yet (found `{}`)",
+                obj.name()
+            );
+        }
+        if ci.is_name_used_as_error(obj.name()) {
+            bail!(
+                "C++ bindings don't support objects as errors yet (found `{}`)",
+                obj.name()
+            );
+        }
+    }
+    if ci.has_async_fns() {
+        bail!("C++ bindings don't support async functions yet");
+    }
+    if let Some((name, ..)) = ci.iter_external_types().next() {
+        bail!("C++ bindings don't support external types yet (found `{name}`)");
+    }
+    Ok(())
+}
+
+/// Get the canonical, unique-within-this-component name for a type.
+///
+/// This is used to name the `FfiConverter` struct for each type.
+pub fn canonical_name(t: &Type) -> String {
+    match t {
+        Type::Int8 => "Int8".into(),
+        Type::UInt8 => "UInt8".into(),
+        Type::Int16 => "Int16".into(),
+        Type::UInt16 => "UInt16".into(),
+        Type::Int32 => "Int32".into(),
+        Type::UInt32 => "UInt32".into(),
+        Type::Int64 => "Int64".into(),
+        Type::UInt64 => "UInt64".into(),
+        Type::Float32 => "Float".into(),
+        Type::Float64 => "Double".into(),
+        Type::Boolean => "Bool".into(),
+        Type::String => "String".into(),
+        Type::Bytes => "Bytes".into(),
+        Type::Timestamp => "Timestamp".into(),
+        Type::Duration => "Duration".into(),
+        Type::Function { .. } => panic!("Function types are not supported by the C++ bindings"),
+        Type::Object { name, .. }
+        | Type::Record { name, .. }
+        | Type::Enum { name, .. }
+        | Type::Custom { name, .. }
+        | Type::External { name, .. } => format!("Type{}", name.to_upper_camel_case()),
+        Type::CallbackInterface { name, .. } => {
+            format!("CallbackInterface{}", name.to_upper_camel_case())
+        }
+        Type::Optional { inner_type } => format!("Optional{}", canonical_name(inner_type)),
+        Type::Sequence { inner_type } => format!("Sequence{}", canonical_name(inner_type)),
+        Type::Map {
+            key_type,
+            value_type,
+        } => format!(
+            "Map{}{}",
+            canonical_name(key_type),
+            canonical_name(value_type)
+        ),
+    }
+}
+
+/// Records and enums, ordered so that each type is defined after the types of its fields
+///
+/// C++ needs the complete definition of a type to use it as a member.  Objects are left out,
+/// since their classes are defined before any of these.
+fn sorted_data_types(ci: &ComponentInterface) -> Vec<Type> {
+    fn visit(
+        ci: &ComponentInterface,
+        t: &Type,
+        seen: &mut BTreeSet<String>,
+        sorted: &mut Vec<Type>,
+    ) {
+        let fields = match t {
+            Type::Record { name, .. } => match ci.get_record_definition(name) {
+                Some(rec) => rec.fields().iter().map(|f| f.as_type()).collect(),
+                None => return,
+            },
+            Type::Enum { name, .. } => match ci.get_enum_definition(name) {
+                Some(e) => e
+                    .variants()
+                    .iter()
+                    .flat_map(|v| v.fields())
+                    .map(|f| f.as_type())
+                    .collect::<Vec<_>>(),
+                None => return,
+            },
+            _ => return,
+        };
+        if !seen.insert(canonical_name(t)) {
+            return;
+        }
+        for field_type in fields {
+            for inner in ci.iter_types_in_item(&field_type) {
+                visit(ci, inner, seen, sorted);
+            }
+        }
+        sorted.push(t.clone());
+    }
+
+    let mut seen = BTreeSet::new();
+    let mut sorted = vec![];
+    for t in ci.iter_types() {
+        visit(ci, t, &mut seen, &mut sorted);
+    }
+    sorted
+}
+
+/// Template for the public header, `{namespace}.hpp`
+///
+/// This doesn't include the C header, so that consumers don't see the raw FFI.
+#[derive(Template)]
+#[template(syntax = "cpp", escape = "none", path = "wrapper.hpp")]
+pub struct CppHeader<'a> {
+    config: Config,
+    ci: &'a ComponentInterface,
+}
+
+impl<'a> CppHeader<'a> {
+    pub fn new(config: Config, ci: &'a ComponentInterface) -> Self {
+        Self { config, ci }
+    }
+
+    fn sorted_data_types(&self) -> Vec<Type> {
+        sorted_data_types(self.ci)
+    }
+}
+
+/// Template for the implementation, `{namespace}.cpp`
+#[derive(Template)]
+#[template(syntax = "cpp", escape = "none", path = "wrapper.cpp")]
+pub struct CppSource<'a> {
+    config: Config,
+    ci: &'a ComponentInterface,
+    header_filename: String,
+    ffi_header_filename: String,
+}
+
+impl<'a> CppSource<'a> {
+    pub fn new(
+        config: Config,
+        ci: &'a ComponentInterface,
+        header_filename: String,
+        ffi_header_filename: String,
+    ) -> Self {
+        Self {
+            config,
+            ci,
+            header_filename,
+            ffi_header_filename,
+        }
+    }
+
+    /// Errors only need a converter to throw them, since they're never passed to Rust.
+    fn is_error(&self, t: &Type) -> bool {
+        matches!(t, Type::Enum { name, .. } if self.ci.is_name_used_as_error(name))
+    }
+}
+
+mod filters {
+    use super::*;
+
+    /// The C++ type for a UniFFI type
+    pub fn type_name(as_type: &impl AsType) -> Result<String, askama::Error> {
+        Ok(match as_type.as_type() {
+            Type::Int8 => "int8_t".into(),
+            Type::UInt8 => "uint8_t".into(),
+            Type::Int16 => "int16_t".into(),
+            Type::UInt16 => "uint16_t".into(),
+            Type::Int32 => "int32_t".into(),
+            Type::UInt32 => "uint32_t".into(),
+            Type::Int64 => "int64_t".into(),
+            Type::UInt64 => "uint64_t".into(),
+            Type::Float32 => "float".into(),
+            Type::Float64 => "double".into(),
+            Type::Boolean => "bool".into(),
+            Type::String => "std::string".into(),
+            Type::Bytes => "std::vector<uint8_t>".into(),
+            Type::Timestamp => "std::chrono::system_clock::time_point".into(),
+            Type::Duration => "std::chrono::nanoseconds".into(),
+            Type::Function { .. } => panic!("Function types are not supported by the C++ bindings"),
+            Type::Object { name, .. }
+            | Type::Record { name, .. }
+            | Type::Enum { name, .. }
+            | Type::Custom { name, .. }
+            | Type::External { name, .. }
+            | Type::CallbackInterface { name, .. } => class_name(&name)?,
+            Type::Optional { inner_type } => format!("std::optional<{}>", type_name(&*inner_type)?),
+            Type::Sequence { inner_type } => format!("std::vector<{}>", type_name(&*inner_type)?),
+            Type::Map {
+                key_type,
+                value_type,
+            } => format!(
+                "std::unordered_map<{}, {}>",
+                type_name(&*key_type)?,
+                type_name(&*value_type)?
+            ),
+        })
+    }
+
+    /// The C++ type for a parameter: primitives are passed by value, other types by const reference
+    pub fn param_type_name(as_type: &impl AsType) -> Result<String, askama::Error> {
+        let t = as_type.as_type();
+        Ok(match t {
+            Type::Int8
+            | Type::UInt8
+            | Type::Int16
+            | Type::UInt16
+            | Type::Int32
+            | Type::UInt32
+            | Type::Int64
+            | Type::UInt64
+            | Type::Float32
+            | Type::Float64
+            | Type::Boolean => type_name(&t)?,
+            _ => format!("const {}&", type_name(&t)?),
+        })
+    }
+
+    pub fn ffi_converter_name(as_type: &impl AsType) -> Result<String, askama::Error> {
+        Ok(format!(
+            "FfiConverter{}",
+            canonical_name(&as_type.as_type())
+        ))
+    }
+
+    /// The C type that a UniFFI type is lowered into
+    pub fn ffi_type_name(as_type: &impl AsType) -> Result<String, askama::Error> {
+        crate::bindings::c::gen_c::filters::ffi_type_name(&FfiType::from(as_type.as_type()))
+    }
+
+    /// Is this type passed across the FFI as a serialized `RustBuffer`?
+    pub fn is_serialized(as_type: &impl AsType) -> Result<bool, askama::Error> {
+        Ok(
+            matches!(FfiType::from(as_type.as_type()), FfiType::RustBuffer(_))
+                && !matches!(as_type.as_type(), Type::String),
+        )
+    }
+
+    /// The C++ rendering of a default value
+    pub fn literal(literal: &Literal, as_type: &impl AsType) -> Result<String, askama::Error> {
+        Ok(match literal {
+            Literal::Boolean(v) => v.to_string(),
+            Literal::String(s) => format!("\"{}\"", s.escape_default()),
+            Literal::UInt(v, _, _) => format!("{v}u"),
+            Literal::Int(v, _, _) => v.to_string(),
+            Literal::Float(v, _) => v.clone(),
+            Literal::Enum(variant, t) => {
+                format!("{}::{}", type_name(t)?, enum_variant_name(variant)?)
+            }
+            Literal::EmptySequence | Literal::EmptyMap => "{}".into(),
+            Literal::Null => match as_type.as_type() {
+                Type::Optional { .. } => "std::nullopt".into(),
+                _ => "{}".into(),
+            },
+        })
+    }
+
+    /// The C++ rendering of a class name (for records, enums, objects, etc).
+    pub fn class_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(nm.to_upper_camel_case())
+    }
+
+    /// The C++ rendering of a function or method name.
+    pub fn fn_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(quote_keyword(nm.to_snake_case()))
+    }
+
+    /// The C++ rendering of a variable, argument or field name.
+    pub fn var_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(quote_keyword(nm.to_snake_case()))
+    }
+
+    /// The C++ rendering of an enum variant name.
+    pub fn enum_variant_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(nm.to_upper_camel_case())
+    }
+
+    pub fn docstring(docstring: &str, spaces: &i32) -> Result<String, askama::Error> {
+        let middle = textwrap::indent(&textwrap::dedent(docstring), " * ");
+        let wrapped = format!("/**\n{middle}\n */");
+
+        let spaces = usize::try_from(*spaces).unwrap_or_default();
+        Ok(textwrap::indent(&wrapped, &" ".repeat(spaces)))
+    }
+}
@@ -0,0 +1,58 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # C++ bindings backend
+//!
+//! This generates a `{namespace}.hpp` header and a `{namespace}.cpp` source file, to be compiled
+//! together with the consumer's code and linked against the cdylib.  They build on the C header
+//! from the `c` backend, which is written alongside them as `{namespace}_ffi.h`.
+//!
+//! Objects are wrapped in RAII classes that own a reference to the Rust object, records become
+//! structs, enums with fields become a `std::variant` of per-variant structs, and errors are
+//! thrown as exceptions.
+
+use anyhow::{Context, Result};
+use askama::Template;
+use camino::Utf8Path;
+use fs_err as fs;
+
+pub mod gen_cpp;
+mod test;
+pub use gen_cpp::{Config, CppHeader, CppSource};
+pub use test::{run_script, run_test};
+
+use super::super::interface::ComponentInterface;
+use super::c;
+
+// Generate C++ bindings for the given ComponentInterface, in the given output directory.
+pub fn write_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+    out_dir: &Utf8Path,
+    _try_format_code: bool,
+) -> Result<()> {
+    gen_cpp::check_supported(ci)?;
+
+    let namespace = ci.namespace();
+    let header_filename = format!("{namespace}.hpp");
+    let ffi_header_filename = format!("{namespace}_ffi.h");
+
+    let ffi_config =
+        c::Config::with_header_filename(ffi_header_filename.clone(), config.cdylib_name());
+    fs::write(
+        out_dir.join(&ffi_header_filename),
+        c::generate_header(&ffi_config, ci)?,
+    )?;
+
+    let header = CppHeader::new(config.clone(), ci)
+        .render()
+        .context("failed to render C++ header")?;
+    fs::write(out_dir.join(&header_filename), header)?;
+
+    let source = CppSource::new(config.clone(), ci, header_filename, ffi_header_filename)
+        .render()
+        .context("failed to render C++ source")?;
+    fs::write(out_dir.join(format!("{namespace}.cpp")), source)?;
+    Ok(())
+}
@@ -0,0 +1,18 @@
+{%- call cpp::docstring(e, 0) %}
+struct {{ e.name()|class_name }} {
+    {%- for variant in e.variants() %}
+    {%- call cpp::docstring(variant, 4) %}
+    struct {{ variant.name()|class_name }} {
+        {%- for field in variant.fields() %}
+        {%- call cpp::docstring(field, 8) %}
+        {{ field|type_name }} {% call cpp::field_name(field, loop.index0) %};
+        {%- endfor %}
+    };
+    {%- endfor %}
+
+    std::variant<
+        {%- for variant in e.variants() -%}
+        {{ variant.name()|class_name }}{% if !loop.last %}, {% endif %}
+        {%- endfor -%}
+    > variant;
+};
@@ -0,0 +1,24 @@
+
+void {{ conv }}::throw_error(RustBuffer buf) {
+    RustStream stream(buf);
+    switch (stream.read<int32_t>()) {
+    {%- for variant in e.variants() %}
+    {%- let variant_name = variant.name()|class_name %}
+    case {{ loop.index }}:
+        {%- if e.is_flat() %}
+        throw {{ type_name }}::{{ variant_name }}(read_string(stream));
+        {%- else if variant.has_fields() %}
+        // Braced initializers are evaluated in order
+        throw {{ type_name }}::{{ variant_name }}{
+            {%- for field in variant.fields() %}
+            {{ field|ffi_converter_name }}::read(stream){% if !loop.last %},{% endif %}
+            {%- endfor %}
+        };
+        {%- else %}
+        throw {{ type_name }}::{{ variant_name }}();
+        {%- endif %}
+    {%- endfor %}
+    default:
+        throw InternalError("Unexpected variant for {{ type_name }}");
+    }
+}
@@ -0,0 +1,36 @@
+{%- let class_name = e.name()|class_name %}
+{%- call cpp::docstring(e, 0) %}
+class {{ class_name }} : public std::runtime_error {
+public:
+    using std::runtime_error::runtime_error;
+    {%- for variant in e.variants() %}
+    class {{ variant.name()|class_name }};
+    {%- endfor %}
+};
+{%- for variant in e.variants() %}
+{% call cpp::docstring(variant, 0) %}
+class {{ class_name }}::{{ variant.name()|class_name }} : public {{ class_name }} {
+public:
+    {%- if e.is_flat() %}
+    using {{ class_name }}::{{ class_name }};
+    {%- else %}
+    {%- if variant.has_fields() %}
+    {{ variant.name()|class_name }}(
+        {%- for field in variant.fields() -%}
+        {{ field|type_name }} {% call cpp::field_name(field, loop.index0) %}{% if !loop.last %}, {% endif %}
+        {%- endfor -%}
+    )
+        : {{ class_name }}("{{ class_name }}::{{ variant.name()|class_name }}")
+        {%- for field in variant.fields() %}
+        , {% call cpp::field_name(field, loop.index0) %}(std::move({% call cpp::field_name(field, loop.index0) %}))
+        {%- endfor %} {}
+    {%- else %}
+    {{ variant.name()|class_name }}() : {{ class_name }}("{{ class_name }}::{{ variant.name()|class_name }}") {}
+    {%- endif %}
+    {%- for field in variant.fields() %}
+    {%- call cpp::docstring(field, 4) %}
+    {{ field|type_name }} {% call cpp::field_name(field, loop.index0) %};
+    {%- endfor %}
+    {%- endif %}
+};
+{%- endfor %}
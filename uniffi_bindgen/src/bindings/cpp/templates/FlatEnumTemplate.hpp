@@ -0,0 +1,7 @@
+{%- call cpp::docstring(e, 0) %}
+enum class {{ e.name()|class_name }} {
+    {%- for variant in e.variants() %}
+    {%- call cpp::docstring(variant, 4) %}
+    {{ variant.name()|enum_variant_name }},
+    {%- endfor %}
+};
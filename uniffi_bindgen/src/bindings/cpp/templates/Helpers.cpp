@@ -0,0 +1,165 @@
+// Free a `RustBuffer` returned by the library.
+//
+// This doesn't throw, since it's called from destructors.
+inline void free_rust_buffer(RustBuffer buf) noexcept {
+    RustCallStatus status = {};
+    {{ ci.ffi_rustbuffer_free().name() }}(buf, &status);
+}
+
+// Reads values from a `RustBuffer`, then frees it.
+class RustStream {
+public:
+    explicit RustStream(RustBuffer buf) : buf_(buf) {}
+    RustStream(const RustStream&) = delete;
+    RustStream& operator=(const RustStream&) = delete;
+    ~RustStream() { free_rust_buffer(buf_); }
+
+    // Read a big-endian integer or float
+    template <typename T>
+    T read() {
+        static_assert(std::is_arithmetic<T>::value, "read() only supports numbers");
+        using Bits = std::conditional_t<sizeof(T) == 1, uint8_t,
+            std::conditional_t<sizeof(T) == 2, uint16_t,
+            std::conditional_t<sizeof(T) == 4, uint32_t, uint64_t>>>;
+        check_remaining(sizeof(T));
+        Bits bits = 0;
+        for (size_t i = 0; i < sizeof(T); i++) {
+            bits = static_cast<Bits>((bits << 8) | buf_.data[pos_++]);
+        }
+        T value;
+        std::memcpy(&value, &bits, sizeof(T));
+        return value;
+    }
+
+    const uint8_t* read_bytes(size_t len) {
+        check_remaining(len);
+        const uint8_t* bytes = buf_.data + pos_;
+        pos_ += len;
+        return bytes;
+    }
+
+    // Check that all the data was read
+    void check_finished() const {
+        if (pos_ != buf_.len) {
+            throw InternalError("Junk data left in buffer after reading a value");
+        }
+    }
+
+private:
+    void check_remaining(size_t len) const {
+        if (buf_.len - pos_ < len) {
+            throw InternalError("Unexpected end of buffer");
+        }
+    }
+
+    RustBuffer buf_;
+    uint64_t pos_ = 0;
+};
+
+inline std::string read_string(RustStream& stream) {
+    size_t len = static_cast<size_t>(stream.read<int32_t>());
+    if (len == 0) {
+        return std::string();
+    }
+    return std::string(reinterpret_cast<const char *>(stream.read_bytes(len)), len);
+}
+
+// Writes values into a buffer, which is then copied into a `RustBuffer`.
+class RustBufferBuilder {
+public:
+    // Write a big-endian integer or float
+    template <typename T>
+    void write(T value) {
+        static_assert(std::is_arithmetic<T>::value, "write() only supports numbers");
+        using Bits = std::conditional_t<sizeof(T) == 1, uint8_t,
+            std::conditional_t<sizeof(T) == 2, uint16_t,
+            std::conditional_t<sizeof(T) == 4, uint32_t, uint64_t>>>;
+        Bits bits;
+        std::memcpy(&bits, &value, sizeof(T));
+        for (size_t i = sizeof(T); i > 0; i--) {
+            bytes_.push_back(static_cast<uint8_t>(bits >> ((i - 1) * 8)));
+        }
+    }
+
+    void write_bytes(const uint8_t* bytes, size_t len) {
+        bytes_.insert(bytes_.end(), bytes, bytes + len);
+    }
+
+    RustBuffer finish() const;
+
+private:
+    std::vector<uint8_t> bytes_;
+};
+
+// Check the status of a scaffolding call, throwing if it failed.
+//
+// `on_error` throws the error type of the function, for functions that can fail.
+inline void check_call_status(RustCallStatus& status, void (*on_error)(RustBuffer)) {
+    switch (status.code) {
+    case UNIFFI_CALL_SUCCESS:
+        return;
+    case UNIFFI_CALL_ERROR:
+        if (on_error != nullptr) {
+            on_error(status.errorBuf);
+        }
+        free_rust_buffer(status.errorBuf);
+        throw InternalError("Unexpected error returned by the Rust library");
+    case UNIFFI_CALL_UNEXPECTED_ERROR:
+        if (status.errorBuf.len > 0) {
+            // The error message is the raw UTF-8 string, like a lowered `String`.
+            size_t len = static_cast<size_t>(status.errorBuf.len);
+            RustStream stream(status.errorBuf);
+            throw InternalError(std::string(reinterpret_cast<const char *>(stream.read_bytes(len)), len));
+        }
+        free_rust_buffer(status.errorBuf);
+        throw InternalError("Unexpected Rust error");
+    case UNIFFI_CALL_PANIC:
+        if (status.errorBuf.len > 0) {
+            // The serialized panic details start with the message.
+            RustStream stream(status.errorBuf);
+            throw RustPanic(read_string(stream));
+        }
+        free_rust_buffer(status.errorBuf);
+        throw RustPanic("Rust panic");
+    default:
+        throw InternalError("Unknown RustCallStatus code: " + std::to_string(status.code));
+    }
+}
+
+// Call a scaffolding function, passing it a `RustCallStatus` to check afterwards.
+template <typename F>
+auto rust_call(void (*on_error)(RustBuffer), F call) {
+    RustCallStatus status = {};
+    if constexpr (std::is_void<decltype(call(&status))>::value) {
+        call(&status);
+        check_call_status(status, on_error);
+    } else {
+        auto result = call(&status);
+        check_call_status(status, on_error);
+        return result;
+    }
+}
+
+inline RustBuffer RustBufferBuilder::finish() const {
+    ForeignBytes bytes = { static_cast<int32_t>(bytes_.size()), bytes_.data() };
+    return rust_call(nullptr, [&](RustCallStatus* status) {
+        return {{ ci.ffi_rustbuffer_from_bytes().name() }}(bytes, status);
+    });
+}
+
+// Lift a value that was serialized into a `RustBuffer`
+template <typename Converter>
+auto lift_from_rust_buffer(RustBuffer buf) {
+    RustStream stream(buf);
+    auto value = Converter::read(stream);
+    stream.check_finished();
+    return value;
+}
+
+// Lower a value by serializing it into a `RustBuffer`
+template <typename Converter, typename T>
+RustBuffer lower_into_rust_buffer(const T& value) {
+    RustBufferBuilder builder;
+    Converter::write(builder, value);
+    return builder.finish();
+}
@@ -0,0 +1,120 @@
+{%- let class_name = obj.name()|class_name %}
+{%- match obj.primary_constructor() %}
+{%- when Some(cons) %}
+
+{{ class_name }}::{{ class_name }}({% call cpp::arg_list_decl(cons) %})
+    : ptr_({% call cpp::rust_call(cons, "") %}) {}
+{%- when None %}
+{%- endmatch %}
+{%- for cons in obj.alternate_constructors() %}
+
+{{ class_name }} {{ class_name }}::{{ cons.name()|fn_name }}({% call cpp::arg_list_decl(cons) %}) {
+    return {{ class_name }}(uniffi_internal::RawPointer{ {% call cpp::rust_call(cons, "") %} });
+}
+{%- endfor %}
+
+{{ class_name }}::{{ class_name }}(uniffi_internal::RawPointer raw) : ptr_(raw.ptr) {}
+
+{{ class_name }}::{{ class_name }}(const {{ class_name }}& other) : ptr_(other.uniffi_clone_pointer()) {}
+
+{{ class_name }}::{{ class_name }}({{ class_name }}&& other) noexcept : ptr_(std::exchange(other.ptr_, nullptr)) {}
+
+{{ class_name }}& {{ class_name }}::operator=({{ class_name }} other) noexcept {
+    std::swap(ptr_, other.ptr_);
+    return *this;
+}
+
+{{ class_name }}::~{{ class_name }}() {
+    // Moved-from objects don't own a pointer anymore
+    if (ptr_ != nullptr) {
+        RustCallStatus status = {};
+        {{ obj.ffi_object_free().name() }}(ptr_, &status);
+    }
+}
+
+// Methods consume a reference to the Rust object, so each call passes a new clone of the pointer.
+void* {{ class_name }}::uniffi_clone_pointer() const {
+    if (ptr_ == nullptr) {
+        throw std::logic_error("{{ class_name }} object has been moved from or consumed");
+    }
+    return uniffi_internal::rust_call(nullptr, [&](RustCallStatus* uniffi_status) {
+        return {{ obj.ffi_object_clone().name() }}(ptr_, uniffi_status);
+    });
+}
+{%- if obj.has_consuming_method() %}
+
+// Methods that take `self` by value pass our own reference instead, leaving the object empty like
+// a moved-from object.  Rust fails with an unexpected error, before taking ownership of the
+// pointer, when it can't consume the object, for example because a copy still refers to it.  The
+// methods put the pointer back then, so the object is still usable.
+void* {{ class_name }}::uniffi_take_pointer() {
+    if (ptr_ == nullptr) {
+        throw std::logic_error("{{ class_name }} object has been moved from or consumed");
+    }
+    return std::exchange(ptr_, nullptr);
+}
+{%- endif %}
+{%- for meth in obj.methods() %}
+{%- if meth.takes_self_by_value() %}
+
+{% call cpp::return_type(meth) %} {{ class_name }}::{{ meth.name()|fn_name }}({% call cpp::arg_list_decl(meth) %}) && {
+    void* uniffi_pointer = uniffi_take_pointer();
+    {%- match meth.return_type() %}
+    {%- when Some(return_type) %}
+    auto uniffi_result = [&] {
+        try {
+            return {% call cpp::rust_call(meth, "uniffi_pointer, ") %};
+        } catch (const RustPanic&) {
+            throw;
+        } catch (const InternalError&) {
+            ptr_ = uniffi_pointer;
+            throw;
+        }
+    }();
+    return uniffi_internal::{{ return_type|ffi_converter_name }}::lift(uniffi_result);
+    {%- when None %}
+    try {
+        {% call cpp::rust_call(meth, "uniffi_pointer, ") %};
+    } catch (const RustPanic&) {
+        throw;
+    } catch (const InternalError&) {
+        ptr_ = uniffi_pointer;
+        throw;
+    }
+    {%- endmatch %}
+}
+{%- else %}
+
+{% call cpp::return_type(meth) %} {{ class_name }}::{{ meth.name()|fn_name }}({% call cpp::arg_list_decl(meth) %}) const {
+    {%- call cpp::call_body(meth, "uniffi_clone_pointer(), ") %}
+}
+{%- endif %}
+{%- endfor %}
+{%- for tm in obj.uniffi_traits() %}
+{%- match tm %}
+{%- when UniffiTrait::Display { fmt } %}
+
+std::string {{ class_name }}::to_string() const {
+    {%- call cpp::call_body(fmt, "uniffi_clone_pointer(), ") %}
+}
+{%- when UniffiTrait::Debug { fmt } %}
+
+std::string {{ class_name }}::debug_string() const {
+    {%- call cpp::call_body(fmt, "uniffi_clone_pointer(), ") %}
+}
+{%- when UniffiTrait::Eq { eq, ne } %}
+
+bool {{ class_name }}::operator==({% call cpp::arg_list_decl(eq) %}) const {
+    {%- call cpp::call_body(eq, "uniffi_clone_pointer(), ") %}
+}
+
+bool {{ class_name }}::operator!=({% call cpp::arg_list_decl(ne) %}) const {
+    {%- call cpp::call_body(ne, "uniffi_clone_pointer(), ") %}
+}
+{%- when UniffiTrait::Hash { hash } %}
+
+uint64_t {{ class_name }}::hash() const {
+    {%- call cpp::call_body(hash, "uniffi_clone_pointer(), ") %}
+}
+{%- endmatch %}
+{%- endfor %}
@@ -0,0 +1,58 @@
+{%- let class_name = obj.name()|class_name %}
+{%- call cpp::docstring(obj, 0) %}
+class {{ class_name }} {
+public:
+    {%- match obj.primary_constructor() %}
+    {%- when Some(cons) %}
+    {%- call cpp::docstring(cons, 4) %}
+    {% if cons.arguments().len() == 1 %}explicit {% endif %}{{ class_name }}({% call cpp::arg_list_decl(cons) %});
+    {%- when None %}
+    {%- endmatch %}
+    {%- for cons in obj.alternate_constructors() %}
+    {%- call cpp::docstring(cons, 4) %}
+    static {{ class_name }} {{ cons.name()|fn_name }}({% call cpp::arg_list_decl(cons) %});
+    {%- endfor %}
+
+    // Copies share the same Rust object, like cloning an `Arc`.
+    {{ class_name }}(const {{ class_name }}& other);
+    {{ class_name }}({{ class_name }}&& other) noexcept;
+    {{ class_name }}& operator=({{ class_name }} other) noexcept;
+    ~{{ class_name }}();
+    {%- for meth in obj.methods() %}
+    {% call cpp::docstring(meth, 4) %}
+    {%- if meth.takes_self_by_value() %}
+    // Consumes the object, which can't be used afterwards: call it as `std::move(object).{{ meth.name()|fn_name }}()`.
+    {% call cpp::return_type(meth) %} {{ meth.name()|fn_name }}({% call cpp::arg_list_decl(meth) %}) &&;
+    {%- else %}
+    {% call cpp::return_type(meth) %} {{ meth.name()|fn_name }}({% call cpp::arg_list_decl(meth) %}) const;
+    {%- endif %}
+    {%- endfor %}
+    {%- for tm in obj.uniffi_traits() %}
+    {%- match tm %}
+    {%- when UniffiTrait::Display { fmt } %}
+
+    std::string to_string() const;
+    {%- when UniffiTrait::Debug { fmt } %}
+
+    std::string debug_string() const;
+    {%- when UniffiTrait::Eq { eq, ne } %}
+
+    bool operator==({% call cpp::arg_list_decl(eq) %}) const;
+    bool operator!=({% call cpp::arg_list_decl(ne) %}) const;
+    {%- when UniffiTrait::Hash { hash } %}
+
+    uint64_t hash() const;
+    {%- endmatch %}
+    {%- endfor %}
+
+private:
+    explicit {{ class_name }}(uniffi_internal::RawPointer raw);
+    void* uniffi_clone_pointer() const;
+    {%- if obj.has_consuming_method() %}
+    void* uniffi_take_pointer();
+    {%- endif %}
+
+    void* ptr_;
+
+    friend struct uniffi_internal::{{ obj|ffi_converter_name }};
+};
@@ -0,0 +1,11 @@
+{%- call cpp::docstring(rec, 0) %}
+struct {{ rec.name()|class_name }} {
+    {%- for field in rec.fields() %}
+    {%- call cpp::docstring(field, 4) %}
+    {{ field|type_name }} {{ field.name()|var_name }}
+    {%- match field.default_value() %}
+    {%- when Some(literal) %} = {{ literal|literal(field) }}
+    {%- else %}
+    {%- endmatch %};
+    {%- endfor %}
+};
@@ -0,0 +1,337 @@
+{#
+// Each type has a `FfiConverter{canonical name}` struct, with static methods to lift and lower
+// it, and to read and write it from a buffer.  The structs are declared first, since the
+// converters for compound types call each other.
+#}
+{%- for type_ in ci.iter_types() %}
+{%- let conv = type_|ffi_converter_name %}
+{%- if self.is_error(type_) %}
+
+struct {{ conv }} {
+    [[noreturn]] static void throw_error(RustBuffer buf);
+};
+{%- else %}
+
+struct {{ conv }} {
+    static {{ type_|type_name }} lift({{ type_|ffi_type_name }} value);
+    static {{ type_|ffi_type_name }} lower({{ type_|param_type_name }} value);
+    static {{ type_|type_name }} read(RustStream& stream);
+    static void write(RustBufferBuilder& builder, {{ type_|param_type_name }} value);
+};
+{%- endif %}
+{%- endfor %}
+
+{%- for type_ in ci.iter_types() %}
+{%- let conv = type_|ffi_converter_name %}
+{%- let type_name = type_|type_name %}
+{%- let param_type_name = type_|param_type_name %}
+{%- if self.is_error(type_) %}
+{%- match type_ %}
+{%- when Type::Enum { name, module_path } %}
+{%- let e = ci.get_enum_definition(name).unwrap() %}
+{% include "ErrorTemplate.cpp" %}
+{%- else %}
+{%- endmatch %}
+{%- else %}
+{%- match type_ %}
+{%- when Type::Custom { name, builtin, module_path } %}
+{%- let builtin_conv = builtin|ffi_converter_name %}
+
+{{ type_name }} {{ conv }}::lift({{ type_|ffi_type_name }} value) {
+    return {{ builtin_conv }}::lift(value);
+}
+
+{{ type_|ffi_type_name }} {{ conv }}::lower({{ param_type_name }} value) {
+    return {{ builtin_conv }}::lower(value);
+}
+
+{{ type_name }} {{ conv }}::read(RustStream& stream) {
+    return {{ builtin_conv }}::read(stream);
+}
+
+void {{ conv }}::write(RustBufferBuilder& builder, {{ param_type_name }} value) {
+    {{ builtin_conv }}::write(builder, value);
+}
+{%- else %}
+{%- if type_|is_serialized %}
+
+{{ type_name }} {{ conv }}::lift(RustBuffer buf) {
+    return lift_from_rust_buffer<{{ conv }}>(buf);
+}
+
+RustBuffer {{ conv }}::lower({{ param_type_name }} value) {
+    return lower_into_rust_buffer<{{ conv }}>(value);
+}
+{%- endif %}
+{%- match type_ %}
+{%- when Type::Boolean %}
+
+bool {{ conv }}::lift(int8_t value) {
+    return value != 0;
+}
+
+int8_t {{ conv }}::lower(bool value) {
+    return value ? 1 : 0;
+}
+
+bool {{ conv }}::read(RustStream& stream) {
+    return stream.read<int8_t>() != 0;
+}
+
+void {{ conv }}::write(RustBufferBuilder& builder, bool value) {
+    builder.write<int8_t>(value ? 1 : 0);
+}
+{%- when Type::String %}
+
+std::string {{ conv }}::lift(RustBuffer buf) {
+    // Strings are lowered as the raw UTF-8 bytes, rather than serialized
+    size_t len = static_cast<size_t>(buf.len);
+    RustStream stream(buf);
+    if (len == 0) {
+        return std::string();
+    }
+    return std::string(reinterpret_cast<const char *>(stream.read_bytes(len)), len);
+}
+
+RustBuffer {{ conv }}::lower(const std::string& value) {
+    ForeignBytes bytes = { static_cast<int32_t>(value.size()), reinterpret_cast<const uint8_t *>(value.data()) };
+    return rust_call(nullptr, [&](RustCallStatus* status) {
+        return {{ ci.ffi_rustbuffer_from_bytes().name() }}(bytes, status);
+    });
+}
+
+std::string {{ conv }}::read(RustStream& stream) {
+    return read_string(stream);
+}
+
+void {{ conv }}::write(RustBufferBuilder& builder, const std::string& value) {
+    builder.write<int32_t>(static_cast<int32_t>(value.size()));
+    builder.write_bytes(reinterpret_cast<const uint8_t *>(value.data()), value.size());
+}
+{%- when Type::Bytes %}
+
+std::vector<uint8_t> {{ conv }}::read(RustStream& stream) {
+    size_t len = static_cast<size_t>(stream.read<int32_t>());
+    const uint8_t* bytes = stream.read_bytes(len);
+    return std::vector<uint8_t>(bytes, bytes + len);
+}
+
+void {{ conv }}::write(RustBufferBuilder& builder, const std::vector<uint8_t>& value) {
+    builder.write<int32_t>(static_cast<int32_t>(value.size()));
+    builder.write_bytes(value.data(), value.size());
+}
+{%- when Type::Timestamp %}
+
+std::chrono::system_clock::time_point {{ conv }}::read(RustStream& stream) {
+    int64_t seconds = stream.read<int64_t>();
+    uint32_t nanos = stream.read<uint32_t>();
+    auto offset = std::chrono::duration_cast<std::chrono::system_clock::duration>(
+        std::chrono::seconds(seconds < 0 ? -seconds : seconds) + std::chrono::nanoseconds(nanos));
+    std::chrono::system_clock::time_point epoch;
+    return seconds >= 0 ? epoch + offset : epoch - offset;
+}
+
+void {{ conv }}::write(RustBufferBuilder& builder, const std::chrono::system_clock::time_point& value) {
+    auto offset = std::chrono::duration_cast<std::chrono::nanoseconds>(value.time_since_epoch());
+    int64_t sign = 1;
+    if (offset.count() < 0) {
+        sign = -1;
+        offset = -offset;
+    }
+    auto seconds = std::chrono::duration_cast<std::chrono::seconds>(offset);
+    builder.write<int64_t>(sign * static_cast<int64_t>(seconds.count()));
+    builder.write<uint32_t>(static_cast<uint32_t>((offset - seconds).count()));
+}
+{%- when Type::Duration %}
+
+std::chrono::nanoseconds {{ conv }}::read(RustStream& stream) {
+    uint64_t seconds = stream.read<uint64_t>();
+    uint32_t nanos = stream.read<uint32_t>();
+    return std::chrono::seconds(seconds) + std::chrono::nanoseconds(nanos);
+}
+
+void {{ conv }}::write(RustBufferBuilder& builder, const std::chrono::nanoseconds& value) {
+    if (value.count() < 0) {
+        throw std::invalid_argument("Invalid duration, must be non-negative");
+    }
+    auto seconds = std::chrono::duration_cast<std::chrono::seconds>(value);
+    builder.write<uint64_t>(static_cast<uint64_t>(seconds.count()));
+    builder.write<uint32_t>(static_cast<uint32_t>((value - seconds).count()));
+}
+{%- when Type::Optional { inner_type } %}
+{%- let inner_conv = inner_type|ffi_converter_name %}
+
+{{ type_name }} {{ conv }}::read(RustStream& stream) {
+    if (stream.read<int8_t>() == 0) {
+        return std::nullopt;
+    }
+    return {{ inner_conv }}::read(stream);
+}
+
+void {{ conv }}::write(RustBufferBuilder& builder, {{ param_type_name }} value) {
+    if (!value.has_value()) {
+        builder.write<int8_t>(0);
+        return;
+    }
+    builder.write<int8_t>(1);
+    {{ inner_conv }}::write(builder, *value);
+}
+{%- when Type::Sequence { inner_type } %}
+{%- let inner_conv = inner_type|ffi_converter_name %}
+
+{{ type_name }} {{ conv }}::read(RustStream& stream) {
+    int32_t count = stream.read<int32_t>();
+    {{ type_name }} value;
+    value.reserve(static_cast<size_t>(count));
+    for (int32_t i = 0; i < count; i++) {
+        value.push_back({{ inner_conv }}::read(stream));
+    }
+    return value;
+}
+
+void {{ conv }}::write(RustBufferBuilder& builder, {{ param_type_name }} value) {
+    builder.write<int32_t>(static_cast<int32_t>(value.size()));
+    for (const auto& item : value) {
+        {{ inner_conv }}::write(builder, item);
+    }
+}
+{%- when Type::Map { key_type, value_type } %}
+{%- let key_conv = key_type|ffi_converter_name %}
+{%- let value_conv = value_type|ffi_converter_name %}
+
+{{ type_name }} {{ conv }}::read(RustStream& stream) {
+    int32_t count = stream.read<int32_t>();
+    {{ type_name }} value;
+    for (int32_t i = 0; i < count; i++) {
+        auto key = {{ key_conv }}::read(stream);
+        value.emplace(std::move(key), {{ value_conv }}::read(stream));
+    }
+    return value;
+}
+
+void {{ conv }}::write(RustBufferBuilder& builder, {{ param_type_name }} value) {
+    builder.write<int32_t>(static_cast<int32_t>(value.size()));
+    for (const auto& entry : value) {
+        {{ key_conv }}::write(builder, entry.first);
+        {{ value_conv }}::write(builder, entry.second);
+    }
+}
+{%- when Type::Object { name, module_path, imp } %}
+
+{{ type_name }} {{ conv }}::lift(void* ptr) {
+    return {{ type_name }}(RawPointer{ptr});
+}
+
+void* {{ conv }}::lower(const {{ type_name }}& value) {
+    return value.uniffi_clone_pointer();
+}
+
+{{ type_name }} {{ conv }}::read(RustStream& stream) {
+    return lift(reinterpret_cast<void *>(static_cast<uintptr_t>(stream.read<uint64_t>())));
+}
+
+void {{ conv }}::write(RustBufferBuilder& builder, const {{ type_name }}& value) {
+    builder.write<uint64_t>(static_cast<uint64_t>(reinterpret_cast<uintptr_t>(lower(value))));
+}
+{%- when Type::Record { name, module_path } %}
+{%- let rec = ci.get_record_definition(name).unwrap() %}
+
+{{ type_name }} {{ conv }}::read(RustStream& stream) {
+    {%- if rec.has_fields() %}
+    // Braced initializers are evaluated in order
+    return {{ type_name }}{
+        {%- for field in rec.fields() %}
+        {{ field|ffi_converter_name }}::read(stream),
+        {%- endfor %}
+    };
+    {%- else %}
+    (void)stream;
+    return {{ type_name }}{};
+    {%- endif %}
+}
+
+void {{ conv }}::write(RustBufferBuilder& builder, const {{ type_name }}& value) {
+    {%- for field in rec.fields() %}
+    {{ field|ffi_converter_name }}::write(builder, value.{{ field.name()|var_name }});
+    {%- else %}
+    (void)builder;
+    (void)value;
+    {%- endfor %}
+}
+{%- when Type::Enum { name, module_path } %}
+{%- let e = ci.get_enum_definition(name).unwrap() %}
+{%- if e.is_flat() %}
+
+{{ type_name }} {{ conv }}::read(RustStream& stream) {
+    switch (stream.read<int32_t>()) {
+    {%- for variant in e.variants() %}
+    case {{ loop.index }}:
+        return {{ type_name }}::{{ variant.name()|enum_variant_name }};
+    {%- endfor %}
+    default:
+        throw InternalError("Unexpected variant for {{ type_name }}");
+    }
+}
+
+void {{ conv }}::write(RustBufferBuilder& builder, {{ param_type_name }} value) {
+    switch (value) {
+    {%- for variant in e.variants() %}
+    case {{ type_name }}::{{ variant.name()|enum_variant_name }}:
+        builder.write<int32_t>({{ loop.index }});
+        break;
+    {%- endfor %}
+    }
+}
+{%- else %}
+
+{{ type_name }} {{ conv }}::read(RustStream& stream) {
+    switch (stream.read<int32_t>()) {
+    {%- for variant in e.variants() %}
+    {%- let variant_name = variant.name()|class_name %}
+    case {{ loop.index }}:
+        return {{ type_name }}{ {{ type_name }}::{{ variant_name }}{
+            {%- for field in variant.fields() %}
+            {{ field|ffi_converter_name }}::read(stream),
+            {%- endfor %}
+        } };
+    {%- endfor %}
+    default:
+        throw InternalError("Unexpected variant for {{ type_name }}");
+    }
+}
+
+void {{ conv }}::write(RustBufferBuilder& builder, const {{ type_name }}& value) {
+    builder.write<int32_t>(static_cast<int32_t>(value.variant.index() + 1));
+    {%- for variant in e.variants() %}
+    {%- if variant.has_fields() %}
+    if (auto* v = std::get_if<{{ type_name }}::{{ variant.name()|class_name }}>(&value.variant)) {
+        {%- for field in variant.fields() %}
+        {{ field|ffi_converter_name }}::write(builder, v->{% call cpp::field_name(field, loop.index0) %});
+        {%- endfor %}
+    }
+    {%- endif %}
+    {%- endfor %}
+}
+{%- endif %}
+{%- else %}
+{#- Integers and floats #}
+
+{{ type_name }} {{ conv }}::lift({{ type_name }} value) {
+    return value;
+}
+
+{{ type_name }} {{ conv }}::lower({{ type_name }} value) {
+    return value;
+}
+
+{{ type_name }} {{ conv }}::read(RustStream& stream) {
+    return stream.read<{{ type_name }}>();
+}
+
+void {{ conv }}::write(RustBufferBuilder& builder, {{ type_name }} value) {
+    builder.write<{{ type_name }}>(value);
+}
+{%- endmatch %}
+{%- endmatch %}
+{%- endif %}
+{%- endfor %}
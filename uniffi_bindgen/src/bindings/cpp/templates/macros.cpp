@@ -0,0 +1,71 @@
+{#
+// Template to call into rust. Used in several places.
+// Variable names in `arg_list_decl` should match up with arg lists
+// passed to rust via `arg_list_lowered`
+#}
+
+{%- macro arg_list_decl(func) %}
+    {%- for arg in func.arguments() -%}
+        {{ arg|param_type_name }} {{ arg.name()|var_name }}
+        {%- if !loop.last %}, {% endif -%}
+    {%- endfor %}
+{%- endmacro %}
+
+{%- macro arg_list_lowered(func) %}
+    {%- for arg in func.arguments() -%}
+        uniffi_internal::{{ arg|ffi_converter_name }}::lower({{ arg.name()|var_name }}), {% endfor -%}
+{%- endmacro %}
+
+{%- macro return_type(func) %}
+    {%- match func.return_type() %}
+    {%- when Some(return_type) %}{{ return_type|type_name }}
+    {%- when None %}void
+    {%- endmatch %}
+{%- endmacro %}
+
+{%- macro error_handler(func) %}
+    {%- match func.throws_type() %}
+    {%- when Some(e) %}&uniffi_internal::{{ e|ffi_converter_name }}::throw_error
+    {%- when None %}nullptr
+    {%- endmatch %}
+{%- endmacro %}
+
+{#
+// Call a scaffolding function, throwing if it fails.
+// `self_ptr` is the expression for the object pointer of methods, or empty otherwise.
+#}
+{%- macro rust_call(func, self_ptr) -%}
+uniffi_internal::rust_call(
+        {% call error_handler(func) %},
+        [&](RustCallStatus* uniffi_status) {
+            return {{ func.ffi_func().name() }}({{ self_ptr }}{% call arg_list_lowered(func) %}uniffi_status);
+        })
+{%- endmacro %}
+
+{#
+// The body of a function or method: call into Rust and lift the return value.
+#}
+{%- macro call_body(func, self_ptr) %}
+    {%- match func.return_type() %}
+    {%- when Some(return_type) %}
+    return uniffi_internal::{{ return_type|ffi_converter_name }}::lift({% call rust_call(func, self_ptr) %});
+    {%- when None %}
+    {% call rust_call(func, self_ptr) %};
+    {%- endmatch %}
+{%- endmacro %}
+
+{%- macro field_name(field, index) %}
+    {%- if field.name().is_empty() %}v{{ index }}{% else %}{{ field.name()|var_name }}{% endif %}
+{%- endmacro %}
+
+{%- macro docstring_value(maybe_docstring, indent_spaces) %}
+{%- match maybe_docstring %}
+{%- when Some(docstring) %}
+{{ docstring|docstring(indent_spaces) }}
+{%- else %}
+{%- endmatch %}
+{%- endmacro %}
+
+{%- macro docstring(defn, indent_spaces) %}
+{%- call docstring_value(defn.docstring(), indent_spaces) %}
+{%- endmacro %}
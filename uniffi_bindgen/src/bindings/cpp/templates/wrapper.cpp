@@ -0,0 +1,31 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+#include "{{ header_filename }}"
+
+#include <cstdlib>
+#include <cstring>
+#include <type_traits>
+
+#include "{{ ffi_header_filename }}"
+
+namespace {{ config.namespace(ci) }} {
+namespace uniffi_internal {
+{% include "Helpers.cpp" %}
+{% include "Types.cpp" %}
+} // namespace uniffi_internal
+
+{%- for obj in ci.object_definitions() %}
+{% include "ObjectTemplate.cpp" %}
+{%- endfor %}
+
+{%- for func in ci.function_definitions() %}
+
+{% call cpp::return_type(func) %} {{ func.name()|fn_name }}({% call cpp::arg_list_decl(func) %}) {
+    {%- call cpp::call_body(func, "") %}
+}
+{%- endfor %}
+
+} // namespace {{ config.namespace(ci) }}
+
+{% import "macros.cpp" as cpp %}
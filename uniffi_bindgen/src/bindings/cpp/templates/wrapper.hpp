@@ -0,0 +1,99 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+#pragma once
+
+#include <chrono>
+#include <cstdint>
+#include <optional>
+#include <stdexcept>
+#include <string>
+#include <unordered_map>
+#include <utility>
+#include <variant>
+#include <vector>
+{% call cpp::docstring_value(ci.namespace_docstring(), 0) %}
+namespace {{ config.namespace(ci) }} {
+
+/**
+ * Thrown when the Rust library panics or returns an error that the bindings don't expect.
+ */
+class InternalError : public std::runtime_error {
+public:
+    using std::runtime_error::runtime_error;
+};
+
+/**
+ * Thrown when the Rust library panics.
+ */
+class RustPanic : public InternalError {
+public:
+    using InternalError::InternalError;
+};
+
+namespace uniffi_internal {
+// Wraps a pointer returned by the Rust library, to construct an object from it.
+struct RawPointer {
+    void* ptr;
+};
+{%- for obj in ci.object_definitions() %}
+struct {{ obj|ffi_converter_name }};
+{%- endfor %}
+} // namespace uniffi_internal
+
+{%- for obj in ci.object_definitions() %}
+class {{ obj.name()|class_name }};
+{%- endfor %}
+{%- for type_ in self.sorted_data_types() %}
+{%- match type_ %}
+{%- when Type::Record { name, module_path } %}
+struct {{ name|class_name }};
+{%- when Type::Enum { name, module_path } %}
+{%- if ci.is_name_used_as_error(name) %}
+class {{ name|class_name }};
+{%- else if !ci.get_enum_definition(name).unwrap().is_flat() %}
+struct {{ name|class_name }};
+{%- endif %}
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
+
+{%- for (name, builtin) in ci.iter_custom_types() %}
+
+using {{ name|class_name }} = {{ builtin|type_name }};
+{%- endfor %}
+
+{%- for e in ci.enum_definitions() %}
+{%- if e.is_flat() && !ci.is_name_used_as_error(e.name()) %}
+{% include "FlatEnumTemplate.hpp" %}
+{%- endif %}
+{%- endfor %}
+
+{%- for obj in ci.object_definitions() %}
+{% include "ObjectTemplate.hpp" %}
+{%- endfor %}
+
+{%- for type_ in self.sorted_data_types() %}
+{%- match type_ %}
+{%- when Type::Record { name, module_path } %}
+{%- let rec = ci.get_record_definition(name).unwrap() %}
+{% include "RecordTemplate.hpp" %}
+{%- when Type::Enum { name, module_path } %}
+{%- let e = ci.get_enum_definition(name).unwrap() %}
+{%- if ci.is_name_used_as_error(name) %}
+{% include "ErrorTemplate.hpp" %}
+{%- else if !e.is_flat() %}
+{% include "EnumTemplate.hpp" %}
+{%- endif %}
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
+
+{%- for func in ci.function_definitions() %}
+{% call cpp::docstring(func, 0) %}
+{% call cpp::return_type(func) %} {{ func.name()|fn_name }}({% call cpp::arg_list_decl(func) %});
+{%- endfor %}
+
+} // namespace {{ config.namespace(ci) }}
+
+{% import "macros.cpp" as cpp %}
@@ -0,0 +1,518 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use askama::Template;
+use heck::{ToLowerCamelCase, ToUpperCamelCase};
+use serde::{Deserialize, Serialize};
+
+use crate::interface::*;
+use crate::BindingsConfig;
+
+// https://learn.microsoft.com/en-us/dotnet/csharp/language-reference/keywords/
+static KEYWORDS: &[&str] = &[
+    "abstract",
+    "as",
+    "base",
+    "bool",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "checked",
+    "class",
+    "const",
+    "continue",
+    "decimal",
+    "default",
+    "delegate",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "event",
+    "explicit",
+    "extern",
+    "false",
+    "finally",
+    "fixed",
+    "float",
+    "for",
+    "foreach",
+    "goto",
+    "if",
+    "implicit",
+    "in",
+    "int",
+    "interface",
+    "internal",
+    "is",
+    "lock",
+    "long",
+    "namespace",
+    "new",
+    "null",
+    "object",
+    "operator",
+    "out",
+    "override",
+    "params",
+    "private",
+    "protected",
+    "public",
+    "readonly",
+    "ref",
+    "return",
+    "sbyte",
+    "sealed",
+    "short",
+    "sizeof",
+    "stackalloc",
+    "static",
+    "string",
+    "struct",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "uint",
+    "ulong",
+    "unchecked",
+    "unsafe",
+    "ushort",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "while",
+];
+
+/// Prefix names that are C# keywords with `@`
+fn quote_keyword(nm: String) -> String {
+    if KEYWORDS.contains(&nm.as_str()) {
+        format!("@{nm}")
+    } else {
+        nm
+    }
+}
+
+// Config options to customize the generated C# code.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Config {
+    namespace: Option<String>,
+    cdylib_name: Option<String>,
+}
+
+impl Config {
+    /// The C# namespace of the generated code, `uniffi.{namespace}` by default.
+    pub fn namespace(&self, ci: &ComponentInterface) -> String {
+        self.namespace
+            .clone()
+            .unwrap_or_else(|| format!("uniffi.{}", ci.namespace()))
+    }
+
+    /// The name of the compiled Rust library containing the FFI implementation.
+    pub fn cdylib_name(&self) -> String {
+        self.cdylib_name
+            .clone()
+            .unwrap_or_else(|| "uniffi".to_string())
+    }
+}
+
+impl BindingsConfig for Config {
+    fn update_from_ci(&mut self, ci: &ComponentInterface) {
+        self.cdylib_name
+            .get_or_insert_with(|| format!("uniffi_{}", ci.namespace()));
+    }
+
+    fn update_from_cdylib_name(&mut self, cdylib_name: &str) {
+        self.cdylib_name
+            .get_or_insert_with(|| cdylib_name.to_string());
+    }
+
+    fn update_from_dependency_configs(&mut self, _config_map: HashMap<&str, &Self>) {}
+}
+
+/// Bail out if the interface uses features that the C# bindings don't support yet.
+pub fn check_supported(ci: &ComponentInterface) -> Result<()> {
+    if let Some(cbi) = ci.callback_interface_definitions().first() {
+        bail!(
+            "C# bindings don't support callback interfaces yet (found `{}`)",
+            cbi.name()
+        );
+    }
+    for obj in ci.object_definitions() {
+        if obj.has_callback_interface() {
+            bail!(
+                "C# bindings don't support foreign trait implementations yet (found `{}`)",
+                obj.name()
+            );
+        }
+        if ci.is_name_used_as_error(obj.name()) {
+            bail!(
+                "C# bindings don't support objects as errors yet (found `{}`)",
+                obj.name()
+            );
+        }
+    }
+    if let Some((name, ..)) = ci.iter_external_types().next() {
+        bail!("C# bindings don't support external types yet (found `{name}`)");
+    }
+    Ok(())
+}
+
+/// Get the canonical, unique-within-this-component name for a type.
+///
+/// This is used to name the `FfiConverter` class for each type.
+pub fn canonical_name(t: &Type) -> String {
+    match t {
+        Type::Int8 => "Int8".into(),
+        Type::UInt8 => "UInt8".into(),
+        Type::Int16 => "Int16".into(),
+        Type::UInt16 => "UInt16".into(),
+        Type::Int32 => "Int32".into(),
+        Type::UInt32 => "UInt32".into(),
+        Type::Int64 => "Int64".into(),
+        Type::UInt64 => "UInt64".into(),
+        Type::Float32 => "Float".into(),
+        Type::Float64 => "Double".into(),
+        Type::Boolean => "Boolean".into(),
+        Type::String => "String".into(),
+        Type::Bytes => "ByteArray".into(),
+        Type::Timestamp => "Timestamp".into(),
+        Type::Duration => "Duration".into(),
+        Type::Function { .. } => panic!("Function types are not supported by the C# bindings"),
+        Type::Object { name, .. }
+        | Type::Record { name, .. }
+        | Type::Enum { name, .. }
+        | Type::Custom { name, .. }
+        | Type::External { name, .. } => format!("Type{}", name.to_upper_camel_case()),
+        Type::CallbackInterface { name, .. } => {
+            format!("CallbackInterface{}", name.to_upper_camel_case())
+        }
+        Type::Optional { inner_type } => format!("Optional{}", canonical_name(inner_type)),
+        Type::Sequence { inner_type } => format!("Sequence{}", canonical_name(inner_type)),
+        Type::Map {
+            key_type,
+            value_type,
+        } => format!(
+            "Map{}{}",
+            canonical_name(key_type),
+            canonical_name(value_type)
+        ),
+    }
+}
+
+/// Render a literal as a C# constant expression, if it can be one.
+///
+/// C# only allows constants as default parameter values, so empty sequences and maps, and
+/// variants of enums with fields, can't be used as defaults.
+fn constant_literal(ci: &ComponentInterface, literal: &Literal, as_type: &Type) -> Option<String> {
+    let suffix = |t: &Type| match t {
+        Type::UInt32 => "u",
+        Type::Int64 => "L",
+        Type::UInt64 => "UL",
+        _ => "",
+    };
+    Some(match literal {
+        Literal::Boolean(v) => v.to_string(),
+        Literal::String(s) => {
+            let mut escaped = String::new();
+            for c in s.chars() {
+                match c {
+                    '\\' => escaped.push_str("\\\\"),
+                    '"' => escaped.push_str("\\\""),
+                    '\n' => escaped.push_str("\\n"),
+                    '\r' => escaped.push_str("\\r"),
+                    '\t' => escaped.push_str("\\t"),
+                    c => escaped.push(c),
+                }
+            }
+            format!("\"{escaped}\"")
+        }
+        Literal::UInt(v, Radix::Hexadecimal, t) => format!("0x{v:x}{}", suffix(t)),
+        Literal::UInt(v, _, t) => format!("{v}{}", suffix(t)),
+        Literal::Int(v, Radix::Hexadecimal, t) => format!("0x{v:x}{}", suffix(t)),
+        Literal::Int(v, _, t) => format!("{v}{}", suffix(t)),
+        Literal::Float(v, Type::Float32) => format!("{v}f"),
+        Literal::Float(v, _) => v.clone(),
+        Literal::Enum(variant, Type::Enum { name, .. }) => {
+            if !ci.get_enum_definition(name)?.is_flat() {
+                return None;
+            }
+            format!(
+                "{}.{}",
+                filters::class_name(name, ci).ok()?,
+                variant.to_upper_camel_case()
+            )
+        }
+        Literal::Null if matches!(as_type, Type::Optional { .. }) => "null".into(),
+        _ => return None,
+    })
+}
+
+/// Pick the default at `index`, if it's one of the trailing parameters which all have defaults.
+///
+/// Optional parameters must come after the required ones in C#.
+fn trailing_default(defaults: Vec<Option<String>>, index: usize) -> Option<String> {
+    if defaults[index..].iter().all(Option::is_some) {
+        defaults[index].clone()
+    } else {
+        None
+    }
+}
+
+/// Template for the C# source file, `{namespace}.cs`
+#[derive(Template)]
+#[template(syntax = "cs", escape = "none", path = "wrapper.cs")]
+pub struct CsharpWrapper<'a> {
+    config: Config,
+    ci: &'a ComponentInterface,
+}
+
+impl<'a> CsharpWrapper<'a> {
+    pub fn new(config: Config, ci: &'a ComponentInterface) -> Self {
+        Self { config, ci }
+    }
+
+    /// The name of the static class holding the top-level functions
+    fn functions_class_name(&self) -> String {
+        format!("{}Methods", self.ci.namespace().to_upper_camel_case())
+    }
+
+    /// The default value of a record field, as a default parameter of the record's constructor.
+    fn field_default(&self, rec: &Record, index: &usize) -> Option<String> {
+        let defaults = rec
+            .fields()
+            .iter()
+            .map(|f| {
+                f.default_value()
+                    .and_then(|lit| constant_literal(self.ci, lit, &f.as_type()))
+            })
+            .collect::<Vec<_>>();
+        trailing_default(defaults, *index)
+    }
+
+    /// The default value of an argument, as a default parameter value.
+    fn arg_default(&self, arguments: Vec<&Argument>, index: &usize) -> Option<String> {
+        let defaults = arguments
+            .iter()
+            .map(|a| {
+                a.default_value()
+                    .and_then(|lit| constant_literal(self.ci, lit, &a.as_type()))
+            })
+            .collect::<Vec<_>>();
+        trailing_default(defaults, *index)
+    }
+}
+
+mod filters {
+    use super::*;
+
+    /// The C# type for a UniFFI type
+    pub fn type_name(
+        as_type: &impl AsType,
+        ci: &ComponentInterface,
+    ) -> Result<String, askama::Error> {
+        Ok(match as_type.as_type() {
+            Type::Int8 => "sbyte".into(),
+            Type::UInt8 => "byte".into(),
+            Type::Int16 => "short".into(),
+            Type::UInt16 => "ushort".into(),
+            Type::Int32 => "int".into(),
+            Type::UInt32 => "uint".into(),
+            Type::Int64 => "long".into(),
+            Type::UInt64 => "ulong".into(),
+            Type::Float32 => "float".into(),
+            Type::Float64 => "double".into(),
+            Type::Boolean => "bool".into(),
+            Type::String => "string".into(),
+            Type::Bytes => "byte[]".into(),
+            Type::Timestamp => "DateTime".into(),
+            Type::Duration => "TimeSpan".into(),
+            // C# has no public type aliases, so custom types use their builtin type.
+            Type::Function { .. } => panic!("Function types are not supported by the C# bindings"),
+            Type::Custom { builtin, .. } => type_name(&*builtin, ci)?,
+            Type::Object { name, .. }
+            | Type::Record { name, .. }
+            | Type::Enum { name, .. }
+            | Type::External { name, .. }
+            | Type::CallbackInterface { name, .. } => class_name(&name, ci)?,
+            Type::Optional { inner_type } => format!("{}?", type_name(&*inner_type, ci)?),
+            Type::Sequence { inner_type } => format!("List<{}>", type_name(&*inner_type, ci)?),
+            Type::Map {
+                key_type,
+                value_type,
+            } => format!(
+                "Dictionary<{}, {}>",
+                type_name(&*key_type, ci)?,
+                type_name(&*value_type, ci)?
+            ),
+        })
+    }
+
+    pub fn converter_name(as_type: &impl AsType) -> Result<String, askama::Error> {
+        Ok(format!(
+            "FfiConverter{}",
+            canonical_name(&as_type.as_type())
+        ))
+    }
+
+    /// The C# type used in `DllImport` declarations for an FFI type
+    pub fn ffi_type_name(ffi_type: &FfiType) -> Result<String, askama::Error> {
+        Ok(match ffi_type {
+            FfiType::Int8 => "sbyte".into(),
+            FfiType::UInt8 => "byte".into(),
+            FfiType::Int16 => "short".into(),
+            FfiType::UInt16 => "ushort".into(),
+            FfiType::Int32 => "int".into(),
+            FfiType::UInt32 => "uint".into(),
+            FfiType::Int64 => "long".into(),
+            FfiType::UInt64 | FfiType::Handle => "ulong".into(),
+            FfiType::Float32 => "float".into(),
+            FfiType::Float64 => "double".into(),
+            FfiType::RustBuffer(_) => "RustBuffer".into(),
+            FfiType::ForeignBytes => "ForeignBytes".into(),
+            FfiType::RustCallStatus => "UniffiRustCallStatus".into(),
+            FfiType::Struct(name) => format!("Uniffi{name}"),
+            FfiType::RustArcPtr(_)
+            | FfiType::Callback(_)
+            | FfiType::Reference(_)
+            | FfiType::VoidPointer => "IntPtr".into(),
+        })
+    }
+
+    /// The C# type that a UniFFI type is lowered into
+    pub fn lowered_type_name(as_type: &impl AsType) -> Result<String, askama::Error> {
+        ffi_type_name(&FfiType::from(as_type.as_type()))
+    }
+
+    /// Is the C# type a value type?  Optionals of value types are `Nullable<T>`.
+    pub fn is_value_type(
+        as_type: &impl AsType,
+        ci: &ComponentInterface,
+    ) -> Result<bool, askama::Error> {
+        Ok(match as_type.as_type() {
+            Type::Int8
+            | Type::UInt8
+            | Type::Int16
+            | Type::UInt16
+            | Type::Int32
+            | Type::UInt32
+            | Type::Int64
+            | Type::UInt64
+            | Type::Float32
+            | Type::Float64
+            | Type::Boolean
+            | Type::Timestamp
+            | Type::Duration => true,
+            Type::Custom { builtin, .. } => is_value_type(&*builtin, ci)?,
+            Type::Enum { name, .. } => ci
+                .get_enum_definition(&name)
+                .map(|e| e.is_flat() && !ci.is_name_used_as_error(&name))
+                .unwrap_or(false),
+            _ => false,
+        })
+    }
+
+    /// The suffix of the `UniffiBufferReader` and `UniffiBufferWriter` methods for a primitive
+    pub fn buffer_method(as_type: &impl AsType) -> Result<&'static str, askama::Error> {
+        Ok(match as_type.as_type() {
+            Type::Int8 => "SByte",
+            Type::UInt8 => "Byte",
+            Type::Int16 => "Int16",
+            Type::UInt16 => "UInt16",
+            Type::Int32 => "Int32",
+            Type::UInt32 => "UInt32",
+            Type::Int64 => "Int64",
+            Type::UInt64 => "UInt64",
+            Type::Float32 => "Single",
+            Type::Float64 => "Double",
+            _ => unimplemented!("{:?} isn't a primitive", as_type.as_type()),
+        })
+    }
+
+    /// The C# rendering of a type name (for records, enums, objects, etc).
+    ///
+    /// Like in Kotlin, error types named `FooError` become `FooException`.
+    pub fn class_name(nm: &str, ci: &ComponentInterface) -> Result<String, askama::Error> {
+        let name = nm.to_upper_camel_case();
+        Ok(match name.strip_suffix("Error") {
+            Some(stripped) if ci.is_name_used_as_error(nm) => format!("{stripped}Exception"),
+            _ => name,
+        })
+    }
+
+    /// The C# rendering of a function or method name.
+    pub fn fn_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(nm.to_upper_camel_case())
+    }
+
+    /// The C# rendering of a variable or argument name.
+    pub fn var_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(quote_keyword(nm.to_lower_camel_case()))
+    }
+
+    /// The C# rendering of an enum variant name.
+    pub fn variant_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(nm.to_upper_camel_case())
+    }
+
+    /// The C# rendering of a property name.
+    ///
+    /// Unnamed fields of enum variants are called `V0`, `V1`, etc.
+    pub fn property_name(field: &Field, index: &usize) -> Result<String, askama::Error> {
+        if field.name().is_empty() {
+            Ok(format!("V{index}"))
+        } else {
+            Ok(field.name().to_upper_camel_case())
+        }
+    }
+
+    /// The C# rendering of a constructor parameter for a field.
+    pub fn param_name(field: &Field, index: &usize) -> Result<String, askama::Error> {
+        if field.name().is_empty() {
+            Ok(format!("v{index}"))
+        } else {
+            var_name(field.name())
+        }
+    }
+
+    fn xml_escape(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    }
+
+    pub fn docstring(docstring: &str, spaces: &i32) -> Result<String, askama::Error> {
+        let middle = textwrap::indent(&xml_escape(&textwrap::dedent(docstring)), "/// ");
+        let wrapped = format!("/// <summary>\n{}\n/// </summary>", middle.trim_end());
+
+        let spaces = usize::try_from(*spaces).unwrap_or_default();
+        Ok(textwrap::indent(&wrapped, &" ".repeat(spaces)))
+    }
+
+    /// A field docstring, as a `<param>` of the record or variant constructor
+    pub fn param_docstring(
+        docstring: &str,
+        name: &str,
+        spaces: &i32,
+    ) -> Result<String, askama::Error> {
+        let text = xml_escape(textwrap::dedent(docstring).trim());
+        let commented = textwrap::indent(&text, "/// ");
+        let wrapped = format!(
+            "/// <param name=\"{name}\">\n{}\n/// </param>",
+            commented.trim_end()
+        );
+
+        let spaces = usize::try_from(*spaces).unwrap_or_default();
+        Ok(textwrap::indent(&wrapped, &" ".repeat(spaces)))
+    }
+}
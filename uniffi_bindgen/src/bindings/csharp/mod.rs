@@ -0,0 +1,40 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # C# bindings backend
+//!
+//! This generates a single `{namespace}.cs` source file, which calls the scaffolding functions
+//! through `[DllImport]` declarations and needs to be compiled into a .NET 6+ project.
+//!
+//! Objects are classes holding a `SafeHandle` to the Rust object, records are `record class`es,
+//! enums with fields are sealed record hierarchies, errors are thrown as exceptions and async
+//! functions return a `Task`, which polls the Rust future with `rust_future_poll`.
+
+use anyhow::{Context, Result};
+use askama::Template;
+use camino::Utf8Path;
+use fs_err as fs;
+
+pub mod gen_csharp;
+mod test;
+pub use gen_csharp::{Config, CsharpWrapper};
+pub use test::{run_script, run_test};
+
+use super::super::interface::ComponentInterface;
+
+// Generate C# bindings for the given ComponentInterface, in the given output directory.
+pub fn write_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+    out_dir: &Utf8Path,
+    _try_format_code: bool,
+) -> Result<()> {
+    gen_csharp::check_supported(ci)?;
+
+    let source = CsharpWrapper::new(config.clone(), ci)
+        .render()
+        .context("failed to render C# bindings")?;
+    fs::write(out_dir.join(format!("{}.cs", ci.namespace())), source)?;
+    Ok(())
+}
@@ -0,0 +1,91 @@
+[UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+internal delegate void UniffiRustFutureContinuationCallback(ulong data, sbyte pollResult);
+
+internal delegate T UniffiRustFutureComplete<T>(ulong handle, ref UniffiRustCallStatus status);
+internal delegate void UniffiRustFutureCompleteVoid(ulong handle, ref UniffiRustCallStatus status);
+
+/// <summary>
+/// Drives Rust futures to completion with `rust_future_poll`.
+/// </summary>
+internal static class UniffiAsync
+{
+    private const sbyte RustFuturePollReady = 0;
+
+    // Rust calls this with the handle passed to `rust_future_poll` when the future should be
+    // polled again.  The delegate is kept in a static field so it's never garbage collected.
+    private static readonly UniffiRustFutureContinuationCallback continuationCallback = OnContinuation;
+    private static readonly IntPtr continuationCallbackPointer =
+        Marshal.GetFunctionPointerForDelegate(continuationCallback);
+
+    private static readonly object continuationsLock = new object();
+    private static readonly Dictionary<ulong, TaskCompletionSource<sbyte>> continuations =
+        new Dictionary<ulong, TaskCompletionSource<sbyte>>();
+    private static ulong nextContinuation = 1;
+
+    private static void OnContinuation(ulong data, sbyte pollResult)
+    {
+        TaskCompletionSource<sbyte> completion;
+        lock (continuationsLock)
+        {
+            completion = continuations[data];
+            continuations.Remove(data);
+        }
+        completion.SetResult(pollResult);
+    }
+
+    private static async Task PollUntilReady(ulong rustFuture, Action<ulong, IntPtr, ulong> poll)
+    {
+        sbyte pollResult;
+        do
+        {
+            // Continuations run asynchronously, rather than on the thread that woke the future.
+            var completion = new TaskCompletionSource<sbyte>(TaskCreationOptions.RunContinuationsAsynchronously);
+            ulong data;
+            lock (continuationsLock)
+            {
+                data = nextContinuation++;
+                continuations[data] = completion;
+            }
+            poll(rustFuture, continuationCallbackPointer, data);
+            pollResult = await completion.Task.ConfigureAwait(false);
+        } while (pollResult != RustFuturePollReady);
+    }
+
+    public static async Task<T> RustCallAsync<T>(
+        ulong rustFuture,
+        Action<ulong, IntPtr, ulong> poll,
+        UniffiRustFutureComplete<T> complete,
+        Action<ulong> free,
+        Func<RustBuffer, Exception>? liftError)
+    {
+        try
+        {
+            await PollUntilReady(rustFuture, poll).ConfigureAwait(false);
+            return UniffiHelpers.RustCallWithError(liftError, (ref UniffiRustCallStatus status) =>
+                complete(rustFuture, ref status));
+        }
+        finally
+        {
+            free(rustFuture);
+        }
+    }
+
+    public static async Task RustCallAsyncVoid(
+        ulong rustFuture,
+        Action<ulong, IntPtr, ulong> poll,
+        UniffiRustFutureCompleteVoid complete,
+        Action<ulong> free,
+        Func<RustBuffer, Exception>? liftError)
+    {
+        try
+        {
+            await PollUntilReady(rustFuture, poll).ConfigureAwait(false);
+            UniffiHelpers.RustCallVoidWithError(liftError, (ref UniffiRustCallStatus status) =>
+                complete(rustFuture, ref status));
+        }
+        finally
+        {
+            free(rustFuture);
+        }
+    }
+}
@@ -0,0 +1,93 @@
+{%- if e.is_flat() %}
+{%- call cs::docstring(e, 0) %}
+public enum {{ type_name }}
+{
+    {%- for variant in e.variants() %}
+    {%- call cs::docstring(variant, 4) %}
+    {{ variant.name()|variant_name }},
+    {%- endfor %}
+}
+
+internal class {{ conv }} : FfiConverterRustBuffer<{{ type_name }}>
+{
+    public static readonly {{ conv }} INSTANCE = new {{ conv }}();
+
+    public override {{ type_name }} Read(UniffiBufferReader reader)
+    {
+        var index = reader.ReadInt32();
+        if (index < 1 || index > {{ e.variants().len() }})
+        {
+            throw new InternalException($"Invalid {{ type_name }} variant: {index}");
+        }
+        return ({{ type_name }})(index - 1);
+    }
+
+    public override void Write({{ type_name }} value, UniffiBufferWriter writer)
+    {
+        writer.WriteInt32((int)value + 1);
+    }
+}
+{%- else %}
+{%- call cs::docstring(e, 0) %}
+public abstract record class {{ type_name }}
+{
+    private {{ type_name }}()
+    {
+    }
+    {%- for variant in e.variants() %}
+{% call cs::docstring(variant, 4) %}
+    {%- call cs::param_docstrings(variant.fields(), 4) %}
+    public sealed record class {{ variant.name()|variant_name }}(
+        {%- for field in variant.fields() %}
+        {{- field|type_name(ci) }} {{ field|property_name(loop.index0) }}
+        {%- if !loop.last %}, {% endif %}
+        {%- endfor -%}
+    ) : {{ type_name }};
+    {%- endfor %}
+}
+
+internal class {{ conv }} : FfiConverterRustBuffer<{{ type_name }}>
+{
+    public static readonly {{ conv }} INSTANCE = new {{ conv }}();
+
+    public override {{ type_name }} Read(UniffiBufferReader reader)
+    {
+        var index = reader.ReadInt32();
+        switch (index)
+        {
+            {%- for variant in e.variants() %}
+            case {{ loop.index }}:
+                {%- if variant.has_fields() %}
+                return new {{ type_name }}.{{ variant.name()|variant_name }}(
+                    {%- for field in variant.fields() %}
+                    {{ field|converter_name }}.INSTANCE.Read(reader)
+                    {%- if !loop.last %},{% endif %}
+                    {%- endfor %}
+                );
+                {%- else %}
+                return new {{ type_name }}.{{ variant.name()|variant_name }}();
+                {%- endif %}
+            {%- endfor %}
+            default:
+                throw new InternalException($"Invalid {{ type_name }} variant: {index}");
+        }
+    }
+
+    public override void Write({{ type_name }} value, UniffiBufferWriter writer)
+    {
+        switch (value)
+        {
+            {%- for variant in e.variants() %}
+            case {{ type_name }}.{{ variant.name()|variant_name }} {% if variant.has_fields() %}variant{% else %}_{% endif %}:
+                writer.WriteInt32({{ loop.index }});
+                {%- for field in variant.fields() %}
+                {{ field|converter_name }}.INSTANCE.Write(variant.{{ field|property_name(loop.index0) }}, writer);
+                {%- endfor %}
+                break;
+            {%- endfor %}
+            default:
+                throw new ArgumentException($"Invalid {{ type_name }} value: {value}", nameof(value));
+        }
+    }
+}
+{%- endif %}
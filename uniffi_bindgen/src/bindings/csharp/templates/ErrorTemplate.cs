@@ -0,0 +1,94 @@
+{%- call cs::docstring(e, 0) %}
+public abstract class {{ type_name }} : Exception
+{
+    private {{ type_name }}(string message) : base(message)
+    {
+    }
+    {%- for variant in e.variants() %}
+    {%- let variant_name = variant.name()|variant_name %}
+{% call cs::docstring(variant, 4) %}
+    public sealed class {{ variant_name }} : {{ type_name }}
+    {
+        {%- if e.is_flat() %}
+        public {{ variant_name }}(string message) : base(message)
+        {
+        }
+        {%- else %}
+        {%- for field in variant.fields() %}
+        {%- call cs::docstring(field, 8) %}
+        public {{ field|type_name(ci) }} {{ field|property_name(loop.index0) }} { get; }
+        {%- endfor %}{% if variant.has_fields() %}
+{% endif %}
+        public {{ variant_name }}(
+            {%- for field in variant.fields() %}
+            {{- field|type_name(ci) }} {{ field|param_name(loop.index0) }}
+            {%- if !loop.last %}, {% endif %}
+            {%- endfor -%}
+        ) : base(
+            {%- if variant.has_fields() %}$"
+            {%- for field in variant.fields() %}
+            {{- field|property_name(loop.index0) }}={ {{- field|param_name(loop.index0) -}} }
+            {%- if !loop.last %}, {% endif %}
+            {%- endfor %}"
+            {%- else %}"{{ variant_name }}"
+            {%- endif %})
+        {
+            {%- for field in variant.fields() %}
+            {{ field|property_name(loop.index0) }} = {{ field|param_name(loop.index0) }};
+            {%- endfor %}
+        }
+        {%- endif %}
+    }
+    {%- endfor %}
+}
+
+internal class {{ conv }} : FfiConverterRustBuffer<{{ type_name }}>
+{
+    public static readonly {{ conv }} INSTANCE = new {{ conv }}();
+
+    public override {{ type_name }} Read(UniffiBufferReader reader)
+    {
+        var index = reader.ReadInt32();
+        switch (index)
+        {
+            {%- for variant in e.variants() %}
+            case {{ loop.index }}:
+                {%- if e.is_flat() %}
+                return new {{ type_name }}.{{ variant.name()|variant_name }}(reader.ReadString());
+                {%- else %}
+                {%- if variant.has_fields() %}
+                return new {{ type_name }}.{{ variant.name()|variant_name }}(
+                    {%- for field in variant.fields() %}
+                    {{ field|converter_name }}.INSTANCE.Read(reader)
+                    {%- if !loop.last %},{% endif %}
+                    {%- endfor %}
+                );
+                {%- else %}
+                return new {{ type_name }}.{{ variant.name()|variant_name }}();
+                {%- endif %}
+                {%- endif %}
+            {%- endfor %}
+            default:
+                throw new InternalException($"Invalid {{ type_name }} variant: {index}");
+        }
+    }
+
+    public override void Write({{ type_name }} value, UniffiBufferWriter writer)
+    {
+        switch (value)
+        {
+            {%- for variant in e.variants() %}
+            case {{ type_name }}.{{ variant.name()|variant_name }} {% if !e.is_flat() && variant.has_fields() %}variant{% else %}_{% endif %}:
+                writer.WriteInt32({{ loop.index }});
+                {%- if !e.is_flat() %}
+                {%- for field in variant.fields() %}
+                {{ field|converter_name }}.INSTANCE.Write(variant.{{ field|property_name(loop.index0) }}, writer);
+                {%- endfor %}
+                {%- endif %}
+                break;
+            {%- endfor %}
+            default:
+                throw new ArgumentException($"Invalid {{ type_name }} value: {value}", nameof(value));
+        }
+    }
+}
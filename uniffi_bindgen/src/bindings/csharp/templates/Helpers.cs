@@ -0,0 +1,429 @@
+/// <summary>
+/// Thrown when the Rust library panics, or when the bindings and the library disagree about the
+/// interface.
+/// </summary>
+public class InternalException : Exception
+{
+    public InternalException(string message) : base(message)
+    {
+    }
+}
+
+/// <summary>
+/// Thrown when the Rust library panics.
+/// </summary>
+public class RustPanicException : InternalException
+{
+    public RustPanicException(string message) : base(message)
+    {
+    }
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal struct RustBuffer
+{
+    public ulong capacity;
+    public ulong len;
+    public IntPtr data;
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal struct ForeignBytes
+{
+    public int len;
+    public IntPtr data;
+}
+
+[StructLayout(LayoutKind.Sequential)]
+internal struct UniffiRustCallStatus
+{
+    public sbyte code;
+    public RustBuffer errorBuf;
+}
+
+internal delegate T UniffiRustCallFunc<T>(ref UniffiRustCallStatus status);
+internal delegate void UniffiRustCallAction(ref UniffiRustCallStatus status);
+
+internal static class UniffiHelpers
+{
+    private const sbyte CallSuccess = 0;
+    private const sbyte CallError = 1;
+    private const sbyte CallUnexpectedError = 2;
+    private const sbyte CallCancelled = 3;
+    private const sbyte CallPanic = 4;
+
+    public static T RustCall<T>(UniffiRustCallFunc<T> call)
+    {
+        return RustCallWithError(null, call);
+    }
+
+    /// <summary>
+    /// Call into Rust, lifting errors with `liftError` for functions that throw.
+    /// </summary>
+    public static T RustCallWithError<T>(Func<RustBuffer, Exception>? liftError, UniffiRustCallFunc<T> call)
+    {
+        var status = new UniffiRustCallStatus();
+        var result = call(ref status);
+        CheckCallStatus(status, liftError);
+        return result;
+    }
+
+    public static void RustCallVoid(UniffiRustCallAction call)
+    {
+        RustCallVoidWithError(null, call);
+    }
+
+    public static void RustCallVoidWithError(Func<RustBuffer, Exception>? liftError, UniffiRustCallAction call)
+    {
+        var status = new UniffiRustCallStatus();
+        call(ref status);
+        CheckCallStatus(status, liftError);
+    }
+
+    public static void CheckCallStatus(UniffiRustCallStatus status, Func<RustBuffer, Exception>? liftError)
+    {
+        switch (status.code)
+        {
+            case CallSuccess:
+                return;
+            case CallError:
+                if (liftError != null)
+                {
+                    throw liftError(status.errorBuf);
+                }
+                FreeRustBuffer(status.errorBuf);
+                throw new InternalException("Unexpected error returned by the Rust library");
+            case CallUnexpectedError:
+                if (status.errorBuf.len > 0)
+                {
+                    // The error message is the raw UTF-8 string, like a lowered `string`.
+                    throw new InternalException(Encoding.UTF8.GetString(RustBufferToBytes(status.errorBuf)));
+                }
+                FreeRustBuffer(status.errorBuf);
+                throw new InternalException("Unexpected Rust error");
+            case CallPanic:
+                if (status.errorBuf.len > 0)
+                {
+                    // The serialized panic details start with the message.
+                    throw new RustPanicException(new UniffiBufferReader(RustBufferToBytes(status.errorBuf)).ReadString());
+                }
+                FreeRustBuffer(status.errorBuf);
+                throw new RustPanicException("Rust panic");
+            case CallCancelled:
+                throw new OperationCanceledException();
+            default:
+                throw new InternalException($"Unknown RustCallStatus code: {status.code}");
+        }
+    }
+
+    /// <summary>
+    /// Copy bytes into a new `RustBuffer`.
+    /// </summary>
+    public static RustBuffer RustBufferFromBytes(byte[] bytes)
+    {
+        var pin = GCHandle.Alloc(bytes, GCHandleType.Pinned);
+        try
+        {
+            var foreign = new ForeignBytes { len = bytes.Length, data = pin.AddrOfPinnedObject() };
+            return RustCall((ref UniffiRustCallStatus status) =>
+                UniffiLib.{{ ci.ffi_rustbuffer_from_bytes().name() }}(foreign, ref status));
+        }
+        finally
+        {
+            pin.Free();
+        }
+    }
+
+    /// <summary>
+    /// Copy the contents of a `RustBuffer` into a byte array, then free it.
+    /// </summary>
+    public static byte[] RustBufferToBytes(RustBuffer buffer)
+    {
+        try
+        {
+            var bytes = new byte[buffer.len];
+            if (buffer.len > 0)
+            {
+                Marshal.Copy(buffer.data, bytes, 0, (int)buffer.len);
+            }
+            return bytes;
+        }
+        finally
+        {
+            FreeRustBuffer(buffer);
+        }
+    }
+
+    public static void FreeRustBuffer(RustBuffer buffer)
+    {
+        RustCallVoid((ref UniffiRustCallStatus status) =>
+            UniffiLib.{{ ci.ffi_rustbuffer_free().name() }}(buffer, ref status));
+    }
+}
+
+/// <summary>
+/// Reads values serialized into a `RustBuffer`, which are in big-endian order.
+/// </summary>
+internal class UniffiBufferReader
+{
+    private readonly byte[] data;
+    private int position;
+
+    public UniffiBufferReader(byte[] data)
+    {
+        this.data = data;
+    }
+
+    public bool HasRemaining => position < data.Length;
+
+    public ReadOnlySpan<byte> ReadBytes(int count)
+    {
+        if (count < 0 || count > data.Length - position)
+        {
+            throw new InternalException("Unexpected end of buffer");
+        }
+        var bytes = new ReadOnlySpan<byte>(data, position, count);
+        position += count;
+        return bytes;
+    }
+
+    public sbyte ReadSByte() => (sbyte)ReadBytes(1)[0];
+    public byte ReadByte() => ReadBytes(1)[0];
+    public short ReadInt16() => BinaryPrimitives.ReadInt16BigEndian(ReadBytes(2));
+    public ushort ReadUInt16() => BinaryPrimitives.ReadUInt16BigEndian(ReadBytes(2));
+    public int ReadInt32() => BinaryPrimitives.ReadInt32BigEndian(ReadBytes(4));
+    public uint ReadUInt32() => BinaryPrimitives.ReadUInt32BigEndian(ReadBytes(4));
+    public long ReadInt64() => BinaryPrimitives.ReadInt64BigEndian(ReadBytes(8));
+    public ulong ReadUInt64() => BinaryPrimitives.ReadUInt64BigEndian(ReadBytes(8));
+    public float ReadSingle() => BinaryPrimitives.ReadSingleBigEndian(ReadBytes(4));
+    public double ReadDouble() => BinaryPrimitives.ReadDoubleBigEndian(ReadBytes(8));
+
+    public string ReadString()
+    {
+        var length = ReadInt32();
+        return Encoding.UTF8.GetString(ReadBytes(length));
+    }
+}
+
+/// <summary>
+/// Serializes values into a byte array for a `RustBuffer`, in big-endian order.
+/// </summary>
+internal class UniffiBufferWriter
+{
+    private readonly MemoryStream stream = new MemoryStream();
+
+    public byte[] ToArray() => stream.ToArray();
+
+    public void WriteBytes(ReadOnlySpan<byte> bytes) => stream.Write(bytes);
+    public void WriteSByte(sbyte value) => stream.WriteByte((byte)value);
+    public void WriteByte(byte value) => stream.WriteByte(value);
+
+    public void WriteInt16(short value)
+    {
+        Span<byte> bytes = stackalloc byte[2];
+        BinaryPrimitives.WriteInt16BigEndian(bytes, value);
+        stream.Write(bytes);
+    }
+
+    public void WriteUInt16(ushort value)
+    {
+        Span<byte> bytes = stackalloc byte[2];
+        BinaryPrimitives.WriteUInt16BigEndian(bytes, value);
+        stream.Write(bytes);
+    }
+
+    public void WriteInt32(int value)
+    {
+        Span<byte> bytes = stackalloc byte[4];
+        BinaryPrimitives.WriteInt32BigEndian(bytes, value);
+        stream.Write(bytes);
+    }
+
+    public void WriteUInt32(uint value)
+    {
+        Span<byte> bytes = stackalloc byte[4];
+        BinaryPrimitives.WriteUInt32BigEndian(bytes, value);
+        stream.Write(bytes);
+    }
+
+    public void WriteInt64(long value)
+    {
+        Span<byte> bytes = stackalloc byte[8];
+        BinaryPrimitives.WriteInt64BigEndian(bytes, value);
+        stream.Write(bytes);
+    }
+
+    public void WriteUInt64(ulong value)
+    {
+        Span<byte> bytes = stackalloc byte[8];
+        BinaryPrimitives.WriteUInt64BigEndian(bytes, value);
+        stream.Write(bytes);
+    }
+
+    public void WriteSingle(float value)
+    {
+        Span<byte> bytes = stackalloc byte[4];
+        BinaryPrimitives.WriteSingleBigEndian(bytes, value);
+        stream.Write(bytes);
+    }
+
+    public void WriteDouble(double value)
+    {
+        Span<byte> bytes = stackalloc byte[8];
+        BinaryPrimitives.WriteDoubleBigEndian(bytes, value);
+        stream.Write(bytes);
+    }
+
+    public void WriteString(string value)
+    {
+        var bytes = Encoding.UTF8.GetBytes(value);
+        WriteInt32(bytes.Length);
+        WriteBytes(bytes);
+    }
+}
+
+/// <summary>
+/// Converts between a C# type and the type it's passed across the FFI as.
+/// </summary>
+internal abstract class FfiConverter<CsType, FfiType>
+{
+    public abstract CsType Lift(FfiType value);
+    public abstract FfiType Lower(CsType value);
+    public abstract CsType Read(UniffiBufferReader reader);
+    public abstract void Write(CsType value, UniffiBufferWriter writer);
+}
+
+/// <summary>
+/// Converter for types which are passed across the FFI serialized into a `RustBuffer`.
+/// </summary>
+internal abstract class FfiConverterRustBuffer<CsType> : FfiConverter<CsType, RustBuffer>
+{
+    public override CsType Lift(RustBuffer value)
+    {
+        var reader = new UniffiBufferReader(UniffiHelpers.RustBufferToBytes(value));
+        var result = Read(reader);
+        if (reader.HasRemaining)
+        {
+            throw new InternalException("Junk data left in buffer after reading a value");
+        }
+        return result;
+    }
+
+    public override RustBuffer Lower(CsType value)
+    {
+        var writer = new UniffiBufferWriter();
+        Write(value, writer);
+        return UniffiHelpers.RustBufferFromBytes(writer.ToArray());
+    }
+}
+
+/// <summary>
+/// Owns a reference to a Rust object, which is freed when the handle is disposed or finalized.
+/// </summary>
+internal abstract class UniffiSafeHandle : SafeHandle
+{
+    protected UniffiSafeHandle(IntPtr pointer) : base(IntPtr.Zero, true)
+    {
+        SetHandle(pointer);
+    }
+
+    public override bool IsInvalid => handle == IntPtr.Zero;
+
+    protected abstract IntPtr Clone(IntPtr pointer, ref UniffiRustCallStatus status);
+    protected abstract void Free(IntPtr pointer, ref UniffiRustCallStatus status);
+
+    /// <summary>
+    /// Each call into Rust consumes a reference, so methods pass a new clone of the pointer.
+    /// </summary>
+    /// <exception cref="ObjectDisposedException">The object has been disposed.</exception>
+    public IntPtr ClonePointer()
+    {
+        var success = false;
+        DangerousAddRef(ref success);
+        try
+        {
+            var pointer = handle;
+            if (pointer == IntPtr.Zero)
+            {
+                throw new ObjectDisposedException(GetType().Name, "The object has already been consumed");
+            }
+            return UniffiHelpers.RustCall((ref UniffiRustCallStatus status) => Clone(pointer, ref status));
+        }
+        finally
+        {
+            if (success)
+            {
+                DangerousRelease();
+            }
+        }
+    }
+
+    /// <summary>
+    /// Methods that take `self` by value pass our own reference instead.  The handle is cleared
+    /// without freeing the Rust object, so the object can't be used afterwards, unless the pointer
+    /// is given back with <see cref="GiveBackPointer"/>.
+    /// </summary>
+    /// <exception cref="ObjectDisposedException">The object has been disposed or consumed.</exception>
+    public IntPtr TakePointer()
+    {
+        var success = false;
+        DangerousAddRef(ref success);
+        try
+        {
+            // Clearing the handle stops `ReleaseHandle()` from freeing it, even if the object is
+            // disposed concurrently.
+            var pointer = System.Threading.Interlocked.Exchange(ref handle, IntPtr.Zero);
+            if (pointer == IntPtr.Zero)
+            {
+                throw new ObjectDisposedException(GetType().Name, "The object has already been consumed");
+            }
+            return pointer;
+        }
+        finally
+        {
+            if (success)
+            {
+                DangerousRelease();
+            }
+        }
+    }
+
+    /// <summary>
+    /// Rust fails with an unexpected error, before taking ownership of the pointer, when it can't
+    /// consume the object, for example because something else still holds a reference to it.  The
+    /// object is still usable then.
+    /// </summary>
+    public void GiveBackPointer(IntPtr pointer)
+    {
+        var success = false;
+        try
+        {
+            DangerousAddRef(ref success);
+        }
+        catch (ObjectDisposedException)
+        {
+        }
+        if (!success)
+        {
+            // The object was disposed meanwhile, so we free the pointer ourselves.
+            UniffiHelpers.RustCallVoid((ref UniffiRustCallStatus status) => Free(pointer, ref status));
+            return;
+        }
+        try
+        {
+            SetHandle(pointer);
+        }
+        finally
+        {
+            // Frees the pointer if the object was disposed while we held the reference
+            DangerousRelease();
+        }
+    }
+
+    protected override bool ReleaseHandle()
+    {
+        var pointer = handle;
+        UniffiHelpers.RustCallVoid((ref UniffiRustCallStatus status) => Free(pointer, ref status));
+        return true;
+    }
+}
@@ -0,0 +1,46 @@
+/// <summary>
+/// The scaffolding functions exported by the Rust library.
+/// </summary>
+internal static class UniffiLib
+{
+    private const string LibraryName = "{{ config.cdylib_name() }}";
+
+    static UniffiLib()
+    {
+        var bindingsContractVersion = {{ ci.uniffi_contract_version() }}u;
+        var scaffoldingContractVersion = {{ ci.ffi_uniffi_contract_version().name() }}();
+        if (bindingsContractVersion != scaffoldingContractVersion)
+        {
+            throw new InternalException(
+                "UniFFI contract version mismatch: try cleaning and rebuilding your project " +
+                $"(the bindings expect version {bindingsContractVersion}, the library has version {scaffoldingContractVersion})");
+        }
+        var mismatches = new List<string>();
+        {%- for check in ci.iter_checksum_checks() %}
+        {
+            var checksum = {{ check.checksum_fn_name }}();
+            if (checksum != {{ check.checksum }})
+            {
+                mismatches.Add($"{{ check.item }}: the bindings expect checksum {{ check.checksum }}, the library has checksum {checksum}");
+            }
+        }
+        {%- endfor %}
+        if (mismatches.Count > 0)
+        {
+            throw new InternalException(
+                "UniFFI API checksum mismatch: try cleaning and rebuilding your project\n" +
+                string.Join("\n", mismatches.ConvertAll(mismatch => $"    {mismatch}")));
+        }
+    }
+    {%- for func in ci.iter_ffi_function_definitions() %}
+
+    [DllImport(LibraryName, CallingConvention = CallingConvention.Cdecl)]
+    public static extern {% match func.return_type() %}{% when Some(return_type) %}{{ return_type|ffi_type_name }}{% when None %}void{% endmatch %} {{ func.name() }}(
+        {%- for arg in func.arguments() %}
+        {{- arg.type_().borrow()|ffi_type_name }} {{ arg.name()|var_name }}
+        {%- if !loop.last || func.has_rust_call_status_arg() %}, {% endif %}
+        {%- endfor %}
+        {%- if func.has_rust_call_status_arg() %}ref UniffiRustCallStatus uniffiCallStatus{% endif -%}
+    );
+    {%- endfor %}
+}
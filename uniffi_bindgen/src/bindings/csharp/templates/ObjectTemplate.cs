@@ -0,0 +1,137 @@
+
+internal sealed class {{ type_name }}SafeHandle : UniffiSafeHandle
+{
+    public {{ type_name }}SafeHandle(IntPtr pointer) : base(pointer)
+    {
+    }
+
+    protected override IntPtr Clone(IntPtr pointer, ref UniffiRustCallStatus status) =>
+        UniffiLib.{{ obj.ffi_object_clone().name() }}(pointer, ref status);
+
+    protected override void Free(IntPtr pointer, ref UniffiRustCallStatus status) =>
+        UniffiLib.{{ obj.ffi_object_free().name() }}(pointer, ref status);
+}
+{% call cs::docstring(obj, 0) %}
+/// <remarks>
+/// The Rust object is freed when this is disposed, or when it's finalized by the garbage
+/// collector.  It can't be used after it's disposed, or after calling a method that consumes it.
+/// </remarks>
+public class {{ type_name }} : IDisposable
+{
+    internal readonly {{ type_name }}SafeHandle uniffiHandle;
+
+    internal {{ type_name }}(IntPtr pointer)
+    {
+        uniffiHandle = new {{ type_name }}SafeHandle(pointer);
+    }
+    {%- match obj.primary_constructor() %}
+    {%- when Some(cons) %}
+{% call cs::docstring(cons, 4) %}
+    public {{ type_name }}({% call cs::arg_list_decl(cons) %})
+        : this({% call cs::to_ffi_call(cons, "") %})
+    {
+    }
+    {%- when None %}
+    {%- endmatch %}
+    {%- for cons in obj.alternate_constructors() %}
+{% call cs::docstring(cons, 4) %}
+    public static {{ type_name }} {{ cons.name()|fn_name }}({% call cs::arg_list_decl(cons) %})
+    {
+        return new {{ type_name }}({% call cs::to_ffi_call(cons, "") %});
+    }
+    {%- endfor %}
+
+    public void Dispose()
+    {
+        uniffiHandle.Dispose();
+    }
+    {%- for meth in obj.methods() %}
+{% call cs::docstring(meth, 4) %}
+    public {% call cs::return_type(meth) %} {{ meth.name()|fn_name }}({% call cs::arg_list_decl(meth) %})
+    {
+        {%- if meth.takes_self_by_value() %}
+        var uniffiPointer = uniffiHandle.TakePointer();
+        {%- match meth.ffi_func().return_type() %}
+        {%- when Some(ffi_return_type) %}
+        {{ ffi_return_type|ffi_type_name }} uniffiResult;
+        {%- when None %}
+        {%- endmatch %}
+        try
+        {
+            {% if meth.ffi_func().return_type().is_some() %}uniffiResult = {% endif %}{% call cs::to_ffi_call(meth, "uniffiPointer") %};
+        }
+        catch (RustPanicException)
+        {
+            throw;
+        }
+        catch (InternalException)
+        {
+            uniffiHandle.GiveBackPointer(uniffiPointer);
+            throw;
+        }
+        {%- match meth.return_type() %}
+        {%- when Some(return_type) %}
+        return {{ return_type|converter_name }}.INSTANCE.Lift(uniffiResult);
+        {%- when None %}
+        {%- endmatch %}
+        {%- else %}
+        {%- call cs::call_body(meth, "uniffiHandle.ClonePointer()") %}
+        {%- endif %}
+    }
+    {%- endfor %}
+    {%- for tm in obj.uniffi_traits() %}
+    {%- match tm %}
+    {%- when UniffiTrait::Display { fmt } %}
+
+    /// <summary>
+    /// The Rust `Display` implementation of the object.
+    /// </summary>
+    public override string ToString()
+    {
+        {%- call cs::call_body(fmt, "uniffiHandle.ClonePointer()") %}
+    }
+    {%- when UniffiTrait::Debug { fmt } %}
+
+    /// <summary>
+    /// The Rust `Debug` implementation of the object.
+    /// </summary>
+    public string ToDebugString()
+    {
+        {%- call cs::call_body(fmt, "uniffiHandle.ClonePointer()") %}
+    }
+    {%- when UniffiTrait::Eq { eq, ne } %}
+
+    /// <summary>
+    /// Compares the Rust objects with their `PartialEq` implementation.
+    /// </summary>
+    public override bool Equals(object? obj)
+    {
+        if (obj is not {{ type_name }} other)
+        {
+            return false;
+        }
+        {%- call cs::call_body(eq, "uniffiHandle.ClonePointer()") %}
+    }
+    {%- when UniffiTrait::Hash { hash } %}
+
+    /// <summary>
+    /// The hash of the Rust object from its `Hash` implementation.
+    /// </summary>
+    public override int GetHashCode()
+    {
+        var hash = {% call cs::to_ffi_call(hash, "uniffiHandle.ClonePointer()") %};
+        return unchecked((int)hash ^ (int)(hash >> 32));
+    }
+    {%- endmatch %}
+    {%- endfor %}
+}
+
+internal class {{ conv }} : FfiConverter<{{ type_name }}, IntPtr>
+{
+    public static readonly {{ conv }} INSTANCE = new {{ conv }}();
+
+    public override {{ type_name }} Lift(IntPtr value) => new {{ type_name }}(value);
+    public override IntPtr Lower({{ type_name }} value) => value.uniffiHandle.ClonePointer();
+    public override {{ type_name }} Read(UniffiBufferReader reader) => Lift(new IntPtr(reader.ReadInt64()));
+    public override void Write({{ type_name }} value, UniffiBufferWriter writer) => writer.WriteInt64(Lower(value).ToInt64());
+}
@@ -0,0 +1,34 @@
+{%- call cs::docstring(rec, 0) %}
+{%- call cs::param_docstrings(rec.fields(), 0) %}
+public record class {{ type_name }}(
+    {%- for field in rec.fields() %}
+    {{ field|type_name(ci) }} {{ field|property_name(loop.index0) }}
+    {%- match self.field_default(rec, loop.index0) %}
+    {%- when Some(default) %} = {{ default }}
+    {%- when None %}
+    {%- endmatch %}
+    {%- if !loop.last %},{% endif %}
+    {%- endfor %}
+);
+
+internal class {{ conv }} : FfiConverterRustBuffer<{{ type_name }}>
+{
+    public static readonly {{ conv }} INSTANCE = new {{ conv }}();
+
+    public override {{ type_name }} Read(UniffiBufferReader reader)
+    {
+        return new {{ type_name }}(
+            {%- for field in rec.fields() %}
+            {{ field|converter_name }}.INSTANCE.Read(reader)
+            {%- if !loop.last %},{% endif %}
+            {%- endfor %}
+        );
+    }
+
+    public override void Write({{ type_name }} value, UniffiBufferWriter writer)
+    {
+        {%- for field in rec.fields() %}
+        {{ field|converter_name }}.INSTANCE.Write(value.{{ field|property_name(loop.index0) }}, writer);
+        {%- endfor %}
+    }
+}
@@ -0,0 +1,5 @@
+{%- call cs::docstring(func, 4) %}
+    public static {% call cs::return_type(func) %} {{ func.name()|fn_name }}({% call cs::arg_list_decl(func) %})
+    {
+        {%- call cs::call_body(func, "") %}
+    }
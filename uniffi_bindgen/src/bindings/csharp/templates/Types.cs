@@ -0,0 +1,226 @@
+{#
+// Each type has an `FfiConverter{canonical name}` class, with methods to lift and lower it, and
+// to read and write it from a buffer.  Records, enums, errors and objects are declared alongside
+// their converter.
+#}
+{%- for type_ in ci.iter_types() %}
+{%- let conv = type_|converter_name %}
+{%- let type_name = type_|type_name(ci) %}
+{%- match type_ %}
+{%- when Type::Boolean %}
+
+internal class {{ conv }} : FfiConverter<bool, sbyte>
+{
+    public static readonly {{ conv }} INSTANCE = new {{ conv }}();
+
+    public override bool Lift(sbyte value) => value != 0;
+    public override sbyte Lower(bool value) => value ? (sbyte)1 : (sbyte)0;
+    public override bool Read(UniffiBufferReader reader) => Lift(reader.ReadSByte());
+    public override void Write(bool value, UniffiBufferWriter writer) => writer.WriteSByte(Lower(value));
+}
+{%- when Type::String %}
+
+internal class {{ conv }} : FfiConverter<string, RustBuffer>
+{
+    public static readonly {{ conv }} INSTANCE = new {{ conv }}();
+
+    // Strings are lowered as the raw UTF-8 bytes, rather than serialized
+    public override string Lift(RustBuffer value) => Encoding.UTF8.GetString(UniffiHelpers.RustBufferToBytes(value));
+    public override RustBuffer Lower(string value) => UniffiHelpers.RustBufferFromBytes(Encoding.UTF8.GetBytes(value));
+
+    public override string Read(UniffiBufferReader reader) => reader.ReadString();
+    public override void Write(string value, UniffiBufferWriter writer) => writer.WriteString(value);
+}
+{%- when Type::Bytes %}
+
+internal class {{ conv }} : FfiConverterRustBuffer<byte[]>
+{
+    public static readonly {{ conv }} INSTANCE = new {{ conv }}();
+
+    public override byte[] Read(UniffiBufferReader reader)
+    {
+        var length = reader.ReadInt32();
+        return reader.ReadBytes(length).ToArray();
+    }
+
+    public override void Write(byte[] value, UniffiBufferWriter writer)
+    {
+        writer.WriteInt32(value.Length);
+        writer.WriteBytes(value);
+    }
+}
+{%- when Type::Timestamp %}
+
+internal class {{ conv }} : FfiConverterRustBuffer<DateTime>
+{
+    public static readonly {{ conv }} INSTANCE = new {{ conv }}();
+
+    // Timestamps are the signed seconds since the epoch, followed by the nanoseconds in the same
+    // direction.  `DateTime` has a resolution of 100 nanoseconds.
+    public override DateTime Read(UniffiBufferReader reader)
+    {
+        var seconds = reader.ReadInt64();
+        var ticks = (long)(reader.ReadUInt32() / 100);
+        if (seconds < 0)
+        {
+            ticks = -ticks;
+        }
+        return DateTime.UnixEpoch.AddTicks(seconds * TimeSpan.TicksPerSecond + ticks);
+    }
+
+    public override void Write(DateTime value, UniffiBufferWriter writer)
+    {
+        var ticks = (value.ToUniversalTime() - DateTime.UnixEpoch).Ticks;
+        writer.WriteInt64(ticks / TimeSpan.TicksPerSecond);
+        writer.WriteUInt32((uint)(Math.Abs(ticks % TimeSpan.TicksPerSecond) * 100));
+    }
+}
+{%- when Type::Duration %}
+
+internal class {{ conv }} : FfiConverterRustBuffer<TimeSpan>
+{
+    public static readonly {{ conv }} INSTANCE = new {{ conv }}();
+
+    public override TimeSpan Read(UniffiBufferReader reader)
+    {
+        var seconds = reader.ReadUInt64();
+        var nanos = reader.ReadUInt32();
+        return TimeSpan.FromTicks((long)seconds * TimeSpan.TicksPerSecond + nanos / 100);
+    }
+
+    public override void Write(TimeSpan value, UniffiBufferWriter writer)
+    {
+        if (value < TimeSpan.Zero)
+        {
+            throw new ArgumentException("Duration must be non-negative", nameof(value));
+        }
+        writer.WriteUInt64((ulong)(value.Ticks / TimeSpan.TicksPerSecond));
+        writer.WriteUInt32((uint)(value.Ticks % TimeSpan.TicksPerSecond * 100));
+    }
+}
+{%- when Type::Optional { inner_type } %}
+{%- let inner_conv = inner_type|converter_name %}
+
+internal class {{ conv }} : FfiConverterRustBuffer<{{ type_name }}>
+{
+    public static readonly {{ conv }} INSTANCE = new {{ conv }}();
+
+    public override {{ type_name }} Read(UniffiBufferReader reader)
+    {
+        if (reader.ReadSByte() == 0)
+        {
+            return null;
+        }
+        return {{ inner_conv }}.INSTANCE.Read(reader);
+    }
+
+    public override void Write({{ type_name }} value, UniffiBufferWriter writer)
+    {
+        if (value == null)
+        {
+            writer.WriteSByte(0);
+            return;
+        }
+        writer.WriteSByte(1);
+        {{ inner_conv }}.INSTANCE.Write(value{% if inner_type|is_value_type(ci) %}.Value{% endif %}, writer);
+    }
+}
+{%- when Type::Sequence { inner_type } %}
+{%- let inner_conv = inner_type|converter_name %}
+
+internal class {{ conv }} : FfiConverterRustBuffer<{{ type_name }}>
+{
+    public static readonly {{ conv }} INSTANCE = new {{ conv }}();
+
+    public override {{ type_name }} Read(UniffiBufferReader reader)
+    {
+        var length = reader.ReadInt32();
+        var result = new {{ type_name }}(length);
+        for (var i = 0; i < length; i++)
+        {
+            result.Add({{ inner_conv }}.INSTANCE.Read(reader));
+        }
+        return result;
+    }
+
+    public override void Write({{ type_name }} value, UniffiBufferWriter writer)
+    {
+        writer.WriteInt32(value.Count);
+        foreach (var item in value)
+        {
+            {{ inner_conv }}.INSTANCE.Write(item, writer);
+        }
+    }
+}
+{%- when Type::Map { key_type, value_type } %}
+{%- let key_conv = key_type|converter_name %}
+{%- let value_conv = value_type|converter_name %}
+
+internal class {{ conv }} : FfiConverterRustBuffer<{{ type_name }}>
+{
+    public static readonly {{ conv }} INSTANCE = new {{ conv }}();
+
+    public override {{ type_name }} Read(UniffiBufferReader reader)
+    {
+        var length = reader.ReadInt32();
+        var result = new {{ type_name }}(length);
+        for (var i = 0; i < length; i++)
+        {
+            var key = {{ key_conv }}.INSTANCE.Read(reader);
+            result[key] = {{ value_conv }}.INSTANCE.Read(reader);
+        }
+        return result;
+    }
+
+    public override void Write({{ type_name }} value, UniffiBufferWriter writer)
+    {
+        writer.WriteInt32(value.Count);
+        foreach (var entry in value)
+        {
+            {{ key_conv }}.INSTANCE.Write(entry.Key, writer);
+            {{ value_conv }}.INSTANCE.Write(entry.Value, writer);
+        }
+    }
+}
+{%- when Type::Custom { name, builtin, module_path } %}
+{%- let builtin_conv = builtin|converter_name %}
+{%- let ffi_type_name = builtin|lowered_type_name %}
+
+// `{{ name }}` is a custom type, which is passed to Rust as its builtin type.
+internal class {{ conv }} : FfiConverter<{{ type_name }}, {{ ffi_type_name }}>
+{
+    public static readonly {{ conv }} INSTANCE = new {{ conv }}();
+
+    public override {{ type_name }} Lift({{ ffi_type_name }} value) => {{ builtin_conv }}.INSTANCE.Lift(value);
+    public override {{ ffi_type_name }} Lower({{ type_name }} value) => {{ builtin_conv }}.INSTANCE.Lower(value);
+    public override {{ type_name }} Read(UniffiBufferReader reader) => {{ builtin_conv }}.INSTANCE.Read(reader);
+    public override void Write({{ type_name }} value, UniffiBufferWriter writer) => {{ builtin_conv }}.INSTANCE.Write(value, writer);
+}
+{%- when Type::Record { name, module_path } %}
+{%- let rec = ci.get_record_definition(name).unwrap() %}
+{% include "RecordTemplate.cs" %}
+{%- when Type::Enum { name, module_path } %}
+{%- let e = ci.get_enum_definition(name).unwrap() %}
+{%- if ci.is_name_used_as_error(name) %}
+{% include "ErrorTemplate.cs" %}
+{%- else %}
+{% include "EnumTemplate.cs" %}
+{%- endif %}
+{%- when Type::Object { name, module_path, imp } %}
+{%- let obj = ci.get_object_definition(name).unwrap() %}
+{% include "ObjectTemplate.cs" %}
+{%- else %}
+{#- Integers and floats #}
+{%- let method = type_|buffer_method %}
+
+internal class {{ conv }} : FfiConverter<{{ type_name }}, {{ type_name }}>
+{
+    public static readonly {{ conv }} INSTANCE = new {{ conv }}();
+
+    public override {{ type_name }} Lift({{ type_name }} value) => value;
+    public override {{ type_name }} Lower({{ type_name }} value) => value;
+    public override {{ type_name }} Read(UniffiBufferReader reader) => reader.Read{{ method }}();
+    public override void Write({{ type_name }} value, UniffiBufferWriter writer) => writer.Write{{ method }}(value);
+}
+{%- endmatch %}
+{%- endfor %}
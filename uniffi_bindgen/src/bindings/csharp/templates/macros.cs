@@ -0,0 +1,107 @@
+{#
+// Template to call into rust. Used in several places.
+// Variable names in `arg_list_decl` should match up with arg lists
+// passed to rust via `arg_list_lowered`
+#}
+
+{%- macro arg_list_decl(func) %}
+    {%- for arg in func.arguments() -%}
+        {{ arg|type_name(ci) }} {{ arg.name()|var_name }}
+        {%- match self.arg_default(func.arguments(), loop.index0) %}
+        {%- when Some(default) %} = {{ default }}
+        {%- when None %}
+        {%- endmatch %}
+        {%- if !loop.last %}, {% endif -%}
+    {%- endfor %}
+{%- endmacro %}
+
+{#
+// The lowered arguments of a call, preceded by the object pointer for methods.
+// `self_ptr` is the expression for the object pointer, or empty for functions and constructors.
+#}
+{%- macro arg_list_lowered(func, self_ptr) %}
+    {%- if !self_ptr.is_empty() %}{{ self_ptr }}{% if !func.arguments().is_empty() %}, {% endif %}{% endif %}
+    {%- for arg in func.arguments() -%}
+        {{ arg|converter_name }}.INSTANCE.Lower({{ arg.name()|var_name }})
+        {%- if !loop.last %}, {% endif -%}
+    {%- endfor %}
+{%- endmacro %}
+
+{%- macro lift_error(func) %}
+    {%- match func.throws_type() %}
+    {%- when Some(e) %}{{ e|converter_name }}.INSTANCE.Lift
+    {%- when None %}null
+    {%- endmatch %}
+{%- endmacro %}
+
+{#
+// The return type of a function or method, including `async` for async functions.
+#}
+{%- macro return_type(func) %}
+    {%- if func.is_async() %}async {% match func.return_type() %}{% when Some(return_type) %}Task<{{ return_type|type_name(ci) }}>{% when None %}Task{% endmatch %}
+    {%- else %}{% match func.return_type() %}{% when Some(return_type) %}{{ return_type|type_name(ci) }}{% when None %}void{% endmatch %}
+    {%- endif %}
+{%- endmacro %}
+
+{#
+// A synchronous call into Rust, which evaluates to the lowered return value.
+#}
+{%- macro to_ffi_call(func, self_ptr) %}
+    {%- match func.ffi_func().return_type() %}
+    {%- when Some(return_type) %}UniffiHelpers.RustCallWithError<{{ return_type|ffi_type_name }}>(
+    {%- when None %}UniffiHelpers.RustCallVoidWithError(
+    {%- endmatch %}{% call lift_error(func) %}, (ref UniffiRustCallStatus status) =>
+            UniffiLib.{{ func.ffi_func().name() }}({% call arg_list_lowered(func, self_ptr) %}
+            {%- if !self_ptr.is_empty() || !func.arguments().is_empty() %}, {% endif %}ref status))
+{%- endmacro %}
+
+{#
+// The body of a function or method: call into Rust and lift the return value.
+#}
+{%- macro call_body(func, self_ptr) %}
+        {%- if func.is_async() %}
+        {%- match func.return_type() %}
+        {%- when Some(return_type) %}
+        return {{ return_type|converter_name }}.INSTANCE.Lift(await UniffiAsync.RustCallAsync<{{ return_type|lowered_type_name }}>(
+        {%- when None %}
+        await UniffiAsync.RustCallAsyncVoid(
+        {%- endmatch %}
+            UniffiLib.{{ func.ffi_func().name() }}({% call arg_list_lowered(func, self_ptr) %}),
+            UniffiLib.{{ func.ffi_rust_future_poll(ci) }},
+            UniffiLib.{{ func.ffi_rust_future_complete(ci) }},
+            UniffiLib.{{ func.ffi_rust_future_free(ci) }},
+            {% call lift_error(func) %}){% if func.return_type().is_some() %}){% endif %};
+        {%- else %}
+        {%- match func.return_type() %}
+        {%- when Some(return_type) %}
+        return {{ return_type|converter_name }}.INSTANCE.Lift({% call to_ffi_call(func, self_ptr) %});
+        {%- when None %}
+        {% call to_ffi_call(func, self_ptr) %};
+        {%- endmatch %}
+        {%- endif %}
+{%- endmacro %}
+
+{%- macro docstring_value(maybe_docstring, indent_spaces) %}
+{%- match maybe_docstring %}
+{%- when Some(docstring) %}
+{{ docstring|docstring(indent_spaces) }}
+{%- else %}
+{%- endmatch %}
+{%- endmacro %}
+
+{%- macro docstring(defn, indent_spaces) %}
+{%- call docstring_value(defn.docstring(), indent_spaces) %}
+{%- endmacro %}
+
+{#
+// The docstrings of the fields of a record or variant, as `<param>` tags.
+#}
+{%- macro param_docstrings(fields, indent_spaces) %}
+{%- for field in fields %}
+{%- match field.docstring() %}
+{%- when Some(docstring) %}
+{{ docstring|param_docstring(field|property_name(loop.index0), indent_spaces) }}
+{%- when None %}
+{%- endmatch %}
+{%- endfor %}
+{%- endmacro %}
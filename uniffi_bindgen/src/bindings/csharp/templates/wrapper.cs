@@ -0,0 +1,39 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+{%- call cs::docstring_value(ci.namespace_docstring(), 0) %}
+
+#nullable enable
+
+using System;
+using System.Buffers.Binary;
+using System.Collections.Generic;
+using System.IO;
+using System.Runtime.InteropServices;
+using System.Text;
+using System.Threading.Tasks;
+
+namespace {{ config.namespace(ci) }};
+
+{% include "Helpers.cs" %}
+{%- if ci.has_async_fns() %}
+
+{% include "Async.cs" %}
+{%- endif %}
+
+{% include "NamespaceLibrary.cs" %}
+
+{% include "Types.cs" %}
+
+/// <summary>
+/// The top-level functions of the `{{ ci.namespace() }}` component.
+/// </summary>
+public static class {{ self.functions_class_name() }}
+{
+    {%- for func in ci.function_definitions() %}
+    {%- if !loop.first %}
+{% endif %}
+    {%- include "TopLevelFunctionTemplate.cs" %}
+    {%- endfor %}
+}
+
+{% import "macros.cs" as cs %}
@@ -0,0 +1,102 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+License, v. 2.0. If a copy of the MPL was not distributed with this
+* file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use crate::{
+    bindings::{RunScriptOptions, TargetLanguage},
+    library_mode::generate_bindings,
+};
+use anyhow::{bail, Context, Result};
+use camino::Utf8Path;
+use fs_err as fs;
+use std::process::Command;
+use uniffi_testing::UniFFITestHelper;
+
+/// Run C# tests for a UniFFI test fixture
+pub fn run_test(tmp_dir: &str, fixture_name: &str, script_file: &str) -> Result<()> {
+    run_script(
+        tmp_dir,
+        fixture_name,
+        script_file,
+        vec![],
+        &RunScriptOptions::default(),
+    )
+}
+
+/// Run a C# script against the generated bindings
+///
+/// The script is compiled with the bindings into a console app, so it can use top-level
+/// statements, and run with `dotnet run`.
+pub fn run_script(
+    tmp_dir: &str,
+    crate_name: &str,
+    script_file: &str,
+    args: Vec<String>,
+    options: &RunScriptOptions,
+) -> Result<()> {
+    let script_path = Utf8Path::new(script_file).canonicalize_utf8()?;
+    let test_helper = UniFFITestHelper::new(crate_name)?;
+    let out_dir = test_helper.create_out_dir(tmp_dir, &script_path)?;
+    let cdylib_path = test_helper.copy_cdylib_to_out_dir(&out_dir)?;
+    generate_bindings(
+        &cdylib_path,
+        None,
+        &[TargetLanguage::CSharp],
+        None,
+        &out_dir,
+        false,
+    )?;
+
+    fs::write(
+        out_dir.join("UniffiTest.csproj"),
+        format!(
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <OutputType>Exe</OutputType>
+    <TargetFramework>{}</TargetFramework>
+    <Nullable>enable</Nullable>
+  </PropertyGroup>
+</Project>
+"#,
+            target_framework()?
+        ),
+    )?;
+    fs::copy(&script_path, out_dir.join(script_path.file_name().unwrap()))?;
+
+    let mut command = Command::new("dotnet");
+    command
+        .current_dir(&out_dir)
+        .env("DOTNET_CLI_TELEMETRY_OPTOUT", "1")
+        .env("DOTNET_NOLOGO", "1")
+        // `DllImport` looks for the cdylib on the library search path
+        .env("LD_LIBRARY_PATH", &out_dir)
+        .env("DYLD_LIBRARY_PATH", &out_dir)
+        .arg("run");
+    if !options.show_compiler_messages {
+        command.arg("--verbosity").arg("quiet");
+    }
+    command.arg("--").args(args);
+    let status = command
+        .spawn()
+        .context("Failed to spawn `dotnet` when running script")?
+        .wait()
+        .context("Failed to wait for `dotnet` when running script")?;
+    if !status.success() {
+        bail!("running `dotnet` failed");
+    }
+    Ok(())
+}
+
+/// The target framework for the installed .NET SDK, like `net8.0`
+fn target_framework() -> Result<String> {
+    let output = Command::new("dotnet")
+        .arg("--version")
+        .output()
+        .context("Failed to run `dotnet --version`")?;
+    if !output.status.success() {
+        bail!("running `dotnet --version` failed");
+    }
+    let version = String::from_utf8(output.stdout)?;
+    let major = version.trim().split('.').next().unwrap_or_default();
+    Ok(format!("net{major}.0"))
+}
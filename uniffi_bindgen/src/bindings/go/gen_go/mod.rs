@@ -0,0 +1,397 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use askama::Template;
+use heck::{ToLowerCamelCase, ToSnakeCase, ToUpperCamelCase};
+use serde::{Deserialize, Serialize};
+
+use crate::interface::*;
+use crate::BindingsConfig;
+
+// https://go.dev/ref/spec#Keywords
+static KEYWORDS: &[&str] = &[
+    "break",
+    "case",
+    "chan",
+    "const",
+    "continue",
+    "default",
+    "defer",
+    "else",
+    "fallthrough",
+    "for",
+    "func",
+    "go",
+    "goto",
+    "if",
+    "import",
+    "interface",
+    "map",
+    "package",
+    "range",
+    "return",
+    "select",
+    "struct",
+    "switch",
+    "type",
+    "var",
+];
+
+/// Append an underscore to names that are Go keywords
+fn quote_keyword(nm: String) -> String {
+    if KEYWORDS.contains(&nm.as_str()) {
+        format!("{nm}_")
+    } else {
+        nm
+    }
+}
+
+// Config options to customize the generated Go package.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Config {
+    package_name: Option<String>,
+    cdylib_name: Option<String>,
+}
+
+impl Config {
+    /// The name of the generated Go package, the component namespace by default.
+    pub fn package_name(&self, ci: &ComponentInterface) -> String {
+        self.package_name
+            .clone()
+            .unwrap_or_else(|| ci.namespace().to_snake_case())
+    }
+
+    /// The name of the compiled Rust library containing the FFI implementation.
+    pub fn cdylib_name(&self) -> String {
+        self.cdylib_name
+            .clone()
+            .unwrap_or_else(|| "uniffi".to_string())
+    }
+}
+
+impl BindingsConfig for Config {
+    fn update_from_ci(&mut self, ci: &ComponentInterface) {
+        self.cdylib_name
+            .get_or_insert_with(|| format!("uniffi_{}", ci.namespace()));
+    }
+
+    fn update_from_cdylib_name(&mut self, cdylib_name: &str) {
+        self.cdylib_name
+            .get_or_insert_with(|| cdylib_name.to_string());
+    }
+
+    fn update_from_dependency_configs(&mut self, _config_map: HashMap<&str, &Self>) {}
+}
+
+/// Bail out if the interface uses features that the Go bindings don't support yet.
+pub fn check_supported(ci: &ComponentInterface) -> Result<()> {
+    if ci.has_async_fns() {
+        bail!("Go bindings don't support async functions yet");
+    }
+    for obj in ci.object_definitions() {
+        if ci.is_name_used_as_error(obj.name()) {
+            bail!(
+                "Go bindings don't support objects as errors yet (found `{}`)",
+                obj.name()
+            );
+        }
+    }
+    if let Some((name, ..)) = ci.iter_external_types().next() {
+        bail!("Go bindings don't support external types yet (found `{name}`)");
+    }
+    Ok(())
+}
+
+/// Get the canonical, unique-within-this-component name for a type.
+///
+/// This is used to name the converter struct for each type.
+pub fn canonical_name(t: &Type) -> String {
+    match t {
+        Type::Int8 => "Int8".into(),
+        Type::UInt8 => "UInt8".into(),
+        Type::Int16 => "Int16".into(),
+        Type::UInt16 => "UInt16".into(),
+        Type::Int32 => "Int32".into(),
+        Type::UInt32 => "UInt32".into(),
+        Type::Int64 => "Int64".into(),
+        Type::UInt64 => "UInt64".into(),
+        Type::Float32 => "Float".into(),
+        Type::Float64 => "Double".into(),
+        Type::Boolean => "Bool".into(),
+        Type::String => "String".into(),
+        Type::Bytes => "Bytes".into(),
+        Type::Timestamp => "Timestamp".into(),
+        Type::Duration => "Duration".into(),
+        Type::Function { .. } => panic!("Function types are not supported by the Go bindings"),
+        Type::Object { name, .. }
+        | Type::Record { name, .. }
+        | Type::Enum { name, .. }
+        | Type::Custom { name, .. }
+        | Type::External { name, .. } => format!("Type{}", name.to_upper_camel_case()),
+        Type::CallbackInterface { name, .. } => {
+            format!("CallbackInterface{}", name.to_upper_camel_case())
+        }
+        Type::Optional { inner_type } => format!("Optional{}", canonical_name(inner_type)),
+        Type::Sequence { inner_type } => format!("Sequence{}", canonical_name(inner_type)),
+        Type::Map {
+            key_type,
+            value_type,
+        } => format!(
+            "Map{}{}",
+            canonical_name(key_type),
+            canonical_name(value_type)
+        ),
+    }
+}
+
+/// A Go function exported to C, to be called through a vtable
+struct ExportedCallback {
+    name: String,
+    arguments: Vec<FfiArgument>,
+    has_rust_call_status_arg: bool,
+}
+
+/// Template for the Go package source, `{package}.go`
+#[derive(Template)]
+#[template(syntax = "go", escape = "none", path = "wrapper.go")]
+pub struct GoWrapper<'a> {
+    config: Config,
+    ci: &'a ComponentInterface,
+    header_filename: String,
+}
+
+impl<'a> GoWrapper<'a> {
+    pub fn new(config: Config, ci: &'a ComponentInterface, header_filename: String) -> Self {
+        Self {
+            config,
+            ci,
+            header_filename,
+        }
+    }
+
+    /// The Go functions that implement the vtables of callback interfaces
+    ///
+    /// cgo only lets the package refer to them as C functions if they're also declared in the
+    /// preamble.
+    fn exported_callbacks(&self) -> Vec<ExportedCallback> {
+        let callback_interfaces = self
+            .ci
+            .callback_interface_definitions()
+            .iter()
+            .map(|cbi| (cbi.name(), cbi.vtable_methods()));
+        let trait_interfaces = self
+            .ci
+            .object_definitions()
+            .iter()
+            .filter(|obj| obj.has_callback_interface())
+            .map(|obj| (obj.name(), obj.vtable_methods()));
+        let mut exported = vec![];
+        for (name, vtable_methods) in callback_interfaces.chain(trait_interfaces) {
+            for (callback, _) in vtable_methods {
+                exported.push(ExportedCallback {
+                    name: filters::exported_callback_name(callback.name()).unwrap(),
+                    arguments: callback.arguments().into_iter().cloned().collect(),
+                    has_rust_call_status_arg: callback.has_rust_call_status_arg(),
+                });
+            }
+            exported.push(ExportedCallback {
+                name: filters::exported_free_name(name).unwrap(),
+                arguments: vec![FfiArgument::new("handle", FfiType::UInt64)],
+                has_rust_call_status_arg: false,
+            });
+        }
+        exported
+    }
+}
+
+mod filters {
+    use super::*;
+
+    /// The Go type for a UniFFI type
+    pub fn type_name(as_type: &impl AsType) -> Result<String, askama::Error> {
+        Ok(match as_type.as_type() {
+            Type::Int8 => "int8".into(),
+            Type::UInt8 => "uint8".into(),
+            Type::Int16 => "int16".into(),
+            Type::UInt16 => "uint16".into(),
+            Type::Int32 => "int32".into(),
+            Type::UInt32 => "uint32".into(),
+            Type::Int64 => "int64".into(),
+            Type::UInt64 => "uint64".into(),
+            Type::Float32 => "float32".into(),
+            Type::Float64 => "float64".into(),
+            Type::Boolean => "bool".into(),
+            Type::String => "string".into(),
+            Type::Bytes => "[]byte".into(),
+            Type::Timestamp => "time.Time".into(),
+            Type::Duration => "time.Duration".into(),
+            // Trait interfaces that can be implemented in Go are Go interfaces
+            Type::Object { name, imp, .. } if imp.has_callback_interface() => class_name(&name)?,
+            Type::Object { name, .. } => format!("*{}", class_name(&name)?),
+            Type::Function { .. } => panic!("Function types are not supported by the Go bindings"),
+            Type::Record { name, .. }
+            | Type::Enum { name, .. }
+            | Type::Custom { name, .. }
+            | Type::External { name, .. }
+            | Type::CallbackInterface { name, .. } => class_name(&name)?,
+            Type::Optional { inner_type } if is_nilable(&*inner_type)? => type_name(&*inner_type)?,
+            Type::Optional { inner_type } => format!("*{}", type_name(&*inner_type)?),
+            Type::Sequence { inner_type } => format!("[]{}", type_name(&*inner_type)?),
+            Type::Map {
+                key_type,
+                value_type,
+            } => format!(
+                "map[{}]{}",
+                type_name(&*key_type)?,
+                type_name(&*value_type)?
+            ),
+        })
+    }
+
+    pub fn converter_name(as_type: &impl AsType) -> Result<String, askama::Error> {
+        Ok(format!(
+            "uniffiConverter{}",
+            canonical_name(&as_type.as_type())
+        ))
+    }
+
+    /// The cgo type for an FFI type
+    pub fn ffi_type_name(ffi_type: &FfiType) -> Result<String, askama::Error> {
+        Ok(match ffi_type {
+            FfiType::RustArcPtr(_) | FfiType::VoidPointer => "unsafe.Pointer".into(),
+            FfiType::Reference(inner) => format!("*{}", ffi_type_name(inner)?),
+            _ => format!(
+                "C.{}",
+                crate::bindings::c::gen_c::filters::ffi_type_name(ffi_type)?
+            ),
+        })
+    }
+
+    /// The cgo type that a UniFFI type is lowered into
+    pub fn lowered_type_name(as_type: &impl AsType) -> Result<String, askama::Error> {
+        ffi_type_name(&FfiType::from(as_type.as_type()))
+    }
+
+    /// The C type for an FFI type, for declarations in the cgo preamble
+    pub fn c_type_name(ffi_type: &FfiType) -> Result<String, askama::Error> {
+        crate::bindings::c::gen_c::filters::ffi_type_name(ffi_type)
+    }
+
+    /// Can a value of this type already be `nil`?  Optionals of these types don't need a pointer.
+    pub fn is_nilable(as_type: &impl AsType) -> Result<bool, askama::Error> {
+        Ok(matches!(
+            as_type.as_type(),
+            Type::Object { .. } | Type::CallbackInterface { .. }
+        ))
+    }
+
+    /// Is this type passed across the FFI as a serialized `RustBuffer`?
+    pub fn is_serialized(as_type: &impl AsType) -> Result<bool, askama::Error> {
+        Ok(
+            matches!(FfiType::from(as_type.as_type()), FfiType::RustBuffer(_))
+                && !matches!(as_type.as_type(), Type::String | Type::Custom { .. }),
+        )
+    }
+
+    /// The Go rendering of a type name (for records, enums, objects, etc).
+    pub fn class_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(nm.to_upper_camel_case())
+    }
+
+    /// The name of the Go struct for objects implemented in Rust
+    ///
+    /// Trait interfaces that can be implemented in Go take the plain name for their Go interface.
+    pub fn object_impl_name(obj: &Object) -> Result<String, askama::Error> {
+        let class_name = class_name(obj.name())?;
+        Ok(if obj.has_callback_interface() {
+            format!("{class_name}Impl")
+        } else {
+            class_name
+        })
+    }
+
+    /// The Go rendering of a function or method name, exported from the package.
+    pub fn fn_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(nm.to_upper_camel_case())
+    }
+
+    /// The Go rendering of a variable or argument name.
+    pub fn var_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(quote_keyword(nm.to_lower_camel_case()))
+    }
+
+    /// The Go rendering of a struct field name, exported from the package.
+    ///
+    /// Unnamed fields of enum variants are called `Field0`, `Field1`, etc.
+    pub fn field_name(field: &Field, index: &usize) -> Result<String, askama::Error> {
+        if field.name().is_empty() {
+            Ok(format!("Field{index}"))
+        } else {
+            Ok(field.name().to_upper_camel_case())
+        }
+    }
+
+    /// The Go rendering of a field name in an error variant struct
+    ///
+    /// A field called `Error` would clash with the `Error()` method, so it gets a trailing
+    /// underscore.
+    pub fn error_field_name(field: &Field, index: &usize) -> Result<String, askama::Error> {
+        let name = field_name(field, index)?;
+        Ok(if name == "Error" {
+            format!("{name}_")
+        } else {
+            name
+        })
+    }
+
+    /// The name that cgo gives to a field of a C struct
+    pub fn cgo_field_name(nm: &str) -> Result<String, askama::Error> {
+        if KEYWORDS.contains(&nm) {
+            Ok(format!("_{nm}"))
+        } else {
+            Ok(nm.to_owned())
+        }
+    }
+
+    /// The name of the Go function exported to C for a vtable method
+    pub fn exported_callback_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(format!("uniffi{}", nm.to_upper_camel_case()))
+    }
+
+    /// The name of the Go function exported to C to free a foreign implementation
+    pub fn exported_free_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(format!(
+            "uniffiCallbackInterface{}Free",
+            nm.to_upper_camel_case()
+        ))
+    }
+
+    /// The C typedef name for an FFI callback function
+    pub fn ffi_callback_name(nm: &str) -> Result<String, askama::Error> {
+        crate::bindings::c::gen_c::filters::ffi_callback_name(nm)
+    }
+
+    /// The C typedef name for an FFI struct
+    pub fn ffi_struct_name(nm: &str) -> Result<String, askama::Error> {
+        crate::bindings::c::gen_c::filters::ffi_struct_name(nm)
+    }
+
+    pub fn docstring(docstring: &str, spaces: &i32) -> Result<String, askama::Error> {
+        let comment = textwrap::indent(&textwrap::dedent(docstring), "// ");
+        let spaces = usize::try_from(*spaces).unwrap_or_default();
+        Ok(textwrap::indent(
+            comment.trim_end(),
+            &"\t".repeat(spaces / 4),
+        ))
+    }
+
+    /// The arguments of a callback, as Go parameters
+    pub fn ffi_arg_type(arg: &FfiArgument) -> Result<String, askama::Error> {
+        ffi_type_name(arg.type_().borrow())
+    }
+}
@@ -0,0 +1,65 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Go bindings backend
+//!
+//! This generates a cgo package in `{out_dir}/{package}`, made of a `{package}.go` source file
+//! and the `{package}.h` C header from the `c` backend, which declares the scaffolding functions.
+//!
+//! Objects are structs holding a pointer to the Rust object, which is freed by a finalizer or by
+//! calling `Destroy()`.  Errors are returned as Go `error` values, and callback interfaces are Go
+//! interfaces whose implementations are called through the vtables registered with Rust.
+
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use askama::Template;
+use camino::Utf8Path;
+use fs_err as fs;
+
+pub mod gen_go;
+mod test;
+pub use gen_go::{Config, GoWrapper};
+pub use test::{run_script, run_test};
+
+use super::super::interface::ComponentInterface;
+use super::c;
+
+// Generate a Go package for the given ComponentInterface, in the given output directory.
+pub fn write_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+    out_dir: &Utf8Path,
+    try_format_code: bool,
+) -> Result<()> {
+    gen_go::check_supported(ci)?;
+
+    let package_name = config.package_name(ci);
+    let package_dir = out_dir.join(&package_name);
+    fs::create_dir_all(&package_dir)?;
+
+    let header_filename = format!("{package_name}.h");
+    let ffi_config = c::Config::with_header_filename(header_filename.clone(), config.cdylib_name());
+    fs::write(
+        package_dir.join(&header_filename),
+        c::generate_header(&ffi_config, ci)?,
+    )?;
+
+    let go_file = package_dir.join(format!("{package_name}.go"));
+    let source = GoWrapper::new(config.clone(), ci, header_filename)
+        .render()
+        .context("failed to render Go bindings")?;
+    fs::write(&go_file, source)?;
+
+    if try_format_code {
+        if let Err(e) = Command::new("gofmt").arg("-w").arg(&go_file).output() {
+            println!(
+                "Warning: Unable to auto-format {} using gofmt: {e:?}",
+                go_file.file_name().unwrap(),
+            )
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+{%- for (callback, meth) in vtable_methods.iter() %}
+{%- let exported_name = callback.name()|exported_callback_name %}
+
+//export {{ exported_name }}
+func {{ exported_name }}(
+	{%- for arg in callback.arguments() -%}
+	{{ arg.name()|var_name }} {{ arg|ffi_arg_type }}, {% endfor -%}
+	uniffiCallStatus *C.RustCallStatus) {
+	defer uniffiCallbackRecover(uniffiCallStatus)
+	uniffiImpl := uniffiHandleMap{{ iface_name }}.get(uint64(uniffiHandle))
+	{% match meth.return_type() %}{% when Some(return_type) %}uniffiResult{% if meth.throws() %}, uniffiErr{% endif %} := {% when None %}{% if meth.throws() %}uniffiErr := {% endif %}{% endmatch -%}
+	uniffiImpl.{{ meth.name()|fn_name }}({% call go::arg_list_lifted(meth) %})
+	{%- match meth.throws_type() %}
+	{%- when Some(error_type) %}
+	if uniffiErr != nil {
+		var uniffiTypedErr {{ error_type|type_name }}
+		if !errors.As(uniffiErr, &uniffiTypedErr) {
+			// Reported to Rust as an unexpected error
+			panic(uniffiErr)
+		}
+		uniffiCallStatus.code = uniffiCallError
+		uniffiCallStatus.errorBuf = {{ error_type|converter_name }}{}.lower(uniffiTypedErr)
+		return
+	}
+	{%- when None %}
+	{%- endmatch %}
+	{%- match meth.return_type() %}
+	{%- when Some(return_type) %}
+	*uniffiOutReturn = {{ return_type|converter_name }}{}.lower(uniffiResult)
+	{%- when None %}
+	{%- endmatch %}
+}
+{%- endfor %}
+
+//export {{ free_name }}
+func {{ free_name }}(handle C.uint64_t) {
+	uniffiHandleMap{{ iface_name }}.remove(uint64(handle))
+}
+
+func init() {
+	{%- let vtable_type = vtable.name()|ffi_struct_name %}
+	// The vtable is allocated with malloc, since Rust keeps a pointer to it.
+	vtable := (*C.{{ vtable_type }})(C.malloc(C.size_t(unsafe.Sizeof(C.{{ vtable_type }}{}))))
+	{%- for (callback, meth) in vtable_methods.iter() %}
+	vtable.{{ meth.name()|cgo_field_name }} = C.{{ callback.name()|ffi_callback_name }}(C.{{ callback.name()|exported_callback_name }})
+	{%- endfor %}
+	vtable.uniffi_free = C.{{ "CallbackInterfaceFree"|ffi_callback_name }}(C.{{ free_name }})
+	C.{{ ffi_init_callback.name() }}(vtable)
+}
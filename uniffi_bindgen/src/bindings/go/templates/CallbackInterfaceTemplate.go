@@ -0,0 +1,32 @@
+{%- let iface_name = cbi.name()|class_name %}
+{%- call go::docstring(cbi, 0) %}
+type {{ iface_name }} interface {
+	{%- for meth in cbi.methods() %}
+	{%- call go::docstring(meth, 4) %}
+	{{ meth.name()|fn_name }}({% call go::arg_list_decl(meth) %}){% call go::return_type(meth) %}
+	{%- endfor %}
+}
+
+// Go implementations are kept here while Rust holds a handle to them.
+var uniffiHandleMap{{ iface_name }} = newUniffiHandleMap[{{ iface_name }}]()
+
+func ({{ conv }}) lift(handle C.uint64_t) {{ iface_name }} {
+	return uniffiHandleMap{{ iface_name }}.get(uint64(handle))
+}
+
+func ({{ conv }}) lower(value {{ iface_name }}) C.uint64_t {
+	return C.uint64_t(uniffiHandleMap{{ iface_name }}.insert(value))
+}
+
+func ({{ conv }}) read(reader *bytes.Reader) {{ iface_name }} {
+	return uniffiHandleMap{{ iface_name }}.get(uniffiReadValue[uint64](reader))
+}
+
+func ({{ conv }}) write(writer *bytes.Buffer, value {{ iface_name }}) {
+	uniffiWriteValue(writer, uniffiHandleMap{{ iface_name }}.insert(value))
+}
+{%- let vtable_methods = cbi.vtable_methods() %}
+{%- let vtable = cbi.vtable_definition() %}
+{%- let ffi_init_callback = cbi.ffi_init_callback() %}
+{%- let free_name = cbi.name()|exported_free_name %}
+{%- include "CallbackInterfaceImpl.go" %}
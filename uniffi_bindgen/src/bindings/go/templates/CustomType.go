@@ -0,0 +1,21 @@
+{%- let builtin_conv = builtin|converter_name %}
+{%- let ffi_type_name = builtin|lowered_type_name %}
+
+// {{ type_name }} is a custom type, which is passed to Rust as its builtin type.
+type {{ type_name }} = {{ builtin|type_name }}
+
+func ({{ conv }}) lift(value {{ ffi_type_name }}) {{ type_name }} {
+	return {{ builtin_conv }}{}.lift(value)
+}
+
+func ({{ conv }}) lower(value {{ type_name }}) {{ ffi_type_name }} {
+	return {{ builtin_conv }}{}.lower(value)
+}
+
+func ({{ conv }}) read(reader *bytes.Reader) {{ type_name }} {
+	return {{ builtin_conv }}{}.read(reader)
+}
+
+func ({{ conv }}) write(writer *bytes.Buffer, value {{ type_name }}) {
+	{{ builtin_conv }}{}.write(writer, value)
+}
@@ -0,0 +1,68 @@
+{%- if e.is_flat() %}
+{%- call go::docstring(e, 0) %}
+type {{ type_name }} uint
+
+const (
+	{%- for variant in e.variants() %}
+	{%- call go::docstring(variant, 4) %}
+	{{ type_name }}{{ variant.name()|class_name }}{% if loop.first %} {{ type_name }} = iota + 1{% endif %}
+	{%- endfor %}
+)
+
+func ({{ conv }}) read(reader *bytes.Reader) {{ type_name }} {
+	value := uniffiReadValue[int32](reader)
+	if value < 1 || value > {{ e.variants().len() }} {
+		panic(&InternalError{Message: fmt.Sprintf("invalid {{ type_name }} variant: %d", value)})
+	}
+	return {{ type_name }}(value)
+}
+
+func ({{ conv }}) write(writer *bytes.Buffer, value {{ type_name }}) {
+	uniffiWriteValue(writer, int32(value))
+}
+{%- else %}
+{%- call go::docstring(e, 0) %}
+type {{ type_name }} interface {
+	is{{ type_name }}()
+}
+{%- for variant in e.variants() %}
+{% call go::docstring(variant, 0) %}
+type {{ type_name }}{{ variant.name()|class_name }} struct {
+	{%- for field in variant.fields() %}
+	{%- call go::docstring(field, 4) %}
+	{{ field|field_name(loop.index0) }} {{ field|type_name }}
+	{%- endfor %}
+}
+
+func ({{ type_name }}{{ variant.name()|class_name }}) is{{ type_name }}() {}
+{%- endfor %}
+
+func ({{ conv }}) read(reader *bytes.Reader) {{ type_name }} {
+	switch index := uniffiReadValue[int32](reader); index {
+	{%- for variant in e.variants() %}
+	case {{ loop.index }}:
+		return {{ type_name }}{{ variant.name()|class_name }}{
+			{%- for field in variant.fields() %}
+			{{ field|field_name(loop.index0) }}: {{ field|converter_name }}{}.read(reader),
+			{%- endfor %}
+		}
+	{%- endfor %}
+	default:
+		panic(&InternalError{Message: fmt.Sprintf("invalid {{ type_name }} variant: %d", index)})
+	}
+}
+
+func ({{ conv }}) write(writer *bytes.Buffer, value {{ type_name }}) {
+	switch variant := value.(type) {
+	{%- for variant in e.variants() %}
+	case {{ type_name }}{{ variant.name()|class_name }}:
+		uniffiWriteValue(writer, int32({{ loop.index }}))
+		{%- for field in variant.fields() %}
+		{{ field|converter_name }}{}.write(writer, variant.{{ field|field_name(loop.index0) }})
+		{%- endfor %}
+	{%- endfor %}
+	default:
+		panic(fmt.Sprintf("invalid {{ type_name }} value: %#v", variant))
+	}
+}
+{%- endif %}
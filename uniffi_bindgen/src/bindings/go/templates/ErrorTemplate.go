@@ -0,0 +1,74 @@
+{%- call go::docstring(e, 0) %}
+{%- if e.docstring().is_some() %}
+//
+{%- endif %}
+// Functions return {{ type_name }} as an `error`. Use `errors.As` to get the struct for a variant.
+type {{ type_name }} interface {
+	error
+	is{{ type_name }}()
+}
+{%- for variant in e.variants() %}
+{%- let variant_name = variant.name()|class_name %}
+{% call go::docstring(variant, 0) %}
+type {{ type_name }}{{ variant_name }} struct {
+	{%- if e.is_flat() %}
+	Message string
+	{%- else %}
+	{%- for field in variant.fields() %}
+	{%- call go::docstring(field, 4) %}
+	{{ field|error_field_name(loop.index0) }} {{ field|type_name }}
+	{%- endfor %}
+	{%- endif %}
+}
+
+func (e *{{ type_name }}{{ variant_name }}) Error() string {
+	{%- if e.is_flat() %}
+	return e.Message
+	{%- else if variant.has_fields() %}
+	return fmt.Sprintf("{{ type_name }}.{{ variant_name }}%+v", *e)
+	{%- else %}
+	return "{{ type_name }}.{{ variant_name }}"
+	{%- endif %}
+}
+
+func (*{{ type_name }}{{ variant_name }}) is{{ type_name }}() {}
+{%- endfor %}
+
+func (c {{ conv }}) liftError(value C.RustBuffer) error {
+	return c.lift(value)
+}
+
+func ({{ conv }}) read(reader *bytes.Reader) {{ type_name }} {
+	switch index := uniffiReadValue[int32](reader); index {
+	{%- for variant in e.variants() %}
+	case {{ loop.index }}:
+		{%- if e.is_flat() %}
+		return &{{ type_name }}{{ variant.name()|class_name }}{Message: string(uniffiReadBytes(reader))}
+		{%- else %}
+		return &{{ type_name }}{{ variant.name()|class_name }}{
+			{%- for field in variant.fields() %}
+			{{ field|error_field_name(loop.index0) }}: {{ field|converter_name }}{}.read(reader),
+			{%- endfor %}
+		}
+		{%- endif %}
+	{%- endfor %}
+	default:
+		panic(&InternalError{Message: fmt.Sprintf("invalid {{ type_name }} variant: %d", index)})
+	}
+}
+
+func ({{ conv }}) write(writer *bytes.Buffer, value {{ type_name }}) {
+	switch variant := value.(type) {
+	{%- for variant in e.variants() %}
+	case *{{ type_name }}{{ variant.name()|class_name }}:
+		uniffiWriteValue(writer, int32({{ loop.index }}))
+		{%- if !e.is_flat() %}
+		{%- for field in variant.fields() %}
+		{{ field|converter_name }}{}.write(writer, variant.{{ field|error_field_name(loop.index0) }})
+		{%- endfor %}
+		{%- endif %}
+	{%- endfor %}
+	default:
+		panic(fmt.Sprintf("invalid {{ type_name }} value: %#v", variant))
+	}
+}
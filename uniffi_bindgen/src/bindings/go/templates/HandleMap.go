@@ -0,0 +1,37 @@
+
+// uniffiHandleMap holds the Go implementations of callback interfaces while Rust code refers to
+// them by handle.
+type uniffiHandleMap[T any] struct {
+	lock   sync.Mutex
+	values map[uint64]T
+	next   uint64
+}
+
+func newUniffiHandleMap[T any]() *uniffiHandleMap[T] {
+	return &uniffiHandleMap[T]{values: map[uint64]T{}, next: 1}
+}
+
+func (m *uniffiHandleMap[T]) insert(value T) uint64 {
+	m.lock.Lock()
+	defer m.lock.Unlock()
+	handle := m.next
+	m.next++
+	m.values[handle] = value
+	return handle
+}
+
+func (m *uniffiHandleMap[T]) get(handle uint64) T {
+	m.lock.Lock()
+	defer m.lock.Unlock()
+	value, ok := m.values[handle]
+	if !ok {
+		panic(&InternalError{Message: "invalid callback handle"})
+	}
+	return value
+}
+
+func (m *uniffiHandleMap[T]) remove(handle uint64) {
+	m.lock.Lock()
+	defer m.lock.Unlock()
+	delete(m.values, handle)
+}
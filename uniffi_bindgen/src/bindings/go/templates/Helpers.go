@@ -0,0 +1,237 @@
+// InternalError is the panic value when the Rust library panics, or when the bindings and the
+// library disagree about the interface.
+type InternalError struct {
+	Message string
+	// Panic is set when the Rust library panicked.
+	Panic bool
+}
+
+func (e *InternalError) Error() string {
+	return "uniffi internal error: " + e.Message
+}
+
+const (
+	uniffiCallSuccess         = 0
+	uniffiCallError           = 1
+	uniffiCallUnexpectedError = 2
+	uniffiCallPanic           = 4
+)
+
+func init() {
+	bindingsContractVersion := {{ ci.uniffi_contract_version() }}
+	scaffoldingContractVersion := C.{{ ci.ffi_uniffi_contract_version().name() }}()
+	if bindingsContractVersion != int(scaffoldingContractVersion) {
+		panic(&InternalError{Message: fmt.Sprintf(
+			"UniFFI contract version mismatch: try cleaning and rebuilding your project "+
+				"(the bindings expect version %d, the library has version %d)",
+			bindingsContractVersion, scaffoldingContractVersion,
+		)})
+	}
+	var mismatches []string
+	{%- for check in ci.iter_checksum_checks() %}
+	if checksum := C.{{ check.checksum_fn_name }}(); checksum != {{ check.checksum }} {
+		mismatches = append(mismatches, fmt.Sprintf("    {{ check.item }}: the bindings expect checksum {{ check.checksum }}, the library has checksum %d", checksum))
+	}
+	{%- endfor %}
+	if len(mismatches) > 0 {
+		panic(&InternalError{Message: "UniFFI API checksum mismatch: try cleaning and rebuilding your project\n" + strings.Join(mismatches, "\n")})
+	}
+}
+
+// uniffiCheckCallStatus turns a failed call into an error, using liftError for the error type of
+// the function, or into a panic for unexpected errors.
+func uniffiCheckCallStatus(status C.RustCallStatus, liftError func(C.RustBuffer) error) error {
+	switch status.code {
+	case uniffiCallSuccess:
+		return nil
+	case uniffiCallError:
+		if liftError != nil {
+			return liftError(status.errorBuf)
+		}
+		uniffiFreeRustBuffer(status.errorBuf)
+		panic(&InternalError{Message: "unexpected error returned by the Rust library"})
+	case uniffiCallUnexpectedError:
+		if status.errorBuf.len > 0 {
+			// The error message is the raw UTF-8 string, like a lowered `String`.
+			panic(&InternalError{Message: string(uniffiRustBufferToBytes(status.errorBuf))})
+		}
+		uniffiFreeRustBuffer(status.errorBuf)
+		panic(&InternalError{Message: "unexpected Rust error"})
+	case uniffiCallPanic:
+		if status.errorBuf.len > 0 {
+			// The serialized panic details start with the message.
+			reader := bytes.NewReader(uniffiRustBufferToBytes(status.errorBuf))
+			panic(&InternalError{Message: string(uniffiReadBytes(reader)), Panic: true})
+		}
+		uniffiFreeRustBuffer(status.errorBuf)
+		panic(&InternalError{Message: "Rust panic", Panic: true})
+	default:
+		panic(&InternalError{Message: fmt.Sprintf("unknown RustCallStatus code: %d", status.code)})
+	}
+}
+
+func uniffiRustCall[T any](liftError func(C.RustBuffer) error, call func(*C.RustCallStatus) T) (T, error) {
+	var status C.RustCallStatus
+	result := call(&status)
+	return result, uniffiCheckCallStatus(status, liftError)
+}
+
+func uniffiRustCallVoid(liftError func(C.RustBuffer) error, call func(*C.RustCallStatus)) error {
+	var status C.RustCallStatus
+	call(&status)
+	return uniffiCheckCallStatus(status, liftError)
+}
+
+// uniffiRustBufferFromBytes copies Go bytes into a new RustBuffer.
+func uniffiRustBufferFromBytes(b []byte) C.RustBuffer {
+	var data *C.uint8_t
+	if len(b) > 0 {
+		data = (*C.uint8_t)(unsafe.Pointer(&b[0]))
+	}
+	foreign := C.ForeignBytes{len: C.int32_t(len(b)), data: data}
+	buf, _ := uniffiRustCall(nil, func(status *C.RustCallStatus) C.RustBuffer {
+		return C.{{ ci.ffi_rustbuffer_from_bytes().name() }}(foreign, status)
+	})
+	runtime.KeepAlive(b)
+	return buf
+}
+
+// uniffiRustBufferToBytes copies the contents of a RustBuffer into Go memory, then frees it.
+func uniffiRustBufferToBytes(rb C.RustBuffer) []byte {
+	defer uniffiFreeRustBuffer(rb)
+	if rb.len == 0 {
+		return []byte{}
+	}
+	return C.GoBytes(unsafe.Pointer(rb.data), C.int(rb.len))
+}
+
+func uniffiFreeRustBuffer(rb C.RustBuffer) {
+	_ = uniffiRustCallVoid(nil, func(status *C.RustCallStatus) {
+		C.{{ ci.ffi_rustbuffer_free().name() }}(rb, status)
+	})
+}
+
+// Values are serialized into a RustBuffer in big-endian order.
+func uniffiReadValue[T any](reader *bytes.Reader) T {
+	var value T
+	if err := binary.Read(reader, binary.BigEndian, &value); err != nil {
+		panic(&InternalError{Message: "unexpected end of buffer"})
+	}
+	return value
+}
+
+func uniffiWriteValue[T any](writer *bytes.Buffer, value T) {
+	_ = binary.Write(writer, binary.BigEndian, value)
+}
+
+func uniffiReadBytes(reader *bytes.Reader) []byte {
+	length := uniffiReadValue[int32](reader)
+	if length < 0 || int(length) > reader.Len() {
+		panic(&InternalError{Message: "unexpected end of buffer"})
+	}
+	value := make([]byte, length)
+	_, _ = reader.Read(value)
+	return value
+}
+
+func uniffiWriteBytes(writer *bytes.Buffer, value []byte) {
+	uniffiWriteValue(writer, int32(len(value)))
+	writer.Write(value)
+}
+
+type uniffiBufferReader[T any] interface {
+	read(reader *bytes.Reader) T
+}
+
+type uniffiBufferWriter[T any] interface {
+	write(writer *bytes.Buffer, value T)
+}
+
+func uniffiLiftFromRustBuffer[T any](converter uniffiBufferReader[T], rb C.RustBuffer) T {
+	reader := bytes.NewReader(uniffiRustBufferToBytes(rb))
+	value := converter.read(reader)
+	if reader.Len() > 0 {
+		panic(&InternalError{Message: "junk data left in buffer after reading a value"})
+	}
+	return value
+}
+
+func uniffiLowerIntoRustBuffer[T any](converter uniffiBufferWriter[T], value T) C.RustBuffer {
+	var writer bytes.Buffer
+	converter.write(&writer, value)
+	return uniffiRustBufferFromBytes(writer.Bytes())
+}
+
+// uniffiObject holds a pointer to a Rust object.
+//
+// Each call into Rust consumes a reference, so methods pass a new clone of the pointer.  The lock
+// stops the pointer from being freed while it's being cloned.
+type uniffiObject struct {
+	lock    sync.RWMutex
+	pointer unsafe.Pointer
+	clone   func(unsafe.Pointer, *C.RustCallStatus) unsafe.Pointer
+	free    func(unsafe.Pointer, *C.RustCallStatus)
+}
+
+func (o *uniffiObject) clonePointer() unsafe.Pointer {
+	o.lock.RLock()
+	defer o.lock.RUnlock()
+	if o.pointer == nil {
+		panic(&InternalError{Message: "object used after Destroy() or after being consumed"})
+	}
+	pointer, _ := uniffiRustCall(nil, func(status *C.RustCallStatus) unsafe.Pointer {
+		return o.clone(o.pointer, status)
+	})
+	return pointer
+}
+
+// consume passes our own reference to a method that takes `self` by value, after which the object
+// can't be used.
+//
+// Rust fails with an unexpected error, before taking ownership of the pointer, when it can't
+// consume the object, for example because something else still holds a reference to it.  We take
+// the pointer back then, so the object is still usable.
+func (o *uniffiObject) consume(call func(unsafe.Pointer)) {
+	pointer := o.takePointer()
+	defer func() {
+		if r := recover(); r != nil {
+			if internalError, ok := r.(*InternalError); ok && !internalError.Panic {
+				o.lock.Lock()
+				o.pointer = pointer
+				o.lock.Unlock()
+			}
+			panic(r)
+		}
+	}()
+	call(pointer)
+}
+
+func (o *uniffiObject) takePointer() unsafe.Pointer {
+	o.lock.Lock()
+	defer o.lock.Unlock()
+	if o.pointer == nil {
+		panic(&InternalError{Message: "object used after Destroy() or after being consumed"})
+	}
+	pointer := o.pointer
+	o.pointer = nil
+	return pointer
+}
+
+func (o *uniffiObject) destroy() {
+	o.lock.Lock()
+	defer o.lock.Unlock()
+	if o.pointer != nil {
+		_ = uniffiRustCallVoid(nil, func(status *C.RustCallStatus) {
+			o.free(o.pointer, status)
+		})
+		o.pointer = nil
+	}
+}
+
+// uniffiCallbackRecover reports a panic in a Go callback to Rust as an unexpected error.
+func uniffiCallbackRecover(status *C.RustCallStatus) {
+	if r := recover(); r != nil {
+		status.code = uniffiCallUnexpectedError
+		status.errorBuf = uniffiRustBufferFromBytes([]byte(fmt.Sprint(r)))
+	}
+}
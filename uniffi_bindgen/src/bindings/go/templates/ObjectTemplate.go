@@ -0,0 +1,122 @@
+{%- let class_name = obj|object_impl_name %}
+{%- let iface_name = obj.name()|class_name %}
+{%- if obj.has_callback_interface() %}
+{%- call go::docstring(obj, 0) %}
+type {{ iface_name }} interface {
+	{%- for meth in obj.methods() %}
+	{%- call go::docstring(meth, 4) %}
+	{{ meth.name()|fn_name }}({% call go::arg_list_decl(meth) %}){% call go::return_type(meth) %}
+	{%- endfor %}
+}
+
+// {{ class_name }} is the {{ iface_name }} implementation for objects created in Rust.
+type {{ class_name }} struct {
+{%- else %}
+{%- call go::docstring(obj, 0) %}
+type {{ class_name }} struct {
+{%- endif %}
+	uniffiObject uniffiObject
+}
+{%- match obj.primary_constructor() %}
+{%- when Some(cons) %}
+{% call go::docstring(cons, 0) %}
+func New{{ class_name }}({% call go::arg_list_decl(cons) %}) {% if cons.throws() %}(*{{ class_name }}, error){% else %}*{{ class_name }}{% endif %} {
+	{%- call go::constructor_body(cons, obj) %}
+}
+{%- when None %}
+{%- endmatch %}
+{%- for cons in obj.alternate_constructors() %}
+{% call go::docstring(cons, 0) %}
+func New{{ class_name }}{{ cons.name()|fn_name }}({% call go::arg_list_decl(cons) %}) {% if cons.throws() %}(*{{ class_name }}, error){% else %}*{{ class_name }}{% endif %} {
+	{%- call go::constructor_body(cons, obj) %}
+}
+{%- endfor %}
+
+// Destroy frees the Rust object now, rather than when the garbage collector finalizes it.
+//
+// The object must not be used afterwards, like after calling a method that consumes it.
+func (uniffiSelf *{{ class_name }}) Destroy() {
+	runtime.SetFinalizer(uniffiSelf, nil)
+	uniffiSelf.uniffiObject.destroy()
+}
+{%- for meth in obj.methods() %}
+{% call go::docstring(meth, 0) %}
+func (uniffiSelf *{{ class_name }}) {{ meth.name()|fn_name }}({% call go::arg_list_decl(meth) %}){% call go::return_type(meth) %} {
+	{%- if meth.takes_self_by_value() %}
+	{%- call go::consuming_call_body(meth) %}
+	{%- else %}
+	{%- call go::call_body(meth, "uniffiSelf.uniffiObject.clonePointer(), ") %}
+	{%- endif %}
+}
+{%- endfor %}
+{%- for tm in obj.uniffi_traits() %}
+{%- match tm %}
+{%- when UniffiTrait::Display { fmt } %}
+
+// String implements `fmt.Stringer` with the Rust `Display` implementation.
+func (uniffiSelf *{{ class_name }}) String() string {
+	{%- call go::call_body(fmt, "uniffiSelf.uniffiObject.clonePointer(), ") %}
+}
+{%- when UniffiTrait::Debug { fmt } %}
+
+// GoString implements `fmt.GoStringer` with the Rust `Debug` implementation.
+func (uniffiSelf *{{ class_name }}) GoString() string {
+	{%- call go::call_body(fmt, "uniffiSelf.uniffiObject.clonePointer(), ") %}
+}
+{%- when UniffiTrait::Eq { eq, ne } %}
+
+// Equals compares the Rust objects with their `PartialEq` implementation.
+func (uniffiSelf *{{ class_name }}) Equals({% call go::arg_list_decl(eq) %}) bool {
+	{%- call go::call_body(eq, "uniffiSelf.uniffiObject.clonePointer(), ") %}
+}
+{%- when UniffiTrait::Hash { hash } %}
+
+// Hash returns the hash of the Rust object from its `Hash` implementation.
+func (uniffiSelf *{{ class_name }}) Hash() uint64 {
+	{%- call go::call_body(hash, "uniffiSelf.uniffiObject.clonePointer(), ") %}
+}
+{%- endmatch %}
+{%- endfor %}
+
+func ({{ conv }}) lift(pointer unsafe.Pointer) {{ obj|type_name }} {
+	value := &{{ class_name }}{uniffiObject{
+		pointer: pointer,
+		clone: func(pointer unsafe.Pointer, status *C.RustCallStatus) unsafe.Pointer {
+			return C.{{ obj.ffi_object_clone().name() }}(pointer, status)
+		},
+		free: func(pointer unsafe.Pointer, status *C.RustCallStatus) {
+			C.{{ obj.ffi_object_free().name() }}(pointer, status)
+		},
+	}}
+	runtime.SetFinalizer(value, (*{{ class_name }}).Destroy)
+	return value
+}
+
+func ({{ conv }}) lower(value {{ obj|type_name }}) unsafe.Pointer {
+	{%- if obj.has_callback_interface() %}
+	// Rust treats every {{ iface_name }} as a foreign implementation and calls it through the
+	// vtable, so objects created in Rust are also passed by handle.  Handles are small integers
+	// which never point into the Go heap.
+	return unsafe.Pointer(uintptr(uniffiHandleMap{{ iface_name }}.insert(value)))
+	{%- else %}
+	return value.uniffiObject.clonePointer()
+	{%- endif %}
+}
+
+func (c {{ conv }}) read(reader *bytes.Reader) {{ obj|type_name }} {
+	return c.lift(unsafe.Pointer(uintptr(uniffiReadValue[uint64](reader))))
+}
+
+func (c {{ conv }}) write(writer *bytes.Buffer, value {{ obj|type_name }}) {
+	uniffiWriteValue(writer, uint64(uintptr(c.lower(value))))
+}
+{%- if obj.has_callback_interface() %}
+
+// Go implementations are kept here while Rust holds a handle to them.
+var uniffiHandleMap{{ iface_name }} = newUniffiHandleMap[{{ iface_name }}]()
+{%- let vtable_methods = obj.vtable_methods() %}
+{%- let vtable = obj.vtable_definition().expect("trait interface should have a vtable") %}
+{%- let ffi_init_callback = obj.ffi_init_callback() %}
+{%- let free_name = obj.name()|exported_free_name %}
+{%- include "CallbackInterfaceImpl.go" %}
+{%- endif %}
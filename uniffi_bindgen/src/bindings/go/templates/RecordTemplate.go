@@ -0,0 +1,21 @@
+{%- call go::docstring(rec, 0) %}
+type {{ type_name }} struct {
+	{%- for field in rec.fields() %}
+	{%- call go::docstring(field, 4) %}
+	{{ field|field_name(loop.index0) }} {{ field|type_name }}
+	{%- endfor %}
+}
+
+func ({{ conv }}) read(reader *bytes.Reader) {{ type_name }} {
+	return {{ type_name }}{
+		{%- for field in rec.fields() %}
+		{{ field|field_name(loop.index0) }}: {{ field|converter_name }}{}.read(reader),
+		{%- endfor %}
+	}
+}
+
+func ({{ conv }}) write(writer *bytes.Buffer, value {{ type_name }}) {
+	{%- for field in rec.fields() %}
+	{{ field|converter_name }}{}.write(writer, value.{{ field|field_name(loop.index0) }})
+	{%- endfor %}
+}
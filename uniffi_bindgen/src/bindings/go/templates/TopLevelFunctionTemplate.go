@@ -0,0 +1,4 @@
+{%- call go::docstring(func, 0) %}
+func {{ func.name()|fn_name }}({% call go::arg_list_decl(func) %}){% call go::return_type(func) %} {
+	{%- call go::call_body(func, "") %}
+}
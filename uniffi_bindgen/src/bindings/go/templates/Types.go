@@ -0,0 +1,210 @@
+{#
+// Each type has a `uniffiConverter{canonical name}` struct, with methods to lift and lower it,
+// and to read and write it from a buffer.
+#}
+{%- for type_ in ci.iter_types() %}
+{%- let conv = type_|converter_name %}
+{%- let type_name = type_|type_name %}
+
+type {{ conv }} struct{}
+{%- if type_|is_serialized %}
+
+func (c {{ conv }}) lift(value C.RustBuffer) {{ type_name }} {
+	return uniffiLiftFromRustBuffer[{{ type_name }}](c, value)
+}
+
+func (c {{ conv }}) lower(value {{ type_name }}) C.RustBuffer {
+	return uniffiLowerIntoRustBuffer[{{ type_name }}](c, value)
+}
+{%- endif %}
+{%- match type_ %}
+{%- when Type::Boolean %}
+
+func ({{ conv }}) lift(value C.int8_t) bool {
+	return value != 0
+}
+
+func ({{ conv }}) lower(value bool) C.int8_t {
+	if value {
+		return 1
+	}
+	return 0
+}
+
+func ({{ conv }}) read(reader *bytes.Reader) bool {
+	return uniffiReadValue[int8](reader) != 0
+}
+
+func (c {{ conv }}) write(writer *bytes.Buffer, value bool) {
+	uniffiWriteValue(writer, int8(c.lower(value)))
+}
+{%- when Type::String %}
+
+func ({{ conv }}) lift(value C.RustBuffer) string {
+	// Strings are lowered as the raw UTF-8 bytes, rather than serialized
+	return string(uniffiRustBufferToBytes(value))
+}
+
+func ({{ conv }}) lower(value string) C.RustBuffer {
+	return uniffiRustBufferFromBytes([]byte(value))
+}
+
+func ({{ conv }}) read(reader *bytes.Reader) string {
+	return string(uniffiReadBytes(reader))
+}
+
+func ({{ conv }}) write(writer *bytes.Buffer, value string) {
+	uniffiWriteBytes(writer, []byte(value))
+}
+{%- when Type::Bytes %}
+
+func ({{ conv }}) read(reader *bytes.Reader) []byte {
+	return uniffiReadBytes(reader)
+}
+
+func ({{ conv }}) write(writer *bytes.Buffer, value []byte) {
+	uniffiWriteBytes(writer, value)
+}
+{%- when Type::Timestamp %}
+
+func ({{ conv }}) read(reader *bytes.Reader) time.Time {
+	// Timestamps are the signed seconds since the epoch, followed by the nanoseconds in the same
+	// direction.
+	seconds := uniffiReadValue[int64](reader)
+	nanos := int64(uniffiReadValue[uint32](reader))
+	if seconds >= 0 {
+		return time.Unix(seconds, nanos)
+	}
+	return time.Unix(seconds, -nanos)
+}
+
+func ({{ conv }}) write(writer *bytes.Buffer, value time.Time) {
+	seconds := value.Unix()
+	nanos := int64(value.Nanosecond())
+	if seconds < 0 && nanos > 0 {
+		seconds++
+		nanos = int64(time.Second) - nanos
+	}
+	uniffiWriteValue(writer, seconds)
+	uniffiWriteValue(writer, uint32(nanos))
+}
+{%- when Type::Duration %}
+
+func ({{ conv }}) read(reader *bytes.Reader) time.Duration {
+	seconds := uniffiReadValue[uint64](reader)
+	nanos := uniffiReadValue[uint32](reader)
+	return time.Duration(seconds)*time.Second + time.Duration(nanos)
+}
+
+func ({{ conv }}) write(writer *bytes.Buffer, value time.Duration) {
+	if value < 0 {
+		panic("invalid duration, must be non-negative")
+	}
+	uniffiWriteValue(writer, uint64(value/time.Second))
+	uniffiWriteValue(writer, uint32(value%time.Second))
+}
+{%- when Type::Optional { inner_type } %}
+{%- let inner_conv = inner_type|converter_name %}
+
+func ({{ conv }}) read(reader *bytes.Reader) {{ type_name }} {
+	if uniffiReadValue[int8](reader) == 0 {
+		return nil
+	}
+	{%- if inner_type|is_nilable %}
+	return {{ inner_conv }}{}.read(reader)
+	{%- else %}
+	value := {{ inner_conv }}{}.read(reader)
+	return &value
+	{%- endif %}
+}
+
+func ({{ conv }}) write(writer *bytes.Buffer, value {{ type_name }}) {
+	if value == nil {
+		uniffiWriteValue(writer, int8(0))
+		return
+	}
+	uniffiWriteValue(writer, int8(1))
+	{%- if inner_type|is_nilable %}
+	{{ inner_conv }}{}.write(writer, value)
+	{%- else %}
+	{{ inner_conv }}{}.write(writer, *value)
+	{%- endif %}
+}
+{%- when Type::Sequence { inner_type } %}
+{%- let inner_conv = inner_type|converter_name %}
+
+func ({{ conv }}) read(reader *bytes.Reader) {{ type_name }} {
+	length := uniffiReadValue[int32](reader)
+	value := make({{ type_name }}, 0, length)
+	for i := int32(0); i < length; i++ {
+		value = append(value, {{ inner_conv }}{}.read(reader))
+	}
+	return value
+}
+
+func ({{ conv }}) write(writer *bytes.Buffer, value {{ type_name }}) {
+	uniffiWriteValue(writer, int32(len(value)))
+	for _, item := range value {
+		{{ inner_conv }}{}.write(writer, item)
+	}
+}
+{%- when Type::Map { key_type, value_type } %}
+{%- let key_conv = key_type|converter_name %}
+{%- let value_conv = value_type|converter_name %}
+
+func ({{ conv }}) read(reader *bytes.Reader) {{ type_name }} {
+	length := uniffiReadValue[int32](reader)
+	value := make({{ type_name }}, length)
+	for i := int32(0); i < length; i++ {
+		key := {{ key_conv }}{}.read(reader)
+		value[key] = {{ value_conv }}{}.read(reader)
+	}
+	return value
+}
+
+func ({{ conv }}) write(writer *bytes.Buffer, value {{ type_name }}) {
+	uniffiWriteValue(writer, int32(len(value)))
+	for key, item := range value {
+		{{ key_conv }}{}.write(writer, key)
+		{{ value_conv }}{}.write(writer, item)
+	}
+}
+{%- when Type::Custom { name, builtin, module_path } %}
+{% include "CustomType.go" %}
+{%- when Type::Record { name, module_path } %}
+{%- let rec = ci.get_record_definition(name).unwrap() %}
+{% include "RecordTemplate.go" %}
+{%- when Type::Enum { name, module_path } %}
+{%- let e = ci.get_enum_definition(name).unwrap() %}
+{%- if ci.is_name_used_as_error(name) %}
+{% include "ErrorTemplate.go" %}
+{%- else %}
+{% include "EnumTemplate.go" %}
+{%- endif %}
+{%- when Type::Object { name, module_path, imp } %}
+{%- let obj = ci.get_object_definition(name).unwrap() %}
+{% include "ObjectTemplate.go" %}
+{%- when Type::CallbackInterface { name, module_path } %}
+{%- let cbi = ci.get_callback_interface_definition(name).unwrap() %}
+{% include "CallbackInterfaceTemplate.go" %}
+{%- else %}
+{#- Integers and floats #}
+{%- let ffi_type_name = type_|lowered_type_name %}
+
+func ({{ conv }}) lift(value {{ ffi_type_name }}) {{ type_name }} {
+	return {{ type_name }}(value)
+}
+
+func ({{ conv }}) lower(value {{ type_name }}) {{ ffi_type_name }} {
+	return {{ ffi_type_name }}(value)
+}
+
+func ({{ conv }}) read(reader *bytes.Reader) {{ type_name }} {
+	return uniffiReadValue[{{ type_name }}](reader)
+}
+
+func ({{ conv }}) write(writer *bytes.Buffer, value {{ type_name }}) {
+	uniffiWriteValue(writer, value)
+}
+{%- endmatch %}
+{%- endfor %}
@@ -0,0 +1,135 @@
+{#
+// Template to call into rust. Used in several places.
+// Variable names in `arg_list_decl` should match up with arg lists
+// passed to rust via `arg_list_lowered`
+#}
+
+{%- macro arg_list_decl(func) %}
+	{%- for arg in func.arguments() -%}
+		{{ arg.name()|var_name }} {{ arg|type_name }}
+		{%- if !loop.last %}, {% endif -%}
+	{%- endfor %}
+{%- endmacro %}
+
+{%- macro arg_list_lowered(func) %}
+	{%- for arg in func.arguments() -%}
+		{{ arg|converter_name }}{}.lower({{ arg.name()|var_name }}), {% endfor -%}
+{%- endmacro %}
+
+{%- macro arg_list_lifted(func) %}
+	{%- for arg in func.arguments() -%}
+		{{ arg|converter_name }}{}.lift({{ arg.name()|var_name }})
+		{%- if !loop.last %}, {% endif -%}
+	{%- endfor %}
+{%- endmacro %}
+
+{#
+// The results of a function: its return type, followed by `error` if it can fail.
+// This includes the leading space, so that nothing is added for functions without results.
+#}
+{%- macro return_type(func) %}
+	{%- match func.return_type() %}
+	{%- when Some(return_type) %}
+	{%- if func.throws() %} ({{ return_type|type_name }}, error){% else %} {{ return_type|type_name }}{% endif %}
+	{%- when None %}
+	{%- if func.throws() %} error{% endif %}
+	{%- endmatch %}
+{%- endmacro %}
+
+{%- macro lift_error(func) %}
+	{%- match func.throws_type() %}
+	{%- when Some(e) %}{{ e|converter_name }}{}.liftError
+	{%- when None %}nil
+	{%- endmatch %}
+{%- endmacro %}
+
+{#
+// The body of a function or method: call into Rust and lift the return value.
+// `self_ptr` is the expression for the object pointer of methods, or empty for functions.
+#}
+{%- macro call_body(func, self_ptr) %}
+	{%- match func.return_type() %}
+	{%- when Some(return_type) %}
+	uniffiResult, uniffiErr := uniffiRustCall({% call lift_error(func) %}, func(uniffiStatus *C.RustCallStatus) {{ return_type|lowered_type_name }} {
+		return C.{{ func.ffi_func().name() }}({{ self_ptr }}{% call arg_list_lowered(func) %}uniffiStatus)
+	})
+	{%- if func.throws() %}
+	if uniffiErr != nil {
+		var uniffiZero {{ return_type|type_name }}
+		return uniffiZero, uniffiErr
+	}
+	return {{ return_type|converter_name }}{}.lift(uniffiResult), nil
+	{%- else %}
+	_ = uniffiErr
+	return {{ return_type|converter_name }}{}.lift(uniffiResult)
+	{%- endif %}
+	{%- when None %}
+	{% if func.throws() %}return{% else %}_ ={% endif %} uniffiRustCallVoid({% call lift_error(func) %}, func(uniffiStatus *C.RustCallStatus) {
+		C.{{ func.ffi_func().name() }}({{ self_ptr }}{% call arg_list_lowered(func) %}uniffiStatus)
+	})
+	{%- endmatch %}
+{%- endmacro %}
+
+{#
+// The body of a method that takes `self` by value, passing our own reference to Rust.
+#}
+{%- macro consuming_call_body(func) %}
+	{%- match func.return_type() %}
+	{%- when Some(return_type) %}
+	var uniffiResult {{ return_type|lowered_type_name }}
+	var uniffiErr error
+	uniffiSelf.uniffiObject.consume(func(uniffiPointer unsafe.Pointer) {
+		uniffiResult, uniffiErr = uniffiRustCall({% call lift_error(func) %}, func(uniffiStatus *C.RustCallStatus) {{ return_type|lowered_type_name }} {
+			return C.{{ func.ffi_func().name() }}(uniffiPointer, {% call arg_list_lowered(func) %}uniffiStatus)
+		})
+	})
+	{%- if func.throws() %}
+	if uniffiErr != nil {
+		var uniffiZero {{ return_type|type_name }}
+		return uniffiZero, uniffiErr
+	}
+	return {{ return_type|converter_name }}{}.lift(uniffiResult), nil
+	{%- else %}
+	_ = uniffiErr
+	return {{ return_type|converter_name }}{}.lift(uniffiResult)
+	{%- endif %}
+	{%- when None %}
+	var uniffiErr error
+	uniffiSelf.uniffiObject.consume(func(uniffiPointer unsafe.Pointer) {
+		uniffiErr = uniffiRustCallVoid({% call lift_error(func) %}, func(uniffiStatus *C.RustCallStatus) {
+			C.{{ func.ffi_func().name() }}(uniffiPointer, {% call arg_list_lowered(func) %}uniffiStatus)
+		})
+	})
+	{% if func.throws() %}return uniffiErr{% else %}_ = uniffiErr{% endif %}
+	{%- endmatch %}
+{%- endmacro %}
+
+{#
+// The body of a constructor, returning the lifted object.
+#}
+{%- macro constructor_body(cons, obj) %}
+	uniffiPointer, uniffiErr := uniffiRustCall({% call lift_error(cons) %}, func(uniffiStatus *C.RustCallStatus) unsafe.Pointer {
+		return C.{{ cons.ffi_func().name() }}({% call arg_list_lowered(cons) %}uniffiStatus)
+	})
+	{%- if cons.throws() %}
+	if uniffiErr != nil {
+		return nil, uniffiErr
+	}
+	return {{ obj|converter_name }}{}.lift(uniffiPointer), nil
+	{%- else %}
+	_ = uniffiErr
+	return {{ obj|converter_name }}{}.lift(uniffiPointer)
+	{%- endif %}
+{%- endmacro %}
+
+{%- macro docstring_value(maybe_docstring, indent_spaces) %}
+{%- match maybe_docstring %}
+{%- when Some(docstring) %}
+{{ docstring|docstring(indent_spaces) }}
+{%- else %}
+{%- endmatch %}
+{%- endmacro %}
+
+{%- macro docstring(defn, indent_spaces) %}
+{%- call docstring_value(defn.docstring(), indent_spaces) %}
+{%- endmacro %}
@@ -0,0 +1,50 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+{% call go::docstring_value(ci.namespace_docstring(), 0) %}
+package {{ config.package_name(ci) }}
+
+/*
+#cgo LDFLAGS: -l{{ config.cdylib_name() }}
+#include <stdlib.h>
+#include "{{ header_filename }}"
+{%- for exported in self.exported_callbacks() %}
+void {{ exported.name }}(
+	{%- for arg in exported.arguments -%}
+	{{ arg.type_().borrow()|c_type_name }} {{ arg.name() }}
+	{%- if !loop.last || exported.has_rust_call_status_arg %}, {% endif %}
+	{%- endfor -%}
+	{%- if exported.has_rust_call_status_arg %}RustCallStatus* uniffi_call_status{% endif -%}
+);
+{%- endfor %}
+*/
+import "C"
+
+import (
+	"bytes"
+	"encoding/binary"
+	"errors"
+	"fmt"
+	"runtime"
+	"strings"
+	"sync"
+	"time"
+	"unsafe"
+)
+
+// Not every interface needs every import.
+var (
+	_ = errors.As
+	_ = runtime.SetFinalizer
+	_ = sync.NewCond
+	_ = time.Unix
+)
+
+{% include "Helpers.go" %}
+{% include "HandleMap.go" %}
+{% include "Types.go" %}
+
+{%- for func in ci.function_definitions() %}
+{% include "TopLevelFunctionTemplate.go" %}
+{%- endfor %}
+
+{% import "macros.go" as go %}
@@ -0,0 +1,71 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+License, v. 2.0. If a copy of the MPL was not distributed with this
+* file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use crate::{
+    bindings::{RunScriptOptions, TargetLanguage},
+    library_mode::generate_bindings,
+};
+use anyhow::{bail, Context, Result};
+use camino::Utf8Path;
+use fs_err as fs;
+use std::process::Command;
+use uniffi_testing::UniFFITestHelper;
+
+/// Run Go tests for a UniFFI test fixture
+pub fn run_test(tmp_dir: &str, fixture_name: &str, script_file: &str) -> Result<()> {
+    run_script(
+        tmp_dir,
+        fixture_name,
+        script_file,
+        vec![],
+        &RunScriptOptions::default(),
+    )
+}
+
+/// Run a Go program against the generated package
+///
+/// The script is the `main` package of a module named `uniffi_test`, so it can import the bindings
+/// as `uniffi_test/{package}`.
+pub fn run_script(
+    tmp_dir: &str,
+    crate_name: &str,
+    script_file: &str,
+    args: Vec<String>,
+    _options: &RunScriptOptions,
+) -> Result<()> {
+    let script_path = Utf8Path::new(script_file).canonicalize_utf8()?;
+    let test_helper = UniFFITestHelper::new(crate_name)?;
+    let out_dir = test_helper.create_out_dir(tmp_dir, &script_path)?;
+    let cdylib_path = test_helper.copy_cdylib_to_out_dir(&out_dir)?;
+    generate_bindings(
+        &cdylib_path,
+        None,
+        &[TargetLanguage::Go],
+        None,
+        &out_dir,
+        false,
+    )?;
+
+    fs::write(out_dir.join("go.mod"), "module uniffi_test\n\ngo 1.18\n")?;
+    fs::copy(&script_path, out_dir.join("main.go"))?;
+
+    let mut command = Command::new("go");
+    command
+        .current_dir(&out_dir)
+        .env("CGO_ENABLED", "1")
+        .env("CGO_LDFLAGS", format!("-L{out_dir} -Wl,-rpath,{out_dir}"))
+        .arg("run")
+        .arg(".")
+        .args(args);
+    // `go run` compiles and runs in one step, so compiler messages and panics both go to stderr
+    let status = command
+        .spawn()
+        .context("Failed to spawn `go` when running script")?
+        .wait()
+        .context("Failed to wait for `go` when running script")?;
+    if !status.success() {
+        bail!("running `go` failed");
+    }
+    Ok(())
+}
@@ -37,7 +37,13 @@ macro_rules! impl_code_type_for_compound {
 
             impl CodeType for $T  {
                 fn type_label(&self, ci: &ComponentInterface) -> String {
-                    format!($type_label_pattern, super::KotlinCodeOracle.find(self.inner()).type_label(ci))
+                    let inner = super::KotlinCodeOracle.find(self.inner()).type_label(ci);
+                    // Function types need parentheses before they can be made nullable
+                    let inner = match self.inner() {
+                        Type::Function { .. } => format!("({inner})"),
+                        _ => inner,
+                    };
+                    format!($type_label_pattern, inner)
                 }
 
                 fn canonical_name(&self) -> String {
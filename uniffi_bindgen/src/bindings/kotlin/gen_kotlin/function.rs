@@ -0,0 +1,54 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use super::CodeType;
+use crate::backend::Type;
+use crate::ComponentInterface;
+
+#[derive(Debug)]
+pub struct FunctionCodeType {
+    args: Vec<Type>,
+    ret: Option<Type>,
+}
+
+impl FunctionCodeType {
+    pub fn new(args: Vec<Type>, ret: Option<Type>) -> Self {
+        Self { args, ret }
+    }
+}
+
+impl CodeType for FunctionCodeType {
+    fn type_label(&self, ci: &ComponentInterface) -> String {
+        let args = self
+            .args
+            .iter()
+            .map(|t| super::KotlinCodeOracle.find(t).type_label(ci))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let ret = match &self.ret {
+            Some(t) => super::KotlinCodeOracle.find(t).type_label(ci),
+            None => "Unit".to_string(),
+        };
+        format!("({args}) -> {ret}")
+    }
+
+    fn canonical_name(&self) -> String {
+        let args = self
+            .args
+            .iter()
+            .map(|t| super::KotlinCodeOracle.find(t).canonical_name())
+            .collect::<String>();
+        match &self.ret {
+            Some(t) => format!(
+                "Function{args}To{}",
+                super::KotlinCodeOracle.find(t).canonical_name()
+            ),
+            None => format!("Function{args}"),
+        }
+    }
+
+    fn initialization_fn(&self) -> Option<String> {
+        Some("uniffiForeignFunction.register".to_string())
+    }
+}
@@ -15,13 +15,19 @@ use serde::{Deserialize, Serialize};
 use crate::backend::TemplateExpression;
 use crate::interface::*;
 use crate::BindingsConfig;
+pub use multiplatform::MultiplatformBindings;
+use multiplatform::{
+    is_common_callable, is_common_object, is_multiplatform_common_type, KotlinNativeWrapper,
+};
 
 mod callback_interface;
 mod compounds;
 mod custom;
 mod enum_;
 mod external;
+mod function;
 mod miscellany;
+mod multiplatform;
 mod object;
 mod primitives;
 mod record;
@@ -81,12 +87,85 @@ pub struct Config {
     android: bool,
     #[serde(default)]
     android_cleaner: Option<bool>,
+    #[serde(default)]
+    serialization: Option<SerializationFramework>,
+    #[serde(default)]
+    multiplatform: bool,
+    #[serde(default)]
+    ffi_backend: Option<FfiBackend>,
+    #[serde(default)]
+    object_cleanup: Option<ObjectCleanup>,
+    lenient_checksums: Option<bool>,
+    log_bridge: Option<bool>,
 }
 
 impl Config {
+    pub(crate) fn android(&self) -> bool {
+        self.android
+    }
+
     pub(crate) fn android_cleaner(&self) -> bool {
         self.android_cleaner.unwrap_or(self.android)
     }
+
+    /// Whether to annotate records and enums with `@kotlinx.serialization.Serializable`
+    pub(crate) fn kotlinx_serialization(&self) -> bool {
+        matches!(self.serialization, Some(SerializationFramework::Kotlinx))
+    }
+
+    /// Whether to split the bindings into Kotlin Multiplatform source sets
+    pub fn multiplatform(&self) -> bool {
+        self.multiplatform
+    }
+
+    /// Whether to call the FFI functions through JNI shims rather than JNA
+    pub fn uses_jni(&self) -> bool {
+        matches!(self.ffi_backend, Some(FfiBackend::Jni))
+    }
+
+    /// Whether objects are freed by a `Cleaner` when they become unreachable
+    pub(crate) fn uses_cleaner(&self) -> bool {
+        !matches!(self.object_cleanup, Some(ObjectCleanup::Close))
+    }
+
+    /// Whether to only disable the functions with mismatched API checksums, rather than failing
+    /// to load the library
+    pub fn lenient_checksums(&self) -> bool {
+        self.lenient_checksums.unwrap_or(false)
+    }
+
+    /// Whether to generate the API that forwards Rust log records to the platform logger
+    pub fn log_bridge(&self) -> bool {
+        self.log_bridge.unwrap_or(false)
+    }
+}
+
+/// Serialization frameworks that generated records and enums can be made compatible with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SerializationFramework {
+    Kotlinx,
+}
+
+/// How the generated code calls the FFI functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FfiBackend {
+    /// Through a JNA `Library` interface
+    Jna,
+    /// Through `external fun` declarations, implemented by JNI shims in the scaffolding.
+    /// This requires the `jni` feature of the `uniffi` crate.
+    Jni,
+}
+
+/// How the Rust peers of objects are freed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ObjectCleanup {
+    /// When the object is closed, or else by a `Cleaner` once it's unreachable
+    Cleaner,
+    /// Only when the object is closed
+    Close,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -150,6 +229,52 @@ pub fn generate_bindings(config: &Config, ci: &ComponentInterface) -> Result<Str
         .context("failed to render kotlin bindings")
 }
 
+// Generate the `external fun` declarations for the JNI shims, as a string.
+//
+// This is only used with `ffi_backend = "jni"`.  The code must be written to the
+// [jni_package_name] package.
+pub fn generate_jni_bindings(ci: &ComponentInterface) -> Result<String> {
+    KotlinJniLib::new(ci)
+        .render()
+        .context("failed to render kotlin JNI bindings")
+}
+
+// Layout of the `UniffiJniFrame` class in `NamespaceLibraryTemplate.kt`: the arguments follow the
+// call status and the return value, and each takes up the size of a `RustBuffer`.
+const JNI_FRAME_ARGS_OFFSET: usize = 56;
+const JNI_FRAME_SLOT_SIZE: usize = 24;
+
+/// Package for the `UniffiJniLib` object declaring the JNI shims.
+///
+/// The shim symbol names are derived from this, see `uniffi_meta::jni_symbol_name`.
+pub fn jni_package_name(ci: &ComponentInterface) -> String {
+    format!("uniffi.jni.{}", ci.crate_name())
+}
+
+// Generate Kotlin Multiplatform bindings for the given ComponentInterface, as strings.
+pub fn generate_multiplatform_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+) -> Result<MultiplatformBindings> {
+    let header_config = crate::bindings::c::Config::with_header_filename(
+        format!("{}.h", ci.namespace()),
+        config.cdylib_name(),
+    );
+    Ok(MultiplatformBindings {
+        common: KotlinCommonWrapper::new(config.clone(), ci)
+            .render()
+            .context("failed to render kotlin common bindings")?,
+        jvm: generate_bindings(config, ci)?,
+        native: KotlinNativeWrapper::new(config.clone(), ci)
+            .render()
+            .context("failed to render kotlin native bindings")?,
+        native_header: crate::bindings::c::generate_header(&header_config, ci)?,
+        native_def: NativeDef::new(config, ci)
+            .render()
+            .context("failed to render kotlin native cinterop definition")?,
+    })
+}
+
 /// A struct to record a Kotlin import statement.
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub enum ImportRequirement {
@@ -184,6 +309,8 @@ pub struct TypeRenderer<'a> {
     include_once_names: RefCell<HashSet<String>>,
     // Track imports added with the `add_import()` macro
     imports: RefCell<BTreeSet<ImportRequirement>>,
+    // Are we rendering the `commonMain` declarations for Kotlin Multiplatform?
+    common: bool,
 }
 
 impl<'a> TypeRenderer<'a> {
@@ -193,9 +320,44 @@ impl<'a> TypeRenderer<'a> {
             ci,
             include_once_names: RefCell::new(HashSet::new()),
             imports: RefCell::new(BTreeSet::new()),
+            common: false,
         }
     }
 
+    fn new_common(config: &'a Config, ci: &'a ComponentInterface) -> Self {
+        Self {
+            common: true,
+            ..Self::new(config, ci)
+        }
+    }
+
+    // Is this type declared in `commonMain` when generating Kotlin Multiplatform bindings?
+    fn is_common_type(&self, type_: &Type) -> bool {
+        self.config.multiplatform() && is_multiplatform_common_type(self.ci, type_)
+    }
+
+    // Should the declaration of this type be rendered into the file we're generating?
+    //
+    // Common types are declared in the `commonMain` file; everything else, including the
+    // FfiConverters for common types, goes in the regular bindings.
+    fn renders_declaration(&self, type_: &Type) -> bool {
+        self.common == self.is_common_type(type_)
+    }
+
+    // Does this object implement an `expect` class from `commonMain`?
+    fn is_actual_object(&self, obj: &Object) -> bool {
+        self.config.multiplatform() && is_common_object(self.ci, obj)
+    }
+
+    // Does this constructor or method implement an `expect` declaration from `commonMain`?
+    fn is_actual_constructor(&self, obj: &Object, cons: &Constructor) -> bool {
+        self.is_actual_object(obj) && is_common_callable(self.ci, cons)
+    }
+
+    fn is_actual_method(&self, obj: &Object, meth: &Method) -> bool {
+        self.is_actual_object(obj) && is_common_callable(self.ci, meth)
+    }
+
     // Get the package name for an external type
     fn external_type_package_name(&self, module_path: &str, namespace: &str) -> String {
         // config overrides are keyed by the crate name, default fallback is the namespace.
@@ -267,16 +429,164 @@ impl<'a> KotlinWrapper<'a> {
     }
 
     pub fn initialization_fns(&self) -> Vec<String> {
+        // Several types can share an initialization function, only call it once.
+        let mut seen = HashSet::new();
         self.ci
             .iter_types()
             .map(|t| KotlinCodeOracle.find(t))
             .filter_map(|ct| ct.initialization_fn())
+            .filter(|init| seen.insert(init.clone()))
             .collect()
     }
 
     pub fn imports(&self) -> Vec<ImportRequirement> {
         self.type_imports.iter().cloned().collect()
     }
+
+    pub fn jni_class_name(&self) -> String {
+        format!("{}.UniffiJniLib", jni_package_name(self.ci))
+    }
+
+    /// Arguments for a JNI shim call, lowered from the arguments of the `UniffiLib` method
+    ///
+    /// `RustBuffer` and `ForeignBytes` arguments are copied into the call's `UniffiJniFrame`, after
+    /// the call status and the return value.
+    pub fn jni_args(&self, func: &FfiFunction) -> Vec<String> {
+        let mut offset = JNI_FRAME_ARGS_OFFSET;
+        let mut args: Vec<String> = func
+            .arguments()
+            .into_iter()
+            .map(|arg| {
+                let nm = KotlinCodeOracle.var_name(arg.name());
+                let put_fn = match arg.type_() {
+                    FfiType::RustBuffer(_) => "putRustBuffer",
+                    FfiType::ForeignBytes => "putForeignBytes",
+                    type_ => return KotlinCodeOracle.jni_lower(&type_, &nm),
+                };
+                let lowered = format!("uniffiFrame.{put_fn}({offset}, {nm})");
+                offset += JNI_FRAME_SLOT_SIZE;
+                lowered
+            })
+            .collect();
+        if func.has_rust_call_status_arg() {
+            args.push("uniffiFrame.status()".to_owned());
+        }
+        args
+    }
+
+    /// Does a JNI shim call pass any values through a `UniffiJniFrame`?
+    pub fn jni_uses_frame(&self, func: &FfiFunction) -> bool {
+        func.has_rust_call_status_arg()
+            || func
+                .return_type()
+                .map_or(false, |t| KotlinCodeOracle.jni_returns_via_frame(t))
+            || func
+                .arguments()
+                .into_iter()
+                .any(|arg| matches!(arg.type_(), FfiType::RustBuffer(_) | FfiType::ForeignBytes))
+    }
+
+    /// Size of a `UniffiJniFrame`, with room for the arguments of any FFI function
+    pub fn jni_frame_size(&self) -> usize {
+        let max_args = self
+            .ci
+            .iter_ffi_function_definitions()
+            .map(|func| {
+                func.arguments()
+                    .into_iter()
+                    .filter(|arg| {
+                        matches!(arg.type_(), FfiType::RustBuffer(_) | FfiType::ForeignBytes)
+                    })
+                    .count()
+            })
+            .max()
+            .unwrap_or(0);
+        JNI_FRAME_ARGS_OFFSET + max_args * JNI_FRAME_SLOT_SIZE
+    }
+
+    // Does this function implement an `expect` declaration from `commonMain`?
+    fn is_actual_function(&self, func: &Function) -> bool {
+        self.config.multiplatform() && is_common_callable(self.ci, func)
+    }
+}
+
+/// Renders the `UniffiJniLib` object for `ffi_backend = "jni"`
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "JniLibTemplate.kt")]
+pub struct KotlinJniLib<'a> {
+    ci: &'a ComponentInterface,
+}
+
+impl<'a> KotlinJniLib<'a> {
+    pub fn new(ci: &'a ComponentInterface) -> Self {
+        Self { ci }
+    }
+
+    pub fn package_name(&self) -> String {
+        jni_package_name(self.ci)
+    }
+}
+
+/// Renders the `commonMain` declarations for Kotlin Multiplatform
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "MultiplatformCommonWrapper.kt")]
+pub struct KotlinCommonWrapper<'a> {
+    config: Config,
+    ci: &'a ComponentInterface,
+    type_helper_code: String,
+    type_imports: BTreeSet<ImportRequirement>,
+}
+
+impl<'a> KotlinCommonWrapper<'a> {
+    pub fn new(config: Config, ci: &'a ComponentInterface) -> Self {
+        let type_renderer = TypeRenderer::new_common(&config, ci);
+        let type_helper_code = type_renderer.render().unwrap();
+        let type_imports = type_renderer.imports.into_inner();
+        Self {
+            config,
+            ci,
+            type_helper_code,
+            type_imports,
+        }
+    }
+
+    pub fn imports(&self) -> Vec<ImportRequirement> {
+        self.type_imports.iter().cloned().collect()
+    }
+
+    fn common_objects(&self) -> Vec<&Object> {
+        self.ci
+            .object_definitions()
+            .iter()
+            .filter(|obj| is_common_object(self.ci, obj))
+            .collect()
+    }
+
+    fn is_common_function(&self, func: &Function) -> bool {
+        is_common_callable(self.ci, func)
+    }
+
+    fn is_common_constructor(&self, cons: &Constructor) -> bool {
+        is_common_callable(self.ci, cons)
+    }
+
+    fn is_common_method(&self, meth: &Method) -> bool {
+        is_common_callable(self.ci, meth)
+    }
+}
+
+/// Renders the Kotlin/Native cinterop `.def` file for the C header
+#[derive(Template)]
+#[template(syntax = "c", escape = "none", path = "MultiplatformNativeDef.def")]
+pub struct NativeDef<'a> {
+    config: &'a Config,
+    ci: &'a ComponentInterface,
+}
+
+impl<'a> NativeDef<'a> {
+    pub fn new(config: &'a Config, ci: &'a ComponentInterface) -> Self {
+        Self { config, ci }
+    }
 }
 
 #[derive(Clone)]
@@ -375,6 +685,66 @@ impl KotlinCodeOracle {
         }
     }
 
+    /// FFI type name to use in the JNI `external fun` declarations
+    ///
+    /// JNI can only pass primitives, so everything else is passed as a `Long` address.
+    fn ffi_type_label_jni(&self, ffi_type: &FfiType) -> String {
+        match ffi_type {
+            FfiType::Int8
+            | FfiType::UInt8
+            | FfiType::Int16
+            | FfiType::UInt16
+            | FfiType::Int32
+            | FfiType::UInt32
+            | FfiType::Int64
+            | FfiType::UInt64
+            | FfiType::Float32
+            | FfiType::Float64
+            | FfiType::Handle => self.ffi_type_label(ffi_type),
+            _ => "Long".to_owned(),
+        }
+    }
+
+    /// Is this return type written to the `UniffiJniFrame` of the call?
+    fn jni_returns_via_frame(&self, ffi_type: &FfiType) -> bool {
+        matches!(ffi_type, FfiType::RustBuffer(_))
+    }
+
+    /// Is this return type written to the out pointer that's passed to every JNI shim?
+    fn jni_returns_via_out_pointer(&self, ffi_type: &FfiType) -> bool {
+        matches!(
+            ffi_type,
+            FfiType::RustBuffer(_)
+                | FfiType::ForeignBytes
+                | FfiType::RustCallStatus
+                | FfiType::Struct(_)
+        )
+    }
+
+    /// Convert a JNA value to the value to pass to a JNI shim
+    fn jni_lower(&self, ffi_type: &FfiType, nm: &str) -> String {
+        match ffi_type {
+            FfiType::RustArcPtr(_) | FfiType::VoidPointer => format!("Pointer.nativeValue({nm})"),
+            FfiType::RustCallStatus | FfiType::Struct(_) => format!("{nm}.uniffiJniPointer()"),
+            FfiType::Reference(inner) => match inner.as_ref() {
+                FfiType::RustBuffer(_) | FfiType::Struct(_) => format!("{nm}.uniffiJniPointer()"),
+                _ => format!("Pointer.nativeValue({nm}.pointer)"),
+            },
+            FfiType::Callback(_) => {
+                format!("Pointer.nativeValue(CallbackReference.getFunctionPointer({nm}))")
+            }
+            _ => nm.to_owned(),
+        }
+    }
+
+    /// Convert a value returned by a JNI shim to the JNA value
+    fn jni_lift(&self, ffi_type: &FfiType, nm: &str) -> String {
+        match ffi_type {
+            FfiType::RustArcPtr(_) | FfiType::VoidPointer => format!("Pointer({nm})"),
+            _ => nm.to_owned(),
+        }
+    }
+
     fn ffi_type_label_by_reference(&self, ffi_type: &FfiType) -> String {
         match ffi_type {
             FfiType::Int8
@@ -485,6 +855,9 @@ impl<T: AsType> AsCodeType for T {
                 key_type,
                 value_type,
             } => Box::new(compounds::MapCodeType::new(*key_type, *value_type)),
+            Type::Function { args, ret } => {
+                Box::new(function::FunctionCodeType::new(args, ret.map(|t| *t)))
+            }
             Type::External { name, .. } => Box::new(external::ExternalCodeType::new(name)),
             Type::Custom { name, .. } => Box::new(custom::CustomCodeType::new(name)),
         }
@@ -503,6 +876,60 @@ mod filters {
         Ok(as_ct.as_codetype().type_label(ci))
     }
 
+    /// The type name for a field of a `@Serializable` class
+    ///
+    /// kotlinx.serialization has no built-in serializers for timestamps, durations, custom and
+    /// external types, so these are marked `@Contextual` and the application registers
+    /// serializers for them.
+    pub(super) fn serializable_type_name(
+        as_type: &impl AsType,
+        ci: &ComponentInterface,
+    ) -> Result<String, askama::Error> {
+        Ok(serializable_type_label(&as_type.as_type(), ci))
+    }
+
+    fn serializable_type_label(type_: &Type, ci: &ComponentInterface) -> String {
+        match type_ {
+            Type::Optional { inner_type } => {
+                format!("{}?", serializable_type_label(inner_type, ci))
+            }
+            Type::Sequence { inner_type } => {
+                format!("List<{}>", serializable_type_label(inner_type, ci))
+            }
+            Type::Map {
+                key_type,
+                value_type,
+            } => format!(
+                "Map<{}, {}>",
+                serializable_type_label(key_type, ci),
+                serializable_type_label(value_type, ci)
+            ),
+            _ if needs_contextual_serializer(type_) => {
+                format!("@Contextual {}", type_.as_codetype().type_label(ci))
+            }
+            _ => type_.as_codetype().type_label(ci),
+        }
+    }
+
+    /// Whether a field type needs the `@Contextual` annotation, see [serializable_type_name]
+    pub(super) fn has_contextual_types(as_type: &impl AsType) -> Result<bool, askama::Error> {
+        Ok(needs_contextual_serializer(&as_type.as_type()))
+    }
+
+    fn needs_contextual_serializer(type_: &Type) -> bool {
+        match type_ {
+            Type::Timestamp | Type::Duration | Type::Custom { .. } | Type::External { .. } => true,
+            Type::Optional { inner_type } | Type::Sequence { inner_type } => {
+                needs_contextual_serializer(inner_type)
+            }
+            Type::Map {
+                key_type,
+                value_type,
+            } => needs_contextual_serializer(key_type) || needs_contextual_serializer(value_type),
+            _ => false,
+        }
+    }
+
     pub(super) fn canonical_name(as_ct: &impl AsCodeType) -> Result<String, askama::Error> {
         Ok(as_ct.as_codetype().canonical_name())
     }
@@ -591,6 +1018,31 @@ mod filters {
         Ok(KotlinCodeOracle.ffi_default_value(&type_))
     }
 
+    pub fn ffi_type_name_jni(type_: &FfiType) -> Result<String, askama::Error> {
+        Ok(KotlinCodeOracle.ffi_type_label_jni(type_))
+    }
+
+    pub fn ffi_return_type_name_jni(func: &FfiFunction) -> Result<String, askama::Error> {
+        Ok(match func.return_type() {
+            Some(type_) if !KotlinCodeOracle.jni_returns_via_out_pointer(type_) => {
+                KotlinCodeOracle.ffi_type_label_jni(type_)
+            }
+            _ => "Unit".to_owned(),
+        })
+    }
+
+    pub fn jni_returns_via_frame(type_: &FfiType) -> Result<bool, askama::Error> {
+        Ok(KotlinCodeOracle.jni_returns_via_frame(type_))
+    }
+
+    pub fn jni_returns_via_out_pointer(type_: &FfiType) -> Result<bool, askama::Error> {
+        Ok(KotlinCodeOracle.jni_returns_via_out_pointer(type_))
+    }
+
+    pub fn jni_lift(type_: &FfiType, nm: &str) -> Result<String, askama::Error> {
+        Ok(KotlinCodeOracle.jni_lift(type_, nm))
+    }
+
     /// Get the idiomatic Kotlin rendering of a function name.
     pub fn class_name(nm: &str, ci: &ComponentInterface) -> Result<String, askama::Error> {
         Ok(KotlinCodeOracle.class_name(ci, nm))
@@ -697,3 +1149,73 @@ mod filters {
         Ok(textwrap::indent(&wrapped, &" ".repeat(spaces)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serializable_type_name() {
+        let ci = ComponentInterface::new("test");
+        let name = |type_: Type| filters::serializable_type_name(&type_, &ci).unwrap();
+        assert_eq!(name(Type::String), "kotlin.String");
+        assert_eq!(name(Type::Timestamp), "@Contextual java.time.Instant");
+        assert_eq!(
+            name(Type::Optional {
+                inner_type: Box::new(Type::Duration)
+            }),
+            "@Contextual java.time.Duration?"
+        );
+        assert_eq!(
+            name(Type::Map {
+                key_type: Box::new(Type::String),
+                value_type: Box::new(Type::Sequence {
+                    inner_type: Box::new(Type::Timestamp)
+                }),
+            }),
+            "Map<kotlin.String, List<@Contextual java.time.Instant>>"
+        );
+        assert!(!filters::has_contextual_types(&Type::Sequence {
+            inner_type: Box::new(Type::Int32)
+        })
+        .unwrap());
+    }
+
+    #[test]
+    fn test_jni_args() {
+        let mut ci = ComponentInterface::from_webidl(
+            r#"
+            namespace test {
+                string concat(string a, u32 sep, string b);
+                void noop();
+            };
+            "#,
+            "crate_name",
+        )
+        .unwrap();
+        ci.derive_ffi_funcs().unwrap();
+        let config = Config {
+            ffi_backend: Some(FfiBackend::Jni),
+            ..Config::default()
+        };
+        let wrapper = KotlinWrapper::new(config, &ci);
+
+        let concat = ci.get_function_definition("concat").unwrap().ffi_func();
+        assert_eq!(
+            wrapper.jni_args(concat),
+            [
+                "uniffiFrame.putRustBuffer(56, `a`)",
+                "`sep`",
+                "uniffiFrame.putRustBuffer(80, `b`)",
+                "uniffiFrame.status()",
+            ]
+        );
+        assert!(wrapper.jni_uses_frame(concat));
+        // Room for the two `RustBuffer` arguments of `concat`
+        assert_eq!(wrapper.jni_frame_size(), 104);
+
+        let contract_version = ci.ffi_uniffi_contract_version();
+        assert!(wrapper.jni_args(&contract_version).is_empty());
+        assert!(!wrapper.jni_uses_frame(&contract_version));
+    }
+}
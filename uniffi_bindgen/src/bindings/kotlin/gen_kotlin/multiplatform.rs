@@ -0,0 +1,259 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Kotlin Multiplatform support
+//!
+//! The `commonMain` source set gets the records and enums that only use builtin types, plus
+//! `expect` declarations for the functions and objects whose signatures only use such types.
+//! `jvmMain` gets the regular JNA bindings, with `actual` modifiers on those declarations, and
+//! `nativeMain` gets `actual` declarations that call the FFI through the Kotlin/Native cinterop
+//! bindings for the C header.
+
+use askama::Template;
+
+use super::{filters, Config};
+use crate::interface::*;
+
+/// Kotlin Multiplatform bindings, split by source set.
+pub struct MultiplatformBindings {
+    /// Declarations shared by all targets, for `commonMain`
+    pub common: String,
+    /// The JNA-based implementation, for `jvmMain`
+    pub jvm: String,
+    /// The cinterop-based implementation, for `nativeMain`
+    pub native: String,
+    /// C header declaring the FFI, for the Kotlin/Native cinterop tool
+    pub native_header: String,
+    /// Kotlin/Native cinterop definition file for `native_header`
+    pub native_def: String,
+}
+
+/// Check if a type can be declared in the `commonMain` source set.
+///
+/// This is true for records and enums that only contain builtin Kotlin types, compound types,
+/// or other records and enums.  Everything else depends on JVM-only types (JNA, `java.time`, ...)
+/// and is declared alongside the JVM implementation.
+pub(super) fn is_multiplatform_common_type(ci: &ComponentInterface, type_: &Type) -> bool {
+    let is_data_class = |t: &Type| match t {
+        Type::Record { .. } => true,
+        Type::Enum { name, .. } => !ci.is_name_used_as_error(name),
+        _ => false,
+    };
+    is_data_class(type_)
+        && ci
+            .iter_types_in_item(type_)
+            .all(|t| is_data_class(t) || is_builtin(t))
+}
+
+fn is_builtin(type_: &Type) -> bool {
+    matches!(
+        type_,
+        Type::UInt8
+            | Type::Int8
+            | Type::UInt16
+            | Type::Int16
+            | Type::UInt32
+            | Type::Int32
+            | Type::UInt64
+            | Type::Int64
+            | Type::Float32
+            | Type::Float64
+            | Type::Boolean
+            | Type::String
+            | Type::Bytes
+            | Type::Optional { .. }
+            | Type::Sequence { .. }
+            | Type::Map { .. }
+    )
+}
+
+/// Check if an object gets an `expect` class in `commonMain`
+///
+/// Objects that can be implemented on the foreign side and objects used as errors are JVM-only.
+pub(super) fn is_common_object(ci: &ComponentInterface, obj: &Object) -> bool {
+    !obj.is_trait_interface() && !ci.is_name_used_as_error(obj.name())
+}
+
+/// Check if a function, method or constructor gets an `expect` declaration in `commonMain`
+///
+/// This is true for synchronous callables that don't throw, and only use common types or common
+/// objects in their signature.
+pub(super) fn is_common_callable(ci: &ComponentInterface, callable: &impl Callable) -> bool {
+    !callable.is_async()
+        && !callable.is_blocking()
+        && callable.throws_type().is_none()
+        && callable
+            .arguments()
+            .into_iter()
+            .all(|arg| is_common_api_type(ci, &arg.as_type()))
+        && callable
+            .return_type()
+            .map_or(true, |t| is_common_api_type(ci, &t))
+}
+
+fn is_common_api_type(ci: &ComponentInterface, type_: &Type) -> bool {
+    match type_ {
+        Type::Optional { inner_type } | Type::Sequence { inner_type } => {
+            is_common_api_type(ci, inner_type)
+        }
+        Type::Map {
+            key_type,
+            value_type,
+        } => is_common_api_type(ci, key_type) && is_common_api_type(ci, value_type),
+        Type::Record { .. } | Type::Enum { .. } => is_multiplatform_common_type(ci, type_),
+        Type::Object { name, .. } => ci
+            .get_object_definition(name)
+            .map_or(false, |obj| is_common_object(ci, obj)),
+        _ => is_builtin(type_),
+    }
+}
+
+/// Renders the `nativeMain` implementation for Kotlin Multiplatform
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "MultiplatformNativeWrapper.kt")]
+pub struct KotlinNativeWrapper<'a> {
+    config: Config,
+    ci: &'a ComponentInterface,
+}
+
+impl<'a> KotlinNativeWrapper<'a> {
+    pub fn new(config: Config, ci: &'a ComponentInterface) -> Self {
+        Self { config, ci }
+    }
+
+    // Types that need a native FfiConverter, other than the builtin types which always have one.
+    fn converter_types(&self) -> Vec<Type> {
+        self.ci
+            .iter_types()
+            .filter(|t| is_common_api_type(self.ci, t) && !is_builtin_scalar(t))
+            .cloned()
+            .collect()
+    }
+
+    fn common_objects(&self) -> Vec<&Object> {
+        self.ci
+            .object_definitions()
+            .iter()
+            .filter(|obj| is_common_object(self.ci, obj))
+            .collect()
+    }
+
+    fn is_common_function(&self, func: &Function) -> bool {
+        is_common_callable(self.ci, func)
+    }
+
+    fn is_common_constructor(&self, cons: &Constructor) -> bool {
+        is_common_callable(self.ci, cons)
+    }
+
+    fn is_common_method(&self, meth: &Method) -> bool {
+        is_common_callable(self.ci, meth)
+    }
+}
+
+fn is_builtin_scalar(type_: &Type) -> bool {
+    is_builtin(type_)
+        && !matches!(
+            type_,
+            Type::Optional { .. } | Type::Sequence { .. } | Type::Map { .. }
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::generate_multiplatform_bindings;
+    use super::*;
+
+    const UDL: &str = r#"
+        namespace geometry {
+            double distance(Point a, Point b);
+            Point? nearest(sequence<Point> points);
+        };
+
+        dictionary Point {
+            double x;
+            double y;
+        };
+
+        interface Shape {
+            constructor(sequence<Point> points);
+            double area();
+            [Async] double slow_area();
+        };
+    "#;
+
+    #[test]
+    fn test_multiplatform_bindings() {
+        let mut ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        ci.derive_ffi_funcs().unwrap();
+        let config = Config {
+            package_name: Some("uniffi.geometry".into()),
+            cdylib_name: Some("geometry".into()),
+            multiplatform: true,
+            ..Config::default()
+        };
+        let bindings = generate_multiplatform_bindings(&config, &ci).unwrap();
+
+        assert!(bindings.common.contains("data class Point ("));
+        assert!(bindings
+            .common
+            .contains("expect fun `distance`(`a`: Point, `b`: Point): kotlin.Double"));
+        assert!(bindings
+            .common
+            .contains("expect fun `nearest`(`points`: List<Point>): Point?"));
+        assert!(bindings.common.contains("expect open class Shape {"));
+        assert!(bindings
+            .common
+            .contains("constructor(`points`: List<Point>)"));
+        assert!(bindings.common.contains("fun `area`(): kotlin.Double"));
+        // Async methods are JVM-only
+        assert!(!bindings.common.contains("slowArea"));
+
+        assert!(bindings.jvm.contains("actual fun `distance`("));
+        assert!(bindings.jvm.contains("actual open class Shape"));
+        assert!(bindings.jvm.contains("actual override fun `area`()"));
+        assert!(!bindings
+            .jvm
+            .contains("actual override suspend fun `slowArea`()"));
+
+        assert!(bindings
+            .native
+            .contains("import uniffi.geometry.cinterop.*"));
+        assert!(bindings.native.contains("actual fun `distance`("));
+        assert!(bindings.native.contains("actual open class Shape"));
+        assert!(bindings
+            .native
+            .contains("actual fun `area`(): kotlin.Double"));
+        assert!(bindings
+            .native
+            .contains("internal object FfiConverterTypePoint"));
+        assert!(bindings
+            .native
+            .contains("internal object FfiConverterOptionalTypePoint"));
+        assert!(bindings
+            .native
+            .contains("internal object FfiConverterSequenceTypePoint"));
+
+        // The header declares every FFI function that the native bindings call
+        for func in ci.iter_ffi_function_definitions() {
+            assert!(
+                bindings.native_header.contains(func.name()),
+                "{} missing from the header",
+                func.name()
+            );
+        }
+        for name in [
+            "uniffi_crate_name_fn_func_distance",
+            "uniffi_crate_name_fn_constructor_shape_new",
+            "uniffi_crate_name_fn_method_shape_area",
+            "uniffi_crate_name_fn_free_shape",
+        ] {
+            assert!(bindings.native.contains(name), "{name} isn't called");
+        }
+        assert!(bindings
+            .native_def
+            .contains("package = uniffi.geometry.cinterop"));
+        assert!(bindings.native_def.contains("linkerOpts = -lgeometry"));
+    }
+}
@@ -8,7 +8,10 @@ use fs_err as fs;
 use std::process::Command;
 
 pub mod gen_kotlin;
-pub use gen_kotlin::{generate_bindings, Config};
+pub use gen_kotlin::{
+    generate_bindings, generate_jni_bindings, generate_multiplatform_bindings, jni_package_name,
+    Config,
+};
 mod test;
 
 use super::super::interface::ComponentInterface;
@@ -20,21 +23,100 @@ pub fn write_bindings(
     out_dir: &Utf8Path,
     try_format_code: bool,
 ) -> Result<()> {
+    if config.multiplatform() {
+        return write_multiplatform_bindings(config, ci, out_dir, try_format_code);
+    }
     let mut kt_file = full_bindings_path(config, out_dir);
     fs::create_dir_all(&kt_file)?;
     kt_file.push(format!("{}.kt", ci.namespace()));
     fs::write(&kt_file, generate_bindings(config, ci)?)?;
     if try_format_code {
-        if let Err(e) = Command::new("ktlint").arg("-F").arg(&kt_file).output() {
-            println!(
-                "Warning: Unable to auto-format {} using ktlint: {e:?}",
-                kt_file.file_name().unwrap(),
-            );
-        }
+        format_code(&kt_file);
+    }
+    if config.uses_jni() {
+        write_jni_bindings(ci, out_dir, try_format_code)?;
+    }
+    Ok(())
+}
+
+/// Write Kotlin Multiplatform bindings, using the standard Gradle source set layout:
+///
+///   - `commonMain/kotlin/{package}/{namespace}.kt`: declarations shared by all targets
+///   - `jvmMain/kotlin/{package}/{namespace}.jvm.kt`: the JNA-based implementation
+///   - `nativeMain/kotlin/{package}/{namespace}.native.kt`: the cinterop-based implementation
+///   - `nativeMain/cinterop/{namespace}.{h,def}`: the FFI for the Kotlin/Native cinterop tool
+fn write_multiplatform_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+    out_dir: &Utf8Path,
+    try_format_code: bool,
+) -> Result<()> {
+    let bindings = generate_multiplatform_bindings(config, ci)?;
+    let namespace = ci.namespace();
+
+    let common_dir = full_bindings_path(config, &out_dir.join("commonMain").join("kotlin"));
+    fs::create_dir_all(&common_dir)?;
+    let common_file = common_dir.join(format!("{namespace}.kt"));
+    fs::write(&common_file, bindings.common)?;
+
+    let jvm_dir = full_bindings_path(config, &out_dir.join("jvmMain").join("kotlin"));
+    fs::create_dir_all(&jvm_dir)?;
+    let jvm_file = jvm_dir.join(format!("{namespace}.jvm.kt"));
+    fs::write(&jvm_file, bindings.jvm)?;
+
+    let native_dir = full_bindings_path(config, &out_dir.join("nativeMain").join("kotlin"));
+    fs::create_dir_all(&native_dir)?;
+    let native_file = native_dir.join(format!("{namespace}.native.kt"));
+    fs::write(&native_file, bindings.native)?;
+
+    let cinterop_dir = out_dir.join("nativeMain").join("cinterop");
+    fs::create_dir_all(&cinterop_dir)?;
+    fs::write(
+        cinterop_dir.join(format!("{namespace}.h")),
+        bindings.native_header,
+    )?;
+    fs::write(
+        cinterop_dir.join(format!("{namespace}.def")),
+        bindings.native_def,
+    )?;
+
+    if try_format_code {
+        format_code(&common_file);
+        format_code(&jvm_file);
+        format_code(&native_file);
+    }
+    if config.uses_jni() {
+        write_jni_bindings(ci, &out_dir.join("jvmMain").join("kotlin"), try_format_code)?;
     }
     Ok(())
 }
 
+/// Write the `UniffiJniLib` object for `ffi_backend = "jni"`, next to the main bindings.
+fn write_jni_bindings(
+    ci: &ComponentInterface,
+    out_dir: &Utf8Path,
+    try_format_code: bool,
+) -> Result<()> {
+    let package_path: Utf8PathBuf = jni_package_name(ci).split('.').collect();
+    let jni_dir = out_dir.join(package_path);
+    fs::create_dir_all(&jni_dir)?;
+    let jni_file = jni_dir.join("UniffiJniLib.kt");
+    fs::write(&jni_file, generate_jni_bindings(ci)?)?;
+    if try_format_code {
+        format_code(&jni_file);
+    }
+    Ok(())
+}
+
+fn format_code(kt_file: &Utf8Path) {
+    if let Err(e) = Command::new("ktlint").arg("-F").arg(kt_file).output() {
+        println!(
+            "Warning: Unable to auto-format {} using ktlint: {e:?}",
+            kt_file.file_name().unwrap(),
+        );
+    }
+}
+
 fn full_bindings_path(config: &Config, out_dir: &Utf8Path) -> Utf8PathBuf {
     let package_path: Utf8PathBuf = config.package_name().split('.').collect();
     Utf8PathBuf::from(out_dir).join(package_path)
@@ -7,9 +7,11 @@
 
 {%- if e.is_flat() %}
 
+{%- if self.renders_declaration(type_) %}
 {%- call kt::docstring(e, 0) %}
 {% match e.variant_discr_type() %}
 {% when None %}
+{%- call kt::serializable_annotation(serializable) %}
 enum class {{ type_name }} {
     {% for variant in e.variants() -%}
     {%- call kt::docstring(variant, 4) %}
@@ -18,6 +20,7 @@ enum class {{ type_name }} {
     companion object
 }
 {% when Some with (variant_discr_type) %}
+{%- call kt::serializable_annotation(serializable) %}
 enum class {{ type_name }}(val value: {{ variant_discr_type|type_name(ci) }}) {
     {% for variant in e.variants() -%}
     {%- call kt::docstring(variant, 4) %}
@@ -26,6 +29,9 @@ enum class {{ type_name }}(val value: {{ variant_discr_type|type_name(ci) }}) {
     companion object
 }
 {% endmatch %}
+{%- endif %}
+
+{%- if !self.common %}
 
 public object {{ e|ffi_converter_name }}: FfiConverterRustBuffer<{{ type_name }}> {
     override fun read(buf: ByteBuffer) = try {
@@ -40,20 +46,27 @@ public object {{ e|ffi_converter_name }}: FfiConverterRustBuffer<{{ type_name }}
         buf.putInt(value.ordinal + 1)
     }
 }
+{%- endif %}
 
 {% else %}
 
+{%- if self.renders_declaration(type_) %}
 {%- call kt::docstring(e, 0) %}
+{%- call kt::serializable_annotation(serializable) %}
 sealed class {{ type_name }}{% if contains_object_references %}: Disposable {% endif %} {
     {% for variant in e.variants() -%}
     {%- call kt::docstring(variant, 4) %}
+    {%- if serializable %}
+    {{- self.add_import("kotlinx.serialization.SerialName") }}
+    @Serializable @SerialName("{{ variant.name() }}")
+    {%- endif %}
     {% if !variant.has_fields() -%}
     object {{ variant|type_name(ci) }} : {{ type_name }}()
     {% else -%}
     data class {{ variant|type_name(ci) }}(
         {%- for field in variant.fields() -%}
         {%- call kt::docstring(field, 8) %}
-        val {% call kt::field_name(field, loop.index) %}: {{ field|type_name(ci) }}{% if loop.last %}{% else %}, {% endif %}
+        val {% call kt::field_name(field, loop.index) %}: {% call kt::field_type_name(field, serializable) %}{% if loop.last %}{% else %}, {% endif %}
         {%- endfor -%}
     ) : {{ type_name }}() {
         companion object
@@ -79,6 +92,9 @@ sealed class {{ type_name }}{% if contains_object_references %}: Disposable {% e
     {% endif %}
     companion object
 }
+{%- endif %}
+
+{%- if !self.common %}
 
 public object {{ e|ffi_converter_name }} : FfiConverterRustBuffer<{{ type_name }}>{
     override fun read(buf: ByteBuffer): {{ type_name }} {
@@ -122,5 +138,6 @@ public object {{ e|ffi_converter_name }} : FfiConverterRustBuffer<{{ type_name }
         }.let { /* this makes the `when` an expression, which ensures it is exhaustive */ }
     }
 }
+{%- endif %}
 
 {% endif %}
@@ -0,0 +1,88 @@
+{%- if self.include_once_check("ObjectCleanerHelper.kt") %}
+    {%- include "ObjectCleanerHelper.kt" %}
+{%- endif %}
+
+// Kotlin lambdas passed to Rust.
+//
+// Lambdas are stored in a handle map and Rust calls them through a single vtable.  To make that
+// work for every lambda signature, the arguments and return value are serialized into
+// `RustBuffer`s.  Each `FfiConverterFunction*` object wraps a lambda in a function that handles
+// the serialization before inserting it into the map.
+internal object uniffiForeignFunction {
+    internal val handleMap = UniffiHandleMap<(ByteBuffer) -> RustBuffer.ByValue>()
+
+    internal object call: {{ "ForeignFunctionCall"|ffi_callback_name }} {
+        override fun callback(
+            handle: Long,
+            args: RustBuffer.ByValue,
+            uniffiOutReturn: RustBuffer,
+            uniffiCallStatus: UniffiRustCallStatus,
+        ) {
+            val function = handleMap.get(handle)
+            uniffiTraitInterfaceCall(
+                uniffiCallStatus,
+                {
+                    try {
+                        function(args.asByteBuffer()!!)
+                    } finally {
+                        RustBuffer.free(args)
+                    }
+                },
+                { value: RustBuffer.ByValue -> uniffiOutReturn.setValue(value) },
+            )
+        }
+    }
+
+    internal object free: {{ "CallbackInterfaceFree"|ffi_callback_name }} {
+        override fun callback(handle: Long) {
+            handleMap.remove(handle)
+        }
+    }
+
+    internal var vtable = {{ "VTableForeignFunction"|ffi_struct_name }}.UniffiByValue(call, free)
+
+    // Registers the vtable with the Rust side.
+    internal fun register(lib: UniffiLib) {
+        lib.{{ ci.ffi_foreign_function_init_vtable().name() }}(vtable)
+    }
+}
+
+// A Rust closure passed to Kotlin.
+//
+// The closure is freed once this object is garbage collected.
+internal class UniffiRustFunction(private val handle: Long) {
+    init {
+        UniffiLib.CLEANER.register(this, UniffiCleanAction(handle))
+    }
+
+    // Call the closure with serialized arguments, returning the serialized result
+    fun call(args: RustBuffer.ByValue): RustBuffer.ByValue {
+        return uniffiRustCall { status ->
+            UniffiLib.INSTANCE.{{ ci.ffi_rust_function_call().name() }}(handle, args, status)
+        }
+    }
+
+    private class UniffiCleanAction(private val handle: Long) : Runnable {
+        override fun run() {
+            uniffiRustCall { status ->
+                UniffiLib.INSTANCE.{{ ci.ffi_rust_function_free().name() }}(handle, status)
+            }
+        }
+    }
+}
+
+// Serialize a list of values into a `RustBuffer`
+internal fun uniffiWriteFunctionArgs(size: ULong, writeArgs: (ByteBuffer) -> Unit): RustBuffer.ByValue {
+    val rbuf = RustBuffer.alloc(size)
+    try {
+        val bbuf = rbuf.data!!.getByteBuffer(0, rbuf.capacity).also {
+            it.order(ByteOrder.BIG_ENDIAN)
+        }
+        writeArgs(bbuf)
+        rbuf.writeField("len", bbuf.position().toLong())
+        return rbuf
+    } catch (e: Throwable) {
+        RustBuffer.free(rbuf)
+        throw e
+    }
+}
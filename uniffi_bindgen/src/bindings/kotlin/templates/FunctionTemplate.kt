@@ -0,0 +1,48 @@
+{%- if self.include_once_check("FunctionRuntime.kt") %}{% include "FunctionRuntime.kt" %}{% endif %}
+
+public object {{ ffi_converter_name }}: FfiConverter<{{ type_name }}, Long> {
+    override fun lift(value: Long): {{ type_name }} {
+        val function = UniffiRustFunction(value)
+        return { {% for arg in args %}arg{{ loop.index0 }}: {{ arg|type_name(ci) }}{% if !loop.last %}, {% endif %}{% endfor %} ->
+            val args = uniffiWriteFunctionArgs(
+                0UL{% for arg in args %} + {{ arg|allocation_size_fn }}(arg{{ loop.index0 }}){% endfor %}
+            ) { buf ->
+                {%- for arg in args %}
+                {{ arg|write_fn }}(arg{{ loop.index0 }}, buf)
+                {%- endfor %}
+            }
+            val result = function.call(args)
+            {%- match ret %}
+            {%- when Some(ret) %}
+            {{ ret|ffi_converter_name }}.liftFromRustBuffer(result)
+            {%- when None %}
+            RustBuffer.free(result)
+            {%- endmatch %}
+        }
+    }
+
+    override fun read(buf: ByteBuffer): {{ type_name }} {
+        return lift(buf.getLong())
+    }
+
+    override fun lower(value: {{ type_name }}): Long {
+        return uniffiForeignFunction.handleMap.insert { {% if args.is_empty() %}_{% else %}buf{% endif %}: ByteBuffer ->
+            {%- for arg in args %}
+            val arg{{ loop.index0 }} = {{ arg|read_fn }}(buf)
+            {%- endfor %}
+            {%- match ret %}
+            {%- when Some(ret) %}
+            {{ ret|ffi_converter_name }}.lowerIntoRustBuffer(value({% for arg in args %}arg{{ loop.index0 }}{% if !loop.last %}, {% endif %}{% endfor %}))
+            {%- when None %}
+            value({% for arg in args %}arg{{ loop.index0 }}{% if !loop.last %}, {% endif %}{% endfor %})
+            RustBuffer.ByValue()
+            {%- endmatch %}
+        }
+    }
+
+    override fun allocationSize(value: {{ type_name }}) = 8UL
+
+    override fun write(value: {{ type_name }}, buf: ByteBuffer) {
+        buf.putLong(lower(value))
+    }
+}
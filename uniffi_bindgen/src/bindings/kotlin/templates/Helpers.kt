@@ -4,6 +4,7 @@
 internal const val UNIFFI_CALL_SUCCESS = 0.toByte()
 internal const val UNIFFI_CALL_ERROR = 1.toByte()
 internal const val UNIFFI_CALL_UNEXPECTED_ERROR = 2.toByte()
+internal const val UNIFFI_CALL_PANIC = 4.toByte()
 
 @Structure.FieldOrder("code", "error_buf")
 internal open class UniffiRustCallStatus : Structure() {
@@ -20,10 +21,14 @@ internal open class UniffiRustCallStatus : Structure() {
         return code == UNIFFI_CALL_ERROR
     }
 
-    fun isPanic(): Boolean {
+    fun isUnexpectedError(): Boolean {
         return code == UNIFFI_CALL_UNEXPECTED_ERROR
     }
 
+    fun isPanic(): Boolean {
+        return code == UNIFFI_CALL_PANIC
+    }
+
     companion object {
         fun create(code: Byte, errorBuf: RustBuffer.ByValue): UniffiRustCallStatus.ByValue {
             val callStatus = UniffiRustCallStatus.ByValue()
@@ -34,7 +39,55 @@ internal open class UniffiRustCallStatus : Structure() {
     }
 }
 
-class InternalException(message: String) : Exception(message)
+open class InternalException(message: String) : Exception(message)
+
+/**
+ * A panic in the Rust code
+ *
+ * `file`, `line` and `threadName` are null when they're unknown.  `rustBacktrace` is only
+ * captured if the `RUST_BACKTRACE` environment variable is set.
+ */
+class RustPanic(
+    message: String,
+    val file: String?,
+    val line: Int?,
+    val threadName: String?,
+    val rustBacktrace: String?,
+) : InternalException(message) {
+    companion object {
+        /**
+         * Called with each Rust panic before it's thrown, for example to report it to a crash
+         * reporter
+         */
+        @Volatile
+        var observer: ((RustPanic) -> Unit)? = null
+
+        internal fun lift(errorBuf: RustBuffer.ByValue): RustPanic {
+            // When the rust code sees a panic, it tries to construct a rustbuffer with the
+            // details.  But if that code panics, then it just sends back an empty buffer.
+            if (errorBuf.len == 0L) {
+                return RustPanic("Rust panic", null, null, null, null)
+            }
+            val buf = errorBuf.asByteBuffer()!!
+            try {
+                val message = {{ Type::String.borrow()|read_fn }}(buf)
+                val file = {{ Type::String.borrow()|read_fn }}(buf)
+                val line = buf.getInt()
+                val threadName = {{ Type::String.borrow()|read_fn }}(buf)
+                val rustBacktrace = {{ Type::String.borrow()|read_fn }}(buf)
+                return RustPanic(
+                    message,
+                    file.ifEmpty { null },
+                    if (line == 0) null else line,
+                    threadName.ifEmpty { null },
+                    rustBacktrace.ifEmpty { null },
+                )
+            } finally {
+                RustBuffer.free(errorBuf)
+            }
+        }
+    }
+}
 
 // Each top-level error class has a companion object that can lift the error from the call status's rust buffer
 interface UniffiRustCallStatusErrorHandler<E> {
@@ -45,9 +98,20 @@ interface UniffiRustCallStatusErrorHandler<E> {
 // In practice we usually need to be synchronized to call this safely, so it doesn't
 // synchronize itself
 
+{%- if config.uses_jni() %}
+
+// The JNI shims write the call status to native memory, and `UniffiJniAdapter` then sets all the
+// fields of this object, so each thread can reuse one rather than allocating a JNA structure.
+private val uniffiCallStatus = ThreadLocal.withInitial { UniffiRustCallStatus() }
+{%- endif %}
+
 // Call a rust function that returns a Result<>.  Pass in the Error class companion that corresponds to the Err
 private inline fun <U, E: Exception> uniffiRustCallWithError(errorHandler: UniffiRustCallStatusErrorHandler<E>, callback: (UniffiRustCallStatus) -> U): U {
+    {%- if config.uses_jni() %}
+    val status = uniffiCallStatus.get()
+    {%- else %}
     var status = UniffiRustCallStatus();
+    {%- endif %}
     val return_value = callback(status)
     uniffiCheckCallStatus(errorHandler, status)
     return return_value
@@ -60,13 +124,14 @@ private fun<E: Exception> uniffiCheckCallStatus(errorHandler: UniffiRustCallStat
     } else if (status.isError()) {
         throw errorHandler.lift(status.error_buf)
     } else if (status.isPanic()) {
-        // when the rust code sees a panic, it tries to construct a rustbuffer
-        // with the message.  but if that code panics, then it just sends back
-        // an empty buffer.
+        val panic = RustPanic.lift(status.error_buf)
+        RustPanic.observer?.invoke(panic)
+        throw panic
+    } else if (status.isUnexpectedError()) {
         if (status.error_buf.len > 0) {
             throw InternalException({{ Type::String.borrow()|lift_fn }}(status.error_buf))
         } else {
-            throw InternalException("Rust panic")
+            throw InternalException("Unexpected Rust error")
         }
     } else {
         throw InternalException("Unknown rust call status: $status.code")
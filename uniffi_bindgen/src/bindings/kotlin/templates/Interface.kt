@@ -2,7 +2,7 @@
 public interface {{ interface_name }} {
     {% for meth in methods.iter() -%}
     {%- call kt::docstring(meth, 4) %}
-    {% if meth.is_async() -%}suspend {% endif -%}
+    {% if meth.is_async() || meth.is_blocking() -%}suspend {% endif -%}
     fun {{ meth.name()|fn_name }}({% call kt::arg_list_decl(meth) %})
     {%- match meth.return_type() -%}
     {%- when Some with (return_type) %}: {{ return_type|type_name(ci) -}}
@@ -0,0 +1,21 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+package {{ self.package_name() }};
+
+// The JNI shims for the `{{ ci.namespace() }}` component.
+//
+// These are exported by the Rust library when it's built with the `jni` feature of `uniffi`.
+// Arguments that JNI can't pass directly are passed as the address of a copy in native memory, and
+// the last argument is the address to write a struct return value to.
+// This is an implementation detail which will be called internally by the public API.
+
+object UniffiJniLib {
+    {% for func in ci.iter_ffi_function_definitions() -%}
+    external fun {{ func.name() }}(
+        {%- call kt::arg_list_jni_decl(func) %}
+    ): {{ func|ffi_return_type_name_jni }}
+    {% endfor %}
+}
+
+{% import "macros.kt" as kt %}
@@ -0,0 +1,63 @@
+// Forwarding Rust log records to the platform logger
+
+/**
+ * Maximum level of the Rust log records to forward, with the same values as Rust's `log::LevelFilter`
+ */
+enum class RustLogLevel(internal val value: Byte) {
+    OFF(0),
+    ERROR(1),
+    WARN(2),
+    INFO(3),
+    DEBUG(4),
+    TRACE(5),
+}
+
+/**
+ * Forwards the records from Rust's `log` crate, and `tracing` events if its `log` feature is
+ * enabled, to {% if config.android() %}Logcat{% else %}`java.util.logging`{% endif %}.
+ *
+ * The Rust log target is used as the {% if config.android() %}Logcat tag{% else %}logger name, with `::` replaced by `.`{% endif %}.
+ */
+object RustLogBridge {
+    // JNA only keeps a weak reference to callbacks, so this needs to stay reachable.
+    internal object Callback: UniffiLogBridgeCallback {
+        override fun callback(level: Byte, target: RustBuffer.ByValue, message: RustBuffer.ByValue) {
+            val targetString = {{ Type::String.borrow()|lift_fn }}(target)
+            val messageString = {{ Type::String.borrow()|lift_fn }}(message)
+            {%- if config.android() %}
+            when (level.toInt()) {
+                1 -> android.util.Log.e(targetString, messageString)
+                2 -> android.util.Log.w(targetString, messageString)
+                3 -> android.util.Log.i(targetString, messageString)
+                4 -> android.util.Log.d(targetString, messageString)
+                else -> android.util.Log.v(targetString, messageString)
+            }
+            {%- else %}
+            val javaLevel = when (level.toInt()) {
+                1 -> java.util.logging.Level.SEVERE
+                2 -> java.util.logging.Level.WARNING
+                3 -> java.util.logging.Level.INFO
+                4 -> java.util.logging.Level.FINE
+                else -> java.util.logging.Level.FINER
+            }
+            java.util.logging.Logger.getLogger(targetString.replace("::", ".")).log(javaLevel, messageString)
+            {%- endif %}
+        }
+    }
+
+    /**
+     * Start forwarding Rust log records up to `level`
+     *
+     * This has no effect if the application already installed a different Rust logger.
+     */
+    fun install(level: RustLogLevel = RustLogLevel.INFO) {
+        UniffiLib.INSTANCE.{{ ci.ffi_log_bridge_init().name() }}(Callback, level.value)
+    }
+
+    /**
+     * Change the maximum level of the forwarded records
+     */
+    fun setLevel(level: RustLogLevel) {
+        UniffiLib.INSTANCE.{{ ci.ffi_log_bridge_set_level().name() }}(level.value)
+    }
+}
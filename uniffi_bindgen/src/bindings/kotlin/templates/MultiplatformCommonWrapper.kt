@@ -0,0 +1,58 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+{%- call kt::docstring_value(ci.namespace_docstring(), 0) %}
+
+@file:Suppress("NAME_SHADOWING")
+
+package {{ config.package_name() }};
+
+// Kotlin Multiplatform declarations.
+//
+// This file contains the records and enums which don't depend on the FFI, and `expect`
+// declarations for the functions and objects which only use those types, so that they can be
+// used from `commonMain` code. Their implementations, along with the rest of the bindings, are
+// generated into the platform-specific source sets.
+
+{%- for req in self.imports() %}
+{{ req.render() }}
+{%- endfor %}
+
+{{ type_helper_code }}
+
+{%- for obj in self.common_objects() %}
+{%- let (interface_name, impl_class_name) = obj|object_names(ci) %}
+
+{%- call kt::docstring_value(obj.class_docstring(), 0) %}
+expect open class {{ impl_class_name }} {
+    {%- match obj.primary_constructor() %}
+    {%- when Some(cons) %}
+    {%- if self.is_common_constructor(cons) %}
+    {%- call kt::docstring(cons, 4) %}
+    constructor({% call kt::arg_list_decl(cons) -%})
+    {%- endif %}
+    {%- when None %}
+    {%- endmatch %}
+
+    {% for meth in obj.methods() -%}
+    {%- if self.is_common_method(meth) %}
+    {%- call kt::docstring(meth, 4) %}
+    fun {{ meth.name()|fn_name }}({% call kt::arg_list_protocol(meth) %}){% match meth.return_type() %}{% when Some(return_type) %}: {{ return_type|type_name(ci) }}{% when None %}{% endmatch %}
+    {%- endif %}
+    {% endfor %}
+
+    /**
+     * Release the Rust object.  It can't be used afterwards.
+     */
+    fun close()
+}
+{%- endfor %}
+
+{%- for func in ci.function_definitions() %}
+{%- if self.is_common_function(func) %}
+{%- call kt::docstring(func, 0) %}
+expect fun {{ func.name()|fn_name }}({% call kt::arg_list_decl(func) %}){% match func.return_type() %}{% when Some(return_type) %}: {{ return_type|type_name(ci) }}{% when None %}{% endmatch %}
+{% endif %}
+{%- endfor %}
+
+{% import "macros.kt" as kt %}
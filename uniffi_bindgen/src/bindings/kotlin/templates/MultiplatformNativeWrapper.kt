@@ -0,0 +1,542 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+@file:Suppress("NAME_SHADOWING")
+@file:OptIn(kotlinx.cinterop.ExperimentalForeignApi::class, kotlin.experimental.ExperimentalNativeApi::class)
+
+package {{ config.package_name() }};
+
+// Kotlin/Native implementation of the `expect` declarations in `commonMain`.
+//
+// This calls the FFI through the declarations that the cinterop tool generates from
+// `{{ ci.namespace() }}.h`, in the `{{ config.package_name() }}.cinterop` package.  Values are
+// serialized into `ByteArray`s in the same format as the JVM bindings, and copied in and out of
+// `RustBuffer`s.
+
+import kotlin.concurrent.AtomicInt
+import kotlin.concurrent.AtomicLong
+import kotlin.native.ref.createCleaner
+import kotlinx.cinterop.*
+import {{ config.package_name() }}.cinterop.*
+
+open class InternalException(message: String) : Exception(message)
+
+// A panic in the Rust code
+class RustPanic(message: String) : InternalException(message)
+
+// Status codes of `RustCallStatus`, see `{{ ci.namespace() }}.h`
+private const val CALL_SUCCESS: Byte = 0
+private const val CALL_UNEXPECTED_ERROR: Byte = 2
+private const val CALL_PANIC: Byte = 4
+
+// Call a Rust function, and check the `RustCallStatus` it sets.
+//
+// Only functions that can't return errors are called from here, so any failure is unexpected.
+internal inline fun <T> uniffiRustCall(block: (CPointer<RustCallStatus>) -> T): T = memScoped {
+    val status = alloc<RustCallStatus>()
+    status.code = CALL_SUCCESS
+    status.errorBuf.capacity = 0UL
+    status.errorBuf.len = 0UL
+    status.errorBuf.data = null
+    val result = block(status.ptr)
+    if (status.code != CALL_SUCCESS) {
+        throw uniffiCallError(status.code, status.errorBuf.readValue())
+    }
+    result
+}
+
+internal fun uniffiCallError(code: Byte, errorBuf: CValue<RustBuffer>): InternalException {
+    val bytes = uniffiConsumeRustBuffer(errorBuf)
+    return when (code) {
+        CALL_UNEXPECTED_ERROR ->
+            InternalException(if (bytes.isEmpty()) "Unexpected Rust error" else bytes.decodeToString())
+        // The panic details start with the message
+        CALL_PANIC ->
+            RustPanic(if (bytes.isEmpty()) "Rust panic" else FfiConverterString.read(UniffiReader(bytes)))
+        else -> InternalException("Unknown rust call status: $code")
+    }
+}
+
+// Copy the contents of a `RustBuffer` returned by Rust, then free it.
+internal fun uniffiConsumeRustBuffer(buf: CValue<RustBuffer>): ByteArray {
+    val bytes = buf.useContents { data?.readBytes(len.toInt()) ?: ByteArray(0) }
+    uniffiRustCall { status -> {{ ci.ffi_rustbuffer_free().name() }}(buf, status) }
+    return bytes
+}
+
+// Copy bytes into a new `RustBuffer`, to pass to Rust.
+internal fun uniffiRustBufferFrom(bytes: ByteArray): CValue<RustBuffer> = memScoped {
+    val foreign = alloc<ForeignBytes>()
+    foreign.len = bytes.size
+    bytes.usePinned { pinned ->
+        foreign.data = if (bytes.isEmpty()) null else pinned.addressOf(0).reinterpret()
+        uniffiRustCall { status -> {{ ci.ffi_rustbuffer_from_bytes().name() }}(foreign.readValue(), status) }
+    }
+}
+
+// Reads serialized values, which are big-endian.
+internal class UniffiReader(private val bytes: ByteArray) {
+    private var position = 0
+
+    fun readByte(): Byte = bytes[position++]
+
+    fun readShort(): Short = ((readByte().toInt() and 0xff shl 8) or (readByte().toInt() and 0xff)).toShort()
+
+    fun readInt(): Int {
+        var value = 0
+        repeat(4) { value = (value shl 8) or (readByte().toInt() and 0xff) }
+        return value
+    }
+
+    fun readLong(): Long {
+        var value = 0L
+        repeat(8) { value = (value shl 8) or (readByte().toLong() and 0xff) }
+        return value
+    }
+
+    fun readBytes(len: Int): ByteArray {
+        val value = bytes.copyOfRange(position, position + len)
+        position += len
+        return value
+    }
+}
+
+// Writes serialized values, which are big-endian.
+internal class UniffiWriter {
+    private var bytes = ByteArray(64)
+    private var size = 0
+
+    private fun reserve(len: Int) {
+        if (size + len > bytes.size) {
+            bytes = bytes.copyOf(maxOf(bytes.size * 2, size + len))
+        }
+    }
+
+    fun writeByte(value: Byte) {
+        reserve(1)
+        bytes[size++] = value
+    }
+
+    fun writeShort(value: Short) {
+        writeByte((value.toInt() shr 8).toByte())
+        writeByte(value.toByte())
+    }
+
+    fun writeInt(value: Int) {
+        for (shift in 24 downTo 0 step 8) {
+            writeByte((value shr shift).toByte())
+        }
+    }
+
+    fun writeLong(value: Long) {
+        for (shift in 56 downTo 0 step 8) {
+            writeByte((value shr shift).toByte())
+        }
+    }
+
+    fun writeBytes(value: ByteArray) {
+        reserve(value.size)
+        value.copyInto(bytes, size)
+        size += value.size
+    }
+
+    fun toByteArray(): ByteArray = bytes.copyOf(size)
+}
+
+internal interface FfiConverter<KotlinType, FfiType> {
+    fun lift(value: FfiType): KotlinType
+    fun lower(value: KotlinType): FfiType
+    fun read(buf: UniffiReader): KotlinType
+    fun write(value: KotlinType, buf: UniffiWriter)
+}
+
+// FfiConverter for types that are passed across the FFI in a serialized `RustBuffer`
+internal interface FfiConverterRustBuffer<KotlinType> : FfiConverter<KotlinType, CValue<RustBuffer>> {
+    override fun lift(value: CValue<RustBuffer>): KotlinType = read(UniffiReader(uniffiConsumeRustBuffer(value)))
+
+    override fun lower(value: KotlinType): CValue<RustBuffer> {
+        val buf = UniffiWriter()
+        write(value, buf)
+        return uniffiRustBufferFrom(buf.toByteArray())
+    }
+}
+
+internal object FfiConverterUByte : FfiConverter<UByte, UByte> {
+    override fun lift(value: UByte) = value
+    override fun lower(value: UByte) = value
+    override fun read(buf: UniffiReader) = buf.readByte().toUByte()
+    override fun write(value: UByte, buf: UniffiWriter) = buf.writeByte(value.toByte())
+}
+
+internal object FfiConverterByte : FfiConverter<Byte, Byte> {
+    override fun lift(value: Byte) = value
+    override fun lower(value: Byte) = value
+    override fun read(buf: UniffiReader) = buf.readByte()
+    override fun write(value: Byte, buf: UniffiWriter) = buf.writeByte(value)
+}
+
+internal object FfiConverterUShort : FfiConverter<UShort, UShort> {
+    override fun lift(value: UShort) = value
+    override fun lower(value: UShort) = value
+    override fun read(buf: UniffiReader) = buf.readShort().toUShort()
+    override fun write(value: UShort, buf: UniffiWriter) = buf.writeShort(value.toShort())
+}
+
+internal object FfiConverterShort : FfiConverter<Short, Short> {
+    override fun lift(value: Short) = value
+    override fun lower(value: Short) = value
+    override fun read(buf: UniffiReader) = buf.readShort()
+    override fun write(value: Short, buf: UniffiWriter) = buf.writeShort(value)
+}
+
+internal object FfiConverterUInt : FfiConverter<UInt, UInt> {
+    override fun lift(value: UInt) = value
+    override fun lower(value: UInt) = value
+    override fun read(buf: UniffiReader) = buf.readInt().toUInt()
+    override fun write(value: UInt, buf: UniffiWriter) = buf.writeInt(value.toInt())
+}
+
+internal object FfiConverterInt : FfiConverter<Int, Int> {
+    override fun lift(value: Int) = value
+    override fun lower(value: Int) = value
+    override fun read(buf: UniffiReader) = buf.readInt()
+    override fun write(value: Int, buf: UniffiWriter) = buf.writeInt(value)
+}
+
+internal object FfiConverterULong : FfiConverter<ULong, ULong> {
+    override fun lift(value: ULong) = value
+    override fun lower(value: ULong) = value
+    override fun read(buf: UniffiReader) = buf.readLong().toULong()
+    override fun write(value: ULong, buf: UniffiWriter) = buf.writeLong(value.toLong())
+}
+
+internal object FfiConverterLong : FfiConverter<Long, Long> {
+    override fun lift(value: Long) = value
+    override fun lower(value: Long) = value
+    override fun read(buf: UniffiReader) = buf.readLong()
+    override fun write(value: Long, buf: UniffiWriter) = buf.writeLong(value)
+}
+
+internal object FfiConverterFloat : FfiConverter<Float, Float> {
+    override fun lift(value: Float) = value
+    override fun lower(value: Float) = value
+    override fun read(buf: UniffiReader) = Float.fromBits(buf.readInt())
+    override fun write(value: Float, buf: UniffiWriter) = buf.writeInt(value.toRawBits())
+}
+
+internal object FfiConverterDouble : FfiConverter<Double, Double> {
+    override fun lift(value: Double) = value
+    override fun lower(value: Double) = value
+    override fun read(buf: UniffiReader) = Double.fromBits(buf.readLong())
+    override fun write(value: Double, buf: UniffiWriter) = buf.writeLong(value.toRawBits())
+}
+
+internal object FfiConverterBoolean : FfiConverter<Boolean, Byte> {
+    override fun lift(value: Byte) = value.toInt() != 0
+    override fun lower(value: Boolean): Byte = if (value) 1 else 0
+    override fun read(buf: UniffiReader) = lift(buf.readByte())
+    override fun write(value: Boolean, buf: UniffiWriter) = buf.writeByte(lower(value))
+}
+
+// Strings are passed as a `RustBuffer` with just the UTF-8 bytes, and serialized with their
+// length first.
+internal object FfiConverterString : FfiConverter<String, CValue<RustBuffer>> {
+    override fun lift(value: CValue<RustBuffer>) = uniffiConsumeRustBuffer(value).decodeToString()
+    override fun lower(value: String) = uniffiRustBufferFrom(value.encodeToByteArray())
+    override fun read(buf: UniffiReader) = buf.readBytes(buf.readInt()).decodeToString()
+
+    override fun write(value: String, buf: UniffiWriter) {
+        val bytes = value.encodeToByteArray()
+        buf.writeInt(bytes.size)
+        buf.writeBytes(bytes)
+    }
+}
+
+internal object FfiConverterByteArray : FfiConverterRustBuffer<ByteArray> {
+    override fun read(buf: UniffiReader) = buf.readBytes(buf.readInt())
+
+    override fun write(value: ByteArray, buf: UniffiWriter) {
+        buf.writeInt(value.size)
+        buf.writeBytes(value)
+    }
+}
+
+// Frees a Rust object once, from either `close()` or the cleaner.
+internal class UniffiFreeAction(private val pointer: COpaquePointer, private val freeFn: (COpaquePointer) -> Unit) {
+    private val freed = AtomicInt(0)
+
+    fun free() {
+        if (freed.compareAndSet(0, 1)) {
+            freeFn(pointer)
+        }
+    }
+
+    // Forget the pointer without freeing it, once Rust has taken ownership of it
+    fun release() {
+        freed.value = 1
+    }
+}
+
+{%- for type_ in self.converter_types() %}
+{%- let type_name = type_|type_name(ci) %}
+{%- let ffi_converter_name = type_|ffi_converter_name %}
+{%- match type_ %}
+
+{%- when Type::Optional { inner_type } %}
+
+internal object {{ ffi_converter_name }} : FfiConverterRustBuffer<{{ type_name }}> {
+    override fun read(buf: UniffiReader): {{ type_name }} {
+        if (buf.readByte().toInt() == 0) {
+            return null
+        }
+        return {{ inner_type|read_fn }}(buf)
+    }
+
+    override fun write(value: {{ type_name }}, buf: UniffiWriter) {
+        if (value == null) {
+            buf.writeByte(0)
+        } else {
+            buf.writeByte(1)
+            {{ inner_type|write_fn }}(value, buf)
+        }
+    }
+}
+
+{%- when Type::Sequence { inner_type } %}
+
+internal object {{ ffi_converter_name }} : FfiConverterRustBuffer<{{ type_name }}> {
+    override fun read(buf: UniffiReader): {{ type_name }} {
+        val len = buf.readInt()
+        return List(len) { {{ inner_type|read_fn }}(buf) }
+    }
+
+    override fun write(value: {{ type_name }}, buf: UniffiWriter) {
+        buf.writeInt(value.size)
+        value.forEach { {{ inner_type|write_fn }}(it, buf) }
+    }
+}
+
+{%- when Type::Map { key_type, value_type } %}
+
+internal object {{ ffi_converter_name }} : FfiConverterRustBuffer<{{ type_name }}> {
+    override fun read(buf: UniffiReader): {{ type_name }} {
+        val len = buf.readInt()
+        return buildMap(len) {
+            repeat(len) {
+                val k = {{ key_type|read_fn }}(buf)
+                val v = {{ value_type|read_fn }}(buf)
+                this[k] = v
+            }
+        }
+    }
+
+    override fun write(value: {{ type_name }}, buf: UniffiWriter) {
+        buf.writeInt(value.size)
+        value.forEach { (k, v) ->
+            {{ key_type|write_fn }}(k, buf)
+            {{ value_type|write_fn }}(v, buf)
+        }
+    }
+}
+
+{%- when Type::Record { name, module_path } %}
+{%- let rec = ci|get_record_definition(name) %}
+
+internal object {{ ffi_converter_name }} : FfiConverterRustBuffer<{{ type_name }}> {
+    override fun read(buf: UniffiReader): {{ type_name }} {
+        return {{ type_name }}(
+        {%- for field in rec.fields() %}
+            {{ field|read_fn }}(buf),
+        {%- endfor %}
+        )
+    }
+
+    override fun write(value: {{ type_name }}, buf: UniffiWriter) {
+        {%- for field in rec.fields() %}
+        {{ field|write_fn }}(value.{{ field.name()|var_name }}, buf)
+        {%- endfor %}
+    }
+}
+
+{%- when Type::Enum { name, module_path } %}
+{%- let e = ci.get_enum_definition(name).unwrap() %}
+{%- if e.is_flat() %}
+
+internal object {{ ffi_converter_name }} : FfiConverterRustBuffer<{{ type_name }}> {
+    override fun read(buf: UniffiReader) = {{ type_name }}.values()[buf.readInt() - 1]
+
+    override fun write(value: {{ type_name }}, buf: UniffiWriter) = buf.writeInt(value.ordinal + 1)
+}
+{%- else %}
+
+internal object {{ ffi_converter_name }} : FfiConverterRustBuffer<{{ type_name }}> {
+    override fun read(buf: UniffiReader): {{ type_name }} {
+        return when (buf.readInt()) {
+            {%- for variant in e.variants() %}
+            {{ loop.index }} -> {{ type_name }}.{{ variant|type_name(ci) }}{% if variant.has_fields() %}(
+                {%- for field in variant.fields() %}
+                {{ field|read_fn }}(buf),
+                {%- endfor %}
+            ){% endif %}
+            {%- endfor %}
+            else -> throw RuntimeException("invalid enum value, something is very wrong!!")
+        }
+    }
+
+    override fun write(value: {{ type_name }}, buf: UniffiWriter) {
+        when (value) {
+            {%- for variant in e.variants() %}
+            is {{ type_name }}.{{ variant|type_name(ci) }} -> {
+                buf.writeInt({{ loop.index }})
+                {%- for field in variant.fields() %}
+                {{ field|write_fn }}(value.{% call kt::field_name(field, loop.index) %}, buf)
+                {%- endfor %}
+            }
+            {%- endfor %}
+        }
+    }
+}
+{%- endif %}
+
+{%- when Type::Object { module_path, name, imp } %}
+
+internal object {{ ffi_converter_name }} : FfiConverter<{{ type_name }}, COpaquePointer?> {
+    override fun lift(value: COpaquePointer?) = {{ type_name }}(value!!)
+    override fun lower(value: {{ type_name }}) = value.uniffiClonePointer()
+    override fun read(buf: UniffiReader) = lift(buf.readLong().toCPointer())
+    override fun write(value: {{ type_name }}, buf: UniffiWriter) = buf.writeLong(lower(value).toLong())
+}
+
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
+
+{%- for obj in self.common_objects() %}
+{%- let (interface_name, impl_class_name) = obj|object_names(ci) %}
+
+// Objects keep count of the calls in flight, like on the JVM, so that the Rust object is only
+// freed once `close()` was called and all calls have completed.  If `close()` isn't called, the
+// object is freed by a cleaner once it's unreachable.
+actual open class {{ impl_class_name }} internal constructor(private val pointer: COpaquePointer) {
+    private val freeAction = UniffiFreeAction(pointer) { ptr ->
+        uniffiRustCall { status -> {{ obj.ffi_object_free().name() }}(ptr, status) }
+    }
+    private val cleaner = createCleaner(freeAction) { it.free() }
+    private val wasDestroyed = AtomicInt(0)
+    private val callCounter = AtomicLong(1L)
+
+    {%- match obj.primary_constructor() %}
+    {%- when Some(cons) %}
+    {%- if self.is_common_constructor(cons) %}
+
+    actual constructor({% call kt::arg_list_protocol(cons) -%}) : this(
+        uniffiRustCall { status -> {% call native_ffi_call(cons, "") %} }!!
+    )
+    {%- endif %}
+    {%- when None %}
+    {%- endmatch %}
+
+    actual fun close() {
+        if (wasDestroyed.compareAndSet(0, 1)) {
+            if (callCounter.decrementAndGet() == 0L) {
+                freeAction.free()
+            }
+        }
+    }
+
+    internal fun uniffiClonePointer(): COpaquePointer? =
+        uniffiRustCall { status -> {{ obj.ffi_object_clone().name() }}(pointer, status) }
+
+    private inline fun <R> callWithPointer(block: (ptr: COpaquePointer?) -> R): R {
+        while (true) {
+            val c = callCounter.value
+            if (c == 0L) {
+                {%- if obj.has_consuming_method() %}
+                if (wasConsumed.value == 1) {
+                    throw IllegalStateException("{{ impl_class_name }} object has already been consumed")
+                }
+                {%- endif %}
+                throw IllegalStateException("{{ impl_class_name }} object has already been destroyed")
+            }
+            if (callCounter.compareAndSet(c, c + 1L)) {
+                break
+            }
+        }
+        try {
+            return block(uniffiClonePointer())
+        } finally {
+            if (callCounter.decrementAndGet() == 0L) {
+                freeAction.free()
+            }
+        }
+    }
+
+    {%- if obj.has_consuming_method() %}
+
+    private val wasConsumed = AtomicInt(0)
+
+    // Call a method that takes `self` by value.  Like on the JVM, this passes Rust the reference
+    // held by this object, so it fails while other calls are in flight.  If Rust can't take the
+    // object, it reports an unexpected error and this object can still be used.
+    private inline fun <R> callConsumingPointer(block: (ptr: COpaquePointer?) -> R): R {
+        if (!wasDestroyed.compareAndSet(0, 1)) {
+            if (wasConsumed.value == 1) {
+                throw IllegalStateException("{{ impl_class_name }} object has already been consumed")
+            }
+            throw IllegalStateException("{{ impl_class_name }} object has already been destroyed")
+        }
+        if (!callCounter.compareAndSet(1L, 0L)) {
+            wasDestroyed.value = 0
+            throw IllegalStateException("{{ impl_class_name }} object is in use and can't be consumed")
+        }
+        wasConsumed.value = 1
+        var consumed = true
+        try {
+            return block(pointer)
+        } catch (e: InternalException) {
+            // Rust panics after taking the object, but reports other internal errors before.
+            consumed = e is RustPanic
+            throw e
+        } finally {
+            if (consumed) {
+                freeAction.release()
+            } else {
+                wasConsumed.value = 0
+                callCounter.value = 1L
+                wasDestroyed.value = 0
+            }
+        }
+    }
+    {%- endif %}
+
+    {%- for meth in obj.methods() %}
+    {%- if self.is_common_method(meth) %}
+
+    actual fun {{ meth.name()|fn_name }}({% call kt::arg_list_protocol(meth) %}){% match meth.return_type() %}{% when Some(return_type) %}: {{ return_type|type_name(ci) }}{% when None %}{% endmatch %} =
+        {% if meth.takes_self_by_value() %}callConsumingPointer{% else %}callWithPointer{% endif %} { ptr ->
+            uniffiRustCall { status -> {% call native_ffi_call(meth, "ptr, ") %} }
+        }{% match meth.return_type() %}{% when Some(return_type) %}.let { {{ return_type|lift_fn }}(it) }{% when None %}{% endmatch %}
+    {%- endif %}
+    {%- endfor %}
+}
+{%- endfor %}
+
+{%- for func in ci.function_definitions() %}
+{%- if self.is_common_function(func) %}
+
+actual fun {{ func.name()|fn_name }}({% call kt::arg_list_protocol(func) %}){% match func.return_type() %}{% when Some(return_type) %}: {{ return_type|type_name(ci) }}{% when None %}{% endmatch %} =
+    uniffiRustCall { status -> {% call native_ffi_call(func, "") %} }{% match func.return_type() %}{% when Some(return_type) %}.let { {{ return_type|lift_fn }}(it) }{% when None %}{% endmatch %}
+{%- endif %}
+{%- endfor %}
+
+{#-
+// Call to the cinterop declaration of a scaffolding function, with the lowered arguments
+-#}
+{%- macro native_ffi_call(func, prefix) -%}
+{{ func.ffi_func().name() }}({{ prefix }}
+{%- for arg in func.arguments() -%}
+{{ arg|lower_fn }}({{ arg.name()|var_name }}), {% endfor -%}
+status)
+{%- endmacro %}
+
+{% import "macros.kt" as kt %}
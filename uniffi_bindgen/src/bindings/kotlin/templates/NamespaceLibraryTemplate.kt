@@ -12,6 +12,18 @@ private inline fun <reified Lib : Library> loadIndirect(
 ): Lib {
     return Native.load<Lib>(findLibraryName(componentName), Lib::class.java)
 }
+{%- if config.uses_jni() %}
+
+private fun loadJni(componentName: String): UniffiLib {
+    // Let JNA find the library, so that it's searched for in the same places as when using JNA
+    // for the calls.
+    val libraryName = findLibraryName(componentName)
+    val libraryFile = NativeLibrary.getInstance(libraryName).file
+        ?: throw UnsatisfiedLinkError("Unable to find the file for library '$libraryName'")
+    System.load(libraryFile.absolutePath)
+    return UniffiJniAdapter()
+}
+{%- endif %}
 
 // Define FFI callback types
 {%- for def in ci.ffi_definitions() %}
@@ -62,7 +74,11 @@ internal open class {{ ffi_struct.name()|ffi_struct_name }}(
 internal interface UniffiLib : Library {
     companion object {
         internal val INSTANCE: UniffiLib by lazy {
+            {%- if config.uses_jni() %}
+            loadJni(componentName = "{{ ci.namespace() }}")
+            {%- else %}
             loadIndirect<UniffiLib>(componentName = "{{ ci.namespace() }}")
+            {%- endif %}
             .also { lib: UniffiLib ->
                 uniffiCheckContractApiVersion(lib)
                 uniffiCheckApiChecksums(lib)
@@ -71,7 +87,7 @@ internal interface UniffiLib : Library {
                 {% endfor -%}
             }
         }
-        {% if ci.contains_object_types() %}
+        {% if ci.contains_object_types() || ci.has_function_types() %}
         // The Cleaner for the whole library
         internal val CLEANER: UniffiCleaner by lazy {
             UniffiCleaner.create()
@@ -85,6 +101,126 @@ internal interface UniffiLib : Library {
     ): {% match func.return_type() %}{% when Some with (return_type) %}{{ return_type.borrow()|ffi_type_name_by_value }}{% when None %}Unit{% endmatch %}
     {% endfor %}
 }
+{%- if config.uses_jni() %}
+
+// Implements `UniffiLib` by calling the JNI shims declared in `{{ self.jni_class_name() }}`.
+// JNA is still used for the FFI structs and callbacks, but not for the calls themselves.
+
+private fun Structure.uniffiJniPointer(): Long {
+    write()
+    return Pointer.nativeValue(pointer)
+}
+
+// Native memory for passing `RustBuffer` and `RustCallStatus` values to a JNI shim.
+//
+// The fields are copied in and out with `ByteBuffer` accesses, rather than allocating JNA structures
+// and synchronizing them with `write()`/`read()` on every call.  The layout is:
+//   - 0: the `RustCallStatus`, with its `errorBuf` at 8
+//   - 32: the `RustBuffer` return value
+//   - 56: the `RustBuffer` and `ForeignBytes` arguments, 24 bytes each
+internal class UniffiJniFrame {
+    private val buffer = ByteBuffer.allocateDirect({{ self.jni_frame_size() }}).order(ByteOrder.nativeOrder())
+    private val address = Pointer.nativeValue(Native.getDirectBufferPointer(buffer))
+
+    // Reset the call status, and return its address
+    fun status(): Long {
+        buffer.put(0, UNIFFI_CALL_SUCCESS)
+        return address
+    }
+
+    fun out(): Long = address + 32
+
+    fun putRustBuffer(offset: Int, value: RustBuffer): Long {
+        buffer.putLong(offset, value.capacity)
+        buffer.putLong(offset + 8, value.len)
+        buffer.putLong(offset + 16, Pointer.nativeValue(value.data))
+        return address + offset
+    }
+
+    fun putForeignBytes(offset: Int, value: ForeignBytes): Long {
+        buffer.putInt(offset, value.len)
+        buffer.putLong(offset + 8, Pointer.nativeValue(value.data))
+        return address + offset
+    }
+
+    fun readStatus(status: UniffiRustCallStatus) {
+        status.code = buffer.get(0)
+        if (status.code != UNIFFI_CALL_SUCCESS) {
+            status.error_buf = readRustBuffer(8)
+        }
+    }
+
+    // The returned buffer uses the frame memory as its JNA memory, rather than allocating its
+    // own.  Its fields are only read from Kotlin, so it doesn't matter that the frame is reused.
+    fun readRustBuffer(offset: Int = 32): RustBuffer.ByValue {
+        val value = RustBuffer.ByValue(Pointer(address + offset))
+        value.capacity = buffer.getLong(offset)
+        value.len = buffer.getLong(offset + 8)
+        value.data = buffer.getLong(offset + 16).let { if (it == 0L) null else Pointer(it) }
+        return value
+    }
+}
+
+// The frames of the current thread.  A call that re-enters Kotlin through a callback can make
+// nested calls, so each call gets its own frame.
+internal class UniffiJniFrames {
+    private val frames = ArrayList<UniffiJniFrame>()
+    private var depth = 0
+
+    fun push(): UniffiJniFrame {
+        if (depth == frames.size) {
+            frames.add(UniffiJniFrame())
+        }
+        return frames[depth++]
+    }
+
+    fun pop() {
+        depth--
+    }
+}
+
+private val uniffiJniFrames = ThreadLocal.withInitial { UniffiJniFrames() }
+
+private inline fun <T> uniffiJniCall(block: (UniffiJniFrame) -> T): T {
+    val frames = uniffiJniFrames.get()
+    val frame = frames.push()
+    try {
+        return block(frame)
+    } finally {
+        frames.pop()
+    }
+}
+
+internal class UniffiJniAdapter : UniffiLib {
+    {% for func in ci.iter_ffi_function_definitions() -%}
+    override fun {{ func.name() }}(
+        {%- call kt::arg_list_ffi_decl(func) %}
+    ): {% match func.return_type() %}{% when Some with (return_type) %}{{ return_type.borrow()|ffi_type_name_by_value }}{% when None %}Unit{% endmatch %} = {% if self.jni_uses_frame(func) %}uniffiJniCall { uniffiFrame ->{% else %}run { {%- endif %}
+        {%- match func.return_type() %}
+        {%- when Some with (return_type) %}
+        {%- if return_type.borrow()|jni_returns_via_frame %}
+        {{ self.jni_class_name() }}.{{ func.name() }}({% call kt::arg_list_jni(func) %}uniffiFrame.out())
+        {%- call kt::jni_read_status(func) %}
+        uniffiFrame.readRustBuffer()
+        {%- else if return_type.borrow()|jni_returns_via_out_pointer %}
+        val uniffiOut = {{ return_type.borrow()|ffi_type_name_by_value }}()
+        {{ self.jni_class_name() }}.{{ func.name() }}({% call kt::arg_list_jni(func) %}uniffiOut.uniffiJniPointer())
+        {%- call kt::jni_read_status(func) %}
+        uniffiOut.read()
+        uniffiOut
+        {%- else %}
+        val uniffiResult = {{ self.jni_class_name() }}.{{ func.name() }}({% call kt::arg_list_jni(func) %}0L)
+        {%- call kt::jni_read_status(func) %}
+        {{ return_type.borrow()|jni_lift("uniffiResult") }}
+        {%- endif %}
+        {%- when None %}
+        {{ self.jni_class_name() }}.{{ func.name() }}({% call kt::arg_list_jni(func) %}0L)
+        {%- call kt::jni_read_status(func) %}
+        {%- endmatch %}
+    }
+    {% endfor %}
+}
+{%- endif %}
 
 private fun uniffiCheckContractApiVersion(lib: UniffiLib) {
     // Get the bindings contract version from our ComponentInterface
@@ -92,15 +228,50 @@ private fun uniffiCheckContractApiVersion(lib: UniffiLib) {
     // Get the scaffolding contract version by calling the into the dylib
     val scaffolding_contract_version = lib.{{ ci.ffi_uniffi_contract_version().name() }}()
     if (bindings_contract_version != scaffolding_contract_version) {
-        throw RuntimeException("UniFFI contract version mismatch: try cleaning and rebuilding your project")
+        throw RuntimeException(
+            "UniFFI contract version mismatch: try cleaning and rebuilding your project " +
+            "(the bindings expect version $bindings_contract_version, the library has version $scaffolding_contract_version)"
+        )
     }
 }
+{%- if config.lenient_checksums() %}
+
+// The scaffolding functions that were disabled because their checksums don't match, with the
+// messages of the exceptions to throw when they're called.
+private val uniffiDisabledFunctions = mutableMapOf<String, String>()
+
+// Get the library to call the scaffolding function `name`, after checking that it wasn't disabled.
+internal fun uniffiEnabledLib(name: String): UniffiLib {
+    val lib = UniffiLib.INSTANCE
+    uniffiDisabledFunctions[name]?.let { throw InternalException(it) }
+    return lib
+}
+{%- endif %}
 
 @Suppress("UNUSED_PARAMETER")
 private fun uniffiCheckApiChecksums(lib: UniffiLib) {
-    {%- for (name, expected_checksum) in ci.iter_checksums() %}
-    if (lib.{{ name }}() != {{ expected_checksum }}.toShort()) {
-        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    val mismatches = mutableListOf<String>()
+    {%- for check in ci.iter_checksum_checks() %}
+    lib.{{ check.checksum_fn_name }}().let { checksum ->
+        if (checksum != {{ check.checksum }}.toShort()) {
+            val detail = "the bindings expect checksum {{ check.checksum }}, the library has checksum ${checksum.toUShort()}"
+            {%- if let Some(ffi_func_name) = check.ffi_func_name %}
+            {%- if config.lenient_checksums() %}
+            uniffiDisabledFunctions["{{ ffi_func_name }}"] = "{{ check.item }} is disabled because of a UniFFI API checksum mismatch: $detail"
+            {%- else %}
+            mismatches.add("{{ check.item }}: $detail")
+            {%- endif %}
+            {%- else %}
+            mismatches.add("{{ check.item }}: $detail")
+            {%- endif %}
+        }
     }
     {%- endfor %}
+    if (mismatches.isNotEmpty()) {
+        throw RuntimeException(
+            "UniFFI API checksum mismatch: try cleaning and rebuilding your project\n" +
+            mismatches.joinToString("\n") { "    $it" }
+        )
+    }
 }
+
@@ -0,0 +1,41 @@
+// Maps Rust pointers to the Kotlin objects wrapping them, for objects with a stable identity.
+//
+// While we hold a reference to a Rust object, Rust hands us the same pointer for it each time: the
+// address of the object, or its slab handle with the `debug-handles` feature, since the slab reuses
+// the slot of an object that already has one.  So when Rust hands us a pointer that we already have
+// a live object for, we can release that extra reference and return the existing object.  Objects
+// are held with a weak reference, so the map doesn't keep them alive.
+internal class UniffiIdentityMap<T : Any> {
+    private val map = HashMap<Long, java.lang.ref.WeakReference<T>>()
+
+    // Get the object wrapping `pointer`, or create one if there's none.
+    //
+    // `free` releases the reference that `pointer` represents if an existing object is returned.
+    @Synchronized
+    fun lift(pointer: Pointer, free: (Pointer) -> Unit, create: (Pointer) -> T): T {
+        val existing = map[Pointer.nativeValue(pointer)]?.get()
+        if (existing != null) {
+            free(pointer)
+            return existing
+        }
+        return create(pointer)
+    }
+
+    // Register a newly created object.  The same reference is passed to `remove()` to unregister it.
+    @Synchronized
+    fun insert(pointer: Pointer, ref: java.lang.ref.WeakReference<T>) {
+        map[Pointer.nativeValue(pointer)] = ref
+    }
+
+    // Unregister an object once it's been destroyed or garbage collected.  This can be called
+    // more than once for the same object.
+    //
+    // A new object may have been registered for the same pointer since, keep that one.
+    @Synchronized
+    fun remove(pointer: Pointer, ref: java.lang.ref.WeakReference<*>) {
+        val key = Pointer.nativeValue(pointer)
+        if (map[key] === ref) {
+            map.remove(key)
+        }
+    }
+}
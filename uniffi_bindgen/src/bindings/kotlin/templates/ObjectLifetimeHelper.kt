@@ -0,0 +1,102 @@
+/**
+ * Reports objects that are garbage collected without being closed.
+ *
+ * This is off by default, since it captures a stack trace for every object allocation.
+ * Turn it on for debug builds by setting [enabled], or the `uniffi.leakDetection` system property.
+ * Only objects allocated while it's on are tracked.
+ */
+object UniffiLeakDetector {
+    @Volatile
+    var enabled: Boolean = System.getProperty("uniffi.leakDetection") == "true"
+
+    /**
+     * Called with the class name and the allocation stack trace of each leaked object.
+     * By default, this logs a warning with `java.util.logging`.
+     */
+    @Volatile
+    var reporter: (String, Throwable) -> Unit = { className, allocationSite ->
+        java.util.logging.Logger.getLogger("uniffi").log(
+            java.util.logging.Level.WARNING,
+            "$className object was garbage collected without calling close()",
+            allocationSite,
+        )
+    }
+}
+
+// Frees the Rust peer of an object, and reports the object to `UniffiLeakDetector` if it was
+// garbage collected without being closed.
+//
+// Subclasses must not capture the object, or it would never become unreachable.
+internal abstract class UniffiCleanUpTask(
+    private val className: String,
+    track: Boolean,
+) : Runnable {
+    private val allocationSite: Throwable? =
+        if (track && UniffiLeakDetector.enabled) Throwable("$className allocated here") else null
+
+    @Volatile
+    internal var closed = false
+
+    abstract fun free()
+
+    override fun run() {
+        if (!closed) {
+            allocationSite?.let { UniffiLeakDetector.reporter(className, it) }
+            {%- if !config.uses_cleaner() %}
+            // With the `close` object cleanup policy, only closing an object frees it.
+            return
+            {%- endif %}
+        }
+        free()
+    }
+}
+
+private class UniffiObjectCleanable(
+    private val task: UniffiCleanUpTask,
+    private val cleanable: UniffiCleaner.Cleanable?,
+) : UniffiCleaner.Cleanable {
+    // Only called once the object has been closed and all in-flight calls have completed.
+    override fun clean() {
+        task.closed = true
+        if (cleanable != null) {
+            cleanable.clean()
+        } else {
+            task.run()
+        }
+    }
+}
+
+private fun uniffiRegisterObject(value: Any, task: UniffiCleanUpTask): UniffiCleaner.Cleanable {
+    {%- if config.uses_cleaner() %}
+    val cleanable = UniffiLib.CLEANER.register(value, task)
+    {%- else %}
+    // The cleaner is only needed to report leaks.
+    val cleanable = if (UniffiLeakDetector.enabled) UniffiLib.CLEANER.register(value, task) else null
+    {%- endif %}
+    return UniffiObjectCleanable(task, cleanable)
+}
+
+/**
+ * Closes the objects passed to [closeOnExit] when the scope ends, in reverse order.
+ *
+ * Use this with [uniffiObjectScope] to tie the lifetime of objects to a block of code.
+ */
+class UniffiObjectScope : AutoCloseable {
+    private val objects = mutableListOf<AutoCloseable>()
+
+    fun <T : AutoCloseable> T.closeOnExit(): T {
+        synchronized(objects) { objects.add(this) }
+        return this
+    }
+
+    override fun close() {
+        val toClose = synchronized(objects) { objects.reversed().also { objects.clear() } }
+        toClose.forEach { it.close() }
+    }
+}
+
+/**
+ * Run [block] in a [UniffiObjectScope], closing the objects it registered once it returns or throws.
+ */
+inline fun <R> uniffiObjectScope(block: UniffiObjectScope.() -> R): R =
+    UniffiObjectScope().use { it.block() }
@@ -36,6 +36,8 @@
 //     `drop` methods or do significant work in their `drop` methods.
 //      2. the thread is shared across the whole library. This can be tuned by using `android_cleaner = true`,
 //         or `android = true` in the [`kotlin` section of the `uniffi.toml` file](https://mozilla.github.io/uniffi-rs/kotlin/configuration.html).
+//     Setting `object_cleanup = "close"` there disables the cleaner, so that objects are only freed by `close()`.
+//     Either way, `UniffiLeakDetector` can report objects that are garbage collected without being closed.
 //
 // If we try to implement this with mutual exclusion on access to the pointer, there is the
 // possibility of a race between a method call and a concurrent call to `destroy`:
@@ -96,11 +98,18 @@
 //
 
 {{ self.add_import("java.util.concurrent.atomic.AtomicBoolean") }}
-{%- if self.include_once_check("interface-support") %}
+{%- if self.include_once_check("ObjectCleanerHelper.kt") %}
     {%- include "ObjectCleanerHelper.kt" %}
 {%- endif %}
+{%- if self.include_once_check("interface-support") %}
+    {%- include "ObjectLifetimeHelper.kt" %}
+{%- endif %}
 
 {%- let obj = ci|get_object_definition(name) %}
+{%- if obj.has_stable_identity() && self.include_once_check("ObjectIdentityHelper.kt") %}
+
+{% include "ObjectIdentityHelper.kt" %}
+{%- endif %}
 {%- let (interface_name, impl_class_name) = obj|object_names(ci) %}
 {%- let methods = obj.methods() %}
 {%- let interface_docstring = obj.docstring() %}
@@ -109,16 +118,24 @@
 
 {%- include "Interface.kt" %}
 
-{%- call kt::docstring(obj, 0) %}
+{%- call kt::docstring_value(obj.class_docstring(), 0) %}
 {% if (is_error) %}
 open class {{ impl_class_name }} : Exception, Disposable, AutoCloseable, {{ interface_name }} {
 {% else -%}
-open class {{ impl_class_name }}: Disposable, AutoCloseable, {{ interface_name }} {
+{% if self.is_actual_object(obj) %}actual {% endif %}open class {{ impl_class_name }}: Disposable, AutoCloseable, {{ interface_name }} {
 {%- endif %}
 
     constructor(pointer: Pointer) {
         this.pointer = pointer
-        this.cleanable = UniffiLib.CLEANER.register(this, UniffiCleanAction(pointer))
+        {%- if obj.has_stable_identity() %}
+        val identity = java.lang.ref.WeakReference(this)
+        this.identity = identity
+        this.cleanable = uniffiRegisterObject(this, UniffiCleanAction(pointer, identity{% if obj.has_consuming_method() %}, wasConsumed{% endif %}))
+        // Register last, so other threads can't see a partially constructed object.
+        {{ ffi_converter_name }}.identityMap.insert(pointer, identity)
+        {%- else %}
+        this.cleanable = uniffiRegisterObject(this, UniffiCleanAction(pointer{% if obj.has_consuming_method() %}, wasConsumed = wasConsumed{% endif %}))
+        {%- endif %}
     }
 
     /**
@@ -129,27 +146,47 @@ open class {{ impl_class_name }}: Disposable, AutoCloseable, {{ interface_name }
     @Suppress("UNUSED_PARAMETER")
     constructor(noPointer: NoPointer) {
         this.pointer = null
-        this.cleanable = UniffiLib.CLEANER.register(this, UniffiCleanAction(pointer))
+        {%- if obj.has_stable_identity() %}
+        this.identity = null
+        {%- endif %}
+        this.cleanable = uniffiRegisterObject(this, UniffiCleanAction(pointer))
     }
 
     {%- match obj.primary_constructor() %}
     {%- when Some(cons) %}
     {%- call kt::docstring(cons, 4) %}
+    {%- if self.is_actual_constructor(obj, cons) %}
+    actual constructor({% call kt::arg_list_protocol(cons) -%}) :
+    {%- else %}
     constructor({% call kt::arg_list_decl(cons) -%}) :
+    {%- endif %}
         this({% call kt::to_ffi_call(cons) %})
     {%- when None %}
     {%- endmatch %}
 
     protected val pointer: Pointer?
     protected val cleanable: UniffiCleaner.Cleanable
+    {%- if obj.has_stable_identity() %}
+    private val identity: java.lang.ref.WeakReference<{{ impl_class_name }}>?
+    {%- endif %}
 
     private val wasDestroyed = AtomicBoolean(false)
     private val callCounter = AtomicLong(1)
+    {%- if obj.has_consuming_method() %}
+    private val wasConsumed = AtomicBoolean(false)
+    {%- endif %}
 
     override fun destroy() {
         // Only allow a single call to this method.
         // TODO: maybe we should log a warning if called more than once?
         if (this.wasDestroyed.compareAndSet(false, true)) {
+            {%- if obj.has_stable_identity() %}
+            // Unregister now rather than when the pointer is freed, which waits for in-flight
+            // calls.  Until then `lift` would return this object, which can't be used anymore.
+            this.pointer?.let { ptr ->
+                this.identity?.let { {{ ffi_converter_name }}.identityMap.remove(ptr, it) }
+            }
+            {%- endif %}
             // This decrement always matches the initial count of 1 given at creation time.
             if (this.callCounter.decrementAndGet() == 0L) {
                 cleanable.clean()
@@ -158,7 +195,7 @@ open class {{ impl_class_name }}: Disposable, AutoCloseable, {{ interface_name }
     }
 
     @Synchronized
-    override fun close() {
+    {% if self.is_actual_object(obj) %}actual {% endif %}override fun close() {
         this.destroy()
     }
 
@@ -168,6 +205,11 @@ open class {{ impl_class_name }}: Disposable, AutoCloseable, {{ interface_name }
         do {
             val c = this.callCounter.get()
             if (c == 0L) {
+                {%- if obj.has_consuming_method() %}
+                if (this.wasConsumed.get()) {
+                    throw IllegalStateException("${this.javaClass.simpleName} object has already been consumed")
+                }
+                {%- endif %}
                 throw IllegalStateException("${this.javaClass.simpleName} object has already been destroyed")
             }
             if (c == Long.MAX_VALUE) {
@@ -185,11 +227,75 @@ open class {{ impl_class_name }}: Disposable, AutoCloseable, {{ interface_name }
         }
     }
 
+    {%- if obj.has_consuming_method() %}
+
+    // Call a method that takes `self` by value.  This passes Rust the reference held by this
+    // object, releasing it the way `destroy()` does, so it fails while other calls are in flight.
+    // If Rust takes the object, this object can't be used afterwards.  If it can't, for example
+    // because there are other references to the object, Rust keeps the reference where it was and
+    // reports an unexpected error, and this object can still be used.
+    internal inline fun <R> callConsumingPointer(block: (ptr: Pointer) -> R): R {
+        if (!this.wasDestroyed.compareAndSet(false, true)) {
+            if (this.wasConsumed.get()) {
+                throw IllegalStateException("${this.javaClass.simpleName} object has already been consumed")
+            }
+            throw IllegalStateException("${this.javaClass.simpleName} object has already been destroyed")
+        }
+        // Calls in flight hold references to the object, so Rust couldn't take it anyway.
+        if (!this.callCounter.compareAndSet(1L, 0L)) {
+            this.wasDestroyed.set(false)
+            throw IllegalStateException("${this.javaClass.simpleName} object is in use and can't be consumed")
+        }
+        // Other calls now fail, and the clean-up action won't free the pointer once Rust owns it.
+        this.wasConsumed.set(true)
+        val ptr = this.pointer!!
+        {%- if obj.has_stable_identity() %}
+        this.identity?.let { {{ ffi_converter_name }}.identityMap.remove(ptr, it) }
+        {%- endif %}
+        var consumed = true
+        try {
+            return block(ptr)
+        } catch (e: InternalException) {
+            // Rust panics after taking the object, but reports other internal errors before.
+            consumed = e is RustPanic
+            throw e
+        } finally {
+            if (consumed) {
+                cleanable.clean()
+            } else {
+                {%- if obj.has_stable_identity() %}
+                this.identity?.let { {{ ffi_converter_name }}.identityMap.insert(ptr, it) }
+                {%- endif %}
+                this.wasConsumed.set(false)
+                this.callCounter.set(1L)
+                this.wasDestroyed.set(false)
+            }
+        }
+    }
+    {%- endif %}
+
     // Use a static inner class instead of a closure so as not to accidentally
     // capture `this` as part of the cleanable's action.
-    private class UniffiCleanAction(private val pointer: Pointer?) : Runnable {
-        override fun run() {
+    private class UniffiCleanAction(
+        private val pointer: Pointer?,
+        {%- if obj.has_stable_identity() %}
+        private val identity: java.lang.ref.WeakReference<*>? = null,
+        {%- endif %}
+        {%- if obj.has_consuming_method() %}
+        // Set once a method that takes `self` by value passed the pointer to Rust
+        private val wasConsumed: AtomicBoolean? = null,
+        {%- endif %}
+    ) : UniffiCleanUpTask("{{ impl_class_name }}", track = pointer != null) {
+        override fun free() {
             pointer?.let { ptr ->
+                {%- if obj.has_stable_identity() %}
+                identity?.let { {{ ffi_converter_name }}.identityMap.remove(ptr, it) }
+                {%- endif %}
+                {%- if obj.has_consuming_method() %}
+                if (wasConsumed?.get() == true) {
+                    return
+                }
+                {%- endif %}
                 uniffiRustCall { status ->
                     UniffiLib.INSTANCE.{{ obj.ffi_object_free().name() }}(ptr, status)
                 }
@@ -217,7 +323,7 @@ open class {{ impl_class_name }}: Disposable, AutoCloseable, {{ interface_name }
     ){% match meth.return_type() %}{% when Some with (return_type) %} : {{ return_type|type_name(ci) }}{% when None %}{%- endmatch %} {
         return uniffiRustCallAsync(
             callWithPointer { thisPtr ->
-                UniffiLib.INSTANCE.{{ meth.ffi_func().name() }}(
+                {% call kt::ffi_lib(meth) %}.{{ meth.ffi_func().name() }}(
                     thisPtr,
                     {% call kt::arg_list_lowered(meth) %}
                 )
@@ -241,23 +347,45 @@ open class {{ impl_class_name }}: Disposable, AutoCloseable, {{ interface_name }
             {%- endmatch %}
         )
     }
+    {%- else if meth.is_blocking() -%}
+    {%- match meth.return_type() -%}
+    {%- when Some with (return_type) -%}
+    override suspend fun {{ meth.name()|fn_name }}(
+        {%- call kt::arg_list_protocol(meth) -%}
+    ): {{ return_type|type_name(ci) }} = withContext(Dispatchers.IO) {
+        callWithPointer {
+            {%- call kt::to_ffi_call_with_prefix("it", meth) %}
+        }.let {
+            {{ return_type|lift_fn }}(it)
+        }
+    }
+
+    {%- when None -%}
+    override suspend fun {{ meth.name()|fn_name }}(
+        {%- call kt::arg_list_protocol(meth) -%}
+    ) = withContext(Dispatchers.IO) {
+        callWithPointer {
+            {%- call kt::to_ffi_call_with_prefix("it", meth) %}
+        }
+    }
+    {% endmatch %}
     {%- else -%}
     {%- match meth.return_type() -%}
     {%- when Some with (return_type) -%}
-    override fun {{ meth.name()|fn_name }}(
+    {% if self.is_actual_method(obj, meth) %}actual {% endif %}override fun {{ meth.name()|fn_name }}(
         {%- call kt::arg_list_protocol(meth) -%}
     ): {{ return_type|type_name(ci) }} =
-        callWithPointer {
+        {% if meth.takes_self_by_value() %}callConsumingPointer{% else %}callWithPointer{% endif %} {
             {%- call kt::to_ffi_call_with_prefix("it", meth) %}
         }.let {
             {{ return_type|lift_fn }}(it)
         }
 
     {%- when None -%}
-    override fun {{ meth.name()|fn_name }}(
+    {% if self.is_actual_method(obj, meth) %}actual {% endif %}override fun {{ meth.name()|fn_name }}(
         {%- call kt::arg_list_protocol(meth) -%}
     ) =
-        callWithPointer {
+        {% if meth.takes_self_by_value() %}callConsumingPointer{% else %}callWithPointer{% endif %} {
             {%- call kt::to_ffi_call_with_prefix("it", meth) %}
         }
     {% endmatch %}
@@ -331,6 +459,9 @@ public object {{ ffi_converter_name }}: FfiConverter<{{ type_name }}, Pointer> {
     {%- if obj.has_callback_interface() %}
     internal val handleMap = UniffiHandleMap<{{ type_name }}>()
     {%- endif %}
+    {%- if obj.has_stable_identity() %}
+    internal val identityMap = UniffiIdentityMap<{{ impl_class_name }}>()
+    {%- endif %}
 
     override fun lower(value: {{ type_name }}): Pointer {
         {%- if obj.has_callback_interface() %}
@@ -341,7 +472,15 @@ public object {{ ffi_converter_name }}: FfiConverter<{{ type_name }}, Pointer> {
     }
 
     override fun lift(value: Pointer): {{ type_name }} {
+        {%- if obj.has_stable_identity() %}
+        return identityMap.lift(
+            value,
+            { ptr -> uniffiRustCall { status -> UniffiLib.INSTANCE.{{ obj.ffi_object_free().name() }}(ptr, status) } },
+            { ptr -> {{ impl_class_name }}(ptr) },
+        )
+        {%- else %}
         return {{ impl_class_name }}(value)
+        {%- endif %}
     }
 
     override fun read(buf: ByteBuffer): {{ type_name }} {
@@ -1,11 +1,14 @@
 {%- let rec = ci|get_record_definition(name) %}
 
+{%- if self.renders_declaration(type_) %}
+
 {%- if rec.has_fields() %}
 {%- call kt::docstring(rec, 0) %}
+{%- call kt::serializable_annotation(serializable) %}
 data class {{ type_name }} (
     {%- for field in rec.fields() %}
     {%- call kt::docstring(field, 4) %}
-    {% if config.generate_immutable_records() %}val{% else %}var{% endif %} {{ field.name()|var_name }}: {{ field|type_name(ci) -}}
+    {% if config.generate_immutable_records() %}val{% else %}var{% endif %} {{ field.name()|var_name }}: {% call kt::field_type_name(field, serializable) -%}
     {%- match field.default_value() %}
         {%- when Some with(literal) %} = {{ literal|render_literal(field, ci) }}
         {%- else %}
@@ -23,18 +26,22 @@ data class {{ type_name }} (
 }
 {%- else -%}
 {%- call kt::docstring(rec, 0) %}
+{%- call kt::serializable_annotation(serializable) %}
 class {{ type_name }} {
     override fun equals(other: Any?): Boolean {
         return other is {{ type_name }}
     }
 
     override fun hashCode(): Int {
-        return javaClass.hashCode()
+        return this::class.hashCode()
     }
 
     companion object
 }
 {%- endif %}
+{%- endif %}
+
+{%- if !self.common %}
 
 public object {{ rec|ffi_converter_name }}: FfiConverterRustBuffer<{{ type_name }}> {
     override fun read(buf: ByteBuffer): {{ type_name }} {
@@ -61,3 +68,4 @@ public object {{ rec|ffi_converter_name }}: FfiConverterRustBuffer<{{ type_name
         {%- endfor %}
     }
 }
+{%- endif %}
@@ -3,14 +3,23 @@
 // pointer to the underlying data.
 
 @Structure.FieldOrder("capacity", "len", "data")
+{%- if config.uses_jni() %}
+// Buffers returned by the JNI shims use the memory they were returned in, see `UniffiJniFrame`.
+open class RustBuffer(pointer: Pointer? = null) : Structure(pointer) {
+{%- else %}
 open class RustBuffer : Structure() {
+{%- endif %}
     // Note: `capacity` and `len` are actually `ULong` values, but JVM only supports signed values.
     // When dealing with these fields, make sure to call `toULong()`.
     @JvmField var capacity: Long = 0
     @JvmField var len: Long = 0
     @JvmField var data: Pointer? = null
 
+    {%- if config.uses_jni() %}
+    class ByValue(pointer: Pointer? = null): RustBuffer(pointer), Structure.ByValue
+    {%- else %}
     class ByValue: RustBuffer(), Structure.ByValue
+    {%- endif %}
     class ByReference: RustBuffer(), Structure.ByReference
 
    internal fun setValue(other: RustBuffer) {
@@ -9,7 +9,7 @@
 @Suppress("ASSIGNED_BUT_NEVER_ACCESSED_VARIABLE")
 suspend fun {{ func.name()|fn_name }}({%- call kt::arg_list_decl(func) -%}){% match func.return_type() %}{% when Some with (return_type) %} : {{ return_type|type_name(ci) }}{% when None %}{%- endmatch %} {
     return uniffiRustCallAsync(
-        UniffiLib.INSTANCE.{{ func.ffi_func().name() }}({% call kt::arg_list_lowered(func) %}),
+        {% call kt::ffi_lib(func) %}.{{ func.ffi_func().name() }}({% call kt::arg_list_lowered(func) %}),
         {{ func|async_poll(ci) }},
         {{ func|async_complete(ci) }},
         {{ func|async_free(ci) }},
@@ -30,6 +30,38 @@ suspend fun {{ func.name()|fn_name }}({%- call kt::arg_list_decl(func) -%}){% ma
     )
 }
 
+{%- else if func.is_blocking() %}
+{%- match func.throws_type() -%}
+{%- when Some with (throwable) %}
+@Throws({{ throwable|type_name(ci) }}::class)
+{%- else -%}
+{%- endmatch %}
+
+suspend fun {{ func.name()|fn_name }}({%- call kt::arg_list_decl(func) -%}){% match func.return_type() %}{% when Some with (return_type) %} : {{ return_type|type_name(ci) }}{% when None %}{%- endmatch %} {
+    return withContext(Dispatchers.IO) {
+        {%- match func.return_type() %}
+        {%- when Some with (return_type) %}
+        {{ return_type|lift_fn }}({% call kt::to_ffi_call(func) %})
+        {%- when None %}
+        {% call kt::to_ffi_call(func) %}
+        {%- endmatch %}
+    }
+}
+
+{%- else if self.is_actual_function(func) %}
+{#- Default values are declared by the `expect` function in `commonMain` #}
+{%- match func.return_type() -%}
+{%- when Some with (return_type) %}
+
+actual fun {{ func.name()|fn_name }}({%- call kt::arg_list_protocol(func) -%}): {{ return_type|type_name(ci) }} {
+    return {{ return_type|lift_fn }}({% call kt::to_ffi_call(func) %})
+}
+{% when None %}
+
+actual fun {{ func.name()|fn_name }}({% call kt::arg_list_protocol(func) %}) =
+    {% call kt::to_ffi_call(func) %}
+
+{% endmatch %}
 {%- else %}
 {%- match func.throws_type() -%}
 {%- when Some with (throwable) %}
@@ -1,5 +1,6 @@
 {%- import "macros.kt" as kt %}
 
+{%- if !self.common %}
 // Interface implemented by anything that can contain an object reference.
 //
 // Such types expose a `destroy()` method that must be called to cleanly
@@ -32,12 +33,15 @@ inline fun <T : Disposable?, R> T.use(block: (T) -> R) =
 
 /** Used to instantiate an interface without an actual pointer, for fakes in tests, mostly. */
 object NoPointer
+{%- endif %}
 
 {%- for type_ in ci.iter_types() %}
 {%- let type_name = type_|type_name(ci) %}
 {%- let ffi_converter_name = type_|ffi_converter_name %}
 {%- let canonical_type_name = type_|canonical_name %}
 {%- let contains_object_references = ci.item_contains_object_references(type_) %}
+{%- let serializable = config.kotlinx_serialization() && !contains_object_references %}
+{%- if !self.common || self.is_common_type(type_) %}
 
 {#
  # Map `Type` instances to an include statement for that type.
@@ -124,14 +128,18 @@ object NoPointer
 {%- when Type::Custom { module_path, name, builtin } %}
 {% include "CustomTypeTemplate.kt" %}
 
+{%- when Type::Function { args, ret } %}
+{% include "FunctionTemplate.kt" %}
+
 {%- when Type::External { module_path, name, namespace, kind, tagged } %}
 {% include "ExternalTypeTemplate.kt" %}
 
 {%- else %}
 {%- endmatch %}
+{%- endif %}
 {%- endfor %}
 
-{%- if ci.has_async_fns() %}
+{%- if ci.has_async_fns() && !self.common %}
 {# Import types needed for async support #}
 {{ self.add_import("kotlin.coroutines.resume") }}
 {{ self.add_import("kotlinx.coroutines.launch") }}
@@ -141,3 +149,9 @@ object NoPointer
 {{ self.add_import("kotlinx.coroutines.Job") }}
 {{ self.add_import("kotlinx.coroutines.GlobalScope") }}
 {%- endif %}
+
+{%- if ci.has_blocking_fns() && !self.common %}
+{# Import types needed to run blocking functions in the background #}
+{{ self.add_import("kotlinx.coroutines.Dispatchers") }}
+{{ self.add_import("kotlinx.coroutines.withContext") }}
+{%- endif %}
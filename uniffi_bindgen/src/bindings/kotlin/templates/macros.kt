@@ -11,7 +11,7 @@
     {%- else %}
     uniffiRustCall()
     {%- endmatch %} { _status ->
-    UniffiLib.INSTANCE.{{ func.ffi_func().name() }}({% call arg_list_lowered(func) -%} _status)
+    {% call ffi_lib(func) %}.{{ func.ffi_func().name() }}({% call arg_list_lowered(func) -%} _status)
 }
 {%- endmacro -%}
 
@@ -22,13 +22,25 @@
     {%- else %}
     uniffiRustCall()
     {%- endmatch %} { _status ->
-    UniffiLib.INSTANCE.{{ func.ffi_func().name() }}(
+    {% call ffi_lib(func) %}.{{ func.ffi_func().name() }}(
         {{- prefix }},
         {% call arg_list_lowered(func) %}
         _status)
 }
 {%- endmacro %}
 
+{#-
+// The library to call a scaffolding function on, which checks that the function wasn't disabled
+// because of a checksum mismatch, in lenient mode.
+-#}
+{%- macro ffi_lib(func) -%}
+{%- if config.lenient_checksums() -%}
+uniffiEnabledLib("{{ func.ffi_func().name() }}")
+{%- else -%}
+UniffiLib.INSTANCE
+{%- endif -%}
+{%- endmacro -%}
+
 {%- macro arg_list_lowered(func) %}
     {%- for arg in func.arguments() %}
         {{- arg|lower_fn }}({{ arg.name()|var_name }}),
@@ -68,6 +80,34 @@
     {%- if func.has_rust_call_status_arg() %}uniffi_out_err: UniffiRustCallStatus, {% endif %}
 {%- endmacro -%}
 
+{#-
+// Arglist as used in the UniffiJniLib function declarations.
+-#}
+{%- macro arg_list_jni_decl(func) %}
+    {%- for arg in func.arguments() %}
+        {{- arg.name()|var_name }}: {{ arg.type_().borrow()|ffi_type_name_jni -}},
+    {%- endfor %}
+    {%- if func.has_rust_call_status_arg() %}uniffi_out_err: Long, {% endif %}uniffi_out: Long,
+{%- endmacro -%}
+
+{#-
+// Arguments for a JNI shim call, from the arguments of the `UniffiLib` method
+-#}
+{%- macro arg_list_jni(func) %}
+    {%- for arg in self.jni_args(func) %}
+        {{- arg }},
+    {%- endfor %}
+{%- endmacro -%}
+
+{#-
+// Copy the call status of a JNI shim call out of its frame
+-#}
+{%- macro jni_read_status(func) %}
+{%- if func.has_rust_call_status_arg() %}
+        uniffiFrame.readStatus(uniffi_out_err)
+{%- endif %}
+{%- endmacro -%}
+
 {% macro field_name(field, field_num) %}
 {%- if field.name().is_empty() -%}
 v{{- field_num -}}
@@ -84,6 +124,26 @@ v{{- field_num -}}
     {% endfor -%})
 {%- endmacro -%}
 
+{%- macro serializable_annotation(serializable) %}
+{%- if serializable %}
+{{- self.add_import("kotlinx.serialization.Serializable") }}
+@Serializable
+{%- endif %}
+{%- endmacro %}
+
+{#-
+// The type of a record or enum variant field, with `@Contextual` where a `@Serializable` class
+// needs it.
+-#}
+{%- macro field_type_name(field, serializable) %}
+{%- if serializable && field|has_contextual_types %}
+{{- self.add_import("kotlinx.serialization.Contextual") }}
+{{- field|serializable_type_name(ci) }}
+{%- else %}
+{{- field|type_name(ci) }}
+{%- endif %}
+{%- endmacro %}
+
 {%- macro docstring_value(maybe_docstring, indent_spaces) %}
 {%- match maybe_docstring %}
 {%- when Some(docstring) %}
@@ -26,6 +26,10 @@ import com.sun.jna.Pointer
 import com.sun.jna.Structure
 import com.sun.jna.Callback
 import com.sun.jna.ptr.*
+{%- if config.uses_jni() %}
+import com.sun.jna.CallbackReference
+import com.sun.jna.NativeLibrary
+{%- endif %}
 import java.nio.ByteBuffer
 import java.nio.ByteOrder
 import java.nio.CharBuffer
@@ -51,6 +55,11 @@ import java.util.concurrent.ConcurrentHashMap
 {% include "Async.kt" %}
 {%- endif %}
 
+{%- if config.log_bridge() %}
+
+{% include "LogBridge.kt" %}
+{%- endif %}
+
 // Public interface members begin here.
 {{ type_helper_code }}
 
@@ -35,13 +35,19 @@ pub fn run_script(
 ) -> Result<()> {
     let script_path = Utf8Path::new(script_file);
     let test_helper = UniFFITestHelper::new(crate_name)?;
-    let out_dir = test_helper.create_out_dir(tmp_dir, script_path)?;
+    // Scripts can be run with different configs, give each run its own directory
+    let out_dir = match &options.config_file {
+        Some(config_file) => {
+            test_helper.create_out_dir(tmp_dir, script_path.join(config_file.as_str()))?
+        }
+        None => test_helper.create_out_dir(tmp_dir, script_path)?,
+    };
     let cdylib_path = test_helper.copy_cdylib_to_out_dir(&out_dir)?;
     generate_bindings(
         &cdylib_path,
         None,
         &[TargetLanguage::Kotlin],
-        None,
+        options.config_file.as_deref(),
         &out_dir,
         false,
     )?;
@@ -99,6 +105,7 @@ fn build_jar(
         .arg(&jar_file)
         .arg("-classpath")
         .arg(calc_classpath(vec![]))
+        .args(serialization_plugin_args(&sources)?)
         .args(sources);
 
     let status = command
@@ -112,6 +119,37 @@ fn build_jar(
     Ok(jar_file)
 }
 
+/// Compiler plugin arguments for bindings generated with `serialization = "kotlinx"`
+fn serialization_plugin_args(sources: &[String]) -> Result<Vec<String>> {
+    let mut uses_serialization = false;
+    for source in sources {
+        if std::fs::read_to_string(source)?.contains("import kotlinx.serialization.") {
+            uses_serialization = true;
+            break;
+        }
+    }
+    if !uses_serialization {
+        return Ok(vec![]);
+    }
+    let plugin = match env::var("KOTLINX_SERIALIZATION_PLUGIN") {
+        Ok(plugin) => Utf8PathBuf::from(plugin),
+        // kotlinc ships with the plugin, in `lib/` next to `bin/kotlinc`
+        Err(_) => {
+            let kotlinc = env::split_paths(&env::var_os("PATH").unwrap_or_default())
+                .map(|dir| dir.join("kotlinc"))
+                .find(|path| path.is_file())
+                .context("Failed to find `kotlinc`")?;
+            let kotlinc = Utf8PathBuf::try_from(kotlinc.canonicalize()?)?;
+            kotlinc
+                .parent()
+                .and_then(Utf8Path::parent)
+                .context("Failed to find the kotlin installation directory")?
+                .join("lib/kotlinx-serialization-compiler-plugin.jar")
+        }
+    };
+    Ok(vec![format!("-Xplugin={plugin}")])
+}
+
 fn kotlinc_command(options: &RunScriptOptions) -> Command {
     let mut command = Command::new("kotlinc");
     if !options.show_compiler_messages {
@@ -8,16 +8,21 @@
 //! along with some helpers for executing foreign language scripts or tests.
 
 use anyhow::{bail, Result};
-use camino::Utf8Path;
+use camino::{Utf8Path, Utf8PathBuf};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
 use crate::interface::ComponentInterface;
 
+pub mod c;
+pub mod cpp;
+pub mod csharp;
+pub mod go;
 pub mod kotlin;
 pub mod python;
 pub mod ruby;
 pub mod swift;
+pub mod typescript;
 
 /// Enumeration of all foreign language targets currently supported by this crate.
 ///
@@ -25,13 +30,20 @@ pub mod swift;
 /// on the provided `TargetLanguage`. For convenience of calling code we also provide
 /// a few `TryFrom` implementations to help guess the correct target language from
 /// e.g. a file extension of command-line argument.
-#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 pub enum TargetLanguage {
     Kotlin,
     Swift,
     Python,
     Ruby,
+    C,
+    Cpp,
+    Go,
+    #[cfg_attr(feature = "clap", value(name = "csharp"))]
+    CSharp,
+    #[cfg_attr(feature = "clap", value(name = "typescript"))]
+    TypeScript,
 }
 
 impl fmt::Display for TargetLanguage {
@@ -41,6 +53,11 @@ impl fmt::Display for TargetLanguage {
             Self::Swift => write!(f, "swift"),
             Self::Python => write!(f, "python"),
             Self::Ruby => write!(f, "ruby"),
+            Self::C => write!(f, "c"),
+            Self::Cpp => write!(f, "cpp"),
+            Self::Go => write!(f, "go"),
+            Self::CSharp => write!(f, "csharp"),
+            Self::TypeScript => write!(f, "typescript"),
         }
     }
 }
@@ -49,12 +66,16 @@ impl fmt::Display for TargetLanguage {
 #[derive(Clone, Debug)]
 pub struct RunScriptOptions {
     pub show_compiler_messages: bool,
+    /// Config file to generate the bindings with, instead of the crate's `uniffi.toml`.
+    /// This is only supported for Kotlin.
+    pub config_file: Option<Utf8PathBuf>,
 }
 
 impl Default for RunScriptOptions {
     fn default() -> Self {
         Self {
             show_compiler_messages: true,
+            config_file: None,
         }
     }
 }
@@ -67,6 +88,11 @@ impl TryFrom<&str> for TargetLanguage {
             "swift" => TargetLanguage::Swift,
             "python" | "py" => TargetLanguage::Python,
             "ruby" | "rb" => TargetLanguage::Ruby,
+            "c" | "h" => TargetLanguage::C,
+            "cpp" | "c++" | "cxx" | "hpp" => TargetLanguage::Cpp,
+            "go" | "golang" => TargetLanguage::Go,
+            "csharp" | "c#" | "cs" | "dotnet" => TargetLanguage::CSharp,
+            "typescript" | "ts" | "js" | "node" => TargetLanguage::TypeScript,
             _ => bail!("Unknown or unsupported target language: \"{value}\""),
         })
     }
@@ -99,6 +125,16 @@ pub struct Config {
     pub(crate) python: python::Config,
     #[serde(default)]
     pub(crate) ruby: ruby::Config,
+    #[serde(default)]
+    pub(crate) c: c::Config,
+    #[serde(default)]
+    pub(crate) cpp: cpp::Config,
+    #[serde(default)]
+    pub(crate) go: go::Config,
+    #[serde(default)]
+    pub(crate) csharp: csharp::Config,
+    #[serde(default)]
+    pub(crate) typescript: typescript::Config,
 }
 
 /// Generate foreign language bindings from a compiled `uniffi` library.
@@ -120,6 +156,15 @@ pub fn write_bindings(
             python::write_bindings(&config.python, ci, out_dir, try_format_code)?
         }
         TargetLanguage::Ruby => ruby::write_bindings(&config.ruby, ci, out_dir, try_format_code)?,
+        TargetLanguage::C => c::write_bindings(&config.c, ci, out_dir, try_format_code)?,
+        TargetLanguage::Cpp => cpp::write_bindings(&config.cpp, ci, out_dir, try_format_code)?,
+        TargetLanguage::Go => go::write_bindings(&config.go, ci, out_dir, try_format_code)?,
+        TargetLanguage::CSharp => {
+            csharp::write_bindings(&config.csharp, ci, out_dir, try_format_code)?
+        }
+        TargetLanguage::TypeScript => {
+            typescript::write_bindings(&config.typescript, ci, out_dir, try_format_code)?
+        }
     }
     Ok(())
 }
@@ -0,0 +1,53 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use super::CodeType;
+use crate::backend::{Literal, Type};
+
+#[derive(Debug)]
+pub struct FunctionCodeType {
+    args: Vec<Type>,
+    ret: Option<Type>,
+}
+
+impl FunctionCodeType {
+    pub fn new(args: Vec<Type>, ret: Option<Type>) -> Self {
+        Self { args, ret }
+    }
+}
+
+impl CodeType for FunctionCodeType {
+    fn type_label(&self) -> String {
+        let args = self
+            .args
+            .iter()
+            .map(|t| super::PythonCodeOracle.find(t).type_label())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let ret = match &self.ret {
+            Some(t) => super::PythonCodeOracle.find(t).type_label(),
+            None => "None".to_string(),
+        };
+        format!("typing.Callable[[{args}], {ret}]")
+    }
+
+    fn canonical_name(&self) -> String {
+        let args = self
+            .args
+            .iter()
+            .map(|t| super::PythonCodeOracle.find(t).canonical_name())
+            .collect::<String>();
+        match &self.ret {
+            Some(t) => format!(
+                "Function{args}To{}",
+                super::PythonCodeOracle.find(t).canonical_name()
+            ),
+            None => format!("Function{args}"),
+        }
+    }
+
+    fn literal(&self, _literal: &Literal) -> String {
+        unreachable!();
+    }
+}
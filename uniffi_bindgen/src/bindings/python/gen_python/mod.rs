@@ -21,6 +21,7 @@ mod compounds;
 mod custom;
 mod enum_;
 mod external;
+mod function;
 mod miscellany;
 mod object;
 mod primitives;
@@ -115,6 +116,9 @@ pub struct Config {
     custom_types: HashMap<String, CustomTypeConfig>,
     #[serde(default)]
     external_packages: HashMap<String, String>,
+    dict_conversion: Option<bool>,
+    lenient_checksums: Option<bool>,
+    log_bridge: Option<bool>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -135,6 +139,22 @@ impl Config {
         }
     }
 
+    /// Whether to generate `to_dict()`/`from_dict()` methods for records and enums
+    pub fn dict_conversion(&self) -> bool {
+        self.dict_conversion.unwrap_or(false)
+    }
+
+    /// Whether to only disable the functions with mismatched API checksums, rather than failing
+    /// to load the library
+    pub fn lenient_checksums(&self) -> bool {
+        self.lenient_checksums.unwrap_or(false)
+    }
+
+    /// Whether to generate the API that forwards Rust log records to the platform logger
+    pub fn log_bridge(&self) -> bool {
+        self.log_bridge.unwrap_or(false)
+    }
+
     /// Get the package name for a given external namespace.
     pub fn module_for_namespace(&self, ns: &str) -> String {
         let ns = ns.to_string().to_snake_case();
@@ -470,6 +490,9 @@ impl<T: AsType> AsCodeType for T {
                 key_type,
                 value_type,
             } => Box::new(compounds::MapCodeType::new(*key_type, *value_type)),
+            Type::Function { args, ret } => {
+                Box::new(function::FunctionCodeType::new(args, ret.map(|t| *t)))
+            }
             Type::External { name, .. } => Box::new(external::ExternalCodeType::new(name)),
             Type::Custom { name, .. } => Box::new(custom::CustomCodeType::new(name)),
         }
@@ -480,6 +503,98 @@ pub mod filters {
     use super::*;
     pub use crate::backend::filters::*;
 
+    /// Python expression that converts `value` into plain `dict`/`list`/builtin values.
+    ///
+    /// Records and enums are converted with their generated `to_dict()` method and compound types
+    /// are converted element-wise.  Timestamps, durations and bytes become an ISO 8601 string, a
+    /// number of seconds and a base64 string, so that the result can be passed to `json.dumps()`.
+    /// Everything else is passed through unchanged. `depth` is used to keep comprehension variable
+    /// names unique when compound types are nested.
+    fn to_dict_expr(ci: &ComponentInterface, type_: &Type, value: &str, depth: usize) -> String {
+        match type_ {
+            Type::Record { .. }
+            | Type::External {
+                kind: ExternalKind::DataClass,
+                ..
+            } => format!("{value}.to_dict()"),
+            Type::Enum { name, .. } if !ci.is_name_used_as_error(name) => {
+                format!("{value}.to_dict()")
+            }
+            Type::Optional { inner_type } => format!(
+                "(None if {value} is None else {})",
+                to_dict_expr(ci, inner_type, value, depth)
+            ),
+            Type::Sequence { inner_type } => {
+                let item = format!("_v{depth}");
+                format!(
+                    "[{} for {item} in {value}]",
+                    to_dict_expr(ci, inner_type, &item, depth + 1)
+                )
+            }
+            Type::Map {
+                key_type,
+                value_type,
+            } => {
+                let (k, v) = (format!("_k{depth}"), format!("_v{depth}"));
+                format!(
+                    "{{{}: {} for ({k}, {v}) in {value}.items()}}",
+                    to_dict_expr(ci, key_type, &k, depth + 1),
+                    to_dict_expr(ci, value_type, &v, depth + 1)
+                )
+            }
+            Type::Timestamp => format!("{value}.isoformat()"),
+            Type::Duration => format!("{value}.total_seconds()"),
+            Type::Bytes => format!("base64.b64encode({value}).decode(\"ascii\")"),
+            _ => value.to_string(),
+        }
+    }
+
+    /// Python expression that converts the output of `to_dict_expr()` back into `type_`.
+    fn from_dict_expr(ci: &ComponentInterface, type_: &Type, value: &str, depth: usize) -> String {
+        match type_ {
+            Type::Record { .. }
+            | Type::External {
+                kind: ExternalKind::DataClass,
+                ..
+            } => format!(
+                "{}.from_dict({value})",
+                PythonCodeOracle.find(type_).type_label()
+            ),
+            Type::Enum { name, .. } if !ci.is_name_used_as_error(name) => {
+                format!(
+                    "{}.from_dict({value})",
+                    PythonCodeOracle.find(type_).type_label()
+                )
+            }
+            Type::Optional { inner_type } => format!(
+                "(None if {value} is None else {})",
+                from_dict_expr(ci, inner_type, value, depth)
+            ),
+            Type::Sequence { inner_type } => {
+                let item = format!("_v{depth}");
+                format!(
+                    "[{} for {item} in {value}]",
+                    from_dict_expr(ci, inner_type, &item, depth + 1)
+                )
+            }
+            Type::Map {
+                key_type,
+                value_type,
+            } => {
+                let (k, v) = (format!("_k{depth}"), format!("_v{depth}"));
+                format!(
+                    "{{{}: {} for ({k}, {v}) in {value}.items()}}",
+                    from_dict_expr(ci, key_type, &k, depth + 1),
+                    from_dict_expr(ci, value_type, &v, depth + 1)
+                )
+            }
+            Type::Timestamp => format!("datetime.datetime.fromisoformat({value})"),
+            Type::Duration => format!("datetime.timedelta(seconds={value})"),
+            Type::Bytes => format!("base64.b64decode({value})"),
+            _ => value.to_string(),
+        }
+    }
+
     pub(super) fn type_name(as_ct: &impl AsCodeType) -> Result<String, askama::Error> {
         Ok(as_ct.as_codetype().type_label())
     }
@@ -525,6 +640,25 @@ pub mod filters {
         Ok(Type::UInt64.as_codetype().literal(&literal))
     }
 
+    /// Expression converting `value` to plain Python data, for the `dict_conversion` option.
+    pub fn to_dict(
+        as_type: &impl AsType,
+        value: &str,
+        ci: &ComponentInterface,
+    ) -> Result<String, askama::Error> {
+        Ok(to_dict_expr(ci, &as_type.as_type(), value, 0))
+    }
+
+    /// Expression converting plain Python data in `value` back to the type, for the
+    /// `dict_conversion` option.
+    pub fn from_dict(
+        as_type: &impl AsType,
+        value: &str,
+        ci: &ComponentInterface,
+    ) -> Result<String, askama::Error> {
+        Ok(from_dict_expr(ci, &as_type.as_type(), value, 0))
+    }
+
     pub fn ffi_type_name(type_: &FfiType) -> Result<String, askama::Error> {
         Ok(PythonCodeOracle.ffi_type_label(type_))
     }
@@ -12,6 +12,15 @@ class {{ type_name }}(enum.Enum):
     {{ variant.name()|enum_variant_py }} = {{ e|variant_discr_literal(loop.index0) }}
     {%- call py::docstring(variant, 4) %}
     {% endfor %}
+    {%- if python_config.dict_conversion() %}
+
+    def to_dict(self):
+        return self.name
+
+    @staticmethod
+    def from_dict(data):
+        return {{ type_name }}[data]
+    {%- endif %}
 {% else %}
 
 class {{ type_name }}:
@@ -44,6 +53,15 @@ class {{ type_name }}:
             if not other.is_{{ variant.name()|var_name }}():
                 return False
             return self._values == other._values
+        {%- if python_config.dict_conversion() %}
+
+        def to_dict(self):
+            return {"{{ variant.name() }}": [
+                {%- for field in variant.fields() %}
+                {{ field|to_dict("self._values[{}]"|format(loop.index0), ci) }},
+                {%- endfor %}
+            ]}
+        {%- endif %}
 
     {%-  else -%}
         {%- for field in variant.fields() %}
@@ -72,6 +90,15 @@ class {{ type_name }}:
                 return False
             {%- endfor %}
             return True
+        {%- if python_config.dict_conversion() %}
+
+        def to_dict(self):
+            return {"{{ variant.name() }}": {
+                {%- for field in variant.fields() %}
+                "{{ field.name() }}": {{ field|to_dict("self.{}"|format(field.name()|var_name), ci) }},
+                {%- endfor %}
+            }}
+        {%- endif %}
     {%  endif %}
     {% endfor %}
 
@@ -81,6 +108,25 @@ class {{ type_name }}:
     def is_{{ variant.name()|var_name }}(self) -> bool:
         return isinstance(self, {{ type_name }}.{{ variant.name()|enum_variant_py }})
     {% endfor %}
+    {%- if python_config.dict_conversion() %}
+
+    @staticmethod
+    def from_dict(data):
+        [(name, fields)] = data.items()
+        {%- for variant in e.variants() %}
+        if name == "{{ variant.name() }}":
+            return {{ type_name }}.{{ variant.name()|enum_variant_py }}(
+                {%- for field in variant.fields() %}
+                {%- if variant.has_nameless_fields() %}
+                {{ field|from_dict("fields[{}]"|format(loop.index0), ci) }},
+                {%- else %}
+                {{ field.name()|var_name }}={{ field|from_dict("fields[{:?}]"|format(field.name()), ci) }},
+                {%- endif %}
+                {%- endfor %}
+            )
+        {%- endfor %}
+        raise ValueError(f"invalid {{ type_name }} variant: {name}")
+    {%- endif %}
 
 # Now, a little trick - we make each nested variant class be a subclass of the main
 # enum class, so that method calls and instance checks etc will work intuitively.
@@ -0,0 +1,50 @@
+# Python callables passed to Rust.
+#
+# Callables are stored in a handle map and Rust calls them through a single vtable.  To make that
+# work for every signature, the arguments and return value are serialized into `_UniffiRustBuffer`s.
+# Each `_UniffiConverterFunction*` class wraps a callable in a function that handles the
+# serialization before inserting it into the map.
+class _UniffiForeignFunction:
+    _handle_map = _UniffiHandleMap()
+
+    @{{ "ForeignFunctionCall"|ffi_callback_name }}
+    def call(handle, args, uniffi_out_return, uniffi_call_status_ptr):
+        def make_call():
+            function = _UniffiForeignFunction._handle_map.get(handle)
+            with args.consume_with_stream() as stream:
+                return function(stream)
+
+        def write_return_value(v):
+            uniffi_out_return[0] = v
+
+        _uniffi_trait_interface_call(
+                uniffi_call_status_ptr.contents,
+                make_call,
+                write_return_value,
+        )
+
+    @{{ "CallbackInterfaceFree"|ffi_callback_name }}
+    def free(handle):
+        _UniffiForeignFunction._handle_map.remove(handle)
+
+    vtable = {{ "VTableForeignFunction"|ffi_struct_name }}(call, free)
+    # Send Rust a pointer to the VTable.  Note: this means we need to keep the struct alive forever,
+    # or else bad things will happen when Rust tries to access it.
+    _UniffiLib.{{ ci.ffi_foreign_function_init_vtable().name() }}(ctypes.byref(vtable))
+
+class _UniffiRustFunction:
+    """
+    A Rust closure passed to Python.
+
+    The closure is freed once this object is garbage collected.
+    """
+
+    def __init__(self, handle):
+        self._handle = handle
+
+    def __del__(self):
+        _rust_call(_UniffiLib.{{ ci.ffi_rust_function_free().name() }}, self._handle)
+
+    def call(self, args):
+        """Call the closure with serialized arguments, returning the serialized result"""
+        return _rust_call(_UniffiLib.{{ ci.ffi_rust_function_call().name() }}, self._handle, args)
@@ -0,0 +1,56 @@
+{%- if self.include_once_check("FunctionRuntime.py") %}{% include "FunctionRuntime.py" %}{% endif %}
+
+class {{ ffi_converter_name }}:
+    @classmethod
+    def lift(cls, handle):
+        function = _UniffiRustFunction(handle)
+
+        def uniffi_closure({% for arg in args %}arg{{ loop.index0 }}{% if !loop.last %}, {% endif %}{% endfor %}):
+            with _UniffiRustBuffer.alloc_with_builder() as builder:
+                {%- for arg in args %}
+                {{ arg|check_lower_fn }}(arg{{ loop.index0 }})
+                {{ arg|write_fn }}(arg{{ loop.index0 }}, builder)
+                {%- endfor %}
+                uniffi_args = builder.finalize()
+            with function.call(uniffi_args).consume_with_stream() as stream:
+                {%- match ret %}
+                {%- when Some(ret) %}
+                return {{ ret|read_fn }}(stream)
+                {%- when None %}
+                pass
+                {%- endmatch %}
+
+        return uniffi_closure
+
+    @classmethod
+    def check_lower(cls, value):
+        if not callable(value):
+            raise TypeError("Expected a callable, got {}".format(type(value).__name__))
+
+    @classmethod
+    def lower(cls, value):
+        def uniffi_call(stream):
+            {%- for arg in args %}
+            arg{{ loop.index0 }} = {{ arg|read_fn }}(stream)
+            {%- endfor %}
+            {%- match ret %}
+            {%- when Some(ret) %}
+            return_value = value({% for arg in args %}arg{{ loop.index0 }}{% if !loop.last %}, {% endif %}{% endfor %})
+            {{ ret|check_lower_fn }}(return_value)
+            with _UniffiRustBuffer.alloc_with_builder() as builder:
+                {{ ret|write_fn }}(return_value, builder)
+                return builder.finalize()
+            {%- when None %}
+            value({% for arg in args %}arg{{ loop.index0 }}{% if !loop.last %}, {% endif %}{% endfor %})
+            return _UniffiRustBuffer.default()
+            {%- endmatch %}
+
+        return _UniffiForeignFunction._handle_map.insert(uniffi_call)
+
+    @classmethod
+    def read(cls, buf):
+        return cls.lift(buf.read_u64())
+
+    @classmethod
+    def write(cls, value, buf):
+        buf.write_u64(cls.lower(value))
@@ -4,6 +4,39 @@
 class InternalError(Exception):
     pass
 
+class RustPanic(InternalError):
+    """
+    A panic in the Rust code
+
+    `file`, `line` and `thread_name` are None when they're unknown.  `backtrace` is only captured
+    if the `RUST_BACKTRACE` environment variable is set.
+    """
+
+    # Called with each Rust panic before it's raised, for example to report it to a crash reporter
+    observer: typing.Optional[typing.Callable[["RustPanic"], None]] = None
+
+    def __init__(self, message, file, line, thread_name, backtrace):
+        super().__init__(message)
+        self.message = message
+        self.file = file
+        self.line = line
+        self.thread_name = thread_name
+        self.backtrace = backtrace
+
+    @staticmethod
+    def _lift(error_buf):
+        # When the rust code sees a panic, it tries to construct a _UniffiRustBuffer with the
+        # details.  But if that code panics, then it just sends back an empty buffer.
+        if error_buf.len == 0:
+            return RustPanic("Unknown rust panic", None, None, None, None)
+        with error_buf.consume_with_stream() as buf:
+            message = {{ Type::String.borrow()|read_fn }}(buf)
+            file = {{ Type::String.borrow()|read_fn }}(buf)
+            line = buf.read_u32()
+            thread_name = {{ Type::String.borrow()|read_fn }}(buf)
+            backtrace = {{ Type::String.borrow()|read_fn }}(buf)
+        return RustPanic(message, file or None, line or None, thread_name or None, backtrace or None)
+
 class _UniffiRustCallStatus(ctypes.Structure):
     """
     Error runtime.
@@ -17,6 +50,7 @@ class _UniffiRustCallStatus(ctypes.Structure):
     CALL_SUCCESS = 0
     CALL_ERROR = 1
     CALL_UNEXPECTED_ERROR = 2
+    CALL_PANIC = 4
 
     @staticmethod
     def default():
@@ -29,6 +63,8 @@ class _UniffiRustCallStatus(ctypes.Structure):
             return "_UniffiRustCallStatus(CALL_ERROR)"
         elif self.code == _UniffiRustCallStatus.CALL_UNEXPECTED_ERROR:
             return "_UniffiRustCallStatus(CALL_UNEXPECTED_ERROR)"
+        elif self.code == _UniffiRustCallStatus.CALL_PANIC:
+            return "_UniffiRustCallStatus(CALL_PANIC)"
         else:
             return "_UniffiRustCallStatus(<invalid code>)"
 
@@ -57,14 +93,16 @@ def _uniffi_check_call_status(error_ffi_converter, call_status):
             raise InternalError("_rust_call_with_error: CALL_ERROR, but error_ffi_converter is None")
         else:
             raise error_ffi_converter.lift(call_status.error_buf)
+    elif call_status.code == _UniffiRustCallStatus.CALL_PANIC:
+        panic = RustPanic._lift(call_status.error_buf)
+        if RustPanic.observer is not None:
+            RustPanic.observer(panic)
+        raise panic
     elif call_status.code == _UniffiRustCallStatus.CALL_UNEXPECTED_ERROR:
-        # When the rust code sees a panic, it tries to construct a _UniffiRustBuffer
-        # with the message.  But if that code panics, then it just sends back
-        # an empty buffer.
         if call_status.error_buf.len > 0:
             msg = _UniffiConverterString.lift(call_status.error_buf)
         else:
-            msg = "Unknown rust panic"
+            msg = "Unknown rust error"
         raise InternalError(msg)
     else:
         raise InternalError("Invalid _UniffiRustCallStatus code: {}".format(
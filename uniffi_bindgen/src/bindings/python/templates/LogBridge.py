@@ -0,0 +1,53 @@
+# Forwarding Rust log records to the `logging` module
+
+# Python level used for Rust `trace` records
+_UNIFFI_LOG_LEVEL_TRACE = logging.DEBUG - 5
+
+def _uniffi_log_level_filter(level):
+    # Convert a Python logging level to the maximum Rust level to forward
+    if level > logging.ERROR:
+        return 0
+    elif level > logging.WARNING:
+        return 1
+    elif level > logging.INFO:
+        return 2
+    elif level > logging.DEBUG:
+        return 3
+    elif level > _UNIFFI_LOG_LEVEL_TRACE:
+        return 4
+    else:
+        return 5
+
+# Called by Rust for each log record.  This needs to stay referenced for as long as Rust may call
+# it.
+@UNIFFI_LOG_BRIDGE_CALLBACK
+def _uniffi_log_bridge_callback(level, target, message):
+    target = {{ Type::String.borrow()|lift_fn }}(target)
+    message = {{ Type::String.borrow()|lift_fn }}(message)
+    python_level = {
+        1: logging.ERROR,
+        2: logging.WARNING,
+        3: logging.INFO,
+        4: logging.DEBUG,
+    }.get(level, _UNIFFI_LOG_LEVEL_TRACE)
+    logging.getLogger(target.replace("::", ".")).log(python_level, message)
+
+def install_rust_log_bridge(level: int = logging.INFO):
+    """
+    Forward the records from Rust's `log` crate, and `tracing` events if its `log` feature is
+    enabled, to the `logging` module
+
+    Records are logged to the logger named after the Rust log target, with `::` replaced by `.`.
+    Rust `trace` records use the level `logging.DEBUG - 5`.
+
+    This has no effect if the application already installed a different Rust logger.
+    """
+    _UniffiLib.{{ ci.ffi_log_bridge_init().name() }}(_uniffi_log_bridge_callback, _uniffi_log_level_filter(level))
+
+def set_rust_log_level(level: int):
+    """
+    Change the maximum level of the forwarded records
+
+    Like the level of a Python logger, records less severe than `level` aren't forwarded.
+    """
+    _UniffiLib.{{ ci.ffi_log_bridge_set_level().name() }}(_uniffi_log_level_filter(level))
@@ -42,15 +42,42 @@ def _uniffi_check_contract_api_version(lib):
     # Get the scaffolding contract version by calling the into the dylib
     scaffolding_contract_version = lib.{{ ci.ffi_uniffi_contract_version().name() }}()
     if bindings_contract_version != scaffolding_contract_version:
-        raise InternalError("UniFFI contract version mismatch: try cleaning and rebuilding your project")
+        raise InternalError(
+            "UniFFI contract version mismatch: try cleaning and rebuilding your project "
+            f"(the bindings expect version {bindings_contract_version}, the library has version {scaffolding_contract_version})"
+        )
+
+{%- if config.lenient_checksums() %}
+
+# Replace a scaffolding function whose checksum doesn't match with one that raises, so that the
+# rest of the library can still be used.
+def _uniffi_disable_function(lib, name, message):
+    def disabled(*args):
+        raise InternalError(message)
+    setattr(lib, name, disabled)
+{%- endif %}
 
 def _uniffi_check_api_checksums(lib):
-    {%- for (name, expected_checksum) in ci.iter_checksums() %}
-    if lib.{{ name }}() != {{ expected_checksum }}:
-        raise InternalError("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
-    {%- else %}
-    pass
+    mismatches = []
+    {%- for check in ci.iter_checksum_checks() %}
+    checksum = lib.{{ check.checksum_fn_name }}()
+    if checksum != {{ check.checksum }}:
+        detail = f"the bindings expect checksum {{ check.checksum }}, the library has checksum {checksum}"
+        {%- if let Some(ffi_func_name) = check.ffi_func_name %}
+        {%- if config.lenient_checksums() %}
+        _uniffi_disable_function(lib, "{{ ffi_func_name }}", f"{{ check.item }} is disabled because of a UniFFI API checksum mismatch: {detail}")
+        {%- else %}
+        mismatches.append(f"{{ check.item }}: {detail}")
+        {%- endif %}
+        {%- else %}
+        mismatches.append(f"{{ check.item }}: {detail}")
+        {%- endif %}
     {%- endfor %}
+    if mismatches:
+        raise InternalError(
+            "UniFFI API checksum mismatch: try cleaning and rebuilding your project\n"
+            + "\n".join(f"    {mismatch}" for mismatch in mismatches)
+        )
 
 # A ctypes library to expose the extern-C FFI definitions.
 # This is an implementation detail which will be called internally by the public API.
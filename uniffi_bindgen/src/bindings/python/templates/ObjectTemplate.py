@@ -10,7 +10,7 @@ class {{ impl_name }}(Exception):
 {%- else %}
 class {{ impl_name }}:
 {%- endif %}
-    {%- call py::docstring(obj, 4) %}
+    {%- call py::docstring_value(obj.class_docstring(), 4) %}
     _pointer: ctypes.c_void_p
 
 {%- match obj.primary_constructor() %}
@@ -19,6 +19,10 @@ class {{ impl_name }}:
         {%- call py::docstring(cons, 8) %}
         {%- call py::setup_args_extra_indent(cons) %}
         self._pointer = {% call py::to_ffi_call(cons) %}
+        {%- if obj.has_stable_identity() %}
+        with self._uniffi_identity_lock:
+            self._uniffi_identity_map[self._pointer] = self
+        {%- endif %}
 {%-     when None %}
     {# no __init__ means simple construction without a pointer works, which can confuse #}
     def __init__(self, *args, **kwargs):
@@ -29,10 +33,74 @@ class {{ impl_name }}:
         # In case of partial initialization of instances.
         pointer = getattr(self, "_pointer", None)
         if pointer is not None:
+            {%- if obj.has_stable_identity() %}
+            with self._uniffi_identity_lock:
+                # Make sure a new object at the same address doesn't get lifted to `self`.
+                if self._uniffi_identity_map.get(pointer) is self:
+                    del self._uniffi_identity_map[pointer]
+            {%- endif %}
             _rust_call(_UniffiLib.{{ obj.ffi_object_free().name() }}, pointer)
 
+{%- if obj.has_consuming_method() %}
+    # Guards against using the pointer while a method that takes `self` by value passes it to Rust.
+    _uniffi_consume_lock = threading.Lock()
+
+    def _uniffi_clone_pointer(self):
+        with self._uniffi_consume_lock:
+            if self._pointer is None:
+                raise RuntimeError(f"{type(self).__name__} object has already been consumed")
+            return _rust_call(_UniffiLib.{{ obj.ffi_object_clone().name() }}, self._pointer)
+
+    def _uniffi_take_pointer(self):
+        # Pass ownership of our pointer to Rust.  The object can't be used afterwards, unless Rust
+        # fails to take it and it's given back with `_uniffi_give_back_pointer()`.
+        with self._uniffi_consume_lock:
+            if self._pointer is None:
+                raise RuntimeError(f"{type(self).__name__} object has already been consumed")
+            pointer = self._pointer
+            self._pointer = None
+            {%- if obj.has_stable_identity() %}
+            with self._uniffi_identity_lock:
+                if self._uniffi_identity_map.get(pointer) is self:
+                    del self._uniffi_identity_map[pointer]
+            {%- endif %}
+            return pointer
+
+    def _uniffi_give_back_pointer(self, pointer):
+        with self._uniffi_consume_lock:
+            self._pointer = pointer
+            {%- if obj.has_stable_identity() %}
+            with self._uniffi_identity_lock:
+                self._uniffi_identity_map.setdefault(pointer, self)
+            {%- endif %}
+{%- else %}
+
     def _uniffi_clone_pointer(self):
         return _rust_call(_UniffiLib.{{ obj.ffi_object_clone().name() }}, self._pointer)
+{%- endif %}
+
+{%- if obj.has_stable_identity() %}
+
+    # Maps Rust pointers to the live instance wrapping them, so lifting the same Rust object
+    # twice returns the same Python object.  While we hold a reference to the object, Rust passes
+    # the same pointer for it, which is its slab handle with the `debug-handles` feature.
+    _uniffi_identity_map = weakref.WeakValueDictionary()
+    _uniffi_identity_lock = threading.RLock()
+
+    # Used by alternative constructors or any methods which return this type.
+    @classmethod
+    def _make_instance_(cls, pointer):
+        with cls._uniffi_identity_lock:
+            inst = cls._uniffi_identity_map.get(pointer)
+            if inst is not None:
+                # We already hold a reference to this Rust object, release the new one.
+                _rust_call(_UniffiLib.{{ obj.ffi_object_free().name() }}, pointer)
+                return inst
+            inst = cls.__new__(cls)
+            inst._pointer = pointer
+            cls._uniffi_identity_map[pointer] = inst
+            return inst
+{%- else %}
 
     # Used by alternative constructors or any methods which return this type.
     @classmethod
@@ -42,6 +110,7 @@ class {{ impl_name }}:
         inst = cls.__new__(cls)
         inst._pointer = pointer
         return inst
+{%- endif %}
 
 {%- for cons in obj.alternate_constructors() %}
 
@@ -36,6 +36,23 @@ class {{ type_name }}:
             return False
         {%- endfor %}
         return True
+    {%- if python_config.dict_conversion() %}
+
+    def to_dict(self):
+        return {
+            {%- for field in rec.fields() %}
+            "{{ field.name() }}": {{ field|to_dict("self.{}"|format(field.name()|var_name), ci) }},
+            {%- endfor %}
+        }
+
+    @staticmethod
+    def from_dict(data):
+        return {{ type_name }}(
+            {%- for field in rec.fields() %}
+            {{ field.name()|var_name }}={{ field|from_dict("data[{:?}]"|format(field.name()), ci) }},
+            {%- endfor %}
+        )
+    {%- endif %}
 
 class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
     @staticmethod
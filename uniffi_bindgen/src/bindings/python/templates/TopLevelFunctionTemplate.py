@@ -24,6 +24,25 @@ async def {{ func.name()|fn_name }}({%- call py::arg_list_decl(func) -%}) -> Non
         {% call py::error_ffi_converter(func) %}
     )
 
+{%- else if func.is_blocking() %}
+{%- match func.return_type() -%}
+{%- when Some with (return_type) %}
+
+async def {{ func.name()|fn_name }}({%- call py::arg_list_decl(func) -%}) -> "{{ return_type|type_name }}":
+    {%- call py::docstring(func, 4) %}
+    {%- call py::setup_args(func) %}
+    def _uniffi_blocking_call():
+        return {{ return_type|lift_fn }}({% call py::to_ffi_call(func) %})
+{% when None %}
+
+async def {{ func.name()|fn_name }}({%- call py::arg_list_decl(func) -%}) -> None:
+    {%- call py::docstring(func, 4) %}
+    {%- call py::setup_args(func) %}
+    def _uniffi_blocking_call():
+        {% call py::to_ffi_call(func) %}
+{% endmatch %}
+    return await asyncio.get_running_loop().run_in_executor(None, _uniffi_blocking_call)
+
 {%- else %}
 {%- match func.return_type() -%}
 {%- when Some with (return_type) %}
@@ -85,6 +85,9 @@
 {%- when Type::Map { key_type, value_type } %}
 {%- include "MapTemplate.py" %}
 
+{%- when Type::Function { args, ret } %}
+{%- include "FunctionTemplate.py" %}
+
 {%- when Type::CallbackInterface { name, module_path } %}
 {%- include "CallbackInterfaceTemplate.py" %}
 
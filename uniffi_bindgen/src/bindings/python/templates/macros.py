@@ -143,6 +143,29 @@ _rust_call(
             {% call error_ffi_converter(meth) %}
         )
 
+{%- else if meth.is_blocking() -%}
+{%-     match meth.return_type() %}
+
+{%-         when Some with (return_type) %}
+
+    async def {{ py_method_name }}(self, {% call arg_list_decl(meth) %}) -> "{{ return_type|type_name }}":
+        {%- call docstring(meth, 8) %}
+        {%- call setup_args_extra_indent(meth) %}
+        def _uniffi_blocking_call():
+            return {{ return_type|lift_fn }}(
+                {% call to_ffi_call_with_prefix("self._uniffi_clone_pointer()", meth) %}
+            )
+
+{%-         when None %}
+
+    async def {{ py_method_name }}(self, {% call arg_list_decl(meth) %}) -> None:
+        {%- call docstring(meth, 8) %}
+        {%- call setup_args_extra_indent(meth) %}
+        def _uniffi_blocking_call():
+            {% call to_ffi_call_with_prefix("self._uniffi_clone_pointer()", meth) %}
+{%      endmatch %}
+        return await asyncio.get_running_loop().run_in_executor(None, _uniffi_blocking_call)
+
 {%- else -%}
 {%-     match meth.return_type() %}
 
@@ -151,21 +174,51 @@ _rust_call(
     def {{ py_method_name }}(self, {% call arg_list_decl(meth) %}) -> "{{ return_type|type_name }}":
         {%- call docstring(meth, 8) %}
         {%- call setup_args_extra_indent(meth) %}
+{%-             if meth.takes_self_by_value() %}
+        {%- call consuming_call(meth) %}
+        return {{ return_type|lift_fn }}(_uniffi_result)
+{%-             else %}
         return {{ return_type|lift_fn }}(
             {% call to_ffi_call_with_prefix("self._uniffi_clone_pointer()", meth) %}
         )
+{%-             endif %}
 
 {%-         when None %}
 
     def {{ py_method_name }}(self, {% call arg_list_decl(meth) %}) -> None:
         {%- call docstring(meth, 8) %}
         {%- call setup_args_extra_indent(meth) %}
+{%-             if meth.takes_self_by_value() %}
+        {%- call consuming_call(meth) %}
+{%-             else %}
         {% call to_ffi_call_with_prefix("self._uniffi_clone_pointer()", meth) %}
+{%-             endif %}
 {%      endmatch %}
 {%  endif %}
 
 {% endmacro %}
 
+{#
+ # Call a method that takes `self` by value, storing the result in `_uniffi_result`
+ #}
+{%- macro consuming_call(meth) %}
+        _uniffi_pointer = self._uniffi_take_pointer()
+        try:
+            _uniffi_result = {% call to_ffi_call_with_prefix("_uniffi_pointer", meth) %}
+        {%- match meth.throws_type() %}
+        {%- when Some(e) %}
+        except ({{ e|type_name }}, RustPanic):
+        {%- when None %}
+        except RustPanic:
+        {%- endmatch %}
+            # Rust took the object, the call failed afterwards
+            raise
+        except BaseException:
+            # Rust didn't take the object, for example because it has other references
+            self._uniffi_give_back_pointer(_uniffi_pointer)
+            raise
+{%- endmacro %}
+
 {%- macro error_ffi_converter(func) %}
     # Error FFI converter
 {%  match func.throws_type() %}
@@ -27,10 +27,19 @@ import threading
 import itertools
 import traceback
 import typing
-{%- if ci.has_async_fns() %}
+{%- if ci.has_stable_identity_objects() %}
+import weakref
+{%- endif %}
+{%- if config.log_bridge() %}
+import logging
+{%- endif %}
+{%- if ci.has_async_fns() || ci.has_blocking_fns() %}
 import asyncio
 {%- endif %}
 import platform
+{%- if config.dict_conversion() %}
+import base64
+{%- endif %}
 {%- for req in self.imports() %}
 {{ req.render() }}
 {%- endfor %}
@@ -54,12 +63,18 @@ _DEFAULT = object()
 {%- include "Async.py" %}
 {%- endif %}
 
+{%- if config.log_bridge() %}
+
+{% include "LogBridge.py" %}
+{%- endif %}
+
 {%- for func in ci.function_definitions() %}
 {%- include "TopLevelFunctionTemplate.py" %}
 {%- endfor %}
 
 __all__ = [
     "InternalError",
+    "RustPanic",
     {%- for e in ci.enum_definitions() %}
     "{{ e|type_name }}",
     {%- endfor %}
@@ -78,6 +93,10 @@ __all__ = [
     {%- if ci.has_async_fns() %}
     "uniffi_set_event_loop",
     {%- endif %}
+    {%- if config.log_bridge() %}
+    "install_rust_log_bridge",
+    "set_rust_log_level",
+    {%- endif %}
 ]
 
 {% import "macros.py" as py %}
@@ -73,6 +73,7 @@ pub fn canonical_name(t: &Type) -> String {
             canonical_name(value_type).to_upper_camel_case()
         ),
         // A type that exists externally.
+        Type::Function { .. } => panic!("Function types are not supported by the Ruby bindings"),
         Type::External { name, .. } | Type::Custom { name, .. } => format!("Type{name}"),
     }
 }
@@ -261,6 +262,9 @@ mod filters {
                     )
                 }
             }
+            Type::Function { .. } => {
+                panic!("Function types are not supported by the Ruby bindings")
+            }
             Type::External { .. } => panic!("No support for external types, yet"),
             Type::Custom { .. } => panic!("No support for custom types, yet"),
         })
@@ -314,6 +318,9 @@ mod filters {
                 class_name_rb(&canonical_name(type_))?,
                 nm
             ),
+            Type::Function { .. } => {
+                panic!("Function types are not supported by the Ruby bindings")
+            }
             Type::External { .. } => panic!("No support for lowering external types, yet"),
             Type::Custom { .. } => panic!("No support for lowering custom types, yet"),
         })
@@ -354,6 +361,9 @@ mod filters {
                 nm,
                 class_name_rb(&canonical_name(type_))?
             ),
+            Type::Function { .. } => {
+                panic!("Function types are not supported by the Ruby bindings")
+            }
             Type::External { .. } => panic!("No support for lifting external types, yet"),
             Type::Custom { .. } => panic!("No support for lifting custom types, yet"),
         })
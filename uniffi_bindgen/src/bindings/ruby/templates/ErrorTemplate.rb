@@ -18,7 +18,8 @@ end
 # These match the values from the uniffi::rustcalls module
 CALL_SUCCESS = 0
 CALL_ERROR = 1
-CALL_PANIC = 2
+CALL_UNEXPECTED_ERROR = 2
+CALL_PANIC = 4
 {%- for e in ci.enum_definitions() %}
 {% if ci.is_name_used_as_error(e.name()) %}
 {% if e.is_flat() %}
@@ -62,7 +63,7 @@ ERROR_MODULE_TO_READER_METHOD = {
 {%- endfor %}
 }
 
-private_constant :ERROR_MODULE_TO_READER_METHOD, :CALL_SUCCESS, :CALL_ERROR, :CALL_PANIC,
+private_constant :ERROR_MODULE_TO_READER_METHOD, :CALL_SUCCESS, :CALL_ERROR, :CALL_UNEXPECTED_ERROR, :CALL_PANIC,
                  :RustCallStatus
 
 def self.consume_buffer_into_error(error_module, rust_buffer)
@@ -75,6 +76,10 @@ end
 class InternalError < StandardError
 end
 
+# A panic in the Rust code
+class RustPanic < InternalError
+end
+
 def self.rust_call(fn_name, *args)
   # Call a rust function
   rust_call_with_error(nil, fn_name, *args)
@@ -104,14 +109,22 @@ def self.rust_call_with_error(error_module, fn_name, *args)
     else
       raise consume_buffer_into_error(error_module, status.error_buf)
     end
+  when CALL_UNEXPECTED_ERROR
+    if status.error_buf.len > 0
+      raise InternalError, status.error_buf.consumeIntoString()
+    else
+      raise InternalError, "Unexpected Rust error"
+    end
   when CALL_PANIC
     # When the rust code sees a panic, it tries to construct a RustBuffer
-    # with the message.  But if that code panics, then it just sends back
-    # an empty buffer.
+    # with the details, starting with the message.  But if that code panics,
+    # then it just sends back an empty buffer.
     if status.error_buf.len > 0
-      raise InternalError, status.error_buf.consumeIntoString()
+      status.error_buf.consumeWithStream do |stream|
+        raise RustPanic, stream.readString
+      end
     else
-      raise InternalError, "Rust panic"
+      raise RustPanic, "Rust panic"
     end
   else
     raise InternalError, "Unknown call status: #{status.code}"
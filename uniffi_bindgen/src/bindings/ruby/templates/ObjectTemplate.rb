@@ -31,12 +31,54 @@ class {{ obj.name()|class_name_rb }}
     end
   end
 
+  {%- if obj.has_consuming_method() %}
+  # Guards against using the pointer while a method that takes `self` by value passes it to Rust.
+  UNIFFI_CONSUME_LOCK = Mutex.new
+
+  def uniffi_clone_pointer()
+    UNIFFI_CONSUME_LOCK.synchronize do
+      if @pointer.nil?
+        raise RuntimeError.new "{{ obj.name()|class_name_rb }} object has already been consumed"
+      end
+      return {{ ci.namespace()|class_name_rb }}.rust_call(
+        :{{ obj.ffi_object_clone().name() }},
+        @pointer
+      )
+    end
+  end
+
+  # Pass ownership of our pointer to Rust.  The object can't be used afterwards, unless Rust gives
+  # the pointer back with `uniffi_give_back_pointer()`.
+  def uniffi_take_pointer()
+    UNIFFI_CONSUME_LOCK.synchronize do
+      if @pointer.nil?
+        raise RuntimeError.new "{{ obj.name()|class_name_rb }} object has already been consumed"
+      end
+      pointer = @pointer
+      @pointer = nil
+      ObjectSpace.undefine_finalizer(self)
+      return pointer
+    end
+  end
+
+  # Rust fails with an unexpected error, before taking ownership of the pointer, when it can't
+  # consume the object, for example because something else still holds a reference to it.  The
+  # object is still usable then.
+  def uniffi_give_back_pointer(pointer)
+    UNIFFI_CONSUME_LOCK.synchronize do
+      @pointer = pointer
+      ObjectSpace.define_finalizer(self, self.class.uniffi_define_finalizer_by_pointer(pointer, self.object_id))
+    end
+  end
+  {%- else %}
+
   def uniffi_clone_pointer()
     return {{ ci.namespace()|class_name_rb }}.rust_call(
       :{{ obj.ffi_object_clone().name() }},
       @pointer
     )
   end
+  {%- endif %}
 
   def self.uniffi_lower(inst)
     return inst.uniffi_clone_pointer()
@@ -69,14 +111,38 @@ class {{ obj.name()|class_name_rb }}
   {%- when Some with (return_type) -%}
   def {{ meth.name()|fn_name_rb }}({% call rb::arg_list_decl(meth) %})
     {%- call rb::setup_args_extra_indent(meth) %}
+    {%- if meth.takes_self_by_value() %}
+    pointer = uniffi_take_pointer()
+    begin
+      result = {% call rb::to_ffi_call_with_prefix("pointer", meth) %}
+    rescue RustPanic
+      raise
+    rescue InternalError
+      uniffi_give_back_pointer(pointer)
+      raise
+    end
+    {%- else %}
     result = {% call rb::to_ffi_call_with_prefix("uniffi_clone_pointer()", meth) %}
+    {%- endif %}
     return {{ "result"|lift_rb(return_type) }}
   end
 
   {%- when None -%}
   def {{ meth.name()|fn_name_rb }}({% call rb::arg_list_decl(meth) %})
       {%- call rb::setup_args_extra_indent(meth) %}
+      {%- if meth.takes_self_by_value() %}
+      pointer = uniffi_take_pointer()
+      begin
+        {% call rb::to_ffi_call_with_prefix("pointer", meth) %}
+      rescue RustPanic
+        raise
+      rescue InternalError
+        uniffi_give_back_pointer(pointer)
+        raise
+      end
+      {%- else %}
       {% call rb::to_ffi_call_with_prefix("uniffi_clone_pointer()", meth) %}
+      {%- endif %}
   end
   {% endmatch %}
   {% endfor %}
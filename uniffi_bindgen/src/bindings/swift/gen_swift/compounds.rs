@@ -18,7 +18,12 @@ impl OptionalCodeType {
 
 impl CodeType for OptionalCodeType {
     fn type_label(&self) -> String {
-        format!("{}?", super::SwiftCodeOracle.find(&self.inner).type_label())
+        let inner = super::SwiftCodeOracle.find(&self.inner).type_label();
+        // Function types need parentheses before they can be made optional
+        match &self.inner {
+            Type::Function { .. } => format!("({inner})?"),
+            _ => format!("{inner}?"),
+        }
     }
 
     fn canonical_name(&self) -> String {
@@ -23,6 +23,7 @@ mod compounds;
 mod custom;
 mod enum_;
 mod external;
+mod function;
 mod miscellany;
 mod object;
 mod primitives;
@@ -196,6 +197,9 @@ pub struct Config {
     generate_module_map: Option<bool>,
     omit_argument_labels: Option<bool>,
     generate_immutable_records: Option<bool>,
+    codable: Option<bool>,
+    lenient_checksums: Option<bool>,
+    log_bridge: Option<bool>,
     #[serde(default)]
     custom_types: HashMap<String, CustomTypeConfig>,
 }
@@ -266,6 +270,22 @@ impl Config {
     pub fn generate_immutable_records(&self) -> bool {
         self.generate_immutable_records.unwrap_or(false)
     }
+
+    /// Whether to make records and enums conform to `Codable`
+    pub fn codable(&self) -> bool {
+        self.codable.unwrap_or(false)
+    }
+
+    /// Whether to only disable the functions with mismatched API checksums, rather than failing
+    /// to load the library
+    pub fn lenient_checksums(&self) -> bool {
+        self.lenient_checksums.unwrap_or(false)
+    }
+
+    /// Whether to generate the API that forwards Rust log records to the platform logger
+    pub fn log_bridge(&self) -> bool {
+        self.log_bridge.unwrap_or(false)
+    }
 }
 
 impl BindingsConfig for Config {
@@ -424,10 +444,13 @@ impl<'a> SwiftWrapper<'a> {
     }
 
     pub fn initialization_fns(&self) -> Vec<String> {
+        // Several types can share an initialization function, only call it once.
+        let mut seen = HashSet::new();
         self.ci
             .iter_types()
             .map(|t| SwiftCodeOracle.find(t))
             .filter_map(|ct| ct.initialization_fn())
+            .filter(|init| seen.insert(init.clone()))
             .collect()
     }
 }
@@ -478,6 +501,9 @@ impl SwiftCodeOracle {
                 key_type,
                 value_type,
             } => Box::new(compounds::MapCodeType::new(*key_type, *value_type)),
+            Type::Function { args, ret } => {
+                Box::new(function::FunctionCodeType::new(args, ret.map(|t| *t)))
+            }
             Type::External { name, .. } => Box::new(external::ExternalCodeType::new(name)),
             Type::Custom { name, .. } => Box::new(custom::CustomCodeType::new(name)),
         }
@@ -611,6 +637,18 @@ pub mod filters {
         Ok(oracle().find(&as_type.as_type()).type_label())
     }
 
+    /// The type name for a function argument.
+    ///
+    /// Closure arguments need to be marked as `@escaping`, since we pass them to Rust.
+    pub fn arg_type_name(as_type: &impl AsType) -> Result<String, askama::Error> {
+        let type_ = as_type.as_type();
+        let type_label = oracle().find(&type_).type_label();
+        Ok(match type_ {
+            Type::Function { .. } => format!("@escaping {type_label}"),
+            _ => type_label,
+        })
+    }
+
     pub fn return_type_name(as_type: Option<&impl AsType>) -> Result<String, askama::Error> {
         Ok(match as_type {
             Some(as_type) => oracle().find(&as_type.as_type()).type_label(),
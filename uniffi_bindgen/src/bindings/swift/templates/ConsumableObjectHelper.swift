@@ -0,0 +1,15 @@
+{#
+// This type is public, so it's named after the namespace, like the RustPanic type.
+#}
+{%- let consumed_error_namespace = ci.namespace()|class_name %}
+{%- let consumed_error_name = format!("{}ObjectConsumed", consumed_error_namespace) %}
+/**
+ * Thrown when calling a method on an object after a method that takes `self` by value consumed it
+ */
+public struct {{ consumed_error_name }}: Error, LocalizedError {
+    public let typeName: String
+
+    public var errorDescription: String? {
+        return "\(typeName) object has already been consumed"
+    }
+}
@@ -77,3 +77,7 @@ public func {{ ffi_converter_name }}_lower(_ value: {{ type_name }}) -> RustBuff
 {% if !contains_object_references %}
 extension {{ type_name }}: Equatable, Hashable {}
 {% endif %}
+
+{% if config.codable() && !contains_object_references %}
+extension {{ type_name }}: Codable {}
+{% endif %}
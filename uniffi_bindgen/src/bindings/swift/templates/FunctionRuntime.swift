@@ -0,0 +1,63 @@
+// Swift closures passed to Rust.
+//
+// Closures are stored in a handle map and Rust calls them through a single vtable.  To make that
+// work for every closure signature, the arguments and return value are serialized into
+// `RustBuffer`s.  Each `FfiConverterFunction*` struct wraps a closure in a function that handles
+// the serialization before inserting it into the map.
+fileprivate struct UniffiForeignFunction {
+    fileprivate static var handleMap = UniffiHandleMap<(inout (data: Data, offset: Data.Index)) throws -> RustBuffer>()
+
+    // Create the VTable using a series of closures.
+    // Swift automatically converts these into C callback functions.
+    static var vtable: {{ "VTableForeignFunction"|ffi_struct_name }} = {{ "VTableForeignFunction"|ffi_struct_name }}(
+        call: { (
+            handle: UInt64,
+            args: RustBuffer,
+            uniffiOutReturn: UnsafeMutablePointer<RustBuffer>,
+            uniffiCallStatus: UnsafeMutablePointer<RustCallStatus>
+        ) in
+            uniffiTraitInterfaceCall(
+                callStatus: uniffiCallStatus,
+                makeCall: {
+                    defer { args.deallocate() }
+                    let function = try UniffiForeignFunction.handleMap.get(handle: handle)
+                    var reader = createReader(data: Data(rustBuffer: args))
+                    return try function(&reader)
+                },
+                writeReturn: { uniffiOutReturn.pointee = $0 }
+            )
+        },
+        free: { (handle: UInt64) -> () in
+            let result = try? UniffiForeignFunction.handleMap.remove(handle: handle)
+            if result == nil {
+                print("Uniffi foreign function: handle missing in free")
+            }
+        }
+    )
+}
+
+private func uniffiForeignFunctionInit() {
+    {{ ci.ffi_foreign_function_init_vtable().name() }}(&UniffiForeignFunction.vtable)
+}
+
+// A Rust closure passed to Swift.
+//
+// The closure is freed once the last reference to this object is dropped.
+fileprivate class UniffiRustFunction {
+    private let handle: UInt64
+
+    init(handle: UInt64) {
+        self.handle = handle
+    }
+
+    deinit {
+        try! rustCall { {{ ci.ffi_rust_function_free().name() }}(handle, $0) }
+    }
+
+    // Call the closure with serialized arguments, returning the serialized result.
+    //
+    // Swift closures can't throw, so a panic in the Rust closure is a fatal error.
+    func call(_ args: [UInt8]) -> RustBuffer {
+        try! rustCall { {{ ci.ffi_rust_function_call().name() }}(handle, RustBuffer(bytes: args), $0) }
+    }
+}
@@ -0,0 +1,53 @@
+{%- if self.include_once_check("FunctionRuntime.swift") %}{%- include "FunctionRuntime.swift" %}{%- endif %}
+
+fileprivate struct {{ ffi_converter_name }} {}
+
+extension {{ ffi_converter_name }} : FfiConverter {
+    typealias SwiftType = {{ type_name }}
+    typealias FfiType = UInt64
+
+    public static func lift(_ handle: UInt64) throws -> SwiftType {
+        let function = UniffiRustFunction(handle: handle)
+        return { {% for arg in args %}(arg{{ loop.index0 }}: {{ arg|type_name }}){% if !loop.last %}, {% endif %}{% endfor %}{% if args.is_empty() %}(){% endif %} in
+            var writer = createWriter()
+            {%- for arg in args %}
+            {{ arg|write_fn }}(arg{{ loop.index0 }}, into: &writer)
+            {%- endfor %}
+            let result = function.call(writer)
+            {%- match ret %}
+            {%- when Some(ret) %}
+            defer { result.deallocate() }
+            var reader = createReader(data: Data(rustBuffer: result))
+            return try! {{ ret|read_fn }}(from: &reader)
+            {%- when None %}
+            result.deallocate()
+            {%- endmatch %}
+        }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        let handle: UInt64 = try readInt(&buf)
+        return try lift(handle)
+    }
+
+    public static func lower(_ v: @escaping SwiftType) -> UInt64 {
+        return UniffiForeignFunction.handleMap.insert(obj: { (buf: inout (data: Data, offset: Data.Index)) throws -> RustBuffer in
+            {%- for arg in args %}
+            let arg{{ loop.index0 }} = try {{ arg|read_fn }}(from: &buf)
+            {%- endfor %}
+            {%- match ret %}
+            {%- when Some(ret) %}
+            var writer = createWriter()
+            {{ ret|write_fn }}(v({% for arg in args %}arg{{ loop.index0 }}{% if !loop.last %}, {% endif %}{% endfor %}), into: &writer)
+            return RustBuffer(bytes: writer)
+            {%- when None %}
+            v({% for arg in args %}arg{{ loop.index0 }}{% if !loop.last %}, {% endif %}{% endfor %})
+            return RustBuffer.empty()
+            {%- endmatch %}
+        })
+    }
+
+    public static func write(_ v: @escaping SwiftType, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(v))
+    }
+}
@@ -26,6 +26,67 @@ fileprivate enum UniffiInternalError: LocalizedError {
     }
 }
 
+{#-
+// This type is public, so it's named after the namespace.  Several components can be compiled
+// into the same Swift module.
+#}
+{%- let rust_panic_namespace = ci.namespace()|class_name %}
+{%- let rust_panic_name = format!("{}RustPanic", rust_panic_namespace) %}
+/**
+ * A panic in the Rust code
+ *
+ * `file`, `line` and `threadName` are nil when they're unknown.  `backtrace` is only captured if
+ * the `RUST_BACKTRACE` environment variable is set.
+ */
+public struct {{ rust_panic_name }}: Error, LocalizedError {
+    public let message: String
+    public let file: String?
+    public let line: Int?
+    public let threadName: String?
+    public let backtrace: String?
+
+    public var errorDescription: String? {
+        return message
+    }
+
+    /**
+     * Called with each Rust panic before it's thrown, for example to report it to a crash reporter
+     */
+    public static var observer: (({{ rust_panic_name }}) -> Void)? {
+        get { uniffiRustPanicObserverLock.withLock { uniffiRustPanicObserver } }
+        set { uniffiRustPanicObserverLock.withLock { uniffiRustPanicObserver = newValue } }
+    }
+
+    fileprivate static func lift(_ errorBuf: RustBuffer) throws -> {{ rust_panic_name }} {
+        defer {
+            errorBuf.deallocate()
+        }
+        // When the rust code sees a panic, it tries to construct a RustBuffer with the details.
+        // But if that code panics, then it just sends back an empty buffer.
+        if errorBuf.len == 0 {
+            return {{ rust_panic_name }}(message: "Rust panic", file: nil, line: nil, threadName: nil, backtrace: nil)
+        }
+        var reader = createReader(data: Data(rustBuffer: errorBuf))
+        let message = try {{ Type::String.borrow()|read_fn }}(from: &reader)
+        let file = try {{ Type::String.borrow()|read_fn }}(from: &reader)
+        let line: UInt32 = try readInt(&reader)
+        let threadName = try {{ Type::String.borrow()|read_fn }}(from: &reader)
+        let backtrace = try {{ Type::String.borrow()|read_fn }}(from: &reader)
+        return {{ rust_panic_name }}(
+            message: message,
+            file: file.isEmpty ? nil : file,
+            line: line == 0 ? nil : Int(line),
+            threadName: threadName.isEmpty ? nil : threadName,
+            backtrace: backtrace.isEmpty ? nil : backtrace
+        )
+    }
+}
+
+// The observer can be set from any thread, while panics are reported on the thread that made
+// the call.
+fileprivate var uniffiRustPanicObserver: (({{ rust_panic_name }}) -> Void)? = nil
+fileprivate let uniffiRustPanicObserverLock = NSLock()
+
 fileprivate extension NSLock {
     func withLock<T>(f: () throws -> T) rethrows -> T {
         self.lock()
@@ -38,6 +99,7 @@ fileprivate let CALL_SUCCESS: Int8 = 0
 fileprivate let CALL_ERROR: Int8 = 1
 fileprivate let CALL_UNEXPECTED_ERROR: Int8 = 2
 fileprivate let CALL_CANCELLED: Int8 = 3
+fileprivate let CALL_PANIC: Int8 = 4
 
 fileprivate extension RustCallStatus {
     init() {
@@ -89,10 +151,12 @@ private func uniffiCheckCallStatus(
                 throw UniffiInternalError.unexpectedRustCallError
             }
 
+        case CALL_PANIC:
+            let panic = try {{ rust_panic_name }}.lift(callStatus.errorBuf)
+            {{ rust_panic_name }}.observer?(panic)
+            throw panic
+
         case CALL_UNEXPECTED_ERROR:
-            // When the rust code sees a panic, it tries to construct a RustBuffer
-            // with the message.  But if that code panics, then it just sends back
-            // an empty buffer.
             if callStatus.errorBuf.len > 0 {
                 throw UniffiInternalError.rustPanic(try {{ Type::String.borrow()|lift_fn }}(callStatus.errorBuf))
             } else {
@@ -0,0 +1,103 @@
+#if canImport(os)
+import os
+#endif
+{#-
+// These types are public, so they're named after the namespace.  Several components can be
+// compiled into the same Swift module and each of them could generate a log bridge.
+#}
+{%- let namespace_class_name = ci.namespace()|class_name %}
+{%- let log_level_name = format!("{}RustLogLevel", namespace_class_name) %}
+{%- let log_bridge_name = format!("{}RustLogBridge", namespace_class_name) %}
+
+/**
+ * Maximum level of the Rust log records to forward, with the same values as Rust's `log::LevelFilter`
+ */
+public enum {{ log_level_name }}: Int8 {
+    case off = 0
+    case error = 1
+    case warn = 2
+    case info = 3
+    case debug = 4
+    case trace = 5
+}
+
+/**
+ * Forwards the records from Rust's `log` crate, and `tracing` events if its `log` feature is
+ * enabled, to `os_log`.
+ *
+ * Records are logged with the main bundle identifier as the subsystem and the Rust log target as
+ * the category.  On platforms without `os_log`, they're printed instead.
+ */
+public enum {{ log_bridge_name }} {
+    /**
+     * Start forwarding Rust log records up to `level`
+     *
+     * If `handler` is set, it's called with the level, target and message of each record instead
+     * of logging them to `os_log`.
+     *
+     * This has no effect if the application already installed a different Rust logger.
+     */
+    public static func install(
+        level: {{ log_level_name }} = .info,
+        handler: (({{ log_level_name }}, String, String) -> Void)? = nil
+    ) {
+        uniffiEnsureInitialized()
+        uniffiLogBridgeHandlerLock.lock()
+        uniffiLogBridgeHandler = handler
+        uniffiLogBridgeHandlerLock.unlock()
+        {{ ci.ffi_log_bridge_init().name() }}(uniffiLogBridgeCallback, level.rawValue)
+    }
+
+    /**
+     * Change the maximum level of the forwarded records
+     */
+    public static func setLevel(_ level: {{ log_level_name }}) {
+        uniffiEnsureInitialized()
+        {{ ci.ffi_log_bridge_set_level().name() }}(level.rawValue)
+    }
+}
+
+fileprivate var uniffiLogBridgeHandler: (({{ log_level_name }}, String, String) -> Void)? = nil
+fileprivate let uniffiLogBridgeHandlerLock = NSLock()
+
+#if canImport(os)
+// OSLog instances for each log target
+fileprivate var uniffiOsLogs: [String: OSLog] = [:]
+fileprivate let uniffiOsLogsLock = NSLock()
+
+fileprivate func uniffiOsLog(category: String) -> OSLog {
+    return uniffiOsLogsLock.withLock { () -> OSLog in
+        if let log = uniffiOsLogs[category] {
+            return log
+        }
+        let log = OSLog(subsystem: Bundle.main.bundleIdentifier ?? "{{ config.module_name() }}", category: category)
+        uniffiOsLogs[category] = log
+        return log
+    }
+}
+#endif
+
+// Called by Rust for each log record.
+fileprivate func uniffiLogBridgeCallback(level: Int8, rustTarget: RustBuffer, rustMessage: RustBuffer) {
+    let target = (try? {{ Type::String.borrow()|lift_fn }}(rustTarget)) ?? "<invalid target>"
+    let message = (try? {{ Type::String.borrow()|lift_fn }}(rustMessage)) ?? "<invalid message>"
+    uniffiLogBridgeHandlerLock.lock()
+    let handler = uniffiLogBridgeHandler
+    uniffiLogBridgeHandlerLock.unlock()
+    if let handler = handler {
+        handler({{ log_level_name }}(rawValue: level) ?? .trace, target, message)
+        return
+    }
+    #if canImport(os)
+    let type: OSLogType
+    switch level {
+    case 1: type = .error
+    case 2: type = .default
+    case 3: type = .info
+    default: type = .debug
+    }
+    os_log("%{public}@", log: uniffiOsLog(category: target), type: type, message)
+    #else
+    print("[\(target)] \(message)")
+    #endif
+}
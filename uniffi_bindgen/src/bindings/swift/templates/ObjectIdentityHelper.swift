@@ -0,0 +1,67 @@
+// Maps Rust pointers to the Swift objects wrapping them, for objects with a stable identity.
+//
+// While we hold a reference to a Rust object, Rust hands us the same pointer for it each time: the
+// address of the object, or its slab handle with the `debug-handles` feature, since the slab reuses
+// the slot of an object that already has one.  So when Rust hands us a pointer that we already have
+// a live object for, we can release that extra reference and return the existing object.  Objects
+// are held with a weak reference, so the map doesn't keep them alive.
+fileprivate class UniffiIdentityMap<T: AnyObject> {
+    private class WeakRef {
+        weak var value: T?
+
+        init(_ value: T) {
+            self.value = value
+        }
+    }
+
+    private var map: [UInt: WeakRef] = [:]
+    // Recursive, since `create` registers the new object with `insert()`
+    private let lock = NSRecursiveLock()
+
+    // Get the object wrapping `pointer`, or create one if there's none.
+    //
+    // `free` releases the reference that `pointer` represents if an existing object is returned.
+    func lift(
+        _ pointer: UnsafeMutableRawPointer,
+        free: (UnsafeMutableRawPointer) -> (),
+        create: (UnsafeMutableRawPointer) -> T
+    ) -> T {
+        lock.lock()
+        defer { lock.unlock() }
+        if let existing = map[UInt(bitPattern: pointer)]?.value {
+            free(pointer)
+            return existing
+        }
+        return create(pointer)
+    }
+
+    // Register a newly created object
+    func insert(_ pointer: UnsafeMutableRawPointer, _ obj: T) {
+        lock.lock()
+        defer { lock.unlock() }
+        map[UInt(bitPattern: pointer)] = WeakRef(obj)
+    }
+
+    // Register an object again after a consuming method failed to take its pointer, unless another
+    // object was created for the pointer meanwhile.
+    func restore(_ pointer: UnsafeMutableRawPointer, _ obj: T) {
+        lock.lock()
+        defer { lock.unlock() }
+        let key = UInt(bitPattern: pointer)
+        if map[key]?.value == nil {
+            map[key] = WeakRef(obj)
+        }
+    }
+
+    // Unregister an object, if it's still the one registered for `pointer`.
+    //
+    // Pass `nil` from `deinit`, where weak references to the object have already been cleared.
+    func remove(_ pointer: UnsafeMutableRawPointer, _ obj: T?) {
+        lock.lock()
+        defer { lock.unlock() }
+        let key = UInt(bitPattern: pointer)
+        if let ref = map[key], ref.value === obj {
+            map.removeValue(forKey: key)
+        }
+    }
+}
@@ -1,13 +1,22 @@
 {%- let obj = ci|get_object_definition(name) %}
+{%- if obj.has_stable_identity() && self.include_once_check("ObjectIdentityHelper.swift") %}
+{%- include "ObjectIdentityHelper.swift" %}
+{%- endif %}
 {%- let (protocol_name, impl_class_name) = obj|object_names %}
 {%- let methods = obj.methods() %}
 {%- let protocol_docstring = obj.docstring() %}
+{%- let consumable = obj.has_consuming_method() %}
+{%- if consumable && self.include_once_check("ConsumableObjectHelper.swift") %}
+{%- include "ConsumableObjectHelper.swift" %}
+{%- endif %}
+{%- let consumed_error_namespace = ci.namespace()|class_name %}
+{%- let consumed_error_name = format!("{}ObjectConsumed", consumed_error_namespace) %}
 
 {%- let is_error = ci.is_name_used_as_error(name) %}
 
 {% include "Protocol.swift" %}
 
-{%- call swift::docstring(obj, 0) %}
+{%- call swift::docstring_value(obj.class_docstring(), 0) %}
 open class {{ impl_class_name }}:
     {%- for tm in obj.uniffi_traits() %}
     {%-     match tm %}
@@ -38,6 +47,9 @@ open class {{ impl_class_name }}:
     // make it `required` without making it `public`.
     required public init(unsafeFromRawPointer pointer: UnsafeMutableRawPointer) {
         self.pointer = pointer
+        {%- if obj.has_stable_identity() %}
+        {{ ffi_converter_name }}.identityMap.insert(pointer, self)
+        {%- endif %}
     }
 
     /// This constructor can be used to instantiate a fake object.
@@ -49,9 +61,63 @@ open class {{ impl_class_name }}:
         self.pointer = nil
     }
 
+    {%- if obj.has_consuming_method() %}
+
+    // Set once a method that takes `self` by value has passed our pointer to Rust.
+    private var uniffiConsumed = false
+    private let uniffiConsumedLock = NSLock()
+
+    // Lowering can't throw, so passing a consumed object to Rust is a fatal error.  Methods call
+    // `uniffiCheckedClonePointer()` instead.
+    public func uniffiClonePointer() -> UnsafeMutableRawPointer {
+        do {
+            return try uniffiCheckedClonePointer()
+        } catch {
+            fatalError(error.localizedDescription)
+        }
+    }
+
+    fileprivate func uniffiCheckedClonePointer() throws -> UnsafeMutableRawPointer {
+        return try uniffiConsumedLock.withLock {
+            if uniffiConsumed {
+                throw {{ consumed_error_name }}(typeName: "\(type(of: self))")
+            }
+            return try! rustCall { {{ obj.ffi_object_clone().name() }}(self.pointer, $0) }
+        }
+    }
+
+    // Pass ownership of our pointer to Rust, for methods that take `self` by value.  The object
+    // can't be used afterwards, unless Rust gives the pointer back with `uniffiGiveBackPointer()`.
+    fileprivate func uniffiTakePointer() throws -> UnsafeMutableRawPointer {
+        return try uniffiConsumedLock.withLock {
+            if uniffiConsumed {
+                throw {{ consumed_error_name }}(typeName: "\(type(of: self))")
+            }
+            uniffiConsumed = true
+            {%- if obj.has_stable_identity() %}
+            {{ ffi_converter_name }}.identityMap.remove(self.pointer, self)
+            {%- endif %}
+            return self.pointer
+        }
+    }
+
+    // Rust fails with an unexpected error, before taking ownership of the pointer, when it can't
+    // consume the object, for example because something else still holds a reference to it.  The
+    // object is still usable then.
+    fileprivate func uniffiGiveBackPointer(_ pointer: UnsafeMutableRawPointer) {
+        uniffiConsumedLock.withLock {
+            uniffiConsumed = false
+            {%- if obj.has_stable_identity() %}
+            {{ ffi_converter_name }}.identityMap.restore(pointer, self)
+            {%- endif %}
+        }
+    }
+    {%- else %}
+
     public func uniffiClonePointer() -> UnsafeMutableRawPointer {
         return try! rustCall { {{ obj.ffi_object_clone().name() }}(self.pointer, $0) }
     }
+    {%- endif %}
 
     {%- match obj.primary_constructor() %}
     {%- when Some with (cons) %}
@@ -66,6 +132,14 @@ open class {{ impl_class_name }}:
         guard let pointer = pointer else {
             return
         }
+        {%- if obj.has_consuming_method() %}
+        if uniffiConsumed {
+            return
+        }
+        {%- endif %}
+        {%- if obj.has_stable_identity() %}
+        {{ ffi_converter_name }}.identityMap.remove(pointer, nil)
+        {%- endif %}
 
         try! rustCall { {{ obj.ffi_object_free().name() }}(pointer, $0) }
     }
@@ -82,11 +156,14 @@ open class {{ impl_class_name }}:
     {% for meth in obj.methods() -%}
     {%- if meth.is_async() %}
     {%- call swift::docstring(meth, 4) %}
-    open func {{ meth.name()|fn_name }}({%- call swift::arg_list_decl(meth) -%}) async {% call swift::throws(meth) %}{% match meth.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_name }}{% when None %}{% endmatch %} {
+    open func {{ meth.name()|fn_name }}({%- call swift::arg_list_decl(meth) -%}) async {% call swift::method_throws(meth, consumable) %}{% match meth.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_name }}{% when None %}{% endmatch %} {
+        {%- if consumable %}
+        let pointer = try self.uniffiCheckedClonePointer()
+        {%- endif %}
         return {% call swift::try(meth) %} await uniffiRustCallAsync(
             rustFutureFunc: {
-                {{ meth.ffi_func().name() }}(
-                    self.uniffiClonePointer()
+                {% call swift::ffi_func(meth) %}(
+                    {% if consumable %}pointer{% else %}self.uniffiClonePointer(){% endif %}
                     {%- for arg in meth.arguments() -%}
                     ,
                     {{ arg|lower_fn }}({{ arg.name()|var_name }})
@@ -111,22 +188,85 @@ open class {{ impl_class_name }}:
         )
     }
 
+    {% else if meth.is_blocking() -%}
+    {%- call swift::docstring(meth, 4) %}
+    open func {{ meth.name()|fn_name }}({%- call swift::arg_list_decl(meth) -%}) async {% call swift::method_throws(meth, consumable) %}{% match meth.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_name }}{% when None %}{% endmatch %} {
+        {%- if consumable %}
+        let pointer = try self.uniffiCheckedClonePointer()
+        {%- endif %}
+        return {% if meth.throws() %}try {% endif %}await Task.detached {
+            {%- match meth.return_type() %}
+            {%- when Some with (return_type) %}
+            return {% call swift::try(meth) %} {{ return_type|lift_fn }}(
+                {%- if consumable %}
+                {% call swift::to_ffi_call_with_prefix("pointer", meth) %}
+                {%- else %}
+                {% call swift::to_ffi_call_with_prefix("self.uniffiClonePointer()", meth) %}
+                {%- endif %}
+            )
+            {%- when None %}
+            {%- if consumable %}
+            {% call swift::to_ffi_call_with_prefix("pointer", meth) %}
+            {%- else %}
+            {% call swift::to_ffi_call_with_prefix("self.uniffiClonePointer()", meth) %}
+            {%- endif %}
+            {%- endmatch %}
+        }.value
+    }
+
+    {% else if meth.takes_self_by_value() -%}
+
+    {#-
+    // Unexpected errors mean that Rust couldn't take ownership of the pointer, so they're thrown
+    // rather than being fatal, and the object stays usable.  Declared errors and panics happen
+    // after Rust took ownership.
+    #}
+    {%- call swift::docstring(meth, 4) %}
+    open func {{ meth.name()|fn_name }}({% call swift::arg_list_decl(meth) %}) throws {% match meth.return_type() %}{% when Some with (return_type) %}-> {{ return_type|type_name }} {% when None %}{% endmatch %}{
+        let pointer = try self.uniffiTakePointer()
+        do {
+            {%- match meth.return_type() %}
+            {%- when Some with (return_type) %}
+            return try {{ return_type|lift_fn }}(
+                {% call swift::to_throwing_ffi_call_with_prefix("pointer", meth) %}
+            )
+            {%- when None %}
+            {% call swift::to_throwing_ffi_call_with_prefix("pointer", meth) %}
+            {%- endmatch %}
+        } catch UniffiInternalError.rustPanic(let message) {
+            self.uniffiGiveBackPointer(pointer)
+            throw UniffiInternalError.rustPanic(message)
+        }
+    }
+
     {% else -%}
 
     {%- match meth.return_type() -%}
 
     {%- when Some with (return_type) %}
     {%- call swift::docstring(meth, 4) %}
-    open func {{ meth.name()|fn_name }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} -> {{ return_type|type_name }} {
+    open func {{ meth.name()|fn_name }}({% call swift::arg_list_decl(meth) %}) {% call swift::method_throws(meth, consumable) %} -> {{ return_type|type_name }} {
+        {%- if consumable %}
+        let pointer = try self.uniffiCheckedClonePointer()
+        return {% call swift::try(meth) %} {{ return_type|lift_fn }}(
+            {% call swift::to_ffi_call_with_prefix("pointer", meth) %}
+        )
+        {%- else %}
         return {% call swift::try(meth) %} {{ return_type|lift_fn }}(
             {% call swift::to_ffi_call_with_prefix("self.uniffiClonePointer()", meth) %}
         )
+        {%- endif %}
     }
 
     {%- when None %}
     {%- call swift::docstring(meth, 4) %}
-    open func {{ meth.name()|fn_name }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} {
+    open func {{ meth.name()|fn_name }}({% call swift::arg_list_decl(meth) %}) {% call swift::method_throws(meth, consumable) %} {
+        {%- if consumable %}
+        let pointer = try self.uniffiCheckedClonePointer()
+        {% call swift::to_ffi_call_with_prefix("pointer", meth) %}
+        {%- else %}
         {% call swift::to_ffi_call_with_prefix("self.uniffiClonePointer()", meth) %}
+        {%- endif %}
     }
 
     {%- endmatch -%}
@@ -179,12 +319,23 @@ public struct {{ ffi_converter_name }}: FfiConverter {
     {%- if obj.has_callback_interface() %}
     fileprivate static var handleMap = UniffiHandleMap<{{ type_name }}>()
     {%- endif %}
+    {%- if obj.has_stable_identity() %}
+    fileprivate static let identityMap = UniffiIdentityMap<{{ impl_class_name }}>()
+    {%- endif %}
 
     typealias FfiType = UnsafeMutableRawPointer
     typealias SwiftType = {{ type_name }}
 
     public static func lift(_ pointer: UnsafeMutableRawPointer) throws -> {{ type_name }} {
+        {%- if obj.has_stable_identity() %}
+        return identityMap.lift(
+            pointer,
+            free: { ptr in try! rustCall { {{ obj.ffi_object_free().name() }}(ptr, $0) } },
+            create: { ptr in {{ impl_class_name }}(unsafeFromRawPointer: ptr) }
+        )
+        {%- else %}
         return {{ impl_class_name }}(unsafeFromRawPointer: pointer)
+        {%- endif %}
     }
 
     public static func lower(_ value: {{ type_name }}) -> UnsafeMutableRawPointer {
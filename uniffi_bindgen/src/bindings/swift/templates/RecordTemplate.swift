@@ -34,6 +34,10 @@ extension {{ type_name }}: Equatable, Hashable {
 }
 {% endif %}
 
+{% if config.codable() && !contains_object_references %}
+extension {{ type_name }}: Codable {}
+{% endif %}
+
 public struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
     public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> {{ type_name }} {
         return {%- if rec.has_fields() %}
@@ -4,7 +4,7 @@
 public func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) async {% call swift::throws(func) %}{% match func.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_name }}{% when None %}{% endmatch %} {
     return {% call swift::try(func) %} await uniffiRustCallAsync(
         rustFutureFunc: {
-            {{ func.ffi_func().name() }}(
+            {% call swift::ffi_func(func) %}(
                 {%- for arg in func.arguments() %}
                 {{ arg|lower_fn }}({{ arg.name()|var_name }}){% if !loop.last %},{% endif %}
                 {%- endfor %}
@@ -28,6 +28,22 @@ public func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) a
     )
 }
 
+{%- else if func.is_blocking() %}
+
+{%- call swift::docstring(func, 0) %}
+public func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) async {% call swift::throws(func) %}{% match func.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_name }}{% when None %}{% endmatch %} {
+    return {% if func.throws() %}try {% endif %}await Task.detached {
+        {%- match func.return_type() %}
+        {%- when Some with (return_type) %}
+        return {% call swift::try(func) %} {{ return_type|lift_fn }}(
+            {% call swift::to_ffi_call(func) %}
+        )
+        {%- when None %}
+        {% call swift::to_ffi_call(func) %}
+        {%- endmatch %}
+    }.value
+}
+
 {% else %}
 
 {%- match func.return_type() -%}
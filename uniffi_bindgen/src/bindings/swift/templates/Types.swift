@@ -90,6 +90,9 @@
 {%- when Type::Map { key_type, value_type } %}
 {%- include "MapTemplate.swift" %}
 
+{%- when Type::Function { args, ret } %}
+{%- include "FunctionTemplate.swift" %}
+
 {%- else %}
 {%- endmatch %}
 {%- endfor %}
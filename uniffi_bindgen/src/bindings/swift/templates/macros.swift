@@ -12,7 +12,7 @@
     {%- else -%}
         rustCall() {
     {%- endmatch %}
-    {{ func.ffi_func().name() }}({% call arg_list_lowered(func) -%} $0)
+    {% call ffi_func(func) %}({% call arg_list_lowered(func) -%} $0)
 }
 {%- endmacro -%}
 
@@ -24,12 +24,41 @@
     {%- else %}
     rustCall() {
     {% endmatch %}
-    {{ func.ffi_func().name() }}(
+    {% call ffi_func(func) %}(
         {{- prefix }}, {% call arg_list_lowered(func) -%} $0
     )
 }
 {%- endmacro %}
 
+{#-
+// Like `to_ffi_call_with_prefix`, but always throws unexpected errors, for methods that take
+// `self` by value.
+-#}
+{%- macro to_throwing_ffi_call_with_prefix(prefix, func) -%}
+try {% match func.throws_type() %}
+    {%- when Some with (e) %}
+    rustCallWithError({{ e|ffi_error_converter_name }}.lift) {
+    {%- else %}
+    rustCall() {
+    {% endmatch %}
+    {% call ffi_func(func) %}(
+        {{- prefix }}, {% call arg_list_lowered(func) -%} $0
+    )
+}
+{%- endmacro %}
+
+{#-
+// The scaffolding function to call, which is checked to not have been disabled because of a
+// checksum mismatch, in lenient mode.
+-#}
+{%- macro ffi_func(func) -%}
+{%- if config.lenient_checksums() -%}
+uniffiEnabledFunction("{{ func.ffi_func().name() }}", {{ func.ffi_func().name() }})
+{%- else -%}
+{{ func.ffi_func().name() }}
+{%- endif -%}
+{%- endmacro -%}
+
 {%- macro arg_list_lowered(func) %}
     {%- for arg in func.arguments() %}
         {{ arg|lower_fn }}({{ arg.name()|var_name }}),
@@ -43,7 +72,7 @@
 
 {% macro arg_list_decl(func) %}
     {%- for arg in func.arguments() -%}
-        {% if config.omit_argument_labels() %}_ {% endif %}{{ arg.name()|var_name }}: {{ arg|type_name -}}
+        {% if config.omit_argument_labels() %}_ {% endif %}{{ arg.name()|var_name }}: {{ arg|arg_type_name -}}
         {%- match arg.default_value() %}
         {%- when Some with(literal) %} = {{ literal|literal_swift(arg) }}
         {%- else %}
@@ -83,20 +112,27 @@ v{{- field_num -}}
 
 {% macro arg_list_protocol(func) %}
     {%- for arg in func.arguments() -%}
-        {% if config.omit_argument_labels() %}_ {% endif %}{{ arg.name()|var_name }}: {{ arg|type_name -}}
+        {% if config.omit_argument_labels() %}_ {% endif %}{{ arg.name()|var_name }}: {{ arg|arg_type_name -}}
         {%- if !loop.last %}, {% endif -%}
     {%- endfor %}
 {%- endmacro %}
 
 
 {%- macro async(func) %}
-{%- if func.is_async() %}async {% endif %}
+{%- if func.is_async() || func.is_blocking() %}async {% endif %}
 {%- endmacro -%}
 
 {%- macro throws(func) %}
 {%- if func.throws() %}throws {% endif %}
 {%- endmacro -%}
 
+{#-
+// Methods of objects that have consuming methods also throw once the object is consumed.
+-#}
+{%- macro method_throws(func, consumable) %}
+{%- if func.throws() || consumable %}throws {% endif %}
+{%- endmacro -%}
+
 {%- macro try(func) %}
 {%- if func.throws() %}try {% else %}try! {% endif %}
 {%- endmacro -%}
@@ -29,15 +29,34 @@ import {{ config.ffi_module_name() }}
 {% include "Async.swift" %}
 {%- endif %}
 
+{%- if config.log_bridge() %}
+
+{% include "LogBridge.swift" %}
+{%- endif %}
+
 {%- for func in ci.function_definitions() %}
 {%- include "TopLevelFunctionTemplate.swift" %}
 {%- endfor %}
 
 private enum InitializationResult {
     case ok
-    case contractVersionMismatch
-    case apiChecksumMismatch
+    case contractVersionMismatch(String)
+    case apiChecksumMismatch([String])
 }
+{%- if config.lenient_checksums() %}
+
+// The scaffolding functions that were disabled because their checksums don't match, with the
+// messages to fail with when they're called.
+private var uniffiDisabledFunctions: [String: String] = [:]
+
+// Get the scaffolding function `name`, after checking that it wasn't disabled.
+private func uniffiEnabledFunction<F>(_ name: String, _ function: F) -> F {
+    if let message = uniffiDisabledFunctions[name] {
+        fatalError(message)
+    }
+    return function
+}
+{%- endif %}
 // Use a global variables to perform the versioning checks. Swift ensures that
 // the code inside is only computed once.
 private var initializationResult: InitializationResult {
@@ -46,14 +65,32 @@ private var initializationResult: InitializationResult {
     // Get the scaffolding contract version by calling the into the dylib
     let scaffolding_contract_version = {{ ci.ffi_uniffi_contract_version().name() }}()
     if bindings_contract_version != scaffolding_contract_version {
-        return InitializationResult.contractVersionMismatch
+        return InitializationResult.contractVersionMismatch(
+            "the bindings expect version \(bindings_contract_version), the library has version \(scaffolding_contract_version)"
+        )
     }
 
-    {%- for (name, expected_checksum) in ci.iter_checksums() %}
-    if ({{ name }}() != {{ expected_checksum }}) {
-        return InitializationResult.apiChecksumMismatch
+    var mismatches: [String] = []
+    {%- for check in ci.iter_checksum_checks() %}
+    do {
+        let checksum = {{ check.checksum_fn_name }}()
+        if checksum != {{ check.checksum }} {
+            let detail = "the bindings expect checksum {{ check.checksum }}, the library has checksum \(checksum)"
+            {%- if let Some(ffi_func_name) = check.ffi_func_name %}
+            {%- if config.lenient_checksums() %}
+            uniffiDisabledFunctions["{{ ffi_func_name }}"] = "{{ check.item }} is disabled because of a UniFFI API checksum mismatch: \(detail)"
+            {%- else %}
+            mismatches.append("{{ check.item }}: \(detail)")
+            {%- endif %}
+            {%- else %}
+            mismatches.append("{{ check.item }}: \(detail)")
+            {%- endif %}
+        }
     }
     {%- endfor %}
+    if !mismatches.isEmpty {
+        return InitializationResult.apiChecksumMismatch(mismatches)
+    }
 
     {% for fn in self.initialization_fns() -%}
     {{ fn }}()
@@ -66,10 +103,13 @@ private func uniffiEnsureInitialized() {
     switch initializationResult {
     case .ok:
         break
-    case .contractVersionMismatch:
-        fatalError("UniFFI contract version mismatch: try cleaning and rebuilding your project")
-    case .apiChecksumMismatch:
-        fatalError("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    case let .contractVersionMismatch(detail):
+        fatalError("UniFFI contract version mismatch: try cleaning and rebuilding your project (\(detail))")
+    case let .apiChecksumMismatch(mismatches):
+        fatalError(
+            "UniFFI API checksum mismatch: try cleaning and rebuilding your project\n" +
+            mismatches.map { "    \($0)" }.joined(separator: "\n")
+        )
     }
 }
 
@@ -4,7 +4,7 @@ License, v. 2.0. If a copy of the MPL was not distributed with this
 
 use crate::{
     bindings::{RunScriptOptions, TargetLanguage},
-    library_mode::generate_bindings,
+    library_mode::{calc_cdylib_name, generate_bindings},
 };
 use anyhow::{bail, Context, Result};
 use camino::{Utf8Path, Utf8PathBuf};
@@ -40,7 +40,7 @@ pub fn run_script(
     let test_helper = UniFFITestHelper::new(crate_name)?;
     let out_dir = test_helper.create_out_dir(tmp_dir, &script_path)?;
     let cdylib_path = test_helper.copy_cdylib_to_out_dir(&out_dir)?;
-    let generated_sources = GeneratedSources::new(crate_name, &cdylib_path, &out_dir)?;
+    let generated_sources = GeneratedSources::new(&cdylib_path, &out_dir)?;
 
     // Compile the generated sources together to create a single swift module
     compile_swift_module(
@@ -125,7 +125,7 @@ struct GeneratedSources {
 }
 
 impl GeneratedSources {
-    fn new(crate_name: &str, cdylib_path: &Utf8Path, out_dir: &Utf8Path) -> Result<Self> {
+    fn new(cdylib_path: &Utf8Path, out_dir: &Utf8Path) -> Result<Self> {
         let sources = generate_bindings(
             cdylib_path,
             None,
@@ -136,7 +136,7 @@ impl GeneratedSources {
         )?;
         let main_source = sources
             .iter()
-            .find(|s| s.package.name == crate_name)
+            .find(|s| Some(s.crate_name.as_str()) == calc_cdylib_name(cdylib_path))
             .unwrap();
         let main_module = main_source.config.bindings.swift.module_name();
         let modulemap_glob = glob(&out_dir.join("*.modulemap"))?;
@@ -0,0 +1,801 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use askama::Template;
+use heck::{ToLowerCamelCase, ToUpperCamelCase};
+use serde::{Deserialize, Serialize};
+
+use crate::interface::*;
+use crate::BindingsConfig;
+
+// https://tc39.es/ecma262/#sec-keywords-and-reserved-words, plus the strict mode reserved words.
+static KEYWORDS: &[&str] = &[
+    "arguments",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "eval",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+/// Append an underscore to names that are JavaScript keywords
+fn quote_keyword(nm: String) -> String {
+    if KEYWORDS.contains(&nm.as_str()) {
+        format!("{nm}_")
+    } else {
+        nm
+    }
+}
+
+// Config options to customize the generated JavaScript module.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Config {
+    module_name: Option<String>,
+    cdylib_name: Option<String>,
+    wasm: Option<bool>,
+    lenient_checksums: Option<bool>,
+}
+
+impl Config {
+    /// The name of the generated module, the component namespace by default.
+    ///
+    /// This names the `.js` and `.d.ts` files, and the `{module_name}_napi` N-API addon.
+    pub fn module_name(&self, ci: &ComponentInterface) -> String {
+        self.module_name
+            .clone()
+            .unwrap_or_else(|| ci.namespace().to_string())
+    }
+
+    /// The name of the N-API addon, without the `.node` extension.
+    pub fn addon_name(&self, ci: &ComponentInterface) -> String {
+        format!("{}_napi", self.module_name(ci))
+    }
+
+    /// Whether the module calls a WebAssembly build of the Rust library, instead of an addon.
+    pub fn wasm(&self) -> bool {
+        self.wasm.unwrap_or(false)
+    }
+
+    /// Whether to only disable the functions with mismatched API checksums, rather than failing
+    /// to load the module.
+    pub fn lenient_checksums(&self) -> bool {
+        self.lenient_checksums.unwrap_or(false)
+    }
+
+    /// The name of the JavaScript glue for the wasm module, without the `.js` extension.
+    pub fn wasm_glue_name(&self, ci: &ComponentInterface) -> String {
+        format!("{}_wasm", self.module_name(ci))
+    }
+
+    /// The name of the compiled Rust library containing the FFI implementation.
+    pub fn cdylib_name(&self) -> String {
+        self.cdylib_name
+            .clone()
+            .unwrap_or_else(|| "uniffi".to_string())
+    }
+}
+
+impl BindingsConfig for Config {
+    fn update_from_ci(&mut self, ci: &ComponentInterface) {
+        self.cdylib_name
+            .get_or_insert_with(|| format!("uniffi_{}", ci.namespace()));
+    }
+
+    fn update_from_cdylib_name(&mut self, cdylib_name: &str) {
+        self.cdylib_name
+            .get_or_insert_with(|| cdylib_name.to_string());
+    }
+
+    fn update_from_dependency_configs(&mut self, _config_map: HashMap<&str, &Self>) {}
+}
+
+/// Bail out if the interface uses features that the JavaScript bindings don't support yet.
+pub fn check_supported(config: &Config, ci: &ComponentInterface) -> Result<()> {
+    // Rust calls back into JavaScript through function pointers, which would have to be added to
+    // the wasm module's function table.
+    if config.wasm() {
+        if ci.has_async_fns() {
+            bail!("TypeScript bindings don't support async functions with `wasm = true` yet");
+        }
+        if let Some(cbi) = ci.callback_interface_definitions().first() {
+            bail!(
+                "TypeScript bindings don't support callback interfaces with `wasm = true` yet (found `{}`)",
+                cbi.name()
+            );
+        }
+    }
+    for obj in ci.object_definitions() {
+        if obj.has_callback_interface() {
+            bail!(
+                "TypeScript bindings don't support foreign trait implementations yet (found `{}`)",
+                obj.name()
+            );
+        }
+        if ci.is_name_used_as_error(obj.name()) {
+            bail!(
+                "TypeScript bindings don't support objects as errors yet (found `{}`)",
+                obj.name()
+            );
+        }
+    }
+    for cbi in ci.callback_interface_definitions() {
+        if cbi.has_async_method() {
+            bail!(
+                "TypeScript bindings don't support async callback interface methods yet (found `{}`)",
+                cbi.name()
+            );
+        }
+    }
+    if let Some((name, ..)) = ci.iter_external_types().next() {
+        bail!("TypeScript bindings don't support external types yet (found `{name}`)");
+    }
+    Ok(())
+}
+
+/// Get the canonical, unique-within-this-component name for a type.
+///
+/// This is used to name the converter object for each type.
+pub fn canonical_name(t: &Type) -> String {
+    match t {
+        Type::Int8 => "Int8".into(),
+        Type::UInt8 => "UInt8".into(),
+        Type::Int16 => "Int16".into(),
+        Type::UInt16 => "UInt16".into(),
+        Type::Int32 => "Int32".into(),
+        Type::UInt32 => "UInt32".into(),
+        Type::Int64 => "Int64".into(),
+        Type::UInt64 => "UInt64".into(),
+        Type::Float32 => "Float32".into(),
+        Type::Float64 => "Float64".into(),
+        Type::Boolean => "Bool".into(),
+        Type::String => "String".into(),
+        Type::Bytes => "Bytes".into(),
+        Type::Timestamp => "Timestamp".into(),
+        Type::Duration => "Duration".into(),
+        Type::Function { .. } => {
+            panic!("Function types are not supported by the TypeScript bindings")
+        }
+        Type::Object { name, .. }
+        | Type::Record { name, .. }
+        | Type::Enum { name, .. }
+        | Type::Custom { name, .. }
+        | Type::External { name, .. } => format!("Type{}", name.to_upper_camel_case()),
+        Type::CallbackInterface { name, .. } => {
+            format!("CallbackInterface{}", name.to_upper_camel_case())
+        }
+        Type::Optional { inner_type } => format!("Optional{}", canonical_name(inner_type)),
+        Type::Sequence { inner_type } => format!("Sequence{}", canonical_name(inner_type)),
+        Type::Map {
+            key_type,
+            value_type,
+        } => format!(
+            "Map{}{}",
+            canonical_name(key_type),
+            canonical_name(value_type)
+        ),
+    }
+}
+
+/// Render a literal as a JavaScript expression.
+///
+/// Variants of enums with fields can't be defaults, since the literal doesn't say what the
+/// fields are.
+fn js_literal(ci: &ComponentInterface, literal: &Literal) -> Option<String> {
+    let bigint_suffix = |t: &Type| match t {
+        Type::Int64 | Type::UInt64 => "n",
+        _ => "",
+    };
+    Some(match literal {
+        Literal::Boolean(v) => v.to_string(),
+        // Rust's debug formatting escapes the string the same way as a JavaScript string literal
+        Literal::String(s) => format!("{s:?}"),
+        Literal::UInt(v, Radix::Hexadecimal, t) => format!("0x{v:x}{}", bigint_suffix(t)),
+        Literal::UInt(v, Radix::Octal, t) => format!("0o{v:o}{}", bigint_suffix(t)),
+        Literal::UInt(v, _, t) => format!("{v}{}", bigint_suffix(t)),
+        Literal::Int(v, Radix::Hexadecimal, t) if *v < 0 => {
+            format!("-0x{:x}{}", v.unsigned_abs(), bigint_suffix(t))
+        }
+        Literal::Int(v, Radix::Hexadecimal, t) => format!("0x{v:x}{}", bigint_suffix(t)),
+        Literal::Int(v, Radix::Octal, t) if *v < 0 => {
+            format!("-0o{:o}{}", v.unsigned_abs(), bigint_suffix(t))
+        }
+        Literal::Int(v, Radix::Octal, t) => format!("0o{v:o}{}", bigint_suffix(t)),
+        Literal::Int(v, _, t) => format!("{v}{}", bigint_suffix(t)),
+        Literal::Float(v, _) => v.clone(),
+        Literal::Enum(variant, Type::Enum { name, .. }) => {
+            if !ci.get_enum_definition(name)?.is_flat() {
+                return None;
+            }
+            format!(
+                "{}.{}",
+                name.to_upper_camel_case(),
+                variant.to_upper_camel_case()
+            )
+        }
+        Literal::Enum(..) => return None,
+        Literal::EmptySequence => "[]".into(),
+        Literal::EmptyMap => "new Map()".into(),
+        Literal::Null => "undefined".into(),
+    })
+}
+
+/// The default value of the argument at `index`, if it and all the following arguments have one.
+///
+/// TypeScript only allows optional parameters after the required ones, so defaults are only used
+/// for the trailing arguments.
+fn trailing_arg_default(
+    ci: &ComponentInterface,
+    arguments: &[&Argument],
+    index: usize,
+) -> Option<String> {
+    let defaults = arguments
+        .iter()
+        .map(|a| a.default_value().and_then(|lit| js_literal(ci, lit)))
+        .collect::<Vec<_>>();
+    if defaults[index..].iter().all(Option::is_some) {
+        defaults[index].clone()
+    } else {
+        None
+    }
+}
+
+/// Template for the JavaScript module, `{module_name}.js`
+#[derive(Template)]
+#[template(syntax = "js", escape = "none", path = "wrapper.js")]
+pub struct JsWrapper<'a> {
+    config: Config,
+    ci: &'a ComponentInterface,
+}
+
+impl<'a> JsWrapper<'a> {
+    pub fn new(config: Config, ci: &'a ComponentInterface) -> Self {
+        Self { config, ci }
+    }
+
+    /// The default value of a record field, used when the field is missing.
+    ///
+    /// Missing optional fields are already `undefined`, so they don't need a default.
+    fn field_default(&self, field: &Field) -> Option<String> {
+        field
+            .default_value()
+            .and_then(|lit| js_literal(self.ci, lit))
+            .filter(|default| default != "undefined")
+    }
+
+    /// The default value of an argument, as a default parameter value.
+    fn arg_default(&self, arguments: Vec<&Argument>, index: &usize) -> Option<String> {
+        trailing_arg_default(self.ci, &arguments, *index)
+    }
+
+    /// The names exported from the module.
+    fn exports(&self) -> Vec<String> {
+        let mut exports = vec![
+            "UniffiInternalError".to_string(),
+            "UniffiRustPanic".to_string(),
+        ];
+        if self.config.wasm() {
+            exports.push("uniffiInitialize".to_string());
+        }
+        exports.extend(
+            self.ci
+                .function_definitions()
+                .iter()
+                .map(|f| filters::fn_name(f.name()).unwrap()),
+        );
+        exports.extend(
+            self.ci
+                .object_definitions()
+                .iter()
+                .map(|o| o.name().to_upper_camel_case()),
+        );
+        exports.extend(
+            self.ci
+                .enum_definitions()
+                .filter(|e| e.is_flat() || self.ci.is_name_used_as_error(e.name()))
+                .map(|e| e.name().to_upper_camel_case()),
+        );
+        exports
+    }
+}
+
+/// Template for the TypeScript declarations, `{module_name}.d.ts`
+#[derive(Template)]
+#[template(syntax = "js", escape = "none", path = "wrapper.d.ts")]
+pub struct DtsWrapper<'a> {
+    config: Config,
+    ci: &'a ComponentInterface,
+}
+
+impl<'a> DtsWrapper<'a> {
+    pub fn new(config: Config, ci: &'a ComponentInterface) -> Self {
+        Self { config, ci }
+    }
+
+    /// Does the argument have a default value, which makes it an optional parameter?
+    fn arg_default(&self, arguments: Vec<&Argument>, index: &usize) -> Option<String> {
+        trailing_arg_default(self.ci, &arguments, *index)
+    }
+
+    /// Does the field have a default value, which makes it optional in the record interface?
+    fn has_field_default(&self, field: &Field) -> bool {
+        field
+            .default_value()
+            .and_then(|lit| js_literal(self.ci, lit))
+            .is_some()
+    }
+}
+
+/// Template for the N-API addon source, `{module_name}_napi.c`
+///
+/// The addon exports each scaffolding function to JavaScript, converting the arguments and
+/// return values between JavaScript values and their C types.
+#[derive(Template)]
+#[template(syntax = "c", escape = "none", path = "napi.c")]
+pub struct NapiShim<'a> {
+    config: Config,
+    ci: &'a ComponentInterface,
+    header_filename: String,
+}
+
+impl<'a> NapiShim<'a> {
+    pub fn new(config: Config, ci: &'a ComponentInterface, header_filename: String) -> Self {
+        Self {
+            config,
+            ci,
+            header_filename,
+        }
+    }
+
+    /// The scaffolding functions that are exported with a generic wrapper.
+    ///
+    /// The `RustBuffer` functions are only used inside the addon, while the `rust_future_poll`
+    /// and vtable registration functions need special wrappers since they take C function
+    /// pointers.  The log bridge isn't supported, so its init function isn't exported.
+    fn plain_functions(&self) -> Vec<FfiFunction> {
+        let rust_buffer_functions = self
+            .ci
+            .iter_rust_buffer_ffi_function_definitions()
+            .map(|f| f.name().to_string())
+            .collect::<Vec<_>>();
+        let init_functions = self
+            .ci
+            .callback_interface_definitions()
+            .iter()
+            .map(|cbi| cbi.ffi_init_callback().name().to_string())
+            .collect::<Vec<_>>();
+        self.ci
+            .iter_ffi_function_definitions()
+            .filter(|f| {
+                !rust_buffer_functions.iter().any(|n| n == f.name())
+                    && !init_functions.iter().any(|n| n == f.name())
+                    && !self.is_poll_function(f)
+                    && f.name() != self.ci.ffi_log_bridge_init().name()
+            })
+            .collect()
+    }
+
+    /// The `rust_future_poll` functions, which get the addon's continuation callback.
+    ///
+    /// These are only exported when the component has async functions.
+    fn poll_functions(&self) -> Vec<FfiFunction> {
+        if !self.ci.has_async_fns() {
+            return vec![];
+        }
+        self.ci
+            .iter_futures_ffi_function_definitions()
+            .filter(|f| self.is_poll_function(f))
+            .collect()
+    }
+
+    /// The number of JavaScript arguments to a function, including the call status object.
+    fn js_argc(&self, func: &FfiFunction) -> usize {
+        func.arguments().len() + usize::from(func.has_rust_call_status_arg())
+    }
+
+    fn is_poll_function(&self, func: &FfiFunction) -> bool {
+        func.name().contains("_rust_future_poll_")
+    }
+
+    /// The functions exported by the addon, as `(JavaScript name, C function)` pairs.
+    fn exports(&self) -> Vec<(String, String)> {
+        let mut names = self
+            .plain_functions()
+            .iter()
+            .chain(self.poll_functions().iter())
+            .map(|f| f.name().to_string())
+            .collect::<Vec<_>>();
+        names.extend(
+            self.ci
+                .callback_interface_definitions()
+                .iter()
+                .map(|cbi| cbi.ffi_init_callback().name().to_string()),
+        );
+        let mut exports = names
+            .into_iter()
+            .map(|name| {
+                let function = format!("uniffi_napi_{name}");
+                (name, function)
+            })
+            .collect::<Vec<_>>();
+        if self.ci.has_async_fns() {
+            exports.push((
+                "uniffi_set_future_continuation".to_string(),
+                "uniffi_napi_set_future_continuation".to_string(),
+            ));
+        }
+        exports
+    }
+}
+
+/// Template for the wasm glue, `{module_name}_wasm.js`
+///
+/// This loads the wasm build of the Rust library and exports its scaffolding functions with the
+/// same JavaScript signatures as the N-API addon, marshalling values through linear memory.
+#[derive(Template)]
+#[template(syntax = "js", escape = "none", path = "wasm.js")]
+pub struct WasmGlue<'a> {
+    config: Config,
+    ci: &'a ComponentInterface,
+}
+
+impl<'a> WasmGlue<'a> {
+    pub fn new(config: Config, ci: &'a ComponentInterface) -> Self {
+        Self { config, ci }
+    }
+
+    /// The scaffolding functions that are exported to the module.
+    ///
+    /// The `RustBuffer` functions are only used inside the glue.
+    fn functions(&self) -> Vec<FfiFunction> {
+        let rust_buffer_functions = self
+            .ci
+            .iter_rust_buffer_ffi_function_definitions()
+            .map(|f| f.name().to_string())
+            .collect::<Vec<_>>();
+        self.ci
+            .iter_ffi_function_definitions_non_async()
+            .filter(|f| !rust_buffer_functions.iter().any(|n| n == f.name()))
+            .collect()
+    }
+}
+
+mod filters {
+    use super::*;
+
+    /// The TypeScript type for a UniFFI type
+    pub fn type_name(as_type: &impl AsType) -> Result<String, askama::Error> {
+        Ok(match as_type.as_type() {
+            Type::Int8
+            | Type::UInt8
+            | Type::Int16
+            | Type::UInt16
+            | Type::Int32
+            | Type::UInt32
+            | Type::Float32
+            | Type::Float64
+            | Type::Duration => "number".into(),
+            Type::Int64 | Type::UInt64 => "bigint".into(),
+            Type::Boolean => "boolean".into(),
+            Type::String => "string".into(),
+            Type::Bytes => "Uint8Array".into(),
+            Type::Timestamp => "Date".into(),
+            Type::Function { .. } => {
+                panic!("Function types are not supported by the TypeScript bindings")
+            }
+            Type::Object { name, .. }
+            | Type::Record { name, .. }
+            | Type::Enum { name, .. }
+            | Type::Custom { name, .. }
+            | Type::External { name, .. }
+            | Type::CallbackInterface { name, .. } => class_name(&name)?,
+            Type::Optional { inner_type } => format!("{} | undefined", type_name(&*inner_type)?),
+            Type::Sequence { inner_type } => format!("Array<{}>", type_name(&*inner_type)?),
+            Type::Map {
+                key_type,
+                value_type,
+            } => format!(
+                "Map<{}, {}>",
+                type_name(&*key_type)?,
+                type_name(&*value_type)?
+            ),
+        })
+    }
+
+    pub fn converter_name(as_type: &impl AsType) -> Result<String, askama::Error> {
+        Ok(format!(
+            "FfiConverter{}",
+            canonical_name(&as_type.as_type())
+        ))
+    }
+
+    /// The `DataView` method suffix for reading and writing a number
+    pub fn buffer_method(as_type: &impl AsType) -> Result<String, askama::Error> {
+        Ok(match as_type.as_type() {
+            Type::Int8 => "Int8".into(),
+            Type::UInt8 => "Uint8".into(),
+            Type::Int16 => "Int16".into(),
+            Type::UInt16 => "Uint16".into(),
+            Type::Int32 => "Int32".into(),
+            Type::UInt32 => "Uint32".into(),
+            Type::Int64 => "BigInt64".into(),
+            Type::UInt64 => "BigUint64".into(),
+            Type::Float32 => "Float32".into(),
+            Type::Float64 => "Float64".into(),
+            _ => unimplemented!("buffer_method for {:?}", as_type.as_type()),
+        })
+    }
+
+    /// The range of an integer type, for checking values before they're lowered.
+    pub fn int_range(as_type: &impl AsType) -> Result<String, askama::Error> {
+        Ok(match as_type.as_type() {
+            Type::Int8 => "-0x80, 0x7f".into(),
+            Type::UInt8 => "0, 0xff".into(),
+            Type::Int16 => "-0x8000, 0x7fff".into(),
+            Type::UInt16 => "0, 0xffff".into(),
+            Type::Int32 => "-0x80000000, 0x7fffffff".into(),
+            Type::UInt32 => "0, 0xffffffff".into(),
+            Type::Int64 => "-0x8000000000000000n, 0x7fffffffffffffffn".into(),
+            Type::UInt64 => "0n, 0xffffffffffffffffn".into(),
+            _ => unimplemented!("int_range for {:?}", as_type.as_type()),
+        })
+    }
+
+    /// The C type for an FFI type
+    pub fn c_type_name(ffi_type: &FfiType) -> Result<String, askama::Error> {
+        crate::bindings::c::gen_c::filters::ffi_type_name(ffi_type)
+    }
+
+    /// The C typedef name for an FFI callback function or struct
+    pub fn ffi_callback_name(nm: &str) -> Result<String, askama::Error> {
+        crate::bindings::c::gen_c::filters::ffi_callback_name(nm)
+    }
+
+    pub fn ffi_struct_name(nm: &str) -> Result<String, askama::Error> {
+        crate::bindings::c::gen_c::filters::ffi_struct_name(nm)
+    }
+
+    /// The suffix of the addon helpers that convert a C value to and from JavaScript
+    ///
+    /// Integers up to 32 bits and floats are numbers, 64-bit integers and pointers are bigints
+    /// and `RustBuffer`s are `Uint8Array`s.
+    pub fn napi_kind(ffi_type: &FfiType) -> Result<String, askama::Error> {
+        Ok(match ffi_type {
+            FfiType::Int8 => "int8".into(),
+            FfiType::UInt8 => "uint8".into(),
+            FfiType::Int16 => "int16".into(),
+            FfiType::UInt16 => "uint16".into(),
+            FfiType::Int32 => "int32".into(),
+            FfiType::UInt32 => "uint32".into(),
+            FfiType::Int64 => "int64".into(),
+            FfiType::UInt64 | FfiType::Handle => "uint64".into(),
+            FfiType::Float32 => "float".into(),
+            FfiType::Float64 => "double".into(),
+            FfiType::RustArcPtr(_) => "pointer".into(),
+            FfiType::RustBuffer(_) => "rust_buffer".into(),
+            _ => unimplemented!("napi_kind for {ffi_type:?}"),
+        })
+    }
+
+    /// The `napi_kind` of the type that a UniFFI type is lowered into
+    pub fn lowered_napi_kind(as_type: &impl AsType) -> Result<String, askama::Error> {
+        napi_kind(&FfiType::from(as_type.as_type()))
+    }
+
+    /// The JavaScript rendering of a type name (for records, enums, objects, etc).
+    pub fn class_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(nm.to_upper_camel_case())
+    }
+
+    /// The JavaScript rendering of a top-level function name.
+    pub fn fn_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(quote_keyword(nm.to_lower_camel_case()))
+    }
+
+    /// The JavaScript rendering of a method name, which can be a keyword.
+    pub fn method_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(nm.to_lower_camel_case())
+    }
+
+    /// The JavaScript rendering of a variable or argument name.
+    pub fn var_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(quote_keyword(nm.to_lower_camel_case()))
+    }
+
+    /// The JavaScript rendering of a record or variant field name.
+    ///
+    /// Unnamed fields of enum variants are called `v0`, `v1`, etc.
+    pub fn field_name(field: &Field, index: &usize) -> Result<String, askama::Error> {
+        if field.name().is_empty() {
+            Ok(format!("v{index}"))
+        } else {
+            Ok(field.name().to_lower_camel_case())
+        }
+    }
+
+    /// The JavaScript rendering of an enum variant name.
+    pub fn variant_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(nm.to_upper_camel_case())
+    }
+
+    /// The JSDoc comment for a docstring
+    pub fn docstring(docstring: &str, spaces: &i32) -> Result<String, askama::Error> {
+        let middle = textwrap::indent(&textwrap::dedent(docstring), " * ");
+        let wrapped = format!("/**\n{}\n */", middle.trim_end());
+
+        let spaces = usize::try_from(*spaces).unwrap_or_default();
+        Ok(textwrap::indent(&wrapped, &" ".repeat(spaces)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UDL: &str = r#"
+        namespace geometry {
+            string describe(Point point, u8 precision);
+            [Throws=GeometryError]
+            Point parse(string text);
+            void reset();
+        };
+
+        dictionary Point {
+            double x;
+            double y;
+        };
+
+        [Error]
+        enum GeometryError { "Invalid" };
+
+        interface Shape {
+            constructor(sequence<Point> points);
+            u64 vertex_count();
+        };
+    "#;
+
+    #[test]
+    fn test_wasm_glue() {
+        let mut ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        ci.derive_ffi_funcs().unwrap();
+        let config = Config {
+            cdylib_name: Some("geometry".into()),
+            wasm: Some(true),
+            ..Config::default()
+        };
+        let glue = WasmGlue::new(config, &ci).render().unwrap();
+
+        // The glue marshals `RustBuffer`s with the crate's own `RustBuffer` functions, which
+        // aren't exported to the module.
+        assert!(glue.contains(&format!(
+            "uniffiWasm.{}(pointer, BigInt(bytes.length), status);",
+            ci.ffi_rustbuffer_alloc().name()
+        )));
+        assert!(glue.contains(&format!(
+            "uniffiWasm.{}(...uniffiRustBufferArgs(pointer), status);",
+            ci.ffi_rustbuffer_free().name()
+        )));
+        assert!(glue.contains(&format!(
+            "exports.{}.length > 2;",
+            ci.ffi_rustbuffer_free().name()
+        )));
+        for func in ci.iter_rust_buffer_ffi_function_definitions() {
+            assert!(!glue.contains(&format!("{}: (...args)", func.name())));
+        }
+
+        // Records and strings are passed and returned as `RustBuffer`s, with a call status.
+        assert!(glue.contains(
+            r#"  uniffi_crate_name_fn_func_describe: (...args) => uniffiCall(
+    "uniffi_crate_name_fn_func_describe",
+    ["rust_buffer", "uint8"],
+    "rust_buffer",
+    true,
+    args,
+  ),"#
+        ));
+        assert!(glue.contains(
+            r#"  uniffi_crate_name_fn_func_reset: (...args) => uniffiCall(
+    "uniffi_crate_name_fn_func_reset",
+    [],
+    null,
+    true,
+    args,
+  ),"#
+        ));
+        // Objects are pointers, and 64-bit integers are bigints.
+        assert!(glue.contains(
+            r#"  uniffi_crate_name_fn_constructor_shape_new: (...args) => uniffiCall(
+    "uniffi_crate_name_fn_constructor_shape_new",
+    ["rust_buffer"],
+    "pointer",
+    true,
+    args,
+  ),"#
+        ));
+        assert!(glue.contains(
+            r#"  uniffi_crate_name_fn_method_shape_vertex_count: (...args) => uniffiCall(
+    "uniffi_crate_name_fn_method_shape_vertex_count",
+    ["pointer"],
+    "uint64",
+    true,
+    args,
+  ),"#
+        ));
+        // Checksums don't take a call status.
+        assert!(glue.contains(
+            r#"  uniffi_crate_name_checksum_func_describe: (...args) => uniffiCall(
+    "uniffi_crate_name_checksum_func_describe",
+    [],
+    "uint16",
+    false,
+    args,
+  ),"#
+        ));
+    }
+
+    #[test]
+    fn test_wasm_checks() {
+        let mut ci = ComponentInterface::from_webidl(
+            r#"
+                namespace clock {
+                    [Async] u64 now();
+                };
+            "#,
+            "crate_name",
+        )
+        .unwrap();
+        ci.derive_ffi_funcs().unwrap();
+        let config = Config {
+            wasm: Some(true),
+            ..Config::default()
+        };
+        assert!(check_supported(&config, &ci).is_err());
+        assert!(check_supported(&Config::default(), &ci).is_ok());
+    }
+}
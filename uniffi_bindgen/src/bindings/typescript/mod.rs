@@ -0,0 +1,98 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # TypeScript/Node.js bindings backend
+//!
+//! This generates a CommonJS module, `{module}.js`, with its TypeScript declarations in
+//! `{module}.d.ts`.  The module calls the scaffolding functions through a Node-API addon,
+//! `{module}_napi.node`, which is compiled from the generated `{module}_napi.c` and the
+//! `{module}.h` C header from the `c` backend.
+//!
+//! Objects are classes holding a pointer to the Rust object, which is freed by `destroy()` or by
+//! a `FinalizationRegistry` when the object is garbage collected.  Async functions return
+//! promises that are resolved when Rust wakes the future, and callback interfaces are
+//! implemented by JavaScript objects, which Rust calls through the addon.
+//!
+//! With `wasm = true`, the module calls a `wasm32-unknown-unknown` build of the Rust library
+//! instead.  The addon is replaced by `{module}_wasm.js`, which exports the scaffolding functions
+//! with the same signatures, copying `RustBuffer`s in and out of the wasm linear memory.
+
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use askama::Template;
+use camino::Utf8Path;
+use fs_err as fs;
+
+pub mod gen_typescript;
+mod test;
+pub use gen_typescript::{Config, DtsWrapper, JsWrapper, NapiShim, WasmGlue};
+pub use test::{run_script, run_test};
+
+use super::super::interface::ComponentInterface;
+use super::c;
+
+// Generate the JavaScript module, its declarations and the N-API addon source (or the wasm glue)
+// for the given ComponentInterface, in the given output directory.
+pub fn write_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+    out_dir: &Utf8Path,
+    try_format_code: bool,
+) -> Result<()> {
+    gen_typescript::check_supported(config, ci)?;
+
+    let module_name = config.module_name(ci);
+
+    if config.wasm() {
+        let glue = WasmGlue::new(config.clone(), ci)
+            .render()
+            .context("failed to render wasm glue")?;
+        fs::write(
+            out_dir.join(format!("{}.js", config.wasm_glue_name(ci))),
+            glue,
+        )?;
+    } else {
+        let header_filename = format!("{module_name}.h");
+        let ffi_config =
+            c::Config::with_header_filename(header_filename.clone(), config.cdylib_name());
+        fs::write(
+            out_dir.join(&header_filename),
+            c::generate_header(&ffi_config, ci)?,
+        )?;
+
+        let addon = NapiShim::new(config.clone(), ci, header_filename)
+            .render()
+            .context("failed to render N-API addon")?;
+        fs::write(out_dir.join(format!("{}.c", config.addon_name(ci))), addon)?;
+    }
+
+    let js_file = out_dir.join(format!("{module_name}.js"));
+    let js = JsWrapper::new(config.clone(), ci)
+        .render()
+        .context("failed to render JavaScript bindings")?;
+    fs::write(&js_file, js)?;
+
+    let dts_file = out_dir.join(format!("{module_name}.d.ts"));
+    let dts = DtsWrapper::new(config.clone(), ci)
+        .render()
+        .context("failed to render TypeScript declarations")?;
+    fs::write(&dts_file, dts)?;
+
+    if try_format_code {
+        if let Err(e) = Command::new("prettier")
+            .arg("--write")
+            .arg(&js_file)
+            .arg(&dts_file)
+            .output()
+        {
+            println!(
+                "Warning: Unable to auto-format {} using prettier: {e:?}",
+                js_file.file_name().unwrap(),
+            )
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+const UNIFFI_RUST_FUTURE_POLL_READY = 0;
+
+// The resolve functions of the promises waiting for a future to be woken, keyed by the
+// `data` passed to `rust_future_poll`.
+const uniffiContinuations = new Map();
+let uniffiNextContinuation = 1;
+
+lib.uniffi_set_future_continuation((data, pollResult) => {
+  const resolve = uniffiContinuations.get(data);
+  uniffiContinuations.delete(data);
+  resolve(pollResult);
+});
+
+function uniffiPoll(rustFuture, poll) {
+  return new Promise((resolve) => {
+    const data = uniffiNextContinuation++;
+    uniffiContinuations.set(data, resolve);
+    poll(rustFuture, data);
+  });
+}
+
+// Drive a Rust future to completion, then lift its result.  `lift` is `null` for futures that
+// don't return a value.
+async function uniffiRustCallAsync(rustFuture, poll, complete, free, lift, liftError) {
+  try {
+    let pollResult;
+    do {
+      pollResult = await uniffiPoll(rustFuture, poll);
+    } while (pollResult !== UNIFFI_RUST_FUTURE_POLL_READY);
+    const result = uniffiRustCall(liftError, complete, rustFuture);
+    return lift !== null ? lift(result) : undefined;
+  } finally {
+    free(rustFuture);
+  }
+}
@@ -0,0 +1,36 @@
+{%- let iface_name = cbi.name()|class_name %}
+{%- let handle_map = format!("uniffiHandleMap{}", iface_name) %}
+// JavaScript implementations are kept here while Rust holds a handle to them.
+const {{ handle_map }} = new UniffiHandleMap();
+
+const {{ conv }} = {
+  lift: (handle) => {{ handle_map }}.get(handle),
+  lower: (value) => {{ handle_map }}.insert(value),
+  read: (reader) => {{ handle_map }}.get(reader.readBigUint64()),
+  write: (value, writer) => writer.writeBigUint64({{ handle_map }}.insert(value)),
+};
+
+lib.{{ cbi.ffi_init_callback().name() }}({
+  {%- for (callback, meth) in cbi.vtable_methods() %}
+  {{ meth.name() }}: (uniffiHandle, {% for arg in meth.arguments() %}{{ arg.name()|var_name }}, {% endfor %}uniffiCallStatus) =>
+    uniffiCallCallback(
+      uniffiCallStatus,
+      {%- match meth.throws_type() %}
+      {%- when Some(error_type) %}
+      {{ error_type|type_name }},
+      {{ error_type|converter_name }},
+      {%- when None %}
+      null,
+      null,
+      {%- endmatch %}
+      () => {
+        const uniffiImpl = {{ handle_map }}.get(uniffiHandle);
+        {% match meth.return_type() %}{% when Some(return_type) %}return {{ return_type|converter_name }}.lower({% when None %}{% endmatch -%}
+        uniffiImpl.{{ meth.name()|method_name }}(
+          {%- for arg in meth.arguments() %}{{ arg|converter_name }}.lift({{ arg.name()|var_name }}){% if !loop.last %}, {% endif %}{% endfor -%}
+        ){% if meth.return_type().is_some() %}){% endif %};
+      },
+    ),
+  {%- endfor %}
+  uniffi_free: (uniffiHandle) => {{ handle_map }}.remove(uniffiHandle),
+});
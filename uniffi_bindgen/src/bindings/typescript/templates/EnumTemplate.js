@@ -0,0 +1,63 @@
+{%- let type_name = e.name()|class_name %}
+{%- if e.is_flat() %}
+{#-
+// Flat enums are numbers, like TypeScript enums.
+#}
+const {{ type_name }} = Object.freeze({
+  {%- for variant in e.variants() %}
+  {{ variant.name()|variant_name }}: {{ loop.index0 }},
+  {%- endfor %}
+});
+
+const {{ conv }} = uniffiRustBufferConverter({
+  read: (reader) => {
+    const index = reader.readInt32();
+    if (index < 1 || index > {{ e.variants().len() }}) {
+      throw new UniffiInternalError(`Invalid {{ type_name }} variant: ${index}`);
+    }
+    return index - 1;
+  },
+  write: (value, writer) => {
+    if (!Number.isInteger(value) || value < 0 || value >= {{ e.variants().len() }}) {
+      throw new TypeError(`Invalid {{ type_name }} value: ${value}`);
+    }
+    writer.writeInt32(value + 1);
+  },
+});
+{%- else %}
+{#-
+// Enums with fields are objects with a `tag` naming the variant.
+#}
+const {{ conv }} = uniffiRustBufferConverter({
+  read: (reader) => {
+    const index = reader.readInt32();
+    switch (index) {
+      {%- for variant in e.variants() %}
+      case {{ loop.index }}:
+        return {
+          tag: "{{ variant.name()|variant_name }}",
+          {%- for field in variant.fields() %}
+          {{ field|field_name(loop.index0) }}: {{ field|converter_name }}.read(reader),
+          {%- endfor %}
+        };
+      {%- endfor %}
+      default:
+        throw new UniffiInternalError(`Invalid {{ type_name }} variant: ${index}`);
+    }
+  },
+  write: (value, writer) => {
+    switch (value.tag) {
+      {%- for variant in e.variants() %}
+      case "{{ variant.name()|variant_name }}":
+        writer.writeInt32({{ loop.index }});
+        {%- for field in variant.fields() %}
+        {{ field|converter_name }}.write(value.{{ field|field_name(loop.index0) }}, writer);
+        {%- endfor %}
+        break;
+      {%- endfor %}
+      default:
+        throw new TypeError(`Invalid {{ type_name }} tag: ${value.tag}`);
+    }
+  },
+});
+{%- endif %}
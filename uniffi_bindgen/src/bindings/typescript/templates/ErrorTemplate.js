@@ -0,0 +1,72 @@
+{%- let type_name = e.name()|class_name %}
+{#-
+// Errors are classes, with a subclass for each variant.
+#}
+class {{ type_name }} extends Error {
+  constructor(message) {
+    super(message);
+    this.name = "{{ type_name }}";
+  }
+}
+{%- for variant in e.variants() %}
+{%- let variant_name = variant.name()|variant_name %}
+
+{{ type_name }}.{{ variant_name }} = class {{ variant_name }} extends {{ type_name }} {
+  {%- if e.is_flat() %}
+  constructor(message) {
+    super(message);
+  {%- else %}
+  constructor(
+    {%- for field in variant.fields() %}{{ field|field_name(loop.index0) }}{% if !loop.last %}, {% endif %}{% endfor -%}
+  ) {
+    {%- if variant.has_fields() %}
+    super(`
+    {%- for field in variant.fields() %}
+    {%- let name = field|field_name(loop.index0) %}{{ name }}=${ {{- name -}} }{% if !loop.last %}, {% endif %}
+    {%- endfor %}`);
+    {%- else %}
+    super("{{ variant_name }}");
+    {%- endif %}
+    {%- for field in variant.fields() %}
+    {%- let name = field|field_name(loop.index0) %}
+    this.{{ name }} = {{ name }};
+    {%- endfor %}
+  {%- endif %}
+    this.name = "{{ type_name }}.{{ variant_name }}";
+  }
+};
+{%- endfor %}
+
+const {{ conv }} = uniffiRustBufferConverter({
+  read: (reader) => {
+    const index = reader.readInt32();
+    switch (index) {
+      {%- for variant in e.variants() %}
+      case {{ loop.index }}:
+        {%- if e.is_flat() %}
+        return new {{ type_name }}.{{ variant.name()|variant_name }}(reader.readString());
+        {%- else %}
+        return new {{ type_name }}.{{ variant.name()|variant_name }}(
+          {%- for field in variant.fields() %}{{ field|converter_name }}.read(reader){% if !loop.last %}, {% endif %}{% endfor -%}
+        );
+        {%- endif %}
+      {%- endfor %}
+      default:
+        throw new UniffiInternalError(`Invalid {{ type_name }} variant: ${index}`);
+    }
+  },
+  write: (value, writer) => {
+    {%- for variant in e.variants() %}
+    if (value instanceof {{ type_name }}.{{ variant.name()|variant_name }}) {
+      writer.writeInt32({{ loop.index }});
+      {%- if !e.is_flat() %}
+      {%- for field in variant.fields() %}
+      {{ field|converter_name }}.write(value.{{ field|field_name(loop.index0) }}, writer);
+      {%- endfor %}
+      {%- endif %}
+      return;
+    }
+    {%- endfor %}
+    throw new TypeError(`Invalid {{ type_name }} value: ${value}`);
+  },
+});
@@ -0,0 +1,274 @@
+const UNIFFI_CALL_SUCCESS = 0;
+const UNIFFI_CALL_ERROR = 1;
+const UNIFFI_CALL_UNEXPECTED_ERROR = 2;
+const UNIFFI_CALL_CANCELLED = 3;
+const UNIFFI_CALL_PANIC = 4;
+
+// Thrown when the Rust library panics, or when the bindings and the library disagree about the
+// interface.
+class UniffiInternalError extends Error {
+  constructor(message) {
+    super(message);
+    this.name = "UniffiInternalError";
+  }
+}
+
+// Thrown when the Rust library panics.
+class UniffiRustPanic extends UniffiInternalError {
+  constructor(message) {
+    super(message);
+    this.name = "UniffiRustPanic";
+  }
+}
+
+const uniffiTextEncoder = new TextEncoder();
+const uniffiTextDecoder = new TextDecoder();
+
+// Call a scaffolding function with a `RustCallStatus`, and throw if the call failed.
+// `liftError` converts the error buffer for functions that throw, and is `null` otherwise.
+function uniffiRustCall(liftError, func, ...args) {
+  const status = { code: UNIFFI_CALL_SUCCESS, errorBuf: undefined };
+  const result = func(...args, status);
+  uniffiCheckCallStatus(status, liftError);
+  return result;
+}
+
+function uniffiCheckCallStatus(status, liftError) {
+  switch (status.code) {
+    case UNIFFI_CALL_SUCCESS:
+      return;
+    case UNIFFI_CALL_ERROR:
+      if (liftError !== null) {
+        throw liftError(status.errorBuf);
+      }
+      throw new UniffiInternalError("Unexpected error returned by the Rust library");
+    case UNIFFI_CALL_UNEXPECTED_ERROR:
+      // The error message is the raw UTF-8 string, like a lowered string.
+      if (status.errorBuf !== undefined && status.errorBuf.length > 0) {
+        throw new UniffiInternalError(uniffiTextDecoder.decode(status.errorBuf));
+      }
+      throw new UniffiInternalError("Unexpected Rust error");
+    case UNIFFI_CALL_PANIC:
+      // The serialized panic details start with the message.
+      if (status.errorBuf !== undefined && status.errorBuf.length > 0) {
+        throw new UniffiRustPanic(new UniffiReader(status.errorBuf).readString());
+      }
+      throw new UniffiRustPanic("Rust panic");
+    case UNIFFI_CALL_CANCELLED:
+      throw new UniffiInternalError("Cancelled");
+    default:
+      throw new UniffiInternalError(`Unknown RustCallStatus code: ${status.code}`);
+  }
+}
+
+// Reads values serialized into a `RustBuffer`, which are in big-endian order.
+class UniffiReader {
+  constructor(bytes) {
+    // The addon returns `Buffer`s, which are viewed as plain `Uint8Array`s so that `readBytes`
+    // returns a copy.
+    this.bytes = new Uint8Array(bytes.buffer, bytes.byteOffset, bytes.byteLength);
+    this.view = new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength);
+    this.position = 0;
+  }
+
+  hasRemaining() {
+    return this.position < this.bytes.length;
+  }
+
+  advance(count) {
+    if (this.position + count > this.bytes.length) {
+      throw new UniffiInternalError("Unexpected end of buffer");
+    }
+    const position = this.position;
+    this.position += count;
+    return position;
+  }
+
+  readBytes(count) {
+    const position = this.advance(count);
+    return this.bytes.slice(position, position + count);
+  }
+
+  readInt8() { return this.view.getInt8(this.advance(1)); }
+  readUint8() { return this.view.getUint8(this.advance(1)); }
+  readInt16() { return this.view.getInt16(this.advance(2)); }
+  readUint16() { return this.view.getUint16(this.advance(2)); }
+  readInt32() { return this.view.getInt32(this.advance(4)); }
+  readUint32() { return this.view.getUint32(this.advance(4)); }
+  readBigInt64() { return this.view.getBigInt64(this.advance(8)); }
+  readBigUint64() { return this.view.getBigUint64(this.advance(8)); }
+  readFloat32() { return this.view.getFloat32(this.advance(4)); }
+  readFloat64() { return this.view.getFloat64(this.advance(8)); }
+
+  readString() {
+    const length = this.readInt32();
+    return uniffiTextDecoder.decode(this.readBytes(length));
+  }
+}
+
+// Serializes values into a byte array for a `RustBuffer`, in big-endian order.
+class UniffiWriter {
+  constructor() {
+    this.bytes = new Uint8Array(64);
+    this.view = new DataView(this.bytes.buffer);
+    this.position = 0;
+  }
+
+  reserve(count) {
+    if (this.position + count > this.bytes.length) {
+      const bytes = new Uint8Array(Math.max(this.bytes.length * 2, this.position + count));
+      bytes.set(this.bytes);
+      this.bytes = bytes;
+      this.view = new DataView(bytes.buffer);
+    }
+    const position = this.position;
+    this.position += count;
+    return position;
+  }
+
+  toBytes() {
+    return this.bytes.subarray(0, this.position);
+  }
+
+  writeBytes(bytes) { this.bytes.set(bytes, this.reserve(bytes.length)); }
+  writeInt8(value) { this.view.setInt8(this.reserve(1), value); }
+  writeUint8(value) { this.view.setUint8(this.reserve(1), value); }
+  writeInt16(value) { this.view.setInt16(this.reserve(2), value); }
+  writeUint16(value) { this.view.setUint16(this.reserve(2), value); }
+  writeInt32(value) { this.view.setInt32(this.reserve(4), value); }
+  writeUint32(value) { this.view.setUint32(this.reserve(4), value); }
+  writeBigInt64(value) { this.view.setBigInt64(this.reserve(8), value); }
+  writeBigUint64(value) { this.view.setBigUint64(this.reserve(8), value); }
+  writeFloat32(value) { this.view.setFloat32(this.reserve(4), value); }
+  writeFloat64(value) { this.view.setFloat64(this.reserve(8), value); }
+
+  writeString(value) {
+    const bytes = uniffiTextEncoder.encode(value);
+    this.writeInt32(bytes.length);
+    this.writeBytes(bytes);
+  }
+}
+
+// Add `lift` and `lower` to a converter for a type that's passed across the FFI serialized
+// into a `RustBuffer`.
+function uniffiRustBufferConverter(converter) {
+  converter.lift = (bytes) => {
+    const reader = new UniffiReader(bytes);
+    const value = converter.read(reader);
+    if (reader.hasRemaining()) {
+      throw new UniffiInternalError("Junk data left in buffer after reading a value");
+    }
+    return value;
+  };
+  converter.lower = (value) => {
+    const writer = new UniffiWriter();
+    converter.write(value, writer);
+    return writer.toBytes();
+  };
+  return converter;
+}
+
+// Integers are checked before they're lowered, since the FFI layer would truncate them.
+function uniffiCheckInteger(value, min, max) {
+  const isInteger = typeof min === "bigint" ? typeof value === "bigint" : Number.isInteger(value);
+  if (!isInteger || value < min || value > max) {
+    throw new RangeError(`${value} is out of range for an integer between ${min} and ${max}`);
+  }
+  return value;
+}
+{%- if !ci.object_definitions().is_empty() %}
+
+// Objects hold a pointer to the Rust object, which is freed by `destroy()` or by a
+// `FinalizationRegistry` when the object is garbage collected.
+const uniffiPointer = Symbol("uniffiPointer");
+
+function uniffiInitObject(object, registry, pointer) {
+  object[uniffiPointer] = pointer;
+  registry.register(object, pointer, object);
+  return object;
+}
+
+// Each call into Rust consumes a reference, so methods pass a new clone of the pointer.
+function uniffiClonePointer(object, clone) {
+  const pointer = object[uniffiPointer];
+  if (pointer === undefined) {
+    throw new Error(`${object.constructor.name} object has already been destroyed or consumed`);
+  }
+  return uniffiRustCall(null, clone, pointer);
+}
+
+// Methods that take `self` by value pass the object's own pointer instead, so the object can't be
+// used afterwards.
+function uniffiTakePointer(object, registry) {
+  const pointer = object[uniffiPointer];
+  if (pointer === undefined) {
+    throw new Error(`${object.constructor.name} object has already been destroyed or consumed`);
+  }
+  object[uniffiPointer] = undefined;
+  registry.unregister(object);
+  return pointer;
+}
+
+// Rust fails with an unexpected error, before taking ownership of the pointer, when it can't
+// consume the object, for example because something else still holds a reference to it.  The
+// object is still usable then.
+function uniffiGiveBackPointer(object, registry, pointer, error) {
+  if (error instanceof UniffiInternalError && !(error instanceof UniffiRustPanic)) {
+    uniffiInitObject(object, registry, pointer);
+  }
+}
+
+function uniffiDestroyObject(object, registry, free) {
+  const pointer = object[uniffiPointer];
+  if (pointer !== undefined) {
+    object[uniffiPointer] = undefined;
+    registry.unregister(object);
+    uniffiRustCall(null, free, pointer);
+  }
+}
+{%- endif %}
+{%- if !ci.callback_interface_definitions().is_empty() %}
+
+// Callback interface implementations are kept here while Rust holds a handle to them.
+class UniffiHandleMap {
+  constructor() {
+    this.map = new Map();
+    this.nextHandle = 1n;
+  }
+
+  insert(value) {
+    const handle = this.nextHandle++;
+    this.map.set(handle, value);
+    return handle;
+  }
+
+  get(handle) {
+    const value = this.map.get(handle);
+    if (value === undefined) {
+      throw new UniffiInternalError(`Invalid callback handle: ${handle}`);
+    }
+    return value;
+  }
+
+  remove(handle) {
+    this.map.delete(handle);
+  }
+}
+
+// Run a callback method for Rust, reporting exceptions through the `{ code, errorBuf }` status.
+// `errorClass` and `errorConverter` are the error type that the method throws, if any.
+function uniffiCallCallback(status, errorClass, errorConverter, makeCall) {
+  try {
+    return makeCall();
+  } catch (e) {
+    if (errorClass !== null && e instanceof errorClass) {
+      status.code = UNIFFI_CALL_ERROR;
+      status.errorBuf = errorConverter.lower(e);
+    } else {
+      status.code = UNIFFI_CALL_UNEXPECTED_ERROR;
+      status.errorBuf = uniffiTextEncoder.encode(String(e));
+    }
+    return undefined;
+  }
+}
+{%- endif %}
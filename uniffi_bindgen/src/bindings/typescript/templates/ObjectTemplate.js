@@ -0,0 +1,88 @@
+{%- let type_name = obj.name()|class_name %}
+{%- let registry = format!("uniffiRegistry{}", type_name) %}
+{%- let clone_self = format!("{}.lower(this)", conv) %}
+// Frees the Rust object when a `{{ type_name }}` is garbage collected without being destroyed.
+const {{ registry }} = new FinalizationRegistry((pointer) =>
+  uniffiRustCall(null, lib.{{ obj.ffi_object_free().name() }}, pointer),
+);
+
+class {{ type_name }} {
+  {%- match obj.primary_constructor() %}
+  {%- when Some(cons) %}
+  constructor({% call js::arg_list_decl(cons) %}) {
+    uniffiInitObject(this, {{ registry }}, {% call js::to_ffi_call(cons, "") %});
+  }
+  {%- when None %}
+  constructor() {
+    throw new TypeError("{{ type_name }} has no primary constructor");
+  }
+  {%- endmatch %}
+  {%- for cons in obj.alternate_constructors() %}
+
+  static {{ cons.name()|method_name }}({% call js::arg_list_decl(cons) %}) {
+    return {{ conv }}.lift({% call js::to_ffi_call(cons, "") %});
+  }
+  {%- endfor %}
+
+  // Free the Rust object.  The object can't be used after it's destroyed.
+  destroy() {
+    uniffiDestroyObject(this, {{ registry }}, lib.{{ obj.ffi_object_free().name() }});
+  }
+  {%- for meth in obj.methods() %}
+
+  {% call js::async_kw(meth) %}{{ meth.name()|method_name }}({% call js::arg_list_decl(meth) %}) {
+    {%- if meth.takes_self_by_value() %}
+    const uniffiArgs = [{% call js::arg_list_lowered(meth, "") %}];
+    const uniffiPointer = uniffiTakePointer(this, {{ registry }});
+    let uniffiResult;
+    try {
+      uniffiResult = uniffiRustCall({% call js::lift_error(meth) %}, lib.{{ meth.ffi_func().name() }}, uniffiPointer, ...uniffiArgs);
+    } catch (e) {
+      uniffiGiveBackPointer(this, {{ registry }}, uniffiPointer, e);
+      throw e;
+    }
+    {%- match meth.return_type() %}
+    {%- when Some(return_type) %}
+    return {{ return_type|converter_name }}.lift(uniffiResult);
+    {%- when None %}
+    {%- endmatch %}
+    {%- else %}
+    {%- call js::call_body(meth, clone_self, "    ") %}
+    {%- endif %}
+  }
+  {%- endfor %}
+  {%- for tm in obj.uniffi_traits() %}
+  {%- match tm %}
+  {%- when UniffiTrait::Display { fmt } %}
+
+  toString() {
+    {%- call js::call_body(fmt, clone_self, "    ") %}
+  }
+  {%- when UniffiTrait::Debug { fmt } %}
+
+  toDebugString() {
+    {%- call js::call_body(fmt, clone_self, "    ") %}
+  }
+  {%- when UniffiTrait::Eq { eq, ne } %}
+
+  equals(other) {
+    if (!(other instanceof {{ type_name }})) {
+      return false;
+    }
+    {%- call js::call_body(eq, clone_self, "    ") %}
+  }
+  {%- when UniffiTrait::Hash { hash } %}
+
+  hashCode() {
+    {%- call js::call_body(hash, clone_self, "    ") %}
+  }
+  {%- endmatch %}
+  {%- endfor %}
+}
+
+const {{ conv }} = {
+  lift: (pointer) => uniffiInitObject(Object.create({{ type_name }}.prototype), {{ registry }}, pointer),
+  lower: (value) => uniffiClonePointer(value, lib.{{ obj.ffi_object_clone().name() }}),
+  read: (reader) => {{ conv }}.lift(reader.readBigUint64()),
+  write: (value, writer) => writer.writeBigUint64({{ conv }}.lower(value)),
+};
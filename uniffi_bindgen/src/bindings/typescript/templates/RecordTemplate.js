@@ -0,0 +1,21 @@
+{#
+// Records are plain objects.  Fields with a default value can be left out.
+#}
+const {{ conv }} = uniffiRustBufferConverter({
+  read: (reader) => ({
+    {%- for field in rec.fields() %}
+    {{ field|field_name(loop.index0) }}: {{ field|converter_name }}.read(reader),
+    {%- endfor %}
+  }),
+  write: (value, writer) => {
+    {%- for field in rec.fields() %}
+    {%- let name = field|field_name(loop.index0) %}
+    {%- match self.field_default(field) %}
+    {%- when Some(default) %}
+    {{ field|converter_name }}.write(value.{{ name }} === undefined ? {{ default }} : value.{{ name }}, writer);
+    {%- when None %}
+    {{ field|converter_name }}.write(value.{{ name }}, writer);
+    {%- endmatch %}
+    {%- endfor %}
+  },
+});
@@ -0,0 +1,172 @@
+{#
+// Each type has an `FfiConverter{canonical name}` object, with functions to lift and lower it,
+// and to read and write it from a buffer.  Records, enums, errors, objects and callback
+// interfaces are defined alongside their converter.
+#}
+{%- for type_ in ci.iter_types() %}
+{%- let conv = type_|converter_name %}
+{%- match type_ %}
+{%- when Type::Boolean %}
+
+const {{ conv }} = {
+  lift: (value) => value !== 0,
+  lower: (value) => (value ? 1 : 0),
+  read: (reader) => reader.readInt8() !== 0,
+  write: (value, writer) => writer.writeInt8(value ? 1 : 0),
+};
+{%- when Type::String %}
+
+// Strings are lowered as the raw UTF-8 bytes, rather than serialized
+const {{ conv }} = {
+  lift: (value) => uniffiTextDecoder.decode(value),
+  lower: (value) => uniffiTextEncoder.encode(value),
+  read: (reader) => reader.readString(),
+  write: (value, writer) => writer.writeString(value),
+};
+{%- when Type::Bytes %}
+
+const {{ conv }} = uniffiRustBufferConverter({
+  read: (reader) => reader.readBytes(reader.readInt32()),
+  write: (value, writer) => {
+    writer.writeInt32(value.length);
+    writer.writeBytes(value);
+  },
+});
+{%- when Type::Timestamp %}
+
+// Timestamps are the signed seconds since the epoch, followed by the nanoseconds in the same
+// direction.  `Date` has a resolution of a millisecond.
+const {{ conv }} = uniffiRustBufferConverter({
+  read: (reader) => {
+    const seconds = reader.readBigInt64();
+    const millis = Math.floor(reader.readUint32() / 1e6);
+    return new Date(Number(seconds) * 1000 + (seconds < 0n ? -millis : millis));
+  },
+  write: (value, writer) => {
+    const time = value.getTime();
+    const millis = Math.abs(time) % 1000;
+    const seconds = (Math.abs(time) - millis) / 1000;
+    writer.writeBigInt64(BigInt(time < 0 ? -seconds : seconds));
+    writer.writeUint32(millis * 1e6);
+  },
+});
+{%- when Type::Duration %}
+
+// Durations are a number of milliseconds, like the differences between `Date`s.
+const {{ conv }} = uniffiRustBufferConverter({
+  read: (reader) => {
+    const seconds = reader.readBigUint64();
+    return Number(seconds) * 1000 + reader.readUint32() / 1e6;
+  },
+  write: (value, writer) => {
+    if (!(value >= 0)) {
+      throw new RangeError(`Durations must be non-negative, got ${value}`);
+    }
+    const seconds = Math.floor(value / 1000);
+    writer.writeBigUint64(BigInt(seconds));
+    writer.writeUint32(Math.min(Math.round((value - seconds * 1000) * 1e6), 999999999));
+  },
+});
+{%- when Type::Optional { inner_type } %}
+{%- let inner_conv = inner_type|converter_name %}
+
+const {{ conv }} = uniffiRustBufferConverter({
+  read: (reader) => (reader.readInt8() === 0 ? undefined : {{ inner_conv }}.read(reader)),
+  write: (value, writer) => {
+    if (value === undefined || value === null) {
+      writer.writeInt8(0);
+    } else {
+      writer.writeInt8(1);
+      {{ inner_conv }}.write(value, writer);
+    }
+  },
+});
+{%- when Type::Sequence { inner_type } %}
+{%- let inner_conv = inner_type|converter_name %}
+
+const {{ conv }} = uniffiRustBufferConverter({
+  read: (reader) => {
+    const length = reader.readInt32();
+    const result = [];
+    for (let i = 0; i < length; i++) {
+      result.push({{ inner_conv }}.read(reader));
+    }
+    return result;
+  },
+  write: (value, writer) => {
+    writer.writeInt32(value.length);
+    for (const item of value) {
+      {{ inner_conv }}.write(item, writer);
+    }
+  },
+});
+{%- when Type::Map { key_type, value_type } %}
+{%- let key_conv = key_type|converter_name %}
+{%- let value_conv = value_type|converter_name %}
+
+const {{ conv }} = uniffiRustBufferConverter({
+  read: (reader) => {
+    const length = reader.readInt32();
+    const result = new Map();
+    for (let i = 0; i < length; i++) {
+      const key = {{ key_conv }}.read(reader);
+      result.set(key, {{ value_conv }}.read(reader));
+    }
+    return result;
+  },
+  write: (value, writer) => {
+    writer.writeInt32(value.size);
+    for (const [key, item] of value) {
+      {{ key_conv }}.write(key, writer);
+      {{ value_conv }}.write(item, writer);
+    }
+  },
+});
+{%- when Type::Custom { name, builtin, module_path } %}
+
+// `{{ name }}` is a custom type, which is passed to Rust as its builtin type.
+const {{ conv }} = {{ builtin|converter_name }};
+{%- when Type::Record { name, module_path } %}
+{%- let rec = ci.get_record_definition(name).unwrap() %}
+{% include "RecordTemplate.js" %}
+{%- when Type::Enum { name, module_path } %}
+{%- let e = ci.get_enum_definition(name).unwrap() %}
+{%- if ci.is_name_used_as_error(name) %}
+{% include "ErrorTemplate.js" %}
+{%- else %}
+{% include "EnumTemplate.js" %}
+{%- endif %}
+{%- when Type::Object { name, module_path, imp } %}
+{%- let obj = ci.get_object_definition(name).unwrap() %}
+{% include "ObjectTemplate.js" %}
+{%- when Type::CallbackInterface { name, module_path } %}
+{%- let cbi = ci.get_callback_interface_definition(name).unwrap() %}
+{% include "CallbackInterfaceTemplate.js" %}
+{%- when Type::Float32 %}
+
+const {{ conv }} = {
+  lift: (value) => value,
+  lower: (value) => value,
+  read: (reader) => reader.readFloat32(),
+  write: (value, writer) => writer.writeFloat32(value),
+};
+{%- when Type::Float64 %}
+
+const {{ conv }} = {
+  lift: (value) => value,
+  lower: (value) => value,
+  read: (reader) => reader.readFloat64(),
+  write: (value, writer) => writer.writeFloat64(value),
+};
+{%- else %}
+{#- Integers #}
+{%- let method = type_|buffer_method %}
+
+const {{ conv }} = {
+  lift: (value) => value,
+  lower: (value) => uniffiCheckInteger(value, {{ type_|int_range }}),
+  read: (reader) => reader.read{{ method }}(),
+  write: (value, writer) => writer.write{{ method }}(uniffiCheckInteger(value, {{ type_|int_range }})),
+};
+{%- endmatch %}
+{%- endfor %}
@@ -0,0 +1,31 @@
+{#
+// The parameters of a function, with the trailing parameters that have defaults optional.
+#}
+{%- macro param_list(func) %}
+    {%- for arg in func.arguments() -%}
+        {{ arg.name()|var_name }}
+        {%- match self.arg_default(func.arguments(), loop.index0) %}
+        {%- when Some(default) %}?
+        {%- when None %}
+        {%- endmatch %}: {{ arg|type_name }}
+        {%- if !loop.last %}, {% endif -%}
+    {%- endfor %}
+{%- endmacro %}
+
+{%- macro return_type(func) %}
+    {%- if func.is_async() %}Promise<{% match func.return_type() %}{% when Some(return_type) %}{{ return_type|type_name }}{% when None %}void{% endmatch %}>
+    {%- else %}{% match func.return_type() %}{% when Some(return_type) %}{{ return_type|type_name }}{% when None %}void{% endmatch %}
+    {%- endif %}
+{%- endmacro %}
+
+{%- macro docstring_value(maybe_docstring, indent_spaces) %}
+{%- match maybe_docstring %}
+{%- when Some(docstring) %}
+{{ docstring|docstring(indent_spaces) }}
+{%- else %}
+{%- endmatch %}
+{%- endmacro %}
+
+{%- macro docstring(defn, indent_spaces) %}
+{%- call docstring_value(defn.docstring(), indent_spaces) %}
+{%- endmacro %}
@@ -0,0 +1,71 @@
+{#
+// Template to call into rust. Used in several places.
+// Variable names in `arg_list_decl` should match up with arg lists
+// passed to rust via `arg_list_lowered`
+#}
+
+{%- macro arg_list_decl(func) %}
+    {%- for arg in func.arguments() -%}
+        {{ arg.name()|var_name }}
+        {%- match self.arg_default(func.arguments(), loop.index0) %}
+        {%- when Some(default) %} = {{ default }}
+        {%- when None %}
+        {%- endmatch %}
+        {%- if !loop.last %}, {% endif -%}
+    {%- endfor %}
+{%- endmacro %}
+
+{#
+// The lowered arguments of a call, preceded by the object pointer for methods.
+// `self_ptr` is the expression for the object pointer, or empty for functions and constructors.
+#}
+{%- macro arg_list_lowered(func, self_ptr) %}
+    {%- if !self_ptr.is_empty() %}{{ self_ptr }}{% if !func.arguments().is_empty() %}, {% endif %}{% endif %}
+    {%- for arg in func.arguments() -%}
+        {{ arg|converter_name }}.lower({{ arg.name()|var_name }})
+        {%- if !loop.last %}, {% endif -%}
+    {%- endfor %}
+{%- endmacro %}
+
+{%- macro lift_error(func) %}
+    {%- match func.throws_type() %}
+    {%- when Some(e) %}{{ e|converter_name }}.lift
+    {%- when None %}null
+    {%- endmatch %}
+{%- endmacro %}
+
+{#
+// A synchronous call into Rust, which evaluates to the lowered return value.
+#}
+{%- macro to_ffi_call(func, self_ptr) %}
+    {%- if !self_ptr.is_empty() || !func.arguments().is_empty() -%}
+    uniffiRustCall({% call lift_error(func) %}, lib.{{ func.ffi_func().name() }}, {% call arg_list_lowered(func, self_ptr) %})
+    {%- else -%}
+    uniffiRustCall({% call lift_error(func) %}, lib.{{ func.ffi_func().name() }})
+    {%- endif %}
+{%- endmacro %}
+
+{#
+// The body of a function or method: call into Rust and lift the return value.
+#}
+{%- macro call_body(func, self_ptr, indent) %}
+{%- if func.is_async() %}
+{{ indent }}return uniffiRustCallAsync(
+{{ indent }}  lib.{{ func.ffi_func().name() }}({% call arg_list_lowered(func, self_ptr) %}),
+{{ indent }}  lib.{{ func.ffi_rust_future_poll(ci) }},
+{{ indent }}  lib.{{ func.ffi_rust_future_complete(ci) }},
+{{ indent }}  lib.{{ func.ffi_rust_future_free(ci) }},
+{{ indent }}  {% match func.return_type() %}{% when Some(return_type) %}{{ return_type|converter_name }}.lift{% when None %}null{% endmatch %},
+{{ indent }}  {% call lift_error(func) %},
+{{ indent }});
+{%- else %}
+{%- match func.return_type() %}
+{%- when Some(return_type) %}
+{{ indent }}return {{ return_type|converter_name }}.lift({% call to_ffi_call(func, self_ptr) %});
+{%- when None %}
+{{ indent }}{% call to_ffi_call(func, self_ptr) %};
+{%- endmatch %}
+{%- endif %}
+{%- endmacro %}
+
+{%- macro async_kw(func) %}{% if func.is_async() %}async {% endif %}{% endmacro %}
@@ -0,0 +1,629 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+/**
+ * N-API addon for the `{{ ci.namespace() }}` component.
+ *
+ * This exports the scaffolding functions of the `{{ config.cdylib_name() }}` library to the
+ * generated `{{ config.module_name(ci) }}.js` module.  Build it as `{{ config.addon_name(ci) }}.node`, next to
+ * the module, with the Node.js headers and linked against the library, for example:
+ *
+ *     cc -shared -fPIC -I<node>/include/node -o {{ config.addon_name(ci) }}.node {{ config.addon_name(ci) }}.c -l{{ config.cdylib_name() }}
+ *
+ * Values are converted between their C and JavaScript types here, but otherwise passed through
+ * unchanged: integers up to 32 bits and floats are numbers, 64-bit integers and pointers are
+ * bigints, and `RustBuffer`s are `Uint8Array`s.  Functions that take a `RustCallStatus` take a
+ * `{ code, errorBuf }` object as their last argument, which is updated after the call.
+ */
+#define NAPI_VERSION 8
+#include <node_api.h>
+
+#include <pthread.h>
+#include <stdbool.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "{{ header_filename }}"
+
+// The environment of the thread that loaded the addon, which callbacks are run on.
+static napi_env uniffi_napi_env;
+static pthread_t uniffi_napi_main_thread;
+
+/**
+ * Throw an error for a failed N-API call, unless there's already an exception pending.
+ */
+static void uniffi_napi_throw_last_error(napi_env env) {
+    bool pending = false;
+    napi_is_exception_pending(env, &pending);
+    if (pending) {
+        return;
+    }
+    const napi_extended_error_info* info = NULL;
+    napi_get_last_error_info(env, &info);
+    napi_throw_error(env, NULL, info != NULL && info->error_message != NULL ? info->error_message : "N-API call failed");
+}
+
+// Conversions from JavaScript values.  These throw and return false on failure.
+
+static inline bool uniffi_napi_get_int32(napi_env env, napi_value value, int32_t* out) {
+    if (napi_get_value_int32(env, value, out) != napi_ok) {
+        uniffi_napi_throw_last_error(env);
+        return false;
+    }
+    return true;
+}
+
+static inline bool uniffi_napi_get_uint32(napi_env env, napi_value value, uint32_t* out) {
+    if (napi_get_value_uint32(env, value, out) != napi_ok) {
+        uniffi_napi_throw_last_error(env);
+        return false;
+    }
+    return true;
+}
+
+static inline bool uniffi_napi_get_int8(napi_env env, napi_value value, int8_t* out) {
+    int32_t v;
+    if (!uniffi_napi_get_int32(env, value, &v)) {
+        return false;
+    }
+    *out = (int8_t)v;
+    return true;
+}
+
+static inline bool uniffi_napi_get_uint8(napi_env env, napi_value value, uint8_t* out) {
+    uint32_t v;
+    if (!uniffi_napi_get_uint32(env, value, &v)) {
+        return false;
+    }
+    *out = (uint8_t)v;
+    return true;
+}
+
+static inline bool uniffi_napi_get_int16(napi_env env, napi_value value, int16_t* out) {
+    int32_t v;
+    if (!uniffi_napi_get_int32(env, value, &v)) {
+        return false;
+    }
+    *out = (int16_t)v;
+    return true;
+}
+
+static inline bool uniffi_napi_get_uint16(napi_env env, napi_value value, uint16_t* out) {
+    uint32_t v;
+    if (!uniffi_napi_get_uint32(env, value, &v)) {
+        return false;
+    }
+    *out = (uint16_t)v;
+    return true;
+}
+
+static inline bool uniffi_napi_get_int64(napi_env env, napi_value value, int64_t* out) {
+    bool lossless;
+    if (napi_get_value_bigint_int64(env, value, out, &lossless) != napi_ok) {
+        uniffi_napi_throw_last_error(env);
+        return false;
+    }
+    return true;
+}
+
+static inline bool uniffi_napi_get_uint64(napi_env env, napi_value value, uint64_t* out) {
+    bool lossless;
+    if (napi_get_value_bigint_uint64(env, value, out, &lossless) != napi_ok) {
+        uniffi_napi_throw_last_error(env);
+        return false;
+    }
+    return true;
+}
+
+static inline bool uniffi_napi_get_float(napi_env env, napi_value value, float* out) {
+    double v;
+    if (napi_get_value_double(env, value, &v) != napi_ok) {
+        uniffi_napi_throw_last_error(env);
+        return false;
+    }
+    *out = (float)v;
+    return true;
+}
+
+static inline bool uniffi_napi_get_double(napi_env env, napi_value value, double* out) {
+    if (napi_get_value_double(env, value, out) != napi_ok) {
+        uniffi_napi_throw_last_error(env);
+        return false;
+    }
+    return true;
+}
+
+static inline bool uniffi_napi_get_pointer(napi_env env, napi_value value, void** out) {
+    uint64_t v;
+    if (!uniffi_napi_get_uint64(env, value, &v)) {
+        return false;
+    }
+    *out = (void*)(uintptr_t)v;
+    return true;
+}
+
+/**
+ * Copy a `Uint8Array` into a new `RustBuffer`, which the callee takes ownership of.
+ */
+static inline bool uniffi_napi_get_rust_buffer(napi_env env, napi_value value, RustBuffer* out) {
+    napi_typedarray_type type;
+    size_t length;
+    void* data;
+    if (napi_get_typedarray_info(env, value, &type, &length, &data, NULL, NULL) != napi_ok || type != napi_uint8_array) {
+        napi_throw_type_error(env, NULL, "Expected a Uint8Array");
+        return false;
+    }
+    ForeignBytes bytes = { (int32_t)length, (const uint8_t*)data };
+    RustCallStatus status = { 0 };
+    *out = {{ ci.ffi_rustbuffer_from_bytes().name() }}(bytes, &status);
+    if (status.code != UNIFFI_CALL_SUCCESS) {
+        napi_throw_error(env, NULL, "Failed to allocate a RustBuffer");
+        return false;
+    }
+    return true;
+}
+
+// Conversions to JavaScript values.  These return NULL, with an exception pending, on failure.
+
+static inline napi_value uniffi_napi_new_int32(napi_env env, int32_t value) {
+    napi_value result = NULL;
+    napi_create_int32(env, value, &result);
+    return result;
+}
+
+static inline napi_value uniffi_napi_new_uint32(napi_env env, uint32_t value) {
+    napi_value result = NULL;
+    napi_create_uint32(env, value, &result);
+    return result;
+}
+
+static inline napi_value uniffi_napi_new_int8(napi_env env, int8_t value) {
+    return uniffi_napi_new_int32(env, value);
+}
+
+static inline napi_value uniffi_napi_new_uint8(napi_env env, uint8_t value) {
+    return uniffi_napi_new_uint32(env, value);
+}
+
+static inline napi_value uniffi_napi_new_int16(napi_env env, int16_t value) {
+    return uniffi_napi_new_int32(env, value);
+}
+
+static inline napi_value uniffi_napi_new_uint16(napi_env env, uint16_t value) {
+    return uniffi_napi_new_uint32(env, value);
+}
+
+static inline napi_value uniffi_napi_new_int64(napi_env env, int64_t value) {
+    napi_value result = NULL;
+    napi_create_bigint_int64(env, value, &result);
+    return result;
+}
+
+static inline napi_value uniffi_napi_new_uint64(napi_env env, uint64_t value) {
+    napi_value result = NULL;
+    napi_create_bigint_uint64(env, value, &result);
+    return result;
+}
+
+static inline napi_value uniffi_napi_new_double(napi_env env, double value) {
+    napi_value result = NULL;
+    napi_create_double(env, value, &result);
+    return result;
+}
+
+static inline napi_value uniffi_napi_new_float(napi_env env, float value) {
+    return uniffi_napi_new_double(env, value);
+}
+
+static inline napi_value uniffi_napi_new_pointer(napi_env env, void* value) {
+    return uniffi_napi_new_uint64(env, (uint64_t)(uintptr_t)value);
+}
+
+/**
+ * Copy a `RustBuffer` into a new `Uint8Array`, then free it.
+ */
+static inline napi_value uniffi_napi_new_rust_buffer(napi_env env, RustBuffer value) {
+    napi_value result = NULL;
+    void* copy = NULL;
+    napi_create_buffer_copy(env, (size_t)value.len, value.data, &copy, &result);
+    RustCallStatus status = { 0 };
+    {{ ci.ffi_rustbuffer_free().name() }}(value, &status);
+    return result;
+}
+
+static napi_value uniffi_napi_undefined(napi_env env) {
+    napi_value result = NULL;
+    napi_get_undefined(env, &result);
+    return result;
+}
+
+/**
+ * Copy a `RustCallStatus` into the `{ code, errorBuf }` object passed by JavaScript.
+ */
+static inline bool uniffi_napi_set_call_status(napi_env env, napi_value object, RustCallStatus* status) {
+    if (napi_set_named_property(env, object, "code", uniffi_napi_new_int32(env, status->code)) != napi_ok) {
+        uniffi_napi_throw_last_error(env);
+        return false;
+    }
+    if (status->code != UNIFFI_CALL_SUCCESS) {
+        napi_value error_buf = uniffi_napi_new_rust_buffer(env, status->errorBuf);
+        if (error_buf == NULL || napi_set_named_property(env, object, "errorBuf", error_buf) != napi_ok) {
+            uniffi_napi_throw_last_error(env);
+            return false;
+        }
+    }
+    return true;
+}
+
+/**
+ * Get the arguments of a call from JavaScript, throwing if there are the wrong number.
+ */
+static inline bool uniffi_napi_get_args(napi_env env, napi_callback_info info, size_t expected, napi_value* argv, const char* name) {
+    size_t argc = expected;
+    if (napi_get_cb_info(env, info, &argc, argv, NULL, NULL) != napi_ok) {
+        uniffi_napi_throw_last_error(env);
+        return false;
+    }
+    if (argc != expected) {
+        char message[256];
+        snprintf(message, sizeof(message), "Expected %zu arguments to `%s`, got %zu", expected, name, argc);
+        napi_throw_type_error(env, NULL, message);
+        return false;
+    }
+    return true;
+}
+{%- for func in self.plain_functions() %}
+{%- let args = func.arguments() %}
+{%- let argc = self.js_argc(func) %}
+
+static napi_value uniffi_napi_{{ func.name() }}(napi_env env, napi_callback_info info) {
+    napi_value argv[{{ argc.max(1) }}];
+    if (!uniffi_napi_get_args(env, info, {{ argc }}, argv, "{{ func.name() }}")) {
+        return NULL;
+    }
+    {%- for arg in args.iter() %}
+    {{ arg.type_().borrow()|c_type_name }} {{ arg.name() }};
+    if (!uniffi_napi_get_{{ arg.type_().borrow()|napi_kind }}(env, argv[{{ loop.index0 }}], &{{ arg.name() }})) {
+        return NULL;
+    }
+    {%- endfor %}
+    {%- if func.has_rust_call_status_arg() %}
+    RustCallStatus status = { 0 };
+    {%- endif %}
+    {% match func.return_type() %}{% when Some(return_type) %}{{ return_type|c_type_name }} result = {% when None %}{% endmatch -%}
+    {{ func.name() }}(
+        {%- for arg in args.iter() %}{{ arg.name() }}{% if !loop.last %}, {% endif %}{% endfor %}
+        {%- if func.has_rust_call_status_arg() %}{% if !args.is_empty() %}, {% endif %}&status{% endif %});
+    {%- if func.has_rust_call_status_arg() %}
+    if (!uniffi_napi_set_call_status(env, argv[{{ args.len() }}], &status)) {
+        return NULL;
+    }
+    {%- match func.return_type() %}
+    {%- when Some(return_type) %}
+    if (status.code != UNIFFI_CALL_SUCCESS) {
+        return uniffi_napi_undefined(env);
+    }
+    {%- when None %}
+    {%- endmatch %}
+    {%- endif %}
+    {%- match func.return_type() %}
+    {%- when Some(return_type) %}
+    return uniffi_napi_new_{{ return_type|napi_kind }}(env, result);
+    {%- when None %}
+    return uniffi_napi_undefined(env);
+    {%- endmatch %}
+}
+{%- endfor %}
+{%- if ci.has_async_fns() %}
+
+// Rust futures wake their continuation from any thread, so it's passed to JavaScript through a
+// thread-safe function.  The function keeps the event loop alive while there are polls waiting
+// for their continuation.
+static napi_threadsafe_function uniffi_napi_continuation_tsfn;
+static size_t uniffi_napi_pending_polls;
+
+typedef struct {
+    uint64_t data;
+    int8_t poll_result;
+} UniffiNapiContinuation;
+
+static void uniffi_napi_continuation_call_js(napi_env env, napi_value js_callback, void* context, void* data) {
+    UniffiNapiContinuation* continuation = data;
+    if (env != NULL) {
+        if (--uniffi_napi_pending_polls == 0) {
+            napi_unref_threadsafe_function(env, uniffi_napi_continuation_tsfn);
+        }
+        napi_value argv[2] = {
+            uniffi_napi_new_double(env, (double)continuation->data),
+            uniffi_napi_new_int32(env, continuation->poll_result),
+        };
+        napi_call_function(env, uniffi_napi_undefined(env), js_callback, 2, argv, NULL);
+    }
+    free(continuation);
+}
+
+static void uniffi_napi_future_continuation(uint64_t data, int8_t poll_result) {
+    UniffiNapiContinuation* continuation = malloc(sizeof(UniffiNapiContinuation));
+    continuation->data = data;
+    continuation->poll_result = poll_result;
+    napi_call_threadsafe_function(uniffi_napi_continuation_tsfn, continuation, napi_tsfn_nonblocking);
+}
+
+/**
+ * Set the JavaScript function that's called with `(data, pollResult)` when a future is woken.
+ */
+static napi_value uniffi_napi_set_future_continuation(napi_env env, napi_callback_info info) {
+    napi_value argv[1];
+    if (!uniffi_napi_get_args(env, info, 1, argv, "uniffi_set_future_continuation")) {
+        return NULL;
+    }
+    napi_value name;
+    napi_create_string_utf8(env, "uniffiFutureContinuation", NAPI_AUTO_LENGTH, &name);
+    if (napi_create_threadsafe_function(env, argv[0], NULL, name, 0, 1, NULL, NULL, NULL, uniffi_napi_continuation_call_js, &uniffi_napi_continuation_tsfn) != napi_ok) {
+        uniffi_napi_throw_last_error(env);
+        return NULL;
+    }
+    napi_unref_threadsafe_function(env, uniffi_napi_continuation_tsfn);
+    return uniffi_napi_undefined(env);
+}
+{%- for func in self.poll_functions() %}
+
+static napi_value uniffi_napi_{{ func.name() }}(napi_env env, napi_callback_info info) {
+    napi_value argv[2];
+    if (!uniffi_napi_get_args(env, info, 2, argv, "{{ func.name() }}")) {
+        return NULL;
+    }
+    uint64_t handle;
+    double data;
+    if (!uniffi_napi_get_uint64(env, argv[0], &handle) || !uniffi_napi_get_double(env, argv[1], &data)) {
+        return NULL;
+    }
+    if (uniffi_napi_pending_polls++ == 0) {
+        napi_ref_threadsafe_function(env, uniffi_napi_continuation_tsfn);
+    }
+    {{ func.name() }}(handle, uniffi_napi_future_continuation, (uint64_t)data);
+    return uniffi_napi_undefined(env);
+}
+{%- endfor %}
+{%- endif %}
+{%- if !ci.callback_interface_definitions().is_empty() %}
+
+/**
+ * A call from Rust to a JavaScript callback.
+ *
+ * Calls on the main thread are run straight away.  Calls from other threads are passed to the
+ * main thread through a thread-safe function, and the calling thread waits for them to finish,
+ * so they can only run while the main thread is idle or awaiting a promise.
+ */
+typedef struct UniffiNapiCall {
+    void (*invoke)(napi_env env, struct UniffiNapiCall* call);
+    RustCallStatus* status;
+    pthread_mutex_t mutex;
+    pthread_cond_t cond;
+    bool done;
+} UniffiNapiCall;
+
+static napi_threadsafe_function uniffi_napi_callback_tsfn;
+
+static void uniffi_napi_callback_call_js(napi_env env, napi_value js_callback, void* context, void* data) {
+    UniffiNapiCall* call = data;
+    if (env != NULL) {
+        call->invoke(env, call);
+    } else {
+        call->status->code = UNIFFI_CALL_UNEXPECTED_ERROR;
+    }
+    pthread_mutex_lock(&call->mutex);
+    call->done = true;
+    pthread_cond_signal(&call->cond);
+    pthread_mutex_unlock(&call->mutex);
+}
+
+static void uniffi_napi_call_js(UniffiNapiCall* call) {
+    if (pthread_equal(pthread_self(), uniffi_napi_main_thread)) {
+        napi_handle_scope scope;
+        napi_open_handle_scope(uniffi_napi_env, &scope);
+        call->invoke(uniffi_napi_env, call);
+        napi_close_handle_scope(uniffi_napi_env, scope);
+        return;
+    }
+    pthread_mutex_init(&call->mutex, NULL);
+    pthread_cond_init(&call->cond, NULL);
+    call->done = false;
+    if (napi_call_threadsafe_function(uniffi_napi_callback_tsfn, call, napi_tsfn_blocking) != napi_ok) {
+        call->status->code = UNIFFI_CALL_UNEXPECTED_ERROR;
+    } else {
+        pthread_mutex_lock(&call->mutex);
+        while (!call->done) {
+            pthread_cond_wait(&call->cond, &call->mutex);
+        }
+        pthread_mutex_unlock(&call->mutex);
+    }
+    pthread_cond_destroy(&call->cond);
+    pthread_mutex_destroy(&call->mutex);
+}
+
+/**
+ * Call a JavaScript vtable method, with a `{ code, errorBuf }` object as its last argument.
+ *
+ * Returns the result, or NULL if the method set an error code or threw an exception.  Errors
+ * are copied into `status`.
+ */
+static napi_value uniffi_napi_call_vtable_method(napi_env env, napi_ref method, size_t argc, napi_value* argv, RustCallStatus* status) {
+    napi_value function, status_object, result;
+    bool pending = false;
+    if (napi_get_reference_value(env, method, &function) != napi_ok
+        || napi_create_object(env, &status_object) != napi_ok
+        || napi_set_named_property(env, status_object, "code", uniffi_napi_new_int32(env, UNIFFI_CALL_SUCCESS)) != napi_ok) {
+        status->code = UNIFFI_CALL_UNEXPECTED_ERROR;
+        return NULL;
+    }
+    argv[argc - 1] = status_object;
+    if (napi_call_function(env, uniffi_napi_undefined(env), function, argc, argv, &result) != napi_ok) {
+        // The generated methods catch exceptions, so this is a bug in the bindings.
+        napi_value exception;
+        napi_is_exception_pending(env, &pending);
+        if (pending) {
+            napi_get_and_clear_last_exception(env, &exception);
+        }
+        status->code = UNIFFI_CALL_UNEXPECTED_ERROR;
+        return NULL;
+    }
+    napi_value code_value, error_buf;
+    int32_t code = UNIFFI_CALL_SUCCESS;
+    napi_get_named_property(env, status_object, "code", &code_value);
+    napi_get_value_int32(env, code_value, &code);
+    if (code != UNIFFI_CALL_SUCCESS) {
+        status->code = (int8_t)code;
+        napi_get_named_property(env, status_object, "errorBuf", &error_buf);
+        if (!uniffi_napi_get_rust_buffer(env, error_buf, &status->errorBuf)) {
+            napi_value exception;
+            napi_get_and_clear_last_exception(env, &exception);
+            status->code = UNIFFI_CALL_UNEXPECTED_ERROR;
+        }
+        return NULL;
+    }
+    return result;
+}
+
+/**
+ * Get a method of a JavaScript vtable, and keep a reference to it.
+ */
+static inline bool uniffi_napi_get_vtable_method(napi_env env, napi_value vtable, const char* name, napi_ref* out) {
+    napi_value method;
+    napi_valuetype type;
+    if (napi_get_named_property(env, vtable, name, &method) != napi_ok || napi_typeof(env, method, &type) != napi_ok) {
+        uniffi_napi_throw_last_error(env);
+        return false;
+    }
+    if (type != napi_function) {
+        char message[256];
+        snprintf(message, sizeof(message), "Missing vtable method `%s`", name);
+        napi_throw_type_error(env, NULL, message);
+        return false;
+    }
+    return napi_create_reference(env, method, 1, out) == napi_ok;
+}
+{%- for cbi in ci.callback_interface_definitions() %}
+{%- let vtable = cbi.vtable_definition() %}
+{%- let methods = cbi.vtable_methods() %}
+
+// `{{ cbi.name() }}` callback interface
+
+// References to the JavaScript vtable methods, followed by `uniffi_free`.
+static napi_ref uniffi_napi_js_{{ vtable.name() }}[{{ methods.len() + 1 }}];
+{%- for (callback, meth) in methods.iter() %}
+{%- let call_type = callback.name()|ffi_callback_name %}
+
+typedef struct {
+    UniffiNapiCall call;
+    {%- for arg in callback.arguments() %}
+    {{ arg.type_().borrow()|c_type_name }} {{ arg.name() }};
+    {%- endfor %}
+} {{ call_type }}Call;
+
+static void uniffi_napi_invoke_{{ callback.name() }}(napi_env env, UniffiNapiCall* uniffi_call) {
+    {{ call_type }}Call* call = ({{ call_type }}Call*)uniffi_call;
+    {%- let js_args = callback.arguments().len() %}
+    napi_value argv[{{ js_args }}];
+    {%- for arg in callback.arguments() %}
+    {%- if !loop.last %}
+    argv[{{ loop.index0 }}] = uniffi_napi_new_{{ arg.type_().borrow()|napi_kind }}(env, call->{{ arg.name() }});
+    {%- endif %}
+    {%- endfor %}
+    napi_value result = uniffi_napi_call_vtable_method(env, uniffi_napi_js_{{ vtable.name() }}[{{ loop.index0 }}], {{ js_args }}, argv, uniffi_call->status);
+    {%- match meth.return_type() %}
+    {%- when Some(return_type) %}
+    if (result != NULL && !uniffi_napi_get_{{ return_type|lowered_napi_kind }}(env, result, call->uniffi_out_return)) {
+        napi_value exception;
+        napi_get_and_clear_last_exception(env, &exception);
+        uniffi_call->status->code = UNIFFI_CALL_UNEXPECTED_ERROR;
+    }
+    {%- when None %}
+    (void)result;
+    {%- endmatch %}
+}
+
+static void uniffi_napi_{{ callback.name() }}(
+    {%- for arg in callback.arguments() %}{{ arg.type_().borrow()|c_type_name }} {{ arg.name() }}, {% endfor -%}
+    RustCallStatus* uniffi_call_status) {
+    {{ call_type }}Call call = { .call = { .invoke = uniffi_napi_invoke_{{ callback.name() }}, .status = uniffi_call_status } };
+    {%- for arg in callback.arguments() %}
+    call.{{ arg.name() }} = {{ arg.name() }};
+    {%- endfor %}
+    uniffi_napi_call_js(&call.call);
+}
+{%- endfor %}
+
+typedef struct {
+    UniffiNapiCall call;
+    uint64_t handle;
+} UniffiNapiFree{{ vtable.name() }}Call;
+
+static void uniffi_napi_invoke_free_{{ vtable.name() }}(napi_env env, UniffiNapiCall* uniffi_call) {
+    UniffiNapiFree{{ vtable.name() }}Call* call = (UniffiNapiFree{{ vtable.name() }}Call*)uniffi_call;
+    napi_value argv[2] = { uniffi_napi_new_uint64(env, call->handle), NULL };
+    uniffi_napi_call_vtable_method(env, uniffi_napi_js_{{ vtable.name() }}[{{ methods.len() }}], 2, argv, uniffi_call->status);
+}
+
+static void uniffi_napi_free_{{ vtable.name() }}(uint64_t handle) {
+    RustCallStatus status = { 0 };
+    UniffiNapiFree{{ vtable.name() }}Call call = { .call = { .invoke = uniffi_napi_invoke_free_{{ vtable.name() }}, .status = &status }, .handle = handle };
+    uniffi_napi_call_js(&call.call);
+}
+
+// Rust keeps a pointer to the vtable, so it's static.
+static {{ vtable.name()|ffi_struct_name }} uniffi_napi_{{ vtable.name() }} = {
+    {%- for (callback, meth) in methods.iter() %}
+    uniffi_napi_{{ callback.name() }},
+    {%- endfor %}
+    uniffi_napi_free_{{ vtable.name() }},
+};
+
+/**
+ * Register a JavaScript vtable for `{{ cbi.name() }}`: an object with a function for each method,
+ * and `uniffi_free`.
+ */
+static napi_value uniffi_napi_{{ cbi.ffi_init_callback().name() }}(napi_env env, napi_callback_info info) {
+    napi_value argv[1];
+    if (!uniffi_napi_get_args(env, info, 1, argv, "{{ cbi.ffi_init_callback().name() }}")) {
+        return NULL;
+    }
+    {%- for (callback, meth) in methods.iter() %}
+    if (!uniffi_napi_get_vtable_method(env, argv[0], "{{ meth.name() }}", &uniffi_napi_js_{{ vtable.name() }}[{{ loop.index0 }}])) {
+        return NULL;
+    }
+    {%- endfor %}
+    if (!uniffi_napi_get_vtable_method(env, argv[0], "uniffi_free", &uniffi_napi_js_{{ vtable.name() }}[{{ methods.len() }}])) {
+        return NULL;
+    }
+    {{ cbi.ffi_init_callback().name() }}(&uniffi_napi_{{ vtable.name() }});
+    return uniffi_napi_undefined(env);
+}
+{%- endfor %}
+{%- endif %}
+
+NAPI_MODULE_INIT() {
+    uniffi_napi_env = env;
+    uniffi_napi_main_thread = pthread_self();
+    {%- if !ci.callback_interface_definitions().is_empty() %}
+    napi_value name;
+    napi_create_string_utf8(env, "uniffiCallback", NAPI_AUTO_LENGTH, &name);
+    if (napi_create_threadsafe_function(env, NULL, NULL, name, 0, 1, NULL, NULL, NULL, uniffi_napi_callback_call_js, &uniffi_napi_callback_tsfn) != napi_ok) {
+        uniffi_napi_throw_last_error(env);
+        return NULL;
+    }
+    // Callbacks from other threads shouldn't keep the process alive.
+    napi_unref_threadsafe_function(env, uniffi_napi_callback_tsfn);
+    {%- endif %}
+    napi_property_descriptor properties[] = {
+        {%- for (name, function) in self.exports() %}
+        { "{{ name }}", NULL, {{ function }}, NULL, NULL, NULL, napi_enumerable, NULL },
+        {%- endfor %}
+    };
+    if (napi_define_properties(env, exports, sizeof(properties) / sizeof(properties[0]), properties) != napi_ok) {
+        uniffi_napi_throw_last_error(env);
+        return NULL;
+    }
+    return exports;
+}
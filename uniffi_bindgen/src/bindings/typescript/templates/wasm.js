@@ -0,0 +1,206 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+/**
+ * Glue for the wasm build of the `{{ config.cdylib_name() }}` library, for the `{{ ci.namespace() }}` component.
+ *
+ * This exports the scaffolding functions to the generated `{{ config.module_name(ci) }}.js` module, with the
+ * same signatures as the N-API addon: integers up to 32 bits and floats are numbers, 64-bit
+ * integers and pointers are bigints, and `RustBuffer`s are `Uint8Array`s.  Functions that take a
+ * `RustCallStatus` take a `{ code, errorBuf }` object as their last argument, which is updated
+ * after the call.
+ *
+ * `RustBuffer`s and `RustCallStatus`es are passed through a scratch page of the wasm memory,
+ * which is reserved when the module is instantiated.
+ */
+
+"use strict";
+
+const UNIFFI_WASM_PAGE_SIZE = 0x10000;
+const UNIFFI_RUST_BUFFER_SIZE = 24;
+const UNIFFI_RUST_CALL_STATUS_SIZE = 32;
+
+let uniffiWasm = undefined;
+let uniffiScratchEnd = 0;
+let uniffiScratchTop = 0;
+// Whether `RustBuffer` arguments are split into their fields and padding, as with the legacy C
+// ABI of `wasm32-unknown-unknown`, rather than passed as a pointer to a copy.
+let uniffiSplitRustBuffers = false;
+
+async function uniffiInstantiate(source, imports) {
+  source = await source;
+  let instance;
+  if (source instanceof WebAssembly.Module) {
+    instance = await WebAssembly.instantiate(source, imports);
+  } else if (typeof Response !== "undefined" && source instanceof Response) {
+    instance = (await WebAssembly.instantiateStreaming(source, imports)).instance;
+  } else {
+    instance = (await WebAssembly.instantiate(source, imports)).instance;
+  }
+  const exports = instance.exports;
+  if (!(exports.memory instanceof WebAssembly.Memory)) {
+    throw new Error("The wasm module doesn't export its memory");
+  }
+  // The Rust allocator only uses the pages that it grows the memory by itself, so it leaves
+  // this one alone.
+  const scratch = exports.memory.grow(1) * UNIFFI_WASM_PAGE_SIZE;
+  uniffiScratchTop = scratch;
+  uniffiScratchEnd = scratch + UNIFFI_WASM_PAGE_SIZE;
+  uniffiSplitRustBuffers = exports.{{ ci.ffi_rustbuffer_free().name() }}.length > 2;
+  uniffiWasm = exports;
+}
+
+function uniffiExports() {
+  if (uniffiWasm === undefined) {
+    throw new Error("The wasm module isn't loaded: call `uniffiInitialize()` first");
+  }
+  return uniffiWasm;
+}
+
+// A view of the memory, which has to be made again after each call since growing the memory
+// detaches the old buffer.
+function uniffiMemory() {
+  return new DataView(uniffiWasm.memory.buffer);
+}
+
+// Allocate zeroed scratch memory, which is freed when the call that allocated it returns.
+function uniffiScratchAlloc(size) {
+  const pointer = uniffiScratchTop;
+  const top = pointer + ((size + 7) & ~7);
+  if (top > uniffiScratchEnd) {
+    throw new Error("Out of wasm scratch memory");
+  }
+  new Uint8Array(uniffiWasm.memory.buffer, pointer, size).fill(0);
+  uniffiScratchTop = top;
+  return pointer;
+}
+
+// The arguments that pass the `RustBuffer` at `pointer` by value.
+function uniffiRustBufferArgs(pointer) {
+  if (!uniffiSplitRustBuffers) {
+    return [pointer];
+  }
+  const memory = uniffiMemory();
+  return [
+    memory.getBigUint64(pointer, true),
+    memory.getBigUint64(pointer + 8, true),
+    memory.getUint32(pointer + 16, true),
+    0,
+  ];
+}
+
+// Copy a `Uint8Array` into a new `RustBuffer`, which the callee takes ownership of.
+function uniffiLowerRustBuffer(bytes, args) {
+  if (!(bytes instanceof Uint8Array)) {
+    throw new TypeError("Expected a Uint8Array");
+  }
+  const pointer = uniffiScratchAlloc(UNIFFI_RUST_BUFFER_SIZE);
+  const status = uniffiScratchAlloc(UNIFFI_RUST_CALL_STATUS_SIZE);
+  uniffiWasm.{{ ci.ffi_rustbuffer_alloc().name() }}(pointer, BigInt(bytes.length), status);
+  const memory = uniffiMemory();
+  if (memory.getInt8(status) !== 0) {
+    throw new Error("Failed to allocate a RustBuffer");
+  }
+  const data = memory.getUint32(pointer + 16, true);
+  new Uint8Array(memory.buffer, data, bytes.length).set(bytes);
+  memory.setBigUint64(pointer + 8, BigInt(bytes.length), true);
+  args.push(...uniffiRustBufferArgs(pointer));
+}
+
+// Copy the `RustBuffer` at `pointer` into a new `Uint8Array`, then free it.
+function uniffiLiftRustBuffer(pointer) {
+  const memory = uniffiMemory();
+  const length = Number(memory.getBigUint64(pointer + 8, true));
+  const data = memory.getUint32(pointer + 16, true);
+  const bytes = new Uint8Array(memory.buffer, data, length).slice();
+  const status = uniffiScratchAlloc(UNIFFI_RUST_CALL_STATUS_SIZE);
+  uniffiWasm.{{ ci.ffi_rustbuffer_free().name() }}(...uniffiRustBufferArgs(pointer), status);
+  return bytes;
+}
+
+// Convert arguments to their wasm values, pushing them onto `args`.
+const uniffiLowerers = {
+  int8: (value, args) => args.push(value),
+  uint8: (value, args) => args.push(value),
+  int16: (value, args) => args.push(value),
+  uint16: (value, args) => args.push(value),
+  int32: (value, args) => args.push(value),
+  uint32: (value, args) => args.push(value),
+  int64: (value, args) => args.push(value),
+  uint64: (value, args) => args.push(value),
+  float: (value, args) => args.push(value),
+  double: (value, args) => args.push(value),
+  pointer: (value, args) => args.push(Number(value)),
+  rust_buffer: uniffiLowerRustBuffer,
+};
+
+// Convert wasm return values to their JavaScript values.  Small integers are returned as `i32`s
+// and 64-bit integers as signed bigints, so they're truncated to their type.
+const uniffiLifters = {
+  int8: (value) => (value << 24) >> 24,
+  uint8: (value) => value & 0xff,
+  int16: (value) => (value << 16) >> 16,
+  uint16: (value) => value & 0xffff,
+  int32: (value) => value,
+  uint32: (value) => value >>> 0,
+  int64: (value) => value,
+  uint64: (value) => BigInt.asUintN(64, value),
+  float: (value) => value,
+  double: (value) => value,
+  pointer: (value) => BigInt(value >>> 0),
+};
+
+// Call a scaffolding function.  `argKinds` and `returnKind` name the conversions for the
+// arguments and the return value, which is `null` for functions that don't return anything.
+function uniffiCall(name, argKinds, returnKind, hasCallStatus, args) {
+  const exports = uniffiExports();
+  const argc = argKinds.length + (hasCallStatus ? 1 : 0);
+  if (args.length !== argc) {
+    throw new TypeError(`Expected ${argc} arguments to \`${name}\`, got ${args.length}`);
+  }
+  const scratchTop = uniffiScratchTop;
+  try {
+    const wasmArgs = [];
+    // `RustBuffer`s are returned through a pointer passed as the first argument.
+    const returnPointer = returnKind === "rust_buffer" ? uniffiScratchAlloc(UNIFFI_RUST_BUFFER_SIZE) : undefined;
+    if (returnPointer !== undefined) {
+      wasmArgs.push(returnPointer);
+    }
+    argKinds.forEach((kind, i) => uniffiLowerers[kind](args[i], wasmArgs));
+    const status = hasCallStatus ? uniffiScratchAlloc(UNIFFI_RUST_CALL_STATUS_SIZE) : undefined;
+    if (status !== undefined) {
+      wasmArgs.push(status);
+    }
+    const result = exports[name](...wasmArgs);
+    if (status !== undefined) {
+      const code = uniffiMemory().getInt8(status);
+      args[argKinds.length].code = code;
+      if (code !== 0) {
+        args[argKinds.length].errorBuf = uniffiLiftRustBuffer(status + 8);
+        return undefined;
+      }
+    }
+    if (returnKind === null) {
+      return undefined;
+    }
+    if (returnPointer !== undefined) {
+      return uniffiLiftRustBuffer(returnPointer);
+    }
+    return uniffiLifters[returnKind](result);
+  } finally {
+    uniffiScratchTop = scratchTop;
+  }
+}
+
+module.exports = {
+  uniffiInstantiate,
+  {%- for func in self.functions() %}
+  {{ func.name() }}: (...args) => uniffiCall(
+    "{{ func.name() }}",
+    [{% for arg in func.arguments() %}"{{ arg.type_().borrow()|napi_kind }}"{% if !loop.last %}, {% endif %}{% endfor %}],
+    {% match func.return_type() %}{% when Some(return_type) %}"{{ return_type|napi_kind }}"{% when None %}null{% endmatch %},
+    {{ func.has_rust_call_status_arg() }},
+    args,
+  ),
+  {%- endfor %}
+};
@@ -0,0 +1,140 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+{%- call ts::docstring_value(ci.namespace_docstring(), 0) %}
+
+/**
+ * Thrown when the Rust library panics, or when the bindings and the library disagree about the
+ * interface.
+ */
+export declare class UniffiInternalError extends Error {}
+
+/**
+ * Thrown when the Rust library panics.
+ */
+export declare class UniffiRustPanic extends UniffiInternalError {}
+{%- if config.wasm() %}
+
+/**
+ * Load the wasm build of the Rust library.  Nothing else in this module can be used until the
+ * returned promise resolves.
+ *
+ * `imports` are passed to the module when it's instantiated, for libraries that import their own
+ * functions.
+ */
+export declare function uniffiInitialize(
+  source: BufferSource | WebAssembly.Module | Response | PromiseLike<Response>,
+  imports?: WebAssembly.Imports,
+): Promise<void>;
+{%- endif %}
+{%- for type_ in ci.iter_types() %}
+{%- match type_ %}
+{%- when Type::Custom { name, builtin, module_path } %}
+
+export type {{ name|class_name }} = {{ builtin|type_name }};
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
+{%- for rec in ci.record_definitions() %}
+{% call ts::docstring(rec, 0) %}
+export interface {{ rec.name()|class_name }} {
+  {%- for field in rec.fields() %}
+  {%- call ts::docstring(field, 2) %}
+  {{ field|field_name(loop.index0) }}{% if self.has_field_default(field) %}?{% endif %}: {{ field|type_name }};
+  {%- endfor %}
+}
+{%- endfor %}
+{%- for e in ci.enum_definitions() %}
+{%- let type_name = e.name()|class_name %}
+{%- if ci.is_name_used_as_error(e.name()) %}
+{% call ts::docstring(e, 0) %}
+export declare class {{ type_name }} extends Error {
+  protected constructor(message: string);
+}
+
+export declare namespace {{ type_name }} {
+  {%- for variant in e.variants() %}
+  {%- call ts::docstring(variant, 2) %}
+  class {{ variant.name()|variant_name }} extends {{ type_name }} {
+    {%- if e.is_flat() %}
+    constructor(message: string);
+    {%- else %}
+    {%- for field in variant.fields() %}
+    {%- call ts::docstring(field, 4) %}
+    readonly {{ field|field_name(loop.index0) }}: {{ field|type_name }};
+    {%- endfor %}
+    constructor(
+      {%- for field in variant.fields() %}{{ field|field_name(loop.index0) }}: {{ field|type_name }}{% if !loop.last %}, {% endif %}{% endfor -%}
+    );
+    {%- endif %}
+  }
+  {%- endfor %}
+}
+{%- else if e.is_flat() %}
+{% call ts::docstring(e, 0) %}
+export declare enum {{ type_name }} {
+  {%- for variant in e.variants() %}
+  {%- call ts::docstring(variant, 2) %}
+  {{ variant.name()|variant_name }} = {{ loop.index0 }},
+  {%- endfor %}
+}
+{%- else %}
+{% call ts::docstring(e, 0) %}
+export type {{ type_name }} =
+  {%- for variant in e.variants() %}
+  | { tag: "{{ variant.name()|variant_name }}"
+    {%- for field in variant.fields() %}; {{ field|field_name(loop.index0) }}: {{ field|type_name }}{% endfor %} }
+  {%- endfor %};
+{%- endif %}
+{%- endfor %}
+{%- for obj in ci.object_definitions() %}
+{%- let type_name = obj.name()|class_name %}
+{% call ts::docstring(obj, 0) %}
+export declare class {{ type_name }} {
+  {%- match obj.primary_constructor() %}
+  {%- when Some(cons) %}
+  {%- call ts::docstring(cons, 2) %}
+  constructor({% call ts::param_list(cons) %});
+  {%- when None %}
+  private constructor();
+  {%- endmatch %}
+  {%- for cons in obj.alternate_constructors() %}
+  {%- call ts::docstring(cons, 2) %}
+  static {{ cons.name()|method_name }}({% call ts::param_list(cons) %}): {{ type_name }};
+  {%- endfor %}
+  /**
+   * Free the Rust object.  The object can't be used after it's destroyed.
+   */
+  destroy(): void;
+  {%- for meth in obj.methods() %}
+  {%- call ts::docstring(meth, 2) %}
+  {{ meth.name()|method_name }}({% call ts::param_list(meth) %}): {% call ts::return_type(meth) %};
+  {%- endfor %}
+  {%- for tm in obj.uniffi_traits() %}
+  {%- match tm %}
+  {%- when UniffiTrait::Display { fmt } %}
+  toString(): string;
+  {%- when UniffiTrait::Debug { fmt } %}
+  toDebugString(): string;
+  {%- when UniffiTrait::Eq { eq, ne } %}
+  equals(other: {{ type_name }}): boolean;
+  {%- when UniffiTrait::Hash { hash } %}
+  hashCode(): bigint;
+  {%- endmatch %}
+  {%- endfor %}
+}
+{%- endfor %}
+{%- for cbi in ci.callback_interface_definitions() %}
+{% call ts::docstring(cbi, 0) %}
+export interface {{ cbi.name()|class_name }} {
+  {%- for meth in cbi.methods() %}
+  {%- call ts::docstring(meth, 2) %}
+  {{ meth.name()|method_name }}({% call ts::param_list(meth) %}): {% call ts::return_type(meth) %};
+  {%- endfor %}
+}
+{%- endfor %}
+{%- for func in ci.function_definitions() %}
+{% call ts::docstring(func, 0) %}
+export declare function {{ func.name()|fn_name }}({% call ts::param_list(func) %}): {% call ts::return_type(func) %};
+{%- endfor %}
+
+{% import "macros.d.ts" as ts %}
@@ -0,0 +1,99 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+"use strict";
+
+{%- if config.wasm() %}
+// The glue that exports the scaffolding functions of the Rust library's wasm module, once it's
+// loaded by `uniffiInitialize()`.
+const {% if config.lenient_checksums() %}uniffiLib{% else %}lib{% endif %} = require("./{{ config.wasm_glue_name(ci) }}.js");
+{%- else %}
+// The N-API addon that exports the scaffolding functions of the Rust library.
+const {% if config.lenient_checksums() %}uniffiLib{% else %}lib{% endif %} = require("./{{ config.addon_name(ci) }}.node");
+{%- endif %}
+{%- if config.lenient_checksums() %}
+
+// The scaffolding functions that were disabled because their checksums don't match, with the
+// messages of the errors to throw when they're called.
+const uniffiDisabledFunctions = new Map();
+
+const lib = new Proxy(uniffiLib, {
+  get(target, name) {
+    const message = uniffiDisabledFunctions.get(name);
+    if (message !== undefined) {
+      return () => {
+        throw new UniffiInternalError(message);
+      };
+    }
+    return target[name];
+  },
+});
+{%- endif %}
+
+{% include "Helpers.js" %}
+
+function uniffiCheckApi() {
+  const bindingsContractVersion = {{ ci.uniffi_contract_version() }};
+  const scaffoldingContractVersion = lib.{{ ci.ffi_uniffi_contract_version().name() }}();
+  if (scaffoldingContractVersion !== bindingsContractVersion) {
+    throw new UniffiInternalError(
+      "UniFFI contract version mismatch: try cleaning and rebuilding your project " +
+      `(the bindings expect version ${bindingsContractVersion}, the library has version ${scaffoldingContractVersion})`,
+    );
+  }
+  const mismatches = [];
+  {%- for check in ci.iter_checksum_checks() %}
+  {
+    const checksum = lib.{{ check.checksum_fn_name }}();
+    if (checksum !== {{ check.checksum }}) {
+      const detail = `the bindings expect checksum {{ check.checksum }}, the library has checksum ${checksum}`;
+      {%- if let Some(ffi_func_name) = check.ffi_func_name %}
+      {%- if config.lenient_checksums() %}
+      uniffiDisabledFunctions.set("{{ ffi_func_name }}", "{{ check.item }} is disabled because of a UniFFI API checksum mismatch: " + detail);
+      {%- else %}
+      mismatches.push("{{ check.item }}: " + detail);
+      {%- endif %}
+      {%- else %}
+      mismatches.push("{{ check.item }}: " + detail);
+      {%- endif %}
+    }
+  }
+  {%- endfor %}
+  if (mismatches.length > 0) {
+    throw new UniffiInternalError(
+      "UniFFI API checksum mismatch: try cleaning and rebuilding your project\n" +
+      mismatches.map((mismatch) => `    ${mismatch}`).join("\n"),
+    );
+  }
+}
+{%- if config.wasm() %}
+
+// Load the wasm module, from its bytes, a `WebAssembly.Module` or a `fetch()` response.  Nothing
+// else in this module can be used until the returned promise resolves.
+async function uniffiInitialize(source, imports = {}) {
+  await lib.uniffiInstantiate(source, imports);
+  uniffiCheckApi();
+}
+{%- else %}
+
+uniffiCheckApi();
+{%- endif %}
+{%- if ci.has_async_fns() %}
+
+{% include "Async.js" %}
+{%- endif %}
+{% include "Types.js" %}
+{%- for func in ci.function_definitions() %}
+
+{% call js::async_kw(func) %}function {{ func.name()|fn_name }}({% call js::arg_list_decl(func) %}) {
+  {%- call js::call_body(func, "", "  ") %}
+}
+{%- endfor %}
+
+module.exports = {
+  {%- for name in self.exports() %}
+  {{ name }},
+  {%- endfor %}
+};
+
+{% import "macros.js" as js %}
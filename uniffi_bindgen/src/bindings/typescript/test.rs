@@ -0,0 +1,114 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+License, v. 2.0. If a copy of the MPL was not distributed with this
+* file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use crate::{
+    bindings::{RunScriptOptions, TargetLanguage},
+    library_mode::generate_bindings,
+};
+use anyhow::{bail, Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use fs_err as fs;
+use std::process::Command;
+use uniffi_testing::UniFFITestHelper;
+
+/// Run JavaScript tests for a UniFFI test fixture
+pub fn run_test(tmp_dir: &str, fixture_name: &str, script_file: &str) -> Result<()> {
+    run_script(
+        tmp_dir,
+        fixture_name,
+        script_file,
+        vec![],
+        &RunScriptOptions::default(),
+    )
+}
+
+/// Run a JavaScript script against the generated modules
+///
+/// The N-API addons are compiled next to the modules, and the script is run from the same
+/// directory, so it can `require("./{module}")`.
+pub fn run_script(
+    tmp_dir: &str,
+    crate_name: &str,
+    script_file: &str,
+    args: Vec<String>,
+    _options: &RunScriptOptions,
+) -> Result<()> {
+    let script_path = Utf8Path::new(script_file).canonicalize_utf8()?;
+    let test_helper = UniFFITestHelper::new(crate_name)?;
+    let out_dir = test_helper.create_out_dir(tmp_dir, &script_path)?;
+    let cdylib_path = test_helper.copy_cdylib_to_out_dir(&out_dir)?;
+    generate_bindings(
+        &cdylib_path,
+        None,
+        &[TargetLanguage::TypeScript],
+        None,
+        &out_dir,
+        false,
+    )?;
+
+    let node_include_dir = node_include_dir()?;
+    for entry in out_dir.read_dir_utf8()? {
+        let path = entry?.into_path();
+        if path.extension() != Some("c") || !path.as_str().ends_with("_napi.c") {
+            continue;
+        }
+        let mut command = Command::new("cc");
+        command
+            .arg("-shared")
+            .arg("-fPIC")
+            .arg(format!("-I{node_include_dir}"))
+            .arg(format!("-I{out_dir}"))
+            .arg("-o")
+            .arg(path.with_extension("node"))
+            .arg(&path)
+            .arg(&cdylib_path)
+            .arg(format!("-Wl,-rpath,{out_dir}"));
+        // Node-API symbols are resolved from the `node` executable when the addon is loaded
+        if cfg!(target_os = "macos") {
+            command.arg("-undefined").arg("dynamic_lookup");
+        }
+        let status = command
+            .spawn()
+            .context("Failed to spawn `cc` when compiling the N-API addon")?
+            .wait()
+            .context("Failed to wait for `cc` when compiling the N-API addon")?;
+        if !status.success() {
+            bail!("compiling the N-API addon `{path}` failed");
+        }
+    }
+
+    let script_name = script_path.file_name().unwrap();
+    fs::copy(&script_path, out_dir.join(script_name))?;
+
+    let status = Command::new("node")
+        .current_dir(&out_dir)
+        .arg(script_name)
+        .args(args)
+        .spawn()
+        .context("Failed to spawn `node` when running script")?
+        .wait()
+        .context("Failed to wait for `node` when running script")?;
+    if !status.success() {
+        bail!("running `node` failed");
+    }
+    Ok(())
+}
+
+/// Find the Node.js headers, which are installed in `include/node` next to `bin/node`.
+fn node_include_dir() -> Result<Utf8PathBuf> {
+    let output = Command::new("node")
+        .arg("-p")
+        .arg("process.execPath")
+        .output()
+        .context("Failed to run `node` to find its headers")?;
+    if !output.status.success() {
+        bail!("running `node` to find its headers failed");
+    }
+    let exec_path = Utf8PathBuf::from(String::from_utf8(output.stdout)?.trim());
+    let prefix = exec_path
+        .parent()
+        .and_then(Utf8Path::parent)
+        .context("Unexpected `node` executable path")?;
+    Ok(prefix.join("include").join("node"))
+}
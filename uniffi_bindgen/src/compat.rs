@@ -0,0 +1,979 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Public API compatibility checks
+//!
+//! This compares the interfaces of two versions of a library, to catch changes that would break
+//! the code using their bindings before they're shipped.
+//!
+//! Any change to an exported item changes its checksum, so the bindings always need to be
+//! regenerated for the new library.  A change is *breaking* for a language when code that was
+//! written against the old bindings also needs to be changed, like when a function is removed or
+//! gets a new argument.  Some changes only break some languages: adding an argument with a default
+//! value is fine for Kotlin, which has default arguments, but not for Go, which doesn't.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use anyhow::Result;
+use camino::Utf8Path;
+
+use crate::bindings::TargetLanguage;
+use crate::interface::{
+    AsType, Callable, ComponentInterface, Enum, Field, Literal, Object, Record, Type,
+};
+use crate::library_mode;
+
+const ALL_LANGUAGES: &[TargetLanguage] = &[
+    TargetLanguage::Kotlin,
+    TargetLanguage::Swift,
+    TargetLanguage::Python,
+    TargetLanguage::Ruby,
+    TargetLanguage::C,
+    TargetLanguage::Cpp,
+    TargetLanguage::Go,
+    TargetLanguage::CSharp,
+    TargetLanguage::TypeScript,
+];
+
+// Languages whose bindings have default values for arguments.
+const ARGUMENT_DEFAULT_LANGUAGES: &[TargetLanguage] = &[
+    TargetLanguage::Kotlin,
+    TargetLanguage::Swift,
+    TargetLanguage::Python,
+    TargetLanguage::Ruby,
+    TargetLanguage::CSharp,
+    TargetLanguage::TypeScript,
+];
+
+// Languages whose bindings have default values for record fields.
+const FIELD_DEFAULT_LANGUAGES: &[TargetLanguage] = &[
+    TargetLanguage::Kotlin,
+    TargetLanguage::Swift,
+    TargetLanguage::Python,
+    TargetLanguage::Ruby,
+    TargetLanguage::Cpp,
+    TargetLanguage::CSharp,
+    TargetLanguage::TypeScript,
+];
+
+// Languages where arguments can be passed by name, so renaming them breaks callers.
+const NAMED_ARGUMENT_LANGUAGES: &[TargetLanguage] = &[
+    TargetLanguage::Kotlin,
+    TargetLanguage::Swift,
+    TargetLanguage::Python,
+    TargetLanguage::CSharp,
+];
+
+// Languages where matching an enum has to be exhaustive, so adding a variant breaks callers.
+const EXHAUSTIVE_MATCH_LANGUAGES: &[TargetLanguage] = &[
+    TargetLanguage::Kotlin,
+    TargetLanguage::Swift,
+    TargetLanguage::Cpp,
+];
+
+// Languages where callers have to handle errors, so making a function throw breaks them.
+const CHECKED_ERROR_LANGUAGES: &[TargetLanguage] = &[TargetLanguage::Swift, TargetLanguage::Go];
+
+// Languages where errors change the signature of a function, so making a function stop throwing
+// breaks callers.
+const ERROR_RETURN_LANGUAGES: &[TargetLanguage] = &[TargetLanguage::Go];
+
+// Languages whose bindings expose blocking functions as async functions.
+const BLOCKING_LANGUAGES: &[TargetLanguage] = &[
+    TargetLanguage::Kotlin,
+    TargetLanguage::Swift,
+    TargetLanguage::Python,
+];
+
+const NO_LANGUAGES: &[TargetLanguage] = &[];
+
+/// What happened to an item
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// A change to an item of the interface
+#[derive(Debug, Clone)]
+pub struct Change {
+    /// The namespace of the component that the item is in
+    pub namespace: String,
+    /// The item, like "function `add`" or "record `Point`, field `x`"
+    pub item: String,
+    pub kind: ChangeKind,
+    pub description: String,
+    /// The languages whose users have to change their code
+    pub breaking_for: Vec<TargetLanguage>,
+}
+
+impl Change {
+    pub fn is_breaking(&self) -> bool {
+        !self.breaking_for.is_empty()
+    }
+
+    /// Whether the change is breaking for any of `languages`
+    pub fn is_breaking_for(&self, languages: &[TargetLanguage]) -> bool {
+        self.breaking_for.iter().any(|l| languages.contains(l))
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.namespace, self.item, self.description)?;
+        if self.breaking_for.len() == ALL_LANGUAGES.len() {
+            write!(f, " (breaking)")
+        } else if self.is_breaking() {
+            let languages = self
+                .breaking_for
+                .iter()
+                .map(|l| l.to_string())
+                .collect::<Vec<_>>();
+            write!(f, " (breaking for {})", languages.join(", "))
+        } else {
+            write!(f, " (non-breaking)")
+        }
+    }
+}
+
+/// The changes between two versions of a library
+#[derive(Debug, Clone, Default)]
+pub struct CompatReport {
+    pub changes: Vec<Change>,
+}
+
+impl CompatReport {
+    /// Compare the interfaces of two libraries
+    ///
+    /// Crates that use UDL files are compared with the UDL embedded in each library, so both need
+    /// to be built with uniffi's `embed-sources` feature.  The current workspace only has the
+    /// sources of one of the versions, so this fails rather than using them.
+    pub fn from_libraries(old_library: &Utf8Path, new_library: &Utf8Path) -> Result<Self> {
+        Ok(Self::from_components(
+            &library_mode::find_embedded_components(old_library)?,
+            &library_mode::find_embedded_components(new_library)?,
+        ))
+    }
+
+    /// Compare two sets of components, which are matched up by crate name.
+    pub fn from_components(old: &[ComponentInterface], new: &[ComponentInterface]) -> Self {
+        let mut report = Self::default();
+        let old = by_name(old, |ci| ci.crate_name());
+        let new = by_name(new, |ci| ci.crate_name());
+        for (name, old_ci, new_ci) in matched(&old, &new) {
+            let mut checker = Checker {
+                namespace: old_ci.or(new_ci).unwrap().namespace().to_string(),
+                changes: &mut report.changes,
+            };
+            checker.check_presence(format!("crate `{name}`"), old_ci, new_ci);
+            if let (Some(old_ci), Some(new_ci)) = (old_ci, new_ci) {
+                checker.check_component(old_ci, new_ci);
+            }
+        }
+        report
+    }
+
+    pub fn is_breaking(&self) -> bool {
+        self.changes.iter().any(Change::is_breaking)
+    }
+
+    /// Whether any change is breaking for any of `languages`
+    pub fn is_breaking_for(&self, languages: &[TargetLanguage]) -> bool {
+        self.changes.iter().any(|c| c.is_breaking_for(languages))
+    }
+}
+
+impl fmt::Display for CompatReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+// Index items by name, sorted so that reports are stable.
+fn by_name<'a, T>(items: &'a [T], name: impl Fn(&'a T) -> &'a str) -> BTreeMap<&'a str, &'a T> {
+    items.iter().map(|item| (name(item), item)).collect()
+}
+
+// Pair up the items with the same name, in name order.
+fn matched<'a, 'b, T>(
+    old: &'b BTreeMap<&'a str, &'a T>,
+    new: &'b BTreeMap<&'a str, &'a T>,
+) -> Vec<(&'a str, Option<&'a T>, Option<&'a T>)> {
+    let mut names = old.keys().chain(new.keys()).copied().collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+    names
+        .into_iter()
+        .map(|name| (name, old.get(name).copied(), new.get(name).copied()))
+        .collect()
+}
+
+// A description of a type for comparisons and reports, which ignores the module paths so that
+// moving a type between Rust modules isn't a change.
+fn type_label(type_: &Type) -> String {
+    match type_ {
+        Type::UInt8 => "u8".into(),
+        Type::Int8 => "i8".into(),
+        Type::UInt16 => "u16".into(),
+        Type::Int16 => "i16".into(),
+        Type::UInt32 => "u32".into(),
+        Type::Int32 => "i32".into(),
+        Type::UInt64 => "u64".into(),
+        Type::Int64 => "i64".into(),
+        Type::Float32 => "f32".into(),
+        Type::Float64 => "f64".into(),
+        Type::Boolean => "bool".into(),
+        Type::String => "string".into(),
+        Type::Bytes => "bytes".into(),
+        Type::Timestamp => "timestamp".into(),
+        Type::Duration => "duration".into(),
+        Type::Object { name, .. }
+        | Type::Record { name, .. }
+        | Type::Enum { name, .. }
+        | Type::CallbackInterface { name, .. }
+        | Type::External { name, .. }
+        | Type::Custom { name, .. } => name.clone(),
+        Type::Optional { inner_type } => format!("{}?", type_label(inner_type)),
+        Type::Sequence { inner_type } => format!("sequence<{}>", type_label(inner_type)),
+        Type::Map {
+            key_type,
+            value_type,
+        } => format!(
+            "record<{}, {}>",
+            type_label(key_type),
+            type_label(value_type)
+        ),
+        Type::Function { args, ret } => format!(
+            "fn({}) -> {}",
+            args.iter().map(type_label).collect::<Vec<_>>().join(", "),
+            optional_type_label(ret.as_deref())
+        ),
+    }
+}
+
+fn optional_type_label(type_: Option<&Type>) -> String {
+    type_.map(type_label).unwrap_or_else(|| "nothing".into())
+}
+
+fn literal_label(literal: &Literal) -> String {
+    match literal {
+        Literal::Boolean(v) => v.to_string(),
+        Literal::String(v) => format!("{v:?}"),
+        Literal::UInt(v, _, _) => v.to_string(),
+        Literal::Int(v, _, _) => v.to_string(),
+        Literal::Float(v, _) => v.clone(),
+        Literal::Enum(v, _) => v.clone(),
+        Literal::EmptySequence => "[]".into(),
+        Literal::EmptyMap => "{}".into(),
+        Literal::Null => "null".into(),
+    }
+}
+
+struct Checker<'a> {
+    namespace: String,
+    changes: &'a mut Vec<Change>,
+}
+
+impl Checker<'_> {
+    fn push(
+        &mut self,
+        item: &str,
+        kind: ChangeKind,
+        description: impl Into<String>,
+        breaking_for: &[TargetLanguage],
+    ) {
+        self.changes.push(Change {
+            namespace: self.namespace.clone(),
+            item: item.to_string(),
+            kind,
+            description: description.into(),
+            breaking_for: breaking_for.to_vec(),
+        });
+    }
+
+    // Report an item that was added or removed.  Adding is non-breaking unless the item has to be
+    // implemented by the foreign code.
+    fn check_presence<T>(&mut self, item: String, old: Option<T>, new: Option<T>) {
+        self.check_presence_with(item, old, new, NO_LANGUAGES)
+    }
+
+    fn check_presence_with<T>(
+        &mut self,
+        item: String,
+        old: Option<T>,
+        new: Option<T>,
+        breaking_if_added: &[TargetLanguage],
+    ) {
+        match (old, new) {
+            (Some(_), None) => self.push(&item, ChangeKind::Removed, "removed", ALL_LANGUAGES),
+            (None, Some(_)) => self.push(&item, ChangeKind::Added, "added", breaking_if_added),
+            _ => (),
+        }
+    }
+
+    fn check_component(&mut self, old: &ComponentInterface, new: &ComponentInterface) {
+        if old.uniffi_contract_version() != new.uniffi_contract_version() {
+            self.push(
+                "component",
+                ChangeKind::Changed,
+                format!(
+                    "UniFFI contract version changed from {} to {}",
+                    old.uniffi_contract_version(),
+                    new.uniffi_contract_version()
+                ),
+                ALL_LANGUAGES,
+            );
+        }
+
+        let old_functions = by_name(old.function_definitions(), |f| f.name());
+        let new_functions = by_name(new.function_definitions(), |f| f.name());
+        for (name, old_func, new_func) in matched(&old_functions, &new_functions) {
+            let item = format!("function `{name}`");
+            self.check_presence(item.clone(), old_func, new_func);
+            if let (Some(old_func), Some(new_func)) = (old_func, new_func) {
+                self.check_callable(&item, old_func, new_func, false);
+            }
+        }
+
+        let old_records = old.record_definitions().collect::<Vec<_>>();
+        let new_records = new.record_definitions().collect::<Vec<_>>();
+        let old_records = by_name(&old_records, |r| r.name());
+        let new_records = by_name(&new_records, |r| r.name());
+        for (name, old_rec, new_rec) in matched(&old_records, &new_records) {
+            let item = format!("record `{name}`");
+            self.check_presence(item.clone(), old_rec, new_rec);
+            if let (Some(old_rec), Some(new_rec)) = (old_rec, new_rec) {
+                self.check_record(&item, old_rec, new_rec);
+            }
+        }
+
+        let old_enums = old.enum_definitions().collect::<Vec<_>>();
+        let new_enums = new.enum_definitions().collect::<Vec<_>>();
+        let old_enums = by_name(&old_enums, |e| e.name());
+        let new_enums = by_name(&new_enums, |e| e.name());
+        for (name, old_enum, new_enum) in matched(&old_enums, &new_enums) {
+            let kind = if old.is_name_used_as_error(name) || new.is_name_used_as_error(name) {
+                "error"
+            } else {
+                "enum"
+            };
+            let item = format!("{kind} `{name}`");
+            self.check_presence(item.clone(), old_enum, new_enum);
+            if let (Some(old_enum), Some(new_enum)) = (old_enum, new_enum) {
+                self.check_enum(&item, old_enum, new_enum);
+            }
+        }
+
+        let old_objects = by_name(old.object_definitions(), |o| o.name());
+        let new_objects = by_name(new.object_definitions(), |o| o.name());
+        for (name, old_obj, new_obj) in matched(&old_objects, &new_objects) {
+            let item = format!("object `{name}`");
+            self.check_presence(item.clone(), old_obj, new_obj);
+            if let (Some(old_obj), Some(new_obj)) = (old_obj, new_obj) {
+                self.check_object(&item, old_obj, new_obj);
+            }
+        }
+
+        let old_callbacks = by_name(old.callback_interface_definitions(), |c| c.name());
+        let new_callbacks = by_name(new.callback_interface_definitions(), |c| c.name());
+        for (name, old_cbi, new_cbi) in matched(&old_callbacks, &new_callbacks) {
+            let item = format!("callback interface `{name}`");
+            self.check_presence(item.clone(), old_cbi, new_cbi);
+            if let (Some(old_cbi), Some(new_cbi)) = (old_cbi, new_cbi) {
+                let old_methods = old_cbi.methods();
+                let new_methods = new_cbi.methods();
+                self.check_methods(
+                    &item,
+                    &by_name(&old_methods, |m| m.name()),
+                    &by_name(&new_methods, |m| m.name()),
+                    true,
+                );
+            }
+        }
+
+        // Records, enums and objects are covered above, which leaves the custom and external
+        // types.
+        let old_types = old.iter_types().filter(|t| is_alias(t)).collect::<Vec<_>>();
+        let new_types = new.iter_types().filter(|t| is_alias(t)).collect::<Vec<_>>();
+        let old_types = by_name(&old_types, |t| alias_name(t));
+        let new_types = by_name(&new_types, |t| alias_name(t));
+        for (name, old_type, new_type) in matched(&old_types, &new_types) {
+            let item = format!("type `{name}`");
+            self.check_presence(item.clone(), old_type, new_type);
+            if let (Some(old_type), Some(new_type)) = (old_type, new_type) {
+                self.check_alias(&item, old_type, new_type);
+            }
+        }
+    }
+
+    fn check_alias(&mut self, item: &str, old: &Type, new: &Type) {
+        let description = match (old, new) {
+            (Type::Custom { builtin: old, .. }, Type::Custom { builtin: new, .. })
+                if type_label(old) != type_label(new) =>
+            {
+                format!(
+                    "builtin type changed from `{}` to `{}`",
+                    type_label(old),
+                    type_label(new)
+                )
+            }
+            (Type::External { kind: old, .. }, Type::External { kind: new, .. }) if old != new => {
+                format!("kind changed from {old:?} to {new:?}")
+            }
+            (Type::Custom { .. }, Type::External { .. }) => "changed to an external type".into(),
+            (Type::External { .. }, Type::Custom { .. }) => "changed to a custom type".into(),
+            _ => return,
+        };
+        self.push(item, ChangeKind::Changed, description, ALL_LANGUAGES);
+    }
+
+    fn check_record(&mut self, item: &str, old: &Record, new: &Record) {
+        self.check_fields(item, old.fields(), new.fields());
+    }
+
+    fn check_enum(&mut self, item: &str, old: &Enum, new: &Enum) {
+        if old.is_flat() != new.is_flat() {
+            let description = if new.is_flat() {
+                "variants no longer have fields"
+            } else {
+                "variants now have fields"
+            };
+            self.push(item, ChangeKind::Changed, description, ALL_LANGUAGES);
+        }
+        if old.is_non_exhaustive() && !new.is_non_exhaustive() {
+            self.push(
+                item,
+                ChangeKind::Changed,
+                "no longer non-exhaustive",
+                NO_LANGUAGES,
+            );
+        } else if !old.is_non_exhaustive() && new.is_non_exhaustive() {
+            // The bindings now need a fallback case when matching on the enum.
+            self.push(
+                item,
+                ChangeKind::Changed,
+                "now non-exhaustive",
+                EXHAUSTIVE_MATCH_LANGUAGES,
+            );
+        }
+
+        let breaking_if_added = if old.is_non_exhaustive() {
+            NO_LANGUAGES
+        } else {
+            EXHAUSTIVE_MATCH_LANGUAGES
+        };
+        let old_variants = by_name(old.variants(), |v| v.name());
+        let new_variants = by_name(new.variants(), |v| v.name());
+        for (name, old_variant, new_variant) in matched(&old_variants, &new_variants) {
+            let variant_item = format!("{item}, variant `{name}`");
+            self.check_presence_with(
+                variant_item.clone(),
+                old_variant,
+                new_variant,
+                breaking_if_added,
+            );
+            if let (Some(old_variant), Some(new_variant)) = (old_variant, new_variant) {
+                self.check_fields(&variant_item, old_variant.fields(), new_variant.fields());
+            }
+        }
+    }
+
+    fn check_fields(&mut self, item: &str, old: &[Field], new: &[Field]) {
+        let old_fields = by_name(old, |f| f.name());
+        let new_fields = by_name(new, |f| f.name());
+        for (name, old_field, new_field) in matched(&old_fields, &new_fields) {
+            let field_item = format!("{item}, field `{name}`");
+            match (old_field, new_field) {
+                (Some(_), None) => {
+                    self.push(&field_item, ChangeKind::Removed, "removed", ALL_LANGUAGES)
+                }
+                (None, Some(new_field)) => {
+                    // Fields with a default don't need to be passed to the constructor, in the
+                    // languages that support defaults.
+                    let breaking_for = if new_field.default_value().is_some() {
+                        without(ALL_LANGUAGES, FIELD_DEFAULT_LANGUAGES)
+                    } else {
+                        ALL_LANGUAGES.to_vec()
+                    };
+                    self.push(&field_item, ChangeKind::Added, "added", &breaking_for)
+                }
+                (Some(old_field), Some(new_field)) => {
+                    self.check_type(
+                        &field_item,
+                        "type",
+                        Some(&old_field.as_type()),
+                        Some(&new_field.as_type()),
+                    );
+                    self.check_default(
+                        &field_item,
+                        old_field.default_value(),
+                        new_field.default_value(),
+                        FIELD_DEFAULT_LANGUAGES,
+                    );
+                }
+                (None, None) => unreachable!(),
+            }
+        }
+    }
+
+    fn check_object(&mut self, item: &str, old: &Object, new: &Object) {
+        if old.imp() != new.imp() {
+            self.push(
+                item,
+                ChangeKind::Changed,
+                format!("changed from {} to {}", object_kind(old), object_kind(new)),
+                ALL_LANGUAGES,
+            );
+        }
+
+        let old_constructors = old.constructors();
+        let new_constructors = new.constructors();
+        let old_constructors = by_name(&old_constructors, |c| c.name());
+        let new_constructors = by_name(&new_constructors, |c| c.name());
+        for (name, old_cons, new_cons) in matched(&old_constructors, &new_constructors) {
+            let cons_item = format!("{item}, constructor `{name}`");
+            self.check_presence(cons_item.clone(), old_cons, new_cons);
+            if let (Some(old_cons), Some(new_cons)) = (old_cons, new_cons) {
+                self.check_callable(&cons_item, old_cons, new_cons, false);
+            }
+        }
+
+        let old_methods = old.methods();
+        let new_methods = new.methods();
+        let old_methods = by_name(&old_methods, |m| m.name());
+        let new_methods = by_name(&new_methods, |m| m.name());
+        self.check_methods(
+            item,
+            &old_methods,
+            &new_methods,
+            new.has_callback_interface(),
+        );
+        for (name, old_meth, new_meth) in matched(&old_methods, &new_methods) {
+            if let (Some(old_meth), Some(new_meth)) = (old_meth, new_meth) {
+                let meth_item = format!("{item}, method `{name}`");
+                match (
+                    old_meth.takes_self_by_value(),
+                    new_meth.takes_self_by_value(),
+                ) {
+                    (false, true) => self.push(
+                        &meth_item,
+                        ChangeKind::Changed,
+                        "now consumes the object",
+                        ALL_LANGUAGES,
+                    ),
+                    (true, false) => self.push(
+                        &meth_item,
+                        ChangeKind::Changed,
+                        "no longer consumes the object",
+                        NO_LANGUAGES,
+                    ),
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    // `implemented_by_foreign` is set for interfaces that foreign code can implement, where any
+    // change to the methods breaks the implementations.
+    fn check_methods<T: Callable>(
+        &mut self,
+        item: &str,
+        old: &BTreeMap<&str, &T>,
+        new: &BTreeMap<&str, &T>,
+        implemented_by_foreign: bool,
+    ) {
+        let breaking_if_added = if implemented_by_foreign {
+            ALL_LANGUAGES
+        } else {
+            NO_LANGUAGES
+        };
+        for (name, old_meth, new_meth) in matched(old, new) {
+            let meth_item = format!("{item}, method `{name}`");
+            self.check_presence_with(meth_item.clone(), old_meth, new_meth, breaking_if_added);
+            if let (Some(old_meth), Some(new_meth)) = (old_meth, new_meth) {
+                self.check_callable(&meth_item, old_meth, new_meth, implemented_by_foreign);
+            }
+        }
+    }
+
+    fn check_callable(
+        &mut self,
+        item: &str,
+        old: &impl Callable,
+        new: &impl Callable,
+        implemented_by_foreign: bool,
+    ) {
+        if old.is_async() != new.is_async() {
+            let description = if new.is_async() {
+                "now async"
+            } else {
+                "no longer async"
+            };
+            self.push(item, ChangeKind::Changed, description, ALL_LANGUAGES);
+        }
+        if old.is_blocking() != new.is_blocking() {
+            let description = if new.is_blocking() {
+                "now blocking"
+            } else {
+                "no longer blocking"
+            };
+            self.push(item, ChangeKind::Changed, description, BLOCKING_LANGUAGES);
+        }
+
+        let old_args = old.arguments();
+        let new_args = new.arguments();
+        for (i, (old_arg, new_arg)) in old_args.iter().zip(new_args.iter()).enumerate() {
+            let arg_item = format!("{item}, argument {} `{}`", i + 1, old_arg.name());
+            if old_arg.name() != new_arg.name() {
+                // Foreign implementations of a method can use any names for the arguments.
+                let breaking_for = if implemented_by_foreign {
+                    NO_LANGUAGES
+                } else {
+                    NAMED_ARGUMENT_LANGUAGES
+                };
+                self.push(
+                    &arg_item,
+                    ChangeKind::Changed,
+                    format!("renamed to `{}`", new_arg.name()),
+                    breaking_for,
+                );
+            }
+            self.check_type(
+                &arg_item,
+                "type",
+                Some(&old_arg.as_type()),
+                Some(&new_arg.as_type()),
+            );
+            if !implemented_by_foreign {
+                self.check_default(
+                    &arg_item,
+                    old_arg.default_value(),
+                    new_arg.default_value(),
+                    ARGUMENT_DEFAULT_LANGUAGES,
+                );
+            }
+        }
+        for (i, old_arg) in old_args.iter().enumerate().skip(new_args.len()) {
+            self.push(
+                &format!("{item}, argument {} `{}`", i + 1, old_arg.name()),
+                ChangeKind::Removed,
+                "removed",
+                ALL_LANGUAGES,
+            );
+        }
+        for (i, new_arg) in new_args.iter().enumerate().skip(old_args.len()) {
+            let breaking_for = if new_arg.default_value().is_some() && !implemented_by_foreign {
+                without(ALL_LANGUAGES, ARGUMENT_DEFAULT_LANGUAGES)
+            } else {
+                ALL_LANGUAGES.to_vec()
+            };
+            self.push(
+                &format!("{item}, argument {} `{}`", i + 1, new_arg.name()),
+                ChangeKind::Added,
+                "added",
+                &breaking_for,
+            );
+        }
+
+        self.check_type(
+            item,
+            "return type",
+            old.return_type().as_ref(),
+            new.return_type().as_ref(),
+        );
+
+        let old_throws = old.throws_type();
+        let new_throws = new.throws_type();
+        match (&old_throws, &new_throws) {
+            (None, Some(new_throws)) => {
+                // Foreign implementations don't have to throw the new error.
+                let breaking_for = if implemented_by_foreign {
+                    NO_LANGUAGES
+                } else {
+                    CHECKED_ERROR_LANGUAGES
+                };
+                self.push(
+                    item,
+                    ChangeKind::Changed,
+                    format!("now throws `{}`", type_label(new_throws)),
+                    breaking_for,
+                );
+            }
+            (Some(_), None) => {
+                let breaking_for = if implemented_by_foreign {
+                    ALL_LANGUAGES
+                } else {
+                    ERROR_RETURN_LANGUAGES
+                };
+                self.push(item, ChangeKind::Changed, "no longer throws", breaking_for);
+            }
+            _ => self.check_type(item, "error type", old_throws.as_ref(), new_throws.as_ref()),
+        }
+    }
+
+    fn check_type(&mut self, item: &str, what: &str, old: Option<&Type>, new: Option<&Type>) {
+        let old = optional_type_label(old);
+        let new = optional_type_label(new);
+        if old != new {
+            self.push(
+                item,
+                ChangeKind::Changed,
+                format!("{what} changed from `{old}` to `{new}`"),
+                ALL_LANGUAGES,
+            );
+        }
+    }
+
+    fn check_default(
+        &mut self,
+        item: &str,
+        old: Option<&Literal>,
+        new: Option<&Literal>,
+        default_languages: &[TargetLanguage],
+    ) {
+        match (old, new) {
+            (None, Some(new)) => self.push(
+                item,
+                ChangeKind::Changed,
+                format!("now defaults to {}", literal_label(new)),
+                NO_LANGUAGES,
+            ),
+            // Callers that relied on the default have to pass a value now.
+            (Some(_), None) => self.push(
+                item,
+                ChangeKind::Changed,
+                "no longer has a default",
+                default_languages,
+            ),
+            (Some(old), Some(new)) if old != new => self.push(
+                item,
+                ChangeKind::Changed,
+                format!(
+                    "default changed from {} to {}",
+                    literal_label(old),
+                    literal_label(new)
+                ),
+                NO_LANGUAGES,
+            ),
+            _ => (),
+        }
+    }
+}
+
+fn is_alias(type_: &Type) -> bool {
+    matches!(type_, Type::Custom { .. } | Type::External { .. })
+}
+
+fn alias_name(type_: &Type) -> &str {
+    match type_ {
+        Type::Custom { name, .. } | Type::External { name, .. } => name,
+        _ => unreachable!("not a custom or external type"),
+    }
+}
+
+fn object_kind(object: &Object) -> &'static str {
+    if object.has_callback_interface() {
+        "a trait interface with foreign implementations"
+    } else if object.is_trait_interface() {
+        "a trait interface"
+    } else {
+        "an interface"
+    }
+}
+
+fn without(languages: &[TargetLanguage], excluded: &[TargetLanguage]) -> Vec<TargetLanguage> {
+    languages
+        .iter()
+        .copied()
+        .filter(|l| !excluded.contains(l))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(udl: &str) -> ComponentInterface {
+        ComponentInterface::from_webidl(udl, "crate_name").unwrap()
+    }
+
+    fn check(old: &str, new: &str) -> Vec<String> {
+        CompatReport::from_components(&[parse(old)], &[parse(new)])
+            .changes
+            .iter()
+            .map(|c| c.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_no_changes() {
+        let udl = r#"
+            namespace test { u32 add(u32 a, u32 b); };
+            dictionary Point { f64 x; f64 y; };
+        "#;
+        assert!(check(udl, udl).is_empty());
+    }
+
+    #[test]
+    fn test_functions() {
+        let changes = check(
+            r#"
+            namespace test {
+                u32 add(u32 a, u32 b);
+                void remove_me();
+                string greet(string name);
+                void save();
+            };
+            "#,
+            r#"
+            namespace test {
+                u64 add(u32 a, u32 c, optional u32 d = 0);
+                void new_function();
+                [Throws=MyError]
+                string greet(string name);
+                [Blocking]
+                void save();
+            };
+            [Error]
+            enum MyError { "Oops" };
+            "#,
+        );
+        assert_eq!(
+            changes,
+            vec![
+                "test: function `add`, argument 2 `b`: renamed to `c` (breaking for kotlin, swift, python, csharp)",
+                "test: function `add`, argument 3 `d`: added (breaking for c, cpp, go)",
+                "test: function `add`: return type changed from `u32` to `u64` (breaking)",
+                "test: function `greet`: now throws `MyError` (breaking for swift, go)",
+                "test: function `new_function`: added (non-breaking)",
+                "test: function `remove_me`: removed (breaking)",
+                "test: function `save`: now blocking (breaking for kotlin, swift, python)",
+                "test: error `MyError`: added (non-breaking)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_records_and_enums() {
+        let changes = check(
+            r#"
+            namespace test {};
+            dictionary Point { f64 x; f64 y; };
+            enum Color { "Red", "Green" };
+            "#,
+            r#"
+            namespace test {};
+            dictionary Point { f64 x; f32 y; f64 z = 0.0; };
+            enum Color { "Red", "Green", "Blue" };
+            "#,
+        );
+        assert_eq!(
+            changes,
+            vec![
+                "test: record `Point`, field `y`: type changed from `f64` to `f32` (breaking)",
+                "test: record `Point`, field `z`: added (breaking for c, go)",
+                "test: enum `Color`, variant `Blue`: added (breaking for kotlin, swift, cpp)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_callback_interfaces() {
+        let changes = check(
+            r#"
+            namespace test {};
+            callback interface Logger { void log(string message); };
+            interface Counter { void increment(); };
+            "#,
+            r#"
+            namespace test {};
+            callback interface Logger { void log(string msg); void flush(); };
+            interface Counter { void increment(); u32 get(); };
+            "#,
+        );
+        assert_eq!(
+            changes,
+            vec![
+                "test: object `Counter`, method `get`: added (non-breaking)",
+                "test: callback interface `Logger`, method `flush`: added (breaking)",
+                "test: callback interface `Logger`, method `log`, argument 1 `message`: renamed to `msg` (non-breaking)",
+            ]
+        );
+    }
+
+    // The metadata of a library built from a UDL file with the `embed-sources` feature
+    fn udl_library(udl: &str) -> Vec<uniffi_meta::Metadata> {
+        vec![
+            uniffi_meta::NamespaceMetadata {
+                crate_name: "crate_name".into(),
+                name: "test".into(),
+            }
+            .into(),
+            uniffi_meta::UdlFile {
+                module_path: "crate_name".into(),
+                namespace: "test".into(),
+                file_stub: "test".into(),
+            }
+            .into(),
+            uniffi_meta::UdlSource {
+                module_path: "crate_name".into(),
+                contents: udl.into(),
+            }
+            .into(),
+        ]
+    }
+
+    #[test]
+    fn test_udl_libraries() {
+        let old = library_mode::embedded_components(
+            udl_library("namespace test { u32 add(u32 a, u32 b); };"),
+            "libold.so",
+        )
+        .unwrap();
+        let new = library_mode::embedded_components(
+            udl_library("namespace test { u32 add(u32 a, u32 b); void reset(); };"),
+            "libnew.so",
+        )
+        .unwrap();
+        let changes = CompatReport::from_components(&old, &new)
+            .changes
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            vec!["test: function `reset`: added (non-breaking)"]
+        );
+    }
+
+    #[test]
+    fn test_udl_library_without_embedded_sources() {
+        let mut items = udl_library("namespace test {};");
+        items.pop();
+        let err = library_mode::embedded_components(items, "libold.so").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "libold.so doesn't embed the UDL file for crate_name, build it with the \
+             `embed-sources` feature of the `uniffi` crate"
+        );
+    }
+
+    #[test]
+    fn test_is_breaking_for() {
+        let report = CompatReport::from_components(
+            &[parse("namespace test {}; enum Color { \"Red\" };")],
+            &[parse(
+                "namespace test {}; enum Color { \"Red\", \"Green\" };",
+            )],
+        );
+        assert!(report.is_breaking());
+        assert!(report.is_breaking_for(&[TargetLanguage::Swift]));
+        assert!(!report.is_breaking_for(&[TargetLanguage::Python, TargetLanguage::Go]));
+    }
+}
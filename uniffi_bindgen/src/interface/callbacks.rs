@@ -36,13 +36,14 @@
 use std::iter;
 
 use heck::ToUpperCamelCase;
+use serde::Serialize;
 use uniffi_meta::Checksum;
 
 use super::ffi::{FfiArgument, FfiCallbackFunction, FfiField, FfiFunction, FfiStruct, FfiType};
 use super::object::Method;
 use super::{AsType, Type, TypeIterator};
 
-#[derive(Debug, Clone, Checksum)]
+#[derive(Debug, Clone, Checksum, Serialize)]
 pub struct CallbackInterface {
     pub(super) name: String,
     pub(super) module_path: String,
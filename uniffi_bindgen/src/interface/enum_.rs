@@ -160,6 +160,7 @@
 //! ```
 
 use anyhow::Result;
+use serde::Serialize;
 use uniffi_meta::Checksum;
 
 use super::record::Field;
@@ -170,7 +171,7 @@ use super::{AsType, Literal, Type, TypeIterator};
 ///
 /// Enums are passed across the FFI by serializing to a bytebuffer, with a
 /// i32 indicating the variant followed by the serialization of each field.
-#[derive(Debug, Clone, PartialEq, Eq, Checksum)]
+#[derive(Debug, Clone, PartialEq, Eq, Checksum, Serialize)]
 pub struct Enum {
     pub(super) name: String,
     pub(super) module_path: String,
@@ -298,7 +299,7 @@ impl AsType for Enum {
 /// Represents an individual variant in an Enum.
 ///
 /// Each variant has a name and zero or more fields.
-#[derive(Debug, Clone, Default, PartialEq, Eq, Checksum)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Checksum, Serialize)]
 pub struct Variant {
     pub(super) name: String,
     pub(super) discr: Option<Literal>,
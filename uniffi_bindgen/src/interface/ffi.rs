@@ -18,9 +18,10 @@
 /// For the types that involve memory allocation, we make a distinction between
 /// "owned" types (the recipient must free it, or pass it to someone else) and
 /// "borrowed" types (the sender must keep it alive for the duration of the call).
+use serde::Serialize;
 use uniffi_meta::{ExternalKind, Type};
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 pub enum FfiType {
     // N.B. there are no booleans at this layer, since they cause problems for JNA.
     UInt8,
@@ -123,6 +124,8 @@ impl From<&Type> for FfiType {
             Type::Object { name, .. } => FfiType::RustArcPtr(name.to_owned()),
             // Callback interfaces are passed as opaque integer handles.
             Type::CallbackInterface { .. } => FfiType::UInt64,
+            // Closures are also passed as handles, for both Rust and foreign closures.
+            Type::Function { .. } => FfiType::UInt64,
             // Other types are serialized into a bytebuffer and deserialized on the other side.
             Type::Enum { .. }
             | Type::Record { .. }
@@ -165,7 +168,7 @@ impl From<&&Type> for FfiType {
 }
 
 /// An Ffi definition
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum FfiDefinition {
     Function(FfiFunction),
     CallbackFunction(FfiCallbackFunction),
@@ -188,7 +191,7 @@ impl FfiDefinition {
 /// from the high-level interface. Each callable thing in the component API will have a
 /// corresponding `FfiFunction` through which it can be invoked, and UniFFI also provides
 /// some built-in `FfiFunction` helpers for use in the foreign language bindings.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct FfiFunction {
     pub(super) name: String,
     pub(super) is_async: bool,
@@ -269,7 +272,7 @@ impl Default for FfiFunction {
 /// Represents an argument to an FFI function.
 ///
 /// Each argument has a name and a type.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct FfiArgument {
     pub(super) name: String,
     pub(super) type_: FfiType,
@@ -295,7 +298,7 @@ impl FfiArgument {
 /// Represents an "extern C"-style callback function
 ///
 /// These are defined in the foreign code and passed to Rust as a function pointer.
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize)]
 pub struct FfiCallbackFunction {
     // Name for this function type. This matches the value inside `FfiType::Callback`
     pub(super) name: String,
@@ -323,7 +326,7 @@ impl FfiCallbackFunction {
 }
 
 /// Represents a repr(C) struct used in the FFI
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize)]
 pub struct FfiStruct {
     pub(super) name: String,
     pub(super) fields: Vec<FfiField>,
@@ -342,7 +345,7 @@ impl FfiStruct {
 }
 
 /// Represents a field of an [FfiStruct]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct FfiField {
     pub(super) name: String,
     pub(super) type_: FfiType,
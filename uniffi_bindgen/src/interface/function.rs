@@ -36,6 +36,7 @@ use anyhow::Result;
 
 use super::ffi::{FfiArgument, FfiFunction, FfiType};
 use super::{AsType, ComponentInterface, Literal, ObjectImpl, Type, TypeIterator};
+use serde::Serialize;
 use uniffi_meta::Checksum;
 
 /// Represents a standalone function.
@@ -44,11 +45,12 @@ use uniffi_meta::Checksum;
 /// and has a corresponding standalone function in the foreign language bindings.
 ///
 /// In the FFI, this will be a standalone function with appropriately lowered types.
-#[derive(Debug, Clone, Checksum)]
+#[derive(Debug, Clone, Checksum, Serialize)]
 pub struct Function {
     pub(super) name: String,
     pub(super) module_path: String,
     pub(super) is_async: bool,
+    pub(super) is_blocking: bool,
     pub(super) arguments: Vec<Argument>,
     pub(super) return_type: Option<Type>,
     // We don't include the FFIFunc in the hash calculation, because:
@@ -77,6 +79,11 @@ impl Function {
         self.is_async
     }
 
+    /// Is this a sync function that the bindings should call from a background thread?
+    pub fn is_blocking(&self) -> bool {
+        self.is_blocking
+    }
+
     pub fn arguments(&self) -> Vec<&Argument> {
         self.arguments.iter().collect()
     }
@@ -166,6 +173,7 @@ impl From<uniffi_meta::FnMetadata> for Function {
             name: meta.name,
             module_path: meta.module_path,
             is_async,
+            is_blocking: meta.is_blocking,
             arguments,
             return_type,
             ffi_func,
@@ -180,7 +188,7 @@ impl From<uniffi_meta::FnMetadata> for Function {
 /// Represents an argument to a function/constructor/method call.
 ///
 /// Each argument has a name and a type, along with some optional metadata.
-#[derive(Debug, Clone, Checksum)]
+#[derive(Debug, Clone, Checksum, Serialize)]
 pub struct Argument {
     pub(super) name: String,
     pub(super) type_: Type,
@@ -249,6 +257,7 @@ pub trait Callable {
     fn return_type(&self) -> Option<Type>;
     fn throws_type(&self) -> Option<Type>;
     fn is_async(&self) -> bool;
+    fn is_blocking(&self) -> bool;
     fn result_type(&self) -> ResultType {
         ResultType {
             return_type: self.return_type(),
@@ -299,6 +308,10 @@ impl Callable for Function {
     fn is_async(&self) -> bool {
         self.is_async
     }
+
+    fn is_blocking(&self) -> bool {
+        self.is_blocking
+    }
 }
 
 // Needed because Askama likes to add extra refs to variables
@@ -318,6 +331,10 @@ impl<T: Callable> Callable for &T {
     fn is_async(&self) -> bool {
         (*self).is_async()
     }
+
+    fn is_blocking(&self) -> bool {
+        (*self).is_blocking()
+    }
 }
 
 #[cfg(test)]
@@ -50,6 +50,7 @@ use std::{
 };
 
 use anyhow::{anyhow, bail, ensure, Result};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
 
 pub mod universe;
 pub use uniffi_meta::{AsType, ExternalKind, ObjectImpl, Type};
@@ -160,6 +161,11 @@ impl ComponentInterface {
         &self.types.namespace.name
     }
 
+    /// The name of the crate that this interface was generated from.
+    pub fn crate_name(&self) -> &str {
+        &self.types.namespace.crate_name
+    }
+
     pub fn namespace_docstring(&self) -> Option<&str> {
         self.types.namespace_docstring.as_deref()
     }
@@ -346,7 +352,7 @@ impl ComponentInterface {
     /// This method uses `iter_types` to iterate over the types contained within the given type,
     /// but additionally recurses into the definition of user-defined types like records and enums
     /// to yield the types that *they* contain.
-    fn iter_types_in_item<'a>(&'a self, item: &'a Type) -> impl Iterator<Item = &'a Type> + 'a {
+    pub fn iter_types_in_item<'a>(&'a self, item: &'a Type) -> impl Iterator<Item = &'a Type> + 'a {
         RecursiveTypeIterator::new(self, item)
     }
 
@@ -402,6 +408,11 @@ impl ComponentInterface {
             .any(|t| matches!(t, Type::Object { .. }))
     }
 
+    /// Check whether the interface contains any objects with a stable identity
+    pub fn has_stable_identity_objects(&self) -> bool {
+        self.objects.iter().any(Object::has_stable_identity)
+    }
+
     // The namespace to use in crate-level FFI function definitions. Not used as the ffi
     // namespace for types - each type has its own `module_path` which is used for them.
     fn ffi_namespace(&self) -> &str {
@@ -496,6 +507,95 @@ impl ComponentInterface {
         }
     }
 
+    /// Builtin FFI function to register the vtable used to call foreign closures.
+    pub fn ffi_foreign_function_init_vtable(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_foreign_function_init_vtable", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![FfiArgument {
+                name: "vtable".to_string(),
+                type_: FfiType::Struct("VTableForeignFunction".to_owned()).reference(),
+            }],
+            return_type: None,
+            has_rust_call_status_arg: false,
+            is_object_free_function: false,
+        }
+    }
+
+    /// Builtin FFI function to call a Rust closure.
+    /// The arguments and return value are serialized into `RustBuffer`s.
+    pub fn ffi_rust_function_call(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_rust_function_call", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![
+                FfiArgument {
+                    name: "handle".to_string(),
+                    type_: FfiType::UInt64,
+                },
+                FfiArgument {
+                    name: "args".to_string(),
+                    type_: FfiType::RustBuffer(None),
+                },
+            ],
+            return_type: Some(FfiType::RustBuffer(None)),
+            has_rust_call_status_arg: true,
+            is_object_free_function: false,
+        }
+    }
+
+    /// Builtin FFI function to free a Rust closure.
+    pub fn ffi_rust_function_free(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_rust_function_free", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![FfiArgument {
+                name: "handle".to_string(),
+                type_: FfiType::UInt64,
+            }],
+            return_type: None,
+            has_rust_call_status_arg: true,
+            is_object_free_function: false,
+        }
+    }
+
+    /// Builtin FFI function to install the logger that forwards Rust log records to the foreign
+    /// side.
+    pub fn ffi_log_bridge_init(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_log_bridge_init", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![
+                FfiArgument {
+                    name: "callback".to_string(),
+                    type_: FfiType::Callback("LogBridgeCallback".to_owned()),
+                },
+                FfiArgument {
+                    name: "max_level".to_string(),
+                    type_: FfiType::Int8,
+                },
+            ],
+            return_type: None,
+            has_rust_call_status_arg: false,
+            is_object_free_function: false,
+        }
+    }
+
+    /// Builtin FFI function to change the maximum level of the forwarded log records.
+    pub fn ffi_log_bridge_set_level(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_log_bridge_set_level", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![FfiArgument {
+                name: "max_level".to_string(),
+                type_: FfiType::Int8,
+            }],
+            return_type: None,
+            has_rust_call_status_arg: false,
+            is_object_free_function: false,
+        }
+    }
+
     /// Builtin FFI function to poll a Rust future.
     pub fn ffi_rust_future_poll(&self, return_ffi_type: Option<FfiType>) -> FfiFunction {
         FfiFunction {
@@ -583,6 +683,21 @@ impl ComponentInterface {
                 .any(CallbackInterface::has_async_method)
     }
 
+    /// Does this interface contain blocking functions or methods?
+    pub fn has_blocking_fns(&self) -> bool {
+        self.function_definitions().iter().any(|f| f.is_blocking())
+            || self
+                .object_definitions()
+                .iter()
+                .any(Object::has_blocking_method)
+    }
+
+    /// Does this interface pass closures across the FFI?
+    pub fn has_function_types(&self) -> bool {
+        self.iter_types()
+            .any(|t| matches!(t, Type::Function { .. }))
+    }
+
     /// Iterate over `T` parameters of the `FutureCallback<T>` callbacks in this interface
     pub fn iter_future_callback_params(&self) -> impl Iterator<Item = FfiType> {
         let unique_results = self
@@ -647,6 +762,17 @@ impl ComponentInterface {
                 has_rust_call_status_arg: false,
             }
             .into(),
+            FfiCallbackFunction {
+                name: "LogBridgeCallback".to_owned(),
+                arguments: vec![
+                    FfiArgument::new("level", FfiType::Int8),
+                    FfiArgument::new("target", FfiType::RustBuffer(None)),
+                    FfiArgument::new("message", FfiType::RustBuffer(None)),
+                ],
+                return_type: None,
+                has_rust_call_status_arg: false,
+            }
+            .into(),
             FfiStruct {
                 name: "ForeignFuture".to_owned(),
                 fields: vec![
@@ -666,6 +792,38 @@ impl ComponentInterface {
                     ]
                 }),
         )
+        .chain(self.function_type_ffi_definitions())
+    }
+
+    /// FFI definitions for calling foreign closures, only present if closures are used
+    fn function_type_ffi_definitions(&self) -> Vec<FfiDefinition> {
+        if !self.has_function_types() {
+            return vec![];
+        }
+        vec![
+            FfiCallbackFunction {
+                name: "ForeignFunctionCall".to_owned(),
+                arguments: vec![
+                    FfiArgument::new("handle", FfiType::UInt64),
+                    FfiArgument::new("args", FfiType::RustBuffer(None)),
+                    FfiArgument::new("uniffi_out_return", FfiType::RustBuffer(None).reference()),
+                ],
+                return_type: None,
+                has_rust_call_status_arg: true,
+            }
+            .into(),
+            FfiStruct {
+                name: "VTableForeignFunction".to_owned(),
+                fields: vec![
+                    FfiField::new("call", FfiType::Callback("ForeignFunctionCall".to_owned())),
+                    FfiField::new(
+                        "free",
+                        FfiType::Callback("CallbackInterfaceFree".to_owned()),
+                    ),
+                ],
+            }
+            .into(),
+        ]
     }
 
     /// List the definitions of all FFI functions in the interface.
@@ -676,7 +834,9 @@ impl ComponentInterface {
         self.iter_user_ffi_function_definitions()
             .cloned()
             .chain(self.iter_rust_buffer_ffi_function_definitions())
+            .chain(self.iter_function_type_ffi_function_definitions())
             .chain(self.iter_futures_ffi_function_definitions())
+            .chain([self.ffi_log_bridge_init(), self.ffi_log_bridge_set_level()])
             .chain(self.iter_checksum_ffi_functions())
             .chain([self.ffi_uniffi_contract_version()])
     }
@@ -688,6 +848,7 @@ impl ComponentInterface {
         self.iter_user_ffi_function_definitions()
             .cloned()
             .chain(self.iter_rust_buffer_ffi_function_definitions())
+            .chain(self.iter_function_type_ffi_function_definitions())
             .chain(self.iter_checksum_ffi_functions())
             .chain([self.ffi_uniffi_contract_version()])
     }
@@ -724,6 +885,22 @@ impl ComponentInterface {
         .into_iter()
     }
 
+    /// List all FFI functions definitions for closure support.
+    ///
+    /// These are only present if closures are used.
+    pub fn iter_function_type_ffi_function_definitions(&self) -> impl Iterator<Item = FfiFunction> {
+        let functions = if self.has_function_types() {
+            vec![
+                self.ffi_foreign_function_init_vtable(),
+                self.ffi_rust_function_call(),
+                self.ffi_rust_function_free(),
+            ]
+        } else {
+            vec![]
+        };
+        functions.into_iter()
+    }
+
     fn all_possible_return_ffi_types(&self) -> impl Iterator<Item = Option<FfiType>> {
         [
             Some(FfiType::UInt8),
@@ -762,35 +939,55 @@ impl ComponentInterface {
     ///
     /// Returns a list of (export_symbol_name, checksum) items
     pub fn iter_checksums(&self) -> impl Iterator<Item = (String, u16)> + '_ {
-        let func_checksums = self
-            .functions
-            .iter()
-            .map(|f| (f.checksum_fn_name(), f.checksum()));
-        let method_checksums = self.objects.iter().flat_map(|o| {
-            o.methods()
-                .into_iter()
-                .map(|m| (m.checksum_fn_name(), m.checksum()))
+        self.iter_checksum_checks()
+            .map(|check| (check.checksum_fn_name, check.checksum))
+    }
+
+    /// List all API checksums to check, with what they're for
+    ///
+    /// This is the same list as [Self::iter_checksums], for bindings that report which items
+    /// have mismatched checksums.
+    pub fn iter_checksum_checks(&self) -> impl Iterator<Item = ChecksumCheck> + '_ {
+        let func_checks = self.functions.iter().map(|f| ChecksumCheck {
+            checksum_fn_name: f.checksum_fn_name().to_string(),
+            checksum: f.checksum(),
+            item: format!("function `{}`", f.name()),
+            ffi_func_name: Some(f.ffi_func().name().to_string()),
         });
-        let constructor_checksums = self.objects.iter().flat_map(|o| {
-            o.constructors()
-                .into_iter()
-                .map(|c| (c.checksum_fn_name(), c.checksum()))
+        // The methods of trait interfaces with foreign implementations are also called by Rust,
+        // through the vtable, so they can't be disabled.
+        let method_checks = self.objects.iter().flat_map(|o| {
+            o.methods().into_iter().map(move |m| ChecksumCheck {
+                checksum_fn_name: m.checksum_fn_name().to_string(),
+                checksum: m.checksum(),
+                item: format!("method `{}.{}`", o.name(), m.name()),
+                ffi_func_name: (!o.has_callback_interface())
+                    .then(|| m.ffi_func().name().to_string()),
+            })
         });
-        let callback_method_checksums = self.callback_interfaces.iter().flat_map(|cbi| {
-            cbi.methods().into_iter().filter_map(|m| {
-                if m.checksum_fn_name().is_empty() {
-                    // UDL-based callbacks don't have checksum functions, skip these
-                    None
-                } else {
-                    Some((m.checksum_fn_name(), m.checksum()))
-                }
+        let constructor_checks = self.objects.iter().flat_map(|o| {
+            o.constructors().into_iter().map(move |c| ChecksumCheck {
+                checksum_fn_name: c.checksum_fn_name().to_string(),
+                checksum: c.checksum(),
+                item: format!("constructor `{}.{}`", o.name(), c.name()),
+                ffi_func_name: Some(c.ffi_func().name().to_string()),
             })
         });
-        func_checksums
-            .chain(method_checksums)
-            .chain(constructor_checksums)
-            .chain(callback_method_checksums)
-            .map(|(fn_name, checksum)| (fn_name.to_string(), checksum))
+        let callback_method_checks = self.callback_interfaces.iter().flat_map(|cbi| {
+            cbi.methods().into_iter().filter_map(move |m| {
+                // UDL-based callbacks don't have checksum functions, skip these
+                (!m.checksum_fn_name().is_empty()).then(|| ChecksumCheck {
+                    checksum_fn_name: m.checksum_fn_name().to_string(),
+                    checksum: m.checksum(),
+                    item: format!("callback interface method `{}.{}`", cbi.name(), m.name()),
+                    ffi_func_name: None,
+                })
+            })
+        });
+        func_checks
+            .chain(method_checks)
+            .chain(constructor_checks)
+            .chain(callback_method_checks)
     }
 
     pub fn iter_checksum_ffi_functions(&self) -> impl Iterator<Item = FfiFunction> + '_ {
@@ -992,6 +1189,45 @@ impl ComponentInterface {
     }
 }
 
+/// Serialize the high-level API of the interface, for tools that don't link against this crate.
+///
+/// The FFI functions of each item are included, but the FFI definitions derived from the whole
+/// interface aren't.
+impl Serialize for ComponentInterface {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut errors = self.errors.iter().collect::<Vec<_>>();
+        errors.sort();
+        let mut state = serializer.serialize_struct("ComponentInterface", 11)?;
+        state.serialize_field("namespace", self.namespace())?;
+        state.serialize_field("crate_name", self.crate_name())?;
+        state.serialize_field("docstring", &self.namespace_docstring())?;
+        state.serialize_field("uniffi_contract_version", &self.uniffi_contract_version())?;
+        state.serialize_field("types", &self.iter_types().collect::<Vec<_>>())?;
+        state.serialize_field("records", &self.record_definitions().collect::<Vec<_>>())?;
+        state.serialize_field("enums", &self.enum_definitions().collect::<Vec<_>>())?;
+        state.serialize_field("functions", self.function_definitions())?;
+        state.serialize_field("objects", self.object_definitions())?;
+        state.serialize_field("callback_interfaces", self.callback_interface_definitions())?;
+        state.serialize_field("errors", &errors)?;
+        state.end()
+    }
+}
+
+/// An API checksum that the bindings check when they're loaded
+#[derive(Debug, Clone)]
+pub struct ChecksumCheck {
+    /// The name of the scaffolding function that returns the checksum
+    pub checksum_fn_name: String,
+    /// The checksum that the bindings expect
+    pub checksum: u16,
+    /// A description of the item, like "function `add`", for error messages
+    pub item: String,
+    /// The scaffolding function to disable when the checksum doesn't match, in lenient mode.
+    /// This is `None` for items whose mismatches are always fatal, like callback interface
+    /// methods, which Rust calls into.
+    pub ffi_func_name: Option<String>,
+}
+
 fn get_object<'a>(objects: &'a mut [Object], name: &str) -> Option<&'a mut Object> {
     objects.iter_mut().find(|o| o.name == name)
 }
@@ -1333,4 +1569,83 @@ new definition: Enum {
         let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
         assert_eq!(ci.namespace_docstring().unwrap(), "informative\ndocstring");
     }
+
+    #[test]
+    fn test_serialize() {
+        const UDL: &str = r#"
+            /// The namespace
+            namespace test{
+                [Throws=TestError]
+                string greet(Person person);
+            };
+            dictionary Person {
+                string name;
+                u32? age = null;
+            };
+            [Error]
+            enum TestError { "Oops" };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let json = serde_json::to_value(&ci).unwrap();
+        assert_eq!(json["namespace"], "test");
+        assert_eq!(json["crate_name"], "crate_name");
+        assert_eq!(json["docstring"], "The namespace");
+        assert_eq!(json["errors"], serde_json::json!(["TestError"]));
+        assert_eq!(json["functions"][0]["name"], "greet");
+        assert_eq!(
+            json["functions"][0]["throws"],
+            serde_json::json!({ "Enum": { "module_path": "crate_name", "name": "TestError" } })
+        );
+        assert_eq!(json["records"][0]["fields"][1]["name"], "age");
+        assert_eq!(json["records"][0]["fields"][1]["default"], "Null");
+        assert_eq!(json["enums"][0]["variants"][0]["name"], "Oops");
+    }
+
+    #[test]
+    fn test_checksum_checks() {
+        const UDL: &str = r#"
+            namespace test{
+                u32 add(u32 a, u32 b);
+            };
+            interface Counter {
+                constructor();
+                void increment();
+            };
+            [Trait, WithForeign]
+            interface Logger {
+                void log(string message);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let checks = ci
+            .iter_checksum_checks()
+            .map(|c| (c.item, c.ffi_func_name))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            checks,
+            vec![
+                (
+                    "function `add`".to_string(),
+                    Some("uniffi_crate_name_fn_func_add".to_string())
+                ),
+                (
+                    "method `Counter.increment`".to_string(),
+                    Some("uniffi_crate_name_fn_method_counter_increment".to_string())
+                ),
+                // Rust calls the methods of trait interfaces with foreign implementations, so
+                // they can't be disabled.
+                ("method `Logger.log`".to_string(), None),
+                (
+                    "constructor `Counter.new`".to_string(),
+                    Some("uniffi_crate_name_fn_constructor_counter_new".to_string())
+                ),
+            ]
+        );
+        assert_eq!(
+            ci.iter_checksum_checks()
+                .map(|c| (c.checksum_fn_name, c.checksum))
+                .collect::<Vec<_>>(),
+            ci.iter_checksums().collect::<Vec<_>>()
+        );
+    }
 }
@@ -58,6 +58,7 @@
 //! ```
 
 use anyhow::Result;
+use serde::Serialize;
 use uniffi_meta::Checksum;
 
 use super::callbacks;
@@ -79,11 +80,15 @@ use super::{AsType, ObjectImpl, Type, TypeIterator};
 ///
 /// TODO:
 ///  - maybe "Class" would be a better name than "Object" here?
-#[derive(Debug, Clone, Checksum)]
+#[derive(Debug, Clone, Checksum, Serialize)]
 pub struct Object {
     pub(super) name: String,
     /// How this object is implemented in Rust
     pub(super) imp: ObjectImpl,
+    /// Can only be used from the thread that created it
+    pub(super) thread_affine: bool,
+    /// Lifting the same Rust object twice returns the same foreign object
+    pub(super) stable_identity: bool,
     pub(super) module_path: String,
     pub(super) constructors: Vec<Constructor>,
     pub(super) methods: Vec<Method>,
@@ -139,6 +144,22 @@ impl Object {
         self.methods.iter().any(Method::is_async)
     }
 
+    pub fn has_blocking_method(&self) -> bool {
+        self.methods.iter().any(Method::is_blocking)
+    }
+
+    pub fn is_thread_affine(&self) -> bool {
+        self.thread_affine
+    }
+
+    pub fn has_stable_identity(&self) -> bool {
+        self.stable_identity
+    }
+
+    pub fn has_consuming_method(&self) -> bool {
+        self.methods.iter().any(Method::takes_self_by_value)
+    }
+
     pub fn constructors(&self) -> Vec<&Constructor> {
         self.constructors.iter().collect()
     }
@@ -190,6 +211,21 @@ impl Object {
         self.docstring.as_deref()
     }
 
+    /// Docstring for the generated class, which documents thread-affinity as well
+    pub fn class_docstring(&self) -> Option<String> {
+        if !self.thread_affine {
+            return self.docstring.clone();
+        }
+        let note = "This object can only be used from the thread that created it. \
+            Calling its methods from any other thread results in an error. \
+            Release it on that thread too: if it's released on another thread, \
+            for example by the garbage collector, it's leaked.";
+        Some(match &self.docstring {
+            Some(docstring) => format!("{docstring}\n\n{note}"),
+            None => note.to_string(),
+        })
+    }
+
     pub fn iter_ffi_function_definitions(&self) -> impl Iterator<Item = &FfiFunction> {
         [&self.ffi_func_clone, &self.ffi_func_free]
             .into_iter()
@@ -309,6 +345,8 @@ impl From<uniffi_meta::ObjectMetadata> for Object {
             module_path: meta.module_path,
             name: meta.name,
             imp: meta.imp,
+            thread_affine: meta.thread_affine,
+            stable_identity: meta.stable_identity,
             constructors: Default::default(),
             methods: Default::default(),
             uniffi_traits: Default::default(),
@@ -350,7 +388,7 @@ impl From<uniffi_meta::UniffiTraitMetadata> for UniffiTrait {
 //
 // In the FFI, this will be a function that returns a pointer to an instance
 // of the corresponding object type.
-#[derive(Debug, Clone, Checksum)]
+#[derive(Debug, Clone, Checksum, Serialize)]
 pub struct Constructor {
     pub(super) name: String,
     pub(super) object_name: String,
@@ -457,12 +495,13 @@ impl From<uniffi_meta::ConstructorMetadata> for Constructor {
 //
 // The FFI will represent this as a function whose first/self argument is a
 // `FfiType::RustArcPtr` to the instance.
-#[derive(Debug, Clone, Checksum)]
+#[derive(Debug, Clone, Checksum, Serialize)]
 pub struct Method {
     pub(super) name: String,
     pub(super) object_name: String,
     pub(super) object_module_path: String,
     pub(super) is_async: bool,
+    pub(super) is_blocking: bool,
     pub(super) object_impl: ObjectImpl,
     pub(super) arguments: Vec<Argument>,
     pub(super) return_type: Option<Type>,
@@ -478,6 +517,7 @@ pub struct Method {
     pub(super) docstring: Option<String>,
     pub(super) throws: Option<Type>,
     pub(super) takes_self_by_arc: bool,
+    pub(super) takes_self_by_value: bool,
     pub(super) checksum_fn_name: String,
     // Force a checksum value, or we'll fallback to the trait.
     #[checksum_ignore]
@@ -493,6 +533,11 @@ impl Method {
         self.is_async
     }
 
+    /// Is this a sync method that the bindings should call from a background thread?
+    pub fn is_blocking(&self) -> bool {
+        self.is_blocking
+    }
+
     pub fn arguments(&self) -> Vec<&Argument> {
         self.arguments.iter().collect()
     }
@@ -554,6 +599,11 @@ impl Method {
         self.takes_self_by_arc
     }
 
+    /// Does this method consume the object?  The foreign object can't be used afterwards.
+    pub fn takes_self_by_value(&self) -> bool {
+        self.takes_self_by_value
+    }
+
     pub fn derive_ffi_func(&mut self) -> Result<()> {
         assert!(!self.ffi_func.name().is_empty());
         self.ffi_func.init(
@@ -597,6 +647,7 @@ impl From<uniffi_meta::MethodMetadata> for Method {
             object_name: meta.self_name,
             object_module_path: meta.module_path,
             is_async,
+            is_blocking: meta.is_blocking,
             object_impl: ObjectImpl::Struct, // will be filled in later
             arguments,
             return_type,
@@ -604,6 +655,7 @@ impl From<uniffi_meta::MethodMetadata> for Method {
             docstring: meta.docstring.clone(),
             throws: meta.throws.map(Into::into),
             takes_self_by_arc: meta.takes_self_by_arc,
+            takes_self_by_value: meta.takes_self_by_value,
             checksum_fn_name,
             checksum: meta.checksum,
         }
@@ -627,11 +679,13 @@ impl From<uniffi_meta::TraitMethodMetadata> for Method {
             object_name: meta.trait_name,
             object_module_path: meta.module_path,
             is_async,
+            is_blocking: false,
             arguments,
             return_type,
             docstring: meta.docstring.clone(),
             throws: meta.throws.map(Into::into),
             takes_self_by_arc: meta.takes_self_by_arc,
+            takes_self_by_value: false,
             checksum_fn_name,
             checksum: meta.checksum,
             ffi_func,
@@ -641,7 +695,7 @@ impl From<uniffi_meta::TraitMethodMetadata> for Method {
 }
 
 /// The list of traits we support generating helper methods for.
-#[derive(Clone, Debug, Checksum)]
+#[derive(Clone, Debug, Checksum, Serialize)]
 pub enum UniffiTrait {
     Debug { fmt: Method },
     Display { fmt: Method },
@@ -699,6 +753,10 @@ impl Callable for Constructor {
     fn is_async(&self) -> bool {
         false
     }
+
+    fn is_blocking(&self) -> bool {
+        false
+    }
 }
 
 impl Callable for Method {
@@ -717,6 +775,10 @@ impl Callable for Method {
     fn is_async(&self) -> bool {
         self.is_async
     }
+
+    fn is_blocking(&self) -> bool {
+        self.is_blocking
+    }
 }
 
 #[cfg(test)]
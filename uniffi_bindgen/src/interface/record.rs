@@ -45,6 +45,7 @@
 //! ```
 
 use anyhow::Result;
+use serde::Serialize;
 use uniffi_meta::Checksum;
 
 use super::Literal;
@@ -55,7 +56,7 @@ use super::{AsType, Type, TypeIterator};
 /// In the FFI these are represented as a byte buffer, which one side explicitly
 /// serializes the data into and the other serializes it out of. So I guess they're
 /// kind of like "pass by clone" values.
-#[derive(Debug, Clone, PartialEq, Eq, Checksum)]
+#[derive(Debug, Clone, PartialEq, Eq, Checksum, Serialize)]
 pub struct Record {
     pub(super) name: String,
     pub(super) module_path: String,
@@ -113,7 +114,7 @@ impl TryFrom<uniffi_meta::RecordMetadata> for Record {
 }
 
 // Represents an individual field on a Record.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Checksum)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Checksum, Serialize)]
 pub struct Field {
     pub(super) name: String,
     pub(super) type_: Type,
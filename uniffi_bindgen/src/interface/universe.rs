@@ -104,6 +104,14 @@ impl TypeUniverse {
                 self.add_known_type(key_type)?;
                 self.add_known_type(value_type)?;
             }
+            Type::Function { args, ret } => {
+                for arg in args {
+                    self.add_known_type(arg)?;
+                }
+                if let Some(ret) = ret {
+                    self.add_known_type(ret)?;
+                }
+            }
         }
         Ok(())
     }
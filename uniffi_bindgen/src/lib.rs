@@ -102,9 +102,11 @@ use std::{collections::HashMap, process::Command};
 
 pub mod backend;
 pub mod bindings;
+pub mod compat;
 pub mod interface;
 pub mod library_mode;
 pub mod macro_metadata;
+pub mod plugin;
 pub mod scaffolding;
 
 use bindings::TargetLanguage;
@@ -194,7 +196,11 @@ impl BindingGenerator for BindingGeneratorDefault {
 
     fn check_library_path(&self, library_path: &Utf8Path, cdylib_name: Option<&str>) -> Result<()> {
         for &language in &self.target_languages {
-            if cdylib_name.is_none() && language != TargetLanguage::Swift {
+            if cdylib_name.is_none()
+                && language != TargetLanguage::Swift
+                && language != TargetLanguage::C
+                && language != TargetLanguage::Cpp
+            {
                 bail!("Generate bindings for {language} requires a cdylib, but {library_path} was given");
             }
         }
@@ -264,8 +270,12 @@ pub fn generate_component_scaffolding(
     out_dir_override: Option<&Utf8Path>,
     format_code: bool,
 ) -> Result<()> {
-    let component = parse_udl(udl_file, &crate_name_from_cargo_toml(udl_file)?)?;
-    generate_component_scaffolding_inner(component, udl_file, out_dir_override, format_code)
+    generate_component_scaffolding_inner(
+        &crate_name_from_cargo_toml(udl_file)?,
+        udl_file,
+        out_dir_override,
+        format_code,
+    )
 }
 
 // Generate the infrastructural Rust code for implementing the UDL interface,
@@ -277,21 +287,22 @@ pub fn generate_component_scaffolding_for_crate(
     out_dir_override: Option<&Utf8Path>,
     format_code: bool,
 ) -> Result<()> {
-    let component = parse_udl(udl_file, crate_name)?;
-    generate_component_scaffolding_inner(component, udl_file, out_dir_override, format_code)
+    generate_component_scaffolding_inner(crate_name, udl_file, out_dir_override, format_code)
 }
 
 fn generate_component_scaffolding_inner(
-    component: ComponentInterface,
+    crate_name: &str,
     udl_file: &Utf8Path,
     out_dir_override: Option<&Utf8Path>,
     format_code: bool,
 ) -> Result<()> {
+    let udl = read_udl(udl_file)?;
+    let component = ComponentInterface::from_metadata(uniffi_udl::parse_udl(&udl, crate_name)?)?;
     let file_stem = udl_file.file_stem().context("not a file")?;
     let filename = format!("{file_stem}.uniffi.rs");
     let out_path = get_out_dir(udl_file, out_dir_override)?.join(filename);
     let mut f = File::create(&out_path)?;
-    write!(f, "{}", RustScaffolding::new(&component, file_stem))
+    write!(f, "{}", RustScaffolding::new(&component, file_stem, &udl))
         .context("Failed to write output file")?;
     if format_code {
         format_code_with_rustfmt(&out_path)?;
@@ -329,6 +340,42 @@ pub fn print_repr(library_path: &Utf8Path) -> Result<()> {
     Ok(())
 }
 
+/// The version of the JSON schema written by [print_repr_json].
+///
+/// This is bumped whenever a change to the schema could break its consumers, such as removing or
+/// renaming a field.  Adding fields or enum variants doesn't change the version.
+pub const REPR_JSON_SCHEMA_VERSION: u32 = 1;
+
+/// A machine-readable representation of the interfaces in a library
+#[derive(Serialize)]
+pub struct InterfaceRepr {
+    pub schema_version: u32,
+    /// The metadata items extracted from the library
+    pub metadata: Vec<uniffi_meta::Metadata>,
+    /// The component interface of each crate in the library
+    pub components: Vec<ComponentInterface>,
+}
+
+impl InterfaceRepr {
+    pub fn from_library(library_path: &Utf8Path) -> Result<Self> {
+        Ok(Self {
+            schema_version: REPR_JSON_SCHEMA_VERSION,
+            metadata: macro_metadata::extract_from_library(library_path)?,
+            components: library_mode::find_components(library_path)?,
+        })
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Print the interfaces in a library as JSON, for tools that don't link against this crate.
+pub fn print_repr_json(library_path: &Utf8Path) -> Result<()> {
+    println!("{}", InterfaceRepr::from_library(library_path)?.to_json()?);
+    Ok(())
+}
+
 // Given the path to a UDL file, locate and parse the corresponding Cargo.toml to determine
 // the library crate name.
 // Note that this is largely a copy of code in uniffi_macros/src/util.rs, but sharing it
@@ -397,12 +444,14 @@ fn get_out_dir(udl_file: &Utf8Path, out_dir_override: Option<&Utf8Path>) -> Resu
 }
 
 fn parse_udl(udl_file: &Utf8Path, crate_name: &str) -> Result<ComponentInterface> {
-    let udl = fs::read_to_string(udl_file)
-        .with_context(|| format!("Failed to read UDL from {udl_file}"))?;
-    let group = uniffi_udl::parse_udl(&udl, crate_name)?;
+    let group = uniffi_udl::parse_udl(&read_udl(udl_file)?, crate_name)?;
     ComponentInterface::from_metadata(group)
 }
 
+fn read_udl(udl_file: &Utf8Path) -> Result<String> {
+    fs::read_to_string(udl_file).with_context(|| format!("Failed to read UDL from {udl_file}"))
+}
+
 fn format_code_with_rustfmt(path: &Utf8Path) -> Result<()> {
     let status = Command::new("rustfmt").arg(path).status().map_err(|e| {
         let ctx = match e.kind() {
@@ -438,10 +487,29 @@ fn load_initial_config<Config: DeserializeOwned>(
     crate_root: &Utf8Path,
     config_file_override: Option<&Utf8Path>,
 ) -> Result<Config> {
-    let mut config = load_toml_file(Some(crate_root.join("uniffi.toml").as_path()))
+    let config = load_toml_file(Some(crate_root.join("uniffi.toml").as_path()))
         .context("default config")?
         .unwrap_or(toml::value::Table::default());
+    apply_config_override(config, config_file_override)
+}
+
+/// Load the `uniffi.toml` contents embedded in a library, merge TOML trees with
+/// `config_file_override` if specified.
+fn load_embedded_config<Config: DeserializeOwned>(
+    contents: Option<&str>,
+    config_file_override: Option<&Utf8Path>,
+) -> Result<Config> {
+    let config = match contents {
+        Some(contents) => toml::de::from_str(contents).context("parse embedded uniffi.toml")?,
+        None => toml::value::Table::default(),
+    };
+    apply_config_override(config, config_file_override)
+}
 
+fn apply_config_override<Config: DeserializeOwned>(
+    mut config: toml::value::Table,
+    config_file_override: Option<&Utf8Path>,
+) -> Result<Config> {
     let override_config = load_toml_file(config_file_override).context("override config")?;
     if let Some(override_config) = override_config {
         merge_toml(&mut config, override_config);
@@ -478,6 +546,11 @@ impl BindingsConfig for Config {
         self.bindings.swift.update_from_ci(ci);
         self.bindings.python.update_from_ci(ci);
         self.bindings.ruby.update_from_ci(ci);
+        self.bindings.c.update_from_ci(ci);
+        self.bindings.cpp.update_from_ci(ci);
+        self.bindings.go.update_from_ci(ci);
+        self.bindings.csharp.update_from_ci(ci);
+        self.bindings.typescript.update_from_ci(ci);
     }
 
     fn update_from_cdylib_name(&mut self, cdylib_name: &str) {
@@ -485,6 +558,13 @@ impl BindingsConfig for Config {
         self.bindings.swift.update_from_cdylib_name(cdylib_name);
         self.bindings.python.update_from_cdylib_name(cdylib_name);
         self.bindings.ruby.update_from_cdylib_name(cdylib_name);
+        self.bindings.c.update_from_cdylib_name(cdylib_name);
+        self.bindings.cpp.update_from_cdylib_name(cdylib_name);
+        self.bindings.go.update_from_cdylib_name(cdylib_name);
+        self.bindings.csharp.update_from_cdylib_name(cdylib_name);
+        self.bindings
+            .typescript
+            .update_from_cdylib_name(cdylib_name);
     }
 
     fn update_from_dependency_configs(&mut self, config_map: HashMap<&str, &Self>) {
@@ -512,6 +592,36 @@ impl BindingsConfig for Config {
                 .map(|(key, config)| (*key, &config.bindings.ruby))
                 .collect(),
         );
+        self.bindings.c.update_from_dependency_configs(
+            config_map
+                .iter()
+                .map(|(key, config)| (*key, &config.bindings.c))
+                .collect(),
+        );
+        self.bindings.cpp.update_from_dependency_configs(
+            config_map
+                .iter()
+                .map(|(key, config)| (*key, &config.bindings.cpp))
+                .collect(),
+        );
+        self.bindings.go.update_from_dependency_configs(
+            config_map
+                .iter()
+                .map(|(key, config)| (*key, &config.bindings.go))
+                .collect(),
+        );
+        self.bindings.csharp.update_from_dependency_configs(
+            config_map
+                .iter()
+                .map(|(key, config)| (*key, &config.bindings.csharp))
+                .collect(),
+        );
+        self.bindings.typescript.update_from_dependency_configs(
+            config_map
+                .iter()
+                .map(|(key, config)| (*key, &config.bindings.typescript))
+                .collect(),
+        );
     }
 }
 
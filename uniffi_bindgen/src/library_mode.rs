@@ -7,7 +7,10 @@
 /// Traditionally, users would invoke `uniffi-bindgen generate` to generate bindings for a single crate, passing it the UDL file, config file, etc.
 ///
 /// library_mode is a new way to generate bindings for multiple crates at once.
-/// Users pass the path to the build cdylib file and UniFFI figures everything out, leveraging `cargo_metadata`, the metadata UniFFI stores inside exported symbols in the dylib, etc.
+/// Users pass the path to the build cdylib file and UniFFI figures everything out, leveraging the metadata UniFFI stores inside exported symbols in the dylib.
+/// With uniffi's `embed-sources` feature, that metadata includes each crate's UDL file and `uniffi.toml`, so no Cargo
+/// workspace is needed.  For libraries built without them, UniFFI falls back to finding the crate sources with
+/// `cargo_metadata`.
 ///
 /// This brings several advantages.:
 ///   - No more need to specify the dylib in the `uniffi.toml` file(s)
@@ -16,16 +19,13 @@
 ///   - UniFFI can figure out the package/module names for each crate, eliminating the external
 ///     package maps.
 use crate::{
-    bindings::TargetLanguage, load_initial_config, macro_metadata, BindingGenerator,
-    BindingGeneratorDefault, BindingsConfig, ComponentInterface, Result,
+    bindings::TargetLanguage, load_embedded_config, load_initial_config, macro_metadata,
+    BindingGenerator, BindingGeneratorDefault, BindingsConfig, ComponentInterface, Result,
 };
 use anyhow::{bail, Context};
-use camino::Utf8Path;
+use camino::{Utf8Path, Utf8PathBuf};
 use cargo_metadata::{MetadataCommand, Package};
-use std::{
-    collections::{HashMap, HashSet},
-    fs,
-};
+use std::{collections::HashMap, fs};
 use uniffi_meta::{
     create_metadata_groups, fixup_external_type, group_metadata, Metadata, MetadataGroup,
 };
@@ -63,18 +63,10 @@ pub fn generate_external_bindings<T: BindingGenerator>(
     config_file_override: Option<&Utf8Path>,
     out_dir: &Utf8Path,
 ) -> Result<Vec<Source<T::Config>>> {
-    let cargo_metadata = MetadataCommand::new()
-        .exec()
-        .context("error running cargo metadata")?;
     let cdylib_name = calc_cdylib_name(library_path);
     binding_generator.check_library_path(library_path, cdylib_name)?;
 
-    let mut sources = find_sources(
-        &cargo_metadata,
-        library_path,
-        cdylib_name,
-        config_file_override,
-    )?;
+    let mut sources = find_sources(library_path, cdylib_name, config_file_override)?;
     for i in 0..sources.len() {
         // Partition up the sources list because we're eventually going to call
         // `update_from_dependency_configs()` which requires an exclusive reference to one source and
@@ -83,12 +75,10 @@ pub fn generate_external_bindings<T: BindingGenerator>(
         let (source, sources_after) = rest.split_first_mut().unwrap();
         let other_sources = sources_before.iter().chain(sources_after.iter());
         // Calculate which configs come from dependent crates
-        let dependencies =
-            HashSet::<&str>::from_iter(source.package.dependencies.iter().map(|d| d.name.as_str()));
         let config_map: HashMap<&str, &T::Config> = other_sources
             .filter_map(|s| {
-                dependencies
-                    .contains(s.package.name.as_str())
+                source
+                    .depends_on(s)
                     .then_some((s.crate_name.as_str(), &s.config))
             })
             .collect();
@@ -118,15 +108,36 @@ pub fn generate_external_bindings<T: BindingGenerator>(
 // A single source that we generate bindings for
 #[derive(Debug)]
 pub struct Source<Config: BindingsConfig> {
-    pub package: Package,
+    /// The crate's package, if `cargo metadata` was needed to find the crate's sources.  This is
+    /// `None` when the library embeds the crate's `uniffi.toml`.
+    pub package: Option<Package>,
     pub crate_name: String,
     pub ci: ComponentInterface,
     pub config: Config,
 }
 
+impl<Config: BindingsConfig> Source<Config> {
+    // Is `other` a dependency of this crate?
+    //
+    // This uses the cargo dependencies when we have them.  Otherwise, the crates that this one
+    // uses external types from are the only dependencies that matter to the bindings.
+    fn depends_on(&self, other: &Source<Config>) -> bool {
+        match (&self.package, &other.package) {
+            (Some(package), Some(other_package)) => package
+                .dependencies
+                .iter()
+                .any(|d| d.name == other_package.name),
+            _ => self
+                .ci
+                .iter_external_types()
+                .any(|(_, crate_name, _, _)| crate_name == other.crate_name),
+        }
+    }
+}
+
 // If `library_path` is a C dynamic library, return its name
 pub fn calc_cdylib_name(library_path: &Utf8Path) -> Option<&str> {
-    let cdylib_extensions = [".so", ".dll", ".dylib"];
+    let cdylib_extensions = [".so", ".dll", ".dylib", ".wasm"];
     let filename = library_path.file_name()?;
     let filename = filename.strip_prefix("lib").unwrap_or(filename);
     for ext in cdylib_extensions {
@@ -138,12 +149,132 @@ pub fn calc_cdylib_name(library_path: &Utf8Path) -> Option<&str> {
 }
 
 fn find_sources<Config: BindingsConfig>(
-    cargo_metadata: &cargo_metadata::Metadata,
     library_path: &Utf8Path,
     cdylib_name: Option<&str>,
     config_file_override: Option<&Utf8Path>,
 ) -> Result<Vec<Source<Config>>> {
-    let items = macro_metadata::extract_from_library(library_path)?;
+    let mut cargo_metadata = LazyCargoMetadata::default();
+    let LibraryComponents {
+        components,
+        mut configs,
+    } = load_components(library_path, |crate_name| {
+        crate_root(&cargo_metadata.find_package(crate_name)?)
+    })?;
+    components
+        .into_iter()
+        .map(|ci| {
+            let crate_name = ci.crate_name().to_string();
+            let (package, mut config) = match configs.remove(&crate_name) {
+                Some(contents) => (
+                    None,
+                    load_embedded_config::<Config>(contents.as_deref(), config_file_override)?,
+                ),
+                None => {
+                    let package = cargo_metadata.find_package(&crate_name)?;
+                    let config = load_initial_config::<Config>(
+                        &crate_root(&package)?,
+                        config_file_override,
+                    )?;
+                    (Some(package), config)
+                }
+            };
+            if let Some(cdylib_name) = cdylib_name {
+                config.update_from_cdylib_name(cdylib_name);
+            }
+            config.update_from_ci(&ci);
+            Ok(Source {
+                config,
+                crate_name,
+                ci,
+                package,
+            })
+        })
+        .collect()
+}
+
+/// Find the component interfaces for the crates in a library
+///
+/// This only runs `cargo metadata` when one of the crates uses a UDL file that isn't embedded in
+/// the library, since the UDL is then loaded from the crate's source directory.
+pub fn find_components(library_path: &Utf8Path) -> Result<Vec<ComponentInterface>> {
+    let mut cargo_metadata = LazyCargoMetadata::default();
+    Ok(load_components(library_path, |crate_name| {
+        crate_root(&cargo_metadata.find_package(crate_name)?)
+    })?
+    .components)
+}
+
+/// Find the component interfaces for the crates in a library, only using the UDL files embedded in
+/// it
+///
+/// This fails for crates that use a UDL file when the library was built without uniffi's
+/// `embed-sources` feature, rather than reading the UDL from the current workspace, which might
+/// not be the one the library was built from.
+pub fn find_embedded_components(library_path: &Utf8Path) -> Result<Vec<ComponentInterface>> {
+    embedded_components(
+        macro_metadata::extract_from_library(library_path)?,
+        library_path.as_str(),
+    )
+}
+
+/// Create the component interfaces from a library's metadata, without looking for UDL files
+pub(crate) fn embedded_components(
+    items: Vec<Metadata>,
+    library_name: &str,
+) -> Result<Vec<ComponentInterface>> {
+    Ok(components_from_metadata(items, |crate_name| {
+        bail!(
+            "{library_name} doesn't embed the UDL file for {crate_name}, build it with the \
+             `embed-sources` feature of the `uniffi` crate"
+        )
+    })?
+    .components)
+}
+
+// The components in a library
+struct LibraryComponents {
+    components: Vec<ComponentInterface>,
+    // Maps crate names to the `uniffi.toml` contents embedded in the library, which are `None`
+    // for crates without a `uniffi.toml`.  Crates built without embedding their config are
+    // missing from the map.
+    configs: HashMap<String, Option<String>>,
+}
+
+/// Create a `ComponentInterface` for each crate with metadata in the library
+///
+/// `find_crate_root` is called to find the source directory of crates that use a UDL file, when
+/// the library doesn't embed the UDL.
+fn load_components(
+    library_path: &Utf8Path,
+    find_crate_root: impl FnMut(&str) -> Result<Utf8PathBuf>,
+) -> Result<LibraryComponents> {
+    components_from_metadata(
+        macro_metadata::extract_from_library(library_path)?,
+        find_crate_root,
+    )
+}
+
+fn components_from_metadata(
+    items: Vec<Metadata>,
+    mut find_crate_root: impl FnMut(&str) -> Result<Utf8PathBuf>,
+) -> Result<LibraryComponents> {
+    // Split out the embedded source files, which aren't part of the interface.
+    let mut udl_sources = HashMap::new();
+    let mut configs = HashMap::new();
+    let items: Vec<_> = items
+        .into_iter()
+        .filter_map(|item| match item {
+            Metadata::UdlSource(udl) => {
+                udl_sources.insert(calc_crate_name(&udl.module_path), udl.contents);
+                None
+            }
+            Metadata::ConfigFile(config) => {
+                configs.insert(calc_crate_name(&config.module_path), config.contents);
+                None
+            }
+            item => Some(item),
+        })
+        .collect();
     let mut metadata_groups = create_metadata_groups(&items);
     group_metadata(&mut metadata_groups, items)?;
 
@@ -152,13 +283,11 @@ fn find_sources<Config: BindingsConfig>(
     let mut udl_items: HashMap<String, MetadataGroup> = HashMap::new();
 
     for group in metadata_groups.values() {
-        let package = find_package_by_crate_name(cargo_metadata, &group.namespace.crate_name)?;
-        let crate_root = package
-            .manifest_path
-            .parent()
-            .context("manifest path has no parent")?;
         let crate_name = group.namespace.crate_name.clone();
-        if let Some(mut metadata_group) = load_udl_metadata(group, crate_root, &crate_name)? {
+        let embedded_udl = udl_sources.remove(&crate_name);
+        if let Some(mut metadata_group) =
+            load_udl_metadata(group, &crate_name, embedded_udl, &mut find_crate_root)?
+        {
             // fixup the items.
             metadata_group.items = metadata_group
                 .items
@@ -173,33 +302,56 @@ fn find_sources<Config: BindingsConfig>(
         };
     }
 
-    metadata_groups
+    let components = metadata_groups
         .into_values()
         .map(|group| {
-            let package = find_package_by_crate_name(cargo_metadata, &group.namespace.crate_name)?;
-            let crate_root = package
-                .manifest_path
-                .parent()
-                .context("manifest path has no parent")?;
             let crate_name = group.namespace.crate_name.clone();
             let mut ci = ComponentInterface::new(&crate_name);
             if let Some(metadata) = udl_items.remove(&crate_name) {
                 ci.add_metadata(metadata)?;
             };
             ci.add_metadata(group)?;
-            let mut config = load_initial_config::<Config>(crate_root, config_file_override)?;
-            if let Some(cdylib_name) = cdylib_name {
-                config.update_from_cdylib_name(cdylib_name);
-            }
-            config.update_from_ci(&ci);
-            Ok(Source {
-                config,
-                crate_name,
-                ci,
-                package,
-            })
+            Ok(ci)
         })
-        .collect()
+        .collect::<Result<_>>()?;
+    Ok(LibraryComponents {
+        components,
+        configs,
+    })
+}
+
+fn calc_crate_name(module_path: &str) -> String {
+    module_path.split("::").next().unwrap().to_string()
+}
+
+// Runs `cargo metadata` the first time a package is needed, since that's only the case for
+// libraries that don't embed the sources of all their crates.
+#[derive(Default)]
+struct LazyCargoMetadata(Option<cargo_metadata::Metadata>);
+
+impl LazyCargoMetadata {
+    fn find_package(&mut self, crate_name: &str) -> Result<Package> {
+        let metadata = match &mut self.0 {
+            Some(metadata) => metadata,
+            None => self
+                .0
+                .insert(MetadataCommand::new().exec().with_context(|| {
+                    format!(
+                        "error running cargo metadata, which is needed because the library \
+                             doesn't embed the UDL or config for {crate_name}"
+                    )
+                })?),
+        };
+        find_package_by_crate_name(metadata, crate_name)
+    }
+}
+
+fn crate_root(package: &Package) -> Result<Utf8PathBuf> {
+    Ok(package
+        .manifest_path
+        .parent()
+        .context("manifest path has no parent")?
+        .to_path_buf())
 }
 
 fn find_package_by_crate_name(
@@ -223,8 +375,9 @@ fn find_package_by_crate_name(
 
 fn load_udl_metadata(
     group: &MetadataGroup,
-    crate_root: &Utf8Path,
     crate_name: &str,
+    embedded_udl: Option<String>,
+    find_crate_root: impl FnOnce(&str) -> Result<Utf8PathBuf>,
 ) -> Result<Option<MetadataGroup>> {
     let udl_items = group
         .items
@@ -246,17 +399,23 @@ fn load_udl_metadata(
                     crate_name
                 );
             }
-            let ci_name = &udl_items[0].file_stub;
-            let ci_path = crate_root.join("src").join(format!("{ci_name}.udl"));
-            if ci_path.exists() {
-                let udl = fs::read_to_string(ci_path)?;
-                let udl_group = uniffi_udl::parse_udl(&udl, crate_name)?;
-                Ok(Some(udl_group))
-            } else {
-                bail!("{ci_path} not found");
-            }
+            let udl = match embedded_udl {
+                Some(udl) => udl,
+                None => {
+                    let ci_name = &udl_items[0].file_stub;
+                    let ci_path = find_crate_root(crate_name)?
+                        .join("src")
+                        .join(format!("{ci_name}.udl"));
+                    if !ci_path.exists() {
+                        bail!("{ci_path} not found");
+                    }
+                    fs::read_to_string(ci_path)?
+                }
+            };
+            let udl_group = uniffi_udl::parse_udl(&udl, crate_name)?;
+            Ok(Some(udl_group))
         }
-        n => bail!("{n} UDL files found for {crate_root}"),
+        n => bail!("{n} UDL files found for {crate_name}"),
     }
 }
 
@@ -278,6 +437,10 @@ mod test {
             "uniffi",
             calc_cdylib_name("/path/to/uniffi.dll".into()).unwrap()
         );
+        assert_eq!(
+            "uniffi",
+            calc_cdylib_name("/path/to/uniffi.wasm".into()).unwrap()
+        );
     }
 
     /// Right now we unconditionally strip the `lib` prefix.
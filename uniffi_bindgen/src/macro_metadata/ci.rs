@@ -76,7 +76,7 @@ fn add_enum_to_ci(
 fn add_item_to_ci(iface: &mut ComponentInterface, item: Metadata) -> anyhow::Result<()> {
     match item {
         Metadata::Namespace(_) => unreachable!(),
-        Metadata::UdlFile(_) => (),
+        Metadata::UdlFile(_) | Metadata::UdlSource(_) | Metadata::ConfigFile(_) => (),
         Metadata::Func(meta) => {
             iface.add_function_definition(meta.into())?;
         }
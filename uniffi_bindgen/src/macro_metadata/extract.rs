@@ -15,6 +15,8 @@ use goblin::{
 use std::collections::HashSet;
 use uniffi_meta::Metadata;
 
+use super::wasm::{WasmModule, WASM_MAGIC};
+
 /// Extract metadata written by the `uniffi::export` macro from a library file
 ///
 /// In addition to generating the scaffolding, that macro and also encodes the
@@ -25,6 +27,9 @@ pub fn extract_from_library(path: &Utf8Path) -> anyhow::Result<Vec<Metadata>> {
 }
 
 fn extract_from_bytes(file_data: &[u8]) -> anyhow::Result<Vec<Metadata>> {
+    if file_data.starts_with(WASM_MAGIC) {
+        return extract_from_wasm(WasmModule::parse(file_data)?, file_data);
+    }
     match Object::parse(file_data)? {
         Object::Elf(elf) => extract_from_elf(elf, file_data),
         Object::PE(pe) => extract_from_pe(pe, file_data),
@@ -118,6 +123,17 @@ pub fn extract_from_macho(macho: MachO<'_>, file_data: &[u8]) -> anyhow::Result<
     Ok(extracted.into_metadata())
 }
 
+pub fn extract_from_wasm(wasm: WasmModule, file_data: &[u8]) -> anyhow::Result<Vec<Metadata>> {
+    let mut extracted = ExtractedItems::new();
+    for name in wasm
+        .global_export_names()
+        .filter(|name| is_metadata_symbol(name))
+    {
+        extracted.extract_item(name, file_data, wasm.static_file_offset(name)?)?;
+    }
+    Ok(extracted.into_metadata())
+}
+
 pub fn extract_from_archive(
     archive: Archive<'_>,
     file_data: &[u8],
@@ -8,6 +8,7 @@ use camino::Utf8Path;
 
 mod ci;
 mod extract;
+mod wasm;
 
 pub use ci::{add_group_to_ci, add_to_ci};
 pub use extract::extract_from_library;
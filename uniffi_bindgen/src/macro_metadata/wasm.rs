@@ -0,0 +1,388 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Just enough of a WebAssembly module parser to find the metadata statics.
+//!
+//! `goblin` doesn't handle wasm modules, but the binary format is simple to walk.  When a
+//! `#[no_mangle]` static is exported from a wasm module, rustc exports an immutable `i32` global
+//! holding the static's address in linear memory.  The static's bytes are in the active data
+//! segment that gets copied to that address when the module is instantiated.
+//!
+//! See <https://webassembly.github.io/spec/core/binary/modules.html> for the format.
+
+use anyhow::{bail, ensure, Context, Result};
+
+pub const WASM_MAGIC: &[u8] = b"\0asm";
+
+const SECTION_IMPORT: u8 = 2;
+const SECTION_GLOBAL: u8 = 6;
+const SECTION_EXPORT: u8 = 7;
+const SECTION_DATA: u8 = 11;
+
+const EXTERNAL_FUNC: u8 = 0;
+const EXTERNAL_TABLE: u8 = 1;
+const EXTERNAL_MEMORY: u8 = 2;
+const EXTERNAL_GLOBAL: u8 = 3;
+const EXTERNAL_TAG: u8 = 4;
+
+/// The parts of a wasm module needed to find the data for an exported static.
+#[derive(Debug, Default)]
+pub struct WasmModule {
+    /// Number of imported globals, which come before the module's own globals in the index space
+    imported_globals: u32,
+    /// Initial values of the module's own globals, if they're constants
+    globals: Vec<Option<i64>>,
+    /// `(name, global index)` for each exported global
+    global_exports: Vec<(String, u32)>,
+    /// Active data segments
+    data_segments: Vec<DataSegment>,
+}
+
+#[derive(Debug)]
+struct DataSegment {
+    /// Address in linear memory that the segment is copied to
+    address: u64,
+    /// Offset of the segment's bytes in the file
+    file_offset: usize,
+    len: usize,
+}
+
+impl WasmModule {
+    pub fn parse(file_data: &[u8]) -> Result<Self> {
+        let mut reader = WasmReader::new(file_data);
+        ensure!(
+            reader.read_bytes(4)? == WASM_MAGIC,
+            "Not a WebAssembly module"
+        );
+        let version = u32::from_le_bytes(reader.read_bytes(4)?.try_into().unwrap());
+        ensure!(version == 1, "Unsupported WebAssembly version: {version}");
+
+        let mut module = Self::default();
+        while reader.has_remaining() {
+            let id = reader.read_u8()?;
+            let size = reader.read_u32()? as usize;
+            let start = reader.position;
+            reader.read_bytes(size)?;
+            // Positions are relative to the start of the file, for the data segment offsets
+            let mut section = WasmReader {
+                data: &file_data[..start + size],
+                position: start,
+            };
+            match id {
+                SECTION_IMPORT => module.read_imports(&mut section)?,
+                SECTION_GLOBAL => module.read_globals(&mut section)?,
+                SECTION_EXPORT => module.read_exports(&mut section)?,
+                SECTION_DATA => module.read_data(&mut section)?,
+                // Custom sections and the code don't matter here
+                _ => (),
+            }
+        }
+        Ok(module)
+    }
+
+    /// The names of the exported globals.
+    pub fn global_export_names(&self) -> impl Iterator<Item = &str> {
+        self.global_exports.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Get the file offset of the data for an exported static.
+    pub fn static_file_offset(&self, name: &str) -> Result<usize> {
+        let (_, index) = self
+            .global_exports
+            .iter()
+            .find(|(n, _)| n == name)
+            .with_context(|| format!("No exported global named `{name}`"))?;
+        let address = index
+            .checked_sub(self.imported_globals)
+            .and_then(|i| self.globals.get(i as usize).copied().flatten())
+            .with_context(|| format!("`{name}` isn't a constant global"))?;
+        let address = u64::try_from(address).context("Negative static address")?;
+        self.data_segments
+            .iter()
+            .find(|seg| address >= seg.address && address < seg.address + seg.len as u64)
+            .map(|seg| seg.file_offset + (address - seg.address) as usize)
+            .with_context(|| format!("`{name}` isn't in an active data segment"))
+    }
+
+    fn read_imports(&mut self, reader: &mut WasmReader<'_>) -> Result<()> {
+        for _ in 0..reader.read_u32()? {
+            reader.read_name()?;
+            reader.read_name()?;
+            match reader.read_u8()? {
+                EXTERNAL_FUNC => {
+                    reader.read_u32()?;
+                }
+                EXTERNAL_TABLE => {
+                    reader.read_u8()?;
+                    reader.read_limits()?;
+                }
+                EXTERNAL_MEMORY => reader.read_limits()?,
+                EXTERNAL_GLOBAL => {
+                    reader.read_bytes(2)?;
+                    self.imported_globals += 1;
+                }
+                EXTERNAL_TAG => {
+                    reader.read_u8()?;
+                    reader.read_u32()?;
+                }
+                kind => bail!("Unknown import kind: {kind}"),
+            }
+        }
+        Ok(())
+    }
+
+    fn read_globals(&mut self, reader: &mut WasmReader<'_>) -> Result<()> {
+        for _ in 0..reader.read_u32()? {
+            // Value type and mutability
+            reader.read_bytes(2)?;
+            let value = reader.read_const_expr()?;
+            self.globals.push(value);
+        }
+        Ok(())
+    }
+
+    fn read_exports(&mut self, reader: &mut WasmReader<'_>) -> Result<()> {
+        for _ in 0..reader.read_u32()? {
+            let name = reader.read_name()?;
+            let kind = reader.read_u8()?;
+            let index = reader.read_u32()?;
+            if kind == EXTERNAL_GLOBAL {
+                self.global_exports.push((name.to_string(), index));
+            }
+        }
+        Ok(())
+    }
+
+    fn read_data(&mut self, reader: &mut WasmReader<'_>) -> Result<()> {
+        for _ in 0..reader.read_u32()? {
+            let address = match reader.read_u32()? {
+                // Active segment in memory 0
+                0 => reader.read_const_expr()?,
+                // Passive segment, which is only copied by `memory.init`
+                1 => None,
+                // Active segment with an explicit memory index
+                2 => {
+                    let memory = reader.read_u32()?;
+                    let address = reader.read_const_expr()?;
+                    address.filter(|_| memory == 0)
+                }
+                flags => bail!("Unknown data segment flags: {flags}"),
+            };
+            let len = reader.read_u32()? as usize;
+            let file_offset = reader.position;
+            reader.read_bytes(len)?;
+            if let Some(address) = address.and_then(|a| u64::try_from(a).ok()) {
+                self.data_segments.push(DataSegment {
+                    address,
+                    file_offset,
+                    len,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+struct WasmReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> WasmReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    fn has_remaining(&self) -> bool {
+        self.position < self.data.len()
+    }
+
+    fn read_bytes(&mut self, count: usize) -> Result<&'a [u8]> {
+        let end = self
+            .position
+            .checked_add(count)
+            .filter(|end| *end <= self.data.len())
+            .context("Unexpected end of WebAssembly module")?;
+        let bytes = &self.data[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> Result<u8> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_uleb128(&mut self) -> Result<u64> {
+        let mut result = 0;
+        let mut shift = 0;
+        loop {
+            let byte = self.read_u8()?;
+            ensure!(shift < 64, "LEB128 value is too large");
+            result |= u64::from(byte & 0x7f) << shift;
+            shift += 7;
+            if byte & 0x80 == 0 {
+                return Ok(result);
+            }
+        }
+    }
+
+    fn read_sleb128(&mut self) -> Result<i64> {
+        let mut result = 0;
+        let mut shift = 0;
+        loop {
+            let byte = self.read_u8()?;
+            ensure!(shift < 64, "LEB128 value is too large");
+            result |= i64::from(byte & 0x7f) << shift;
+            shift += 7;
+            if byte & 0x80 == 0 {
+                if shift < 64 && byte & 0x40 != 0 {
+                    result |= -1 << shift;
+                }
+                return Ok(result);
+            }
+        }
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        u32::try_from(self.read_uleb128()?).context("Integer is too large for a u32")
+    }
+
+    fn read_name(&mut self) -> Result<&'a str> {
+        let len = self.read_u32()? as usize;
+        std::str::from_utf8(self.read_bytes(len)?).context("Invalid UTF-8 name")
+    }
+
+    fn read_limits(&mut self) -> Result<()> {
+        let flags = self.read_u8()?;
+        self.read_uleb128()?;
+        if flags & 1 != 0 {
+            self.read_uleb128()?;
+        }
+        Ok(())
+    }
+
+    /// Read a constant expression, returning its value if it's an integer constant.
+    fn read_const_expr(&mut self) -> Result<Option<i64>> {
+        let mut value = None;
+        loop {
+            match self.read_u8()? {
+                // end
+                0x0b => return Ok(value),
+                // i32.const, where addresses are unsigned
+                0x41 => value = Some(i64::from(self.read_sleb128()? as i32 as u32)),
+                // i64.const
+                0x42 => value = Some(self.read_sleb128()?),
+                // f32.const and f64.const
+                0x43 => {
+                    self.read_bytes(4)?;
+                    value = None;
+                }
+                0x44 => {
+                    self.read_bytes(8)?;
+                    value = None;
+                }
+                // global.get and ref.func
+                0x23 | 0xd2 => {
+                    self.read_uleb128()?;
+                    value = None;
+                }
+                // ref.null
+                0xd0 => {
+                    self.read_u8()?;
+                    value = None;
+                }
+                opcode => bail!("Unsupported opcode in constant expression: {opcode:#x}"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn uleb128(mut value: usize) -> Vec<u8> {
+        let mut bytes = vec![];
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                bytes.push(byte);
+                return bytes;
+            }
+            bytes.push(byte | 0x80);
+        }
+    }
+
+    fn section(id: u8, items: &[Vec<u8>]) -> Vec<u8> {
+        let mut contents = uleb128(items.len());
+        contents.extend(items.concat());
+        let mut bytes = vec![id];
+        bytes.extend(uleb128(contents.len()));
+        bytes.extend(contents);
+        bytes
+    }
+
+    fn name(s: &str) -> Vec<u8> {
+        [uleb128(s.len()), s.as_bytes().to_vec()].concat()
+    }
+
+    #[test]
+    fn finds_exported_statics() {
+        let module = [
+            b"\0asm\x01\0\0\0".to_vec(),
+            // An imported global, which shifts the index of the module's own globals
+            section(
+                SECTION_IMPORT,
+                &[[name("env"), name("base"), vec![EXTERNAL_GLOBAL, 0x7f, 0]].concat()],
+            ),
+            // `i32.const 1024` and `i32.const -1`
+            section(
+                SECTION_GLOBAL,
+                &[
+                    vec![0x7f, 0, 0x41, 0x80, 0x08, 0x0b],
+                    vec![0x7f, 0, 0x41, 0x7f, 0x0b],
+                ],
+            ),
+            section(
+                SECTION_EXPORT,
+                &[
+                    [name("memory"), vec![EXTERNAL_MEMORY, 0]].concat(),
+                    [name("UNIFFI_META_TEST"), vec![EXTERNAL_GLOBAL, 1]].concat(),
+                    [name("OTHER"), vec![EXTERNAL_GLOBAL, 2]].concat(),
+                ],
+            ),
+            // A passive segment, then an active one at address 1000
+            section(
+                SECTION_DATA,
+                &[
+                    [vec![1], name("passive")].concat(),
+                    [
+                        vec![0, 0x41, 0xe8, 0x07, 0x0b, 30],
+                        vec![0; 24],
+                        b"static".to_vec(),
+                    ]
+                    .concat(),
+                ],
+            ),
+        ]
+        .concat();
+
+        let wasm = WasmModule::parse(&module).unwrap();
+        assert_eq!(
+            wasm.global_export_names().collect::<Vec<_>>(),
+            ["UNIFFI_META_TEST", "OTHER"]
+        );
+        let offset = wasm.static_file_offset("UNIFFI_META_TEST").unwrap();
+        assert_eq!(&module[offset..offset + 6], b"static");
+        assert!(wasm.static_file_offset("OTHER").is_err());
+        assert!(wasm.static_file_offset("MISSING").is_err());
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        assert!(WasmModule::parse(b"\x7fELF").is_err());
+        assert!(WasmModule::parse(b"\0asm\x02\0\0\0").is_err());
+    }
+}
@@ -0,0 +1,247 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Out-of-process binding generators
+//!
+//! A plugin is an executable that generates bindings for a language that isn't built into
+//! `uniffi-bindgen`, selected with `uniffi-bindgen generate --language plugin:<exe>`.  This lets
+//! backends be written in any language, and be maintained and released separately.
+//!
+//! For each component, `uniffi-bindgen` runs the plugin and writes a [`PluginRequest`] to its
+//! stdin as JSON.  The plugin then writes a [`PluginResponse`] to its stdout as JSON, listing the
+//! files to generate, and exits.  `uniffi-bindgen` writes those files to the output directory.
+//! Anything the plugin writes to stderr is passed through, and exiting with a non-zero status
+//! fails the generation.
+//!
+//! The request looks like this:
+//!
+//! ```json
+//! {
+//!   "protocol_version": 1,
+//!   "component": { "namespace": "arithmetic", ... },
+//!   "config": { "dart": { "module_name": "arithmetic" } },
+//!   "cdylib_name": "arithmetical",
+//!   "dependency_configs": { "other_crate": { "dart": { ... } } }
+//! }
+//! ```
+//!
+//! - `component` is the [`ComponentInterface`], in the same format as the `components` of
+//!   `uniffi-bindgen print-repr --format json`.
+//! - `config` is the `[bindings]` table of the crate's `uniffi.toml`, merged with the `--config`
+//!   file.  Plugins should read their settings from a table named after their language.
+//! - `cdylib_name` is the name of the library to load, when it's known.
+//! - `dependency_configs` maps the crate names of the dependencies of the crate to their
+//!   `[bindings]` tables, in library mode.
+//!
+//! The response looks like this:
+//!
+//! ```json
+//! {
+//!   "files": [
+//!     { "path": "arithmetic.dart", "contents": "..." }
+//!   ]
+//! }
+//! ```
+//!
+//! Paths are relative to the output directory, and can't leave it.
+
+use std::{
+    collections::HashMap,
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use anyhow::{bail, Context, Result};
+use camino::{Utf8Component, Utf8Path, Utf8PathBuf};
+use fs_err as fs;
+use serde::{Deserialize, Serialize};
+
+use crate::{BindingGenerator, BindingsConfig, ComponentInterface};
+
+/// The version of the plugin protocol.
+///
+/// This follows the same rules as [`crate::REPR_JSON_SCHEMA_VERSION`]: it's bumped whenever a
+/// change to the request or response could break plugins.
+pub const PLUGIN_PROTOCOL_VERSION: u32 = 1;
+
+/// The prefix of the `--language` values that name a plugin.
+pub const PLUGIN_LANGUAGE_PREFIX: &str = "plugin:";
+
+/// The config passed to plugins: the `[bindings]` table, which is left for the plugin to parse.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PluginConfig {
+    #[serde(default)]
+    bindings: toml::value::Table,
+    #[serde(skip)]
+    cdylib_name: Option<String>,
+    #[serde(skip)]
+    dependency_configs: HashMap<String, toml::value::Table>,
+}
+
+impl BindingsConfig for PluginConfig {
+    fn update_from_ci(&mut self, _ci: &ComponentInterface) {}
+
+    fn update_from_cdylib_name(&mut self, cdylib_name: &str) {
+        self.cdylib_name
+            .get_or_insert_with(|| cdylib_name.to_string());
+    }
+
+    fn update_from_dependency_configs(&mut self, config_map: HashMap<&str, &Self>) {
+        for (crate_name, config) in config_map {
+            self.dependency_configs
+                .entry(crate_name.to_string())
+                .or_insert_with(|| config.bindings.clone());
+        }
+    }
+}
+
+/// The request written to the plugin's stdin
+#[derive(Debug, Serialize)]
+pub struct PluginRequest<'a> {
+    pub protocol_version: u32,
+    pub component: &'a ComponentInterface,
+    pub config: &'a toml::value::Table,
+    pub cdylib_name: Option<&'a str>,
+    pub dependency_configs: &'a HashMap<String, toml::value::Table>,
+}
+
+/// The response read from the plugin's stdout
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PluginResponse {
+    pub files: Vec<PluginFile>,
+}
+
+/// A file generated by a plugin
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PluginFile {
+    /// The path of the file, relative to the output directory
+    pub path: Utf8PathBuf,
+    pub contents: String,
+}
+
+/// A [`BindingGenerator`] that runs a plugin executable
+pub struct PluginBindingGenerator {
+    executable: Utf8PathBuf,
+}
+
+impl PluginBindingGenerator {
+    pub fn new(executable: impl Into<Utf8PathBuf>) -> Self {
+        Self {
+            executable: executable.into(),
+        }
+    }
+
+    /// Run the plugin for a component and return the files it generated.
+    pub fn run(&self, ci: &ComponentInterface, config: &PluginConfig) -> Result<PluginResponse> {
+        let request = serde_json::to_vec(&PluginRequest {
+            protocol_version: PLUGIN_PROTOCOL_VERSION,
+            component: ci,
+            config: &config.bindings,
+            cdylib_name: config.cdylib_name.as_deref(),
+            dependency_configs: &config.dependency_configs,
+        })?;
+
+        let mut child = Command::new(&self.executable)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .with_context(|| format!("Failed to run plugin {}", self.executable))?;
+        // Write the request from another thread, so a plugin that writes its response before
+        // reading all of its input can't deadlock.
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let writer = std::thread::spawn(move || stdin.write_all(&request));
+        let output = child
+            .wait_with_output()
+            .with_context(|| format!("Failed to run plugin {}", self.executable))?;
+        let write_result = writer.join().expect("writer thread panicked");
+        if !output.status.success() {
+            bail!("Plugin {} failed: {}", self.executable, output.status);
+        }
+        write_result.with_context(|| format!("Failed to write to plugin {}", self.executable))?;
+
+        serde_json::from_slice(&output.stdout)
+            .with_context(|| format!("Invalid response from plugin {}", self.executable))
+    }
+}
+
+impl BindingGenerator for PluginBindingGenerator {
+    type Config = PluginConfig;
+
+    fn write_bindings(
+        &self,
+        ci: &ComponentInterface,
+        config: &Self::Config,
+        out_dir: &Utf8Path,
+    ) -> Result<()> {
+        let response = self.run(ci, config)?;
+        for file in response.files {
+            let out_path = out_dir.join(checked_relative_path(&file.path)?);
+            if let Some(parent) = out_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&out_path, file.contents)?;
+        }
+        Ok(())
+    }
+
+    // Whether the library can be loaded is up to the plugin, which is told the cdylib name when
+    // there is one.
+    fn check_library_path(
+        &self,
+        _library_path: &Utf8Path,
+        _cdylib_name: Option<&str>,
+    ) -> Result<()> {
+        Ok(())
+    }
+}
+
+// Check that a path returned by a plugin stays inside the output directory.
+fn checked_relative_path(path: &Utf8Path) -> Result<&Utf8Path> {
+    let mut has_file_name = false;
+    for component in path.components() {
+        match component {
+            Utf8Component::Normal(_) => has_file_name = true,
+            Utf8Component::CurDir => (),
+            _ => bail!("Plugin returned a path outside of the output directory: {path}"),
+        }
+    }
+    if !has_file_name {
+        bail!("Plugin returned an empty path");
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_checked_relative_path() {
+        assert!(checked_relative_path("foo.dart".into()).is_ok());
+        assert!(checked_relative_path("./lib/foo.dart".into()).is_ok());
+        assert!(checked_relative_path("".into()).is_err());
+        assert!(checked_relative_path(".".into()).is_err());
+        assert!(checked_relative_path("../foo.dart".into()).is_err());
+        assert!(checked_relative_path("lib/../../foo.dart".into()).is_err());
+        assert!(checked_relative_path("/tmp/foo.dart".into()).is_err());
+    }
+
+    #[test]
+    fn test_config_from_toml() {
+        let mut config: PluginConfig = toml::from_str(
+            r#"
+            [bindings.dart]
+            module_name = "arithmetic"
+            "#,
+        )
+        .unwrap();
+        config.update_from_cdylib_name("arithmetical");
+        assert_eq!(config.cdylib_name.as_deref(), Some("arithmetical"));
+        assert_eq!(
+            config.bindings["dart"]["module_name"].as_str(),
+            Some("arithmetic")
+        );
+    }
+}
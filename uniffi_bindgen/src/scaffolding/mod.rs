@@ -14,15 +14,39 @@ use heck::{ToShoutySnakeCase, ToSnakeCase};
 pub struct RustScaffolding<'a> {
     ci: &'a ComponentInterface,
     udl_base_name: &'a str,
+    udl: &'a str,
 }
 impl<'a> RustScaffolding<'a> {
-    pub fn new(ci: &'a ComponentInterface, udl_base_name: &'a str) -> Self {
-        Self { ci, udl_base_name }
+    pub fn new(ci: &'a ComponentInterface, udl_base_name: &'a str, udl: &'a str) -> Self {
+        Self {
+            ci,
+            udl_base_name,
+            udl,
+        }
+    }
+
+    // The `UdlSource` metadata item for the UDL
+    fn udl_source(&self) -> Vec<u8> {
+        uniffi_meta::encode_udl_source(&self.ci.types.namespace.crate_name, self.udl)
     }
 }
 mod filters {
     use super::*;
 
+    // Render bytes as a byte string literal
+    pub fn byte_string(data: &[u8]) -> Result<String, askama::Error> {
+        let mut literal = String::from("b\"");
+        for &byte in data {
+            match byte {
+                b'"' | b'\\' => literal.push_str(&format!("\\{}", byte as char)),
+                b' '..=b'~' => literal.push(byte as char),
+                _ => literal.push_str(&format!("\\x{byte:02x}")),
+            }
+        }
+        literal.push('"');
+        Ok(literal)
+    }
+
     pub fn type_rs(type_: &Type) -> Result<String, askama::Error> {
         Ok(match type_ {
             Type::Int8 => "i8".into(),
@@ -57,6 +81,17 @@ mod filters {
                 type_rs(key_type)?,
                 type_rs(value_type)?
             ),
+            Type::Function { args, ret } => format!(
+                "Box<dyn Fn({}) -> {} + Send + Sync>",
+                args.iter()
+                    .map(type_rs)
+                    .collect::<Result<Vec<_>, _>>()?
+                    .join(", "),
+                match ret {
+                    Some(ret) => type_rs(ret)?,
+                    None => "()".into(),
+                }
+            ),
             Type::Custom { name, .. } => format!("r#{name}"),
             Type::External {
                 name,
@@ -72,6 +107,11 @@ mod filters {
         })
     }
 
+    // Name of the JNI shim for an FFI function.
+    pub fn jni_symbol_name(nm: &str, ci: &ComponentInterface) -> Result<String, askama::Error> {
+        Ok(uniffi_meta::jni_symbol_name(ci.crate_name(), nm))
+    }
+
     // Turns a `crate-name` into the `crate_name` the .rs code needs to specify.
     pub fn crate_name_rs(nm: &str) -> Result<String, askama::Error> {
         Ok(format!("r#{}", nm.to_string().to_snake_case()))
@@ -4,4 +4,6 @@
 pub extern "C" fn r#{{ name }}() -> u16 {
     {{ checksum }}
 }
+
+::uniffi::jni_shim!({{ name|jni_symbol_name(ci) }} => r#{{ name }}() -> u16);
 {%- endfor %}
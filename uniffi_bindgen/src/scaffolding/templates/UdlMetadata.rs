@@ -14,3 +14,10 @@ const {{ const_udl_var }}: ::uniffi::MetadataBuffer = ::uniffi::MetadataBuffer::
 #[doc(hidden)]
 #[no_mangle]
 pub static {{ static_udl_var }}: [u8; {{ const_udl_var }}.size] = {{ const_udl_var }}.into_array();
+
+// The UDL itself, so library mode can parse it without finding the crate's sources.  This is only
+// embedded with uniffi's `embed-sources` feature.
+{%- let static_udl_source_var = "UNIFFI_META_UDL_SOURCE_{}"|format(ci.namespace().to_shouty_snake_case()) %}
+{%- let udl_source = self.udl_source() %}
+
+::uniffi::embed_source_file!({{ static_udl_source_var }}, {{ udl_source.len() }}, {{ udl_source|byte_string }});
@@ -0,0 +1,233 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Closures passed across the FFI
+//!
+//! `Box<dyn Fn(A, B) -> R + Send + Sync>` and `Arc<dyn Fn(A, B) -> R + Send + Sync>` can be used
+//! as argument and return types.  Closures are type-erased so that each crate only needs a single
+//! vtable and a single pair of scaffolding functions, no matter how many closure signatures it
+//! uses: the arguments are serialized into a `RustBuffer` and the return value is serialized into
+//! another one.
+//!
+//!   * Foreign closures are stored in a handle map on the foreign side.  Rust calls them through
+//!     the [UniffiVTableForeignFunction] that the bindings register at startup.
+//!   * Rust closures are leaked into a [Handle].  The foreign side calls them with the
+//!     `ffi_[crate]_rust_function_call` scaffolding function and frees them with
+//!     `ffi_[crate]_rust_function_free`.
+//!
+//! Closures can't return errors, so a foreign closure that throws panics, like a callback
+//! interface method that can't return an error.  With `panic = "abort"` that would abort the
+//! process, so closures aren't supported then: the `FfiConverter` implementations are missing.
+
+use crate::{rust_call, Handle, RustBuffer, RustCallStatus};
+
+/// VTable for foreign closures, registered by the bindings
+#[repr(C)]
+pub struct UniffiVTableForeignFunction {
+    pub call: extern "C" fn(
+        handle: u64,
+        args: RustBuffer,
+        out_return: &mut RustBuffer,
+        call_status: &mut RustCallStatus,
+    ),
+    pub free: extern "C" fn(handle: u64),
+}
+
+/// Get the foreign closure vtable for a crate
+///
+/// `setup_scaffolding!` implements this for each crate's `UniFfiTag`.
+pub trait ForeignFunctionVTable {
+    fn foreign_function_vtable() -> &'static UniffiVTableForeignFunction;
+}
+
+/// A type-erased Rust closure, which reads its arguments from a buffer and returns the serialized
+/// result
+type RustFunction = Box<dyn Fn(RustBuffer) -> RustBuffer + Send + Sync>;
+
+/// Call a Rust closure for the foreign side
+///
+/// # Safety
+/// `handle` must have been created by lowering a closure and not freed yet.
+pub unsafe fn rust_function_call(
+    handle: u64,
+    args: RustBuffer,
+    call_status: &mut RustCallStatus,
+) -> RustBuffer {
+    let f = &*Handle::from_raw_unchecked(handle).as_pointer::<RustFunction>();
+    rust_call(call_status, std::panic::AssertUnwindSafe(|| Ok(f(args))))
+}
+
+/// Free a Rust closure
+///
+/// # Safety
+/// `handle` must have been created by lowering a closure and not freed yet.
+pub unsafe fn rust_function_free(handle: u64, call_status: &mut RustCallStatus) {
+    rust_call(call_status, || {
+        drop(Box::from_raw(
+            Handle::from_raw_unchecked(handle).as_pointer::<RustFunction>() as *mut RustFunction,
+        ));
+        Ok(())
+    })
+}
+
+// Foreign closures that throw panic, which would abort the process with `panic = "abort"`
+#[cfg(not(panic = "abort"))]
+mod converters {
+    use std::sync::Arc;
+
+    use anyhow::Result;
+    use bytes::Buf;
+
+    use super::{ForeignFunctionVTable, RustFunction, UniffiVTableForeignFunction};
+    use crate::{
+        check_remaining, derive_ffi_traits, metadata, FfiConverter, FfiConverterArc, Handle, Lift,
+        Lower, LowerReturn, MetadataBuffer, RustBuffer, RustCallStatus, RustCallStatusCode,
+    };
+
+    /// A foreign closure, freed when dropped
+    struct ForeignFunction {
+        handle: u64,
+        vtable: &'static UniffiVTableForeignFunction,
+    }
+
+    impl ForeignFunction {
+        /// Call the foreign closure
+        ///
+        /// Closures can't return errors, so this panics if the call fails.
+        fn call(&self, args: Vec<u8>) -> RustBuffer {
+            let mut call_status = RustCallStatus::new();
+            let mut out_return = RustBuffer::new();
+            (self.vtable.call)(
+                self.handle,
+                RustBuffer::from_vec(args),
+                &mut out_return,
+                &mut call_status,
+            );
+            match call_status.code {
+                RustCallStatusCode::Success => out_return,
+                _ => {
+                    let message = <String as FfiConverter<crate::UniFfiTag>>::try_lift(unsafe {
+                        call_status.error_buf.assume_init()
+                    })
+                    .unwrap_or_else(|e| format!("(Error lifting message: {e})"));
+                    panic!("Foreign closure failed: {message}")
+                }
+            }
+        }
+    }
+
+    impl Drop for ForeignFunction {
+        fn drop(&mut self) {
+            (self.vtable.free)(self.handle)
+        }
+    }
+
+    fn new_rust_function_handle(f: RustFunction) -> u64 {
+        Handle::from_pointer(Box::into_raw(Box::new(f))).as_raw()
+    }
+
+    fn read_handle(buf: &mut &[u8]) -> Result<u64> {
+        check_remaining(buf, 8)?;
+        Ok(buf.get_u64())
+    }
+
+    macro_rules! impl_ffi_converter_for_function {
+        ($($arg:ident),*) => {
+            unsafe impl<UT, R, $($arg),*> FfiConverter<UT> for Box<dyn Fn($($arg),*) -> R + Send + Sync>
+            where
+                UT: ForeignFunctionVTable + 'static,
+                R: Lift<UT> + Lower<UT> + LowerReturn<UT> + 'static,
+                $($arg: Lift<UT> + Lower<UT> + 'static,)*
+            {
+                type FfiType = u64;
+
+                #[allow(non_snake_case)]
+                fn lower(obj: Self) -> u64 {
+                    new_rust_function_handle(Box::new(move |args: RustBuffer| {
+                        let args = args.destroy_into_vec();
+                        let mut _buf = args.as_slice();
+                        $(
+                            let $arg = <$arg as Lift<UT>>::try_read(&mut _buf)
+                                .unwrap_or_else(|e| panic!("Failed to read closure argument: {e}"));
+                        )*
+                        <R as Lower<UT>>::lower_into_rust_buffer(obj($($arg),*))
+                    }))
+                }
+
+                #[allow(non_snake_case)]
+                fn try_lift(handle: u64) -> Result<Self> {
+                    let f = ForeignFunction {
+                        handle,
+                        vtable: UT::foreign_function_vtable(),
+                    };
+                    Ok(Box::new(move |$($arg),*| {
+                        let mut _args = Vec::new();
+                        $(<$arg as Lower<UT>>::write($arg, &mut _args);)*
+                        <R as Lift<UT>>::try_lift_from_rust_buffer(f.call(_args))
+                            .unwrap_or_else(|e| panic!("Failed to lift closure return value: {e}"))
+                    }))
+                }
+
+                fn write(obj: Self, buf: &mut Vec<u8>) {
+                    bytes::BufMut::put_u64(buf, <Self as FfiConverter<UT>>::lower(obj));
+                }
+
+                fn try_read(buf: &mut &[u8]) -> Result<Self> {
+                    <Self as FfiConverter<UT>>::try_lift(read_handle(buf)?)
+                }
+
+                const TYPE_ID_META: MetadataBuffer =
+                    MetadataBuffer::from_code(metadata::codes::TYPE_FUNCTION)
+                        .concat_value(impl_ffi_converter_for_function!(@count $($arg)*))
+                        $(.concat(<$arg as Lower<UT>>::TYPE_ID_META))*
+                        .concat(<R as LowerReturn<UT>>::TYPE_ID_META);
+            }
+
+            derive_ffi_traits!(impl<$($arg,)* UT, R> Lower<UT> for Box<dyn Fn($($arg),*) -> R + Send + Sync> where Self: FfiConverter<UT>);
+            derive_ffi_traits!(impl<$($arg,)* UT, R> Lift<UT> for Box<dyn Fn($($arg),*) -> R + Send + Sync> where Self: FfiConverter<UT>);
+            derive_ffi_traits!(impl<$($arg,)* UT, R> LowerReturn<UT> for Box<dyn Fn($($arg),*) -> R + Send + Sync> where Self: Lower<UT>);
+            derive_ffi_traits!(impl<$($arg,)* UT, R> LiftReturn<UT> for Box<dyn Fn($($arg),*) -> R + Send + Sync> where Self: Lift<UT>);
+            derive_ffi_traits!(impl<$($arg,)* UT, R> LiftRef<UT> for Box<dyn Fn($($arg),*) -> R + Send + Sync> where Self: Lift<UT>);
+
+            // `Arc<dyn Fn>` uses the same representation, we just convert to/from a `Box<dyn Fn>`.
+            unsafe impl<UT, R, $($arg),*> FfiConverterArc<UT> for dyn Fn($($arg),*) -> R + Send + Sync
+            where
+                UT: ForeignFunctionVTable + 'static,
+                R: Lift<UT> + Lower<UT> + LowerReturn<UT> + 'static,
+                $($arg: Lift<UT> + Lower<UT> + 'static,)*
+            {
+                type FfiType = u64;
+
+                #[allow(non_snake_case)]
+                fn lower(obj: Arc<Self>) -> u64 {
+                    <Box<Self> as FfiConverter<UT>>::lower(Box::new(move |$($arg),*| obj($($arg),*)))
+                }
+
+                fn try_lift(handle: u64) -> Result<Arc<Self>> {
+                    Ok(Arc::from(<Box<Self> as FfiConverter<UT>>::try_lift(handle)?))
+                }
+
+                fn write(obj: Arc<Self>, buf: &mut Vec<u8>) {
+                    bytes::BufMut::put_u64(buf, <Self as FfiConverterArc<UT>>::lower(obj));
+                }
+
+                fn try_read(buf: &mut &[u8]) -> Result<Arc<Self>> {
+                    <Self as FfiConverterArc<UT>>::try_lift(read_handle(buf)?)
+                }
+
+                const TYPE_ID_META: MetadataBuffer = <Box<Self> as FfiConverter<UT>>::TYPE_ID_META;
+            }
+        };
+        (@count) => { 0 };
+        (@count $first:ident $($rest:ident)*) => { 1 + impl_ffi_converter_for_function!(@count $($rest)*) };
+    }
+
+    impl_ffi_converter_for_function!();
+    impl_ffi_converter_for_function!(A);
+    impl_ffi_converter_for_function!(A, B);
+    impl_ffi_converter_for_function!(A, B, C);
+    impl_ffi_converter_for_function!(A, B, C, D);
+    impl_ffi_converter_for_function!(A, B, C, D, E);
+    impl_ffi_converter_for_function!(A, B, C, D, E, F);
+}
@@ -0,0 +1,220 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! JNI shims for the scaffolding functions
+//!
+//! The Kotlin bindings normally call the scaffolding functions through JNA, whose reflective
+//! `Library` proxies dominate the cost of small calls.  When the `jni` feature is enabled, the
+//! scaffolding also exports a `Java_*` function for each scaffolding function so that Kotlin can
+//! call it directly through an `external fun` (see `kotlin.ffi_backend = "jni"`).
+//!
+//! JNI can only pass primitives, so the shims use these conventions:
+//!   - Integers and floats are passed as-is, with unsigned integers reinterpreted as the
+//!     signed JNI type of the same size.
+//!   - Handles, pointers, and callback function pointers are passed as a `jlong`.
+//!   - Structs, like `RustBuffer` and `RustCallStatus`, are passed as a `jlong` pointer to
+//!     memory owned by the Kotlin side.
+//!   - Every shim inputs an extra `jlong` out pointer. Struct return values are written there
+//!     rather than returned.
+//!
+//! The shims are generated by the [crate::jni_shim] macro, which expands to nothing when the
+//! feature is disabled.
+
+#[cfg(feature = "jni")]
+mod imp {
+    use crate::{
+        ForeignBytes, Handle, LogBridgeCallback, RustBuffer, RustCallStatus,
+        RustFutureContinuationCallback,
+    };
+    use std::{ffi::c_void, ptr::NonNull};
+
+    /// Convert a JNI argument into a scaffolding function argument
+    pub trait FromJni: Sized {
+        type JniType;
+
+        /// # Safety
+        ///
+        /// `value` must have been lowered by the generated Kotlin code following the conventions
+        /// in the module docs.  Pointers are dereferenced without any checks.
+        unsafe fn from_jni(value: Self::JniType) -> Self;
+    }
+
+    /// Convert a scaffolding function return value into a JNI return value
+    pub trait IntoJni {
+        type JniType;
+
+        /// # Safety
+        ///
+        /// `out` must point to memory with room for a `Self` if the value is returned through it.
+        unsafe fn into_jni(self, out: i64) -> Self::JniType;
+    }
+
+    macro_rules! impl_jni_for_primitive {
+        ($($T:ty => $J:ty),* $(,)?) => {
+            $(
+                impl FromJni for $T {
+                    type JniType = $J;
+
+                    unsafe fn from_jni(value: $J) -> Self {
+                        value as $T
+                    }
+                }
+
+                impl IntoJni for $T {
+                    type JniType = $J;
+
+                    unsafe fn into_jni(self, _out: i64) -> $J {
+                        self as $J
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_jni_for_primitive! {
+        i8 => i8,
+        u8 => i8,
+        i16 => i16,
+        u16 => i16,
+        i32 => i32,
+        u32 => i32,
+        i64 => i64,
+        u64 => i64,
+        f32 => f32,
+        f64 => f64,
+    }
+
+    impl IntoJni for () {
+        type JniType = ();
+
+        unsafe fn into_jni(self, _out: i64) {}
+    }
+
+    impl FromJni for Handle {
+        type JniType = i64;
+
+        unsafe fn from_jni(value: i64) -> Self {
+            Handle::from_raw_unchecked(value as u64)
+        }
+    }
+
+    impl IntoJni for Handle {
+        type JniType = i64;
+
+        unsafe fn into_jni(self, _out: i64) -> i64 {
+            self.as_raw() as i64
+        }
+    }
+
+    impl FromJni for *const c_void {
+        type JniType = i64;
+
+        unsafe fn from_jni(value: i64) -> Self {
+            value as usize as *const c_void
+        }
+    }
+
+    impl IntoJni for *const c_void {
+        type JniType = i64;
+
+        unsafe fn into_jni(self, _out: i64) -> i64 {
+            self as usize as i64
+        }
+    }
+
+    impl FromJni for RustBuffer {
+        type JniType = i64;
+
+        unsafe fn from_jni(value: i64) -> Self {
+            std::ptr::read(value as usize as *const RustBuffer)
+        }
+    }
+
+    impl IntoJni for RustBuffer {
+        type JniType = ();
+
+        unsafe fn into_jni(self, out: i64) {
+            std::ptr::write(out as usize as *mut RustBuffer, self)
+        }
+    }
+
+    impl FromJni for ForeignBytes {
+        type JniType = i64;
+
+        unsafe fn from_jni(value: i64) -> Self {
+            std::ptr::read(value as usize as *const ForeignBytes)
+        }
+    }
+
+    impl<'a> FromJni for &'a mut RustCallStatus {
+        type JniType = i64;
+
+        unsafe fn from_jni(value: i64) -> Self {
+            &mut *(value as usize as *mut RustCallStatus)
+        }
+    }
+
+    impl FromJni for RustFutureContinuationCallback {
+        type JniType = i64;
+
+        unsafe fn from_jni(value: i64) -> Self {
+            std::mem::transmute::<usize, RustFutureContinuationCallback>(value as usize)
+        }
+    }
+
+    impl FromJni for LogBridgeCallback {
+        type JniType = i64;
+
+        unsafe fn from_jni(value: i64) -> Self {
+            std::mem::transmute::<usize, LogBridgeCallback>(value as usize)
+        }
+    }
+
+    impl<T> FromJni for NonNull<T> {
+        type JniType = i64;
+
+        unsafe fn from_jni(value: i64) -> Self {
+            NonNull::new_unchecked(value as usize as *mut T)
+        }
+    }
+}
+
+#[cfg(feature = "jni")]
+pub use imp::{FromJni, IntoJni};
+
+/// Export a JNI shim for a scaffolding function
+///
+/// `jni_shim!(Java_... => ffi_fn(arg: Type, ...) -> ReturnType)` defines the `Java_...` function,
+/// which converts its arguments using [FromJni], calls `ffi_fn`, then converts the return value
+/// using [IntoJni].
+#[cfg(feature = "jni")]
+#[macro_export]
+macro_rules! jni_shim {
+    ($jni_ident:ident => $ffi_ident:ident($($name:ident: $ty:ty),* $(,)?) $(-> $return_ty:ty)?) => {
+        #[doc(hidden)]
+        #[no_mangle]
+        unsafe extern "system" fn $jni_ident(
+            _env: *mut ::std::ffi::c_void,
+            _this: *mut ::std::ffi::c_void,
+            $($name: <$ty as $crate::FromJni>::JniType,)*
+            uniffi_out: i64,
+        ) -> <$crate::jni_shim!(@return_ty $($return_ty)?) as $crate::IntoJni>::JniType {
+            $crate::IntoJni::into_jni(
+                $ffi_ident($(<$ty as $crate::FromJni>::from_jni($name)),*),
+                uniffi_out,
+            )
+        }
+    };
+    (@return_ty $return_ty:ty) => { $return_ty };
+    (@return_ty) => { () };
+}
+
+/// Export a JNI shim for a scaffolding function
+///
+/// This is a no-op since the `jni` feature is disabled.
+#[cfg(not(feature = "jni"))]
+#[macro_export]
+macro_rules! jni_shim {
+    ($($tt:tt)*) => {};
+}
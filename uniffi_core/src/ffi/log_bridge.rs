@@ -0,0 +1,136 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Forwarding Rust log records to the foreign side
+//!
+//! The bindings can install a [log::Log] implementation that passes each record to a foreign
+//! callback, which hands it to the platform logger (Logcat/`java.util.logging`, `os_log`, Python's
+//! `logging` module, etc).  The `tracing` crate is only supported through its `log` feature, which
+//! turns its events into `log` records.
+//!
+//! Levels are passed across the FFI as an `i8`, using the same values as [log::LevelFilter]:
+//! 0 is `Off`, 1 is `Error` and so on up to 5 for `Trace`.
+
+use crate::{Lower, RustBuffer, UniFfiTag};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    RwLock,
+};
+
+/// Foreign callback that receives log records
+///
+/// `target` and `message` are serialized strings, the foreign side is responsible for freeing
+/// them.
+pub type LogBridgeCallback = extern "C" fn(level: i8, target: RustBuffer, message: RustBuffer);
+
+static CALLBACK: RwLock<Option<LogBridgeCallback>> = RwLock::new(None);
+
+// Set once our logger is installed.  If the application installed its own logger instead, we
+// leave the max level alone since it also applies to that logger.
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+struct ForeignLogger;
+
+static LOGGER: ForeignLogger = ForeignLogger;
+
+impl log::Log for ForeignLogger {
+    fn enabled(&self, metadata: &log::Metadata<'_>) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &log::Record<'_>) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let callback = *CALLBACK.read().unwrap_or_else(|e| e.into_inner());
+        if let Some(callback) = callback {
+            callback(
+                record.level() as i8,
+                <String as Lower<UniFfiTag>>::lower(record.target().to_string()),
+                <String as Lower<UniFfiTag>>::lower(record.args().to_string()),
+            );
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+fn level_filter(max_level: i8) -> log::LevelFilter {
+    match max_level {
+        i8::MIN..=0 => log::LevelFilter::Off,
+        1 => log::LevelFilter::Error,
+        2 => log::LevelFilter::Warn,
+        3 => log::LevelFilter::Info,
+        4 => log::LevelFilter::Debug,
+        _ => log::LevelFilter::Trace,
+    }
+}
+
+/// Install the logger, sending records up to `max_level` to `callback`
+///
+/// This replaces any callback set previously.  If the application already installed a different
+/// logger, that one is kept and `callback` won't receive any records.
+pub fn log_bridge_init(callback: LogBridgeCallback, max_level: i8) {
+    let mut current_callback = CALLBACK.write().unwrap_or_else(|e| e.into_inner());
+    *current_callback = Some(callback);
+    // This fails if a logger was already set, which is fine if it's ours.  Holding the lock
+    // means `INSTALLED` is set before any other call checks it.
+    if log::set_logger(&LOGGER).is_ok() {
+        INSTALLED.store(true, Ordering::Release);
+    }
+    drop(current_callback);
+    log_bridge_set_level(max_level);
+}
+
+/// Change the maximum level of the records sent to the foreign side
+///
+/// This has no effect if our logger isn't installed.
+pub fn log_bridge_set_level(max_level: i8) {
+    if INSTALLED.load(Ordering::Acquire) {
+        log::set_max_level(level_filter(max_level));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Lift;
+    use std::sync::Mutex;
+
+    static RECORDS: Mutex<Vec<(i8, String, String)>> = Mutex::new(Vec::new());
+
+    extern "C" fn test_callback(level: i8, target: RustBuffer, message: RustBuffer) {
+        RECORDS.lock().unwrap().push((
+            level,
+            <String as Lift<UniFfiTag>>::try_lift(target).unwrap(),
+            <String as Lift<UniFfiTag>>::try_lift(message).unwrap(),
+        ));
+    }
+
+    #[test]
+    fn test_log_bridge() {
+        log_bridge_init(test_callback, 3);
+        log::info!(target: "test_target", "hello {}", 1);
+        log::debug!(target: "test_target", "filtered out");
+        log_bridge_set_level(4);
+        log::debug!(target: "test_target", "debug message");
+        log_bridge_set_level(0);
+        log::error!(target: "test_target", "off");
+        // Other tests may log at the same time
+        let records: Vec<_> = RECORDS
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, target, _)| target == "test_target")
+            .cloned()
+            .collect();
+        assert_eq!(
+            records,
+            vec![
+                (3, "test_target".to_string(), "hello 1".to_string()),
+                (4, "test_target".to_string(), "debug message".to_string()),
+            ]
+        );
+    }
+}
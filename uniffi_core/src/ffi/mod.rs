@@ -9,17 +9,28 @@ pub mod ffidefault;
 pub mod foreignbytes;
 pub mod foreigncallbacks;
 pub mod foreignfuture;
+pub mod function;
 pub mod handle;
+pub mod jni;
+pub mod log_bridge;
+pub mod object_handle;
 pub mod rustbuffer;
 pub mod rustcalls;
 pub mod rustfuture;
+pub mod thread_bound;
 
 pub use callbackinterface::*;
 pub use ffidefault::FfiDefault;
 pub use foreignbytes::*;
 pub use foreigncallbacks::*;
 pub use foreignfuture::*;
+pub use function::{ForeignFunctionVTable, UniffiVTableForeignFunction};
 pub use handle::*;
+#[cfg(feature = "jni")]
+pub use jni::*;
+pub use log_bridge::*;
+pub use object_handle::*;
 pub use rustbuffer::*;
 pub use rustcalls::*;
 pub use rustfuture::*;
+pub use thread_bound::ThreadBound;
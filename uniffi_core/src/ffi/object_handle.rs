@@ -0,0 +1,563 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Passing Rust objects to the foreign side
+//!
+//! Objects are passed across the FFI as an `Arc<T>` that's been leaked into a raw pointer.  Each
+//! pointer the foreign side holds represents one strong reference to the object.  The generated
+//! scaffolding manages these pointers with the functions in this module.
+//!
+//! By default, these are thin wrappers around the `Arc` raw pointer functions.  This means a
+//! foreign object freed twice, or used after it was freed, results in memory corruption.
+//!
+//! With the `debug-handles` feature, the foreign side gets a handle into a global slab instead.
+//! The slab holds the `Arc` and a count of the references the foreign side holds, and each handle
+//! includes a generation counter for its slot.  Passing the same object again reuses its slot, so
+//! the foreign side sees the same handle for an object while it holds a reference to it, just like
+//! it sees the same pointer without the feature.  Stale handles, double frees and handles for the
+//! wrong type are reported as an error that names the object type.  This is slower, so it's meant
+//! for tests and debug builds.
+
+use std::{ffi::c_void, sync::Arc};
+
+use crate::{rust_call_with_out_status, RustCallStatus};
+
+/// Pass a strong reference to the foreign side
+pub fn object_handle_new<T: Send + Sync + 'static>(
+    obj: Arc<T>,
+    type_name: &'static str,
+) -> *const c_void {
+    #[cfg(not(feature = "debug-handles"))]
+    {
+        let _ = type_name;
+        Arc::into_raw(obj) as *const c_void
+    }
+    #[cfg(feature = "debug-handles")]
+    {
+        slab::insert(obj, type_name)
+    }
+}
+
+/// Create another strong reference from a handle
+///
+/// With the `debug-handles` feature, an invalid `handle` is reported in `call_status` as an
+/// unexpected error that names the object type, and a null pointer is returned.
+///
+/// # Safety
+///
+/// `handle` must have been returned by [object_handle_new] or [object_handle_clone] for the same
+/// type and not freed yet.  This is only checked with the `debug-handles` feature.
+pub unsafe fn object_handle_clone<T: Send + Sync + 'static>(
+    handle: *const c_void,
+    type_name: &'static str,
+    call_status: &mut RustCallStatus,
+) -> *const c_void {
+    let result = rust_call_with_out_status(call_status, || {
+        #[cfg(not(feature = "debug-handles"))]
+        {
+            let _ = type_name;
+            Arc::increment_strong_count(handle as *const T);
+            Ok(Ok::<_, String>(handle))
+        }
+        #[cfg(feature = "debug-handles")]
+        {
+            Ok(slab::clone::<T>(handle, type_name))
+        }
+    });
+    match result {
+        Some(Ok(handle)) => handle,
+        Some(Err(message)) => {
+            *call_status = RustCallStatus::error(message);
+            std::ptr::null()
+        }
+        None => std::ptr::null(),
+    }
+}
+
+/// Release the strong reference that a handle represents
+///
+/// With the `debug-handles` feature, an invalid `handle` is reported in `call_status` as an
+/// unexpected error that names the object type.
+///
+/// # Safety
+///
+/// `handle` must have been returned by [object_handle_new] or [object_handle_clone] for the same
+/// type and not freed yet.  This is only checked with the `debug-handles` feature.
+pub unsafe fn object_handle_free<T: Send + Sync + 'static>(
+    handle: *const c_void,
+    type_name: &'static str,
+    call_status: &mut RustCallStatus,
+) {
+    let result = rust_call_with_out_status(call_status, || {
+        assert!(!handle.is_null());
+        #[cfg(not(feature = "debug-handles"))]
+        {
+            let _ = type_name;
+            Arc::decrement_strong_count(handle as *const T);
+            Ok(Ok::<_, String>(()))
+        }
+        #[cfg(feature = "debug-handles")]
+        {
+            Ok(slab::free::<T>(handle, type_name))
+        }
+    });
+    if let Some(Err(message)) = result {
+        *call_status = RustCallStatus::error(message);
+    }
+}
+
+/// Take back the strong reference that a handle represents
+///
+/// With the `debug-handles` feature, this fails if `handle` is invalid.
+///
+/// # Safety
+///
+/// `handle` must have been returned by [object_handle_new] or [object_handle_clone] for the same
+/// type and not freed yet.  This is only checked with the `debug-handles` feature.
+pub unsafe fn object_handle_lift<T: Send + Sync + 'static>(
+    handle: *const c_void,
+    type_name: &'static str,
+) -> anyhow::Result<Arc<T>> {
+    #[cfg(not(feature = "debug-handles"))]
+    {
+        let _ = type_name;
+        Ok(Arc::from_raw(handle as *const T))
+    }
+    #[cfg(feature = "debug-handles")]
+    {
+        slab::lift(handle, type_name).map_err(anyhow::Error::msg)
+    }
+}
+
+/// Take the object that a handle refers to, for methods that consume `self`
+///
+/// This takes back the strong reference that `handle` represents and unwraps the `Arc`, which
+/// fails if `check` fails or there are other references to the object.  On failure, the error is
+/// reported in `call_status` as an unexpected error and `None` is returned.  The handle is left as
+/// it was, so the foreign side can keep using it.
+///
+/// `check` runs before the object is taken.  With the `debug-handles` feature, it runs with the
+/// slab locked, so it must not use any handles.
+///
+/// # Safety
+///
+/// `handle` must have been returned by [object_handle_new] or [object_handle_clone] for the same
+/// type and not freed yet.  This is only checked with the `debug-handles` feature.
+pub unsafe fn object_handle_take<T: Send + Sync + 'static>(
+    handle: *const c_void,
+    type_name: &'static str,
+    check: impl FnOnce(&T) -> anyhow::Result<()>,
+    call_status: &mut RustCallStatus,
+) -> Option<T> {
+    // `check` is only called before the object is taken, so a panic leaves the handle as it was
+    let result = rust_call_with_out_status(
+        call_status,
+        std::panic::AssertUnwindSafe(|| {
+            #[cfg(not(feature = "debug-handles"))]
+            {
+                // Don't drop the reference if `check` fails or panics, the handle still owns it
+                let obj = std::mem::ManuallyDrop::new(Arc::from_raw(handle as *const T));
+                if let Err(e) = check(&**obj) {
+                    return Ok(Err(e.to_string()));
+                }
+                Ok(
+                    Arc::try_unwrap(std::mem::ManuallyDrop::into_inner(obj)).map_err(|obj| {
+                        // Give the reference back to the handle, this is the same pointer
+                        let _ = Arc::into_raw(obj);
+                        shared_error(type_name)
+                    }),
+                )
+            }
+            #[cfg(feature = "debug-handles")]
+            {
+                Ok(slab::take::<T>(handle, type_name, check))
+            }
+        }),
+    );
+    match result {
+        Some(Ok(obj)) => Some(obj),
+        Some(Err(message)) => {
+            *call_status = RustCallStatus::error(message);
+            None
+        }
+        None => None,
+    }
+}
+
+fn shared_error(type_name: &str) -> String {
+    format!("{type_name} object has other references and can't be consumed")
+}
+
+#[cfg(feature = "debug-handles")]
+mod slab {
+    use once_cell::sync::Lazy;
+    use std::{
+        any::{Any, TypeId},
+        collections::HashMap,
+        ffi::c_void,
+        sync::{Arc, Mutex, MutexGuard},
+    };
+
+    // Handles store the slot index + 1 in the high bits and the generation in the low bits.
+    // Adding 1 to the index means a handle is never null.
+    const GENERATION_BITS: u32 = usize::BITS / 2;
+    const GENERATION_MASK: usize = (1 << GENERATION_BITS) - 1;
+
+    static SLAB: Lazy<Mutex<Slab>> = Lazy::new(Default::default);
+
+    struct Object {
+        value: Arc<dyn Any + Send + Sync>,
+        type_id: TypeId,
+        type_name: &'static str,
+        // Number of strong references held by the foreign side
+        foreign_refs: usize,
+    }
+
+    #[derive(Default)]
+    struct Slot {
+        generation: usize,
+        object: Option<Object>,
+    }
+
+    #[derive(Default)]
+    struct Slab {
+        slots: Vec<Slot>,
+        free_slots: Vec<usize>,
+        // Maps the address of each live object to its slot
+        by_address: HashMap<usize, usize>,
+    }
+
+    fn lock() -> MutexGuard<'static, Slab> {
+        // We never panic while holding the lock, but if we did the slab would still be consistent.
+        SLAB.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    impl Slab {
+        fn handle(&self, index: usize) -> *const c_void {
+            (((index + 1) << GENERATION_BITS) | self.slots[index].generation) as *const c_void
+        }
+
+        /// Find the slot for a handle, checking that it's still live and has the right type.
+        fn find<T: 'static>(
+            &mut self,
+            handle: *const c_void,
+            type_name: &'static str,
+        ) -> Result<(usize, &mut Object), String> {
+            let raw = handle as usize;
+            let index = (raw >> GENERATION_BITS).wrapping_sub(1);
+            let slot = self
+                .slots
+                .get_mut(index)
+                .ok_or_else(|| format!("{type_name} handle {raw:#x} is not a valid handle"))?;
+            let object = match slot.object.as_mut() {
+                Some(object) if slot.generation == raw & GENERATION_MASK => object,
+                _ => {
+                    return Err(format!(
+                        "{type_name} handle {raw:#x} is stale, the object was already freed"
+                    ))
+                }
+            };
+            if object.type_id != TypeId::of::<T>() {
+                return Err(format!(
+                    "{type_name} handle {raw:#x} is invalid, it refers to a {} object",
+                    object.type_name
+                ));
+            }
+            Ok((index, object))
+        }
+
+        /// Release one foreign reference, removing the object once there are none left.
+        ///
+        /// Returns the removed `Arc`, which must be dropped after releasing the lock, since
+        /// dropping the object could run arbitrary code.
+        fn release(&mut self, index: usize) -> Option<Arc<dyn Any + Send + Sync>> {
+            let slot = &mut self.slots[index];
+            let object = slot.object.as_mut()?;
+            object.foreign_refs -= 1;
+            if object.foreign_refs > 0 {
+                return None;
+            }
+            let object = slot.object.take()?;
+            slot.generation = (slot.generation + 1) & GENERATION_MASK;
+            self.free_slots.push(index);
+            self.by_address.remove(&address(&object.value));
+            Some(object.value)
+        }
+    }
+
+    fn address<T: ?Sized>(obj: &Arc<T>) -> usize {
+        Arc::as_ptr(obj) as *const () as usize
+    }
+
+    pub fn insert<T: Send + Sync + 'static>(obj: Arc<T>, type_name: &'static str) -> *const c_void {
+        let mut slab = lock();
+        if let Some(&index) = slab.by_address.get(&address(&obj)) {
+            // The foreign side already holds a reference to this object, count another one in the
+            // same slot.  The slot keeps the object alive, so dropping `obj` can't run its
+            // destructor while we hold the lock.
+            slab.slots[index]
+                .object
+                .as_mut()
+                .unwrap_or_else(|| unreachable!("by_address only contains live slots"))
+                .foreign_refs += 1;
+            return slab.handle(index);
+        }
+        let index = match slab.free_slots.pop() {
+            Some(index) => index,
+            None => {
+                slab.slots.push(Slot::default());
+                slab.slots.len() - 1
+            }
+        };
+        slab.by_address.insert(address(&obj), index);
+        slab.slots[index].object = Some(Object {
+            value: obj,
+            type_id: TypeId::of::<T>(),
+            type_name,
+            foreign_refs: 1,
+        });
+        slab.handle(index)
+    }
+
+    pub fn clone<T: 'static>(
+        handle: *const c_void,
+        type_name: &'static str,
+    ) -> Result<*const c_void, String> {
+        let mut slab = lock();
+        let (_, object) = slab.find::<T>(handle, type_name)?;
+        object.foreign_refs += 1;
+        Ok(handle)
+    }
+
+    pub fn free<T: 'static>(handle: *const c_void, type_name: &'static str) -> Result<(), String> {
+        let mut slab = lock();
+        let (index, _) = slab.find::<T>(handle, type_name)?;
+        let removed = slab.release(index);
+        drop(slab);
+        drop(removed);
+        Ok(())
+    }
+
+    pub fn take<T: Send + Sync + 'static>(
+        handle: *const c_void,
+        type_name: &'static str,
+        check: impl FnOnce(&T) -> anyhow::Result<()>,
+    ) -> Result<T, String> {
+        let mut guard = lock();
+        let slab = &mut *guard;
+        let (index, object) = slab.find::<T>(handle, type_name)?;
+        let value = object
+            .value
+            .downcast_ref::<T>()
+            .unwrap_or_else(|| unreachable!("type checked by find()"));
+        check(value).map_err(|e| e.to_string())?;
+        // Other handles for this object would keep it alive
+        if object.foreign_refs > 1 {
+            return Err(super::shared_error(type_name));
+        }
+        let slot = &mut slab.slots[index];
+        let Object {
+            value,
+            type_id,
+            type_name: stored_type_name,
+            foreign_refs,
+        } = slot
+            .object
+            .take()
+            .unwrap_or_else(|| unreachable!("checked by find()"));
+        let obj_address = address(&value);
+        let obj = value
+            .downcast::<T>()
+            .unwrap_or_else(|_| unreachable!("type checked by find()"));
+        match Arc::try_unwrap(obj) {
+            Ok(obj) => {
+                slot.generation = (slot.generation + 1) & GENERATION_MASK;
+                slab.free_slots.push(index);
+                slab.by_address.remove(&obj_address);
+                Ok(obj)
+            }
+            Err(obj) => {
+                // Put the object back, the handle is still valid
+                slot.object = Some(Object {
+                    value: obj,
+                    type_id,
+                    type_name: stored_type_name,
+                    foreign_refs,
+                });
+                Err(super::shared_error(type_name))
+            }
+        }
+    }
+
+    pub fn lift<T: Send + Sync + 'static>(
+        handle: *const c_void,
+        type_name: &'static str,
+    ) -> Result<Arc<T>, String> {
+        let mut slab = lock();
+        let (index, object) = slab.find::<T>(handle, type_name)?;
+        let value = Arc::clone(&object.value);
+        let removed = slab.release(index);
+        drop(slab);
+        drop(removed);
+        Ok(value
+            .downcast::<T>()
+            .unwrap_or_else(|_| unreachable!("type checked by find()")))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Lift, RustCallStatusCode, UniFfiTag};
+
+    #[derive(Debug)]
+    struct Counter(u32);
+    #[cfg(feature = "debug-handles")]
+    #[derive(Debug)]
+    struct Other;
+
+    fn error_message(call_status: RustCallStatus) -> String {
+        assert_eq!(call_status.code, RustCallStatusCode::UnexpectedError);
+        <String as Lift<UniFfiTag>>::try_lift(unsafe { call_status.error_buf.assume_init() })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_lower_and_lift() {
+        let obj = Arc::new(Counter(1));
+        let handle = object_handle_new(obj.clone(), "Counter");
+        let mut call_status = RustCallStatus::new();
+        let cloned = unsafe { object_handle_clone::<Counter>(handle, "Counter", &mut call_status) };
+        assert_eq!(call_status.code, RustCallStatusCode::Success);
+        let lifted = unsafe { object_handle_lift::<Counter>(cloned, "Counter") }.unwrap();
+        assert_eq!(lifted.0, 1);
+        assert!(Arc::ptr_eq(&obj, &lifted));
+        unsafe { object_handle_free::<Counter>(handle, "Counter", &mut call_status) };
+        assert_eq!(call_status.code, RustCallStatusCode::Success);
+        drop(lifted);
+        assert_eq!(Arc::strong_count(&obj), 1);
+    }
+
+    #[test]
+    fn test_take() {
+        let obj = Arc::new(Counter(8));
+        let handle = object_handle_new(obj.clone(), "Counter");
+        // Another reference is alive, so the handle is left as it was
+        let mut call_status = RustCallStatus::new();
+        let taken = unsafe {
+            object_handle_take::<Counter>(handle, "Counter", |_| Ok(()), &mut call_status)
+        };
+        assert!(taken.is_none());
+        assert!(error_message(call_status).contains("other references"));
+        let lifted = unsafe { object_handle_lift::<Counter>(handle, "Counter") }.unwrap();
+        assert!(Arc::ptr_eq(&obj, &lifted));
+        // `lift` consumed the handle's reference, so pass it again
+        let handle = object_handle_new(lifted, "Counter");
+        // A failing check leaves the handle as it was too
+        let mut call_status = RustCallStatus::new();
+        let taken = unsafe {
+            object_handle_take::<Counter>(
+                handle,
+                "Counter",
+                |_| anyhow::bail!("check failed"),
+                &mut call_status,
+            )
+        };
+        assert!(taken.is_none());
+        assert_eq!(error_message(call_status), "check failed");
+        drop(obj);
+        let mut call_status = RustCallStatus::new();
+        let taken = unsafe {
+            object_handle_take::<Counter>(handle, "Counter", |_| Ok(()), &mut call_status)
+        };
+        assert_eq!(call_status.code, RustCallStatusCode::Success);
+        assert_eq!(taken.unwrap().0, 8);
+    }
+
+    #[cfg(feature = "debug-handles")]
+    #[test]
+    fn test_same_object_same_handle() {
+        let obj = Arc::new(Counter(2));
+        let handle = object_handle_new(obj.clone(), "Counter");
+        let handle2 = object_handle_new(obj.clone(), "Counter");
+        assert_eq!(handle, handle2);
+        let other = object_handle_new(Arc::new(Counter(2)), "Counter");
+        assert_ne!(handle, other);
+        let mut call_status = RustCallStatus::new();
+        unsafe { object_handle_free::<Counter>(other, "Counter", &mut call_status) };
+        unsafe { object_handle_free::<Counter>(handle, "Counter", &mut call_status) };
+        assert_eq!(call_status.code, RustCallStatusCode::Success);
+        // The second reference keeps the slot alive
+        let lifted = unsafe { object_handle_lift::<Counter>(handle2, "Counter") }.unwrap();
+        assert!(Arc::ptr_eq(&obj, &lifted));
+        drop(lifted);
+        assert_eq!(Arc::strong_count(&obj), 1);
+        // Once all the references are released, the object gets a new handle
+        let handle3 = object_handle_new(obj.clone(), "Counter");
+        assert_ne!(handle, handle3);
+        unsafe { object_handle_free::<Counter>(handle3, "Counter", &mut call_status) };
+        assert_eq!(call_status.code, RustCallStatusCode::Success);
+    }
+
+    #[cfg(feature = "debug-handles")]
+    #[test]
+    fn test_stale_handle() {
+        let handle = object_handle_new(Arc::new(Counter(3)), "Counter");
+        unsafe { object_handle_free::<Counter>(handle, "Counter", &mut RustCallStatus::new()) };
+        let err = unsafe { object_handle_lift::<Counter>(handle, "Counter") }.unwrap_err();
+        assert!(err.to_string().contains("Counter handle"));
+        assert!(err.to_string().contains("stale"));
+        // A new object may reuse the slot, but gets a new generation
+        let handle2 = object_handle_new(Arc::new(Counter(4)), "Counter");
+        assert_ne!(handle, handle2);
+        assert!(unsafe { object_handle_lift::<Counter>(handle, "Counter") }.is_err());
+        let mut call_status = RustCallStatus::new();
+        let cloned = unsafe { object_handle_clone::<Counter>(handle, "Counter", &mut call_status) };
+        assert!(cloned.is_null());
+        assert!(error_message(call_status).contains("Counter handle"));
+        unsafe { object_handle_free::<Counter>(handle2, "Counter", &mut RustCallStatus::new()) };
+    }
+
+    #[cfg(feature = "debug-handles")]
+    #[test]
+    fn test_double_free() {
+        let handle = object_handle_new(Arc::new(Counter(5)), "Counter");
+        let mut call_status = RustCallStatus::new();
+        unsafe { object_handle_free::<Counter>(handle, "Counter", &mut call_status) };
+        assert_eq!(call_status.code, RustCallStatusCode::Success);
+        unsafe { object_handle_free::<Counter>(handle, "Counter", &mut call_status) };
+        assert!(error_message(call_status).contains("Counter handle"));
+    }
+
+    #[cfg(feature = "debug-handles")]
+    #[test]
+    fn test_extra_free_of_shared_handle() {
+        // Freeing a shared handle more times than it was passed is an error, the extra free
+        // doesn't release a reference the foreign side no longer holds
+        let obj = Arc::new(Counter(6));
+        let handle = object_handle_new(obj.clone(), "Counter");
+        object_handle_new(obj.clone(), "Counter");
+        let mut call_status = RustCallStatus::new();
+        unsafe {
+            object_handle_free::<Counter>(handle, "Counter", &mut call_status);
+            object_handle_free::<Counter>(handle, "Counter", &mut call_status);
+        }
+        assert_eq!(call_status.code, RustCallStatusCode::Success);
+        assert_eq!(Arc::strong_count(&obj), 1);
+        unsafe { object_handle_free::<Counter>(handle, "Counter", &mut call_status) };
+        assert!(error_message(call_status).contains("stale"));
+    }
+
+    #[cfg(feature = "debug-handles")]
+    #[test]
+    fn test_wrong_type() {
+        let handle = object_handle_new(Arc::new(Counter(7)), "Counter");
+        let err = unsafe { object_handle_lift::<Other>(handle, "Other") }.unwrap_err();
+        assert!(err.to_string().contains("refers to a Counter object"));
+        let mut call_status = RustCallStatus::new();
+        unsafe { object_handle_free::<Other>(handle, "Other", &mut call_status) };
+        assert!(error_message(call_status).contains("refers to a Counter object"));
+        unsafe { object_handle_free::<Counter>(handle, "Counter", &mut RustCallStatus::new()) };
+    }
+}
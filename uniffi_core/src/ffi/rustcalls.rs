@@ -12,6 +12,7 @@
 //!      exception
 
 use crate::{FfiDefault, Lower, RustBuffer, UniFfiTag};
+use std::any::Any;
 use std::mem::MaybeUninit;
 use std::panic;
 
@@ -24,9 +25,10 @@ use std::panic;
 /// - A pointer to this object is passed to the rust FFI function.  This is an
 ///   "out parameter" which will be updated with any error that occurred during the function's
 ///   execution.
-/// - After the call, if `code` is [RustCallStatusCode::Error] or [RustCallStatusCode::UnexpectedError]
-///   then `error_buf` will be updated to contain a serialized error object.   See
-///   [RustCallStatusCode] for what gets serialized. The consumer is responsible for freeing `error_buf`.
+/// - After the call, if `code` is [RustCallStatusCode::Error], [RustCallStatusCode::UnexpectedError]
+///   or [RustCallStatusCode::Panic] then `error_buf` will be updated to contain a serialized error
+///   object.   See [RustCallStatusCode] for what gets serialized. The consumer is responsible for
+///   freeing `error_buf`.
 ///
 /// ## Layout/fields
 ///
@@ -104,6 +106,66 @@ pub enum RustCallStatusCode {
     /// This is only returned for async functions and only if the bindings code uses the
     /// [rust_future_cancel] call.
     Cancelled = 3,
+    /// The Rust code panicked.  [RustCallStatus::error_buf] will contain the serialized
+    /// [PanicDetails], or be empty if they couldn't be serialized.
+    Panic = 4,
+}
+
+/// Details of a Rust panic, passed to the foreign side with [RustCallStatusCode::Panic]
+///
+/// These are serialized like a record with these fields, in this order.  The strings are empty
+/// and `line` is 0 when they're unknown.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PanicDetails {
+    /// The panic message
+    pub message: String,
+    /// Source file where the panic happened
+    pub file: String,
+    /// Line where the panic happened
+    pub line: u32,
+    /// Name of the panicking thread
+    pub thread: String,
+    /// Backtrace of the panic, only captured if enabled with the `RUST_BACKTRACE` or
+    /// `RUST_LIB_BACKTRACE` environment variables
+    pub backtrace: String,
+}
+
+impl PanicDetails {
+    /// Get the details of a panic caught by `catch_unwind()` on the panicking thread
+    pub fn from_panic(cause: &(dyn Any + Send)) -> Self {
+        // The documentation suggests that it will *usually* be a str or String.
+        let message = if let Some(s) = cause.downcast_ref::<&'static str>() {
+            (*s).to_string()
+        } else if let Some(s) = cause.downcast_ref::<String>() {
+            s.clone()
+        } else {
+            "Unknown panic!".to_string()
+        };
+        let location = crate::panichook::take_panic_location();
+        Self {
+            message,
+            file: location
+                .as_ref()
+                .map(|l| l.file.clone())
+                .unwrap_or_default(),
+            line: location.as_ref().map(|l| l.line).unwrap_or_default(),
+            thread: std::thread::current()
+                .name()
+                .map(str::to_string)
+                .unwrap_or_default(),
+            backtrace: location.and_then(|l| l.backtrace).unwrap_or_default(),
+        }
+    }
+
+    pub fn lower(self) -> RustBuffer {
+        let mut buf = Vec::new();
+        <String as Lower<UniFfiTag>>::write(self.message, &mut buf);
+        <String as Lower<UniFfiTag>>::write(self.file, &mut buf);
+        <u32 as Lower<UniFfiTag>>::write(self.line, &mut buf);
+        <String as Lower<UniFfiTag>>::write(self.thread, &mut buf);
+        <String as Lower<UniFfiTag>>::write(self.backtrace, &mut buf);
+        RustBuffer::from_vec(buf)
+    }
 }
 
 /// Handle a scaffolding calls
@@ -121,9 +183,9 @@ pub enum RustCallStatusCode {
 ///       code is responsible for freeing the `RustBuffer`
 ///     - `FfiDefault::ffi_default()` is returned, although foreign code should ignore this value
 /// - If the function panics:
-///     - `out_status.code` will be set to `CALL_PANIC`
-///     - `out_status.error_buf` will be set to a newly allocated `RustBuffer` containing a
-///       serialized error message.  The calling code is responsible for freeing the `RustBuffer`
+///     - `out_status.code` will be set to [RustCallStatusCode::Panic]
+///     - `out_status.error_buf` will be set to a newly allocated `RustBuffer` containing the
+///       serialized [PanicDetails].  The calling code is responsible for freeing the `RustBuffer`
 ///     - `FfiDefault::ffi_default()` is returned, although foreign code should ignore this value
 pub fn rust_call<F, R>(out_status: &mut RustCallStatus, callback: F) -> R
 where
@@ -166,20 +228,13 @@ where
         }
         // Callback panicked
         Err(cause) => {
-            out_status.code = RustCallStatusCode::UnexpectedError;
-            // Try to coerce the cause into a RustBuffer containing a String.  Since this code can
-            // panic, we need to use a second catch_unwind().
+            out_status.code = RustCallStatusCode::Panic;
+            // Try to serialize the panic details into a RustBuffer.  Since this code can panic, we
+            // need to use a second catch_unwind().
             let message_result = panic::catch_unwind(panic::AssertUnwindSafe(move || {
-                // The documentation suggests that it will *usually* be a str or String.
-                let message = if let Some(s) = cause.downcast_ref::<&'static str>() {
-                    (*s).to_string()
-                } else if let Some(s) = cause.downcast_ref::<String>() {
-                    s.clone()
-                } else {
-                    "Unknown panic!".to_string()
-                };
-                log::error!("Caught a panic calling rust code: {:?}", message);
-                <String as Lower<UniFfiTag>>::lower(message)
+                let details = PanicDetails::from_panic(&*cause);
+                log::error!("Caught a panic calling rust code: {:?}", details.message);
+                details.lower()
             }));
             if let Ok(buf) = message_result {
                 unsafe {
@@ -241,12 +296,28 @@ mod test {
         rust_call(&mut status, || {
             <Result<i8, TestError> as LowerReturn<UniFfiTag>>::lower_return(test_callback(2))
         });
-        assert_eq!(status.code, RustCallStatusCode::UnexpectedError);
-        unsafe {
-            assert_eq!(
-                <String as Lift<UniFfiTag>>::try_lift(status.error_buf.assume_init()).unwrap(),
-                "Unexpected value: 2"
-            );
-        }
+        assert_eq!(status.code, RustCallStatusCode::Panic);
+        let details = read_panic_details(unsafe { status.error_buf.assume_init() });
+        assert_eq!(details.message, "Unexpected value: 2");
+        assert!(details.file.ends_with("rustcalls.rs"));
+        assert!(details.line > 0);
+        assert_eq!(
+            details.thread,
+            std::thread::current().name().unwrap_or_default()
+        );
+    }
+
+    fn read_panic_details(buf: RustBuffer) -> PanicDetails {
+        let vec = buf.destroy_into_vec();
+        let mut buf = vec.as_slice();
+        let details = PanicDetails {
+            message: <String as Lift<UniFfiTag>>::try_read(&mut buf).unwrap(),
+            file: <String as Lift<UniFfiTag>>::try_read(&mut buf).unwrap(),
+            line: <u32 as Lift<UniFfiTag>>::try_read(&mut buf).unwrap(),
+            thread: <String as Lift<UniFfiTag>>::try_read(&mut buf).unwrap(),
+            backtrace: <String as Lift<UniFfiTag>>::try_read(&mut buf).unwrap(),
+        };
+        assert!(buf.is_empty());
+        details
     }
 }
@@ -5,8 +5,10 @@
 use std::{future::Future, sync::Arc};
 
 mod future;
+mod runtime;
 mod scheduler;
 use future::*;
+pub use runtime::*;
 use scheduler::*;
 
 #[cfg(test)]
@@ -56,6 +58,21 @@ where
     )
 }
 
+/// Create a new [Handle] for a Rust future that runs in an [AsyncRuntime]
+///
+/// This is used instead of [rust_future_new] for functions exported with
+/// `#[uniffi::export(async_runtime = ...)]`.
+pub fn rust_future_new_in_runtime<R, F, T, UT>(future: F, tag: UT) -> Handle
+where
+    R: AsyncRuntime,
+    F: Future<Output = T> + Send + 'static,
+    T: LowerReturn<UT> + Send + 'static,
+    UT: Send + 'static,
+    dyn RustFutureFfi<T::ReturnType>: HandleAlloc<UT>,
+{
+    rust_future_new(R::wrap(future), tag)
+}
+
 /// Poll a Rust future
 ///
 /// When the future is ready to progress the continuation will be called with the `data` value and
@@ -0,0 +1,81 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Async runtimes for exported async functions
+//!
+//! The foreign executor polls Rust futures, which is all that's needed for futures that only wait
+//! on other futures.  Futures that use runtime services, like IO or timers, also need that
+//! runtime.  `#[uniffi::export(async_runtime = ...)]` names an [AsyncRuntime] to provide it.
+
+use std::future::Future;
+
+/// An async runtime that exported async functions can run in
+///
+/// Implement this once for a type, then export functions with
+/// `#[uniffi::export(async_runtime = my_crate::MyRuntime)]`.  The scaffolding passes each future
+/// to [AsyncRuntime::wrap] before handing it to the foreign executor.
+///
+/// Implementations can either wrap the future so that it enters the runtime whenever it's
+/// polled, or spawn it onto the runtime and return a future for its result.  In the second case,
+/// dropping the returned future should cancel the spawned task, since that's what happens when
+/// the foreign code cancels the call.
+pub trait AsyncRuntime {
+    /// The future returned by [AsyncRuntime::wrap]
+    type Future<F>: Future<Output = F::Output> + Send + 'static
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static;
+
+    /// Wrap or spawn the future of an exported async function
+    fn wrap<F>(future: F) -> Self::Future<F>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static;
+}
+
+/// Run futures in the Tokio runtime, with `#[uniffi::export(async_runtime = "tokio")]`
+///
+/// Futures are wrapped with [async_compat::Compat], which enters a Tokio runtime when they're
+/// polled.
+#[cfg(feature = "tokio")]
+pub struct TokioRuntime;
+
+#[cfg(feature = "tokio")]
+impl AsyncRuntime for TokioRuntime {
+    type Future<F> = async_compat::Compat<F>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static;
+
+    fn wrap<F>(future: F) -> Self::Future<F>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        async_compat::Compat::new(future)
+    }
+}
+
+/// Run futures in smol's global executor, with `#[uniffi::export(async_runtime = "smol")]`
+///
+/// Futures are spawned with [smol::spawn], which also works for `async-std` futures, since both
+/// are built on `async-io`.
+#[cfg(feature = "smol")]
+pub struct SmolRuntime;
+
+#[cfg(feature = "smol")]
+impl AsyncRuntime for SmolRuntime {
+    type Future<F> = smol::Task<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static;
+
+    fn wrap<F>(future: F) -> Self::Future<F>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        smol::spawn(future)
+    }
+}
@@ -0,0 +1,125 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::{
+    mem::ManuallyDrop,
+    thread::{self, ThreadId},
+};
+
+/// Storage for `#[uniffi(thread_affine)]` objects
+///
+/// Wraps a value that's not `Send`/`Sync` and records the thread that created it.  The value can
+/// only be accessed from that thread.  This is what lets us pass thread-affine objects across the
+/// FFI in an `Arc`: the scaffolding checks [ThreadBound::is_owner_thread] whenever the object is
+/// lifted and returns an error rather than touching the value from another thread.
+pub struct ThreadBound<T> {
+    owner: ThreadId,
+    value: ManuallyDrop<T>,
+}
+
+// Safety: these impls don't need `T: Send` or `T: Sync` because the inner value is only ever
+// touched on the owner thread.  Every path to it checks the current thread first: `get()` and
+// `into_inner()` panic on other threads, and `Drop` leaks the value rather than dropping it there.
+// Other threads can only read `owner`, which is `Send + Sync`.
+unsafe impl<T> Send for ThreadBound<T> {}
+unsafe impl<T> Sync for ThreadBound<T> {}
+
+impl<T> ThreadBound<T> {
+    /// Wrap a value, binding it to the current thread
+    pub fn new(value: T) -> Self {
+        Self {
+            owner: thread::current().id(),
+            value: ManuallyDrop::new(value),
+        }
+    }
+
+    /// Is the current thread the one that created this value?
+    pub fn is_owner_thread(&self) -> bool {
+        thread::current().id() == self.owner
+    }
+
+    /// Get a reference to the value
+    ///
+    /// Panics if called from any thread other than the owner thread.
+    pub fn get(&self) -> &T {
+        self.assert_owner_thread();
+        &self.value
+    }
+
+    /// Unwrap the value
+    ///
+    /// Panics if called from any thread other than the owner thread.
+    pub fn into_inner(self) -> T {
+        self.assert_owner_thread();
+        let mut this = ManuallyDrop::new(self);
+        // Safety: `this` is never used again and its destructor won't run.
+        unsafe { ManuallyDrop::take(&mut this.value) }
+    }
+
+    fn assert_owner_thread(&self) {
+        if !self.is_owner_thread() {
+            panic!("ThreadBound value accessed from a thread other than the one that created it");
+        }
+    }
+}
+
+impl<T> Drop for ThreadBound<T> {
+    fn drop(&mut self) {
+        if self.is_owner_thread() {
+            // Safety: `self.value` is never used again.
+            unsafe { ManuallyDrop::drop(&mut self.value) }
+        } else {
+            // Dropping the value here could break whatever invariant made it thread-affine.
+            // Leaking is the only safe option.
+            log::warn!(
+                "{} dropped from a thread other than the one that created it, leaking it",
+                std::any::type_name::<T>()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::{rc::Rc, sync::Arc};
+
+    #[test]
+    fn test_owner_thread() {
+        let bound = Arc::new(ThreadBound::new(Rc::new(5)));
+        assert!(bound.is_owner_thread());
+        assert_eq!(**bound.get(), 5);
+
+        let other = Arc::clone(&bound);
+        let from_other_thread = thread::spawn(move || other.is_owner_thread())
+            .join()
+            .unwrap();
+        assert!(!from_other_thread);
+    }
+
+    #[test]
+    fn test_get_from_other_thread_panics() {
+        let bound = Arc::new(ThreadBound::new(Rc::new(5)));
+        let other = Arc::clone(&bound);
+        let result = thread::spawn(move || {
+            other.get();
+        })
+        .join();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_drop_on_other_thread_leaks() {
+        let value = Rc::new(5);
+        let bound = ThreadBound::new(Rc::clone(&value));
+        thread::spawn(move || drop(bound)).join().unwrap();
+        // The clone was leaked rather than dropped
+        assert_eq!(Rc::strong_count(&value), 2);
+
+        let bound = ThreadBound::new(Rc::clone(&value));
+        drop(bound);
+        assert_eq!(Rc::strong_count(&value), 2);
+        assert_eq!(*ThreadBound::new(value).into_inner(), 5);
+    }
+}
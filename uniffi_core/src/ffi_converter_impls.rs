@@ -445,6 +445,38 @@ derive_ffi_traits!(impl<T, UT> LiftRef<UT> for Arc<T> where Arc<T>: Lift<UT>, T:
 
 // Implement LowerReturn/LiftReturn for the unit type (void returns)
 
+// `()` only implements `Lower` and `Lift` so that it can be used as the return type of closures,
+// which pass their return values in a `RustBuffer`.  The bindings have no unit type to use
+// anywhere else, for example in a `Vec<()>` or as an argument.  To keep it out of those, its
+// `TYPE_ID_META` fails to compile when it's evaluated.  The closure code takes the metadata for its
+// return type from `LowerReturn` instead, so it's the only place `()` can be used.
+
+unsafe impl<UT> Lower<UT> for () {
+    type FfiType = ();
+
+    fn lower(_: ()) {}
+
+    fn write(_: (), _: &mut Vec<u8>) {}
+
+    const TYPE_ID_META: MetadataBuffer =
+        panic!("`()` can only be used as a return type, including the return type of closures");
+}
+
+unsafe impl<UT> Lift<UT> for () {
+    type FfiType = ();
+
+    fn try_lift(_: ()) -> Result<()> {
+        Ok(())
+    }
+
+    fn try_read(_: &mut &[u8]) -> Result<()> {
+        Ok(())
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        panic!("`()` can only be used as a return type, including the return type of closures");
+}
+
 unsafe impl<UT> LowerReturn<UT> for () {
     type ReturnType = ();
 
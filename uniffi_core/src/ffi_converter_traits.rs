@@ -46,7 +46,11 @@
 //! These traits should not be used directly, only in generated code, and the generated code should
 //! have fixture tests to test that everything works correctly together.
 
-use std::{borrow::Borrow, sync::Arc};
+use std::{
+    borrow::Borrow,
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
 
 use anyhow::bail;
 use bytes::Buf;
@@ -377,6 +381,51 @@ pub unsafe trait LiftRef<UT> {
     type LiftType: Lift<UT> + Borrow<Self>;
 }
 
+/// Access the Rust value behind an object pointer
+///
+/// Objects are passed across the FFI as `Arc<Self::Stored>`.  For most objects `Stored` is just
+/// `Self`, but objects declared with `#[uniffi(mutable)]` are stored in a `Mutex` or `RwLock` so
+/// that methods can take `&mut self`.  The generated method scaffolding uses this trait to get
+/// from the stored value to the `self` that the method is called with.
+///
+/// This trait is implemented by `#[derive(uniffi::Object)]` and should not be implemented
+/// manually.
+pub trait ObjectAccess<UT> {
+    /// The value that the `Arc` passed across the FFI points to.
+    type Stored: Send + Sync;
+    /// Guard returned when a method borrows `&self`.
+    type Ref<'a>: Deref<Target = Self>
+    where
+        Self: 'a;
+
+    fn get_ref(stored: &Self::Stored) -> Self::Ref<'_>;
+
+    /// Check that the object can be taken, before taking it for a method that takes `self` by
+    /// value.
+    fn check_take(stored: &Self::Stored) -> Result<()>;
+
+    /// Take ownership of the object, for methods that take `self` by value.
+    ///
+    /// `stored` was unwrapped from the last reference to the object with
+    /// [crate::object_handle_take].
+    fn take(stored: Self::Stored) -> Self
+    where
+        Self: Sized;
+}
+
+/// Mutably access the Rust value behind an object pointer
+///
+/// Implemented for objects declared with `#[uniffi(mutable)]`, which is what allows their methods
+/// to take `&mut self`.
+pub trait ObjectAccessMut<UT>: ObjectAccess<UT> {
+    /// Guard returned when a method borrows `&mut self`.
+    type RefMut<'a>: DerefMut<Target = Self>
+    where
+        Self: 'a;
+
+    fn get_mut(stored: &Self::Stored) -> Self::RefMut<'_>;
+}
+
 pub trait ConvertError<UT>: Sized {
     fn try_convert_unexpected_callback_error(e: UnexpectedUniFFICallbackError) -> Result<Self>;
 }
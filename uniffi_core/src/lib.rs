@@ -46,7 +46,7 @@ pub mod metadata;
 pub use ffi::*;
 pub use ffi_converter_traits::{
     ConvertError, FfiConverter, FfiConverterArc, HandleAlloc, Lift, LiftRef, LiftReturn, Lower,
-    LowerReturn,
+    LowerReturn, ObjectAccess, ObjectAccessMut,
 };
 pub use metadata::*;
 
@@ -133,6 +133,29 @@ macro_rules! assert_compatible_version {
     };
 }
 
+/// Define a static holding an embedded source file, i.e. a UDL file or `uniffi.toml`.
+///
+/// This is a helper hook for the generated Rust scaffolding.  Library mode reads these statics
+/// to generate bindings without the crate's sources.  They're only defined when the
+/// `embed-sources` feature is enabled, since they ship the files with the library.
+#[cfg(feature = "embed-sources")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! embed_source_file {
+    ($ident:ident, $size:expr, $data:expr $(,)?) => {
+        #[doc(hidden)]
+        #[no_mangle]
+        pub static $ident: [u8; $size] = *$data;
+    };
+}
+
+#[cfg(not(feature = "embed-sources"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! embed_source_file {
+    ($ident:ident, $size:expr, $data:expr $(,)?) => {};
+}
+
 /// Struct to use when we want to lift/lower/serialize types inside the `uniffi` crate.
 struct UniFfiTag;
 
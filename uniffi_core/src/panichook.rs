@@ -1,34 +1,82 @@
-/// Initialize our panic handling hook to optionally log panics
-#[cfg(feature = "log_panics")]
+//! Panic hook that records where panics happen
+//!
+//! `rust_call()` only gets the panic payload from `catch_unwind()`, so the location and backtrace
+//! are recorded by the hook, on the panicking thread, and picked up from there afterwards.
+
+use std::cell::RefCell;
+
+/// Where a panic happened
+pub struct PanicLocation {
+    pub file: String,
+    pub line: u32,
+    /// Only captured if enabled with the `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE` environment
+    /// variables
+    pub backtrace: Option<String>,
+}
+
+thread_local! {
+    static LAST_PANIC: RefCell<Option<PanicLocation>> = RefCell::new(None);
+}
+
+/// Take the location of the last panic on this thread
+///
+/// This is `None` if the application replaced our panic hook.
+pub fn take_panic_location() -> Option<PanicLocation> {
+    LAST_PANIC
+        .try_with(|last| last.borrow_mut().take())
+        .ok()
+        .flatten()
+}
+
+/// Initialize our panic hook, which records the location of panics and optionally logs them
+///
+/// With the `log_panics` feature, our hook replaces the previous one so that panics are only
+/// reported once.  Otherwise the previous hook is still called afterwards.
 pub fn ensure_setup() {
     use std::sync::Once;
-    static INIT_BACKTRACES: Once = Once::new();
-    INIT_BACKTRACES.call_once(move || {
+    static INIT_HOOK: Once = Once::new();
+    INIT_HOOK.call_once(move || {
         #[cfg(all(feature = "log_backtraces", not(target_os = "android")))]
         {
             std::env::set_var("RUST_BACKTRACE", "1");
         }
-        // Turn on a panic hook which logs both backtraces and the panic
-        // "Location" (file/line). We do both in case we've been stripped,
-        // ).
+        #[cfg(not(feature = "log_panics"))]
+        let previous_hook = std::panic::take_hook();
         std::panic::set_hook(Box::new(move |panic_info| {
-            let (file, line) = if let Some(loc) = panic_info.location() {
-                (loc.file(), loc.line())
-            } else {
-                // Apparently this won't happen but rust has reserved the
-                // ability to start returning None from location in some cases
-                // in the future.
-                ("<unknown>", 0)
-            };
-            log::error!("### Rust `panic!` hit at file '{file}', line {line}");
-            #[cfg(all(feature = "log_backtraces", not(target_os = "android")))]
+            // Apparently the location is always set but rust has reserved the ability to start
+            // returning None in some cases in the future.
+            if let Some(loc) = panic_info.location() {
+                let backtrace = std::backtrace::Backtrace::capture();
+                let backtrace = match backtrace.status() {
+                    std::backtrace::BacktraceStatus::Captured => Some(backtrace.to_string()),
+                    _ => None,
+                };
+                // This fails if the thread is being torn down, then there's no call to report the
+                // panic to anyway.
+                let _ = LAST_PANIC.try_with(|last| {
+                    *last.borrow_mut() = Some(PanicLocation {
+                        file: loc.file().to_string(),
+                        line: loc.line(),
+                        backtrace,
+                    })
+                });
+            }
+            // Log both backtraces and the panic "Location" (file/line). We do both in case we've
+            // been stripped.
+            #[cfg(feature = "log_panics")]
             {
-                log::error!("  Complete stack trace:\n{:?}", backtrace::Backtrace::new());
+                let (file, line) = match panic_info.location() {
+                    Some(loc) => (loc.file(), loc.line()),
+                    None => ("<unknown>", 0),
+                };
+                log::error!("### Rust `panic!` hit at file '{file}', line {line}");
+                #[cfg(all(feature = "log_backtraces", not(target_os = "android")))]
+                {
+                    log::error!("  Complete stack trace:\n{:?}", backtrace::Backtrace::new());
+                }
             }
+            #[cfg(not(feature = "log_panics"))]
+            previous_hook(panic_info);
         }));
     });
 }
-
-/// Initialize our panic handling hook to optionally log panics
-#[cfg(not(feature = "log_panics"))]
-pub fn ensure_setup() {}
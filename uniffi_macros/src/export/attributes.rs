@@ -6,11 +6,11 @@ use crate::{
 };
 
 use proc_macro2::TokenStream;
-use quote::ToTokens;
+use quote::{quote_spanned, ToTokens};
 use syn::{
     parenthesized,
     parse::{Parse, ParseStream},
-    Attribute, Ident, LitStr, Meta, PathArguments, PathSegment, Token,
+    Attribute, Ident, LitStr, Meta, Path, PathArguments, PathSegment, Token,
 };
 use uniffi_meta::UniffiTraitDiscriminants;
 
@@ -74,6 +74,7 @@ impl UniffiAttributeArgs for ExportTraitArgs {
 #[derive(Clone, Default)]
 pub struct ExportFnArgs {
     pub(crate) async_runtime: Option<AsyncRuntime>,
+    pub(crate) blocking: Option<kw::blocking>,
     pub(crate) name: Option<String>,
     pub(crate) defaults: DefaultMap,
 }
@@ -94,6 +95,11 @@ impl UniffiAttributeArgs for ExportFnArgs {
                 async_runtime: Some(input.parse()?),
                 ..Self::default()
             })
+        } else if lookahead.peek(kw::blocking) {
+            Ok(Self {
+                blocking: input.parse()?,
+                ..Self::default()
+            })
         } else if lookahead.peek(kw::name) {
             let _: kw::name = input.parse()?;
             let _: Token![=] = input.parse()?;
@@ -118,6 +124,7 @@ impl UniffiAttributeArgs for ExportFnArgs {
     fn merge(self, other: Self) -> syn::Result<Self> {
         Ok(Self {
             async_runtime: either_attribute_arg(self.async_runtime, other.async_runtime)?,
+            blocking: either_attribute_arg(self.blocking, other.blocking)?,
             name: either_attribute_arg(self.name, other.name)?,
             defaults: self.defaults.merge(other.defaults),
         })
@@ -212,18 +219,41 @@ impl UniffiAttributeArgs for ExportStructArgs {
 
 #[derive(Clone)]
 pub(crate) enum AsyncRuntime {
-    Tokio(LitStr),
+    // One of the runtimes built into UniFFI, named with a string
+    Builtin(LitStr),
+    // A type that implements `uniffi::AsyncRuntime`
+    Path(Path),
+}
+
+impl AsyncRuntime {
+    /// The type that implements `uniffi::AsyncRuntime`
+    pub(crate) fn runtime_type(&self) -> TokenStream {
+        match self {
+            Self::Builtin(lit) => match lit.value().as_str() {
+                "tokio" => quote_spanned! { lit.span() => ::uniffi::TokioRuntime },
+                "smol" => quote_spanned! { lit.span() => ::uniffi::SmolRuntime },
+                _ => unreachable!(),
+            },
+            Self::Path(path) => path.to_token_stream(),
+        }
+    }
 }
 
 impl Parse for AsyncRuntime {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
-        let lit: LitStr = input.parse()?;
-        match lit.value().as_str() {
-            "tokio" => Ok(Self::Tokio(lit)),
-            _ => Err(syn::Error::new_spanned(
-                lit,
-                "unknown async runtime, currently only `tokio` is supported",
-            )),
+        if input.peek(LitStr) {
+            let lit: LitStr = input.parse()?;
+            match lit.value().as_str() {
+                "tokio" | "smol" => Ok(Self::Builtin(lit)),
+                _ => Err(syn::Error::new_spanned(
+                    lit,
+                    "unknown async runtime, the built-in runtimes are `tokio` and `smol`. \
+                     Other runtimes can be used by passing the path to a type that implements \
+                     `uniffi::AsyncRuntime`",
+                )),
+            }
+        } else {
+            Ok(Self::Path(input.parse()?))
         }
     }
 }
@@ -231,7 +261,8 @@ impl Parse for AsyncRuntime {
 impl ToTokens for AsyncRuntime {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         match self {
-            AsyncRuntime::Tokio(lit) => lit.to_tokens(tokens),
+            AsyncRuntime::Builtin(lit) => lit.to_tokens(tokens),
+            AsyncRuntime::Path(path) => path.to_tokens(tokens),
         }
     }
 }
@@ -241,6 +272,7 @@ impl ToTokens for AsyncRuntime {
 /// This stores the parsed arguments for `uniffi::constructor` and `uniffi::method`
 #[derive(Clone, Default)]
 pub struct ExportedImplFnArgs {
+    pub(crate) blocking: Option<kw::blocking>,
     pub(crate) name: Option<String>,
     pub(crate) defaults: DefaultMap,
 }
@@ -254,7 +286,12 @@ impl Parse for ExportedImplFnArgs {
 impl UniffiAttributeArgs for ExportedImplFnArgs {
     fn parse_one(input: ParseStream<'_>) -> syn::Result<Self> {
         let lookahead = input.lookahead1();
-        if lookahead.peek(kw::name) {
+        if lookahead.peek(kw::blocking) {
+            Ok(Self {
+                blocking: input.parse()?,
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::name) {
             let _: kw::name = input.parse()?;
             let _: Token![=] = input.parse()?;
             let name = Some(input.parse::<LitStr>()?.value());
@@ -277,6 +314,7 @@ impl UniffiAttributeArgs for ExportedImplFnArgs {
 
     fn merge(self, other: Self) -> syn::Result<Self> {
         Ok(Self {
+            blocking: either_attribute_arg(self.blocking, other.blocking)?,
             name: either_attribute_arg(self.name, other.name)?,
             defaults: self.defaults.merge(other.defaults),
         })
@@ -6,7 +6,8 @@ use crate::{
     export::ImplItem,
     fnsig::{FnKind, FnSignature, ReceiverArg},
     util::{
-        create_metadata_items, derive_ffi_traits, ident_to_string, mod_path, tagged_impl_header,
+        create_metadata_items, derive_ffi_traits, ident_to_string, jni_shim, mod_path,
+        tagged_impl_header,
     },
 };
 use proc_macro2::{Span, TokenStream};
@@ -77,6 +78,11 @@ pub(super) fn trait_impl(
         .map(|sig| gen_method_impl(sig, &vtable_cell))
         .collect::<syn::Result<Vec<_>>>()?;
     let has_async_method = methods.iter().any(|m| m.is_async);
+    let init_jni_shim = jni_shim(
+        &init_ident,
+        quote! { vtable: ::std::ptr::NonNull<#vtable_type> },
+        None,
+    );
     let impl_attributes = has_async_method.then(|| quote! { #[::async_trait::async_trait] });
 
     Ok(quote! {
@@ -92,6 +98,8 @@ pub(super) fn trait_impl(
             #vtable_cell.set(vtable);
         }
 
+        #init_jni_shim
+
         #[derive(Debug)]
         struct #trait_impl_ident {
             handle: u64,
@@ -194,6 +202,12 @@ fn gen_method_impl(sig: &FnSignature, vtable_cell: &Ident) -> syn::Result<TokenS
     let self_param = match receiver {
         Some(ReceiverArg::Ref) => quote! { &self },
         Some(ReceiverArg::Arc) => quote! { self: Arc<Self> },
+        Some(ReceiverArg::RefMut | ReceiverArg::Value) => {
+            return Err(syn::Error::new(
+                *span,
+                "callback interface methods must take &self or self: Arc<Self>",
+            ));
+        }
         None => {
             return Err(syn::Error::new(
                 *span,
@@ -7,7 +7,10 @@ use quote::quote;
 use std::iter;
 
 use super::attributes::AsyncRuntime;
-use crate::fnsig::{FnKind, FnSignature};
+use crate::{
+    fnsig::{FnKind, FnSignature, ReceiverArg},
+    util::{ident_to_string, jni_shim},
+};
 
 pub(super) fn gen_fn_scaffolding(
     sig: FnSignature,
@@ -94,6 +97,9 @@ struct ScaffoldingBits {
     param_names: Vec<TokenStream>,
     /// Parameter types for the scaffolding function
     param_types: Vec<TokenStream>,
+    /// Statements that run before the lift closure, outside `rust_call`.  Methods that consume
+    /// `self` use this to take the object, returning early if that fails.
+    take_self: Option<TokenStream>,
     /// Lift closure.  See `FnSignature::lift_closure` for an explanation of this.
     lift_closure: TokenStream,
     /// Expression to call the Rust function after a successful lift.
@@ -118,6 +124,7 @@ impl ScaffoldingBits {
         Self {
             param_names: sig.scaffolding_param_names().collect(),
             param_types: sig.scaffolding_param_types().collect(),
+            take_self: None,
             lift_closure: sig.lift_closure(None),
             rust_fn_call,
             convert_result,
@@ -131,24 +138,65 @@ impl ScaffoldingBits {
         udl_mode: bool,
     ) -> Self {
         let ident = &sig.ident;
+        // Sync methods that borrow or consume `self` go through `ObjectAccess`, which knows how the
+        // object is stored.  This is what lets `#[uniffi(mutable)]` objects lock their
+        // `Mutex`/`RwLock`.
+        let access_self = !is_trait
+            && !sig.is_async
+            && matches!(
+                sig.receiver,
+                Some(ReceiverArg::Ref | ReceiverArg::RefMut | ReceiverArg::Value)
+            );
         let lift_impl = if is_trait {
             quote! {
                 <::std::sync::Arc<dyn #self_ident> as ::uniffi::Lift<crate::UniFfiTag>>
             }
+        } else if access_self {
+            quote! {
+                <::std::sync::Arc<<#self_ident as ::uniffi::ObjectAccess<crate::UniFfiTag>>::Stored> as ::uniffi::Lift<crate::UniFfiTag>>
+            }
         } else {
             quote! {
                 <::std::sync::Arc<#self_ident> as ::uniffi::Lift<crate::UniFfiTag>>
             }
         };
+        // Methods that consume `self` need to unwrap the `Arc`, which fails if the object is still
+        // referenced elsewhere.  That happens before the call, so that the failure is reported as
+        // an unexpected error and the foreign side keeps its reference.
+        let take_self = matches!(sig.receiver, Some(ReceiverArg::Value)).then(|| {
+            let self_name = ident_to_string(self_ident);
+            let return_impl = sig.lower_return_impl();
+            quote! {
+                let uniffi_self_taken = match unsafe {
+                    ::uniffi::object_handle_take::<<#self_ident as ::uniffi::ObjectAccess<crate::UniFfiTag>>::Stored>(
+                        uniffi_self_lowered,
+                        #self_name,
+                        <#self_ident as ::uniffi::ObjectAccess<crate::UniFfiTag>>::check_take,
+                        call_status,
+                    )
+                } {
+                    Some(v) => ::std::panic::AssertUnwindSafe(v),
+                    None => return <#return_impl::ReturnType as ::uniffi::FfiDefault>::ffi_default(),
+                };
+            }
+        });
         let try_lift_self = if is_trait {
             // For trait interfaces we need to special case this.  Trait interfaces normally lift
             // foreign trait impl pointers.  However, for a method call, we want to lift a Rust
             // pointer.
+            let self_name = ident_to_string(self_ident);
+            quote! {
+                unsafe {
+                    ::uniffi::object_handle_lift::<::std::sync::Arc<dyn #self_ident>>(uniffi_self_lowered, #self_name)
+                }
+                .map(|outer| ::std::sync::Arc::clone(&*outer))
+            }
+        } else if take_self.is_some() {
             quote! {
                 {
-                    let boxed_foreign_arc = unsafe { Box::from_raw(uniffi_self_lowered as *mut ::std::sync::Arc<dyn #self_ident>) };
-                    // Take a clone for our own use.
-                    Ok(*boxed_foreign_arc)
+                    // Move the whole `AssertUnwindSafe` into the closure, not just its field
+                    let uniffi_self_taken = uniffi_self_taken;
+                    Ok(<#self_ident as ::uniffi::ObjectAccess<crate::UniFfiTag>>::take(uniffi_self_taken.0))
                 }
             }
         } else {
@@ -162,7 +210,15 @@ impl ScaffoldingBits {
             }
         }));
         let call_params = sig.rust_call_params(true);
-        let rust_fn_call = quote! { uniffi_args.0.#ident(#call_params) };
+        let rust_fn_call = match (access_self, &sig.receiver) {
+            (true, Some(ReceiverArg::RefMut)) => quote! {
+                <#self_ident as ::uniffi::ObjectAccessMut<crate::UniFfiTag>>::get_mut(&uniffi_args.0).#ident(#call_params)
+            },
+            (true, Some(ReceiverArg::Ref)) => quote! {
+                <#self_ident as ::uniffi::ObjectAccess<crate::UniFfiTag>>::get_ref(&uniffi_args.0).#ident(#call_params)
+            },
+            _ => quote! { uniffi_args.0.#ident(#call_params) },
+        };
         // UDL mode adds an extra conversion (#1749)
         let convert_result = if udl_mode && sig.looks_like_result {
             quote! { uniffi_result .map_err(::std::convert::Into::into) }
@@ -177,6 +233,7 @@ impl ScaffoldingBits {
             param_types: iter::once(quote! { #lift_impl::FfiType })
                 .chain(sig.scaffolding_param_types())
                 .collect(),
+            take_self,
             lift_closure,
             rust_fn_call,
             convert_result,
@@ -200,6 +257,7 @@ impl ScaffoldingBits {
         Self {
             param_names: sig.scaffolding_param_names().collect(),
             param_types: sig.scaffolding_param_types().collect(),
+            take_self: None,
             lift_closure: sig.lift_closure(None),
             rust_fn_call,
             convert_result,
@@ -219,6 +277,7 @@ pub(super) fn gen_ffi_function(
     let ScaffoldingBits {
         param_names,
         param_types,
+        take_self,
         lift_closure,
         rust_fn_call,
         convert_result,
@@ -241,20 +300,35 @@ pub(super) fn gen_ffi_function(
         true => quote! {},
     };
 
+    // Trait methods pass the `self` pointer straight to `object_handle_lift` and consuming methods
+    // pass it to `object_handle_take`
+    let allow_ptr_deref = (matches!(sig.kind, FnKind::TraitMethod { .. }) || take_self.is_some())
+        .then(|| quote! { #[allow(clippy::not_unsafe_ptr_arg_deref)] });
+
     let ffi_ident = sig.scaffolding_fn_ident()?;
     let name = &sig.name;
     let return_ty = &sig.return_ty;
     let return_impl = &sig.lower_return_impl();
 
     Ok(if !sig.is_async {
+        let jni_shim = jni_shim(
+            &ffi_ident,
+            quote! {
+                #(#param_names: #param_types,)*
+                call_status: &mut ::uniffi::RustCallStatus,
+            },
+            Some(quote! { #return_impl::ReturnType }),
+        );
         quote! {
             #[doc(hidden)]
             #[no_mangle]
+            #allow_ptr_deref
             #vis extern "C" fn #ffi_ident(
                 #(#param_names: #param_types,)*
                 call_status: &mut ::uniffi::RustCallStatus,
             ) -> #return_impl::ReturnType {
                 ::uniffi::deps::log::debug!(#name);
+                #take_self
                 let uniffi_lift_args = #lift_closure;
                 ::uniffi::rust_call(call_status, || {
                     #return_impl::lower_return(
@@ -270,24 +344,37 @@ pub(super) fn gen_ffi_function(
                     )
                 })
             }
+
+            #jni_shim
         }
     } else {
-        let mut future_expr = rust_fn_call;
-        if matches!(ar, Some(AsyncRuntime::Tokio(_))) {
-            future_expr = quote! { ::uniffi::deps::async_compat::Compat::new(#future_expr) }
-        }
+        // Functions with an async runtime create the future with `rust_future_new_in_runtime`,
+        // which passes it to the runtime first.
+        let rust_future_new = match ar {
+            Some(ar) => {
+                let runtime_type = ar.runtime_type();
+                quote! { ::uniffi::rust_future_new_in_runtime::<#runtime_type, _, #return_ty, _> }
+            }
+            None => quote! { ::uniffi::rust_future_new::<_, #return_ty, _> },
+        };
+        let jni_shim = jni_shim(
+            &ffi_ident,
+            quote! { #(#param_names: #param_types,)* },
+            Some(quote! { ::uniffi::Handle }),
+        );
 
         quote! {
             #[doc(hidden)]
             #[no_mangle]
+            #allow_ptr_deref
             pub extern "C" fn #ffi_ident(#(#param_names: #param_types,)*) -> ::uniffi::Handle {
                 ::uniffi::deps::log::debug!(#name);
                 let uniffi_lift_args = #lift_closure;
                 match uniffi_lift_args() {
                     Ok(uniffi_args) => {
-                        ::uniffi::rust_future_new::<_, #return_ty, _>(
+                        #rust_future_new(
                             async move {
-                                let uniffi_result = #future_expr.await;
+                                let uniffi_result = #rust_fn_call.await;
                                 #convert_result
                             },
                             crate::UniFfiTag
@@ -303,6 +390,8 @@ pub(super) fn gen_ffi_function(
                     },
                 }
             }
+
+            #jni_shim
         }
     })
 }
@@ -12,7 +12,7 @@ use crate::{
         attributes::ExportTraitArgs, callback_interface, gen_method_scaffolding, item::ImplItem,
     },
     object::interface_meta_static_var,
-    util::{ident_to_string, tagged_impl_header},
+    util::{ident_to_string, jni_shim, tagged_impl_header},
 };
 
 pub(super) fn gen_trait_scaffolding(
@@ -42,6 +42,17 @@ pub(super) fn gen_trait_scaffolding(
         Span::call_site(),
     );
 
+    let ffi_params = quote! {
+        ptr: *const ::std::ffi::c_void,
+        call_status: &mut ::uniffi::RustCallStatus
+    };
+    let clone_jni_shim = jni_shim(
+        &clone_fn_ident,
+        ffi_params.clone(),
+        Some(quote! { *const ::std::ffi::c_void }),
+    );
+    let free_jni_shim = jni_shim(&free_fn_ident, ffi_params, None);
+
     let helper_fn_tokens = quote! {
         #[doc(hidden)]
         #[no_mangle]
@@ -53,11 +64,11 @@ pub(super) fn gen_trait_scaffolding(
             ptr: *const ::std::ffi::c_void,
             call_status: &mut ::uniffi::RustCallStatus
         ) -> *const ::std::ffi::c_void {
-            uniffi::rust_call(call_status, || {
-                let ptr = ptr as *mut std::sync::Arc<dyn #self_ident>;
-                let arc = unsafe { ::std::sync::Arc::clone(&*ptr) };
-                Ok(::std::boxed::Box::into_raw(::std::boxed::Box::new(arc)) as  *const ::std::ffi::c_void)
-            })
+            ::uniffi::object_handle_clone::<::std::sync::Arc<dyn #self_ident>>(
+                ptr,
+                #trait_name,
+                call_status,
+            )
         }
 
         #[doc(hidden)]
@@ -66,19 +77,19 @@ pub(super) fn gen_trait_scaffolding(
         ///
         /// Safety: Only pass pointers returned by a UniFFI call.  Do not pass pointers that were
         /// passed to the free function.
-        ///
-        /// Note: clippy doesn't complain about this being unsafe, but it definitely is since it
-        /// calls `Box::from_raw`.
         pub unsafe extern "C" fn #free_fn_ident(
             ptr: *const ::std::ffi::c_void,
             call_status: &mut ::uniffi::RustCallStatus
         ) {
-            uniffi::rust_call(call_status, || {
-                assert!(!ptr.is_null());
-                drop(unsafe { ::std::boxed::Box::from_raw(ptr as *mut std::sync::Arc<dyn #self_ident>) });
-                Ok(())
-            });
+            ::uniffi::object_handle_free::<::std::sync::Arc<dyn #self_ident>>(
+                ptr,
+                #trait_name,
+                call_status,
+            )
         }
+
+        #clone_jni_shim
+        #free_jni_shim
     };
 
     let impl_tokens: TokenStream = items
@@ -95,7 +106,7 @@ pub(super) fn gen_trait_scaffolding(
         } else {
             ObjectImpl::Trait
         };
-        interface_meta_static_var(&self_ident, imp, mod_path, docstring)
+        interface_meta_static_var(&self_ident, imp, false, false, mod_path, docstring)
             .unwrap_or_else(syn::Error::into_compile_error)
     });
     let ffi_converter_tokens = ffi_converter(mod_path, &self_ident, udl_mode, with_foreign);
@@ -127,10 +138,14 @@ pub(crate) fn ffi_converter(
         }
     } else {
         quote! {
+            #[allow(clippy::not_unsafe_ptr_arg_deref)]
             fn try_lift(v: Self::FfiType) -> ::uniffi::deps::anyhow::Result<::std::sync::Arc<Self>> {
-                unsafe {
-                    Ok(*::std::boxed::Box::from_raw(v as *mut ::std::sync::Arc<Self>))
-                }
+                // Trait objects are wide pointers, so they're passed as a second `Arc` wrapping
+                // the first one.
+                let outer = unsafe {
+                    ::uniffi::object_handle_lift::<::std::sync::Arc<Self>>(v, #trait_name)
+                }?;
+                Ok(::std::sync::Arc::clone(&*outer))
             }
         }
     };
@@ -151,7 +166,7 @@ pub(crate) fn ffi_converter(
             type FfiType = *const ::std::os::raw::c_void;
 
             fn lower(obj: ::std::sync::Arc<Self>) -> Self::FfiType {
-                ::std::boxed::Box::into_raw(::std::boxed::Box::new(obj)) as *const ::std::os::raw::c_void
+                ::uniffi::object_handle_new(::std::sync::Arc::new(obj), #trait_name)
             }
 
             #try_lift
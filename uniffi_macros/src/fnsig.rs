@@ -5,7 +5,7 @@
 use crate::{
     default::{default_value_metadata_calls, DefaultValue},
     export::{DefaultMap, ExportFnArgs, ExportedImplFnArgs},
-    util::{create_metadata_items, ident_to_string, mod_path, try_metadata_value_from_usize},
+    util::{create_metadata_items, ident_to_string, kw, mod_path, try_metadata_value_from_usize},
 };
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
@@ -20,6 +20,8 @@ pub(crate) struct FnSignature {
     // The foreign name for this function, usually == ident.
     pub name: String,
     pub is_async: bool,
+    // Sync function that the bindings should call from a background thread
+    pub is_blocking: bool,
     pub receiver: Option<ReceiverArg>,
     pub args: Vec<NamedArg>,
     pub return_ty: TokenStream,
@@ -36,7 +38,14 @@ impl FnSignature {
         args: ExportFnArgs,
         docstring: String,
     ) -> syn::Result<Self> {
-        Self::new(FnKind::Function, sig, args.name, args.defaults, docstring)
+        Self::new(
+            FnKind::Function,
+            sig,
+            args.name,
+            args.blocking,
+            args.defaults,
+            docstring,
+        )
     }
 
     pub(crate) fn new_method(
@@ -49,6 +58,7 @@ impl FnSignature {
             FnKind::Method { self_ident },
             sig,
             args.name,
+            args.blocking,
             args.defaults,
             docstring,
         )
@@ -64,6 +74,7 @@ impl FnSignature {
             FnKind::Constructor { self_ident },
             sig,
             args.name,
+            args.blocking,
             args.defaults,
             docstring,
         )
@@ -80,6 +91,7 @@ impl FnSignature {
             FnKind::TraitMethod { self_ident, index },
             sig,
             args.name,
+            args.blocking,
             args.defaults,
             docstring,
         )
@@ -89,6 +101,7 @@ impl FnSignature {
         kind: FnKind,
         sig: syn::Signature,
         name: Option<String>,
+        blocking: Option<kw::blocking>,
         mut defaults: DefaultMap,
         docstring: String,
     ) -> syn::Result<Self> {
@@ -108,6 +121,22 @@ impl FnSignature {
             ));
         }
 
+        if let Some(blocking) = blocking {
+            if is_async {
+                return Err(syn::Error::new(
+                    blocking.span,
+                    "`blocking` is only supported for sync functions",
+                ));
+            }
+            if !matches!(kind, FnKind::Function | FnKind::Method { .. }) {
+                return Err(syn::Error::new(
+                    blocking.span,
+                    "`blocking` is only supported for functions and methods",
+                ));
+            }
+        }
+        let is_blocking = blocking.is_some();
+
         let mut input_iter = sig
             .inputs
             .into_iter()
@@ -119,10 +148,39 @@ impl FnSignature {
             .map(|a| match a {
                 Ok(Arg {
                     kind: ArgKind::Receiver(r),
-                    ..
-                }) => r,
+                    span,
+                }) => (r, span),
                 _ => unreachable!(),
             });
+        if let Some((ReceiverArg::RefMut, span)) = &receiver {
+            if !matches!(kind, FnKind::Method { .. }) {
+                return Err(syn::Error::new(
+                    *span,
+                    "`&mut self` is only supported for methods of objects",
+                ));
+            }
+            if is_async {
+                return Err(syn::Error::new(
+                    *span,
+                    "`&mut self` is not supported for async methods",
+                ));
+            }
+        }
+        if let Some((ReceiverArg::Value, span)) = &receiver {
+            if !matches!(kind, FnKind::Method { .. }) {
+                return Err(syn::Error::new(
+                    *span,
+                    "`self` by value is only supported for methods of objects",
+                ));
+            }
+            if is_async || is_blocking {
+                return Err(syn::Error::new(
+                    *span,
+                    "`self` by value is not supported for async or blocking methods",
+                ));
+            }
+        }
+        let receiver = receiver.map(|(r, _)| r);
         let args = input_iter
             .map(|a| {
                 a.and_then(|a| match a.kind {
@@ -148,6 +206,7 @@ impl FnSignature {
             name: name.unwrap_or_else(|| ident_to_string(&ident)),
             ident,
             is_async,
+            is_blocking,
             receiver,
             args,
             return_ty: output,
@@ -257,6 +316,7 @@ impl FnSignature {
             name,
             return_ty,
             is_async,
+            is_blocking,
             mod_path,
             docstring,
             ..
@@ -279,6 +339,7 @@ impl FnSignature {
                     .concat_str(#mod_path)
                     .concat_str(#name)
                     .concat_bool(#is_async)
+                    .concat_bool(#is_blocking)
                     .concat_value(#args_len)
                     #(#arg_metadata_calls)*
                     .concat(<#return_ty as ::uniffi::LowerReturn<crate::UniFfiTag>>::TYPE_ID_META)
@@ -287,12 +348,15 @@ impl FnSignature {
 
             FnKind::Method { self_ident } => {
                 let object_name = ident_to_string(self_ident);
+                let takes_self_by_value = matches!(self.receiver, Some(ReceiverArg::Value));
                 Ok(quote! {
                     ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::METHOD)
                         .concat_str(#mod_path)
                         .concat_str(#object_name)
                         .concat_str(#name)
                         .concat_bool(#is_async)
+                        .concat_bool(#is_blocking)
+                        .concat_bool(#takes_self_by_value)
                         .concat_value(#args_len)
                         #(#arg_metadata_calls)*
                         .concat(<#return_ty as ::uniffi::LowerReturn<crate::UniFfiTag>>::TYPE_ID_META)
@@ -488,11 +552,20 @@ impl Arg {
 
 pub(crate) enum ReceiverArg {
     Ref,
+    RefMut,
     Arc,
+    Value,
 }
 
 impl From<Receiver> for ReceiverArg {
     fn from(receiver: Receiver) -> Self {
+        if receiver.reference.is_some() && receiver.mutability.is_some() {
+            return ReceiverArg::RefMut;
+        }
+        // `self` or `mut self`, without an explicit type
+        if receiver.reference.is_none() && receiver.colon_token.is_none() {
+            return ReceiverArg::Value;
+        }
         if let Type::Path(p) = *receiver.ty {
             if let Some(segment) = p.path.segments.last() {
                 // This comparison will fail if a user uses a typedef for Arc.  Maybe we could
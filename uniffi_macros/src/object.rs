@@ -1,9 +1,10 @@
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::quote;
-use syn::DeriveInput;
+use quote::{quote, ToTokens};
+use syn::{parse::ParseStream, DeriveInput, LitStr, Token};
 
 use crate::util::{
-    create_metadata_items, extract_docstring, ident_to_string, mod_path, tagged_impl_header,
+    create_metadata_items, either_attribute_arg, extract_docstring, ident_to_string, jni_shim, kw,
+    mod_path, tagged_impl_header, AttributeSliceExt, UniffiAttributeArgs,
 };
 use uniffi_meta::ObjectImpl;
 
@@ -11,6 +12,9 @@ pub fn expand_object(input: DeriveInput, udl_mode: bool) -> syn::Result<TokenStr
     let module_path = mod_path()?;
     let ident = &input.ident;
     let docstring = extract_docstring(&input.attrs)?;
+    let attr: ObjectAttr = input.attrs.parse_uniffi_attr_args()?;
+    let storage = attr.storage()?;
+    let stored = storage.stored_type(ident);
     let name = ident_to_string(ident);
     let clone_fn_ident = Ident::new(
         &uniffi_meta::clone_fn_symbol_name(&module_path, &name),
@@ -21,10 +25,28 @@ pub fn expand_object(input: DeriveInput, udl_mode: bool) -> syn::Result<TokenStr
         Span::call_site(),
     );
     let meta_static_var = (!udl_mode).then(|| {
-        interface_meta_static_var(ident, ObjectImpl::Struct, &module_path, docstring)
-            .unwrap_or_else(syn::Error::into_compile_error)
+        interface_meta_static_var(
+            ident,
+            ObjectImpl::Struct,
+            storage == ObjectStorage::ThreadBound,
+            attr.stable_identity.is_some(),
+            &module_path,
+            docstring,
+        )
+        .unwrap_or_else(syn::Error::into_compile_error)
     });
-    let interface_impl = interface_impl(ident, udl_mode);
+    let interface_impl = interface_impl(ident, storage, udl_mode);
+    let object_access_impl = object_access_impl(ident, storage, udl_mode);
+    let ffi_params = quote! {
+        ptr: *const ::std::ffi::c_void,
+        call_status: &mut ::uniffi::RustCallStatus
+    };
+    let clone_jni_shim = jni_shim(
+        &clone_fn_ident,
+        ffi_params.clone(),
+        Some(quote! { *const ::std::ffi::c_void }),
+    );
+    let free_jni_shim = jni_shim(&free_fn_ident, ffi_params, None);
 
     Ok(quote! {
         #[doc(hidden)]
@@ -33,10 +55,7 @@ pub fn expand_object(input: DeriveInput, udl_mode: bool) -> syn::Result<TokenStr
             ptr: *const ::std::ffi::c_void,
             call_status: &mut ::uniffi::RustCallStatus
         ) -> *const ::std::ffi::c_void {
-            uniffi::rust_call(call_status, || {
-                unsafe { ::std::sync::Arc::increment_strong_count(ptr) };
-                Ok(ptr)
-            })
+            ::uniffi::object_handle_clone::<#stored>(ptr, #name, call_status)
         }
 
         #[doc(hidden)]
@@ -45,26 +64,268 @@ pub fn expand_object(input: DeriveInput, udl_mode: bool) -> syn::Result<TokenStr
             ptr: *const ::std::ffi::c_void,
             call_status: &mut ::uniffi::RustCallStatus
         ) {
-            uniffi::rust_call(call_status, || {
-                assert!(!ptr.is_null());
-                let ptr = ptr.cast::<#ident>();
-                unsafe {
-                    ::std::sync::Arc::decrement_strong_count(ptr);
-                }
-                Ok(())
-            });
+            ::uniffi::object_handle_free::<#stored>(ptr, #name, call_status)
         }
 
+        #clone_jni_shim
+        #free_jni_shim
+
         #interface_impl
+        #object_access_impl
         #meta_static_var
     })
 }
 
-pub(crate) fn interface_impl(ident: &Ident, udl_mode: bool) -> TokenStream {
+/// What the `Arc` passed across the FFI points to
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ObjectStorage {
+    /// The object itself
+    Shared,
+    /// `#[uniffi(mutable)]` objects
+    Mutex,
+    RwLock,
+    /// `#[uniffi(thread_affine)]` objects
+    ThreadBound,
+}
+
+impl ObjectStorage {
+    fn stored_type(self, ident: &Ident) -> TokenStream {
+        match self {
+            Self::Shared => quote! { #ident },
+            Self::Mutex => quote! { ::std::sync::Mutex<#ident> },
+            Self::RwLock => quote! { ::std::sync::RwLock<#ident> },
+            Self::ThreadBound => quote! { ::uniffi::ThreadBound<#ident> },
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ObjectAttr {
+    mutable: Option<MutableArg>,
+    thread_affine: Option<kw::thread_affine>,
+    stable_identity: Option<kw::stable_identity>,
+}
+
+/// `mutable`, optionally followed by `= "mutex"` or `= "rwlock"`
+struct MutableArg {
+    kw: kw::mutable,
+    lock: Option<LitStr>,
+}
+
+impl ToTokens for MutableArg {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.kw.to_tokens(tokens);
+    }
+}
+
+impl ObjectAttr {
+    fn storage(&self) -> syn::Result<ObjectStorage> {
+        let mutable = match (&self.mutable, &self.thread_affine) {
+            (None, None) => return Ok(ObjectStorage::Shared),
+            (None, Some(_)) => return Ok(ObjectStorage::ThreadBound),
+            (Some(mutable), None) => mutable,
+            (Some(_), Some(thread_affine)) => {
+                return Err(syn::Error::new_spanned(
+                    thread_affine,
+                    "`thread_affine` and `mutable` can't be combined",
+                ))
+            }
+        };
+        match &mutable.lock {
+            None => Ok(ObjectStorage::Mutex),
+            Some(lit) => match lit.value().as_str() {
+                "mutex" => Ok(ObjectStorage::Mutex),
+                "rwlock" => Ok(ObjectStorage::RwLock),
+                _ => Err(syn::Error::new_spanned(
+                    lit,
+                    "unknown lock type, expected `mutex` or `rwlock`",
+                )),
+            },
+        }
+    }
+}
+
+impl UniffiAttributeArgs for ObjectAttr {
+    fn parse_one(input: ParseStream<'_>) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::mutable) {
+            let kw = input.parse()?;
+            let lock = if input.peek(Token![=]) {
+                let _: Token![=] = input.parse()?;
+                Some(input.parse()?)
+            } else {
+                None
+            };
+            Ok(Self {
+                mutable: Some(MutableArg { kw, lock }),
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::thread_affine) {
+            Ok(Self {
+                thread_affine: input.parse()?,
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::stable_identity) {
+            Ok(Self {
+                stable_identity: input.parse()?,
+                ..Self::default()
+            })
+        } else {
+            Err(lookahead.error())
+        }
+    }
+
+    fn merge(self, other: Self) -> syn::Result<Self> {
+        Ok(Self {
+            mutable: either_attribute_arg(self.mutable, other.mutable)?,
+            thread_affine: either_attribute_arg(self.thread_affine, other.thread_affine)?,
+            stable_identity: either_attribute_arg(self.stable_identity, other.stable_identity)?,
+        })
+    }
+}
+
+fn object_access_impl(ident: &Ident, storage: ObjectStorage, udl_mode: bool) -> TokenStream {
+    let stored = storage.stored_type(ident);
+    let access_impl_spec = tagged_impl_header("ObjectAccess", ident, udl_mode);
+    let access_mut_impl_spec = tagged_impl_header("ObjectAccessMut", ident, udl_mode);
+    let poisoned = format!(
+        "{} lock poisoned by a panic in a previous call",
+        ident_to_string(ident)
+    );
+    let (ref_type, get_ref, ref_mut_type, get_mut) = match storage {
+        ObjectStorage::Shared => {
+            return quote! {
+                #[doc(hidden)]
+                #[automatically_derived]
+                #access_impl_spec {
+                    type Stored = Self;
+                    type Ref<'a> = &'a Self where Self: 'a;
+
+                    fn get_ref(stored: &Self::Stored) -> Self::Ref<'_> {
+                        stored
+                    }
+
+                    fn check_take(_stored: &Self::Stored) -> ::uniffi::Result<()> {
+                        Ok(())
+                    }
+
+                    fn take(stored: Self::Stored) -> Self {
+                        stored
+                    }
+                }
+            }
+        }
+        ObjectStorage::ThreadBound => {
+            let message = wrong_thread_message(ident);
+            // No `ObjectAccessMut`, since `&mut self` would require a `RefCell`
+            return quote! {
+                #[doc(hidden)]
+                #[automatically_derived]
+                #access_impl_spec {
+                    type Stored = #stored;
+                    type Ref<'a> = &'a Self where Self: 'a;
+
+                    fn get_ref(stored: &Self::Stored) -> Self::Ref<'_> {
+                        stored.get()
+                    }
+
+                    fn check_take(stored: &Self::Stored) -> ::uniffi::Result<()> {
+                        if !stored.is_owner_thread() {
+                            ::uniffi::deps::anyhow::bail!(#message);
+                        }
+                        Ok(())
+                    }
+
+                    fn take(stored: Self::Stored) -> Self {
+                        stored.into_inner()
+                    }
+                }
+            };
+        }
+        ObjectStorage::Mutex => (
+            quote! { ::std::sync::MutexGuard<'a, Self> },
+            quote! { stored.lock().expect(#poisoned) },
+            quote! { ::std::sync::MutexGuard<'a, Self> },
+            quote! { stored.lock().expect(#poisoned) },
+        ),
+        ObjectStorage::RwLock => (
+            quote! { ::std::sync::RwLockReadGuard<'a, Self> },
+            quote! { stored.read().expect(#poisoned) },
+            quote! { ::std::sync::RwLockWriteGuard<'a, Self> },
+            quote! { stored.write().expect(#poisoned) },
+        ),
+    };
+
+    quote! {
+        #[doc(hidden)]
+        #[automatically_derived]
+        #access_impl_spec {
+            type Stored = #stored;
+            type Ref<'a> = #ref_type where Self: 'a;
+
+            fn get_ref(stored: &Self::Stored) -> Self::Ref<'_> {
+                #get_ref
+            }
+
+            fn check_take(_stored: &Self::Stored) -> ::uniffi::Result<()> {
+                Ok(())
+            }
+
+            fn take(stored: Self::Stored) -> Self {
+                stored.into_inner().expect(#poisoned)
+            }
+        }
+
+        #[doc(hidden)]
+        #[automatically_derived]
+        #access_mut_impl_spec {
+            type RefMut<'a> = #ref_mut_type where Self: 'a;
+
+            fn get_mut(stored: &Self::Stored) -> Self::RefMut<'_> {
+                #get_mut
+            }
+        }
+    }
+}
+
+fn wrong_thread_message(ident: &Ident) -> String {
+    format!(
+        "{} object can only be used from the thread that created it",
+        ident_to_string(ident)
+    )
+}
+
+pub(crate) fn interface_impl(ident: &Ident, storage: ObjectStorage, udl_mode: bool) -> TokenStream {
     let name = ident_to_string(ident);
-    let impl_spec = tagged_impl_header("FfiConverterArc", ident, udl_mode);
+    let stored = storage.stored_type(ident);
+    // Mutable and thread-affine objects are passed across the FFI as `Arc<Mutex<T>>`,
+    // `Arc<ThreadBound<T>>`, etc.  The orphan rules only let us implement `FfiConverterArc` for
+    // those types using the local tag.
+    let (impl_spec, lift_ref_impl_spec) = match storage {
+        ObjectStorage::Shared => (
+            tagged_impl_header("FfiConverterArc", ident, udl_mode),
+            tagged_impl_header("LiftRef", ident, udl_mode),
+        ),
+        _ => (
+            quote! { impl ::uniffi::FfiConverterArc<crate::UniFfiTag> for #stored },
+            quote! { impl ::uniffi::LiftRef<crate::UniFfiTag> for #stored },
+        ),
+    };
     let lower_return_impl_spec = tagged_impl_header("LowerReturn", ident, udl_mode);
-    let lift_ref_impl_spec = tagged_impl_header("LiftRef", ident, udl_mode);
+    let wrap_obj = match storage {
+        ObjectStorage::Shared => quote! { obj },
+        _ => quote! { <#stored>::new(obj) },
+    };
+    // Thread-affine objects check the thread whenever they're lifted, which covers all method
+    // calls.  This turns a call from the wrong thread into an error rather than UB.
+    let check_thread = (storage == ObjectStorage::ThreadBound).then(|| {
+        let message = wrong_thread_message(ident);
+        quote! {
+            if !obj.is_owner_thread() {
+                ::uniffi::deps::anyhow::bail!(#message);
+            }
+        }
+    });
     let mod_path = match mod_path() {
         Ok(p) => p,
         Err(e) => return e.into_compile_error(),
@@ -75,7 +336,7 @@ pub(crate) fn interface_impl(ident: &Ident, udl_mode: bool) -> TokenStream {
         // if they are not, but unfortunately it fails with an unactionably obscure error message.
         // By asserting the requirement explicitly, we help Rust produce a more scrutable error message
         // and thus help the user debug why the requirement isn't being met.
-        uniffi::deps::static_assertions::assert_impl_all!(#ident: ::core::marker::Sync, ::core::marker::Send);
+        uniffi::deps::static_assertions::assert_impl_all!(#stored: ::core::marker::Sync, ::core::marker::Send);
 
         #[doc(hidden)]
         #[automatically_derived]
@@ -98,13 +359,15 @@ pub(crate) fn interface_impl(ident: &Ident, udl_mode: bool) -> TokenStream {
             /// call the destructor function specific to the type `T`. Calling the destructor
             /// function for other types may lead to undefined behaviour.
             fn lower(obj: ::std::sync::Arc<Self>) -> Self::FfiType {
-                ::std::sync::Arc::into_raw(obj) as Self::FfiType
+                ::uniffi::object_handle_new(obj, #name)
             }
 
             /// When lifting, we receive an owned `Arc` that the foreign language code cloned.
+            #[allow(clippy::not_unsafe_ptr_arg_deref)]
             fn try_lift(v: Self::FfiType) -> ::uniffi::Result<::std::sync::Arc<Self>> {
-                let v = v as *const #ident;
-                Ok(unsafe { ::std::sync::Arc::<Self>::from_raw(v) })
+                let obj = unsafe { ::uniffi::object_handle_lift::<Self>(v, #name) }?;
+                #check_thread
+                Ok(obj)
             }
 
             /// When writing as a field of a complex structure, make a clone and transfer ownership
@@ -137,13 +400,13 @@ pub(crate) fn interface_impl(ident: &Ident, udl_mode: bool) -> TokenStream {
         }
 
         unsafe #lower_return_impl_spec {
-            type ReturnType = <Self as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::FfiType;
+            type ReturnType = <#stored as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::FfiType;
 
             fn lower_return(obj: Self) -> ::std::result::Result<Self::ReturnType, ::uniffi::RustBuffer> {
-                Ok(<Self as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::lower(::std::sync::Arc::new(obj)))
+                Ok(<#stored as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::lower(::std::sync::Arc::new(#wrap_obj)))
             }
 
-            const TYPE_ID_META: ::uniffi::MetadataBuffer = <Self as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::TYPE_ID_META;
+            const TYPE_ID_META: ::uniffi::MetadataBuffer = <#stored as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::TYPE_ID_META;
         }
 
         unsafe #lift_ref_impl_spec {
@@ -155,6 +418,8 @@ pub(crate) fn interface_impl(ident: &Ident, udl_mode: bool) -> TokenStream {
 pub(crate) fn interface_meta_static_var(
     ident: &Ident,
     imp: ObjectImpl,
+    thread_affine: bool,
+    stable_identity: bool,
     module_path: &str,
     docstring: String,
 ) -> syn::Result<TokenStream> {
@@ -172,6 +437,8 @@ pub(crate) fn interface_meta_static_var(
             ::uniffi::MetadataBuffer::from_code(#code)
                 .concat_str(#module_path)
                 .concat_str(#name)
+                .concat_bool(#thread_affine)
+                .concat_bool(#stable_identity)
                 .concat_long_str(#docstring)
         },
         None,
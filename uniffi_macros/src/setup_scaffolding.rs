@@ -2,11 +2,13 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use proc_macro2::TokenStream;
+use camino::Utf8PathBuf;
+use fs_err as fs;
+use proc_macro2::{Ident, Literal, Span, TokenStream};
 use quote::{format_ident, quote};
 use syn::Result;
 
-use crate::util::mod_path;
+use crate::util::{jni_shim, mod_path};
 use uniffi_meta::UNIFFI_CONTRACT_VERSION;
 
 pub fn setup_scaffolding(namespace: String) -> Result<TokenStream> {
@@ -15,12 +17,70 @@ pub fn setup_scaffolding(namespace: String) -> Result<TokenStream> {
     let namespace_upper = namespace.to_ascii_uppercase();
     let namespace_const_ident = format_ident!("UNIFFI_META_CONST_NAMESPACE_{namespace_upper}");
     let namespace_static_ident = format_ident!("UNIFFI_META_NAMESPACE_{namespace_upper}");
+    let config_static = config_file_static(
+        &format_ident!("UNIFFI_META_CONFIG_{namespace_upper}"),
+        &module_path,
+    )?;
     let ffi_rustbuffer_alloc_ident = format_ident!("ffi_{module_path}_rustbuffer_alloc");
     let ffi_rustbuffer_from_bytes_ident = format_ident!("ffi_{module_path}_rustbuffer_from_bytes");
     let ffi_rustbuffer_free_ident = format_ident!("ffi_{module_path}_rustbuffer_free");
     let ffi_rustbuffer_reserve_ident = format_ident!("ffi_{module_path}_rustbuffer_reserve");
+    let ffi_foreign_function_init_vtable_ident =
+        format_ident!("ffi_{module_path}_foreign_function_init_vtable");
+    let ffi_rust_function_call_ident = format_ident!("ffi_{module_path}_rust_function_call");
+    let ffi_rust_function_free_ident = format_ident!("ffi_{module_path}_rust_function_free");
+    let ffi_log_bridge_init_ident = format_ident!("ffi_{module_path}_log_bridge_init");
+    let ffi_log_bridge_set_level_ident = format_ident!("ffi_{module_path}_log_bridge_set_level");
     let reexport_hack_ident = format_ident!("{module_path}_uniffi_reexport_hack");
     let ffi_rust_future_scaffolding_fns = rust_future_scaffolding_fns(&module_path);
+    let jni_shims = [
+        jni_shim(&ffi_contract_version_ident, quote! {}, Some(quote! { u32 })),
+        jni_shim(
+            &ffi_rustbuffer_alloc_ident,
+            quote! { size: u64, call_status: &mut uniffi::RustCallStatus },
+            Some(quote! { uniffi::RustBuffer }),
+        ),
+        jni_shim(
+            &ffi_rustbuffer_from_bytes_ident,
+            quote! { bytes: uniffi::ForeignBytes, call_status: &mut uniffi::RustCallStatus },
+            Some(quote! { uniffi::RustBuffer }),
+        ),
+        jni_shim(
+            &ffi_rustbuffer_free_ident,
+            quote! { buf: uniffi::RustBuffer, call_status: &mut uniffi::RustCallStatus },
+            None,
+        ),
+        jni_shim(
+            &ffi_rustbuffer_reserve_ident,
+            quote! { buf: uniffi::RustBuffer, additional: u64, call_status: &mut uniffi::RustCallStatus },
+            Some(quote! { uniffi::RustBuffer }),
+        ),
+        jni_shim(
+            &ffi_foreign_function_init_vtable_ident,
+            quote! { vtable: ::std::ptr::NonNull<uniffi::UniffiVTableForeignFunction> },
+            None,
+        ),
+        jni_shim(
+            &ffi_rust_function_call_ident,
+            quote! { handle: u64, args: uniffi::RustBuffer, call_status: &mut uniffi::RustCallStatus },
+            Some(quote! { uniffi::RustBuffer }),
+        ),
+        jni_shim(
+            &ffi_rust_function_free_ident,
+            quote! { handle: u64, call_status: &mut uniffi::RustCallStatus },
+            None,
+        ),
+        jni_shim(
+            &ffi_log_bridge_init_ident,
+            quote! { callback: uniffi::LogBridgeCallback, max_level: i8 },
+            None,
+        ),
+        jni_shim(
+            &ffi_log_bridge_set_level_ident,
+            quote! { max_level: i8 },
+            None,
+        ),
+    ];
 
     Ok(quote! {
         // Unit struct to parameterize the FfiConverter trait.
@@ -53,6 +113,8 @@ pub fn setup_scaffolding(namespace: String) -> Result<TokenStream> {
         #[no_mangle]
         pub static #namespace_static_ident: [u8; #namespace_const_ident.size] = #namespace_const_ident.into_array();
 
+        #config_static
+
         // Everybody gets basic buffer support, since it's needed for passing complex types over the FFI.
         //
         // See `uniffi/src/ffi/rustbuffer.rs` for documentation on these functions
@@ -87,6 +149,60 @@ pub fn setup_scaffolding(namespace: String) -> Result<TokenStream> {
 
         #ffi_rust_future_scaffolding_fns
 
+        // Support for passing closures across the FFI.
+        //
+        // See `uniffi/src/ffi/function.rs` for documentation on these functions
+
+        static UNIFFI_FOREIGN_FUNCTION_VTABLE: uniffi::UniffiForeignPointerCell<uniffi::UniffiVTableForeignFunction> =
+            uniffi::UniffiForeignPointerCell::new();
+
+        impl uniffi::ForeignFunctionVTable for UniFfiTag {
+            fn foreign_function_vtable() -> &'static uniffi::UniffiVTableForeignFunction {
+                UNIFFI_FOREIGN_FUNCTION_VTABLE.get()
+            }
+        }
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub extern "C" fn #ffi_foreign_function_init_vtable_ident(vtable: ::std::ptr::NonNull<uniffi::UniffiVTableForeignFunction>) {
+            UNIFFI_FOREIGN_FUNCTION_VTABLE.set(vtable);
+        }
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe extern "C" fn #ffi_rust_function_call_ident(handle: u64, args: uniffi::RustBuffer, call_status: &mut uniffi::RustCallStatus) -> uniffi::RustBuffer {
+            uniffi::ffi::function::rust_function_call(handle, args, call_status)
+        }
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe extern "C" fn #ffi_rust_function_free_ident(handle: u64, call_status: &mut uniffi::RustCallStatus) {
+            uniffi::ffi::function::rust_function_free(handle, call_status);
+        }
+
+        // Support for forwarding log records to the foreign side.
+        //
+        // See `uniffi/src/ffi/log_bridge.rs` for documentation on these functions
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub extern "C" fn #ffi_log_bridge_init_ident(callback: uniffi::LogBridgeCallback, max_level: i8) {
+            uniffi::ffi::log_bridge::log_bridge_init(callback, max_level);
+        }
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub extern "C" fn #ffi_log_bridge_set_level_ident(max_level: i8) {
+            uniffi::ffi::log_bridge::log_bridge_set_level(max_level);
+        }
+
+        #(#jni_shims)*
+
         // Code to re-export the UniFFI scaffolding functions.
         //
         // Rust won't always re-export the functions from dependencies
@@ -127,6 +243,40 @@ pub fn setup_scaffolding(namespace: String) -> Result<TokenStream> {
     })
 }
 
+// Embed the crate's `uniffi.toml`, so library mode can load it without finding the crate's sources.
+//
+// The static is only defined with uniffi's `embed-sources` feature.  Nothing is embedded if
+// `CARGO_MANIFEST_DIR` isn't set either, in which case library mode falls back to running
+// `cargo metadata`.
+fn config_file_static(ident: &Ident, module_path: &str) -> Result<TokenStream> {
+    let Some(manifest_dir) = std::env::var_os("CARGO_MANIFEST_DIR") else {
+        return Ok(quote! {});
+    };
+    let path = Utf8PathBuf::try_from(std::path::PathBuf::from(manifest_dir))
+        .map_err(|e| syn::Error::new(Span::call_site(), e))?
+        .join("uniffi.toml");
+    let contents = if path.is_file() {
+        Some(fs::read_to_string(&path).map_err(|e| syn::Error::new(Span::call_site(), e))?)
+    } else {
+        None
+    };
+    // Include the file in the crate, so that cargo rebuilds it when the config changes.  There's
+    // no stable way for a proc-macro to depend on a file that doesn't exist, so adding a
+    // `uniffi.toml` later needs a clean build of the crate, which the manual explains.
+    let track_file = contents.is_some().then(|| {
+        let path = path.as_str();
+        quote! { const _: &[u8] = include_bytes!(#path); }
+    });
+    let data = uniffi_meta::encode_config_file(module_path, contents.as_deref());
+    let size = data.len();
+    let data = Literal::byte_string(&data);
+    Ok(quote! {
+        #track_file
+
+        ::uniffi::embed_source_file!(#ident, #size, #data);
+    })
+}
+
 /// Generates the rust_future_* functions
 ///
 /// The foreign side uses a type-erased `Handle` to interact with futures, which presents
@@ -161,6 +311,20 @@ fn rust_future_scaffolding_fns(module_path: &str) -> TokenStream {
         let ffi_rust_future_cancel = format_ident!("ffi_{module_path}_rust_future_cancel_{fn_suffix}");
         let ffi_rust_future_complete = format_ident!("ffi_{module_path}_rust_future_complete_{fn_suffix}");
         let ffi_rust_future_free = format_ident!("ffi_{module_path}_rust_future_free_{fn_suffix}");
+        let jni_shims = [
+            jni_shim(
+                &ffi_rust_future_poll,
+                quote! { handle: ::uniffi::Handle, callback: ::uniffi::RustFutureContinuationCallback, data: u64 },
+                None,
+            ),
+            jni_shim(&ffi_rust_future_cancel, quote! { handle: ::uniffi::Handle }, None),
+            jni_shim(
+                &ffi_rust_future_complete,
+                quote! { handle: ::uniffi::Handle, out_status: &mut ::uniffi::RustCallStatus },
+                Some(return_type.clone()),
+            ),
+            jni_shim(&ffi_rust_future_free, quote! { handle: ::uniffi::Handle }, None),
+        ];
 
         quote! {
             #[allow(clippy::missing_safety_doc, missing_docs)]
@@ -193,6 +357,8 @@ fn rust_future_scaffolding_fns(module_path: &str) -> TokenStream {
             pub unsafe extern "C" fn #ffi_rust_future_free(handle: ::uniffi::Handle) {
                 ::uniffi::ffi::rust_future_free::<#return_type, crate::UniFfiTag>(handle)
             }
+
+            #(#jni_shims)*
         }
     })
     .collect()
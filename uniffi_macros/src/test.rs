@@ -41,6 +41,21 @@ pub(crate) fn build_foreign_language_testcases(tokens: TokenStream) -> TokenStre
                 Some("rb") => quote! {
                     uniffi::ruby_run_test
                 },
+                Some("c") => quote! {
+                    uniffi::c_run_test
+                },
+                Some("cpp") => quote! {
+                    uniffi::cpp_run_test
+                },
+                Some("go") => quote! {
+                    uniffi::go_run_test
+                },
+                Some("cs") => quote! {
+                    uniffi::csharp_run_test
+                },
+                Some("js") => quote! {
+                    uniffi::typescript_run_test
+                },
                 _ => panic!("Unexpected extension for test script: {test_file_name}"),
             };
             let maybe_ignore = if should_skip_path(&test_file_pathbuf) {
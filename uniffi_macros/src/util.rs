@@ -97,6 +97,25 @@ pub fn crate_name() -> String {
     std::env::var("CARGO_CRATE_NAME").unwrap().replace('-', "_")
 }
 
+/// Generate the JNI shim for a scaffolding function
+///
+/// `params` is the scaffolding function's parameter list.  The shim is only exported when the `jni`
+/// feature of `uniffi` is enabled.
+pub fn jni_shim(
+    ffi_ident: &Ident,
+    params: TokenStream,
+    return_ty: Option<TokenStream>,
+) -> TokenStream {
+    let jni_ident = Ident::new(
+        &uniffi_meta::jni_symbol_name(&crate_name(), &ident_to_string(ffi_ident)),
+        Span::call_site(),
+    );
+    let return_ty = return_ty.map(|ty| quote! { -> #ty });
+    quote! {
+        ::uniffi::jni_shim!(#jni_ident => #ffi_ident(#params) #return_ty);
+    }
+}
+
 pub fn create_metadata_items(
     kind: &str,
     name: &str,
@@ -113,12 +132,15 @@ pub fn create_metadata_items(
 
     let checksum_fn = checksum_fn_name.map(|name| {
         let ident = Ident::new(&name, Span::call_site());
+        let jni_shim = jni_shim(&ident, quote! {}, Some(quote! { u16 }));
         quote! {
             #[doc(hidden)]
             #[no_mangle]
             pub extern "C" fn #ident() -> u16 {
                 #const_ident.checksum()
             }
+
+            #jni_shim
         }
     });
 
@@ -249,14 +271,18 @@ pub(crate) fn derive_ffi_traits(
 /// Custom keywords
 pub mod kw {
     syn::custom_keyword!(async_runtime);
+    syn::custom_keyword!(blocking);
     syn::custom_keyword!(callback_interface);
     syn::custom_keyword!(with_foreign);
     syn::custom_keyword!(default);
     syn::custom_keyword!(flat_error);
     syn::custom_keyword!(None);
     syn::custom_keyword!(with_try_read);
+    syn::custom_keyword!(mutable);
     syn::custom_keyword!(name);
     syn::custom_keyword!(non_exhaustive);
+    syn::custom_keyword!(thread_affine);
+    syn::custom_keyword!(stable_identity);
     syn::custom_keyword!(Debug);
     syn::custom_keyword!(Display);
     syn::custom_keyword!(Eq);
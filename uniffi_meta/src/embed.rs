@@ -0,0 +1,45 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Encode the source files that get embedded in the library next to the metadata.
+//!
+//! These can be much larger than a `MetadataBuffer`, so they're encoded when the scaffolding is
+//! generated, rather than by const code.  Strings are encoded as a `u8` length, like in
+//! `MetadataBuffer`, while file contents are encoded as a `u32` length followed by the utf8 data.
+
+use crate::metadata::codes;
+
+/// Encode a [crate::UdlSource] item
+pub fn encode_udl_source(module_path: &str, contents: &str) -> Vec<u8> {
+    let mut buf = vec![codes::UDL_SOURCE];
+    write_str(&mut buf, module_path);
+    write_text(&mut buf, contents);
+    buf
+}
+
+/// Encode a [crate::ConfigFile] item
+pub fn encode_config_file(module_path: &str, contents: Option<&str>) -> Vec<u8> {
+    let mut buf = vec![codes::CONFIG_FILE];
+    write_str(&mut buf, module_path);
+    match contents {
+        Some(contents) => {
+            buf.push(1);
+            write_text(&mut buf, contents);
+        }
+        None => buf.push(0),
+    }
+    buf
+}
+
+fn write_str(buf: &mut Vec<u8>, value: &str) {
+    assert!(value.len() < 256);
+    buf.push(value.len() as u8);
+    buf.extend_from_slice(value.as_bytes());
+}
+
+fn write_text(buf: &mut Vec<u8>, value: &str) {
+    let len = u32::try_from(value.len()).expect("embedded file is too large");
+    buf.extend_from_slice(&len.to_le_bytes());
+    buf.extend_from_slice(value.as_bytes());
+}
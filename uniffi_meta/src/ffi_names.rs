@@ -73,3 +73,33 @@ pub fn method_checksum_symbol_name(namespace: &str, object_name: &str, name: &st
     let name = name.to_ascii_lowercase();
     format!("uniffi_{namespace}_checksum_method_{object_name}_{name}")
 }
+
+/// JNI symbol name for the shim that forwards to the FFI function `ffi_name`
+///
+/// Unlike the functions above, this inputs the crate name rather than the namespace.  The Kotlin
+/// bindings declare the shims as `external fun`s on the `uniffi.jni.{crate_name}.UniffiJniLib`
+/// object, and JNI derives the symbol name from that class.
+pub fn jni_symbol_name(crate_name: &str, ffi_name: &str) -> String {
+    format!(
+        "Java_uniffi_jni_{}_UniffiJniLib_{}",
+        jni_mangle(crate_name),
+        jni_mangle(ffi_name)
+    )
+}
+
+/// Escape a name component following the JNI name mangling rules
+fn jni_mangle(name: &str) -> String {
+    let mut mangled = String::with_capacity(name.len());
+    for c in name.chars() {
+        match c {
+            '_' => mangled.push_str("_1"),
+            c if c.is_ascii_alphanumeric() => mangled.push(c),
+            c => {
+                for unit in c.encode_utf16(&mut [0; 2]) {
+                    mangled.push_str(&format!("_0{unit:04x}"));
+                }
+            }
+        }
+    }
+    mangled
+}
@@ -237,6 +237,10 @@ impl<'a> ExternalTypeConverter<'a> {
                 key_type: Box::new(self.convert_type(*key_type)),
                 value_type: Box::new(self.convert_type(*value_type)),
             },
+            Type::Function { args, ret } => Type::Function {
+                args: args.into_iter().map(|t| self.convert_type(t)).collect(),
+                ret: ret.map(|t| Box::new(self.convert_type(*t))),
+            },
             // Existing External types probably need namespace fixed.
             Type::External {
                 namespace,
@@ -19,11 +19,14 @@ pub use types::{AsType, ExternalKind, ObjectImpl, Type, TypeIterator};
 
 mod metadata;
 
+mod embed;
+pub use embed::{encode_config_file, encode_udl_source};
+
 // This needs to match the minor version of the `uniffi` crate.  See
 // `docs/uniffi-versioning.md` for details.
 //
 // Once we get to 1.0, then we'll need to update the scheme to something like 100 + major_version
-pub const UNIFFI_CONTRACT_VERSION: u32 = 26;
+pub const UNIFFI_CONTRACT_VERSION: u32 = 27;
 
 /// Similar to std::hash::Hash.
 ///
@@ -117,6 +120,7 @@ impl Checksum for &str {
 //
 // This is used to match up the macro metadata with the UDL items.
 #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NamespaceMetadata {
     pub crate_name: String,
     pub name: String,
@@ -126,6 +130,7 @@ pub struct NamespaceMetadata {
 //
 // This is to find the UDL files in library mode generation
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UdlFile {
     // The module path specified when the UDL file was parsed.
     pub module_path: String,
@@ -134,11 +139,35 @@ pub struct UdlFile {
     pub file_stub: String,
 }
 
+// Contents of the UDL file included with `include_scaffolding!()`
+//
+// This lets library mode generation parse the UDL without finding the crate's sources.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UdlSource {
+    pub module_path: String,
+    pub contents: String,
+}
+
+// Contents of the crate's `uniffi.toml`, embedded by `setup_scaffolding!()`
+//
+// This lets library mode generation load the config without finding the crate's sources.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigFile {
+    pub module_path: String,
+    // `None` if the crate doesn't have a `uniffi.toml` file.
+    pub contents: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FnMetadata {
     pub module_path: String,
     pub name: String,
     pub is_async: bool,
+    // Sync function that the bindings should call from a background thread.
+    pub is_blocking: bool,
     pub inputs: Vec<FnParamMetadata>,
     pub return_type: Option<Type>,
     pub throws: Option<Type>,
@@ -157,6 +186,7 @@ impl FnMetadata {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ConstructorMetadata {
     pub module_path: String,
     pub self_name: String,
@@ -182,15 +212,20 @@ impl ConstructorMetadata {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MethodMetadata {
     pub module_path: String,
     pub self_name: String,
     pub name: String,
     pub is_async: bool,
+    // Sync method that the bindings should call from a background thread.
+    pub is_blocking: bool,
     pub inputs: Vec<FnParamMetadata>,
     pub return_type: Option<Type>,
     pub throws: Option<Type>,
     pub takes_self_by_arc: bool, // unused except by rust udl bindgen.
+    // Method consumes `self`, the foreign object can't be used after calling it.
+    pub takes_self_by_value: bool,
     pub checksum: Option<u16>,
     pub docstring: Option<String>,
 }
@@ -206,6 +241,7 @@ impl MethodMetadata {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TraitMethodMetadata {
     pub module_path: String,
     pub trait_name: String,
@@ -233,6 +269,7 @@ impl TraitMethodMetadata {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FnParamMetadata {
     pub name: String,
     pub ty: Type,
@@ -254,6 +291,7 @@ impl FnParamMetadata {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Checksum)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LiteralMetadata {
     Boolean(bool),
     String(String),
@@ -285,6 +323,7 @@ impl LiteralMetadata {
 // Represent the radix of integer literal values.
 // We preserve the radix into the generated bindings for readability reasons.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Checksum)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Radix {
     Decimal = 10,
     Octal = 8,
@@ -292,6 +331,7 @@ pub enum Radix {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RecordMetadata {
     pub module_path: String,
     pub name: String,
@@ -300,6 +340,7 @@ pub struct RecordMetadata {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FieldMetadata {
     pub name: String,
     pub ty: Type,
@@ -308,6 +349,7 @@ pub struct FieldMetadata {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EnumMetadata {
     pub module_path: String,
     pub name: String,
@@ -319,6 +361,7 @@ pub struct EnumMetadata {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VariantMetadata {
     pub name: String,
     pub discr: Option<LiteralMetadata>,
@@ -327,14 +370,20 @@ pub struct VariantMetadata {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ObjectMetadata {
     pub module_path: String,
     pub name: String,
     pub imp: types::ObjectImpl,
+    // Object can only be used from the thread that created it.
+    pub thread_affine: bool,
+    // Lifting the same Rust object twice results in the same foreign object.
+    pub stable_identity: bool,
     pub docstring: Option<String>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CallbackInterfaceMetadata {
     pub module_path: String,
     pub name: String,
@@ -360,6 +409,7 @@ impl ObjectMetadata {
 
 /// The list of traits we support generating helper methods for.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum UniffiTraitMetadata {
     Debug {
         fmt: MethodMetadata,
@@ -420,6 +470,7 @@ impl UniffiTraitDiscriminants {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CustomTypeMetadata {
     pub module_path: String,
     pub name: String,
@@ -438,9 +489,12 @@ pub fn checksum<T: Checksum>(val: &T) -> u16 {
 
 /// Enum covering all the possible metadata types
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Metadata {
     Namespace(NamespaceMetadata),
     UdlFile(UdlFile),
+    UdlSource(UdlSource),
+    ConfigFile(ConfigFile),
     Func(FnMetadata),
     Object(ObjectMetadata),
     CallbackInterface(CallbackInterfaceMetadata),
@@ -462,6 +516,8 @@ impl Metadata {
         match self {
             Metadata::Namespace(meta) => &meta.crate_name,
             Metadata::UdlFile(meta) => &meta.module_path,
+            Metadata::UdlSource(meta) => &meta.module_path,
+            Metadata::ConfigFile(meta) => &meta.module_path,
             Metadata::Func(meta) => &meta.module_path,
             Metadata::Constructor(meta) => &meta.module_path,
             Metadata::Method(meta) => &meta.module_path,
@@ -488,6 +544,18 @@ impl From<UdlFile> for Metadata {
     }
 }
 
+impl From<UdlSource> for Metadata {
+    fn from(value: UdlSource) -> Metadata {
+        Self::UdlSource(value)
+    }
+}
+
+impl From<ConfigFile> for Metadata {
+    fn from(value: ConfigFile) -> Metadata {
+        Self::ConfigFile(value)
+    }
+}
+
 impl From<FnMetadata> for Metadata {
     fn from(value: FnMetadata) -> Metadata {
         Self::Func(value)
@@ -23,6 +23,8 @@ pub mod codes {
     pub const UNIFFI_TRAIT: u8 = 11;
     pub const TRAIT_INTERFACE: u8 = 12;
     pub const CALLBACK_TRAIT_INTERFACE: u8 = 13;
+    pub const UDL_SOURCE: u8 = 14;
+    pub const CONFIG_FILE: u8 = 15;
     //pub const UNKNOWN: u8 = 255;
 
     // Type codes
@@ -52,6 +54,7 @@ pub mod codes {
     pub const TYPE_RESULT: u8 = 23;
     pub const TYPE_TRAIT_INTERFACE: u8 = 24;
     pub const TYPE_CALLBACK_TRAIT_INTERFACE: u8 = 25;
+    pub const TYPE_FUNCTION: u8 = 26;
     pub const TYPE_UNIT: u8 = 255;
 
     // Literal codes
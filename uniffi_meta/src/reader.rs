@@ -48,6 +48,20 @@ impl<'a> MetadataReader<'a> {
                 file_stub: self.read_string()?,
             }
             .into(),
+            codes::UDL_SOURCE => UdlSource {
+                module_path: self.read_string()?,
+                contents: self.read_text()?,
+            }
+            .into(),
+            codes::CONFIG_FILE => ConfigFile {
+                module_path: self.read_string()?,
+                contents: if self.read_bool()? {
+                    Some(self.read_text()?)
+                } else {
+                    None
+                },
+            }
+            .into(),
             codes::FUNC => self.read_func()?.into(),
             codes::CONSTRUCTOR => self.read_constructor()?.into(),
             codes::METHOD => self.read_method()?.into(),
@@ -124,6 +138,17 @@ impl<'a> MetadataReader<'a> {
         String::from_utf8(slice.into()).context("Invalid string data")
     }
 
+    // Read text that may be too large for a long string, see `crate::embed`
+    fn read_text(&mut self) -> Result<String> {
+        let size = self.read_u32()? as usize;
+        if self.buf.len() < size {
+            bail!("Not enough data left in buffer to read text");
+        }
+        let slice;
+        (slice, self.buf) = self.buf.split_at(size);
+        String::from_utf8(slice.into()).context("Invalid text data")
+    }
+
     fn read_optional_long_string(&mut self) -> Result<Option<String>> {
         Ok(Some(self.read_long_string()?).filter(|str| !str.is_empty()))
     }
@@ -194,6 +219,15 @@ impl<'a> MetadataReader<'a> {
                 key_type: Box::new(self.read_type()?),
                 value_type: Box::new(self.read_type()?),
             },
+            codes::TYPE_FUNCTION => {
+                let arg_count = self.read_u8()?;
+                Type::Function {
+                    args: (0..arg_count)
+                        .map(|_| self.read_type())
+                        .collect::<Result<_>>()?,
+                    ret: self.read_optional_type()?.map(Box::new),
+                }
+            }
             codes::TYPE_UNIT => bail!("Unexpected TYPE_UNIT"),
             codes::TYPE_RESULT => bail!("Unexpected TYPE_RESULT"),
             _ => bail!("Unexpected metadata type code: {value:?}"),
@@ -228,6 +262,7 @@ impl<'a> MetadataReader<'a> {
         let module_path = self.read_string()?;
         let name = self.read_string()?;
         let is_async = self.read_bool()?;
+        let is_blocking = self.read_bool()?;
         let inputs = self.read_inputs()?;
         let (return_type, throws) = self.read_return_type()?;
         let docstring = self.read_optional_long_string()?;
@@ -235,6 +270,7 @@ impl<'a> MetadataReader<'a> {
             module_path,
             name,
             is_async,
+            is_blocking,
             inputs,
             return_type,
             throws,
@@ -276,6 +312,8 @@ impl<'a> MetadataReader<'a> {
         let self_name = self.read_string()?;
         let name = self.read_string()?;
         let is_async = self.read_bool()?;
+        let is_blocking = self.read_bool()?;
+        let takes_self_by_value = self.read_bool()?;
         let inputs = self.read_inputs()?;
         let (return_type, throws) = self.read_return_type()?;
         let docstring = self.read_optional_long_string()?;
@@ -284,10 +322,12 @@ impl<'a> MetadataReader<'a> {
             self_name,
             name,
             is_async,
+            is_blocking,
             inputs,
             return_type,
             throws,
             takes_self_by_arc: false, // not emitted by macros
+            takes_self_by_value,
             checksum: self.calc_checksum(),
             docstring,
         })
@@ -338,6 +378,8 @@ impl<'a> MetadataReader<'a> {
             module_path: self.read_string()?,
             name: self.read_string()?,
             imp,
+            thread_affine: self.read_bool()?,
+            stable_identity: self.read_bool()?,
             docstring: self.read_optional_long_string()?,
         })
     }
@@ -20,6 +20,7 @@
 use crate::Checksum;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Checksum, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ObjectImpl {
     // A single Rust type
     Struct,
@@ -52,6 +53,7 @@ impl ObjectImpl {
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Checksum, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ExternalKind {
     Interface,
     Trait,
@@ -63,6 +65,7 @@ pub enum ExternalKind {
 /// At this level we identify user-defined types by name, without knowing any details
 /// of their internal structure apart from what type of thing they are (record, enum, etc).
 #[derive(Debug, Clone, Eq, PartialEq, Checksum, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Type {
     // Primitive types.
     UInt8,
@@ -112,6 +115,11 @@ pub enum Type {
         key_type: Box<Type>,
         value_type: Box<Type>,
     },
+    // `Box<dyn Fn(..) -> R + Send + Sync>` or `Arc<dyn Fn(..) -> R + Send + Sync>`
+    Function {
+        args: Vec<Type>,
+        ret: Option<Box<Type>>,
+    },
     // An FfiConverter we `use` from an external crate
     External {
         module_path: String,
@@ -139,6 +147,9 @@ impl Type {
                 key_type,
                 value_type,
             } => Box::new(key_type.iter_types().chain(value_type.iter_types())),
+            Type::Function { args, ret } => {
+                Box::new(args.iter().chain(ret.as_deref()).flat_map(Type::iter_types))
+            }
             _ => Box::new(std::iter::empty()),
         };
         Box::new(std::iter::once(self).chain(nested_types))
@@ -47,6 +47,7 @@ pub(super) enum Attribute {
     // Modifies `Trait` to enable foreign implementations (callback interfaces)
     WithForeign,
     Async,
+    Blocking,
     NonExhaustive,
 }
 
@@ -88,6 +89,7 @@ impl TryFrom<&weedle::attribute::ExtendedAttribute<'_>> for Attribute {
                 "Trait" => Ok(Attribute::Trait),
                 "WithForeign" => Ok(Attribute::WithForeign),
                 "Async" => Ok(Attribute::Async),
+                "Blocking" => Ok(Attribute::Blocking),
                 "NonExhaustive" => Ok(Attribute::NonExhaustive),
                 _ => anyhow::bail!("ExtendedAttributeNoArgs not supported: {:?}", (attr.0).0),
             },
@@ -263,6 +265,7 @@ impl<T: TryInto<EnumAttributes, Error = anyhow::Error>> TryFrom<Option<T>> for E
 /// This supports:
 ///   * `[Throws=ErrorName]` attribute for functions that can produce an error.
 ///   * `[Async] for async functions
+///   * `[Blocking]` for sync functions that the bindings should call from a background thread
 #[derive(Debug, Clone, Checksum, Default)]
 pub(super) struct FunctionAttributes(Vec<Attribute>);
 
@@ -279,6 +282,12 @@ impl FunctionAttributes {
     pub(super) fn is_async(&self) -> bool {
         self.0.iter().any(|attr| matches!(attr, Attribute::Async))
     }
+
+    pub(super) fn is_blocking(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Blocking))
+    }
 }
 
 impl FromIterator<Attribute> for FunctionAttributes {
@@ -293,9 +302,10 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for FunctionAttribut
         weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
     ) -> Result<Self, Self::Error> {
         let attrs = parse_attributes(weedle_attributes, |attr| match attr {
-            Attribute::Throws(_) | Attribute::Async => Ok(()),
+            Attribute::Throws(_) | Attribute::Async | Attribute::Blocking => Ok(()),
             _ => bail!(format!("{attr:?} not supported for functions")),
         })?;
+        check_async_and_blocking(&attrs)?;
         Ok(Self(attrs))
     }
 }
@@ -472,6 +482,16 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for ConstructorAttri
     }
 }
 
+/// `[Blocking]` marks a sync function, so it can't be combined with `[Async]`.
+fn check_async_and_blocking(attrs: &[Attribute]) -> Result<()> {
+    if attrs.iter().any(|attr| matches!(attr, Attribute::Async))
+        && attrs.iter().any(|attr| matches!(attr, Attribute::Blocking))
+    {
+        bail!("Async and Blocking can't be combined");
+    }
+    Ok(())
+}
+
 /// Represents UDL attributes that might appear on a method.
 ///
 /// This supports the `[Throws=ErrorName]` attribute for methods that can produce
@@ -493,6 +513,12 @@ impl MethodAttributes {
         self.0.iter().any(|attr| matches!(attr, Attribute::Async))
     }
 
+    pub(super) fn is_blocking(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Blocking))
+    }
+
     pub(super) fn get_self_by_arc(&self) -> bool {
         self.0
             .iter()
@@ -512,9 +538,13 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for MethodAttributes
         weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
     ) -> Result<Self, Self::Error> {
         let attrs = parse_attributes(weedle_attributes, |attr| match attr {
-            Attribute::SelfType(_) | Attribute::Throws(_) | Attribute::Async => Ok(()),
+            Attribute::SelfType(_)
+            | Attribute::Throws(_)
+            | Attribute::Async
+            | Attribute::Blocking => Ok(()),
             _ => bail!(format!("{attr:?} not supported for methods")),
         })?;
+        check_async_and_blocking(&attrs)?;
         Ok(Self(attrs))
     }
 }
@@ -751,6 +781,25 @@ mod test {
         let attrs = FunctionAttributes::try_from(&node).unwrap();
         assert!(matches!(attrs.get_throws_err(), Some("Error")));
         assert!(attrs.is_async());
+        assert!(!attrs.is_blocking());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Throws=Error, Blocking]").unwrap();
+        let attrs = FunctionAttributes::try_from(&node).unwrap();
+        assert!(matches!(attrs.get_throws_err(), Some("Error")));
+        assert!(!attrs.is_async());
+        assert!(attrs.is_blocking());
+    }
+
+    #[test]
+    fn test_async_and_blocking_not_supported() {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Async, Blocking]").unwrap();
+        let err = FunctionAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "Async and Blocking can't be combined");
+
+        let err = MethodAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "Async and Blocking can't be combined");
     }
 
     #[test]
@@ -803,6 +852,12 @@ mod test {
         assert!(attrs.get_self_by_arc());
         assert!(attrs.get_throws_err().is_none());
         assert!(!attrs.is_async());
+        assert!(!attrs.is_blocking());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Blocking]").unwrap();
+        let attrs = MethodAttributes::try_from(&node).unwrap();
+        assert!(!attrs.is_async());
+        assert!(attrs.is_blocking());
     }
 
     #[test]
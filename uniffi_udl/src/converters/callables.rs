@@ -92,6 +92,7 @@ impl APIConverter<FnMetadata> for weedle::namespace::OperationNamespaceMember<'_
         };
         let attrs = FunctionAttributes::try_from(self.attributes.as_ref())?;
         let is_async = attrs.is_async();
+        let is_blocking = attrs.is_blocking();
         let throws = match attrs.get_throws_err() {
             None => None,
             Some(name) => match ci.get_type(name) {
@@ -103,6 +104,7 @@ impl APIConverter<FnMetadata> for weedle::namespace::OperationNamespaceMember<'_
             module_path: ci.module_path(),
             name,
             is_async,
+            is_blocking,
             return_type,
             inputs: self.args.body.list.convert(ci)?,
             throws,
@@ -146,6 +148,7 @@ impl APIConverter<MethodMetadata> for weedle::interface::OperationInterfaceMembe
         let return_type = ci.resolve_return_type_expression(&self.return_type)?;
         let attributes = MethodAttributes::try_from(self.attributes.as_ref())?;
         let is_async = attributes.is_async();
+        let is_blocking = attributes.is_blocking();
 
         let throws = match attributes.get_throws_err() {
             Some(name) => match ci.get_type(name) {
@@ -171,10 +174,12 @@ impl APIConverter<MethodMetadata> for weedle::interface::OperationInterfaceMembe
             // We don't know the name of the containing `Object` at this point, fill it in later.
             self_name: Default::default(),
             is_async,
+            is_blocking,
             inputs: self.args.body.list.convert(ci)?,
             return_type,
             throws,
             takes_self_by_arc,
+            takes_self_by_value: false,
             checksum: None,
             docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
         })
@@ -192,6 +197,9 @@ impl APIConverter<TraitMethodMetadata> for weedle::interface::OperationInterface
         let return_type = ci.resolve_return_type_expression(&self.return_type)?;
         let attributes = MethodAttributes::try_from(self.attributes.as_ref())?;
         let is_async = attributes.is_async();
+        if attributes.is_blocking() {
+            bail!("Blocking is not supported for trait methods");
+        }
 
         let throws = match attributes.get_throws_err() {
             Some(name) => match ci.get_type(name) {
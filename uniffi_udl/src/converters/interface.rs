@@ -65,10 +65,12 @@ impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
                 name: name.to_string(),
                 self_name: object_name.to_string(),
                 is_async: false,
+                is_blocking: false,
                 inputs,
                 return_type,
                 throws: None,
                 takes_self_by_arc: false,
+                takes_self_by_value: false,
                 checksum: None,
                 docstring: None,
             })
@@ -131,6 +133,8 @@ impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
             module_path: ci.module_path(),
             name: object_name.to_string(),
             imp: object_impl,
+            thread_affine: false,
+            stable_identity: false,
             docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
         })
     }